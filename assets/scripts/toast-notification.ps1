@@ -6,7 +6,13 @@ param(
     [string]$Message,
     
     [Parameter(Mandatory=$false)]
-    [string]$AppName = "Vibe Kanban"
+    [string]$AppName = "Vibe Kanban",
+
+    [Parameter(Mandatory=$false)]
+    [string]$Url,
+
+    [Parameter(Mandatory=$false)]
+    [string]$Group
 )
 
 [Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] | Out-Null
@@ -14,10 +20,16 @@ $Template = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateCont
 $RawXml = [xml] $Template.GetXml()
 ($RawXml.toast.visual.binding.text|where {$_.id -eq "1"}).AppendChild($RawXml.CreateTextNode($Title)) | Out-Null
 ($RawXml.toast.visual.binding.text|where {$_.id -eq "2"}).AppendChild($RawXml.CreateTextNode($Message)) | Out-Null
+if ($Url) {
+    # Protocol activation opens the URL in the default browser when the toast is clicked
+    $RawXml.toast.SetAttribute("activationType", "protocol")
+    $RawXml.toast.SetAttribute("launch", $Url)
+}
 $SerializedXml = New-Object Windows.Data.Xml.Dom.XmlDocument
 $SerializedXml.LoadXml($RawXml.OuterXml)
 $Toast = [Windows.UI.Notifications.ToastNotification]::new($SerializedXml)
 $Toast.Tag = $AppName
-$Toast.Group = $AppName
+# Toasts with the same tag and group replace each other, so one entry is kept per project
+$Toast.Group = if ($Group) { $Group } else { $AppName }
 $Notifier = [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier($AppName)
 $Notifier.Show($Toast)
\ No newline at end of file
@@ -0,0 +1,19 @@
+-- History of every dispatched notification, one row per channel
+CREATE TABLE notifications (
+    id          BLOB PRIMARY KEY,
+    channel     TEXT NOT NULL,
+    event       TEXT NOT NULL,
+    project_id  BLOB,
+    task_id     BLOB,
+    title       TEXT NOT NULL,
+    message     TEXT NOT NULL,
+    task_url    TEXT,
+    status      TEXT NOT NULL DEFAULT 'sent'
+                   CHECK (status IN ('sent','failed','suppressed')),
+    error       TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE SET NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_notifications_created_at ON notifications(created_at);
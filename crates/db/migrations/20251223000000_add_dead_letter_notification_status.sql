@@ -0,0 +1,49 @@
+-- Allow 'deadletter' for notifications that kept failing after every retry.
+-- SQLite cannot alter CHECK constraints, so the table is rebuilt:
+-- https://www.sqlite.org/lang_altertable.html#otheralter
+PRAGMA foreign_keys = OFF;
+
+-- This is a sqlx workaround to enable BEGIN TRANSACTION in this migration, until `-- no-transaction` lands in sqlx-sqlite.
+-- https://github.com/launchbadge/sqlx/issues/2085#issuecomment-1499859906
+COMMIT TRANSACTION;
+
+BEGIN TRANSACTION;
+
+CREATE TABLE notifications_new (
+    id          BLOB PRIMARY KEY,
+    channel     TEXT NOT NULL,
+    event       TEXT NOT NULL,
+    project_id  BLOB,
+    task_id     BLOB,
+    title       TEXT NOT NULL,
+    message     TEXT NOT NULL,
+    task_url    TEXT,
+    status      TEXT NOT NULL DEFAULT 'sent'
+                   CHECK (status IN ('sent','failed','suppressed','deadletter')),
+    error       TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE SET NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE SET NULL
+);
+
+INSERT INTO notifications_new (
+    id, channel, event, project_id, task_id, title, message, task_url, status, error, created_at
+)
+SELECT
+    id, channel, event, project_id, task_id, title, message, task_url, status, error, created_at
+FROM notifications;
+
+DROP TABLE notifications;
+
+ALTER TABLE notifications_new RENAME TO notifications;
+
+CREATE INDEX idx_notifications_created_at ON notifications(created_at);
+
+PRAGMA foreign_key_check;
+
+COMMIT;
+
+PRAGMA foreign_keys = ON;
+
+-- sqlx workaround due to lack of `-- no-transaction` in sqlx-sqlite.
+BEGIN TRANSACTION;
@@ -0,0 +1,9 @@
+-- Slack message that later notifications for the same workspace reply to
+CREATE TABLE slack_threads (
+    workspace_id  BLOB NOT NULL,
+    channel       TEXT NOT NULL,
+    thread_ts     TEXT NOT NULL,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (workspace_id, channel),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
@@ -0,0 +1,9 @@
+-- Browser Web Push subscriptions, one per endpoint
+CREATE TABLE push_subscriptions (
+    id          BLOB PRIMARY KEY,
+    endpoint    TEXT NOT NULL UNIQUE,
+    p256dh      TEXT NOT NULL,
+    auth        TEXT NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
@@ -0,0 +1,22 @@
+-- Failure notifications waiting to be acknowledged before they escalate to other channels
+CREATE TABLE notification_escalations (
+    id               BLOB PRIMARY KEY,
+    event            TEXT NOT NULL,
+    project_id       BLOB,
+    task_id          BLOB,
+    workspace_id     BLOB,
+    title            TEXT NOT NULL,
+    message          TEXT NOT NULL,
+    task_url         TEXT,
+    escalate_at      TEXT NOT NULL,
+    acknowledged_at  TEXT,
+    escalated_at     TEXT,
+    created_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_notification_escalations_open
+    ON notification_escalations(escalate_at)
+    WHERE acknowledged_at IS NULL AND escalated_at IS NULL;
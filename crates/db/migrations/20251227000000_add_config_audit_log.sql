@@ -0,0 +1,11 @@
+-- Every change to a config key, with values as JSON and credentials redacted
+CREATE TABLE config_audit_log (
+    id          BLOB PRIMARY KEY,
+    source      TEXT NOT NULL,
+    path        TEXT NOT NULL,
+    old_value   TEXT,
+    new_value   TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE INDEX idx_config_audit_log_created_at ON config_audit_log(created_at);
@@ -0,0 +1,14 @@
+-- A task is blocked by another until that task is done or cancelled.
+-- resolved_at is set once the dependent has been handled for the blocker's completion.
+CREATE TABLE task_dependencies (
+    task_id     BLOB NOT NULL,
+    blocker_id  BLOB NOT NULL,
+    resolved_at TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_id, blocker_id),
+    CHECK (task_id != blocker_id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (blocker_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_dependencies_blocker_id ON task_dependencies(blocker_id);
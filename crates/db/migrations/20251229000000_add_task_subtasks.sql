@@ -0,0 +1,13 @@
+-- A task can be split into ordered subtasks; the parent's status rolls up from them.
+-- Deleting a parent only drops the links, so its subtasks become top-level tasks.
+CREATE TABLE task_subtasks (
+    task_id        BLOB PRIMARY KEY,
+    parent_task_id BLOB NOT NULL,
+    position       INTEGER NOT NULL DEFAULT 0,
+    created_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    CHECK (task_id != parent_task_id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (parent_task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_subtasks_parent_task_id ON task_subtasks(parent_task_id, position);
@@ -0,0 +1,22 @@
+-- Recurring schedules that create a task (and optionally start an attempt) each time
+-- their cron expression or RRULE fires. executor_profile_id and repos are JSON.
+CREATE TABLE task_schedules (
+    id                  BLOB PRIMARY KEY,
+    project_id          BLOB NOT NULL,
+    title               TEXT NOT NULL,
+    description         TEXT,
+    rule                TEXT NOT NULL,
+    utc_offset          TEXT,
+    executor_profile_id TEXT,
+    repos               TEXT NOT NULL DEFAULT '[]',
+    enabled             BOOLEAN NOT NULL DEFAULT 1,
+    next_run_at         TEXT,
+    last_run_at         TEXT,
+    last_task_id        BLOB,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (last_task_id) REFERENCES tasks(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_task_schedules_next_run_at ON task_schedules(enabled, next_run_at);
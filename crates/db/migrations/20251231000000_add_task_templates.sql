@@ -0,0 +1,17 @@
+-- Reusable task templates. A NULL project_id makes the template available in every project.
+-- labels and executor_profile_id are JSON.
+CREATE TABLE task_templates (
+    id                  BLOB PRIMARY KEY,
+    project_id          BLOB,
+    name                TEXT NOT NULL,
+    title_pattern       TEXT NOT NULL,
+    description         TEXT,
+    labels              TEXT NOT NULL DEFAULT '[]',
+    executor_profile_id TEXT,
+    base_branch         TEXT,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_templates_project_id ON task_templates(project_id);
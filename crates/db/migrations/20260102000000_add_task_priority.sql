@@ -0,0 +1,5 @@
+-- Task priority, used to order task lists and the attempts waiting to start.
+ALTER TABLE tasks ADD COLUMN priority TEXT NOT NULL DEFAULT 'normal'
+    CHECK (priority IN ('urgent', 'high', 'normal', 'low'));
+
+CREATE INDEX idx_tasks_project_id_priority ON tasks(project_id, priority);
@@ -0,0 +1,6 @@
+-- Optional task due dates. overdue_notified_at records when the overdue notification went out,
+-- and is cleared whenever the due date changes so the new one can fire again.
+ALTER TABLE tasks ADD COLUMN due_at TEXT;
+ALTER TABLE tasks ADD COLUMN overdue_notified_at TEXT;
+
+CREATE INDEX idx_tasks_due_at ON tasks(due_at) WHERE due_at IS NOT NULL;
@@ -0,0 +1,60 @@
+-- Full-text search over task titles/descriptions and execution logs, kept in sync by triggers.
+CREATE VIRTUAL TABLE task_search USING fts5(
+    task_id UNINDEXED,
+    title,
+    description,
+    tokenize = 'porter unicode61'
+);
+
+-- One row per persisted stdout/stderr line; rowid matches execution_process_logs.rowid so rows
+-- can be dropped when their log line is (logs are only ever deleted by cascade).
+CREATE VIRTUAL TABLE log_search USING fts5(
+    execution_id UNINDEXED,
+    body,
+    tokenize = 'porter unicode61'
+);
+
+CREATE TRIGGER trg_task_search_insert AFTER INSERT ON tasks
+BEGIN
+    INSERT INTO task_search (task_id, title, description)
+    VALUES (NEW.id, NEW.title, COALESCE(NEW.description, ''));
+END;
+
+CREATE TRIGGER trg_task_search_update AFTER UPDATE OF title, description ON tasks
+BEGIN
+    DELETE FROM task_search WHERE task_id = OLD.id;
+    INSERT INTO task_search (task_id, title, description)
+    VALUES (NEW.id, NEW.title, COALESCE(NEW.description, ''));
+END;
+
+CREATE TRIGGER trg_task_search_delete AFTER DELETE ON tasks
+BEGIN
+    DELETE FROM task_search WHERE task_id = OLD.id;
+END;
+
+CREATE TRIGGER trg_log_search_insert AFTER INSERT ON execution_process_logs
+WHEN json_valid(NEW.logs)
+    AND TRIM(COALESCE(json_extract(NEW.logs, '$.Stdout'), json_extract(NEW.logs, '$.Stderr'), '')) <> ''
+BEGIN
+    INSERT INTO log_search (rowid, execution_id, body)
+    VALUES (
+        NEW.rowid,
+        NEW.execution_id,
+        COALESCE(json_extract(NEW.logs, '$.Stdout'), json_extract(NEW.logs, '$.Stderr'))
+    );
+END;
+
+CREATE TRIGGER trg_log_search_delete AFTER DELETE ON execution_process_logs
+BEGIN
+    DELETE FROM log_search WHERE rowid = OLD.rowid;
+END;
+
+-- Backfill
+INSERT INTO task_search (task_id, title, description)
+SELECT id, title, COALESCE(description, '') FROM tasks;
+
+INSERT INTO log_search (rowid, execution_id, body)
+SELECT rowid, execution_id, COALESCE(json_extract(logs, '$.Stdout'), json_extract(logs, '$.Stderr'))
+FROM execution_process_logs
+WHERE json_valid(logs)
+    AND TRIM(COALESCE(json_extract(logs, '$.Stdout'), json_extract(logs, '$.Stderr'), '')) <> '';
@@ -0,0 +1,46 @@
+-- Discussion thread on tasks. Editing a comment keeps its previous body in task_comment_edits.
+CREATE TABLE task_comments (
+    id          BLOB PRIMARY KEY,
+    task_id     BLOB NOT NULL,
+    author      TEXT NOT NULL,
+    body        TEXT NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_comments_task_id ON task_comments(task_id, created_at);
+
+CREATE TABLE task_comment_edits (
+    id          BLOB PRIMARY KEY,
+    comment_id  BLOB NOT NULL,
+    body        TEXT NOT NULL,      -- body before the edit
+    edited_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (comment_id) REFERENCES task_comments(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_comment_edits_comment_id ON task_comment_edits(comment_id, edited_at);
+
+-- Comments are searchable alongside tasks and logs
+CREATE VIRTUAL TABLE comment_search USING fts5(
+    comment_id UNINDEXED,
+    task_id UNINDEXED,
+    body,
+    tokenize = 'porter unicode61'
+);
+
+CREATE TRIGGER trg_comment_search_insert AFTER INSERT ON task_comments
+BEGIN
+    INSERT INTO comment_search (comment_id, task_id, body) VALUES (NEW.id, NEW.task_id, NEW.body);
+END;
+
+CREATE TRIGGER trg_comment_search_update AFTER UPDATE OF body ON task_comments
+BEGIN
+    DELETE FROM comment_search WHERE comment_id = OLD.id;
+    INSERT INTO comment_search (comment_id, task_id, body) VALUES (NEW.id, NEW.task_id, NEW.body);
+END;
+
+CREATE TRIGGER trg_comment_search_delete AFTER DELETE ON task_comments
+BEGIN
+    DELETE FROM comment_search WHERE comment_id = OLD.id;
+END;
@@ -0,0 +1,16 @@
+-- Files attached to tasks. storage_key is the file name under the app data dir for local
+-- attachments, or the object key for ones stored in an S3-compatible bucket.
+CREATE TABLE task_attachments (
+    id             BLOB PRIMARY KEY,
+    task_id        BLOB NOT NULL,
+    original_name  TEXT NOT NULL,
+    mime_type      TEXT,
+    size_bytes     INTEGER NOT NULL,
+    storage        TEXT NOT NULL DEFAULT 'local'
+                      CHECK (storage IN ('local', 's3')),
+    storage_key    TEXT NOT NULL,
+    created_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_attachments_task_id ON task_attachments(task_id);
@@ -0,0 +1,123 @@
+-- Audit history for tasks: status transitions, field edits, coding agent runs and merges / PR
+-- events. Rows are written by triggers so every code path that touches a task is covered.
+CREATE TABLE task_activity (
+    id                    BLOB PRIMARY KEY,
+    task_id               BLOB NOT NULL,
+    kind                  TEXT NOT NULL
+                             CHECK (kind IN ('created', 'status_changed', 'field_changed',
+                                             'attempt_started', 'attempt_stopped',
+                                             'pr_opened', 'pr_status_changed', 'merged')),
+    field                 TEXT,
+    old_value             TEXT,
+    new_value             TEXT,
+    workspace_id          BLOB,
+    execution_process_id  BLOB,
+    created_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_activity_task_id_created_at ON task_activity(task_id, created_at);
+
+CREATE TRIGGER trg_task_activity_created AFTER INSERT ON tasks
+BEGIN
+    INSERT INTO task_activity (id, task_id, kind, new_value)
+    VALUES (randomblob(16), NEW.id, 'created', NEW.title);
+END;
+
+CREATE TRIGGER trg_task_activity_status AFTER UPDATE OF status ON tasks
+WHEN OLD.status IS NOT NEW.status
+BEGIN
+    INSERT INTO task_activity (id, task_id, kind, field, old_value, new_value)
+    VALUES (randomblob(16), NEW.id, 'status_changed', 'status', OLD.status, NEW.status);
+END;
+
+CREATE TRIGGER trg_task_activity_fields AFTER UPDATE OF title, description, priority, due_at ON tasks
+BEGIN
+    INSERT INTO task_activity (id, task_id, kind, field, old_value, new_value)
+    SELECT randomblob(16), NEW.id, 'field_changed', 'title', OLD.title, NEW.title
+    WHERE OLD.title IS NOT NEW.title;
+    INSERT INTO task_activity (id, task_id, kind, field, old_value, new_value)
+    SELECT randomblob(16), NEW.id, 'field_changed', 'description', OLD.description, NEW.description
+    WHERE OLD.description IS NOT NEW.description;
+    INSERT INTO task_activity (id, task_id, kind, field, old_value, new_value)
+    SELECT randomblob(16), NEW.id, 'field_changed', 'priority', OLD.priority, NEW.priority
+    WHERE OLD.priority IS NOT NEW.priority;
+    INSERT INTO task_activity (id, task_id, kind, field, old_value, new_value)
+    SELECT randomblob(16), NEW.id, 'field_changed', 'due_at', OLD.due_at, NEW.due_at
+    WHERE OLD.due_at IS NOT NEW.due_at;
+END;
+
+-- Attempt start/stop is the coding agent run; new_value is the executor when it starts and the
+-- final process status when it stops
+CREATE TRIGGER trg_task_activity_attempt_started AFTER INSERT ON execution_processes
+WHEN NEW.run_reason = 'codingagent'
+BEGIN
+    INSERT INTO task_activity (id, task_id, kind, new_value, workspace_id, execution_process_id)
+    SELECT randomblob(16), w.task_id, 'attempt_started', s.executor, w.id, NEW.id
+    FROM sessions s
+    JOIN workspaces w ON w.id = s.workspace_id
+    WHERE s.id = NEW.session_id;
+END;
+
+CREATE TRIGGER trg_task_activity_attempt_stopped AFTER UPDATE OF status ON execution_processes
+WHEN NEW.run_reason = 'codingagent' AND OLD.status = 'running' AND NEW.status != 'running'
+BEGIN
+    INSERT INTO task_activity (id, task_id, kind, field, old_value, new_value, workspace_id, execution_process_id)
+    SELECT randomblob(16), w.task_id, 'attempt_stopped', 'status', OLD.status, NEW.status, w.id, NEW.id
+    FROM sessions s
+    JOIN workspaces w ON w.id = s.workspace_id
+    WHERE s.id = NEW.session_id;
+END;
+
+-- new_value is the PR URL for PRs and the merge commit for direct merges
+CREATE TRIGGER trg_task_activity_merge AFTER INSERT ON merges
+BEGIN
+    INSERT INTO task_activity (id, task_id, kind, new_value, workspace_id)
+    SELECT randomblob(16), w.task_id,
+           CASE NEW.merge_type WHEN 'pr' THEN 'pr_opened' ELSE 'merged' END,
+           COALESCE(NEW.pr_url, NEW.merge_commit), w.id
+    FROM workspaces w
+    WHERE w.id = NEW.workspace_id;
+END;
+
+CREATE TRIGGER trg_task_activity_pr_status AFTER UPDATE OF pr_status ON merges
+WHEN OLD.pr_status IS NOT NEW.pr_status
+BEGIN
+    INSERT INTO task_activity (id, task_id, kind, field, old_value, new_value, workspace_id)
+    SELECT randomblob(16), w.task_id, 'pr_status_changed', 'pr_status', OLD.pr_status, NEW.pr_status, w.id
+    FROM workspaces w
+    WHERE w.id = NEW.workspace_id;
+END;
+
+-- Backfill what can be recovered from existing rows; earlier status changes and edits are lost
+INSERT INTO task_activity (id, task_id, kind, new_value, created_at)
+SELECT randomblob(16), id, 'created', title, created_at FROM tasks;
+
+INSERT INTO task_activity (id, task_id, kind, new_value, workspace_id, execution_process_id, created_at)
+SELECT randomblob(16), w.task_id, 'attempt_started', s.executor, w.id, ep.id, ep.started_at
+FROM execution_processes ep
+JOIN sessions s ON s.id = ep.session_id
+JOIN workspaces w ON w.id = s.workspace_id
+WHERE ep.run_reason = 'codingagent';
+
+INSERT INTO task_activity (id, task_id, kind, field, old_value, new_value, workspace_id, execution_process_id, created_at)
+SELECT randomblob(16), w.task_id, 'attempt_stopped', 'status', 'running', ep.status, w.id, ep.id,
+       COALESCE(ep.completed_at, ep.updated_at)
+FROM execution_processes ep
+JOIN sessions s ON s.id = ep.session_id
+JOIN workspaces w ON w.id = s.workspace_id
+WHERE ep.run_reason = 'codingagent' AND ep.status != 'running';
+
+INSERT INTO task_activity (id, task_id, kind, new_value, workspace_id, created_at)
+SELECT randomblob(16), w.task_id,
+       CASE m.merge_type WHEN 'pr' THEN 'pr_opened' ELSE 'merged' END,
+       COALESCE(m.pr_url, m.merge_commit), w.id, m.created_at
+FROM merges m
+JOIN workspaces w ON w.id = m.workspace_id;
+
+INSERT INTO task_activity (id, task_id, kind, field, old_value, new_value, workspace_id, created_at)
+SELECT randomblob(16), w.task_id, 'pr_status_changed', 'pr_status', 'open', m.pr_status, w.id,
+       COALESCE(m.pr_merged_at, m.created_at)
+FROM merges m
+JOIN workspaces w ON w.id = m.workspace_id
+WHERE m.merge_type = 'pr' AND m.pr_status != 'open';
@@ -0,0 +1,90 @@
+-- Custom board columns per project. Each column maps to one of the built-in task statuses, which
+-- the agent workflow keeps using: moving a task to a column sets its status, and a status change
+-- made elsewhere moves the task to the first column for that status.
+CREATE TABLE project_columns (
+    id              BLOB PRIMARY KEY,
+    project_id      BLOB NOT NULL,
+    name            TEXT NOT NULL,
+    position        INTEGER NOT NULL,
+    status          TEXT NOT NULL
+                       CHECK (status IN ('todo', 'inprogress', 'inreview', 'done', 'cancelled')),
+    wip_limit       INTEGER CHECK (wip_limit IS NULL OR wip_limit > 0),
+    counts_as_done  INTEGER NOT NULL DEFAULT 0
+                       CHECK (counts_as_done = 0 OR status IN ('done', 'cancelled')),
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_project_columns_project_id_position ON project_columns(project_id, position);
+
+ALTER TABLE tasks ADD COLUMN column_id BLOB REFERENCES project_columns(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_tasks_column_id ON tasks(column_id);
+
+-- Every project starts with a column per built-in status
+INSERT INTO project_columns (id, project_id, name, position, status, counts_as_done)
+SELECT randomblob(16), p.id, d.name, d.position, d.status, d.counts_as_done
+FROM projects p
+CROSS JOIN (
+    SELECT 'To Do' AS name, 0 AS position, 'todo' AS status, 0 AS counts_as_done
+    UNION ALL SELECT 'In Progress', 1, 'inprogress', 0
+    UNION ALL SELECT 'In Review', 2, 'inreview', 0
+    UNION ALL SELECT 'Done', 3, 'done', 1
+    UNION ALL SELECT 'Cancelled', 4, 'cancelled', 1
+) d;
+
+CREATE TRIGGER trg_project_columns_defaults AFTER INSERT ON projects
+BEGIN
+    INSERT INTO project_columns (id, project_id, name, position, status, counts_as_done)
+    VALUES (randomblob(16), NEW.id, 'To Do', 0, 'todo', 0),
+           (randomblob(16), NEW.id, 'In Progress', 1, 'inprogress', 0),
+           (randomblob(16), NEW.id, 'In Review', 2, 'inreview', 0),
+           (randomblob(16), NEW.id, 'Done', 3, 'done', 1),
+           (randomblob(16), NEW.id, 'Cancelled', 4, 'cancelled', 1);
+END;
+
+UPDATE tasks
+SET column_id = (
+    SELECT c.id FROM project_columns c
+    WHERE c.project_id = tasks.project_id AND c.status = tasks.status
+    ORDER BY c.position
+    LIMIT 1
+);
+
+CREATE TRIGGER trg_tasks_column_on_insert AFTER INSERT ON tasks
+WHEN NEW.column_id IS NULL
+BEGIN
+    UPDATE tasks
+    SET column_id = (
+        SELECT c.id FROM project_columns c
+        WHERE c.project_id = NEW.project_id AND c.status = NEW.status
+        ORDER BY c.position
+        LIMIT 1
+    )
+    WHERE id = NEW.id;
+END;
+
+-- Keep the column in step with status changes that didn't go through a column move
+CREATE TRIGGER trg_tasks_column_on_status AFTER UPDATE OF status ON tasks
+WHEN NEW.column_id IS NULL
+  OR NEW.status IS NOT (SELECT status FROM project_columns WHERE id = NEW.column_id)
+BEGIN
+    UPDATE tasks
+    SET column_id = (
+        SELECT c.id FROM project_columns c
+        WHERE c.project_id = NEW.project_id AND c.status = NEW.status
+        ORDER BY c.position
+        LIMIT 1
+    )
+    WHERE id = NEW.id;
+END;
+
+CREATE TRIGGER trg_task_activity_column AFTER UPDATE OF column_id ON tasks
+WHEN OLD.column_id IS NOT NULL AND OLD.column_id IS NOT NEW.column_id
+BEGIN
+    INSERT INTO task_activity (id, task_id, kind, field, old_value, new_value)
+    VALUES (randomblob(16), NEW.id, 'field_changed', 'column',
+            (SELECT name FROM project_columns WHERE id = OLD.column_id),
+            (SELECT name FROM project_columns WHERE id = NEW.column_id));
+END;
@@ -0,0 +1,13 @@
+-- Archived tasks keep their attempts and history but drop off the board and default listings.
+-- They stay searchable and can be restored by clearing archived_at.
+ALTER TABLE tasks ADD COLUMN archived_at TEXT;
+
+CREATE INDEX idx_tasks_project_id_archived_at ON tasks(project_id, archived_at);
+
+CREATE TRIGGER trg_task_activity_archived AFTER UPDATE OF archived_at ON tasks
+WHEN OLD.archived_at IS NOT NEW.archived_at
+BEGIN
+    INSERT INTO task_activity (id, task_id, kind, field, old_value, new_value)
+    VALUES (randomblob(16), NEW.id, 'field_changed', 'archived_at',
+            OLD.archived_at, NEW.archived_at);
+END;
@@ -0,0 +1,26 @@
+-- Milestones group a project's tasks into sprints or releases with a date range
+CREATE TABLE milestones (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    name        TEXT NOT NULL,
+    description TEXT,
+    start_date  TEXT NOT NULL,
+    end_date    TEXT NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    CHECK (end_date >= start_date),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_milestones_project_id_start_date ON milestones(project_id, start_date);
+
+-- A task belongs to at most one milestone
+CREATE TABLE milestone_tasks (
+    task_id       BLOB PRIMARY KEY,
+    milestone_id  BLOB NOT NULL,
+    added_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (milestone_id) REFERENCES milestones(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_milestone_tasks_milestone_id ON milestone_tasks(milestone_id);
@@ -0,0 +1,21 @@
+-- Epics group related tasks, which may come from different projects
+CREATE TABLE epics (
+    id           BLOB PRIMARY KEY,
+    name         TEXT NOT NULL,
+    description  TEXT,
+    -- Set once every task in the epic is done or cancelled, cleared when one reopens
+    completed_at TEXT,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE TABLE epic_tasks (
+    epic_id   BLOB NOT NULL,
+    task_id   BLOB NOT NULL,
+    added_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (epic_id, task_id),
+    FOREIGN KEY (epic_id) REFERENCES epics(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_epic_tasks_task_id ON epic_tasks(task_id);
@@ -0,0 +1,14 @@
+-- Structured checklist inside a task, ticked off by people or from a coding agent's final message
+CREATE TABLE task_checklist_items (
+    id            BLOB PRIMARY KEY,
+    task_id       BLOB NOT NULL,
+    text          TEXT NOT NULL,
+    done          BOOLEAN NOT NULL DEFAULT 0,
+    position      INTEGER NOT NULL,
+    completed_at  TEXT,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_checklist_items_task_id ON task_checklist_items(task_id, position);
@@ -0,0 +1,59 @@
+-- Per-project task numbers, so descriptions can refer to other tasks as `#12`. Existing tasks are
+-- numbered in creation order; new ones get the next number when inserted.
+ALTER TABLE tasks ADD COLUMN number INTEGER NOT NULL DEFAULT 0;
+
+UPDATE tasks
+SET number = (
+    SELECT COUNT(*) FROM tasks t
+    WHERE t.project_id = tasks.project_id
+      AND (t.created_at < tasks.created_at
+           OR (t.created_at = tasks.created_at AND t.rowid <= tasks.rowid))
+);
+
+CREATE INDEX idx_tasks_project_id_number ON tasks(project_id, number);
+
+-- Resolved references found in a task's description: other tasks (`#12`), files in the project's
+-- repos and commit SHAs. Rows pointing at a task double as that task's backlinks.
+CREATE TABLE task_references (
+    id              BLOB PRIMARY KEY,
+    task_id         BLOB NOT NULL,
+    kind            TEXT NOT NULL CHECK (kind IN ('task', 'file', 'commit')),
+    -- As written in the description, e.g. `#12`, `src/main.rs:40` or `3f2a9c1`
+    reference       TEXT NOT NULL,
+    target_task_id  BLOB,
+    repo_id         BLOB,
+    -- Full commit SHA or repo-relative file path
+    resolved        TEXT,
+    -- Web link when the repo is hosted on GitHub
+    url             TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (target_task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (repo_id) REFERENCES repos(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_references_task_id ON task_references(task_id);
+CREATE INDEX idx_task_references_target_task_id ON task_references(target_task_id);
+
+-- When each task's description was last resolved; tasks without a row are (re)scanned by the
+-- background reference service
+CREATE TABLE task_reference_scans (
+    task_id     BLOB PRIMARY KEY,
+    scanned_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE TRIGGER trg_task_reference_scans_description AFTER UPDATE OF description ON tasks
+WHEN OLD.description IS NOT NEW.description
+BEGIN
+    DELETE FROM task_reference_scans WHERE task_id = NEW.id;
+END;
+
+-- A new task may be what an earlier `#N` in the project was pointing at
+CREATE TRIGGER trg_task_reference_scans_new_task AFTER INSERT ON tasks
+BEGIN
+    DELETE FROM task_reference_scans
+    WHERE task_id IN (SELECT id FROM tasks
+                      WHERE project_id = NEW.project_id
+                        AND description LIKE '%#' || NEW.number || '%');
+END;
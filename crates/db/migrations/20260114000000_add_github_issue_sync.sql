@@ -0,0 +1,47 @@
+-- Two-way sync between a project's tasks and the GitHub issues of one of its repos, polled in
+-- the background. At most one synced repo per project.
+CREATE TABLE github_issue_syncs (
+    project_id      BLOB PRIMARY KEY,
+    repo_id         BLOB NOT NULL,
+    -- Only issues carrying this label are imported
+    label           TEXT,
+    enabled         INTEGER NOT NULL DEFAULT 1,
+    -- Issues updated since this are fetched on the next poll; NULL imports all open issues
+    last_synced_at  TEXT,
+    last_error      TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (repo_id) REFERENCES repos(id) ON DELETE CASCADE
+);
+
+-- A task mirroring an issue, with both sides' state as of the last sync so changes can be told
+-- apart from the other side's
+CREATE TABLE github_issue_links (
+    task_id          BLOB PRIMARY KEY,
+    project_id       BLOB NOT NULL,
+    owner            TEXT NOT NULL,
+    repo             TEXT NOT NULL,
+    issue_number     INTEGER NOT NULL,
+    issue_url        TEXT NOT NULL,
+    synced_state     TEXT NOT NULL CHECK (synced_state IN ('open', 'closed')),
+    synced_status    TEXT NOT NULL,
+    -- JSON array of label names
+    synced_labels    TEXT NOT NULL DEFAULT '[]',
+    synced_at        TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    created_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, owner, repo, issue_number)
+);
+
+-- Task comments that came from or were posted to the linked issue, so neither side is copied twice
+CREATE TABLE github_issue_comments (
+    task_comment_id    BLOB PRIMARY KEY,
+    task_id            BLOB NOT NULL,
+    github_comment_id  INTEGER NOT NULL,
+    created_at         TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_comment_id) REFERENCES task_comments(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    UNIQUE (task_id, github_comment_id)
+);
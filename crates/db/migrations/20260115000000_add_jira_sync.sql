@@ -0,0 +1,33 @@
+-- Import of the Jira issues a JQL filter matches as tasks of a project, polled in the background.
+-- Jira stays the system of record; only completing a task is pushed back, as a transition.
+CREATE TABLE jira_syncs (
+    project_id       BLOB PRIMARY KEY,
+    jql              TEXT NOT NULL,
+    -- Name of the transition that completes an issue; NULL picks the first leading to a done status
+    done_transition  TEXT,
+    enabled          INTEGER NOT NULL DEFAULT 1,
+    -- Issues updated since this are fetched on the next poll; NULL fetches all matching issues
+    last_synced_at   TEXT,
+    last_error       TEXT,
+    created_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+-- A task mirroring a Jira issue, with both sides' status as of the last sync so changes can be
+-- told apart from the other side's
+CREATE TABLE jira_issue_links (
+    task_id             BLOB PRIMARY KEY,
+    project_id          BLOB NOT NULL,
+    issue_key           TEXT NOT NULL,
+    issue_url           TEXT NOT NULL,
+    -- Jira status name and its category: 'new', 'indeterminate' or 'done'
+    jira_status         TEXT NOT NULL,
+    jira_category       TEXT NOT NULL,
+    synced_status       TEXT NOT NULL,
+    synced_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, issue_key)
+);
@@ -0,0 +1,37 @@
+-- Sync between a project's tasks and the issues of a Linear team. Issues arrive through Linear
+-- webhooks once the first import has run; attempt results and pull requests are posted back as
+-- comments.
+CREATE TABLE linear_syncs (
+    project_id      BLOB PRIMARY KEY,
+    team_id         TEXT NOT NULL,
+    -- e.g. 'ENG', the prefix of the team's issue identifiers
+    team_key        TEXT NOT NULL,
+    enabled         INTEGER NOT NULL DEFAULT 1,
+    -- Issues updated since this are fetched on the next import; NULL until the first one
+    last_synced_at  TEXT,
+    last_error      TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_linear_syncs_team_id ON linear_syncs(team_id);
+
+-- A task mirroring a Linear issue
+CREATE TABLE linear_issue_links (
+    task_id           BLOB PRIMARY KEY,
+    project_id        BLOB NOT NULL,
+    issue_id          TEXT NOT NULL,
+    -- e.g. 'ENG-123'
+    identifier        TEXT NOT NULL,
+    issue_url         TEXT NOT NULL,
+    -- Workflow state type as of the last sync: 'triage', 'backlog', 'unstarted', 'started',
+    -- 'completed' or 'canceled'
+    synced_state      TEXT NOT NULL,
+    -- Task activity up to this point has been posted to the issue
+    posted_through    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    created_at        TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, issue_id)
+);
@@ -0,0 +1,8 @@
+-- Read-only iCal feed of a project's due dates and scheduled runs. Anyone with the feed's URL
+-- can read it; regenerating the token turns old URLs off.
+CREATE TABLE calendar_feeds (
+    project_id  BLOB PRIMARY KEY,
+    token       TEXT NOT NULL UNIQUE,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
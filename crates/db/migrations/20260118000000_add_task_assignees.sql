@@ -0,0 +1,14 @@
+-- People and the coding agent a task is assigned to. A task may have any number of human
+-- assignees and at most one agent.
+CREATE TABLE task_assignees (
+    task_id     BLOB NOT NULL,
+    kind        TEXT NOT NULL CHECK (kind IN ('human', 'agent')),
+    -- A person's name or handle, or an executor such as 'CLAUDE_CODE'
+    name        TEXT NOT NULL COLLATE NOCASE,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_id, kind, name),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_assignees_name ON task_assignees(name);
+CREATE UNIQUE INDEX idx_task_assignees_one_agent ON task_assignees(task_id) WHERE kind = 'agent';
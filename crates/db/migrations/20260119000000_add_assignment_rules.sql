@@ -0,0 +1,35 @@
+-- Rules applied to new tasks: a rule matches on title keywords, the integration that created
+-- the task, or file paths mentioned in its description, and adds labels and picks the executor
+-- profile and base branch attempts should use. A NULL project_id applies the rule in every
+-- project. List columns and executor_profile_id are JSON.
+CREATE TABLE assignment_rules (
+    id                  BLOB PRIMARY KEY,
+    project_id          BLOB,
+    name                TEXT NOT NULL,
+    enabled             INTEGER NOT NULL DEFAULT 1,
+    -- Rules are tried in ascending order
+    position            INTEGER NOT NULL DEFAULT 0,
+    title_keywords      TEXT NOT NULL DEFAULT '[]',
+    sources             TEXT NOT NULL DEFAULT '[]',
+    path_prefixes       TEXT NOT NULL DEFAULT '[]',
+    labels              TEXT NOT NULL DEFAULT '[]',
+    executor_profile_id TEXT,
+    base_branch         TEXT,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_assignment_rules_project_id ON assignment_rules(project_id);
+
+-- Executor profile and base branch picked for a task by the assignment rules
+CREATE TABLE task_run_defaults (
+    task_id             BLOB PRIMARY KEY,
+    executor_profile_id TEXT,
+    base_branch         TEXT,
+    -- Rule that picked them; NULL once the rule is deleted
+    rule_id             BLOB,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (rule_id) REFERENCES assignment_rules(id) ON DELETE SET NULL
+);
@@ -0,0 +1,16 @@
+-- Named board filters of a project, stored so anyone using the board can pick them up instead
+-- of rebuilding them each session. filters and columns are JSON.
+CREATE TABLE saved_views (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    name        TEXT NOT NULL,
+    filters     TEXT NOT NULL DEFAULT '{}',
+    sort        TEXT CHECK (sort IN ('created_at', 'priority')),
+    -- Board columns shown, in order; empty shows all
+    columns     TEXT NOT NULL DEFAULT '[]',
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_saved_views_project_id ON saved_views(project_id);
@@ -0,0 +1,15 @@
+-- Typed links between tasks besides dependencies. 'duplicates' and 'supersedes' read from
+-- task_id to related_id ("task_id duplicates related_id"); 'relates_to' goes both ways. A pair
+-- of tasks has at most one relation, in either direction.
+CREATE TABLE task_relations (
+    task_id     BLOB NOT NULL,
+    related_id  BLOB NOT NULL,
+    kind        TEXT NOT NULL CHECK (kind IN ('duplicates', 'relates_to', 'supersedes')),
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_id, related_id),
+    CHECK (task_id != related_id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (related_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_relations_related_id ON task_relations(related_id);
@@ -0,0 +1,18 @@
+-- Tokens and API spend of one execution process, read from its executor's output when it
+-- finishes. Input tokens exclude cache reads and writes. cost_usd is NULL when the executor
+-- reported no cost and the model has no known price; cost_estimated is set when the cost was
+-- worked out from token counts rather than reported.
+CREATE TABLE execution_usage (
+    execution_process_id BLOB PRIMARY KEY,
+    model                TEXT,
+    input_tokens         INTEGER NOT NULL DEFAULT 0,
+    output_tokens        INTEGER NOT NULL DEFAULT 0,
+    cache_read_tokens    INTEGER NOT NULL DEFAULT 0,
+    cache_write_tokens   INTEGER NOT NULL DEFAULT 0,
+    cost_usd             REAL,
+    cost_estimated       INTEGER NOT NULL DEFAULT 0,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_execution_usage_created_at ON execution_usage(created_at);
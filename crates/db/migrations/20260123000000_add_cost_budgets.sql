@@ -0,0 +1,18 @@
+-- Monthly API spend limits, counted over execution_usage in the current UTC calendar month.
+-- Reaching the soft limit sends a notification; reaching the hard limit also refuses new
+-- attempts. A NULL project_id is the budget across every project. The *_notified_month
+-- columns hold the month ('YYYY-MM') each limit was last reported for, so it's reported once.
+CREATE TABLE cost_budgets (
+    id                  BLOB PRIMARY KEY,
+    project_id          BLOB,
+    soft_limit_usd      REAL,
+    hard_limit_usd      REAL,
+    soft_notified_month TEXT,
+    hard_notified_month TEXT,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+-- One budget per project, and one across every project
+CREATE UNIQUE INDEX idx_cost_budgets_project_id ON cost_budgets(IFNULL(project_id, ''));
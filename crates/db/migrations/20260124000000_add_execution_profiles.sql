@@ -0,0 +1,12 @@
+-- Executor profile and model a coding agent execution ran with, recorded when it starts so
+-- attempts can be compared even after the profile is edited. model is the model the profile
+-- (or the attempt's override) named, NULL when it left the choice to the agent.
+CREATE TABLE execution_profiles (
+    execution_process_id BLOB PRIMARY KEY,
+    executor             TEXT NOT NULL,
+    variant              TEXT,
+    model                TEXT,
+    model_override       INTEGER NOT NULL DEFAULT 0,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
@@ -0,0 +1,10 @@
+-- Attempts waiting for a free slot under the global and per-project concurrency limits. They
+-- start by their task's priority, most urgent first, then in ascending position.
+CREATE TABLE queued_attempts (
+    id                  BLOB PRIMARY KEY,
+    workspace_id        BLOB NOT NULL UNIQUE,
+    executor_profile_id TEXT NOT NULL,
+    position            INTEGER NOT NULL,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
@@ -0,0 +1,7 @@
+-- Running executions whose process group is suspended. A row only counts while its execution
+-- is still running; resuming or stopping the execution removes it.
+CREATE TABLE execution_pauses (
+    execution_process_id BLOB PRIMARY KEY,
+    paused_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
@@ -0,0 +1,18 @@
+-- Points in an attempt to roll back to: each repo's HEAD and the agent session to resume from.
+-- They are taken after every successful agent turn and on request, and attach to the coding
+-- agent process they follow, so dropping that process on a rollback or retry hides them.
+CREATE TABLE attempt_checkpoints (
+    id                   BLOB PRIMARY KEY,
+    workspace_id         BLOB NOT NULL,
+    session_id           BLOB NOT NULL,
+    execution_process_id BLOB NOT NULL,
+    agent_session_id     TEXT,
+    label                TEXT,
+    repo_commits         TEXT NOT NULL,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_attempt_checkpoints_workspace_id ON attempt_checkpoints(workspace_id);
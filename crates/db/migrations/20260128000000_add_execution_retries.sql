@@ -0,0 +1,11 @@
+-- Coding agent runs started automatically after an earlier run failed with a transient error.
+-- retry_number counts from 1 along a chain of retries.
+CREATE TABLE execution_retries (
+    execution_process_id BLOB PRIMARY KEY,
+    retried_process_id   BLOB NOT NULL,
+    retry_number         INTEGER NOT NULL,
+    reason               TEXT NOT NULL,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    FOREIGN KEY (retried_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
@@ -0,0 +1,19 @@
+-- Audit trail of tool calls an agent asked the user to approve. A row is written when the
+-- request is made and completed with the decision; gate_pattern is set when a command gate,
+-- rather than the agent's own approvals mode, asked for it.
+CREATE TABLE approval_decisions (
+    id                   BLOB PRIMARY KEY,
+    approval_id          TEXT NOT NULL UNIQUE,
+    execution_process_id BLOB NOT NULL,
+    tool_name            TEXT NOT NULL,
+    tool_input           TEXT NOT NULL,
+    gate_pattern         TEXT,
+    status               TEXT NOT NULL DEFAULT 'pending'
+                            CHECK (status IN ('pending', 'approved', 'denied', 'timed_out')),
+    reason               TEXT,
+    requested_at         TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    decided_at           TEXT,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_approval_decisions_execution_process_id ON approval_decisions(execution_process_id);
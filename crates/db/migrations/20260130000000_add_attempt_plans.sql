@@ -0,0 +1,14 @@
+-- Attempts started in plan-then-execute mode. The agent's first run only writes a plan, which
+-- is stored here once it finishes; the user approves it, possibly edited, before a follow-up
+-- carries it out, or sends feedback to have it revised.
+CREATE TABLE attempt_plans (
+    workspace_id         BLOB PRIMARY KEY,
+    status               TEXT NOT NULL DEFAULT 'drafting'
+                            CHECK (status IN ('drafting', 'awaiting_approval', 'approved', 'rejected')),
+    plan                 TEXT,
+    execution_process_id BLOB,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL
+);
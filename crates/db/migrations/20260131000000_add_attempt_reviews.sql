@@ -0,0 +1,33 @@
+-- Attempts with a reviewer stage: once the agent finishes, a second executor profile reviews
+-- its work in a session of its own, and requested changes go back to the agent as a follow-up.
+CREATE TABLE attempt_reviewers (
+    workspace_id        BLOB PRIMARY KEY,
+    executor_profile_id TEXT NOT NULL,
+    -- Created for the first review; later rounds continue it
+    reviewer_session_id BLOB,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (reviewer_session_id) REFERENCES sessions(id) ON DELETE SET NULL
+);
+
+CREATE TABLE attempt_review_rounds (
+    id                   BLOB PRIMARY KEY,
+    workspace_id         BLOB NOT NULL,
+    -- The agent run whose work was reviewed
+    reviewed_process_id  BLOB NOT NULL,
+    reviewer_process_id  BLOB NOT NULL,
+    verdict              TEXT NOT NULL DEFAULT 'pending'
+                            CHECK (verdict IN ('pending', 'approved', 'changes_requested', 'failed')),
+    comments             TEXT,
+    -- The agent run that addressed the comments
+    follow_up_process_id BLOB,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    completed_at         TEXT,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (reviewed_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    FOREIGN KEY (reviewer_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    FOREIGN KEY (follow_up_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_attempt_review_rounds_workspace_id ON attempt_review_rounds(workspace_id);
+CREATE UNIQUE INDEX idx_attempt_review_rounds_reviewer_process_id ON attempt_review_rounds(reviewer_process_id);
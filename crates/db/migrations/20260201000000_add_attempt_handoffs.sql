@@ -0,0 +1,32 @@
+-- Handoffs of an attempt to a different executor profile: the new executor continues in the same
+-- worktree, in a session of its own that starts from a summary of the conversation so far.
+CREATE TABLE attempt_handoffs (
+    id                       BLOB PRIMARY KEY,
+    workspace_id             BLOB NOT NULL,
+    from_session_id          BLOB NOT NULL,
+    to_session_id            BLOB NOT NULL,
+    from_executor_profile_id TEXT NOT NULL,
+    to_executor_profile_id   TEXT NOT NULL,
+    reason                   TEXT,
+    -- The new executor's first run
+    execution_process_id     BLOB NOT NULL,
+    created_at               TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (from_session_id) REFERENCES sessions(id) ON DELETE CASCADE,
+    FOREIGN KEY (to_session_id) REFERENCES sessions(id) ON DELETE CASCADE,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_attempt_handoffs_workspace_id ON attempt_handoffs(workspace_id);
+
+-- A handoff shows in the task's history as a change of the attempt's executor
+CREATE TRIGGER trg_task_activity_handoff AFTER INSERT ON attempt_handoffs
+BEGIN
+    INSERT INTO task_activity (id, task_id, kind, field, old_value, new_value, workspace_id, execution_process_id)
+    SELECT randomblob(16), w.task_id, 'field_changed', 'executor',
+           (SELECT executor FROM sessions WHERE id = NEW.from_session_id),
+           (SELECT executor FROM sessions WHERE id = NEW.to_session_id),
+           w.id, NEW.execution_process_id
+    FROM workspaces w
+    WHERE w.id = NEW.workspace_id;
+END;
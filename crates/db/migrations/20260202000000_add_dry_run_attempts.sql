@@ -0,0 +1,28 @@
+-- Dry-run attempts: the agent works in the worktree, but nothing is committed or pushed. After
+-- each run the worktree's changes are captured as a patch per repo, until the patches are
+-- promoted to the attempt's branch.
+CREATE TABLE attempt_dry_runs (
+    workspace_id BLOB PRIMARY KEY,
+    -- Set once the patches were committed to the branch; the attempt then works as usual
+    promoted_at  TEXT,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
+
+-- The latest capture for each repo of the attempt
+CREATE TABLE attempt_patches (
+    id                   BLOB PRIMARY KEY,
+    workspace_id         BLOB NOT NULL,
+    repo_id              BLOB NOT NULL,
+    -- The run the patch was captured after
+    execution_process_id BLOB NOT NULL,
+    patch                TEXT NOT NULL,
+    files_changed        INTEGER NOT NULL DEFAULT 0,
+    additions            INTEGER NOT NULL DEFAULT 0,
+    deletions            INTEGER NOT NULL DEFAULT 0,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (repo_id) REFERENCES repos(id) ON DELETE CASCADE,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    UNIQUE (workspace_id, repo_id)
+);
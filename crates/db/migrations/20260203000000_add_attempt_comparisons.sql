@@ -0,0 +1,27 @@
+-- A/B comparison runs: the same task started against several executor profiles at once, each in
+-- a workspace and branch of its own, until one of the variants is chosen.
+CREATE TABLE attempt_comparisons (
+    id                  BLOB PRIMARY KEY,
+    task_id             BLOB NOT NULL,
+    -- The variant picked over the others; NULL until one is
+    chosen_workspace_id BLOB,
+    chosen_at           TEXT,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (chosen_workspace_id) REFERENCES workspaces(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_attempt_comparisons_task_id ON attempt_comparisons(task_id);
+
+CREATE TABLE attempt_comparison_variants (
+    workspace_id        BLOB PRIMARY KEY,
+    comparison_id       BLOB NOT NULL,
+    executor_profile_id TEXT NOT NULL,
+    -- Order the variants were requested in
+    position            INTEGER NOT NULL,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (comparison_id) REFERENCES attempt_comparisons(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_attempt_comparison_variants_comparison_id
+    ON attempt_comparison_variants(comparison_id);
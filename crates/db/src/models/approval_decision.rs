@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use ts_rs::TS;
+use utils::approvals::ApprovalStatus;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS)]
+#[sqlx(type_name = "approval_decision_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(use_ts_enum)]
+pub enum ApprovalDecisionStatus {
+    Pending,
+    Approved,
+    Denied,
+    TimedOut,
+}
+
+/// A tool call an agent asked the user to approve, and what was decided
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ApprovalDecision {
+    pub id: Uuid,
+    /// Id the approval was requested and answered under
+    pub approval_id: String,
+    pub execution_process_id: Uuid,
+    pub tool_name: String,
+    #[ts(type = "JsonValue")]
+    pub tool_input: Json<Value>,
+    /// The command gate pattern that asked for approval, absent when the agent asked itself
+    pub gate_pattern: Option<String>,
+    pub status: ApprovalDecisionStatus,
+    /// Why the call was denied
+    pub reason: Option<String>,
+    pub requested_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateApprovalDecision {
+    pub approval_id: String,
+    pub execution_process_id: Uuid,
+    pub tool_name: String,
+    pub tool_input: Value,
+    pub gate_pattern: Option<String>,
+}
+
+impl ApprovalDecision {
+    /// Decisions in the workspace's attempt, most recent first
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalDecision,
+            r#"SELECT d.id as "id!: Uuid", d.approval_id, d.execution_process_id as "execution_process_id!: Uuid", d.tool_name, d.tool_input as "tool_input!: Json<Value>", d.gate_pattern, d.status as "status!: ApprovalDecisionStatus", d.reason, d.requested_at as "requested_at!: DateTime<Utc>", d.decided_at as "decided_at: DateTime<Utc>"
+               FROM approval_decisions d
+               JOIN execution_processes ep ON ep.id = d.execution_process_id
+               JOIN sessions s ON s.id = ep.session_id
+               WHERE s.workspace_id = $1
+               ORDER BY d.requested_at DESC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateApprovalDecision,
+    ) -> Result<(), sqlx::Error> {
+        let id = Uuid::new_v4();
+        let tool_input = Json(&data.tool_input);
+        sqlx::query!(
+            r#"INSERT INTO approval_decisions (id, approval_id, execution_process_id, tool_name, tool_input, gate_pattern)
+               VALUES ($1, $2, $3, $4, $5, $6)"#,
+            id,
+            data.approval_id,
+            data.execution_process_id,
+            data.tool_name,
+            tool_input,
+            data.gate_pattern
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record how a pending request was settled
+    pub async fn record_outcome(
+        pool: &SqlitePool,
+        approval_id: &str,
+        outcome: &ApprovalStatus,
+    ) -> Result<(), sqlx::Error> {
+        let (status, reason) = match outcome {
+            ApprovalStatus::Pending => return Ok(()),
+            ApprovalStatus::Approved => (ApprovalDecisionStatus::Approved, None),
+            ApprovalStatus::Denied { reason } => (ApprovalDecisionStatus::Denied, reason.clone()),
+            ApprovalStatus::TimedOut => (ApprovalDecisionStatus::TimedOut, None),
+        };
+        sqlx::query!(
+            r#"UPDATE approval_decisions
+               SET status = $2, reason = $3, decided_at = datetime('now', 'subsec')
+               WHERE approval_id = $1 AND status = 'pending'"#,
+            approval_id,
+            status,
+            reason
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,278 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Where a new task came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskSource {
+    Manual,
+    Template,
+    Import,
+    Trello,
+    GitHub,
+    Jira,
+    Linear,
+    Schedule,
+}
+
+/// Labels, executor profile and base branch for new tasks matching every condition; empty
+/// conditions match anything. Available in one project or, without a project, in all.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AssignmentRule {
+    pub id: Uuid,
+    /// `None` for rules applied in every project
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    pub enabled: bool,
+    /// Rules are tried in ascending order
+    pub position: i64,
+    /// Matches titles containing any of these words, ignoring case
+    #[ts(type = "Array<string>")]
+    pub title_keywords: Json<Vec<String>>,
+    /// Matches tasks created by any of these
+    #[ts(type = "Array<TaskSource>")]
+    pub sources: Json<Vec<TaskSource>>,
+    /// Matches descriptions mentioning a path under any of these, e.g. `docs/`
+    #[ts(type = "Array<string>")]
+    pub path_prefixes: Json<Vec<String>>,
+    /// Added to matching tasks, together with those of other matching rules
+    #[ts(type = "Array<string>")]
+    pub labels: Json<Vec<String>>,
+    /// Executor profile for attempts; the first matching rule that sets one wins
+    #[ts(type = "ExecutorProfileId | null")]
+    pub executor_profile_id: Option<Json<ExecutorProfileId>>,
+    /// Branch attempts target in each project repo; the first matching rule that sets one wins
+    pub base_branch: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateAssignmentRule {
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    #[serde(default)]
+    #[ts(optional)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub position: Option<i64>,
+    #[serde(default)]
+    pub title_keywords: Vec<String>,
+    #[serde(default)]
+    pub sources: Vec<TaskSource>,
+    #[serde(default)]
+    pub path_prefixes: Vec<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    pub base_branch: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateAssignmentRule {
+    pub name: Option<String>,
+    pub enabled: Option<bool>,
+    pub position: Option<i64>,
+    pub title_keywords: Option<Vec<String>>,
+    pub sources: Option<Vec<TaskSource>>,
+    pub path_prefixes: Option<Vec<String>>,
+    pub labels: Option<Vec<String>>,
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    pub clear_executor_profile_id: Option<bool>,
+    /// An empty string clears the base branch
+    pub base_branch: Option<String>,
+}
+
+/// Executor profile and base branch the assignment rules picked for a task
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskRunDefaults {
+    pub task_id: Uuid,
+    #[ts(type = "ExecutorProfileId | null")]
+    pub executor_profile_id: Option<Json<ExecutorProfileId>>,
+    pub base_branch: Option<String>,
+    /// `None` once the rule is deleted
+    pub rule_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AssignmentRule {
+    /// Global rules, plus the project's own when `project_id` is set, in the order they're tried
+    pub async fn find_available(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AssignmentRule,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id: Uuid", name, enabled as "enabled!: bool", position, title_keywords as "title_keywords!: Json<Vec<String>>", sources as "sources!: Json<Vec<TaskSource>>", path_prefixes as "path_prefixes!: Json<Vec<String>>", labels as "labels!: Json<Vec<String>>", executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>", base_branch, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM assignment_rules
+               WHERE project_id IS NULL OR project_id = $1
+               ORDER BY position ASC, created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AssignmentRule,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id: Uuid", name, enabled as "enabled!: bool", position, title_keywords as "title_keywords!: Json<Vec<String>>", sources as "sources!: Json<Vec<TaskSource>>", path_prefixes as "path_prefixes!: Json<Vec<String>>", labels as "labels!: Json<Vec<String>>", executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>", base_branch, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM assignment_rules
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateAssignmentRule,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let enabled = data.enabled.unwrap_or(true);
+        let position = data.position.unwrap_or(0);
+        let title_keywords = Json(&data.title_keywords);
+        let sources = Json(&data.sources);
+        let path_prefixes = Json(&data.path_prefixes);
+        let labels = Json(&data.labels);
+        let executor_profile_id = data.executor_profile_id.as_ref().map(Json);
+        let base_branch = data
+            .base_branch
+            .as_deref()
+            .map(str::trim)
+            .filter(|branch| !branch.is_empty());
+        sqlx::query_as!(
+            AssignmentRule,
+            r#"INSERT INTO assignment_rules (id, project_id, name, enabled, position, title_keywords, sources, path_prefixes, labels, executor_profile_id, base_branch)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+               RETURNING id as "id!: Uuid", project_id as "project_id: Uuid", name, enabled as "enabled!: bool", position, title_keywords as "title_keywords!: Json<Vec<String>>", sources as "sources!: Json<Vec<TaskSource>>", path_prefixes as "path_prefixes!: Json<Vec<String>>", labels as "labels!: Json<Vec<String>>", executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>", base_branch, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.name,
+            enabled,
+            position,
+            title_keywords,
+            sources,
+            path_prefixes,
+            labels,
+            executor_profile_id,
+            base_branch
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateAssignmentRule,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let enabled = data.enabled.unwrap_or(existing.enabled);
+        let position = data.position.unwrap_or(existing.position);
+        let title_keywords = Json(
+            data.title_keywords
+                .as_ref()
+                .unwrap_or(&existing.title_keywords.0),
+        );
+        let sources = Json(data.sources.as_ref().unwrap_or(&existing.sources.0));
+        let path_prefixes = Json(
+            data.path_prefixes
+                .as_ref()
+                .unwrap_or(&existing.path_prefixes.0),
+        );
+        let labels = Json(data.labels.as_ref().unwrap_or(&existing.labels.0));
+        let executor_profile_id = if data.clear_executor_profile_id.unwrap_or(false) {
+            None
+        } else {
+            data.executor_profile_id
+                .as_ref()
+                .or(existing.executor_profile_id.as_ref().map(|id| &id.0))
+                .map(Json)
+        };
+        let base_branch = match data.base_branch.as_deref().map(str::trim) {
+            Some("") => None,
+            Some(branch) => Some(branch),
+            None => existing.base_branch.as_deref(),
+        };
+
+        sqlx::query_as!(
+            AssignmentRule,
+            r#"UPDATE assignment_rules
+               SET name = $2, enabled = $3, position = $4, title_keywords = $5, sources = $6, path_prefixes = $7, labels = $8, executor_profile_id = $9, base_branch = $10, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id: Uuid", name, enabled as "enabled!: bool", position, title_keywords as "title_keywords!: Json<Vec<String>>", sources as "sources!: Json<Vec<TaskSource>>", path_prefixes as "path_prefixes!: Json<Vec<String>>", labels as "labels!: Json<Vec<String>>", executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>", base_branch, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            enabled,
+            position,
+            title_keywords,
+            sources,
+            path_prefixes,
+            labels,
+            executor_profile_id,
+            base_branch
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM assignment_rules WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl TaskRunDefaults {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskRunDefaults,
+            r#"SELECT task_id as "task_id!: Uuid", executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>", base_branch, rule_id as "rule_id: Uuid", created_at as "created_at!: DateTime<Utc>"
+               FROM task_run_defaults
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn set(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        executor_profile_id: Option<&ExecutorProfileId>,
+        base_branch: Option<&str>,
+        rule_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        let executor_profile_id = executor_profile_id.map(Json);
+        sqlx::query!(
+            r#"INSERT INTO task_run_defaults (task_id, executor_profile_id, base_branch, rule_id)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(task_id) DO UPDATE SET
+                   executor_profile_id = excluded.executor_profile_id,
+                   base_branch = excluded.base_branch,
+                   rule_id = excluded.rule_id"#,
+            task_id,
+            executor_profile_id,
+            base_branch,
+            rule_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A repo's HEAD when the checkpoint was taken
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct CheckpointCommit {
+    pub repo_id: Uuid,
+    pub commit: String,
+}
+
+/// A point in an attempt the worktrees and agent session can be rolled back to
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AttemptCheckpoint {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub session_id: Uuid,
+    /// The coding agent process the checkpoint follows
+    pub execution_process_id: Uuid,
+    /// Agent session a follow-up resumes from after rolling back
+    pub agent_session_id: Option<String>,
+    /// Set for checkpoints taken on request
+    pub label: Option<String>,
+    #[ts(type = "Array<CheckpointCommit>")]
+    pub repo_commits: Json<Vec<CheckpointCommit>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateAttemptCheckpoint {
+    pub workspace_id: Uuid,
+    pub session_id: Uuid,
+    pub execution_process_id: Uuid,
+    pub agent_session_id: Option<String>,
+    pub label: Option<String>,
+    pub repo_commits: Vec<CheckpointCommit>,
+}
+
+impl AttemptCheckpoint {
+    /// Checkpoints of the workspace that are still reachable, oldest first. Those following a
+    /// dropped process were rolled back past.
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptCheckpoint,
+            r#"SELECT c.id as "id!: Uuid", c.workspace_id as "workspace_id!: Uuid", c.session_id as "session_id!: Uuid", c.execution_process_id as "execution_process_id!: Uuid", c.agent_session_id, c.label, c.repo_commits as "repo_commits!: Json<Vec<CheckpointCommit>>", c.created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_checkpoints c
+               JOIN execution_processes ep ON ep.id = c.execution_process_id
+               WHERE c.workspace_id = $1 AND ep.dropped = FALSE
+               ORDER BY c.created_at ASC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptCheckpoint,
+            r#"SELECT id as "id!: Uuid", workspace_id as "workspace_id!: Uuid", session_id as "session_id!: Uuid", execution_process_id as "execution_process_id!: Uuid", agent_session_id, label, repo_commits as "repo_commits!: Json<Vec<CheckpointCommit>>", created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_checkpoints
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateAttemptCheckpoint,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let repo_commits = Json(&data.repo_commits);
+        sqlx::query!(
+            r#"INSERT INTO attempt_checkpoints (id, workspace_id, session_id, execution_process_id, agent_session_id, label, repo_commits)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
+            id,
+            data.workspace_id,
+            data.session_id,
+            data.execution_process_id,
+            data.agent_session_id,
+            data.label,
+            repo_commits
+        )
+        .execute(pool)
+        .await?;
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM attempt_checkpoints WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,162 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// The same task run against several executor profiles side by side
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AttemptComparison {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// The variant picked over the others, if any yet
+    pub chosen_workspace_id: Option<Uuid>,
+    pub chosen_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One of a comparison's attempts
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ComparisonVariant {
+    pub workspace_id: Uuid,
+    pub comparison_id: Uuid,
+    #[ts(type = "ExecutorProfileId")]
+    pub executor_profile_id: Json<ExecutorProfileId>,
+    /// Order the variants were requested in
+    pub position: i64,
+}
+
+/// Position of the smallest value, skipping variants without one; ties go to the earlier variant
+pub fn lowest(values: &[Option<f64>]) -> Option<usize> {
+    values
+        .iter()
+        .enumerate()
+        .filter_map(|(position, value)| value.map(|value| (position, value)))
+        .fold(
+            None,
+            |best: Option<(usize, f64)>, (position, value)| match best {
+                Some((_, lowest)) if lowest <= value => best,
+                _ => Some((position, value)),
+            },
+        )
+        .map(|(position, _)| position)
+}
+
+impl AttemptComparison {
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptComparison,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", chosen_workspace_id as "chosen_workspace_id: Uuid", chosen_at as "chosen_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_comparisons
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// The task's comparisons, newest first
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptComparison,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", chosen_workspace_id as "chosen_workspace_id: Uuid", chosen_at as "chosen_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_comparisons
+               WHERE task_id = $1
+               ORDER BY created_at DESC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Record a comparison of the given workspaces, one per executor profile, in order
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        variants: &[(Uuid, ExecutorProfileId)],
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let mut tx = pool.begin().await?;
+        let comparison = sqlx::query_as!(
+            AttemptComparison,
+            r#"INSERT INTO attempt_comparisons (id, task_id)
+               VALUES ($1, $2)
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", chosen_workspace_id as "chosen_workspace_id: Uuid", chosen_at as "chosen_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            task_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        for (position, (workspace_id, executor_profile_id)) in variants.iter().enumerate() {
+            let executor_profile_id = Json(executor_profile_id);
+            let position = position as i64;
+            sqlx::query!(
+                r#"INSERT INTO attempt_comparison_variants (workspace_id, comparison_id, executor_profile_id, position)
+                   VALUES ($1, $2, $3, $4)"#,
+                workspace_id,
+                id,
+                executor_profile_id,
+                position
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(comparison)
+    }
+
+    /// Record the variant picked over the others; picking again replaces the choice
+    pub async fn choose(
+        pool: &SqlitePool,
+        id: Uuid,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptComparison,
+            r#"UPDATE attempt_comparisons
+               SET chosen_workspace_id = $2, chosen_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", chosen_workspace_id as "chosen_workspace_id: Uuid", chosen_at as "chosen_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
+
+impl ComparisonVariant {
+    /// The comparison's variants, in the order they were requested
+    pub async fn find_by_comparison_id(
+        pool: &SqlitePool,
+        comparison_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ComparisonVariant,
+            r#"SELECT workspace_id as "workspace_id!: Uuid", comparison_id as "comparison_id!: Uuid", executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>", position as "position!: i64"
+               FROM attempt_comparison_variants
+               WHERE comparison_id = $1
+               ORDER BY position ASC"#,
+            comparison_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lowest() {
+        assert_eq!(lowest(&[Some(3.0), Some(1.5), Some(2.0)]), Some(1));
+        assert_eq!(lowest(&[None, Some(2.0), Some(2.0)]), Some(1));
+        assert_eq!(lowest(&[None, None]), None);
+        assert_eq!(lowest(&[]), None);
+    }
+}
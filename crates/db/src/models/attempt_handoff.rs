@@ -0,0 +1,254 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use utils::text::truncate_to_char_boundary;
+use uuid::Uuid;
+
+use super::coding_agent_turn::CodingAgentTurn;
+
+/// Most of the conversation carried over: the latest turns, each cut to a length
+const MAX_HANDOFF_TURNS: usize = 10;
+const MAX_HANDOFF_TURN_CHARS: usize = 2000;
+const MAX_HANDOFF_FILES: usize = 100;
+
+/// An attempt handed from one executor profile to another
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AttemptHandoff {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub from_session_id: Uuid,
+    pub to_session_id: Uuid,
+    #[ts(type = "ExecutorProfileId")]
+    pub from_executor_profile_id: Json<ExecutorProfileId>,
+    #[ts(type = "ExecutorProfileId")]
+    pub to_executor_profile_id: Json<ExecutorProfileId>,
+    pub reason: Option<String>,
+    /// The new executor's first run
+    pub execution_process_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateAttemptHandoff {
+    pub workspace_id: Uuid,
+    pub from_session_id: Uuid,
+    pub to_session_id: Uuid,
+    pub from_executor_profile_id: ExecutorProfileId,
+    pub to_executor_profile_id: ExecutorProfileId,
+    pub reason: Option<String>,
+    pub execution_process_id: Uuid,
+}
+
+/// Files a repository's branch changed, for the new executor
+pub struct HandoffRepoChanges {
+    pub name: String,
+    pub target_branch: String,
+    /// Paths with how they changed, e.g. `src/main.rs (modified)`
+    pub files: Vec<String>,
+}
+
+fn truncated(text: &str) -> String {
+    let text = text.trim();
+    let cut = truncate_to_char_boundary(text, MAX_HANDOFF_TURN_CHARS);
+    if cut.len() < text.len() {
+        format!("{cut}…")
+    } else {
+        cut.to_string()
+    }
+}
+
+/// The new executor's first prompt: the task, the conversation so far and the changes made
+pub fn handoff_prompt(
+    task_prompt: &str,
+    from_executor: &ExecutorProfileId,
+    turns: &[CodingAgentTurn],
+    changes: &[HandoffRepoChanges],
+    reason: Option<&str>,
+) -> String {
+    let mut prompt = format!(
+        "You are taking over this task from another coding agent ({from_executor}):\n\n\
+         {task_prompt}"
+    );
+    if let Some(reason) = reason.map(str::trim).filter(|reason| !reason.is_empty()) {
+        prompt.push_str(&format!("\n\nIt was handed over to you because: {reason}"));
+    }
+
+    let skipped = turns.len().saturating_sub(MAX_HANDOFF_TURNS);
+    let conversation = turns[skipped..]
+        .iter()
+        .filter_map(|turn| {
+            let mut entry = Vec::new();
+            if let Some(prompt) = turn.prompt.as_deref().filter(|p| !p.trim().is_empty()) {
+                entry.push(format!("Request:\n{}", truncated(prompt)));
+            }
+            if let Some(summary) = turn.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+                entry.push(format!("Agent's reply:\n{}", truncated(summary)));
+            }
+            (!entry.is_empty()).then(|| entry.join("\n\n"))
+        })
+        .collect::<Vec<_>>();
+    if !conversation.is_empty() {
+        prompt.push_str("\n\n## Conversation so far\n\n");
+        if skipped > 0 {
+            prompt.push_str(&format!("({skipped} earlier turns left out)\n\n"));
+        }
+        prompt.push_str(&conversation.join("\n\n---\n\n"));
+    }
+
+    let changes = changes
+        .iter()
+        .filter(|repo| !repo.files.is_empty())
+        .map(|repo| {
+            let mut section = format!(
+                "`{}`, compared with `{}` (see `git diff {}...HEAD`):",
+                repo.name, repo.target_branch, repo.target_branch
+            );
+            for file in repo.files.iter().take(MAX_HANDOFF_FILES) {
+                section.push_str(&format!("\n- {file}"));
+            }
+            if repo.files.len() > MAX_HANDOFF_FILES {
+                section.push_str(&format!(
+                    "\n- and {} more",
+                    repo.files.len() - MAX_HANDOFF_FILES
+                ));
+            }
+            section
+        })
+        .collect::<Vec<_>>();
+    prompt.push_str("\n\n## Changes so far\n\n");
+    if changes.is_empty() {
+        prompt.push_str("None yet.");
+    } else {
+        prompt.push_str(&changes.join("\n\n"));
+    }
+
+    prompt.push_str(
+        "\n\nThe changes are in your working directory. Review them, then carry on with the task.",
+    );
+    prompt
+}
+
+impl AttemptHandoff {
+    /// Handoffs of the attempt, oldest first
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptHandoff,
+            r#"SELECT id as "id!: Uuid", workspace_id as "workspace_id!: Uuid", from_session_id as "from_session_id!: Uuid", to_session_id as "to_session_id!: Uuid", from_executor_profile_id as "from_executor_profile_id!: Json<ExecutorProfileId>", to_executor_profile_id as "to_executor_profile_id!: Json<ExecutorProfileId>", reason, execution_process_id as "execution_process_id!: Uuid", created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_handoffs
+               WHERE workspace_id = $1
+               ORDER BY created_at ASC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateAttemptHandoff,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let from_executor_profile_id = Json(&data.from_executor_profile_id);
+        let to_executor_profile_id = Json(&data.to_executor_profile_id);
+        sqlx::query_as!(
+            AttemptHandoff,
+            r#"INSERT INTO attempt_handoffs (id, workspace_id, from_session_id, to_session_id, from_executor_profile_id, to_executor_profile_id, reason, execution_process_id)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+               RETURNING id as "id!: Uuid", workspace_id as "workspace_id!: Uuid", from_session_id as "from_session_id!: Uuid", to_session_id as "to_session_id!: Uuid", from_executor_profile_id as "from_executor_profile_id!: Json<ExecutorProfileId>", to_executor_profile_id as "to_executor_profile_id!: Json<ExecutorProfileId>", reason, execution_process_id as "execution_process_id!: Uuid", created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            data.workspace_id,
+            data.from_session_id,
+            data.to_session_id,
+            from_executor_profile_id,
+            to_executor_profile_id,
+            data.reason,
+            data.execution_process_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+
+    fn turn(prompt: &str, summary: Option<&str>) -> CodingAgentTurn {
+        CodingAgentTurn {
+            id: Uuid::new_v4(),
+            execution_process_id: Uuid::new_v4(),
+            agent_session_id: None,
+            prompt: Some(prompt.to_string()),
+            summary: summary.map(str::to_string),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_handoff_prompt() {
+        let from = ExecutorProfileId::new(BaseCodingAgent::ClaudeCode);
+        let turns = [
+            turn("Fix the login bug", Some("I changed the session check.")),
+            turn("The tests still fail", None),
+        ];
+        let changes = [
+            HandoffRepoChanges {
+                name: "app".to_string(),
+                target_branch: "main".to_string(),
+                files: vec!["src/auth.rs (modified)".to_string()],
+            },
+            HandoffRepoChanges {
+                name: "docs".to_string(),
+                target_branch: "main".to_string(),
+                files: Vec::new(),
+            },
+        ];
+        let prompt = handoff_prompt(
+            "Fix the login bug",
+            &from,
+            &turns,
+            &changes,
+            Some("stuck on failing tests"),
+        );
+        assert!(prompt.starts_with(&format!(
+            "You are taking over this task from another coding agent ({from}):"
+        )));
+        assert!(prompt.contains("It was handed over to you because: stuck on failing tests"));
+        assert!(prompt.contains(
+            "Request:\nFix the login bug\n\nAgent's reply:\nI changed the session check.\n\n---\n\n\
+             Request:\nThe tests still fail"
+        ));
+        assert!(prompt.contains(
+            "`app`, compared with `main` (see `git diff main...HEAD`):\n- src/auth.rs (modified)"
+        ));
+        assert!(!prompt.contains("`docs`"));
+
+        let prompt = handoff_prompt("Add a page", &from, &[], &[], None);
+        assert!(!prompt.contains("Conversation so far"));
+        assert!(!prompt.contains("handed over to you because"));
+        assert!(prompt.contains("## Changes so far\n\nNone yet."));
+    }
+
+    #[test]
+    fn test_handoff_prompt_limits_conversation() {
+        let from = ExecutorProfileId::new(BaseCodingAgent::ClaudeCode);
+        let long = "x".repeat(MAX_HANDOFF_TURN_CHARS + 10);
+        let turns: Vec<_> = (0..MAX_HANDOFF_TURNS + 2)
+            .map(|i| turn(&format!("request {i}"), Some(&long)))
+            .collect();
+        let prompt = handoff_prompt("Task", &from, &turns, &[], None);
+        assert!(prompt.contains("(2 earlier turns left out)"));
+        assert!(!prompt.contains("request 1\n"));
+        assert!(prompt.contains(&format!("request {}", MAX_HANDOFF_TURNS + 1)));
+        assert!(prompt.contains(&format!("{}…", "x".repeat(MAX_HANDOFF_TURN_CHARS))));
+        assert!(!prompt.contains(&"x".repeat(MAX_HANDOFF_TURN_CHARS + 1)));
+    }
+}
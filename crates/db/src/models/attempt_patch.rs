@@ -0,0 +1,217 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// An attempt started in dry-run mode, whose changes are kept as patches rather than commits
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AttemptDryRun {
+    pub workspace_id: Uuid,
+    /// When the patches were committed to the attempt's branch; from then on it works as usual
+    pub promoted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A repo's uncommitted changes in a dry-run attempt, as of its latest run
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AttemptPatch {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub repo_id: Uuid,
+    /// The run the patch was captured after
+    pub execution_process_id: Uuid,
+    /// `git diff --binary` output against the branch's head
+    pub patch: String,
+    pub files_changed: i64,
+    pub additions: i64,
+    pub deletions: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Files, added lines and deleted lines in a git patch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PatchStats {
+    pub files_changed: i64,
+    pub additions: i64,
+    pub deletions: i64,
+}
+
+pub fn patch_stats(patch: &str) -> PatchStats {
+    let mut stats = PatchStats::default();
+    let mut in_hunk = false;
+    for line in patch.lines() {
+        if line.starts_with("diff --git ") {
+            stats.files_changed += 1;
+            in_hunk = false;
+        } else if line.starts_with("@@") {
+            in_hunk = true;
+        } else if in_hunk && line.starts_with('+') {
+            stats.additions += 1;
+        } else if in_hunk && line.starts_with('-') {
+            stats.deletions += 1;
+        }
+    }
+    stats
+}
+
+impl AttemptDryRun {
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptDryRun,
+            r#"SELECT workspace_id as "workspace_id!: Uuid", promoted_at as "promoted_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_dry_runs
+               WHERE workspace_id = $1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Whether the workspace's changes are still kept out of its branch
+    pub async fn is_active(pool: &SqlitePool, workspace_id: Uuid) -> Result<bool, sqlx::Error> {
+        Ok(Self::find_by_workspace_id(pool, workspace_id)
+            .await?
+            .is_some_and(|dry_run| dry_run.promoted_at.is_none()))
+    }
+
+    /// Mark a new attempt as a dry run, before its first run starts
+    pub async fn create(pool: &SqlitePool, workspace_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO attempt_dry_runs (workspace_id)
+               VALUES ($1)
+               ON CONFLICT(workspace_id) DO NOTHING"#,
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// `false` when the attempt was already promoted
+    pub async fn mark_promoted(pool: &SqlitePool, workspace_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE attempt_dry_runs
+               SET promoted_at = datetime('now', 'subsec')
+               WHERE workspace_id = $1 AND promoted_at IS NULL"#,
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+impl AttemptPatch {
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptPatch,
+            r#"SELECT id as "id!: Uuid", workspace_id as "workspace_id!: Uuid", repo_id as "repo_id!: Uuid", execution_process_id as "execution_process_id!: Uuid", patch, files_changed, additions, deletions, created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_patches
+               WHERE workspace_id = $1
+               ORDER BY created_at ASC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replace the repo's patch with a new capture
+    pub async fn upsert(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        repo_id: Uuid,
+        execution_process_id: Uuid,
+        patch: &str,
+    ) -> Result<(), sqlx::Error> {
+        let id = Uuid::new_v4();
+        let stats = patch_stats(patch);
+        sqlx::query!(
+            r#"INSERT INTO attempt_patches (id, workspace_id, repo_id, execution_process_id, patch, files_changed, additions, deletions)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+               ON CONFLICT(workspace_id, repo_id) DO UPDATE SET
+                   execution_process_id = excluded.execution_process_id,
+                   patch = excluded.patch,
+                   files_changed = excluded.files_changed,
+                   additions = excluded.additions,
+                   deletions = excluded.deletions,
+                   created_at = datetime('now', 'subsec')"#,
+            id,
+            workspace_id,
+            repo_id,
+            execution_process_id,
+            patch,
+            stats.files_changed,
+            stats.additions,
+            stats.deletions
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Drop the repo's patch once its changes are gone
+    pub async fn delete(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        repo_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM attempt_patches WHERE workspace_id = $1 AND repo_id = $2",
+            workspace_id,
+            repo_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patch_stats() {
+        let patch = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn main() {
+-    println!(\"old\");
++    println!(\"new\");
++    // --- not a header
+ }
+diff --git a/notes.md b/notes.md
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/notes.md
+@@ -0,0 +1 @@
++++ plus signs
+diff --git a/logo.png b/logo.png
+new file mode 100644
+index 0000000..4444444
+GIT binary patch
+literal 4
+LcmZQzWMT#Y01f~L
+";
+        assert_eq!(
+            patch_stats(patch),
+            PatchStats {
+                files_changed: 3,
+                additions: 3,
+                deletions: 1,
+            }
+        );
+        assert_eq!(patch_stats(""), PatchStats::default());
+    }
+}
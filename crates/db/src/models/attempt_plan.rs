@@ -0,0 +1,126 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS)]
+#[sqlx(type_name = "attempt_plan_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(use_ts_enum)]
+pub enum AttemptPlanStatus {
+    /// The agent is writing or revising the plan
+    Drafting,
+    AwaitingApproval,
+    /// The plan was handed to the agent to carry out
+    Approved,
+    /// The plan was dropped; the attempt carries on with ordinary follow-ups
+    Rejected,
+}
+
+/// The plan of an attempt started in plan-then-execute mode
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AttemptPlan {
+    pub workspace_id: Uuid,
+    pub status: AttemptPlanStatus,
+    /// The agent's plan, as edited when it was approved; absent until the first draft is done
+    pub plan: Option<String>,
+    /// The planning run the current draft came from
+    pub execution_process_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The task prompt turned into a request for a plan only
+pub fn planning_prompt(task_prompt: &str) -> String {
+    format!(
+        "{task_prompt}\n\nDon't make any changes yet. Investigate the codebase and reply with a \
+         step-by-step plan for this task: the files you will change, how, and how you will \
+         verify the result. The plan will be reviewed before you carry it out."
+    )
+}
+
+/// Feedback on a plan, asking for a revised one
+pub fn revision_prompt(feedback: &str) -> String {
+    format!(
+        "The plan was not approved. Feedback:\n\n{feedback}\n\nStill don't make any changes. \
+         Reply with the complete revised plan."
+    )
+}
+
+/// The approved plan, for the run that carries it out
+pub fn execution_prompt(plan: &str) -> String {
+    format!("Your plan was approved. Carry it out now:\n\n{plan}")
+}
+
+impl AttemptPlan {
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptPlan,
+            r#"SELECT workspace_id as "workspace_id!: Uuid", status as "status!: AttemptPlanStatus", plan, execution_process_id as "execution_process_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM attempt_plans
+               WHERE workspace_id = $1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Mark a new attempt as plan-first, before its first run starts
+    pub async fn create(pool: &SqlitePool, workspace_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO attempt_plans (workspace_id)
+               VALUES ($1)
+               ON CONFLICT(workspace_id) DO NOTHING"#,
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Store the draft a planning run finished with; `false` unless the plan was being drafted
+    pub async fn submit_draft(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        execution_process_id: Uuid,
+        plan: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE attempt_plans
+               SET status = 'awaiting_approval', plan = $3, execution_process_id = $2,
+                   updated_at = datetime('now', 'subsec')
+               WHERE workspace_id = $1 AND status = 'drafting'"#,
+            workspace_id,
+            execution_process_id,
+            plan
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Settle a plan awaiting approval, replacing its text when `plan` is set; `false` unless
+    /// it was awaiting approval
+    pub async fn decide(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        status: AttemptPlanStatus,
+        plan: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE attempt_plans
+               SET status = $2, plan = COALESCE($3, plan), updated_at = datetime('now', 'subsec')
+               WHERE workspace_id = $1 AND status = 'awaiting_approval'"#,
+            workspace_id,
+            status,
+            plan
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,283 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// The second executor profile reviewing an attempt's work
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AttemptReviewer {
+    pub workspace_id: Uuid,
+    #[ts(type = "ExecutorProfileId")]
+    pub executor_profile_id: Json<ExecutorProfileId>,
+    /// The reviewer's own session, once it has reviewed
+    pub reviewer_session_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS)]
+#[sqlx(type_name = "review_verdict", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(use_ts_enum)]
+pub enum ReviewVerdict {
+    Pending,
+    Approved,
+    ChangesRequested,
+    /// The reviewer run failed or ended without a verdict
+    Failed,
+}
+
+/// One review of the agent's work and what came of it
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AttemptReviewRound {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    /// The agent run whose work was reviewed
+    pub reviewed_process_id: Uuid,
+    pub reviewer_process_id: Uuid,
+    pub verdict: ReviewVerdict,
+    /// The reviewer's comments, without the verdict line
+    pub comments: Option<String>,
+    /// The agent run that addressed the comments
+    pub follow_up_process_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// A repository to review, and the branch its changes are compared with
+pub struct ReviewedRepo<'a> {
+    pub name: &'a str,
+    pub target_branch: &'a str,
+}
+
+/// The first request to the reviewer
+pub fn review_prompt(task_prompt: &str, repos: &[ReviewedRepo]) -> String {
+    let repos = repos
+        .iter()
+        .map(|repo| {
+            format!(
+                "- `{}`: `git diff {}...HEAD`",
+                repo.name, repo.target_branch
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        "You are reviewing another coding agent's work on this task:\n\n{task_prompt}\n\n\
+         Its changes are committed on the current branch of each repository:\n\n{repos}\n\n\
+         Don't modify any files. Check the changes for correctness, for completeness against the \
+         task and for code quality. End your reply with the line `VERDICT: APPROVED` if they are \
+         ready, or `VERDICT: CHANGES REQUESTED` after the comments the agent should address."
+    )
+}
+
+/// The reviewer's request for later rounds, in the same reviewer session
+pub fn re_review_prompt() -> String {
+    "The agent has addressed your comments and committed its changes. Review the changes again \
+     the same way, ending your reply with a `VERDICT:` line."
+        .to_string()
+}
+
+/// The reviewer's comments, for the agent's follow-up turn
+pub fn feedback_prompt(comments: &str) -> String {
+    format!("A reviewer requested changes to your work:\n\n{comments}\n\nAddress these comments.")
+}
+
+/// The verdict a reviewer's final message ends with, and its comments without the verdict line
+pub fn parse_verdict(message: &str) -> (ReviewVerdict, String) {
+    let lines: Vec<&str> = message.lines().collect();
+    let verdict = lines.iter().enumerate().rev().find_map(|(index, line)| {
+        let line = line
+            .trim()
+            .trim_matches(|c: char| c == '*' || c == '`' || c == '_')
+            .to_ascii_uppercase();
+        let verdict = line.strip_prefix("VERDICT:")?.trim().to_string();
+        match verdict.as_str() {
+            "APPROVED" => Some((index, ReviewVerdict::Approved)),
+            "CHANGES REQUESTED" => Some((index, ReviewVerdict::ChangesRequested)),
+            _ => None,
+        }
+    });
+    match verdict {
+        Some((index, verdict)) => {
+            let comments = lines
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != index)
+                .map(|(_, line)| *line)
+                .collect::<Vec<_>>()
+                .join("\n");
+            (verdict, comments.trim().to_string())
+        }
+        None => (ReviewVerdict::Failed, message.trim().to_string()),
+    }
+}
+
+impl AttemptReviewer {
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptReviewer,
+            r#"SELECT workspace_id as "workspace_id!: Uuid", executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>", reviewer_session_id as "reviewer_session_id: Uuid", created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_reviewers
+               WHERE workspace_id = $1"#,
+            workspace_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        executor_profile_id: &ExecutorProfileId,
+    ) -> Result<(), sqlx::Error> {
+        let executor_profile_id = Json(executor_profile_id);
+        sqlx::query!(
+            r#"INSERT INTO attempt_reviewers (workspace_id, executor_profile_id)
+               VALUES ($1, $2)
+               ON CONFLICT(workspace_id) DO UPDATE SET executor_profile_id = excluded.executor_profile_id"#,
+            workspace_id,
+            executor_profile_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_session(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        reviewer_session_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE attempt_reviewers SET reviewer_session_id = $2 WHERE workspace_id = $1",
+            workspace_id,
+            reviewer_session_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
+
+impl AttemptReviewRound {
+    /// Rounds of the attempt, oldest first
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptReviewRound,
+            r#"SELECT id as "id!: Uuid", workspace_id as "workspace_id!: Uuid", reviewed_process_id as "reviewed_process_id!: Uuid", reviewer_process_id as "reviewer_process_id!: Uuid", verdict as "verdict!: ReviewVerdict", comments, follow_up_process_id as "follow_up_process_id: Uuid", created_at as "created_at!: DateTime<Utc>", completed_at as "completed_at: DateTime<Utc>"
+               FROM attempt_review_rounds
+               WHERE workspace_id = $1
+               ORDER BY created_at ASC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_reviewer_process_id(
+        pool: &SqlitePool,
+        reviewer_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptReviewRound,
+            r#"SELECT id as "id!: Uuid", workspace_id as "workspace_id!: Uuid", reviewed_process_id as "reviewed_process_id!: Uuid", reviewer_process_id as "reviewer_process_id!: Uuid", verdict as "verdict!: ReviewVerdict", comments, follow_up_process_id as "follow_up_process_id: Uuid", created_at as "created_at!: DateTime<Utc>", completed_at as "completed_at: DateTime<Utc>"
+               FROM attempt_review_rounds
+               WHERE reviewer_process_id = $1"#,
+            reviewer_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        reviewed_process_id: Uuid,
+        reviewer_process_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO attempt_review_rounds (id, workspace_id, reviewed_process_id, reviewer_process_id)
+               VALUES ($1, $2, $3, $4)"#,
+            id,
+            workspace_id,
+            reviewed_process_id,
+            reviewer_process_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn complete(
+        pool: &SqlitePool,
+        id: Uuid,
+        verdict: ReviewVerdict,
+        comments: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE attempt_review_rounds
+               SET verdict = $2, comments = $3, completed_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            verdict,
+            comments
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_follow_up(
+        pool: &SqlitePool,
+        id: Uuid,
+        follow_up_process_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE attempt_review_rounds SET follow_up_process_id = $2 WHERE id = $1",
+            id,
+            follow_up_process_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_verdict() {
+        let (verdict, comments) = parse_verdict("Looks good overall.\n\n**VERDICT: APPROVED**\n");
+        assert_eq!(verdict, ReviewVerdict::Approved);
+        assert_eq!(comments, "Looks good overall.");
+
+        let (verdict, comments) = parse_verdict(
+            "1. The new endpoint has no tests.\n2. `parse` panics on empty input.\n\
+             Verdict: changes requested",
+        );
+        assert_eq!(verdict, ReviewVerdict::ChangesRequested);
+        assert_eq!(
+            comments,
+            "1. The new endpoint has no tests.\n2. `parse` panics on empty input."
+        );
+
+        let (verdict, comments) = parse_verdict("VERDICT: CHANGES REQUESTED\n- Rename `foo`.");
+        assert_eq!(verdict, ReviewVerdict::ChangesRequested);
+        assert_eq!(comments, "- Rename `foo`.");
+
+        let (verdict, comments) = parse_verdict("I couldn't find the changes.");
+        assert_eq!(verdict, ReviewVerdict::Failed);
+        assert_eq!(comments, "I couldn't find the changes.");
+    }
+}
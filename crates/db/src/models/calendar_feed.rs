@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A project's read-only iCal feed, served at a URL carrying `token`
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct CalendarFeed {
+    pub project_id: Uuid,
+    pub token: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CalendarFeed {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CalendarFeed,
+            r#"SELECT project_id as "project_id!: Uuid", token, created_at as "created_at!: DateTime<Utc>"
+               FROM calendar_feeds
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_token(
+        pool: &SqlitePool,
+        token: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CalendarFeed,
+            r#"SELECT project_id as "project_id!: Uuid", token, created_at as "created_at!: DateTime<Utc>"
+               FROM calendar_feeds
+               WHERE token = $1"#,
+            token
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Give the project's feed a new token; URLs with the old one stop working
+    pub async fn rotate(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        token: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            CalendarFeed,
+            r#"INSERT INTO calendar_feeds (project_id, token)
+               VALUES ($1, $2)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   token = excluded.token,
+                   created_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid", token, created_at as "created_at!: DateTime<Utc>""#,
+            project_id,
+            token
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM calendar_feeds WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -45,6 +45,31 @@ impl CodingAgentTurn {
         .await
     }
 
+    /// Turns of a session's coding agent runs, oldest first, leaving out dropped runs
+    pub async fn find_by_session_id(
+        pool: &SqlitePool,
+        session_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CodingAgentTurn,
+            r#"SELECT
+                cat.id as "id!: Uuid",
+                cat.execution_process_id as "execution_process_id!: Uuid",
+                cat.agent_session_id,
+                cat.prompt,
+                cat.summary,
+                cat.created_at as "created_at!: DateTime<Utc>",
+                cat.updated_at as "updated_at!: DateTime<Utc>"
+               FROM coding_agent_turns cat
+               JOIN execution_processes ep ON ep.id = cat.execution_process_id
+               WHERE ep.session_id = $1 AND ep.dropped = FALSE
+               ORDER BY cat.created_at ASC"#,
+            session_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     pub async fn find_by_agent_session_id(
         pool: &SqlitePool,
         agent_session_id: &str,
@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A change to one config key
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ConfigAuditEntry {
+    pub id: Uuid,
+    /// What made the change, e.g. `PUT /api/config` or `config file`
+    pub source: String,
+    /// Dotted path to the key, e.g. `notifications.webhook_urls`
+    pub path: String,
+    /// JSON value before the change, absent when the key was added
+    pub old_value: Option<String>,
+    /// JSON value after the change, absent when the key was removed
+    pub new_value: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateConfigAuditEntry {
+    pub path: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+impl ConfigAuditEntry {
+    /// Record the changes made by one update together
+    pub async fn create_many(
+        pool: &SqlitePool,
+        source: &str,
+        entries: &[CreateConfigAuditEntry],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for entry in entries {
+            let id = Uuid::new_v4();
+            sqlx::query!(
+                r#"INSERT INTO config_audit_log (id, source, path, old_value, new_value)
+                   VALUES ($1, $2, $3, $4, $5)"#,
+                id,
+                source,
+                entry.path,
+                entry.old_value,
+                entry.new_value
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Most recent changes first, optionally only those to `path` or keys nested under it
+    pub async fn find_page(
+        pool: &SqlitePool,
+        path: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ConfigAuditEntry,
+            r#"SELECT id as "id!: Uuid", source, path, old_value, new_value, created_at as "created_at!: DateTime<Utc>"
+               FROM config_audit_log
+               WHERE $1 IS NULL OR path = $1 OR path LIKE $1 || '.%'
+               ORDER BY created_at DESC
+               LIMIT $2 OFFSET $3"#,
+            path,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
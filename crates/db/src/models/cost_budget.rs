@@ -0,0 +1,237 @@
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A monthly API spend limit for one project, or across every project
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct CostBudget {
+    pub id: Uuid,
+    /// `None` for the budget across every project
+    pub project_id: Option<Uuid>,
+    /// Spend in a UTC calendar month that sends a notification
+    pub soft_limit_usd: Option<f64>,
+    /// Spend in a UTC calendar month after which new attempts are refused
+    pub hard_limit_usd: Option<f64>,
+    /// Month (`YYYY-MM`) the soft limit was last reported for
+    pub soft_notified_month: Option<String>,
+    /// Month (`YYYY-MM`) the hard limit was last reported for
+    pub hard_notified_month: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct SetCostBudget {
+    /// `None` for the budget across every project
+    pub project_id: Option<Uuid>,
+    pub soft_limit_usd: Option<f64>,
+    pub hard_limit_usd: Option<f64>,
+}
+
+/// How a month's spend compares to a budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetLevel {
+    Under,
+    /// At or over the soft limit
+    Soft,
+    /// At or over the hard limit; new attempts are refused
+    Hard,
+}
+
+/// Start of the UTC calendar month `now` falls in
+pub fn month_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now)
+}
+
+/// `YYYY-MM` of the UTC calendar month `now` falls in
+pub fn month_key(now: DateTime<Utc>) -> String {
+    now.format("%Y-%m").to_string()
+}
+
+impl CostBudget {
+    pub fn level(&self, spent_usd: f64) -> BudgetLevel {
+        let reached = |limit: Option<f64>| limit.is_some_and(|limit| spent_usd >= limit);
+        if reached(self.hard_limit_usd) {
+            BudgetLevel::Hard
+        } else if reached(self.soft_limit_usd) {
+            BudgetLevel::Soft
+        } else {
+            BudgetLevel::Under
+        }
+    }
+
+    /// Whether `level` was already reported for `month`
+    pub fn notified(&self, level: BudgetLevel, month: &str) -> bool {
+        match level {
+            BudgetLevel::Under => true,
+            BudgetLevel::Soft => self.soft_notified_month.as_deref() == Some(month),
+            BudgetLevel::Hard => self.hard_notified_month.as_deref() == Some(month),
+        }
+    }
+
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CostBudget,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id: Uuid", soft_limit_usd as "soft_limit_usd: f64", hard_limit_usd as "hard_limit_usd: f64", soft_notified_month, hard_notified_month, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM cost_budgets
+               ORDER BY project_id IS NOT NULL, created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The project's budget and the one across every project, that one first
+    pub async fn find_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CostBudget,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id: Uuid", soft_limit_usd as "soft_limit_usd: f64", hard_limit_usd as "hard_limit_usd: f64", soft_notified_month, hard_notified_month, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM cost_budgets
+               WHERE project_id IS NULL OR project_id = $1
+               ORDER BY project_id IS NOT NULL"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CostBudget,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id: Uuid", soft_limit_usd as "soft_limit_usd: f64", hard_limit_usd as "hard_limit_usd: f64", soft_notified_month, hard_notified_month, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM cost_budgets
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Create or replace the limits of the budget for `data.project_id`. Changing a limit lets
+    /// it be reported again this month.
+    pub async fn set(pool: &SqlitePool, data: &SetCostBudget) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            CostBudget,
+            r#"INSERT INTO cost_budgets (id, project_id, soft_limit_usd, hard_limit_usd)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(IFNULL(project_id, '')) DO UPDATE SET
+                 soft_limit_usd = excluded.soft_limit_usd,
+                 hard_limit_usd = excluded.hard_limit_usd,
+                 soft_notified_month = CASE WHEN soft_limit_usd IS excluded.soft_limit_usd THEN soft_notified_month END,
+                 hard_notified_month = CASE WHEN hard_limit_usd IS excluded.hard_limit_usd THEN hard_notified_month END,
+                 updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid", project_id as "project_id: Uuid", soft_limit_usd as "soft_limit_usd: f64", hard_limit_usd as "hard_limit_usd: f64", soft_notified_month, hard_notified_month, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.soft_limit_usd,
+            data.hard_limit_usd
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn mark_notified(
+        pool: &SqlitePool,
+        id: Uuid,
+        level: BudgetLevel,
+        month: &str,
+    ) -> Result<(), sqlx::Error> {
+        match level {
+            BudgetLevel::Under => {}
+            BudgetLevel::Soft => {
+                sqlx::query!(
+                    "UPDATE cost_budgets SET soft_notified_month = $2 WHERE id = $1",
+                    id,
+                    month
+                )
+                .execute(pool)
+                .await?;
+            }
+            BudgetLevel::Hard => {
+                sqlx::query!(
+                    "UPDATE cost_budgets SET soft_notified_month = $2, hard_notified_month = $2 WHERE id = $1",
+                    id,
+                    month
+                )
+                .execute(pool)
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM cost_budgets WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Reported and estimated spend recorded since `since`, in one project or across all
+    pub async fn spend_since(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+        since: DateTime<Utc>,
+    ) -> Result<f64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COALESCE(SUM(u.cost_usd), 0.0) as "spent!: f64"
+               FROM execution_usage u
+               JOIN execution_processes ep ON ep.id = u.execution_process_id
+               JOIN sessions s ON s.id = ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE ($1 IS NULL OR t.project_id = $1)
+                 AND datetime(u.created_at) >= datetime($2)"#,
+            project_id,
+            since
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_and_month() {
+        let budget = CostBudget {
+            id: Uuid::new_v4(),
+            project_id: None,
+            soft_limit_usd: Some(80.0),
+            hard_limit_usd: Some(100.0),
+            soft_notified_month: Some("2026-03".to_string()),
+            hard_notified_month: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        assert_eq!(budget.level(79.99), BudgetLevel::Under);
+        assert_eq!(budget.level(80.0), BudgetLevel::Soft);
+        assert_eq!(budget.level(120.0), BudgetLevel::Hard);
+        assert!(budget.notified(BudgetLevel::Soft, "2026-03"));
+        assert!(!budget.notified(BudgetLevel::Soft, "2026-04"));
+        assert!(!budget.notified(BudgetLevel::Hard, "2026-03"));
+
+        let soft_only = CostBudget {
+            hard_limit_usd: None,
+            ..budget
+        };
+        assert_eq!(soft_only.level(1_000.0), BudgetLevel::Soft);
+
+        let now = Utc.with_ymd_and_hms(2026, 3, 31, 23, 59, 59).unwrap();
+        assert_eq!(
+            month_start(now),
+            Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(month_key(now), "2026-03");
+    }
+}
@@ -0,0 +1,506 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{
+    task::{Task, TaskPriority, TaskStatus},
+    time_tracking::{AttemptTime, TimeTotals, format_seconds},
+};
+
+#[derive(Debug, Error)]
+pub enum EpicError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Epic name cannot be empty")]
+    EmptyName,
+    #[error("Task not found")]
+    TaskNotFound,
+}
+
+/// A group of related tasks, which may come from different projects
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct Epic {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub task_count: i64,
+    /// Tasks that are done or cancelled
+    pub completed_count: i64,
+    /// Projects the epic's tasks belong to
+    pub project_count: i64,
+    /// When every task in the epic was last found done or cancelled; cleared when one reopens
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateEpic {
+    pub name: String,
+    pub description: Option<String>,
+    /// Tasks to start the epic with
+    #[serde(default)]
+    #[ts(optional)]
+    pub task_ids: Option<Vec<Uuid>>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateEpic {
+    #[serde(default)]
+    #[ts(optional)]
+    pub name: Option<String>,
+    /// An empty string clears the description
+    #[serde(default)]
+    #[ts(optional)]
+    pub description: Option<String>,
+}
+
+/// A task's part in an epic, with the time its attempts took
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct EpicTask {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub title: String,
+    pub status: TaskStatus,
+    pub time: TimeTotals,
+}
+
+/// Aggregate status and cost of an epic's tasks
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct EpicSummary {
+    pub epic: Epic,
+    /// Status of the tasks taken together, see `aggregate_status`
+    pub status: TaskStatus,
+    pub todo: i64,
+    pub in_progress: i64,
+    pub in_review: i64,
+    pub done: i64,
+    pub cancelled: i64,
+    /// Share of tasks done or cancelled, from 0 to 100
+    pub percent_complete: f64,
+    /// Attempts and agent time across every task
+    pub time: TimeTotals,
+    /// Grouped by project name, oldest task first within each project
+    pub tasks: Vec<EpicTask>,
+}
+
+/// Status of a group of tasks: done or cancelled once every task is finished (done if any of
+/// them is), to do until any has started, in review once every unfinished task is, otherwise
+/// in progress. An empty group is to do.
+pub fn aggregate_status(statuses: &[TaskStatus]) -> TaskStatus {
+    let unfinished: Vec<&TaskStatus> = statuses
+        .iter()
+        .filter(|status| !matches!(status, TaskStatus::Done | TaskStatus::Cancelled))
+        .collect();
+    if unfinished.is_empty() && !statuses.is_empty() {
+        return if statuses.contains(&TaskStatus::Done) {
+            TaskStatus::Done
+        } else {
+            TaskStatus::Cancelled
+        };
+    }
+    if unfinished.len() == statuses.len()
+        && unfinished.iter().all(|status| **status == TaskStatus::Todo)
+    {
+        TaskStatus::Todo
+    } else if unfinished
+        .iter()
+        .all(|status| **status == TaskStatus::InReview)
+    {
+        TaskStatus::InReview
+    } else {
+        TaskStatus::InProgress
+    }
+}
+
+/// `1 attempt` or `3 attempts`
+fn attempts(count: i64) -> String {
+    if count == 1 {
+        "1 attempt".to_string()
+    } else {
+        format!("{count} attempts")
+    }
+}
+
+fn validate_name(name: &str) -> Result<String, EpicError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(EpicError::EmptyName);
+    }
+    Ok(name.to_string())
+}
+
+impl EpicSummary {
+    pub fn new(epic: Epic, tasks: Vec<EpicTask>) -> Self {
+        let statuses: Vec<TaskStatus> = tasks.iter().map(|task| task.status.clone()).collect();
+        let count = |status: TaskStatus| statuses.iter().filter(|s| **s == status).count() as i64;
+        let total = statuses.len() as i64;
+        let done = count(TaskStatus::Done);
+        let cancelled = count(TaskStatus::Cancelled);
+        let percent_complete = if total == 0 {
+            0.0
+        } else {
+            (done + cancelled) as f64 * 100.0 / total as f64
+        };
+        let time = tasks
+            .iter()
+            .fold(TimeTotals::default(), |mut totals, task| {
+                totals.attempts += task.time.attempts;
+                totals.wall_clock_seconds += task.time.wall_clock_seconds;
+                totals.active_seconds += task.time.active_seconds;
+                totals
+            });
+        Self {
+            epic,
+            status: aggregate_status(&statuses),
+            todo: count(TaskStatus::Todo),
+            in_progress: count(TaskStatus::InProgress),
+            in_review: count(TaskStatus::InReview),
+            done,
+            cancelled,
+            percent_complete,
+            time,
+            tasks,
+        }
+    }
+
+    /// The summary as a Markdown report, with the tasks listed under their projects
+    pub fn report(&self) -> String {
+        let mut lines = vec![format!("# {}", self.epic.name), String::new()];
+        if let Some(description) = &self.epic.description {
+            lines.extend([description.clone(), String::new()]);
+        }
+        lines.push(format!(
+            "- Status: {} ({} of {} tasks finished, {:.0}%)",
+            self.status,
+            self.done + self.cancelled,
+            self.tasks.len(),
+            self.percent_complete
+        ));
+        lines.push(format!(
+            "- Agent time: {} active, {} wall clock, over {}",
+            format_seconds(self.time.active_seconds),
+            format_seconds(self.time.wall_clock_seconds),
+            attempts(self.time.attempts)
+        ));
+
+        let mut project_id = None;
+        for task in &self.tasks {
+            if project_id != Some(task.project_id) {
+                project_id = Some(task.project_id);
+                lines.extend([String::new(), format!("## {}", task.project_name)]);
+            }
+            lines.push(format!(
+                "- [{}] {} ({} active, {})",
+                task.status,
+                task.title,
+                format_seconds(task.time.active_seconds),
+                attempts(task.time.attempts)
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+impl Epic {
+    /// Newest first; with `project_id`, only epics holding at least one of its tasks
+    pub async fn find_all(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Epic,
+            r#"SELECT e.id as "id!: Uuid", e.name, e.description,
+                      (SELECT COUNT(*) FROM epic_tasks et WHERE et.epic_id = e.id) as "task_count!: i64",
+                      (SELECT COUNT(*) FROM epic_tasks et JOIN tasks t ON t.id = et.task_id
+                        WHERE et.epic_id = e.id AND t.status IN ('done', 'cancelled')) as "completed_count!: i64",
+                      (SELECT COUNT(DISTINCT t.project_id) FROM epic_tasks et JOIN tasks t ON t.id = et.task_id
+                        WHERE et.epic_id = e.id) as "project_count!: i64",
+                      e.completed_at as "completed_at: DateTime<Utc>",
+                      e.created_at as "created_at!: DateTime<Utc>", e.updated_at as "updated_at!: DateTime<Utc>"
+               FROM epics e
+               WHERE $1 IS NULL
+                  OR EXISTS (SELECT 1 FROM epic_tasks et JOIN tasks t ON t.id = et.task_id
+                              WHERE et.epic_id = e.id AND t.project_id = $1)
+               ORDER BY e.created_at DESC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Epic,
+            r#"SELECT e.id as "id!: Uuid", e.name, e.description,
+                      (SELECT COUNT(*) FROM epic_tasks et WHERE et.epic_id = e.id) as "task_count!: i64",
+                      (SELECT COUNT(*) FROM epic_tasks et JOIN tasks t ON t.id = et.task_id
+                        WHERE et.epic_id = e.id AND t.status IN ('done', 'cancelled')) as "completed_count!: i64",
+                      (SELECT COUNT(DISTINCT t.project_id) FROM epic_tasks et JOIN tasks t ON t.id = et.task_id
+                        WHERE et.epic_id = e.id) as "project_count!: i64",
+                      e.completed_at as "completed_at: DateTime<Utc>",
+                      e.created_at as "created_at!: DateTime<Utc>", e.updated_at as "updated_at!: DateTime<Utc>"
+               FROM epics e
+               WHERE e.id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, data: &CreateEpic) -> Result<Self, EpicError> {
+        let name = validate_name(&data.name)?;
+        let description = data
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|d| !d.is_empty());
+
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            "INSERT INTO epics (id, name, description) VALUES ($1, $2, $3)",
+            id,
+            name,
+            description
+        )
+        .execute(pool)
+        .await?;
+        if let Some(task_ids) = &data.task_ids {
+            Self::add_tasks(pool, id, task_ids).await?;
+        }
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(EpicError::Database(sqlx::Error::RowNotFound))
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        existing: &Epic,
+        data: &UpdateEpic,
+    ) -> Result<Self, EpicError> {
+        let name = validate_name(data.name.as_deref().unwrap_or(&existing.name))?;
+        let description = match &data.description {
+            Some(description) => Some(description.trim()).filter(|d| !d.is_empty()),
+            None => existing.description.as_deref(),
+        };
+
+        sqlx::query!(
+            r#"UPDATE epics
+               SET name = $2, description = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            existing.id,
+            name,
+            description
+        )
+        .execute(pool)
+        .await?;
+        Self::find_by_id(pool, existing.id)
+            .await?
+            .ok_or(EpicError::Database(sqlx::Error::RowNotFound))
+    }
+
+    /// Delete the epic; its tasks are kept
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM epics WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Newest first
+    pub async fn find_tasks(&self, pool: &SqlitePool) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.number, t.title, t.description, t.status as "status!: TaskStatus", t.column_id as "column_id: Uuid", t.priority as "priority!: TaskPriority", t.due_at as "due_at: DateTime<Utc>", t.parent_workspace_id as "parent_workspace_id: Uuid", t.shared_task_id as "shared_task_id: Uuid", t.archived_at as "archived_at: DateTime<Utc>", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM epic_tasks et
+               JOIN tasks t ON t.id = et.task_id
+               WHERE et.epic_id = $1
+               ORDER BY t.created_at DESC"#,
+            self.id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Add tasks from any project; tasks already in the epic are left as they are
+    pub async fn add_tasks(
+        pool: &SqlitePool,
+        id: Uuid,
+        task_ids: &[Uuid],
+    ) -> Result<(), EpicError> {
+        let mut tx = pool.begin().await?;
+        for task_id in task_ids {
+            let exists = sqlx::query_scalar!(
+                r#"SELECT EXISTS(SELECT 1 FROM tasks WHERE id = $1) as "exists!: bool""#,
+                task_id
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+            if !exists {
+                return Err(EpicError::TaskNotFound);
+            }
+            sqlx::query!(
+                "INSERT INTO epic_tasks (epic_id, task_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+                id,
+                task_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn remove_task(&self, pool: &SqlitePool, task_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM epic_tasks WHERE epic_id = $1 AND task_id = $2",
+            self.id,
+            task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn summary(self, pool: &SqlitePool) -> Result<EpicSummary, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"SELECT t.id as "task_id!: Uuid", t.project_id as "project_id!: Uuid",
+                      p.name as project_name, t.title, t.status as "status!: TaskStatus"
+               FROM epic_tasks et
+               JOIN tasks t ON t.id = et.task_id
+               JOIN projects p ON p.id = t.project_id
+               WHERE et.epic_id = $1
+               ORDER BY p.name ASC, t.project_id, t.created_at ASC"#,
+            self.id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut tasks = Vec::with_capacity(records.len());
+        for record in records {
+            let time = AttemptTime::find_for_task(pool, record.task_id).await?;
+            tasks.push(EpicTask {
+                task_id: record.task_id,
+                project_id: record.project_id,
+                project_name: record.project_name,
+                title: record.title,
+                status: record.status,
+                time: time.totals,
+            });
+        }
+        Ok(EpicSummary::new(self, tasks))
+    }
+
+    /// Mark epics whose tasks are all done or cancelled as completed, returning the ones that
+    /// just were. Epics with a reopened or newly added unfinished task are cleared again, so
+    /// finishing them a second time is reported too.
+    pub async fn resolve_completed(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE epics
+               SET completed_at = NULL
+               WHERE completed_at IS NOT NULL
+                 AND EXISTS (SELECT 1 FROM epic_tasks et JOIN tasks t ON t.id = et.task_id
+                              WHERE et.epic_id = epics.id AND t.status NOT IN ('done', 'cancelled'))"#
+        )
+        .execute(pool)
+        .await?;
+
+        let completed = sqlx::query_scalar!(
+            r#"UPDATE epics
+               SET completed_at = datetime('now', 'subsec')
+               WHERE completed_at IS NULL
+                 AND EXISTS (SELECT 1 FROM epic_tasks et WHERE et.epic_id = epics.id)
+                 AND NOT EXISTS (SELECT 1 FROM epic_tasks et JOIN tasks t ON t.id = et.task_id
+                                  WHERE et.epic_id = epics.id AND t.status NOT IN ('done', 'cancelled'))
+               RETURNING id as "id!: Uuid""#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut epics = Vec::with_capacity(completed.len());
+        for id in completed {
+            if let Some(epic) = Self::find_by_id(pool, id).await? {
+                epics.push(epic);
+            }
+        }
+        Ok(epics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_status() {
+        use TaskStatus::*;
+
+        assert_eq!(aggregate_status(&[]), Todo);
+        assert_eq!(aggregate_status(&[Todo, Todo]), Todo);
+        assert_eq!(aggregate_status(&[Todo, InProgress]), InProgress);
+        assert_eq!(aggregate_status(&[Todo, Done]), InProgress);
+        assert_eq!(aggregate_status(&[InReview, Done, Cancelled]), InReview);
+        assert_eq!(aggregate_status(&[InReview, Todo]), InProgress);
+        assert_eq!(aggregate_status(&[Done, Cancelled]), Done);
+        assert_eq!(aggregate_status(&[Cancelled, Cancelled]), Cancelled);
+    }
+
+    #[test]
+    fn test_summary_report() {
+        let epic = Epic {
+            id: Uuid::new_v4(),
+            name: "Billing".to_string(),
+            description: Some("Move billing to the new provider".to_string()),
+            task_count: 3,
+            completed_count: 2,
+            project_count: 2,
+            completed_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let (api, web) = (Uuid::new_v4(), Uuid::new_v4());
+        let task =
+            |project_id, project_name: &str, title: &str, status, attempts, active_seconds| {
+                EpicTask {
+                    task_id: Uuid::new_v4(),
+                    project_id,
+                    project_name: project_name.to_string(),
+                    title: title.to_string(),
+                    status,
+                    time: TimeTotals {
+                        attempts,
+                        wall_clock_seconds: active_seconds + 60,
+                        active_seconds,
+                    },
+                }
+            };
+        let summary = EpicSummary::new(
+            epic,
+            vec![
+                task(api, "api", "Add webhooks", TaskStatus::Done, 2, 600),
+                task(api, "api", "Drop old client", TaskStatus::InReview, 1, 300),
+                task(web, "web", "Update checkout", TaskStatus::Cancelled, 0, 0),
+            ],
+        );
+
+        assert_eq!(summary.status, TaskStatus::InReview);
+        assert_eq!(summary.time.attempts, 3);
+        assert_eq!(summary.time.active_seconds, 900);
+        assert_eq!(
+            summary.report(),
+            "# Billing\n\n\
+             Move billing to the new provider\n\n\
+             - Status: inreview (2 of 3 tasks finished, 67%)\n\
+             - Agent time: 15m 0s active, 18m 0s wall clock, over 3 attempts\n\n\
+             ## api\n\
+             - [done] Add webhooks (10m 0s active, 2 attempts)\n\
+             - [inreview] Drop old client (5m 0s active, 1 attempt)\n\n\
+             ## web\n\
+             - [cancelled] Update checkout (0s active, 0 attempts)"
+        );
+    }
+}
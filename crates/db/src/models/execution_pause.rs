@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+/// A running execution whose process group is suspended
+#[derive(Debug, Clone, FromRow)]
+pub struct ExecutionPause {
+    pub execution_process_id: Uuid,
+    pub paused_at: DateTime<Utc>,
+}
+
+impl ExecutionPause {
+    /// Paused executions of the workspace that are still running
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionPause,
+            r#"SELECT p.execution_process_id as "execution_process_id!: Uuid", p.paused_at as "paused_at!: DateTime<Utc>"
+               FROM execution_pauses p
+               JOIN execution_processes ep ON ep.id = p.execution_process_id
+               JOIN sessions s ON s.id = ep.session_id
+               WHERE s.workspace_id = $1 AND ep.status = 'running'
+               ORDER BY p.paused_at ASC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, execution_process_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO execution_pauses (execution_process_id)
+               VALUES ($1)
+               ON CONFLICT(execution_process_id) DO NOTHING"#,
+            execution_process_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Whether the execution was paused
+    pub async fn delete(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM execution_pauses WHERE execution_process_id = $1",
+            execution_process_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -311,6 +311,51 @@ impl ExecutionProcess {
         Ok(count > 0)
     }
 
+    /// Number of attempts running their setup scripts or coding agent, per executor
+    pub async fn count_running_attempts_by_executor(
+        pool: &SqlitePool,
+    ) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"SELECT s.executor as "executor!: String", COUNT(DISTINCT s.workspace_id) as "count!: i64"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+               WHERE ep.status = 'running'
+                 AND ep.run_reason IN ('setupscript', 'codingagent')
+                 AND s.executor IS NOT NULL
+               GROUP BY s.executor"#
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(records
+            .into_iter()
+            .map(|record| (record.executor, record.count))
+            .collect())
+    }
+
+    /// Number of attempts running their setup scripts or coding agent, per project. Paused
+    /// executions don't count.
+    pub async fn count_running_attempts_by_project(
+        pool: &SqlitePool,
+    ) -> Result<Vec<(Uuid, i64)>, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"SELECT t.project_id as "project_id!: Uuid", COUNT(DISTINCT s.workspace_id) as "count!: i64"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+               JOIN workspaces w ON w.id = s.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE ep.status = 'running'
+                 AND ep.run_reason IN ('setupscript', 'codingagent')
+                 AND NOT EXISTS (SELECT 1 FROM execution_pauses p WHERE p.execution_process_id = ep.id)
+               GROUP BY t.project_id"#
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(records
+            .into_iter()
+            .map(|record| (record.project_id, record.count))
+            .collect())
+    }
+
     /// Find running dev servers for a specific workspace (across all sessions)
     pub async fn find_running_dev_servers_by_workspace(
         pool: &SqlitePool,
@@ -545,6 +590,26 @@ impl ExecutionProcess {
         Ok(result.rows_affected() as i64)
     }
 
+    /// Soft-drop processes after the specified boundary (exclusive)
+    pub async fn drop_after(
+        pool: &SqlitePool,
+        session_id: Uuid,
+        boundary_process_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE execution_processes
+               SET dropped = TRUE
+             WHERE session_id = $1
+               AND created_at > (SELECT created_at FROM execution_processes WHERE id = $2)
+               AND dropped = FALSE"#,
+            session_id,
+            boundary_process_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() as i64)
+    }
+
     /// Find the previous process's after_head_commit before the given boundary process
     /// for a specific repository
     pub async fn find_prev_after_head_commit(
@@ -0,0 +1,33 @@
+use executors::profile::ExecutorProfileId;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// Executor profile and model a coding agent execution ran with
+pub struct ExecutionProfile;
+
+impl ExecutionProfile {
+    /// Store the profile an execution starts with. `model` is the one the profile, or the
+    /// attempt's override, resolved to.
+    pub async fn record(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        executor_profile_id: &ExecutorProfileId,
+        model: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let executor = executor_profile_id.executor.to_string();
+        let model_override = executor_profile_id.model_override.is_some();
+        sqlx::query!(
+            r#"INSERT INTO execution_profiles (execution_process_id, executor, variant, model, model_override)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT(execution_process_id) DO NOTHING"#,
+            execution_process_id,
+            executor,
+            executor_profile_id.variant,
+            model,
+            model_override
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
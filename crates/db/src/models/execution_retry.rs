@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+/// A coding agent run started again after the one before it failed with a transient error
+#[derive(Debug, Clone, FromRow)]
+pub struct ExecutionRetry {
+    pub execution_process_id: Uuid,
+    pub retried_process_id: Uuid,
+    /// Counts from 1 along a chain of retries
+    pub retry_number: i64,
+    /// e.g. `rate_limit`
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ExecutionRetry {
+    pub async fn find_by_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionRetry,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid", retried_process_id as "retried_process_id!: Uuid", retry_number, reason, created_at as "created_at!: DateTime<Utc>"
+               FROM execution_retries
+               WHERE execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        retried_process_id: Uuid,
+        retry_number: i64,
+        reason: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO execution_retries (execution_process_id, retried_process_id, retry_number, reason)
+               VALUES ($1, $2, $3, $4)"#,
+            execution_process_id,
+            retried_process_id,
+            retry_number,
+            reason
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
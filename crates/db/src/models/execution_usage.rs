@@ -0,0 +1,408 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Tokens and API spend of one execution process, as read from its executor's output
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ExecutionUsage {
+    pub execution_process_id: Uuid,
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    /// Executor and variant the run started with; `None` for runs recorded before these were
+    pub executor: Option<String>,
+    pub variant: Option<String>,
+    /// The model that did most of the work, when the executor names it, otherwise the model its
+    /// profile named
+    pub model: Option<String>,
+    /// Input tokens besides cache reads and writes
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_tokens: i64,
+    pub cache_write_tokens: i64,
+    /// `None` when the executor reported no cost and the model's price isn't known
+    pub cost_usd: Option<f64>,
+    /// Whether the cost was worked out from token counts rather than reported by the executor
+    pub cost_estimated: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Usage read from an execution process's output, to be stored against it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecordUsage {
+    pub model: Option<String>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_tokens: i64,
+    pub cache_write_tokens: i64,
+    pub cost_usd: Option<f64>,
+    pub cost_estimated: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+pub struct UsageTotals {
+    pub runs: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_tokens: i64,
+    pub cache_write_tokens: i64,
+    /// Reported and estimated costs together; runs without a cost add nothing
+    pub cost_usd: f64,
+    /// Runs whose cost was estimated from token counts
+    pub estimated_runs: i64,
+    /// Runs without a cost
+    pub unpriced_runs: i64,
+}
+
+impl UsageTotals {
+    pub fn sum<'a>(runs: impl IntoIterator<Item = &'a ExecutionUsage>) -> Self {
+        runs.into_iter().fold(Self::default(), |mut totals, run| {
+            totals.runs += 1;
+            totals.input_tokens += run.input_tokens;
+            totals.output_tokens += run.output_tokens;
+            totals.cache_read_tokens += run.cache_read_tokens;
+            totals.cache_write_tokens += run.cache_write_tokens;
+            match run.cost_usd {
+                Some(cost) => {
+                    totals.cost_usd += cost;
+                    if run.cost_estimated {
+                        totals.estimated_runs += 1;
+                    }
+                }
+                None => totals.unpriced_runs += 1,
+            }
+            totals
+        })
+    }
+}
+
+/// A task's runs, newest first, with their totals
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskUsage {
+    pub task_id: Uuid,
+    pub totals: UsageTotals,
+    pub runs: Vec<ExecutionUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskUsageTotals {
+    pub task_id: Uuid,
+    pub title: String,
+    pub totals: UsageTotals,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ModelUsageTotals {
+    /// `None` for runs whose executor didn't name the model
+    pub model: Option<String>,
+    pub totals: UsageTotals,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProfileUsageTotals {
+    /// `None` for runs recorded before profiles were
+    pub executor: Option<String>,
+    pub variant: Option<String>,
+    pub totals: UsageTotals,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct DailyUsageTotals {
+    /// UTC day, `YYYY-MM-DD`
+    #[ts(type = "string")]
+    pub date: NaiveDate,
+    pub totals: UsageTotals,
+}
+
+/// Usage across the runs matching a stats query
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct UsageStats {
+    pub totals: UsageTotals,
+    /// Most expensive first
+    pub tasks: Vec<TaskUsageTotals>,
+    /// Most expensive first
+    pub models: Vec<ModelUsageTotals>,
+    /// Most expensive first
+    pub profiles: Vec<ProfileUsageTotals>,
+    /// Oldest first, days without runs left out
+    pub days: Vec<DailyUsageTotals>,
+}
+
+impl UsageStats {
+    /// Group runs by task, model, executor profile and day. `titles` names the tasks; runs of tasks missing from
+    /// it still count towards the totals.
+    pub fn from_runs(runs: &[ExecutionUsage], titles: &[(Uuid, String)]) -> Self {
+        let by_cost = |a: &UsageTotals, b: &UsageTotals| b.cost_usd.total_cmp(&a.cost_usd);
+
+        let mut tasks: Vec<TaskUsageTotals> = titles
+            .iter()
+            .filter_map(|(task_id, title)| {
+                let task_runs: Vec<&ExecutionUsage> =
+                    runs.iter().filter(|run| run.task_id == *task_id).collect();
+                (!task_runs.is_empty()).then(|| TaskUsageTotals {
+                    task_id: *task_id,
+                    title: title.clone(),
+                    totals: UsageTotals::sum(task_runs),
+                })
+            })
+            .collect();
+        tasks.sort_by(|a, b| by_cost(&a.totals, &b.totals));
+
+        let mut by_model: BTreeMap<Option<&str>, Vec<&ExecutionUsage>> = BTreeMap::new();
+        let mut by_profile: BTreeMap<(Option<&str>, Option<&str>), Vec<&ExecutionUsage>> =
+            BTreeMap::new();
+        let mut by_day: BTreeMap<NaiveDate, Vec<&ExecutionUsage>> = BTreeMap::new();
+        for run in runs {
+            by_model.entry(run.model.as_deref()).or_default().push(run);
+            by_profile
+                .entry((run.executor.as_deref(), run.variant.as_deref()))
+                .or_default()
+                .push(run);
+            by_day
+                .entry(run.created_at.date_naive())
+                .or_default()
+                .push(run);
+        }
+        let mut models: Vec<ModelUsageTotals> = by_model
+            .into_iter()
+            .map(|(model, runs)| ModelUsageTotals {
+                model: model.map(str::to_string),
+                totals: UsageTotals::sum(runs),
+            })
+            .collect();
+        models.sort_by(|a, b| by_cost(&a.totals, &b.totals));
+        let mut profiles: Vec<ProfileUsageTotals> = by_profile
+            .into_iter()
+            .map(|((executor, variant), runs)| ProfileUsageTotals {
+                executor: executor.map(str::to_string),
+                variant: variant.map(str::to_string),
+                totals: UsageTotals::sum(runs),
+            })
+            .collect();
+        profiles.sort_by(|a, b| by_cost(&a.totals, &b.totals));
+
+        Self {
+            totals: UsageTotals::sum(runs),
+            tasks,
+            models,
+            profiles,
+            days: by_day
+                .into_iter()
+                .map(|(date, runs)| DailyUsageTotals {
+                    date,
+                    totals: UsageTotals::sum(runs),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ExecutionUsage {
+    /// Store the usage of an execution process, replacing any recorded before
+    pub async fn record(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        usage: &RecordUsage,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO execution_usage
+                 (execution_process_id, model, input_tokens, output_tokens, cache_read_tokens, cache_write_tokens, cost_usd, cost_estimated)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+               ON CONFLICT(execution_process_id) DO UPDATE SET
+                 model = excluded.model,
+                 input_tokens = excluded.input_tokens,
+                 output_tokens = excluded.output_tokens,
+                 cache_read_tokens = excluded.cache_read_tokens,
+                 cache_write_tokens = excluded.cache_write_tokens,
+                 cost_usd = excluded.cost_usd,
+                 cost_estimated = excluded.cost_estimated"#,
+            execution_process_id,
+            usage.model,
+            usage.input_tokens,
+            usage.output_tokens,
+            usage.cache_read_tokens,
+            usage.cache_write_tokens,
+            usage.cost_usd,
+            usage.cost_estimated
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Runs matching every filter that is set, newest first. `to` is exclusive.
+    pub async fn find_where(
+        pool: &SqlitePool,
+        task_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionUsage,
+            r#"SELECT
+                 u.execution_process_id as "execution_process_id!: Uuid",
+                 w.id as "workspace_id!: Uuid",
+                 w.task_id as "task_id!: Uuid",
+                 p.executor,
+                 p.variant,
+                 COALESCE(u.model, p.model) as "model: String",
+                 u.input_tokens as "input_tokens!: i64",
+                 u.output_tokens as "output_tokens!: i64",
+                 u.cache_read_tokens as "cache_read_tokens!: i64",
+                 u.cache_write_tokens as "cache_write_tokens!: i64",
+                 u.cost_usd as "cost_usd: f64",
+                 u.cost_estimated as "cost_estimated!: bool",
+                 u.created_at as "created_at!: DateTime<Utc>"
+               FROM execution_usage u
+               JOIN execution_processes ep ON ep.id = u.execution_process_id
+               JOIN sessions s ON s.id = ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               LEFT JOIN execution_profiles p ON p.execution_process_id = u.execution_process_id
+               WHERE ($1 IS NULL OR w.task_id = $1)
+                 AND ($2 IS NULL OR t.project_id = $2)
+                 AND ($3 IS NULL OR datetime(u.created_at) >= datetime($3))
+                 AND ($4 IS NULL OR datetime(u.created_at) < datetime($4))
+               ORDER BY u.created_at DESC"#,
+            task_id,
+            project_id,
+            from,
+            to
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_for_task(pool: &SqlitePool, task_id: Uuid) -> Result<TaskUsage, sqlx::Error> {
+        let runs = Self::find_where(pool, Some(task_id), None, None, None).await?;
+        Ok(TaskUsage {
+            task_id,
+            totals: UsageTotals::sum(&runs),
+            runs,
+        })
+    }
+
+    /// Usage per task, model, executor profile and day, optionally within one project and a time range
+    pub async fn stats(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<UsageStats, sqlx::Error> {
+        let runs = Self::find_where(pool, None, project_id, from, to).await?;
+        let titles: Vec<(Uuid, String)> = sqlx::query!(
+            r#"SELECT id as "id!: Uuid", title FROM tasks WHERE ($1 IS NULL OR project_id = $1)"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|task| (task.id, task.title))
+        .collect();
+        Ok(UsageStats::from_runs(&runs, &titles))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_stats_from_runs() {
+        let (fix, docs) = (Uuid::new_v4(), Uuid::new_v4());
+        let run = |task_id, model: Option<&str>, day, cost_usd, cost_estimated| ExecutionUsage {
+            execution_process_id: Uuid::new_v4(),
+            workspace_id: Uuid::new_v4(),
+            task_id,
+            executor: model.map(|model| {
+                if model.starts_with("claude") {
+                    "CLAUDE_CODE"
+                } else {
+                    "CODEX"
+                }
+                .to_string()
+            }),
+            variant: None,
+            model: model.map(str::to_string),
+            input_tokens: 100,
+            output_tokens: 20,
+            cache_read_tokens: 1_000,
+            cache_write_tokens: 0,
+            cost_usd,
+            cost_estimated,
+            created_at: Utc.with_ymd_and_hms(2026, 1, day, 12, 0, 0).unwrap(),
+        };
+        let runs = [
+            run(fix, Some("claude-sonnet-4-5"), 2, Some(0.5), false),
+            run(docs, Some("gpt-5"), 1, Some(1.25), true),
+            run(fix, Some("claude-sonnet-4-5"), 1, Some(1.0), false),
+            run(docs, None, 2, None, false),
+        ];
+        let titles = vec![(fix, "Fix login".to_string()), (docs, "Docs".to_string())];
+
+        let stats = UsageStats::from_runs(&runs, &titles);
+        assert_eq!(
+            stats.totals,
+            UsageTotals {
+                runs: 4,
+                input_tokens: 400,
+                output_tokens: 80,
+                cache_read_tokens: 4_000,
+                cache_write_tokens: 0,
+                cost_usd: 2.75,
+                estimated_runs: 1,
+                unpriced_runs: 1,
+            }
+        );
+        let tasks: Vec<(&str, f64)> = stats
+            .tasks
+            .iter()
+            .map(|task| (task.title.as_str(), task.totals.cost_usd))
+            .collect();
+        assert_eq!(tasks, vec![("Fix login", 1.5), ("Docs", 1.25)]);
+        let models: Vec<(Option<&str>, i64)> = stats
+            .models
+            .iter()
+            .map(|model| (model.model.as_deref(), model.totals.runs))
+            .collect();
+        assert_eq!(
+            models,
+            vec![
+                (Some("claude-sonnet-4-5"), 2),
+                (Some("gpt-5"), 1),
+                (None, 1)
+            ]
+        );
+        let profiles: Vec<(Option<&str>, f64)> = stats
+            .profiles
+            .iter()
+            .map(|profile| (profile.executor.as_deref(), profile.totals.cost_usd))
+            .collect();
+        assert_eq!(
+            profiles,
+            vec![
+                (Some("CLAUDE_CODE"), 1.5),
+                (Some("CODEX"), 1.25),
+                (None, 0.0)
+            ]
+        );
+        let days: Vec<(String, f64)> = stats
+            .days
+            .iter()
+            .map(|day| (day.date.to_string(), day.totals.cost_usd))
+            .collect();
+        assert_eq!(
+            days,
+            vec![
+                ("2026-01-01".to_string(), 2.25),
+                ("2026-01-02".to_string(), 0.5)
+            ]
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS)]
+#[sqlx(type_name = "github_issue_state", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum GitHubIssueState {
+    Open,
+    Closed,
+}
+
+/// A project's issue sync with the GitHub repo behind one of its repos
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct GitHubIssueSync {
+    pub project_id: Uuid,
+    pub repo_id: Uuid,
+    /// Only issues carrying this label are imported
+    pub label: Option<String>,
+    pub enabled: bool,
+    /// Issues updated since are fetched on the next poll; `None` until the first sync
+    pub last_synced_at: Option<DateTime<Utc>>,
+    /// Why the last sync failed, cleared once one succeeds
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpsertGitHubIssueSync {
+    pub repo_id: Uuid,
+    #[serde(default)]
+    #[ts(optional)]
+    pub label: Option<String>,
+    pub enabled: bool,
+}
+
+/// A task mirroring a GitHub issue, with both sides as of the last sync
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct GitHubIssueLink {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub owner: String,
+    pub repo: String,
+    pub issue_number: i64,
+    pub issue_url: String,
+    pub synced_state: GitHubIssueState,
+    pub synced_status: TaskStatus,
+    #[ts(type = "Array<string>")]
+    pub synced_labels: Json<Vec<String>>,
+    pub synced_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A link to store for a task just imported from an issue
+#[derive(Debug, Clone)]
+pub struct NewGitHubIssueLink {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub owner: String,
+    pub repo: String,
+    pub issue_number: i64,
+    pub issue_url: String,
+    pub state: GitHubIssueState,
+    pub status: TaskStatus,
+    pub labels: Vec<String>,
+}
+
+/// A task comment mirrored from or to an issue comment
+#[derive(Debug, Clone, FromRow)]
+pub struct GitHubIssueComment {
+    pub task_comment_id: Uuid,
+    pub github_comment_id: i64,
+}
+
+impl GitHubIssueSync {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueSync,
+            r#"SELECT project_id as "project_id!: Uuid", repo_id as "repo_id!: Uuid", label, enabled as "enabled!: bool",
+                      last_synced_at as "last_synced_at: DateTime<Utc>", last_error,
+                      created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM github_issue_syncs
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_enabled(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueSync,
+            r#"SELECT project_id as "project_id!: Uuid", repo_id as "repo_id!: Uuid", label, enabled as "enabled!: bool",
+                      last_synced_at as "last_synced_at: DateTime<Utc>", last_error,
+                      created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM github_issue_syncs
+               WHERE enabled = 1"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Switching to another repo starts over from its open issues
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &UpsertGitHubIssueSync,
+    ) -> Result<Self, sqlx::Error> {
+        let label = data
+            .label
+            .as_deref()
+            .map(str::trim)
+            .filter(|label| !label.is_empty());
+        sqlx::query_as!(
+            GitHubIssueSync,
+            r#"INSERT INTO github_issue_syncs (project_id, repo_id, label, enabled)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   last_synced_at = CASE WHEN repo_id = excluded.repo_id THEN last_synced_at END,
+                   repo_id = excluded.repo_id,
+                   label = excluded.label,
+                   enabled = excluded.enabled,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid", repo_id as "repo_id!: Uuid", label, enabled as "enabled!: bool",
+                         last_synced_at as "last_synced_at: DateTime<Utc>", last_error,
+                         created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            data.repo_id,
+            label,
+            data.enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Stop syncing; tasks already imported keep their links
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM github_issue_syncs WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Record the outcome of a sync; only a successful one moves `last_synced_at`
+    pub async fn record_result(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        synced_at: Option<DateTime<Utc>>,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE github_issue_syncs
+               SET last_synced_at = COALESCE($2, last_synced_at), last_error = $3
+               WHERE project_id = $1"#,
+            project_id,
+            synced_at,
+            error
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
+
+impl GitHubIssueLink {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueLink,
+            r#"SELECT task_id as "task_id!: Uuid", project_id as "project_id!: Uuid", owner, repo, issue_number,
+                      issue_url, synced_state as "synced_state!: GitHubIssueState", synced_status as "synced_status!: TaskStatus",
+                      synced_labels as "synced_labels!: Json<Vec<String>>", synced_at as "synced_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM github_issue_links
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueLink,
+            r#"SELECT task_id as "task_id!: Uuid", project_id as "project_id!: Uuid", owner, repo, issue_number,
+                      issue_url, synced_state as "synced_state!: GitHubIssueState", synced_status as "synced_status!: TaskStatus",
+                      synced_labels as "synced_labels!: Json<Vec<String>>", synced_at as "synced_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM github_issue_links
+               WHERE project_id = $1
+               ORDER BY issue_number ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, link: &NewGitHubIssueLink) -> Result<(), sqlx::Error> {
+        let labels = Json(&link.labels);
+        sqlx::query!(
+            r#"INSERT INTO github_issue_links (task_id, project_id, owner, repo, issue_number, issue_url,
+                                              synced_state, synced_status, synced_labels)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
+            link.task_id,
+            link.project_id,
+            link.owner,
+            link.repo,
+            link.issue_number,
+            link.issue_url,
+            link.state,
+            link.status,
+            labels
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Store both sides' state as they are once a sync has reconciled them
+    pub async fn mark_synced(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        state: GitHubIssueState,
+        status: &TaskStatus,
+        labels: &[String],
+    ) -> Result<(), sqlx::Error> {
+        let labels = Json(labels);
+        sqlx::query!(
+            r#"UPDATE github_issue_links
+               SET synced_state = $2, synced_status = $3, synced_labels = $4, synced_at = datetime('now', 'subsec')
+               WHERE task_id = $1"#,
+            task_id,
+            state,
+            status,
+            labels
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// The task's comments that came from or were posted to the issue
+    pub async fn find_comments(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<GitHubIssueComment>, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueComment,
+            r#"SELECT task_comment_id as "task_comment_id!: Uuid", github_comment_id
+               FROM github_issue_comments
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn record_comment(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        task_comment_id: Uuid,
+        github_comment_id: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT OR IGNORE INTO github_issue_comments (task_comment_id, task_id, github_comment_id)
+               VALUES ($1, $2, $3)"#,
+            task_comment_id,
+            task_id,
+            github_comment_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
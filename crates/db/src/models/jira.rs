@@ -0,0 +1,242 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+/// The category every Jira status belongs to, whatever a workflow names the status itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS)]
+#[sqlx(type_name = "jira_status_category", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum JiraStatusCategory {
+    New,
+    Indeterminate,
+    Done,
+}
+
+/// A project's import of the Jira issues a JQL filter matches
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct JiraSync {
+    pub project_id: Uuid,
+    pub jql: String,
+    /// Transition that completes an issue; the first one leading to a done status when unset
+    pub done_transition: Option<String>,
+    pub enabled: bool,
+    /// Issues updated since are fetched on the next poll; `None` until the first sync
+    pub last_synced_at: Option<DateTime<Utc>>,
+    /// Why the last sync failed, cleared once one succeeds
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpsertJiraSync {
+    pub jql: String,
+    #[serde(default)]
+    #[ts(optional)]
+    pub done_transition: Option<String>,
+    pub enabled: bool,
+}
+
+/// A task mirroring a Jira issue, with both sides as of the last sync
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct JiraIssueLink {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    /// e.g. `PROJ-123`
+    pub issue_key: String,
+    pub issue_url: String,
+    pub jira_status: String,
+    pub jira_category: JiraStatusCategory,
+    pub synced_status: TaskStatus,
+    pub synced_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A link to store for a task just imported from an issue
+#[derive(Debug, Clone)]
+pub struct NewJiraIssueLink {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub issue_key: String,
+    pub issue_url: String,
+    pub jira_status: String,
+    pub jira_category: JiraStatusCategory,
+    pub status: TaskStatus,
+}
+
+impl JiraSync {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            JiraSync,
+            r#"SELECT project_id as "project_id!: Uuid", jql, done_transition, enabled as "enabled!: bool",
+                      last_synced_at as "last_synced_at: DateTime<Utc>", last_error,
+                      created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM jira_syncs
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_enabled(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            JiraSync,
+            r#"SELECT project_id as "project_id!: Uuid", jql, done_transition, enabled as "enabled!: bool",
+                      last_synced_at as "last_synced_at: DateTime<Utc>", last_error,
+                      created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM jira_syncs
+               WHERE enabled = 1"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Changing the filter starts over from every issue it matches
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &UpsertJiraSync,
+    ) -> Result<Self, sqlx::Error> {
+        let jql = data.jql.trim();
+        let done_transition = data
+            .done_transition
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty());
+        sqlx::query_as!(
+            JiraSync,
+            r#"INSERT INTO jira_syncs (project_id, jql, done_transition, enabled)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   last_synced_at = CASE WHEN jql = excluded.jql THEN last_synced_at END,
+                   jql = excluded.jql,
+                   done_transition = excluded.done_transition,
+                   enabled = excluded.enabled,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid", jql, done_transition, enabled as "enabled!: bool",
+                         last_synced_at as "last_synced_at: DateTime<Utc>", last_error,
+                         created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            jql,
+            done_transition,
+            data.enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Stop syncing; tasks already imported keep their links
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM jira_syncs WHERE project_id = $1", project_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Record the outcome of a sync; only a successful one moves `last_synced_at`
+    pub async fn record_result(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        synced_at: Option<DateTime<Utc>>,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE jira_syncs
+               SET last_synced_at = COALESCE($2, last_synced_at), last_error = $3
+               WHERE project_id = $1"#,
+            project_id,
+            synced_at,
+            error
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
+
+impl JiraIssueLink {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            JiraIssueLink,
+            r#"SELECT task_id as "task_id!: Uuid", project_id as "project_id!: Uuid", issue_key, issue_url,
+                      jira_status, jira_category as "jira_category!: JiraStatusCategory",
+                      synced_status as "synced_status!: TaskStatus", synced_at as "synced_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM jira_issue_links
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            JiraIssueLink,
+            r#"SELECT task_id as "task_id!: Uuid", project_id as "project_id!: Uuid", issue_key, issue_url,
+                      jira_status, jira_category as "jira_category!: JiraStatusCategory",
+                      synced_status as "synced_status!: TaskStatus", synced_at as "synced_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM jira_issue_links
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, link: &NewJiraIssueLink) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO jira_issue_links (task_id, project_id, issue_key, issue_url, jira_status,
+                                            jira_category, synced_status)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
+            link.task_id,
+            link.project_id,
+            link.issue_key,
+            link.issue_url,
+            link.jira_status,
+            link.jira_category,
+            link.status
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Store both sides' status as they are once a sync has reconciled them
+    pub async fn mark_synced(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        jira_status: &str,
+        jira_category: JiraStatusCategory,
+        status: &TaskStatus,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE jira_issue_links
+               SET jira_status = $2, jira_category = $3, synced_status = $4, synced_at = datetime('now', 'subsec')
+               WHERE task_id = $1"#,
+            task_id,
+            jira_status,
+            jira_category,
+            status
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
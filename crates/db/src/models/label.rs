@@ -0,0 +1,214 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Label that can be attached to any number of tasks; names are unique ignoring case
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct Label {
+    pub id: Uuid,
+    pub name: String,
+    /// CSS color, e.g. `#ef4444`
+    pub color: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateLabel {
+    pub name: String,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateLabel {
+    pub name: Option<String>,
+    /// An empty string clears the color
+    pub color: Option<String>,
+}
+
+impl Label {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT id as "id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels
+               ORDER BY name COLLATE NOCASE ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT id as "id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_name(pool: &SqlitePool, name: &str) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT id as "id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels
+               WHERE name = $1 COLLATE NOCASE"#,
+            name
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, data: &CreateLabel) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let name = data.name.trim();
+        sqlx::query_as!(
+            Label,
+            r#"INSERT INTO labels (id, name, color)
+               VALUES ($1, $2, $3)
+               RETURNING id as "id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            data.color
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// The label named `name`, created without a color if there isn't one yet
+    pub async fn find_or_create_by_name(
+        pool: &SqlitePool,
+        name: &str,
+    ) -> Result<Self, sqlx::Error> {
+        if let Some(label) = Self::find_by_name(pool, name.trim()).await? {
+            return Ok(label);
+        }
+        Self::create(
+            pool,
+            &CreateLabel {
+                name: name.to_string(),
+                color: None,
+            },
+        )
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        existing: &Label,
+        data: &UpdateLabel,
+    ) -> Result<Self, sqlx::Error> {
+        let name = data
+            .name
+            .as_deref()
+            .map_or(existing.name.as_str(), str::trim);
+        let color = match data.color.as_deref() {
+            Some(s) if s.trim().is_empty() => None,
+            Some(s) => Some(s),
+            None => existing.color.as_deref(),
+        };
+        sqlx::query_as!(
+            Label,
+            r#"UPDATE labels
+               SET name = $2, color = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            existing.id,
+            name,
+            color
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM labels WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn find_for_task(pool: &SqlitePool, task_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT l.id as "id!: Uuid", l.name, l.color, l.created_at as "created_at!: DateTime<Utc>", l.updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels l
+               JOIN task_labels tl ON tl.label_id = l.id
+               WHERE tl.task_id = $1
+               ORDER BY l.name COLLATE NOCASE ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Label names on a task, as matched by notification rules
+    pub async fn names_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        Ok(Self::find_for_task(pool, task_id)
+            .await?
+            .into_iter()
+            .map(|label| label.name)
+            .collect())
+    }
+
+    /// Ids of the tasks carrying the label named `name`
+    pub async fn task_ids_for_name(
+        pool: &SqlitePool,
+        name: &str,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT tl.task_id as "task_id!: Uuid"
+               FROM task_labels tl
+               JOIN labels l ON l.id = tl.label_id
+               WHERE l.name = $1 COLLATE NOCASE"#,
+            name
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn add_to_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        label_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT OR IGNORE INTO task_labels (task_id, label_id) VALUES ($1, $2)",
+            task_id,
+            label_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Replace the task's labels with `label_ids`
+    pub async fn set_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        label_ids: &[Uuid],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!("DELETE FROM task_labels WHERE task_id = $1", task_id)
+            .execute(&mut *tx)
+            .await?;
+        for label_id in label_ids {
+            sqlx::query!(
+                "INSERT OR IGNORE INTO task_labels (task_id, label_id) VALUES ($1, $2)",
+                task_id,
+                label_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+}
@@ -0,0 +1,266 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A project's sync with the issues of one Linear team
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct LinearSync {
+    pub project_id: Uuid,
+    pub team_id: String,
+    /// e.g. `ENG`, the prefix of the team's issue identifiers
+    pub team_key: String,
+    pub enabled: bool,
+    /// Issues updated since are fetched on the next import; `None` until the first one
+    pub last_synced_at: Option<DateTime<Utc>>,
+    /// Why the last sync failed, cleared once one succeeds
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpsertLinearSync {
+    /// Key of the team to sync with, e.g. `ENG`
+    pub team_key: String,
+    pub enabled: bool,
+}
+
+/// A task mirroring a Linear issue
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct LinearIssueLink {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub issue_id: String,
+    /// e.g. `ENG-123`
+    pub identifier: String,
+    pub issue_url: String,
+    /// Workflow state type as of the last sync, e.g. `started`
+    pub synced_state: String,
+    /// Task activity up to this point has been posted to the issue
+    pub posted_through: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A link to store for a task just imported from an issue
+#[derive(Debug, Clone)]
+pub struct NewLinearIssueLink {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub issue_id: String,
+    pub identifier: String,
+    pub issue_url: String,
+    pub state: String,
+}
+
+impl LinearSync {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            LinearSync,
+            r#"SELECT project_id as "project_id!: Uuid", team_id, team_key, enabled as "enabled!: bool",
+                      last_synced_at as "last_synced_at: DateTime<Utc>", last_error,
+                      created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM linear_syncs
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_enabled(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            LinearSync,
+            r#"SELECT project_id as "project_id!: Uuid", team_id, team_key, enabled as "enabled!: bool",
+                      last_synced_at as "last_synced_at: DateTime<Utc>", last_error,
+                      created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM linear_syncs
+               WHERE enabled = 1"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Enabled syncs of every project mapped to the team, for routing its webhooks
+    pub async fn find_enabled_by_team_id(
+        pool: &SqlitePool,
+        team_id: &str,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            LinearSync,
+            r#"SELECT project_id as "project_id!: Uuid", team_id, team_key, enabled as "enabled!: bool",
+                      last_synced_at as "last_synced_at: DateTime<Utc>", last_error,
+                      created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM linear_syncs
+               WHERE team_id = $1 AND enabled = 1"#,
+            team_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Switching to another team starts over with a full import
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        team_id: &str,
+        team_key: &str,
+        enabled: bool,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            LinearSync,
+            r#"INSERT INTO linear_syncs (project_id, team_id, team_key, enabled)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   last_synced_at = CASE WHEN team_id = excluded.team_id THEN last_synced_at END,
+                   team_id = excluded.team_id,
+                   team_key = excluded.team_key,
+                   enabled = excluded.enabled,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid", team_id, team_key, enabled as "enabled!: bool",
+                         last_synced_at as "last_synced_at: DateTime<Utc>", last_error,
+                         created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            team_id,
+            team_key,
+            enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Stop syncing; tasks already imported keep their links
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM linear_syncs WHERE project_id = $1", project_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Record the outcome of a sync; only a successful one moves `last_synced_at`
+    pub async fn record_result(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        synced_at: Option<DateTime<Utc>>,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE linear_syncs
+               SET last_synced_at = COALESCE($2, last_synced_at), last_error = $3
+               WHERE project_id = $1"#,
+            project_id,
+            synced_at,
+            error
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
+
+impl LinearIssueLink {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            LinearIssueLink,
+            r#"SELECT task_id as "task_id!: Uuid", project_id as "project_id!: Uuid", issue_id, identifier,
+                      issue_url, synced_state, posted_through as "posted_through!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM linear_issue_links
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            LinearIssueLink,
+            r#"SELECT task_id as "task_id!: Uuid", project_id as "project_id!: Uuid", issue_id, identifier,
+                      issue_url, synced_state, posted_through as "posted_through!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM linear_issue_links
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_issue(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        issue_id: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            LinearIssueLink,
+            r#"SELECT task_id as "task_id!: Uuid", project_id as "project_id!: Uuid", issue_id, identifier,
+                      issue_url, synced_state, posted_through as "posted_through!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM linear_issue_links
+               WHERE project_id = $1 AND issue_id = $2"#,
+            project_id,
+            issue_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, link: &NewLinearIssueLink) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO linear_issue_links (task_id, project_id, issue_id, identifier, issue_url, synced_state)
+               VALUES ($1, $2, $3, $4, $5, $6)"#,
+            link.task_id,
+            link.project_id,
+            link.issue_id,
+            link.identifier,
+            link.issue_url,
+            link.state
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn mark_synced(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        state: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE linear_issue_links SET synced_state = $2 WHERE task_id = $1",
+            task_id,
+            state
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Move the cursor past task activity that has been posted to the issue
+    pub async fn mark_posted(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        through: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE linear_issue_links SET posted_through = $2 WHERE task_id = $1",
+            task_id,
+            through
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
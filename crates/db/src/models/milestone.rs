@@ -0,0 +1,470 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::{Task, TaskPriority, TaskStatus};
+
+#[derive(Debug, Error)]
+pub enum MilestoneError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Milestone name cannot be empty")]
+    EmptyName,
+    #[error("Milestone cannot end before it starts")]
+    InvalidDateRange,
+    #[error("Task not found")]
+    TaskNotFound,
+    #[error("Only tasks of the milestone's project can be added to it")]
+    DifferentProject,
+}
+
+/// A sprint or release: a date range and the project tasks planned for it. A task belongs to at
+/// most one milestone.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct Milestone {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub start_date: NaiveDate,
+    /// Last day of the milestone, inclusive
+    pub end_date: NaiveDate,
+    pub task_count: i64,
+    /// Tasks that are done or cancelled
+    pub completed_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateMilestone {
+    pub name: String,
+    pub description: Option<String>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateMilestone {
+    #[serde(default)]
+    #[ts(optional)]
+    pub name: Option<String>,
+    /// An empty string clears the description
+    #[serde(default)]
+    #[ts(optional)]
+    pub description: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub start_date: Option<NaiveDate>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub end_date: Option<NaiveDate>,
+}
+
+/// One day of a burndown chart, counted at the end of the day (UTC)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct BurndownPoint {
+    pub date: NaiveDate,
+    /// Tasks in the milestone by the end of the day
+    pub scope: i64,
+    /// Of those, tasks not yet done or cancelled
+    pub remaining: i64,
+    /// Where `remaining` would be if the current scope burned down evenly to zero by the end date
+    pub ideal: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct MilestoneStats {
+    pub milestone_id: Uuid,
+    pub total: i64,
+    pub todo: i64,
+    pub in_progress: i64,
+    pub in_review: i64,
+    pub done: i64,
+    pub cancelled: i64,
+    /// Share of tasks done or cancelled, from 0 to 100
+    pub percent_complete: f64,
+    /// From the start date through today or the end date, whichever comes first
+    pub burndown: Vec<BurndownPoint>,
+}
+
+/// A task's part in a milestone's progress
+#[derive(Debug, Clone)]
+pub struct MilestoneMember {
+    pub status: TaskStatus,
+    pub added_at: DateTime<Utc>,
+    /// When the task was last marked done or cancelled, if it still is
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Check a milestone's settings, returning its trimmed name
+pub fn validate_milestone(
+    name: &str,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Result<String, MilestoneError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(MilestoneError::EmptyName);
+    }
+    if end_date < start_date {
+        return Err(MilestoneError::InvalidDateRange);
+    }
+    Ok(name.to_string())
+}
+
+/// Daily scope and remaining work from `start_date` through `today` or `end_date`, whichever
+/// comes first
+pub fn burndown(
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    today: NaiveDate,
+    members: &[MilestoneMember],
+) -> Vec<BurndownPoint> {
+    let total = members.len() as f64;
+    let total_days = (end_date - start_date).num_days();
+    start_date
+        .iter_days()
+        .take_while(|date| *date <= end_date.min(today))
+        .map(|date| {
+            let end_of_day = (date + Duration::days(1))
+                .and_time(Default::default())
+                .and_utc();
+            let in_scope = members.iter().filter(|m| m.added_at < end_of_day);
+            let scope = in_scope.clone().count() as i64;
+            let remaining = in_scope
+                .filter(|m| m.finished_at.is_none_or(|at| at >= end_of_day))
+                .count() as i64;
+            let ideal = if total_days == 0 {
+                0.0
+            } else {
+                total * (end_date - date).num_days() as f64 / total_days as f64
+            };
+            BurndownPoint {
+                date,
+                scope,
+                remaining,
+                ideal,
+            }
+        })
+        .collect()
+}
+
+impl MilestoneStats {
+    pub fn new(milestone: &Milestone, members: &[MilestoneMember], today: NaiveDate) -> Self {
+        let count =
+            |status: TaskStatus| members.iter().filter(|m| m.status == status).count() as i64;
+        let total = members.len() as i64;
+        let done = count(TaskStatus::Done);
+        let cancelled = count(TaskStatus::Cancelled);
+        let percent_complete = if total == 0 {
+            0.0
+        } else {
+            (done + cancelled) as f64 * 100.0 / total as f64
+        };
+        Self {
+            milestone_id: milestone.id,
+            total,
+            todo: count(TaskStatus::Todo),
+            in_progress: count(TaskStatus::InProgress),
+            in_review: count(TaskStatus::InReview),
+            done,
+            cancelled,
+            percent_complete,
+            burndown: burndown(milestone.start_date, milestone.end_date, today, members),
+        }
+    }
+}
+
+impl Milestone {
+    /// Earliest first
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Milestone,
+            r#"SELECT m.id as "id!: Uuid", m.project_id as "project_id!: Uuid", m.name, m.description, m.start_date as "start_date!: NaiveDate", m.end_date as "end_date!: NaiveDate",
+                      (SELECT COUNT(*) FROM milestone_tasks mt WHERE mt.milestone_id = m.id) as "task_count!: i64",
+                      (SELECT COUNT(*) FROM milestone_tasks mt JOIN tasks t ON t.id = mt.task_id
+                        WHERE mt.milestone_id = m.id AND t.status IN ('done', 'cancelled')) as "completed_count!: i64",
+                      m.created_at as "created_at!: DateTime<Utc>", m.updated_at as "updated_at!: DateTime<Utc>"
+               FROM milestones m
+               WHERE m.project_id = $1
+               ORDER BY m.start_date ASC, m.created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Milestone,
+            r#"SELECT m.id as "id!: Uuid", m.project_id as "project_id!: Uuid", m.name, m.description, m.start_date as "start_date!: NaiveDate", m.end_date as "end_date!: NaiveDate",
+                      (SELECT COUNT(*) FROM milestone_tasks mt WHERE mt.milestone_id = m.id) as "task_count!: i64",
+                      (SELECT COUNT(*) FROM milestone_tasks mt JOIN tasks t ON t.id = mt.task_id
+                        WHERE mt.milestone_id = m.id AND t.status IN ('done', 'cancelled')) as "completed_count!: i64",
+                      m.created_at as "created_at!: DateTime<Utc>", m.updated_at as "updated_at!: DateTime<Utc>"
+               FROM milestones m
+               WHERE m.id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateMilestone,
+    ) -> Result<Self, MilestoneError> {
+        let name = validate_milestone(&data.name, data.start_date, data.end_date)?;
+        let description = data
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|d| !d.is_empty());
+
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO milestones (id, project_id, name, description, start_date, end_date)
+               VALUES ($1, $2, $3, $4, $5, $6)"#,
+            id,
+            project_id,
+            name,
+            description,
+            data.start_date,
+            data.end_date
+        )
+        .execute(pool)
+        .await?;
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(MilestoneError::Database(sqlx::Error::RowNotFound))
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        existing: &Milestone,
+        data: &UpdateMilestone,
+    ) -> Result<Self, MilestoneError> {
+        let start_date = data.start_date.unwrap_or(existing.start_date);
+        let end_date = data.end_date.unwrap_or(existing.end_date);
+        let name = validate_milestone(
+            data.name.as_deref().unwrap_or(&existing.name),
+            start_date,
+            end_date,
+        )?;
+        let description = match &data.description {
+            Some(description) => Some(description.trim()).filter(|d| !d.is_empty()),
+            None => existing.description.as_deref(),
+        };
+
+        sqlx::query!(
+            r#"UPDATE milestones
+               SET name = $2, description = $3, start_date = $4, end_date = $5,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            existing.id,
+            name,
+            description,
+            start_date,
+            end_date
+        )
+        .execute(pool)
+        .await?;
+        Self::find_by_id(pool, existing.id)
+            .await?
+            .ok_or(MilestoneError::Database(sqlx::Error::RowNotFound))
+    }
+
+    /// Delete the milestone; its tasks are kept
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM milestones WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Newest first
+    pub async fn find_tasks(&self, pool: &SqlitePool) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.number, t.title, t.description, t.status as "status!: TaskStatus", t.column_id as "column_id: Uuid", t.priority as "priority!: TaskPriority", t.due_at as "due_at: DateTime<Utc>", t.parent_workspace_id as "parent_workspace_id: Uuid", t.shared_task_id as "shared_task_id: Uuid", t.archived_at as "archived_at: DateTime<Utc>", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM milestone_tasks mt
+               JOIN tasks t ON t.id = mt.task_id
+               WHERE mt.milestone_id = $1
+               ORDER BY t.created_at DESC"#,
+            self.id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Ids of the tasks in the milestone
+    pub async fn task_ids(pool: &SqlitePool, id: Uuid) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT task_id as "task_id!: Uuid" FROM milestone_tasks WHERE milestone_id = $1"#,
+            id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Add tasks to the milestone, moving them out of any other milestone
+    pub async fn add_tasks(
+        &self,
+        pool: &SqlitePool,
+        task_ids: &[Uuid],
+    ) -> Result<(), MilestoneError> {
+        let mut tx = pool.begin().await?;
+        for task_id in task_ids {
+            let project_id = sqlx::query_scalar!(
+                r#"SELECT project_id as "project_id!: Uuid" FROM tasks WHERE id = $1"#,
+                task_id
+            )
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(MilestoneError::TaskNotFound)?;
+            if project_id != self.project_id {
+                return Err(MilestoneError::DifferentProject);
+            }
+            sqlx::query!(
+                r#"INSERT INTO milestone_tasks (task_id, milestone_id)
+                   VALUES ($1, $2)
+                   ON CONFLICT(task_id) DO UPDATE
+                   SET milestone_id = excluded.milestone_id, added_at = datetime('now', 'subsec')
+                   WHERE milestone_id IS NOT excluded.milestone_id"#,
+                task_id,
+                self.id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn remove_task(&self, pool: &SqlitePool, task_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM milestone_tasks WHERE milestone_id = $1 AND task_id = $2",
+            self.id,
+            task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn find_members(
+        &self,
+        pool: &SqlitePool,
+    ) -> Result<Vec<MilestoneMember>, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"SELECT t.status as "status!: TaskStatus",
+                      mt.added_at as "added_at!: DateTime<Utc>",
+                      CASE WHEN t.status IN ('done', 'cancelled') THEN
+                          COALESCE((SELECT MAX(a.created_at) FROM task_activity a
+                                     WHERE a.task_id = t.id
+                                       AND a.kind = 'status_changed'
+                                       AND a.new_value IN ('done', 'cancelled')),
+                                   t.updated_at)
+                      END as "finished_at: DateTime<Utc>"
+               FROM milestone_tasks mt
+               JOIN tasks t ON t.id = mt.task_id
+               WHERE mt.milestone_id = $1"#,
+            self.id
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(records
+            .into_iter()
+            .map(|record| MilestoneMember {
+                status: record.status,
+                added_at: record.added_at,
+                finished_at: record.finished_at,
+            })
+            .collect())
+    }
+
+    pub async fn stats(
+        &self,
+        pool: &SqlitePool,
+        today: NaiveDate,
+    ) -> Result<MilestoneStats, sqlx::Error> {
+        let members = self.find_members(pool).await?;
+        Ok(MilestoneStats::new(self, &members, today))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_validate_milestone() {
+        assert_eq!(
+            validate_milestone(" Sprint 1 ", date(1), date(1)).unwrap(),
+            "Sprint 1"
+        );
+        assert!(matches!(
+            validate_milestone("  ", date(1), date(14)),
+            Err(MilestoneError::EmptyName)
+        ));
+        assert!(matches!(
+            validate_milestone("Sprint 1", date(14), date(1)),
+            Err(MilestoneError::InvalidDateRange)
+        ));
+    }
+
+    #[test]
+    fn test_burndown() {
+        let members = [
+            MilestoneMember {
+                status: TaskStatus::Done,
+                added_at: at(1, 9),
+                finished_at: Some(at(2, 15)),
+            },
+            MilestoneMember {
+                status: TaskStatus::InProgress,
+                added_at: at(1, 9),
+                finished_at: None,
+            },
+            // Added mid-sprint
+            MilestoneMember {
+                status: TaskStatus::Cancelled,
+                added_at: at(3, 10),
+                finished_at: Some(at(3, 11)),
+            },
+            MilestoneMember {
+                status: TaskStatus::Todo,
+                added_at: at(3, 10),
+                finished_at: None,
+            },
+        ];
+
+        let points = burndown(date(1), date(5), date(3), &members);
+        let counts: Vec<(i64, i64)> = points.iter().map(|p| (p.scope, p.remaining)).collect();
+        assert_eq!(counts, [(2, 2), (2, 1), (4, 2)]);
+        assert_eq!(points[0].ideal, 4.0);
+        assert_eq!(points[2].ideal, 2.0);
+
+        assert_eq!(burndown(date(1), date(5), date(9), &members).len(), 5);
+        assert!(burndown(date(4), date(5), date(3), &members).is_empty());
+        assert_eq!(burndown(date(1), date(1), date(1), &members)[0].ideal, 0.0);
+    }
+}
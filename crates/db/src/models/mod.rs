@@ -1,15 +1,57 @@
+pub mod approval_decision;
+pub mod assignment_rule;
+pub mod attempt_checkpoint;
+pub mod attempt_comparison;
+pub mod attempt_handoff;
+pub mod attempt_patch;
+pub mod attempt_plan;
+pub mod attempt_review;
+pub mod calendar_feed;
 pub mod coding_agent_turn;
+pub mod config_audit;
+pub mod cost_budget;
+pub mod epic;
+pub mod execution_pause;
 pub mod execution_process;
 pub mod execution_process_logs;
 pub mod execution_process_repo_state;
+pub mod execution_profile;
+pub mod execution_retry;
+pub mod execution_usage;
+pub mod github_issue;
 pub mod image;
+pub mod jira;
+pub mod label;
+pub mod linear;
 pub mod merge;
+pub mod milestone;
+pub mod notification;
+pub mod notification_escalation;
 pub mod project;
+pub mod project_column;
 pub mod project_repo;
+pub mod push_subscription;
+pub mod queued_attempt;
 pub mod repo;
+pub mod saved_view;
 pub mod scratch;
+pub mod search;
 pub mod session;
+pub mod slack_thread;
+pub mod swimlane;
 pub mod tag;
 pub mod task;
+pub mod task_activity;
+pub mod task_assignee;
+pub mod task_attachment;
+pub mod task_checklist_item;
+pub mod task_comment;
+pub mod task_dependency;
+pub mod task_reference;
+pub mod task_relation;
+pub mod task_schedule;
+pub mod task_subtask;
+pub mod task_template;
+pub mod time_tracking;
 pub mod workspace;
 pub mod workspace_repo;
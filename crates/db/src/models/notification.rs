@@ -0,0 +1,147 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Delivery outcome; `suppressed` marks notifications skipped on purpose (e.g. quiet hours)
+/// and `deadletter` ones that still failed after every retry
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display)]
+#[sqlx(type_name = "notification_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum NotificationStatus {
+    Sent,
+    Failed,
+    Suppressed,
+    DeadLetter,
+}
+
+/// A notification delivered (or attempted) through a single channel
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct NotificationRecord {
+    pub id: Uuid,
+    pub channel: String,
+    pub event: String,
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub title: String,
+    pub message: String,
+    pub task_url: Option<String>,
+    pub status: NotificationStatus,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Latest delivery results for one channel, ignoring suppressed notifications
+#[derive(Debug, Clone, FromRow)]
+pub struct ChannelDeliveryStats {
+    pub channel: String,
+    pub last_status: NotificationStatus,
+    pub last_attempt_at: DateTime<Utc>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateNotificationRecord {
+    pub channel: String,
+    pub event: String,
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub title: String,
+    pub message: String,
+    pub task_url: Option<String>,
+    pub status: NotificationStatus,
+    pub error: Option<String>,
+}
+
+impl NotificationRecord {
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateNotificationRecord,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            NotificationRecord,
+            r#"INSERT INTO notifications (id, channel, event, project_id, task_id, title, message, task_url, status, error)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+               RETURNING id as "id!: Uuid", channel, event, project_id as "project_id: Uuid", task_id as "task_id: Uuid", title, message, task_url, status as "status!: NotificationStatus", error, created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            data.channel,
+            data.event,
+            data.project_id,
+            data.task_id,
+            data.title,
+            data.message,
+            data.task_url,
+            data.status,
+            data.error
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            NotificationRecord,
+            r#"SELECT id as "id!: Uuid", channel, event, project_id as "project_id: Uuid", task_id as "task_id: Uuid", title, message, task_url, status as "status!: NotificationStatus", error, created_at as "created_at!: DateTime<Utc>"
+               FROM notifications
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Most recent notifications first
+    pub async fn find_page(
+        pool: &SqlitePool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            NotificationRecord,
+            r#"SELECT id as "id!: Uuid", channel, event, project_id as "project_id: Uuid", task_id as "task_id: Uuid", title, message, task_url, status as "status!: NotificationStatus", error, created_at as "created_at!: DateTime<Utc>"
+               FROM notifications
+               ORDER BY created_at DESC
+               LIMIT $1 OFFSET $2"#,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn channel_stats(
+        pool: &SqlitePool,
+    ) -> Result<Vec<ChannelDeliveryStats>, sqlx::Error> {
+        sqlx::query_as!(
+            ChannelDeliveryStats,
+            r#"SELECT
+                 n.channel AS "channel!",
+                 (SELECT status FROM notifications
+                   WHERE channel = n.channel AND status != 'suppressed'
+                   ORDER BY created_at DESC LIMIT 1) AS "last_status!: NotificationStatus",
+                 MAX(n.created_at) AS "last_attempt_at!: DateTime<Utc>",
+                 MAX(CASE WHEN n.status = 'sent' THEN n.created_at END) AS "last_success_at: DateTime<Utc>",
+                 (SELECT error FROM notifications
+                   WHERE channel = n.channel AND status IN ('failed', 'deadletter')
+                   ORDER BY created_at DESC LIMIT 1) AS "last_error: String",
+                 MAX(CASE WHEN n.status IN ('failed', 'deadletter') THEN n.created_at END) AS "last_error_at: DateTime<Utc>"
+               FROM notifications n
+               WHERE n.status != 'suppressed'
+               GROUP BY n.channel"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn count(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(r#"SELECT COUNT(*) as "count!: i64" FROM notifications"#)
+            .fetch_one(pool)
+            .await
+    }
+}
@@ -0,0 +1,129 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A failure notification that is re-sent on other channels unless acknowledged by `escalate_at`
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct NotificationEscalation {
+    pub id: Uuid,
+    pub event: String,
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub workspace_id: Option<Uuid>,
+    pub title: String,
+    pub message: String,
+    pub task_url: Option<String>,
+    pub escalate_at: DateTime<Utc>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    pub escalated_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateNotificationEscalation {
+    pub event: String,
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub workspace_id: Option<Uuid>,
+    pub title: String,
+    pub message: String,
+    pub task_url: Option<String>,
+    pub delay_minutes: i64,
+}
+
+impl NotificationEscalation {
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateNotificationEscalation,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            NotificationEscalation,
+            r#"INSERT INTO notification_escalations (id, event, project_id, task_id, workspace_id, title, message, task_url, escalate_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, datetime('now', 'subsec', '+' || $9 || ' minutes'))
+               RETURNING id as "id!: Uuid", event, project_id as "project_id: Uuid", task_id as "task_id: Uuid", workspace_id as "workspace_id: Uuid", title, message, task_url, escalate_at as "escalate_at!: DateTime<Utc>", acknowledged_at as "acknowledged_at: DateTime<Utc>", escalated_at as "escalated_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            data.event,
+            data.project_id,
+            data.task_id,
+            data.workspace_id,
+            data.title,
+            data.message,
+            data.task_url,
+            data.delay_minutes
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            NotificationEscalation,
+            r#"SELECT id as "id!: Uuid", event, project_id as "project_id: Uuid", task_id as "task_id: Uuid", workspace_id as "workspace_id: Uuid", title, message, task_url, escalate_at as "escalate_at!: DateTime<Utc>", acknowledged_at as "acknowledged_at: DateTime<Utc>", escalated_at as "escalated_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>"
+               FROM notification_escalations
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Pending escalation for a task, so repeated failures share one timer
+    pub async fn find_open_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            NotificationEscalation,
+            r#"SELECT id as "id!: Uuid", event, project_id as "project_id: Uuid", task_id as "task_id: Uuid", workspace_id as "workspace_id: Uuid", title, message, task_url, escalate_at as "escalate_at!: DateTime<Utc>", acknowledged_at as "acknowledged_at: DateTime<Utc>", escalated_at as "escalated_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>"
+               FROM notification_escalations
+               WHERE task_id = $1 AND acknowledged_at IS NULL AND escalated_at IS NULL
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Unacknowledged escalations whose deadline has passed
+    pub async fn find_due(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            NotificationEscalation,
+            r#"SELECT id as "id!: Uuid", event, project_id as "project_id: Uuid", task_id as "task_id: Uuid", workspace_id as "workspace_id: Uuid", title, message, task_url, escalate_at as "escalate_at!: DateTime<Utc>", acknowledged_at as "acknowledged_at: DateTime<Utc>", escalated_at as "escalated_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>"
+               FROM notification_escalations
+               WHERE acknowledged_at IS NULL
+                 AND escalated_at IS NULL
+                 AND escalate_at <= datetime('now', 'subsec')
+               ORDER BY escalate_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Mark as acknowledged; acknowledging twice keeps the first timestamp
+    pub async fn acknowledge(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            NotificationEscalation,
+            r#"UPDATE notification_escalations
+               SET acknowledged_at = COALESCE(acknowledged_at, datetime('now', 'subsec'))
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", event, project_id as "project_id: Uuid", task_id as "task_id: Uuid", workspace_id as "workspace_id: Uuid", title, message, task_url, escalate_at as "escalate_at!: DateTime<Utc>", acknowledged_at as "acknowledged_at: DateTime<Utc>", escalated_at as "escalated_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>""#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn mark_escalated(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE notification_escalations SET escalated_at = datetime('now', 'subsec') WHERE id = $1",
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
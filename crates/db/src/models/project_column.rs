@@ -0,0 +1,377 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{
+    task::{Task, TaskStatus},
+    task_subtask::TaskSubtask,
+};
+
+#[derive(Debug, Error)]
+pub enum ProjectColumnError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Column name cannot be empty")]
+    EmptyName,
+    #[error("WIP limit must be at least 1")]
+    InvalidWipLimit,
+    #[error("Only columns for done or cancelled tasks can count as done")]
+    NotFinished,
+    #[error("Column '{0}' is at its WIP limit of {1}")]
+    WipLimitReached(String, i64),
+    #[error("Column '{0}' still has tasks; move them to another column first")]
+    NotEmpty(String),
+    #[error("Tasks can only move between columns of their own project")]
+    DifferentProject,
+}
+
+/// A column on a project's kanban board
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectColumn {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    /// Columns are shown in ascending order
+    pub position: i64,
+    /// Status a task takes on when moved into the column; status changes made by the agent move
+    /// the task to the first column for its new status
+    pub status: TaskStatus,
+    /// Most tasks the column may hold; moves into a full column are rejected
+    pub wip_limit: Option<i64>,
+    pub counts_as_done: bool,
+    pub task_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateProjectColumn {
+    pub name: String,
+    /// Defaults to `done` for columns that count as done, otherwise `todo`
+    #[serde(default)]
+    #[ts(optional)]
+    pub status: Option<TaskStatus>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub wip_limit: Option<i64>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub counts_as_done: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateProjectColumn {
+    #[serde(default)]
+    #[ts(optional)]
+    pub name: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub status: Option<TaskStatus>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub wip_limit: Option<i64>,
+    /// Remove the WIP limit
+    #[serde(default)]
+    #[ts(optional)]
+    pub clear_wip_limit: Option<bool>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub counts_as_done: Option<bool>,
+}
+
+/// Check a column's settings, returning its trimmed name
+pub fn validate_column(
+    name: &str,
+    status: &TaskStatus,
+    wip_limit: Option<i64>,
+    counts_as_done: bool,
+) -> Result<String, ProjectColumnError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ProjectColumnError::EmptyName);
+    }
+    if wip_limit.is_some_and(|limit| limit < 1) {
+        return Err(ProjectColumnError::InvalidWipLimit);
+    }
+    if counts_as_done && !matches!(status, TaskStatus::Done | TaskStatus::Cancelled) {
+        return Err(ProjectColumnError::NotFinished);
+    }
+    Ok(name.to_string())
+}
+
+impl ProjectColumn {
+    /// Whether another task can't be moved in without going over the WIP limit
+    pub fn is_full(&self) -> bool {
+        self.wip_limit.is_some_and(|limit| self.task_count >= limit)
+    }
+
+    /// In board order
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectColumn,
+            r#"SELECT c.id as "id!: Uuid", c.project_id as "project_id!: Uuid", c.name, c.position as "position!: i64", c.status as "status!: TaskStatus", c.wip_limit as "wip_limit: i64", c.counts_as_done as "counts_as_done!: bool",
+                      (SELECT COUNT(*) FROM tasks t WHERE t.column_id = c.id AND t.archived_at IS NULL) as "task_count!: i64",
+                      c.created_at as "created_at!: DateTime<Utc>", c.updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_columns c
+               WHERE c.project_id = $1
+               ORDER BY c.position ASC, c.created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectColumn,
+            r#"SELECT c.id as "id!: Uuid", c.project_id as "project_id!: Uuid", c.name, c.position as "position!: i64", c.status as "status!: TaskStatus", c.wip_limit as "wip_limit: i64", c.counts_as_done as "counts_as_done!: bool",
+                      (SELECT COUNT(*) FROM tasks t WHERE t.column_id = c.id AND t.archived_at IS NULL) as "task_count!: i64",
+                      c.created_at as "created_at!: DateTime<Utc>", c.updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_columns c
+               WHERE c.id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// The first column, in board order, for tasks with `status`
+    pub async fn find_first_for_status(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        status: TaskStatus,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectColumn,
+            r#"SELECT c.id as "id!: Uuid", c.project_id as "project_id!: Uuid", c.name, c.position as "position!: i64", c.status as "status!: TaskStatus", c.wip_limit as "wip_limit: i64", c.counts_as_done as "counts_as_done!: bool",
+                      (SELECT COUNT(*) FROM tasks t WHERE t.column_id = c.id AND t.archived_at IS NULL) as "task_count!: i64",
+                      c.created_at as "created_at!: DateTime<Utc>", c.updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_columns c
+               WHERE c.project_id = $1 AND c.status = $2
+               ORDER BY c.position ASC, c.created_at ASC
+               LIMIT 1"#,
+            project_id,
+            status
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Add a column at the end of the board
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateProjectColumn,
+    ) -> Result<Self, ProjectColumnError> {
+        let counts_as_done = data.counts_as_done.unwrap_or(false);
+        let status = data.status.clone().unwrap_or(if counts_as_done {
+            TaskStatus::Done
+        } else {
+            TaskStatus::Todo
+        });
+        let name = validate_column(&data.name, &status, data.wip_limit, counts_as_done)?;
+
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO project_columns (id, project_id, name, position, status, wip_limit, counts_as_done)
+               VALUES ($1, $2, $3,
+                       (SELECT COALESCE(MAX(position) + 1, 0) FROM project_columns WHERE project_id = $2),
+                       $4, $5, $6)"#,
+            id,
+            project_id,
+            name,
+            status,
+            data.wip_limit,
+            counts_as_done
+        )
+        .execute(pool)
+        .await?;
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(ProjectColumnError::Database(sqlx::Error::RowNotFound))
+    }
+
+    /// Changing the status also updates the tasks already in the column
+    pub async fn update(
+        pool: &SqlitePool,
+        existing: &ProjectColumn,
+        data: &UpdateProjectColumn,
+    ) -> Result<Self, ProjectColumnError> {
+        let status = data.status.clone().unwrap_or(existing.status.clone());
+        let wip_limit = if data.clear_wip_limit.unwrap_or(false) {
+            None
+        } else {
+            data.wip_limit.or(existing.wip_limit)
+        };
+        let counts_as_done = data.counts_as_done.unwrap_or(existing.counts_as_done);
+        let name = validate_column(
+            data.name.as_deref().unwrap_or(&existing.name),
+            &status,
+            wip_limit,
+            counts_as_done,
+        )?;
+
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            r#"UPDATE project_columns
+               SET name = $2, status = $3, wip_limit = $4, counts_as_done = $5,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            existing.id,
+            name,
+            status,
+            wip_limit,
+            counts_as_done
+        )
+        .execute(&mut *tx)
+        .await?;
+        let moved_task_ids = if status != existing.status {
+            sqlx::query_scalar!(
+                r#"UPDATE tasks SET status = $2, updated_at = CURRENT_TIMESTAMP
+                   WHERE column_id = $1
+                   RETURNING id as "id!: Uuid""#,
+                existing.id,
+                status
+            )
+            .fetch_all(&mut *tx)
+            .await?
+        } else {
+            Vec::new()
+        };
+        tx.commit().await?;
+
+        for task_id in moved_task_ids {
+            TaskSubtask::roll_up(pool, task_id).await?;
+        }
+        Self::find_by_id(pool, existing.id)
+            .await?
+            .ok_or(ProjectColumnError::Database(sqlx::Error::RowNotFound))
+    }
+
+    /// Delete an empty column
+    pub async fn delete(
+        pool: &SqlitePool,
+        column: &ProjectColumn,
+    ) -> Result<(), ProjectColumnError> {
+        if column.task_count > 0 {
+            return Err(ProjectColumnError::NotEmpty(column.name.clone()));
+        }
+        sqlx::query!("DELETE FROM project_columns WHERE id = $1", column.id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Put the project's columns in the order given; columns not listed keep their relative
+    /// order after the listed ones
+    pub async fn reorder(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        column_ids: &[Uuid],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        let offset = column_ids.len() as i64;
+        sqlx::query!(
+            "UPDATE project_columns SET position = position + $2 WHERE project_id = $1",
+            project_id,
+            offset
+        )
+        .execute(&mut *tx)
+        .await?;
+        for (position, column_id) in column_ids.iter().enumerate() {
+            let position = position as i64;
+            sqlx::query!(
+                "UPDATE project_columns SET position = $3 WHERE project_id = $1 AND id = $2",
+                project_id,
+                column_id,
+                position
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Move a task into this column, setting its status to the column's. A full column is only
+    /// entered with `ignore_wip_limit`.
+    pub async fn move_task(
+        &self,
+        pool: &SqlitePool,
+        task: &Task,
+        ignore_wip_limit: bool,
+    ) -> Result<(), ProjectColumnError> {
+        if task.project_id != self.project_id {
+            return Err(ProjectColumnError::DifferentProject);
+        }
+        if task.column_id == Some(self.id) {
+            return Ok(());
+        }
+        if self.is_full() && !ignore_wip_limit {
+            return Err(ProjectColumnError::WipLimitReached(
+                self.name.clone(),
+                self.wip_limit.unwrap_or_default(),
+            ));
+        }
+        sqlx::query!(
+            "UPDATE tasks SET column_id = $2, status = $3, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+            task.id,
+            self.id,
+            self.status
+        )
+        .execute(pool)
+        .await?;
+        TaskSubtask::roll_up(pool, task.id).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_column() {
+        assert_eq!(
+            validate_column("  QA  ", &TaskStatus::InReview, Some(3), false).unwrap(),
+            "QA"
+        );
+        assert!(matches!(
+            validate_column(" ", &TaskStatus::Todo, None, false),
+            Err(ProjectColumnError::EmptyName)
+        ));
+        assert!(matches!(
+            validate_column("Doing", &TaskStatus::InProgress, Some(0), false),
+            Err(ProjectColumnError::InvalidWipLimit)
+        ));
+        assert!(matches!(
+            validate_column("Shipped", &TaskStatus::InReview, None, true),
+            Err(ProjectColumnError::NotFinished)
+        ));
+        assert!(validate_column("Won't do", &TaskStatus::Cancelled, None, true).is_ok());
+    }
+
+    #[test]
+    fn test_is_full() {
+        let column = |wip_limit, task_count| ProjectColumn {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            name: "Doing".to_string(),
+            position: 1,
+            status: TaskStatus::InProgress,
+            wip_limit,
+            counts_as_done: false,
+            task_count,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        assert!(!column(None, 100).is_full());
+        assert!(!column(Some(3), 2).is_full());
+        assert!(column(Some(3), 3).is_full());
+    }
+}
@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+/// A browser registered to receive Web Push notifications
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct PushSubscription {
+    pub id: Uuid,
+    pub endpoint: String,
+    /// Browser's P-256 public key, base64url encoded
+    pub p256dh: String,
+    /// Browser's authentication secret, base64url encoded
+    pub auth: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PushSubscription {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PushSubscription,
+            r#"SELECT id as "id!: Uuid", endpoint, p256dh, auth, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM push_subscriptions
+               ORDER BY created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Register an endpoint, refreshing its keys if it is already known
+    pub async fn upsert(
+        pool: &SqlitePool,
+        endpoint: &str,
+        p256dh: &str,
+        auth: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            PushSubscription,
+            r#"INSERT INTO push_subscriptions (id, endpoint, p256dh, auth)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(endpoint) DO UPDATE SET
+                   p256dh = excluded.p256dh,
+                   auth = excluded.auth,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid", endpoint, p256dh, auth, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            endpoint,
+            p256dh,
+            auth
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete_by_endpoint(pool: &SqlitePool, endpoint: &str) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM push_subscriptions WHERE endpoint = $1",
+            endpoint
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,156 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskPriority;
+
+/// An attempt waiting for a free slot under the concurrency limits. Its workspace exists, but
+/// nothing has run in it yet.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct QueuedAttempt {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub task_title: String,
+    /// The task's current priority, which is the band the attempt waits in
+    pub priority: TaskPriority,
+    #[ts(type = "ExecutorProfileId")]
+    pub executor_profile_id: Json<ExecutorProfileId>,
+    /// Order within the priority band, ascending
+    pub position: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Put attempts in the order they start: the most urgent band first, then by position
+pub fn queue_order(attempts: &mut [QueuedAttempt]) {
+    attempts.sort_by_key(|attempt| (attempt.priority, attempt.position));
+}
+
+impl QueuedAttempt {
+    /// Every waiting attempt, or those of one project, in the order they start
+    pub async fn find_all(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let mut attempts = sqlx::query_as!(
+            QueuedAttempt,
+            r#"SELECT q.id as "id!: Uuid", q.workspace_id as "workspace_id!: Uuid", t.id as "task_id!: Uuid", t.project_id as "project_id!: Uuid", t.title as "task_title!", t.priority as "priority!: TaskPriority", q.executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>", q.position, q.created_at as "created_at!: DateTime<Utc>"
+               FROM queued_attempts q
+               JOIN workspaces w ON w.id = q.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE ($1 IS NULL OR t.project_id = $1)
+               ORDER BY q.position ASC, q.created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+        queue_order(&mut attempts);
+        Ok(attempts)
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            QueuedAttempt,
+            r#"SELECT q.id as "id!: Uuid", q.workspace_id as "workspace_id!: Uuid", t.id as "task_id!: Uuid", t.project_id as "project_id!: Uuid", t.title as "task_title!", t.priority as "priority!: TaskPriority", q.executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>", q.position, q.created_at as "created_at!: DateTime<Utc>"
+               FROM queued_attempts q
+               JOIN workspaces w ON w.id = q.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE q.id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Add an attempt to the end of its priority band
+    pub async fn create(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        executor_profile_id: &ExecutorProfileId,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let executor_profile_id = Json(executor_profile_id);
+        sqlx::query!(
+            r#"INSERT INTO queued_attempts (id, workspace_id, executor_profile_id, position)
+               VALUES ($1, $2, $3, (SELECT COALESCE(MAX(position) + 1, 0) FROM queued_attempts))"#,
+            id,
+            workspace_id,
+            executor_profile_id
+        )
+        .execute(pool)
+        .await?;
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM queued_attempts WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Put the attempts in the order given; attempts left out keep their relative order after
+    /// the listed ones. The order only applies within a priority band.
+    pub async fn reorder(pool: &SqlitePool, ids: &[Uuid]) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        let offset = ids.len() as i64;
+        sqlx::query!(
+            "UPDATE queued_attempts SET position = position + $1",
+            offset
+        )
+        .execute(&mut *tx)
+        .await?;
+        for (position, id) in ids.iter().enumerate() {
+            let position = position as i64;
+            sqlx::query!(
+                "UPDATE queued_attempts SET position = $2 WHERE id = $1",
+                id,
+                position
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+
+    #[test]
+    fn test_queue_order() {
+        let attempt = |title: &str, priority, position| QueuedAttempt {
+            id: Uuid::new_v4(),
+            workspace_id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            project_id: Uuid::nil(),
+            task_title: title.to_string(),
+            priority,
+            executor_profile_id: Json(ExecutorProfileId::new(BaseCodingAgent::ClaudeCode)),
+            position,
+            created_at: Utc::now(),
+        };
+        let mut attempts = vec![
+            attempt("Docs", TaskPriority::Low, 0),
+            attempt("Refactor", TaskPriority::Normal, 3),
+            attempt("Outage", TaskPriority::Urgent, 4),
+            attempt("Feature", TaskPriority::Normal, 1),
+            attempt("Bug", TaskPriority::High, 2),
+        ];
+        queue_order(&mut attempts);
+        let titles: Vec<&str> = attempts
+            .iter()
+            .map(|attempt| attempt.task_title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["Outage", "Bug", "Feature", "Refactor", "Docs"]);
+    }
+}
@@ -0,0 +1,213 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::{TaskPriority, TaskStatus};
+
+/// Order of a task list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS)]
+#[sqlx(type_name = "task_sort", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSort {
+    /// Newest first
+    CreatedAt,
+    /// Most urgent first, newest first within a priority
+    Priority,
+}
+
+/// Conditions a task must meet to be listed; unset ones match every task
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct ViewFilters {
+    /// Only tasks carrying all of these labels
+    pub labels: Vec<String>,
+    /// Only tasks in any of these statuses
+    pub statuses: Vec<TaskStatus>,
+    pub priority: Option<TaskPriority>,
+    /// Only tasks assigned to this person or agent
+    pub assignee: Option<String>,
+    /// Only tasks whose latest attempt ran on this executor, e.g. `CLAUDE_CODE`
+    pub executor: Option<String>,
+    /// Only tasks with this text in the title or description, ignoring case
+    pub text: Option<String>,
+}
+
+impl ViewFilters {
+    pub fn matches_text(&self, title: &str, description: Option<&str>) -> bool {
+        let Some(text) = self
+            .text
+            .as_deref()
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+        else {
+            return true;
+        };
+        let text = text.to_lowercase();
+        title.to_lowercase().contains(&text)
+            || description.is_some_and(|description| description.to_lowercase().contains(&text))
+    }
+
+    pub fn matches_executor(&self, executor: &str) -> bool {
+        match self.executor.as_deref().map(str::trim) {
+            Some(wanted) if !wanted.is_empty() => {
+                let normalize = |name: &str| name.replace('-', "_").to_ascii_uppercase();
+                normalize(wanted) == normalize(executor)
+            }
+            _ => true,
+        }
+    }
+}
+
+/// A named set of board filters, sort and columns, shared by everyone using the project's board
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct SavedView {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    #[ts(type = "ViewFilters")]
+    pub filters: Json<ViewFilters>,
+    pub sort: Option<TaskSort>,
+    /// Board columns shown, in order; empty shows all
+    #[ts(type = "Array<string>")]
+    pub columns: Json<Vec<Uuid>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateSavedView {
+    pub name: String,
+    #[serde(default)]
+    pub filters: ViewFilters,
+    pub sort: Option<TaskSort>,
+    #[serde(default)]
+    pub columns: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateSavedView {
+    pub name: Option<String>,
+    pub filters: Option<ViewFilters>,
+    pub sort: Option<TaskSort>,
+    pub clear_sort: Option<bool>,
+    pub columns: Option<Vec<Uuid>>,
+}
+
+impl SavedView {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SavedView,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, filters as "filters!: Json<ViewFilters>", sort as "sort: TaskSort", columns as "columns!: Json<Vec<Uuid>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM saved_views
+               WHERE project_id = $1
+               ORDER BY name COLLATE NOCASE ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SavedView,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, filters as "filters!: Json<ViewFilters>", sort as "sort: TaskSort", columns as "columns!: Json<Vec<Uuid>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM saved_views
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateSavedView,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let name = data.name.trim();
+        let filters = Json(&data.filters);
+        let columns = Json(&data.columns);
+        sqlx::query_as!(
+            SavedView,
+            r#"INSERT INTO saved_views (id, project_id, name, filters, sort, columns)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, filters as "filters!: Json<ViewFilters>", sort as "sort: TaskSort", columns as "columns!: Json<Vec<Uuid>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            name,
+            filters,
+            data.sort,
+            columns
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        existing: &SavedView,
+        data: &UpdateSavedView,
+    ) -> Result<Self, sqlx::Error> {
+        let name = data
+            .name
+            .as_deref()
+            .map_or(existing.name.as_str(), str::trim);
+        let filters = Json(data.filters.as_ref().unwrap_or(&existing.filters.0));
+        let sort = if data.clear_sort.unwrap_or(false) {
+            None
+        } else {
+            data.sort.or(existing.sort)
+        };
+        let columns = Json(data.columns.as_ref().unwrap_or(&existing.columns.0));
+        sqlx::query_as!(
+            SavedView,
+            r#"UPDATE saved_views
+               SET name = $2, filters = $3, sort = $4, columns = $5, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, filters as "filters!: Json<ViewFilters>", sort as "sort: TaskSort", columns as "columns!: Json<Vec<Uuid>>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            existing.id,
+            name,
+            filters,
+            sort,
+            columns
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM saved_views WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_and_executor_filters() {
+        let filters = ViewFilters {
+            text: Some(" Login ".to_string()),
+            executor: Some("claude-code".to_string()),
+            ..Default::default()
+        };
+        assert!(filters.matches_text("Fix login redirect", None));
+        assert!(filters.matches_text("Flaky test", Some("Fails on the LOGIN page")));
+        assert!(!filters.matches_text("Flaky test", None));
+        assert!(filters.matches_executor("CLAUDE_CODE"));
+        assert!(!filters.matches_executor("AMP"));
+
+        let unset = ViewFilters::default();
+        assert!(unset.matches_text("Anything", None));
+        assert!(unset.matches_executor(""));
+    }
+}
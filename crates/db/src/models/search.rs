@@ -0,0 +1,242 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchResultKind {
+    Task,
+    Log,
+    Comment,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct SearchResult {
+    pub kind: SearchResultKind,
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub task_title: String,
+    /// Archived tasks are off the board but still turn up in search
+    pub task_archived: bool,
+    /// Attempt and process the matching log line came from; only set for `log` results
+    pub workspace_id: Option<Uuid>,
+    pub execution_process_id: Option<Uuid>,
+    /// Only set for `comment` results
+    pub comment_id: Option<Uuid>,
+    /// Matching text with the matched terms wrapped in `**`
+    pub snippet: String,
+    /// bm25 score; lower is a better match
+    pub rank: f64,
+}
+
+/// Turn free text into an FTS5 query matching every word as a prefix, so user input can't
+/// trip over FTS5 syntax. `None` when there is nothing to search for.
+pub fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|term| term.replace('"', ""))
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{term}\"*"))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Best-ranked first, keeping only the best match per task, comment and log-producing process
+pub fn merge_results(
+    results: impl IntoIterator<Item = SearchResult>,
+    limit: usize,
+) -> Vec<SearchResult> {
+    let mut results: Vec<SearchResult> = results.into_iter().collect();
+    results.sort_by(|a, b| a.rank.total_cmp(&b.rank));
+    let mut seen = HashSet::new();
+    results
+        .into_iter()
+        .filter(|result| {
+            seen.insert((
+                result.kind,
+                result.task_id,
+                result.execution_process_id,
+                result.comment_id,
+            ))
+        })
+        .take(limit)
+        .collect()
+}
+
+impl SearchResult {
+    /// Tasks, comments and execution logs matching every word of `query`, optionally within one
+    /// project
+    pub async fn search(
+        pool: &SqlitePool,
+        query: &str,
+        project_id: Option<Uuid>,
+        limit: usize,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let Some(query) = fts_query(query) else {
+            return Ok(Vec::new());
+        };
+        let task_limit = limit as i64;
+        // Noisy processes can match on many lines; fetch extra so merging still fills the page
+        let log_limit = task_limit * 5;
+
+        let tasks = sqlx::query!(
+            r#"SELECT
+                 ts.task_id as "task_id!: Uuid",
+                 t.project_id as "project_id!: Uuid",
+                 t.title as "task_title!: String",
+                 t.archived_at IS NOT NULL as "task_archived!: bool",
+                 snippet(task_search, -1, '**', '**', '…', 16) as "snippet!: String",
+                 ts.rank as "rank!: f64"
+               FROM task_search ts
+               JOIN tasks t ON t.id = ts.task_id
+               WHERE task_search MATCH $1
+                 AND ($2 IS NULL OR t.project_id = $2)
+               ORDER BY ts.rank
+               LIMIT $3"#,
+            query,
+            project_id,
+            task_limit
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|row| SearchResult {
+            kind: SearchResultKind::Task,
+            task_id: row.task_id,
+            project_id: row.project_id,
+            task_title: row.task_title,
+            task_archived: row.task_archived,
+            workspace_id: None,
+            execution_process_id: None,
+            comment_id: None,
+            snippet: row.snippet,
+            rank: row.rank,
+        });
+
+        let comments = sqlx::query!(
+            r#"SELECT
+                 cs.comment_id as "comment_id!: Uuid",
+                 cs.task_id as "task_id!: Uuid",
+                 t.project_id as "project_id!: Uuid",
+                 t.title as "task_title!: String",
+                 t.archived_at IS NOT NULL as "task_archived!: bool",
+                 snippet(comment_search, 2, '**', '**', '…', 16) as "snippet!: String",
+                 cs.rank as "rank!: f64"
+               FROM comment_search cs
+               JOIN tasks t ON t.id = cs.task_id
+               WHERE comment_search MATCH $1
+                 AND ($2 IS NULL OR t.project_id = $2)
+               ORDER BY cs.rank
+               LIMIT $3"#,
+            query,
+            project_id,
+            task_limit
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|row| SearchResult {
+            kind: SearchResultKind::Comment,
+            task_id: row.task_id,
+            project_id: row.project_id,
+            task_title: row.task_title,
+            task_archived: row.task_archived,
+            workspace_id: None,
+            execution_process_id: None,
+            comment_id: Some(row.comment_id),
+            snippet: row.snippet,
+            rank: row.rank,
+        });
+
+        let logs = sqlx::query!(
+            r#"SELECT
+                 w.task_id as "task_id!: Uuid",
+                 t.project_id as "project_id!: Uuid",
+                 t.title as "task_title!: String",
+                 t.archived_at IS NOT NULL as "task_archived!: bool",
+                 w.id as "workspace_id!: Uuid",
+                 ls.execution_id as "execution_id!: Uuid",
+                 snippet(log_search, 1, '**', '**', '…', 16) as "snippet!: String",
+                 ls.rank as "rank!: f64"
+               FROM log_search ls
+               JOIN execution_processes ep ON ep.id = ls.execution_id
+               JOIN sessions s ON s.id = ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE log_search MATCH $1
+                 AND ($2 IS NULL OR t.project_id = $2)
+               ORDER BY ls.rank
+               LIMIT $3"#,
+            query,
+            project_id,
+            log_limit
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|row| SearchResult {
+            kind: SearchResultKind::Log,
+            task_id: row.task_id,
+            project_id: row.project_id,
+            task_title: row.task_title,
+            task_archived: row.task_archived,
+            workspace_id: Some(row.workspace_id),
+            execution_process_id: Some(row.execution_id),
+            comment_id: None,
+            snippet: row.snippet,
+            rank: row.rank,
+        });
+
+        Ok(merge_results(tasks.chain(comments).chain(logs), limit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fts_query() {
+        assert_eq!(
+            fts_query("login  redirect"),
+            Some("\"login\"* \"redirect\"*".to_string())
+        );
+        assert_eq!(
+            fts_query("say \"NEAR(a b)\" OR"),
+            Some("\"say\"* \"NEAR(a\"* \"b)\"* \"OR\"*".to_string())
+        );
+        assert_eq!(fts_query("  \"\" "), None);
+    }
+
+    #[test]
+    fn test_merge_results() {
+        let task_id = Uuid::new_v4();
+        let process_id = Uuid::new_v4();
+        let result = |kind, execution_process_id, rank| SearchResult {
+            kind,
+            task_id,
+            project_id: Uuid::new_v4(),
+            task_title: "Fix login".to_string(),
+            task_archived: false,
+            workspace_id: None,
+            execution_process_id,
+            comment_id: None,
+            snippet: String::new(),
+            rank,
+        };
+        let merged = merge_results(
+            [
+                result(SearchResultKind::Log, Some(process_id), -1.0),
+                result(SearchResultKind::Task, None, -2.0),
+                result(SearchResultKind::Log, Some(process_id), -3.0),
+                result(SearchResultKind::Log, Some(Uuid::new_v4()), -0.5),
+            ],
+            2,
+        );
+        let ranks: Vec<f64> = merged.iter().map(|r| r.rank).collect();
+        assert_eq!(ranks, vec![-3.0, -2.0]);
+    }
+}
@@ -66,7 +66,7 @@ impl Session {
         .await
     }
 
-    /// Find the latest session for a workspace
+    /// Find the latest session for a workspace, other than a reviewer's
     pub async fn find_latest_by_workspace_id(
         pool: &SqlitePool,
         workspace_id: Uuid,
@@ -80,6 +80,8 @@ impl Session {
                       updated_at AS "updated_at!: DateTime<Utc>"
                FROM sessions
                WHERE workspace_id = $1
+                 AND id NOT IN (SELECT reviewer_session_id FROM attempt_reviewers
+                                WHERE reviewer_session_id IS NOT NULL)
                ORDER BY created_at DESC
                LIMIT 1"#,
             workspace_id
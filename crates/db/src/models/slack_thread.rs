@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+/// The first Slack message posted for a workspace, used as the parent of follow-up replies
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SlackThread {
+    pub workspace_id: Uuid,
+    pub channel: String,
+    pub thread_ts: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SlackThread {
+    pub async fn find(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        channel: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            SlackThread,
+            r#"SELECT workspace_id as "workspace_id!: Uuid", channel, thread_ts, created_at as "created_at!: DateTime<Utc>"
+               FROM slack_threads
+               WHERE workspace_id = $1 AND channel = $2"#,
+            workspace_id,
+            channel
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Remember the parent message, keeping the existing one if another notification won the race
+    pub async fn create(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        channel: &str,
+        thread_ts: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT OR IGNORE INTO slack_threads (workspace_id, channel, thread_ts)
+               VALUES ($1, $2, $3)"#,
+            workspace_id,
+            channel,
+            thread_ts
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskWithAttemptStatus;
+
+/// What a board's swimlanes group tasks by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum SwimlaneKey {
+    Label,
+    Assignee,
+    Epic,
+}
+
+impl SwimlaneKey {
+    /// Name of the lane holding tasks without a label, assignee or epic
+    pub fn none_name(self) -> &'static str {
+        match self {
+            SwimlaneKey::Label => "No label",
+            SwimlaneKey::Assignee => "Unassigned",
+            SwimlaneKey::Epic => "No epic",
+        }
+    }
+}
+
+/// A task's place in one lane
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaneMembership {
+    pub task_id: Uuid,
+    /// Label or epic id, or assignee name in lowercase
+    pub key: String,
+    pub name: String,
+}
+
+/// One row of the board. A task with several labels, assignees or epics shows in each of their
+/// lanes.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct Swimlane {
+    /// Label or epic id, or assignee name in lowercase; `None` for the lane of tasks without one
+    pub key: Option<String>,
+    pub name: String,
+    pub tasks: Vec<TaskWithAttemptStatus>,
+}
+
+/// Lanes in name order, then the lane of tasks without one, each keeping the order of `task_ids`.
+/// Lanes without tasks are left out.
+pub fn group_task_ids(
+    key: SwimlaneKey,
+    task_ids: &[Uuid],
+    memberships: &[LaneMembership],
+) -> Vec<(Option<String>, String, Vec<Uuid>)> {
+    let mut lanes_by_task: HashMap<Uuid, Vec<&LaneMembership>> = HashMap::new();
+    for membership in memberships {
+        lanes_by_task
+            .entry(membership.task_id)
+            .or_default()
+            .push(membership);
+    }
+
+    let mut lanes: Vec<(Option<String>, String, Vec<Uuid>)> = Vec::new();
+    let mut unlaned = Vec::new();
+    for task_id in task_ids {
+        let Some(memberships) = lanes_by_task.get(task_id) else {
+            unlaned.push(*task_id);
+            continue;
+        };
+        for membership in memberships {
+            match lanes
+                .iter_mut()
+                .find(|(key, _, _)| key.as_deref() == Some(membership.key.as_str()))
+            {
+                Some((_, _, ids)) if ids.contains(task_id) => {}
+                Some((_, _, ids)) => ids.push(*task_id),
+                None => lanes.push((
+                    Some(membership.key.clone()),
+                    membership.name.clone(),
+                    vec![*task_id],
+                )),
+            }
+        }
+    }
+    lanes.sort_by_cached_key(|(_, name, _)| name.to_lowercase());
+    if !unlaned.is_empty() {
+        lanes.push((None, key.none_name().to_string(), unlaned));
+    }
+    lanes
+}
+
+/// Bucket the tasks, already filtered and sorted, into swimlanes
+pub async fn swimlanes(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    key: SwimlaneKey,
+    tasks: Vec<TaskWithAttemptStatus>,
+) -> Result<Vec<Swimlane>, sqlx::Error> {
+    let memberships = find_memberships(pool, project_id, key).await?;
+    let task_ids: Vec<Uuid> = tasks.iter().map(|task| task.id).collect();
+    let by_id: HashMap<Uuid, TaskWithAttemptStatus> =
+        tasks.into_iter().map(|task| (task.id, task)).collect();
+    Ok(group_task_ids(key, &task_ids, &memberships)
+        .into_iter()
+        .map(|(key, name, ids)| Swimlane {
+            key,
+            name,
+            tasks: ids.iter().filter_map(|id| by_id.get(id).cloned()).collect(),
+        })
+        .collect())
+}
+
+async fn find_memberships(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    key: SwimlaneKey,
+) -> Result<Vec<LaneMembership>, sqlx::Error> {
+    let memberships = match key {
+        SwimlaneKey::Label => sqlx::query!(
+            r#"SELECT tl.task_id as "task_id!: Uuid", l.id as "label_id!: Uuid", l.name
+               FROM task_labels tl
+               JOIN labels l ON l.id = tl.label_id
+               JOIN tasks t ON t.id = tl.task_id
+               WHERE t.project_id = $1"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|row| LaneMembership {
+            task_id: row.task_id,
+            key: row.label_id.to_string(),
+            name: row.name,
+        })
+        .collect(),
+        SwimlaneKey::Assignee => sqlx::query!(
+            r#"SELECT ta.task_id as "task_id!: Uuid", ta.name
+               FROM task_assignees ta
+               JOIN tasks t ON t.id = ta.task_id
+               WHERE t.project_id = $1"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|row| LaneMembership {
+            task_id: row.task_id,
+            key: row.name.to_lowercase(),
+            name: row.name,
+        })
+        .collect(),
+        SwimlaneKey::Epic => sqlx::query!(
+            r#"SELECT et.task_id as "task_id!: Uuid", e.id as "epic_id!: Uuid", e.name
+               FROM epic_tasks et
+               JOIN epics e ON e.id = et.epic_id
+               JOIN tasks t ON t.id = et.task_id
+               WHERE t.project_id = $1"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|row| LaneMembership {
+            task_id: row.task_id,
+            key: row.epic_id.to_string(),
+            name: row.name,
+        })
+        .collect(),
+    };
+    Ok(memberships)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_task_ids() {
+        let tasks: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let membership = |task: usize, name: &str| LaneMembership {
+            task_id: tasks[task],
+            key: name.to_lowercase(),
+            name: name.to_string(),
+        };
+        let memberships = vec![
+            membership(2, "bob"),
+            membership(0, "Alice"),
+            membership(2, "alice"),
+            membership(3, "Bob"),
+        ];
+
+        let lanes = group_task_ids(SwimlaneKey::Assignee, &tasks, &memberships);
+        assert_eq!(
+            lanes,
+            vec![
+                (
+                    Some("alice".to_string()),
+                    "Alice".to_string(),
+                    vec![tasks[0], tasks[2]]
+                ),
+                (
+                    Some("bob".to_string()),
+                    "bob".to_string(),
+                    vec![tasks[2], tasks[3]]
+                ),
+                (None, "Unassigned".to_string(), vec![tasks[1]]),
+            ]
+        );
+        assert!(group_task_ids(SwimlaneKey::Epic, &[], &memberships).is_empty());
+    }
+}
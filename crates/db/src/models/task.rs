@@ -1,11 +1,12 @@
 use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
 use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
 use strum_macros::{Display, EnumString};
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::{project::Project, workspace::Workspace};
+use super::{project::Project, task_subtask::TaskSubtask, workspace::Workspace};
 
 #[derive(
     Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display, Default,
@@ -22,15 +23,50 @@ pub enum TaskStatus {
     Cancelled,
 }
 
+/// How urgently a task should run; variants are declared most urgent first, so sorting
+/// ascending puts urgent tasks at the front
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    TS,
+    EnumString,
+    Display,
+    Default,
+)]
+#[sqlx(type_name = "task_priority", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum TaskPriority {
+    Urgent,
+    High,
+    #[default]
+    Normal,
+    Low,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct Task {
     pub id: Uuid,
     pub project_id: Uuid, // Foreign key to Project
+    pub number: i64,      // Per-project, written as `#number` in descriptions
     pub title: String,
     pub description: Option<String>,
     pub status: TaskStatus,
+    pub column_id: Option<Uuid>, // Board column; follows status unless moved explicitly
+    pub priority: TaskPriority,
+    pub due_at: Option<DateTime<Utc>>,
     pub parent_workspace_id: Option<Uuid>, // Foreign key to parent Workspace
     pub shared_task_id: Option<Uuid>,
+    pub archived_at: Option<DateTime<Utc>>, // Set while archived and off the board
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -41,8 +77,14 @@ pub struct TaskWithAttemptStatus {
     #[ts(flatten)]
     pub task: Task,
     pub has_in_progress_attempt: bool,
+    /// An attempt's agent is paused; `has_in_progress_attempt` stays set meanwhile
+    pub has_paused_attempt: bool,
     pub last_attempt_failed: bool,
     pub executor: String,
+    /// Checklist items on the task
+    pub checklist_total: i64,
+    /// Of those, items that are ticked
+    pub checklist_done: i64,
 }
 
 impl std::ops::Deref for TaskWithAttemptStatus {
@@ -71,9 +113,20 @@ pub struct CreateTask {
     pub title: String,
     pub description: Option<String>,
     pub status: Option<TaskStatus>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub priority: Option<TaskPriority>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub due_at: Option<DateTime<Utc>>,
     pub parent_workspace_id: Option<Uuid>,
     pub image_ids: Option<Vec<Uuid>>,
     pub shared_task_id: Option<Uuid>,
+    /// Executor profile, and optionally model, for the task's attempts; overrides the one an
+    /// assignment rule would pick
+    #[serde(default)]
+    #[ts(optional)]
+    pub executor_profile_id: Option<ExecutorProfileId>,
 }
 
 impl CreateTask {
@@ -87,9 +140,12 @@ impl CreateTask {
             title,
             description,
             status: Some(TaskStatus::Todo),
+            priority: None,
+            due_at: None,
             parent_workspace_id: None,
             image_ids: None,
             shared_task_id: None,
+            executor_profile_id: None,
         }
     }
 
@@ -105,9 +161,12 @@ impl CreateTask {
             title,
             description,
             status: Some(status),
+            priority: None,
+            due_at: None,
             parent_workspace_id: None,
             image_ids: None,
             shared_task_id: Some(shared_task_id),
+            executor_profile_id: None,
         }
     }
 }
@@ -117,10 +176,40 @@ pub struct UpdateTask {
     pub title: Option<String>,
     pub description: Option<String>,
     pub status: Option<TaskStatus>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub priority: Option<TaskPriority>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub due_at: Option<DateTime<Utc>>,
+    /// Remove the due date
+    #[serde(default)]
+    #[ts(optional)]
+    pub clear_due_at: Option<bool>,
     pub parent_workspace_id: Option<Uuid>,
     pub image_ids: Option<Vec<Uuid>>,
 }
 
+/// Unfinished tasks past their due date, and those due within the requested window
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct DueTasks {
+    /// Earliest due first
+    pub overdue: Vec<Task>,
+    /// Earliest due first
+    pub due_soon: Vec<Task>,
+}
+
+/// Task activity over a summary period
+#[derive(Debug, Clone, Default)]
+pub struct TaskActivitySummary {
+    pub completed: i64,
+    pub failed: i64,
+    /// Tasks currently in review, regardless of when they got there
+    pub awaiting_review: i64,
+    /// Reported and estimated API spend of executions that finished in the period
+    pub cost_usd: f64,
+}
+
 impl Task {
     pub fn to_prompt(&self) -> String {
         if let Some(description) = self.description.as_ref().filter(|d| !d.trim().is_empty()) {
@@ -134,19 +223,26 @@ impl Task {
         Project::find_by_id(pool, self.project_id).await
     }
 
+    /// Newest first; archived tasks are left out unless `include_archived`
     pub async fn find_by_project_id_with_attempt_status(
         pool: &SqlitePool,
         project_id: Uuid,
+        include_archived: bool,
     ) -> Result<Vec<TaskWithAttemptStatus>, sqlx::Error> {
         let records = sqlx::query!(
             r#"SELECT
   t.id                            AS "id!: Uuid",
   t.project_id                    AS "project_id!: Uuid",
+  t.number,
   t.title,
   t.description,
   t.status                        AS "status!: TaskStatus",
+  t.column_id                     AS "column_id: Uuid",
+  t.priority                      AS "priority!: TaskPriority",
+  t.due_at                        AS "due_at: DateTime<Utc>",
   t.parent_workspace_id           AS "parent_workspace_id: Uuid",
   t.shared_task_id                AS "shared_task_id: Uuid",
+  t.archived_at                   AS "archived_at: DateTime<Utc>",
   t.created_at                    AS "created_at!: DateTime<Utc>",
   t.updated_at                    AS "updated_at!: DateTime<Utc>",
 
@@ -161,6 +257,17 @@ impl Task {
      LIMIT 1
   ) THEN 1 ELSE 0 END            AS "has_in_progress_attempt!: i64",
 
+  CASE WHEN EXISTS (
+    SELECT 1
+      FROM workspaces w
+      JOIN sessions s ON s.workspace_id = w.id
+      JOIN execution_processes ep ON ep.session_id = s.id
+      JOIN execution_pauses p ON p.execution_process_id = ep.id
+     WHERE w.task_id       = t.id
+       AND ep.status        = 'running'
+     LIMIT 1
+  ) THEN 1 ELSE 0 END            AS "has_paused_attempt!: i64",
+
   CASE WHEN (
     SELECT ep.status
       FROM workspaces w
@@ -179,12 +286,19 @@ impl Task {
       WHERE w.task_id = t.id
      ORDER BY s.created_at DESC
       LIMIT 1
-    )                               AS "executor!: String"
+    )                               AS "executor!: String",
+
+  ( SELECT COUNT(*) FROM task_checklist_items ci WHERE ci.task_id = t.id )
+                                  AS "checklist_total!: i64",
+  ( SELECT COUNT(*) FROM task_checklist_items ci WHERE ci.task_id = t.id AND ci.done )
+                                  AS "checklist_done!: i64"
 
 FROM tasks t
 WHERE t.project_id = $1
+  AND ($2 OR t.archived_at IS NULL)
 ORDER BY t.created_at DESC"#,
-            project_id
+            project_id,
+            include_archived
         )
         .fetch_all(pool)
         .await?;
@@ -195,27 +309,156 @@ ORDER BY t.created_at DESC"#,
                 task: Task {
                     id: rec.id,
                     project_id: rec.project_id,
+                    number: rec.number,
                     title: rec.title,
                     description: rec.description,
                     status: rec.status,
+                    column_id: rec.column_id,
+                    priority: rec.priority,
+                    due_at: rec.due_at,
                     parent_workspace_id: rec.parent_workspace_id,
                     shared_task_id: rec.shared_task_id,
+                    archived_at: rec.archived_at,
                     created_at: rec.created_at,
                     updated_at: rec.updated_at,
                 },
                 has_in_progress_attempt: rec.has_in_progress_attempt != 0,
+                has_paused_attempt: rec.has_paused_attempt != 0,
                 last_attempt_failed: rec.last_attempt_failed != 0,
                 executor: rec.executor,
+                checklist_total: rec.checklist_total,
+                checklist_done: rec.checklist_done,
             })
             .collect();
 
         Ok(tasks)
     }
 
+    /// Tasks marked done, tasks whose coding agent failed and API spend since `since`, plus the
+    /// current review queue
+    pub async fn activity_summary(
+        pool: &SqlitePool,
+        since: DateTime<Utc>,
+    ) -> Result<TaskActivitySummary, sqlx::Error> {
+        sqlx::query_as!(
+            TaskActivitySummary,
+            r#"SELECT
+                 (SELECT COUNT(*) FROM tasks
+                   WHERE status = 'done' AND datetime(updated_at) >= datetime($1)) AS "completed!: i64",
+                 (SELECT COUNT(DISTINCT w.task_id)
+                    FROM execution_processes ep
+                    JOIN sessions s ON ep.session_id = s.id
+                    JOIN workspaces w ON s.workspace_id = w.id
+                   WHERE ep.run_reason = 'codingagent'
+                     AND ep.status = 'failed'
+                     AND datetime(ep.completed_at) >= datetime($1)) AS "failed!: i64",
+                 (SELECT COUNT(*) FROM tasks
+                   WHERE status = 'inreview' AND archived_at IS NULL) AS "awaiting_review!: i64",
+                 (SELECT COALESCE(SUM(cost_usd), 0.0) FROM execution_usage
+                   WHERE datetime(created_at) >= datetime($1)) AS "cost_usd!: f64""#,
+            since
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Unfinished tasks due before `until`, optionally in one project, earliest due first
+    pub async fn find_due_before(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", number, title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", priority as "priority!: TaskPriority", due_at as "due_at: DateTime<Utc>", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE due_at IS NOT NULL
+                 AND datetime(due_at) <= datetime($2)
+                 AND status NOT IN ('done', 'cancelled')
+                 AND archived_at IS NULL
+                 AND ($1 IS NULL OR project_id = $1)
+               ORDER BY datetime(due_at) ASC"#,
+            project_id,
+            until
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Split the unfinished tasks due before `now + within` into overdue and due soon
+    pub async fn find_due(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+        now: DateTime<Utc>,
+        within: chrono::Duration,
+    ) -> Result<DueTasks, sqlx::Error> {
+        let (overdue, due_soon): (Vec<Task>, Vec<Task>) =
+            Self::find_due_before(pool, project_id, now + within)
+                .await?
+                .into_iter()
+                .partition(|task| task.due_at.is_some_and(|due_at| due_at <= now));
+        Ok(DueTasks { overdue, due_soon })
+    }
+
+    /// The project's unarchived tasks that have a due date, finished ones included
+    pub async fn find_with_due_date(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", number, title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", priority as "priority!: TaskPriority", due_at as "due_at: DateTime<Utc>", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE project_id = $1
+                 AND due_at IS NOT NULL
+                 AND archived_at IS NULL
+               ORDER BY datetime(due_at) ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Unfinished tasks that passed their due date by `now` and haven't been notified about
+    pub async fn find_newly_overdue(
+        pool: &SqlitePool,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", number, title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", priority as "priority!: TaskPriority", due_at as "due_at: DateTime<Utc>", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE due_at IS NOT NULL
+                 AND datetime(due_at) <= datetime($1)
+                 AND overdue_notified_at IS NULL
+                 AND status NOT IN ('done', 'cancelled')
+                 AND archived_at IS NULL
+               ORDER BY datetime(due_at) ASC"#,
+            now
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn mark_overdue_notified(
+        pool: &SqlitePool,
+        id: Uuid,
+        at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE tasks SET overdue_notified_at = $2 WHERE id = $1",
+            id,
+            at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", number, title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", priority as "priority!: TaskPriority", due_at as "due_at: DateTime<Utc>", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE id = $1"#,
             id
@@ -224,10 +467,47 @@ ORDER BY t.created_at DESC"#,
         .await
     }
 
+    /// The project's task referred to as `#number`
+    pub async fn find_by_number(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        number: i64,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", number, title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", priority as "priority!: TaskPriority", due_at as "due_at: DateTime<Utc>", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE project_id = $1 AND number = $2"#,
+            project_id,
+            number
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Most recent task whose id starts with `prefix` (hex digits without dashes), for short links
+    pub async fn find_by_short_id(
+        pool: &SqlitePool,
+        prefix: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let pattern = format!("{}%", prefix.to_ascii_uppercase());
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", number, title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", priority as "priority!: TaskPriority", due_at as "due_at: DateTime<Utc>", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE hex(id) LIKE $1
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            pattern
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", number, title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", priority as "priority!: TaskPriority", due_at as "due_at: DateTime<Utc>", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE rowid = $1"#,
             rowid
@@ -245,7 +525,7 @@ ORDER BY t.created_at DESC"#,
     {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", number, title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", priority as "priority!: TaskPriority", due_at as "due_at: DateTime<Utc>", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE shared_task_id = $1
                LIMIT 1"#,
@@ -258,7 +538,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_all_shared(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", number, title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", priority as "priority!: TaskPriority", due_at as "due_at: DateTime<Utc>", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE shared_task_id IS NOT NULL"#
         )
@@ -272,18 +552,23 @@ ORDER BY t.created_at DESC"#,
         task_id: Uuid,
     ) -> Result<Self, sqlx::Error> {
         let status = data.status.clone().unwrap_or_default();
+        let priority = data.priority.unwrap_or_default();
         sqlx::query_as!(
             Task,
-            r#"INSERT INTO tasks (id, project_id, title, description, status, parent_workspace_id, shared_task_id)
-               VALUES ($1, $2, $3, $4, $5, $6, $7)
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO tasks (id, project_id, title, description, status, parent_workspace_id, shared_task_id, priority, due_at, column_id, number)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9,
+                       (SELECT c.id FROM project_columns c WHERE c.project_id = $2 AND c.status = $5 ORDER BY c.position LIMIT 1),
+                       (SELECT COALESCE(MAX(number), 0) + 1 FROM tasks WHERE project_id = $2))
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", number, title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", priority as "priority!: TaskPriority", due_at as "due_at: DateTime<Utc>", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             task_id,
             data.project_id,
             data.title,
             data.description,
             status,
             data.parent_workspace_id,
-            data.shared_task_id
+            data.shared_task_id,
+            priority,
+            data.due_at
         )
         .fetch_one(pool)
         .await
@@ -296,23 +581,32 @@ ORDER BY t.created_at DESC"#,
         title: String,
         description: Option<String>,
         status: TaskStatus,
+        priority: TaskPriority,
+        due_at: Option<DateTime<Utc>>,
         parent_workspace_id: Option<Uuid>,
     ) -> Result<Self, sqlx::Error> {
-        sqlx::query_as!(
+        let task = sqlx::query_as!(
             Task,
             r#"UPDATE tasks
-               SET title = $3, description = $4, status = $5, parent_workspace_id = $6
+               SET title = $3, description = $4, status = $5, parent_workspace_id = $6, priority = $7,
+                   column_id = CASE WHEN (SELECT c.status FROM project_columns c WHERE c.id = tasks.column_id) IS $5 THEN column_id
+                                    ELSE (SELECT c.id FROM project_columns c WHERE c.project_id = $2 AND c.status = $5 ORDER BY c.position LIMIT 1) END,
+                   overdue_notified_at = CASE WHEN due_at IS $8 THEN overdue_notified_at END, due_at = $8
                WHERE id = $1 AND project_id = $2
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", number, title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", priority as "priority!: TaskPriority", due_at as "due_at: DateTime<Utc>", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             project_id,
             title,
             description,
             status,
-            parent_workspace_id
+            parent_workspace_id,
+            priority,
+            due_at
         )
         .fetch_one(pool)
-        .await
+        .await?;
+        TaskSubtask::roll_up(pool, task.id).await?;
+        Ok(task)
     }
 
     pub async fn update_status(
@@ -327,9 +621,70 @@ ORDER BY t.created_at DESC"#,
         )
         .execute(pool)
         .await?;
+        TaskSubtask::roll_up(pool, id).await
+    }
+
+    /// Take the task off the board; a no-op for tasks already archived
+    pub async fn archive(
+        pool: &SqlitePool,
+        id: Uuid,
+        at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE tasks SET archived_at = $2 WHERE id = $1 AND archived_at IS NULL",
+            id,
+            at
+        )
+        .execute(pool)
+        .await?;
         Ok(())
     }
 
+    /// Put an archived task back on the board, in the first column for its status if its column
+    /// was deleted meanwhile. Counts as a change, so auto-archiving leaves it alone for a while.
+    pub async fn restore(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE tasks
+               SET archived_at = NULL,
+                   updated_at = CURRENT_TIMESTAMP,
+                   column_id = COALESCE(column_id, (
+                       SELECT c.id FROM project_columns c
+                       WHERE c.project_id = tasks.project_id AND c.status = tasks.status
+                       ORDER BY c.position
+                       LIMIT 1
+                   ))
+               WHERE id = $1 AND archived_at IS NOT NULL"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Archive the project's done and cancelled tasks that haven't changed since `before`,
+    /// returning how many were archived
+    pub async fn archive_finished_before(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        before: DateTime<Utc>,
+        at: DateTime<Utc>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE tasks
+               SET archived_at = $3
+               WHERE project_id = $1
+                 AND archived_at IS NULL
+                 AND status IN ('done', 'cancelled')
+                 AND datetime(updated_at) <= datetime($2)"#,
+            project_id,
+            before,
+            at
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     /// Update the parent_workspace_id field for a task
     pub async fn update_parent_workspace_id(
         pool: &SqlitePool,
@@ -446,7 +801,7 @@ ORDER BY t.created_at DESC"#,
         // Find only child tasks that have this workspace as their parent
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", number, title, description, status as "status!: TaskStatus", column_id as "column_id: Uuid", priority as "priority!: TaskPriority", due_at as "due_at: DateTime<Utc>", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE parent_workspace_id = $1
                ORDER BY created_at DESC"#,
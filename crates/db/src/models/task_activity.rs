@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS)]
+#[sqlx(type_name = "task_activity_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TaskActivityKind {
+    Created,
+    StatusChanged,
+    FieldChanged,
+    AttemptStarted,
+    AttemptStopped,
+    PrOpened,
+    PrStatusChanged,
+    Merged,
+}
+
+/// One entry in a task's audit history. Entries are recorded by database triggers, so they cover
+/// changes made by humans through the API as well as by the agent and background services.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskActivity {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub kind: TaskActivityKind,
+    /// Changed field for `status_changed`, `field_changed`, `attempt_stopped` and
+    /// `pr_status_changed`, e.g. `title`, `due_at` or `executor` when an attempt is handed off
+    pub field: Option<String>,
+    pub old_value: Option<String>,
+    /// Title for `created`, executor for `attempt_started`, PR URL for `pr_opened` and merge
+    /// commit for `merged`; otherwise the field's new value
+    pub new_value: Option<String>,
+    pub workspace_id: Option<Uuid>,
+    pub execution_process_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskActivity {
+    /// Most recent first
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskActivity,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", kind as "kind!: TaskActivityKind", field, old_value, new_value, workspace_id as "workspace_id: Uuid", execution_process_id as "execution_process_id: Uuid", created_at as "created_at!: DateTime<Utc>"
+               FROM task_activity
+               WHERE task_id = $1
+               ORDER BY created_at DESC, rowid DESC
+               LIMIT $2 OFFSET $3"#,
+            task_id,
+            limit,
+            offset
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Oldest first, entries recorded after `after`
+    pub async fn find_after(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        after: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskActivity,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", kind as "kind!: TaskActivityKind", field, old_value, new_value, workspace_id as "workspace_id: Uuid", execution_process_id as "execution_process_id: Uuid", created_at as "created_at!: DateTime<Utc>"
+               FROM task_activity
+               WHERE task_id = $1 AND julianday(created_at) > julianday($2)
+               ORDER BY created_at ASC, rowid ASC"#,
+            task_id,
+            after
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
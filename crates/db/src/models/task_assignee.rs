@@ -0,0 +1,112 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A person who owns the task, or the coding agent that runs its attempts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS)]
+#[sqlx(type_name = "task_assignee_kind", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum TaskAssigneeKind {
+    Human,
+    Agent,
+}
+
+/// Someone a task is assigned to. A task has any number of human assignees and at most one agent.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskAssignee {
+    pub task_id: Uuid,
+    pub kind: TaskAssigneeKind,
+    /// A person's name or handle, or an executor such as `CLAUDE_CODE`; matched ignoring case
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskAssignee {
+    /// Humans first, then the agent, each by name
+    pub async fn find_for_task(pool: &SqlitePool, task_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAssignee,
+            r#"SELECT task_id as "task_id!: Uuid", kind as "kind!: TaskAssigneeKind", name,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_assignees
+               WHERE task_id = $1
+               ORDER BY kind = 'agent' ASC, name ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Names of everyone assigned to a task, as matched by notification rules
+    pub async fn names_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        Ok(Self::find_for_task(pool, task_id)
+            .await?
+            .into_iter()
+            .map(|assignee| assignee.name)
+            .collect())
+    }
+
+    /// Ids of the tasks assigned to `name`, a person or an agent
+    pub async fn task_ids_for_name(
+        pool: &SqlitePool,
+        name: &str,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT DISTINCT task_id as "task_id!: Uuid"
+               FROM task_assignees
+               WHERE name = $1"#,
+            name
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replace the task's assignees
+    pub async fn set_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        humans: &[String],
+        agent: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!("DELETE FROM task_assignees WHERE task_id = $1", task_id)
+            .execute(&mut *tx)
+            .await?;
+        let assignees = humans
+            .iter()
+            .map(|name| (TaskAssigneeKind::Human, name.as_str()))
+            .chain(agent.map(|name| (TaskAssigneeKind::Agent, name)));
+        for (kind, name) in assignees {
+            sqlx::query!(
+                "INSERT OR IGNORE INTO task_assignees (task_id, kind, name) VALUES ($1, $2, $3)",
+                task_id,
+                kind,
+                name
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    pub async fn copy_to_task(
+        pool: &SqlitePool,
+        from_task_id: Uuid,
+        to_task_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT OR IGNORE INTO task_assignees (task_id, kind, name)
+               SELECT $2, kind, name FROM task_assignees WHERE task_id = $1"#,
+            from_task_id,
+            to_task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use utils::path::VIBE_ATTACHMENTS_DIR;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS)]
+#[sqlx(type_name = "attachment_storage", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum AttachmentStorage {
+    Local,
+    S3,
+}
+
+/// File attached to a task, e.g. a screenshot, log or design doc
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskAttachment {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub original_name: String,
+    pub mime_type: Option<String>,
+    pub size_bytes: i64,
+    pub storage: AttachmentStorage,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub storage_key: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateTaskAttachment {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub original_name: String,
+    pub mime_type: Option<String>,
+    pub size_bytes: i64,
+    pub storage: AttachmentStorage,
+    pub storage_key: String,
+}
+
+/// `name` reduced to characters that are safe in a file name on every platform
+pub fn sanitize_file_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let trimmed = sanitized.trim_matches('.');
+    if trimmed.is_empty() {
+        "attachment".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Attachment paths, relative to the workspace, for a coding agent prompt; `None` when there are
+/// none
+pub fn attachments_prompt(attachments: &[TaskAttachment]) -> Option<String> {
+    if attachments.is_empty() {
+        return None;
+    }
+    let files = attachments
+        .iter()
+        .map(|attachment| format!("- {}", attachment.workspace_path()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(format!("Files attached to this task:\n{files}"))
+}
+
+impl TaskAttachment {
+    /// File name inside the workspace's attachments directory; prefixed with part of the id so
+    /// equal names don't clash
+    pub fn workspace_file_name(&self) -> String {
+        let id = self.id.simple().to_string();
+        format!("{}-{}", &id[..8], sanitize_file_name(&self.original_name))
+    }
+
+    pub fn workspace_path(&self) -> String {
+        format!("{}/{}", VIBE_ATTACHMENTS_DIR, self.workspace_file_name())
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttachment,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", original_name, mime_type, size_bytes as "size_bytes!: i64", storage as "storage!: AttachmentStorage", storage_key, created_at as "created_at!: DateTime<Utc>"
+               FROM task_attachments
+               WHERE task_id = $1
+               ORDER BY created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttachment,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", original_name, mime_type, size_bytes as "size_bytes!: i64", storage as "storage!: AttachmentStorage", storage_key, created_at as "created_at!: DateTime<Utc>"
+               FROM task_attachments
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateTaskAttachment,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttachment,
+            r#"INSERT INTO task_attachments (id, task_id, original_name, mime_type, size_bytes, storage, storage_key)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", original_name, mime_type, size_bytes as "size_bytes!: i64", storage as "storage!: AttachmentStorage", storage_key, created_at as "created_at!: DateTime<Utc>""#,
+            data.id,
+            data.task_id,
+            data.original_name,
+            data.mime_type,
+            data.size_bytes,
+            data.storage,
+            data.storage_key
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_attachments WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_paths_and_prompt() {
+        assert_eq!(sanitize_file_name("crash log (1).txt"), "crash_log__1_.txt");
+        assert_eq!(sanitize_file_name("../../etc/passwd"), "_.._etc_passwd");
+        assert_eq!(sanitize_file_name(".."), "attachment");
+
+        let attachment = TaskAttachment {
+            id: Uuid::parse_str("0f8b2c1e-0000-4000-8000-000000000000").unwrap(),
+            task_id: Uuid::new_v4(),
+            original_name: "design doc.pdf".to_string(),
+            mime_type: Some("application/pdf".to_string()),
+            size_bytes: 1024,
+            storage: AttachmentStorage::Local,
+            storage_key: "0f8b2c1e.pdf".to_string(),
+            created_at: Utc::now(),
+        };
+        assert_eq!(
+            attachment.workspace_path(),
+            ".vibe-attachments/0f8b2c1e-design_doc.pdf"
+        );
+        assert_eq!(attachments_prompt(&[]), None);
+        assert_eq!(
+            attachments_prompt(&[attachment]).unwrap(),
+            "Files attached to this task:\n- .vibe-attachments/0f8b2c1e-design_doc.pdf"
+        );
+    }
+}
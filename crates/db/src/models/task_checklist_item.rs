@@ -0,0 +1,273 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use utils::text::checklist_states;
+use uuid::Uuid;
+
+/// One item of a task's checklist
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskChecklistItem {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub text: String,
+    pub done: bool,
+    /// Items are shown in ascending order
+    pub position: i64,
+    /// When the item was last ticked, while it is
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateTaskChecklistItem {
+    pub text: String,
+    #[serde(default)]
+    #[ts(optional)]
+    pub done: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateTaskChecklistItem {
+    #[serde(default)]
+    #[ts(optional)]
+    pub text: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub done: Option<bool>,
+}
+
+/// Item text compared case- and whitespace-insensitively
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// The checklist formatted for a coding agent prompt, asking the agent to report progress on it;
+/// `None` when there are no items
+pub fn checklist_prompt(items: &[TaskChecklistItem]) -> Option<String> {
+    if items.is_empty() {
+        return None;
+    }
+    let list = items
+        .iter()
+        .map(|item| format!("- [{}] {}", if item.done { "x" } else { " " }, item.text))
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(format!(
+        "Checklist for this task:\n\n{list}\n\nEnd your final message with this checklist, \
+         marking each item you completed as `- [x]`."
+    ))
+}
+
+/// Unticked items that an agent message reports as done, matching item text case- and
+/// whitespace-insensitively. Agents only tick items; unticking is left to people, so a stale
+/// copy of the list can't undo their progress.
+pub fn reported_done<'a>(
+    items: &'a [TaskChecklistItem],
+    message: &str,
+) -> Vec<&'a TaskChecklistItem> {
+    let reported: Vec<String> = checklist_states(message)
+        .into_iter()
+        .filter_map(|(text, done)| done.then(|| normalize(&text)))
+        .collect();
+    items
+        .iter()
+        .filter(|item| !item.done && reported.contains(&normalize(&item.text)))
+        .collect()
+}
+
+impl TaskChecklistItem {
+    /// In checklist order
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskChecklistItem,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", text, done as "done!: bool", position,
+                      completed_at as "completed_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_checklist_items
+               WHERE task_id = $1
+               ORDER BY position ASC, created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskChecklistItem,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", text, done as "done!: bool", position,
+                      completed_at as "completed_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_checklist_items
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Append an item to the end of the task's checklist
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        text: &str,
+        done: bool,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let text = text.trim();
+        sqlx::query!(
+            r#"INSERT INTO task_checklist_items (id, task_id, text, done, position, completed_at)
+               VALUES ($1, $2, $3, $4,
+                       (SELECT COALESCE(MAX(position) + 1, 0) FROM task_checklist_items WHERE task_id = $2),
+                       CASE WHEN $4 THEN datetime('now', 'subsec') END)"#,
+            id,
+            task_id,
+            text,
+            done
+        )
+        .execute(pool)
+        .await?;
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        existing: &TaskChecklistItem,
+        data: &UpdateTaskChecklistItem,
+    ) -> Result<Self, sqlx::Error> {
+        let text = data
+            .text
+            .as_deref()
+            .map(str::trim)
+            .unwrap_or(&existing.text);
+        let done = data.done.unwrap_or(existing.done);
+        sqlx::query!(
+            r#"UPDATE task_checklist_items
+               SET text = $2,
+                   done = $3,
+                   completed_at = CASE WHEN NOT $3 THEN NULL
+                                       WHEN done THEN completed_at
+                                       ELSE datetime('now', 'subsec') END,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            existing.id,
+            text,
+            done
+        )
+        .execute(pool)
+        .await?;
+        Self::find_by_id(pool, existing.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_checklist_items WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Put the task's items in the order given; items left out keep their relative order after
+    /// the listed ones
+    pub async fn reorder(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        item_ids: &[Uuid],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        let offset = item_ids.len() as i64;
+        sqlx::query!(
+            "UPDATE task_checklist_items SET position = position + $2 WHERE task_id = $1",
+            task_id,
+            offset
+        )
+        .execute(&mut *tx)
+        .await?;
+        for (position, item_id) in item_ids.iter().enumerate() {
+            let position = position as i64;
+            sqlx::query!(
+                "UPDATE task_checklist_items SET position = $3 WHERE task_id = $1 AND id = $2",
+                task_id,
+                item_id,
+                position
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Tick the items an agent's message reports as done, returning them
+    pub async fn apply_agent_report(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        message: &str,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let items = Self::find_by_task_id(pool, task_id).await?;
+        let mut ticked = Vec::new();
+        for item in reported_done(&items, message) {
+            let data = UpdateTaskChecklistItem {
+                text: None,
+                done: Some(true),
+            };
+            ticked.push(Self::update(pool, item, &data).await?);
+        }
+        Ok(ticked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(text: &str, done: bool) -> TaskChecklistItem {
+        TaskChecklistItem {
+            id: Uuid::new_v4(),
+            task_id: Uuid::nil(),
+            text: text.to_string(),
+            done,
+            position: 0,
+            completed_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_checklist_prompt() {
+        assert_eq!(checklist_prompt(&[]), None);
+        assert_eq!(
+            checklist_prompt(&[item("Add the table", true), item("Wire the routes", false)])
+                .unwrap(),
+            "Checklist for this task:\n\n- [x] Add the table\n- [ ] Wire the routes\n\n\
+             End your final message with this checklist, marking each item you completed as `- [x]`."
+        );
+    }
+
+    #[test]
+    fn test_reported_done() {
+        let items = [
+            item("Add the table", false),
+            item("Wire  the routes", false),
+            item("Write docs", true),
+            item("Add tests", false),
+        ];
+        let message = "All set.\n\n- [x] add the table\n- [X] Wire the routes\n- [ ] Write docs\n- [ ] Add tests\n- [x] Something else";
+        let ticked: Vec<&str> = reported_done(&items, message)
+            .into_iter()
+            .map(|item| item.text.as_str())
+            .collect();
+        assert_eq!(ticked, vec!["Add the table", "Wire  the routes"]);
+    }
+}
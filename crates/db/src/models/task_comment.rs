@@ -0,0 +1,218 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Comment in a task's discussion thread; the body is markdown
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskComment {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub author: String,
+    pub body: String,
+    /// Number of times the body has been edited; see the comment's history for earlier versions
+    pub edit_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A comment body as it was before an edit
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskCommentEdit {
+    pub id: Uuid,
+    pub comment_id: Uuid,
+    pub body: String,
+    pub edited_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateTaskComment {
+    /// Defaults to the configured GitHub username
+    #[ts(optional)]
+    pub author: Option<String>,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateTaskComment {
+    pub body: String,
+}
+
+/// Comments formatted for a coding agent prompt, oldest first; `None` when there are none
+pub fn comments_prompt(comments: &[TaskComment]) -> Option<String> {
+    if comments.is_empty() {
+        return None;
+    }
+    let thread = comments
+        .iter()
+        .map(|comment| {
+            format!(
+                "{} ({}):\n{}",
+                comment.author,
+                comment.created_at.format("%Y-%m-%d %H:%M UTC"),
+                comment.body.trim()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Some(format!("Comments on this task:\n\n{thread}"))
+}
+
+impl TaskComment {
+    /// Oldest first
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskComment,
+            r#"SELECT c.id as "id!: Uuid", c.task_id as "task_id!: Uuid", c.author, c.body,
+                      (SELECT COUNT(*) FROM task_comment_edits e WHERE e.comment_id = c.id) as "edit_count!: i64",
+                      c.created_at as "created_at!: DateTime<Utc>", c.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_comments c
+               WHERE c.task_id = $1
+               ORDER BY c.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskComment,
+            r#"SELECT c.id as "id!: Uuid", c.task_id as "task_id!: Uuid", c.author, c.body,
+                      (SELECT COUNT(*) FROM task_comment_edits e WHERE e.comment_id = c.id) as "edit_count!: i64",
+                      c.created_at as "created_at!: DateTime<Utc>", c.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_comments c
+               WHERE c.id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskComment,
+            r#"SELECT c.id as "id!: Uuid", c.task_id as "task_id!: Uuid", c.author, c.body,
+                      (SELECT COUNT(*) FROM task_comment_edits e WHERE e.comment_id = c.id) as "edit_count!: i64",
+                      c.created_at as "created_at!: DateTime<Utc>", c.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_comments c
+               WHERE c.rowid = $1"#,
+            rowid
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        author: &str,
+        body: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            "INSERT INTO task_comments (id, task_id, author, body) VALUES ($1, $2, $3, $4)",
+            id,
+            task_id,
+            author,
+            body
+        )
+        .execute(pool)
+        .await?;
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Replace the body, keeping the previous one in the comment's history
+    pub async fn update(
+        pool: &SqlitePool,
+        existing: &TaskComment,
+        body: &str,
+    ) -> Result<Self, sqlx::Error> {
+        if existing.body == body {
+            return Ok(existing.clone());
+        }
+        let edit_id = Uuid::new_v4();
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            "INSERT INTO task_comment_edits (id, comment_id, body) VALUES ($1, $2, $3)",
+            edit_id,
+            existing.id,
+            existing.body
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE task_comments SET body = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            existing.id,
+            body
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Self::find_by_id(pool, existing.id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_comments WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Earlier versions of the comment, newest first
+    pub async fn find_edits(
+        pool: &SqlitePool,
+        comment_id: Uuid,
+    ) -> Result<Vec<TaskCommentEdit>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskCommentEdit,
+            r#"SELECT id as "id!: Uuid", comment_id as "comment_id!: Uuid", body, edited_at as "edited_at!: DateTime<Utc>"
+               FROM task_comment_edits
+               WHERE comment_id = $1
+               ORDER BY edited_at DESC"#,
+            comment_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_comments_prompt() {
+        assert_eq!(comments_prompt(&[]), None);
+
+        let comment = |author: &str, body: &str, hour| TaskComment {
+            id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            author: author.to_string(),
+            body: body.to_string(),
+            edit_count: 0,
+            created_at: Utc.with_ymd_and_hms(2026, 1, 5, hour, 30, 0).unwrap(),
+            updated_at: Utc.with_ymd_and_hms(2026, 1, 5, hour, 30, 0).unwrap(),
+        };
+        assert_eq!(
+            comments_prompt(&[
+                comment("alice", "The bug only shows up on Safari.\n", 9),
+                comment("bob", "Repro steps are in the linked issue", 10),
+            ])
+            .unwrap(),
+            "Comments on this task:\n\n\
+             alice (2026-01-05 09:30 UTC):\nThe bug only shows up on Safari.\n\n\
+             bob (2026-01-05 10:30 UTC):\nRepro steps are in the linked issue"
+        );
+    }
+}
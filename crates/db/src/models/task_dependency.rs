@@ -0,0 +1,210 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::{Task, TaskPriority, TaskStatus};
+
+#[derive(Debug, Error)]
+pub enum TaskDependencyError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Task not found")]
+    TaskNotFound,
+    #[error("A task cannot block itself")]
+    SelfDependency,
+    #[error("Blocking tasks must be in the same project")]
+    DifferentProject,
+    #[error("'{0}' already depends on this task")]
+    Cycle(String),
+}
+
+/// Tasks that must finish before a task can start, and tasks waiting on it
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskDependencies {
+    pub blockers: Vec<Task>,
+    pub dependents: Vec<Task>,
+}
+
+/// Whether a blocker no longer holds up its dependents
+fn is_complete(status: &TaskStatus) -> bool {
+    matches!(status, TaskStatus::Done | TaskStatus::Cancelled)
+}
+
+/// `task_id` can't start until `blocker_id` is done or cancelled
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskDependency {
+    pub task_id: Uuid,
+    pub blocker_id: Uuid,
+    /// When the dependent was last released by the blocker completing
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskDependency {
+    /// Make `task_id` wait on `blocker_id`, rejecting dependencies across projects and cycles
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        blocker_id: Uuid,
+    ) -> Result<Self, TaskDependencyError> {
+        if task_id == blocker_id {
+            return Err(TaskDependencyError::SelfDependency);
+        }
+        let task = Task::find_by_id(pool, task_id)
+            .await?
+            .ok_or(TaskDependencyError::TaskNotFound)?;
+        let blocker = Task::find_by_id(pool, blocker_id)
+            .await?
+            .ok_or(TaskDependencyError::TaskNotFound)?;
+        if task.project_id != blocker.project_id {
+            return Err(TaskDependencyError::DifferentProject);
+        }
+        if Self::depends_on(pool, blocker_id, task_id).await? {
+            return Err(TaskDependencyError::Cycle(blocker.title));
+        }
+
+        // A blocker that is already complete has nothing left to report
+        let resolved = is_complete(&blocker.status);
+        Ok(sqlx::query_as!(
+            TaskDependency,
+            r#"INSERT INTO task_dependencies (task_id, blocker_id, resolved_at)
+               VALUES ($1, $2, CASE WHEN $3 THEN datetime('now', 'subsec') END)
+               ON CONFLICT(task_id, blocker_id) DO UPDATE SET task_id = excluded.task_id
+               RETURNING task_id as "task_id!: Uuid", blocker_id as "blocker_id!: Uuid", resolved_at as "resolved_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>""#,
+            task_id,
+            blocker_id,
+            resolved
+        )
+        .fetch_one(pool)
+        .await?)
+    }
+
+    pub async fn delete(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        blocker_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM task_dependencies WHERE task_id = $1 AND blocker_id = $2",
+            task_id,
+            blocker_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Whether `task_id` waits on `blocker_id`, directly or through other tasks
+    async fn depends_on(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        blocker_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"WITH RECURSIVE blockers(id) AS (
+                   SELECT blocker_id FROM task_dependencies WHERE task_id = $1
+                   UNION
+                   SELECT d.blocker_id FROM task_dependencies d JOIN blockers b ON d.task_id = b.id
+               )
+               SELECT EXISTS(SELECT 1 FROM blockers WHERE id = $2) as "exists!: bool""#,
+            task_id,
+            blocker_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<TaskDependencies, sqlx::Error> {
+        let blockers = sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.number, t.title, t.description, t.status as "status!: TaskStatus", t.column_id as "column_id: Uuid", t.priority as "priority!: TaskPriority", t.due_at as "due_at: DateTime<Utc>", t.parent_workspace_id as "parent_workspace_id: Uuid", t.shared_task_id as "shared_task_id: Uuid", t.archived_at as "archived_at: DateTime<Utc>", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks t
+               JOIN task_dependencies d ON d.blocker_id = t.id
+               WHERE d.task_id = $1
+               ORDER BY t.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await?;
+        let dependents = sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.number, t.title, t.description, t.status as "status!: TaskStatus", t.column_id as "column_id: Uuid", t.priority as "priority!: TaskPriority", t.due_at as "due_at: DateTime<Utc>", t.parent_workspace_id as "parent_workspace_id: Uuid", t.shared_task_id as "shared_task_id: Uuid", t.archived_at as "archived_at: DateTime<Utc>", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks t
+               JOIN task_dependencies d ON d.task_id = t.id
+               WHERE d.blocker_id = $1
+               ORDER BY t.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(TaskDependencies {
+            blockers,
+            dependents,
+        })
+    }
+
+    /// Blockers of `task_id` that are neither done nor cancelled
+    pub async fn find_incomplete_blockers(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.number, t.title, t.description, t.status as "status!: TaskStatus", t.column_id as "column_id: Uuid", t.priority as "priority!: TaskPriority", t.due_at as "due_at: DateTime<Utc>", t.parent_workspace_id as "parent_workspace_id: Uuid", t.shared_task_id as "shared_task_id: Uuid", t.archived_at as "archived_at: DateTime<Utc>", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks t
+               JOIN task_dependencies d ON d.blocker_id = t.id
+               WHERE d.task_id = $1 AND t.status NOT IN ('done', 'cancelled')
+               ORDER BY t.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Mark dependencies on newly completed blockers as resolved, returning the to-do tasks that
+    /// no longer have any incomplete blocker, most urgent first. Dependencies on reopened
+    /// blockers are unresolved again, so completing them a second time is reported too.
+    pub async fn resolve_completed(pool: &SqlitePool) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE task_dependencies
+               SET resolved_at = NULL
+               WHERE resolved_at IS NOT NULL
+                 AND blocker_id IN (SELECT id FROM tasks WHERE status NOT IN ('done', 'cancelled'))"#
+        )
+        .execute(pool)
+        .await?;
+
+        let affected = sqlx::query_scalar!(
+            r#"UPDATE task_dependencies
+               SET resolved_at = datetime('now', 'subsec')
+               WHERE resolved_at IS NULL
+                 AND blocker_id IN (SELECT id FROM tasks WHERE status IN ('done', 'cancelled'))
+               RETURNING task_id as "task_id!: Uuid""#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut unblocked: Vec<Task> = Vec::new();
+        for task_id in affected {
+            if unblocked.iter().any(|task| task.id == task_id) {
+                continue;
+            }
+            if let Some(task) = Task::find_by_id(pool, task_id).await?
+                && task.status == TaskStatus::Todo
+                && Self::find_incomplete_blockers(pool, task_id)
+                    .await?
+                    .is_empty()
+            {
+                unblocked.push(task);
+            }
+        }
+        unblocked.sort_by_key(|task| task.priority);
+        Ok(unblocked)
+    }
+}
@@ -0,0 +1,171 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS)]
+#[sqlx(type_name = "task_reference_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TaskReferenceKind {
+    Task,
+    File,
+    Commit,
+}
+
+/// A resolved reference in a task's description
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskReference {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub kind: TaskReferenceKind,
+    /// As written in the description, e.g. `#12`, `src/main.rs:40` or `3f2a9c1`
+    pub reference: String,
+    /// The task a `task` reference points at
+    pub target_task_id: Option<Uuid>,
+    /// The repo a `file` or `commit` reference was found in
+    pub repo_id: Option<Uuid>,
+    /// Full commit SHA or repo-relative file path
+    pub resolved: Option<String>,
+    /// Web link for files and commits in repos hosted on GitHub
+    pub url: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A reference to be stored for a task
+#[derive(Debug, Clone)]
+pub struct NewTaskReference {
+    pub kind: TaskReferenceKind,
+    pub reference: String,
+    pub target_task_id: Option<Uuid>,
+    pub repo_id: Option<Uuid>,
+    pub resolved: Option<String>,
+    pub url: Option<String>,
+}
+
+/// A task whose description refers to another task
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskBacklink {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub number: i64,
+    pub title: String,
+    pub status: TaskStatus,
+    /// As written in the referring task, e.g. `#12`
+    pub reference: String,
+}
+
+/// A task's outgoing references and the tasks referring to it
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskReferences {
+    pub references: Vec<TaskReference>,
+    pub backlinks: Vec<TaskBacklink>,
+}
+
+impl TaskReference {
+    /// In the order they appear in the description
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskReference,
+            r#"SELECT id as "id!: Uuid", task_id as "task_id!: Uuid", kind as "kind!: TaskReferenceKind", reference,
+                      target_task_id as "target_task_id: Uuid", repo_id as "repo_id: Uuid", resolved, url,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_references
+               WHERE task_id = $1
+               ORDER BY rowid ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Tasks referring to the given one, most recently updated first
+    pub async fn find_backlinks(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<TaskBacklink>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskBacklink,
+            r#"SELECT t.id as "task_id!: Uuid", t.project_id as "project_id!: Uuid", t.number, t.title,
+                      t.status as "status!: TaskStatus", r.reference
+               FROM task_references r
+               JOIN tasks t ON t.id = r.task_id
+               WHERE r.target_task_id = $1
+               ORDER BY t.updated_at DESC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Whether the task's description has been resolved since it last changed
+    pub async fn is_scanned(pool: &SqlitePool, task_id: Uuid) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM task_reference_scans WHERE task_id = $1) as "scanned!: bool""#,
+            task_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Tasks whose descriptions changed, or that a newly created task may resolve, since they were
+    /// last scanned
+    pub async fn find_unscanned_task_ids(
+        pool: &SqlitePool,
+        limit: i64,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT t.id as "id!: Uuid"
+               FROM tasks t
+               LEFT JOIN task_reference_scans s ON s.task_id = t.id
+               WHERE s.task_id IS NULL
+               ORDER BY t.updated_at DESC
+               LIMIT $1"#,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replace the task's references and mark it scanned
+    pub async fn replace_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        references: &[NewTaskReference],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!("DELETE FROM task_references WHERE task_id = $1", task_id)
+            .execute(&mut *tx)
+            .await?;
+        for reference in references {
+            let id = Uuid::new_v4();
+            sqlx::query!(
+                r#"INSERT INTO task_references (id, task_id, kind, reference, target_task_id, repo_id, resolved, url)
+                   VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#,
+                id,
+                task_id,
+                reference.kind,
+                reference.reference,
+                reference.target_task_id,
+                reference.repo_id,
+                reference.resolved,
+                reference.url
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query!(
+            r#"INSERT INTO task_reference_scans (task_id) VALUES ($1)
+               ON CONFLICT(task_id) DO UPDATE SET scanned_at = datetime('now', 'subsec')"#,
+            task_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await
+    }
+}
@@ -0,0 +1,183 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::Task;
+
+#[derive(Debug, Error)]
+pub enum TaskRelationError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Task not found")]
+    TaskNotFound,
+    #[error("A task cannot be related to itself")]
+    SelfRelation,
+    #[error("This task is already linked to '{0}'")]
+    AlreadyRelated(String),
+    #[error("'{0}' already leads back to this task")]
+    Cycle(String),
+}
+
+/// How one task relates to another. `Duplicates` and `Supersedes` read from the task to the
+/// related task; `RelatesTo` goes both ways.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS)]
+#[sqlx(type_name = "task_relation_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TaskRelationKind {
+    Duplicates,
+    RelatesTo,
+    Supersedes,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskRelation {
+    pub task_id: Uuid,
+    pub related_id: Uuid,
+    pub kind: TaskRelationKind,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A task's relations, from its point of view
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct TaskRelations {
+    /// Tasks this one duplicates
+    pub duplicates: Vec<Task>,
+    /// Tasks that duplicate this one
+    pub duplicated_by: Vec<Task>,
+    /// Tasks this one replaces
+    pub supersedes: Vec<Task>,
+    /// Tasks that replace this one
+    pub superseded_by: Vec<Task>,
+    pub relates_to: Vec<Task>,
+}
+
+impl TaskRelation {
+    /// Link `task_id` to `related_id`, replacing the kind of an existing link in the same
+    /// direction. Rejects a second link between the pair in the other direction, and duplicate
+    /// or supersede chains that would loop back.
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        related_id: Uuid,
+        kind: TaskRelationKind,
+    ) -> Result<Self, TaskRelationError> {
+        if task_id == related_id {
+            return Err(TaskRelationError::SelfRelation);
+        }
+        Task::find_by_id(pool, task_id)
+            .await?
+            .ok_or(TaskRelationError::TaskNotFound)?;
+        let related = Task::find_by_id(pool, related_id)
+            .await?
+            .ok_or(TaskRelationError::TaskNotFound)?;
+        let reverse = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM task_relations WHERE task_id = $1 AND related_id = $2) as "exists!: bool""#,
+            related_id,
+            task_id
+        )
+        .fetch_one(pool)
+        .await?;
+        if reverse {
+            return Err(TaskRelationError::AlreadyRelated(related.title));
+        }
+        if kind != TaskRelationKind::RelatesTo
+            && Self::reaches(pool, related_id, task_id, kind).await?
+        {
+            return Err(TaskRelationError::Cycle(related.title));
+        }
+
+        Ok(sqlx::query_as!(
+            TaskRelation,
+            r#"INSERT INTO task_relations (task_id, related_id, kind)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(task_id, related_id) DO UPDATE SET kind = excluded.kind
+               RETURNING task_id as "task_id!: Uuid", related_id as "related_id!: Uuid", kind as "kind!: TaskRelationKind", created_at as "created_at!: DateTime<Utc>""#,
+            task_id,
+            related_id,
+            kind
+        )
+        .fetch_one(pool)
+        .await?)
+    }
+
+    /// Remove the link between two tasks, whichever direction it was made in
+    pub async fn delete(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        related_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"DELETE FROM task_relations
+               WHERE (task_id = $1 AND related_id = $2) OR (task_id = $2 AND related_id = $1)"#,
+            task_id,
+            related_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Whether a chain of `kind` links leads from `from_id` to `to_id`
+    async fn reaches(
+        pool: &SqlitePool,
+        from_id: Uuid,
+        to_id: Uuid,
+        kind: TaskRelationKind,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"WITH RECURSIVE reached(id) AS (
+                   SELECT related_id FROM task_relations WHERE task_id = $1 AND kind = $3
+                   UNION
+                   SELECT r.related_id FROM task_relations r JOIN reached ON r.task_id = reached.id
+                   WHERE r.kind = $3
+               )
+               SELECT EXISTS(SELECT 1 FROM reached WHERE id = $2) as "exists!: bool""#,
+            from_id,
+            to_id,
+            kind
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<TaskRelations, sqlx::Error> {
+        let relations = sqlx::query_as!(
+            TaskRelation,
+            r#"SELECT task_id as "task_id!: Uuid", related_id as "related_id!: Uuid", kind as "kind!: TaskRelationKind", created_at as "created_at!: DateTime<Utc>"
+               FROM task_relations
+               WHERE task_id = $1 OR related_id = $1
+               ORDER BY created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut result = TaskRelations::default();
+        for relation in relations {
+            let outgoing = relation.task_id == task_id;
+            let other_id = if outgoing {
+                relation.related_id
+            } else {
+                relation.task_id
+            };
+            let Some(other) = Task::find_by_id(pool, other_id).await? else {
+                continue;
+            };
+            let list = match (relation.kind, outgoing) {
+                (TaskRelationKind::Duplicates, true) => &mut result.duplicates,
+                (TaskRelationKind::Duplicates, false) => &mut result.duplicated_by,
+                (TaskRelationKind::Supersedes, true) => &mut result.supersedes,
+                (TaskRelationKind::Supersedes, false) => &mut result.superseded_by,
+                (TaskRelationKind::RelatesTo, _) => &mut result.relates_to,
+            };
+            list.push(other);
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,221 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Repository and base branch a scheduled attempt runs against
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ScheduleRepo {
+    pub repo_id: Uuid,
+    pub target_branch: String,
+}
+
+/// Creates a task from `title` and `description` each time `rule` fires, and starts an
+/// attempt on it when an executor profile and repos are set
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskSchedule {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// The title and description may contain `{{date}}`, replaced with the run's date
+    pub title: String,
+    pub description: Option<String>,
+    /// Cron expression, e.g. `0 9 * * MON`, or RRULE, e.g. `FREQ=WEEKLY;BYDAY=MO;BYHOUR=9`
+    pub rule: String,
+    /// Offset the rule's times are in, e.g. `+02:00`; the server's timezone when unset
+    pub utc_offset: Option<String>,
+    #[ts(type = "ExecutorProfileId | null")]
+    pub executor_profile_id: Option<Json<ExecutorProfileId>>,
+    #[ts(type = "Array<ScheduleRepo>")]
+    pub repos: Json<Vec<ScheduleRepo>>,
+    pub enabled: bool,
+    /// `None` once the rule will never fire again
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_task_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateTaskSchedule {
+    pub project_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub rule: String,
+    pub utc_offset: Option<String>,
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    #[serde(default)]
+    pub repos: Vec<ScheduleRepo>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateTaskSchedule {
+    pub title: Option<String>,
+    /// An empty string clears the description
+    pub description: Option<String>,
+    pub rule: Option<String>,
+    /// An empty string falls back to the server's timezone
+    pub utc_offset: Option<String>,
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    /// Stop starting attempts, only creating the task
+    pub clear_executor_profile_id: Option<bool>,
+    pub repos: Option<Vec<ScheduleRepo>>,
+    pub enabled: Option<bool>,
+}
+
+impl TaskSchedule {
+    pub async fn find_all(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, rule, utc_offset, executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>", repos as "repos!: Json<Vec<ScheduleRepo>>", enabled as "enabled!: bool", next_run_at as "next_run_at: DateTime<Utc>", last_run_at as "last_run_at: DateTime<Utc>", last_task_id as "last_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_schedules
+               WHERE $1 IS NULL OR project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, rule, utc_offset, executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>", repos as "repos!: Json<Vec<ScheduleRepo>>", enabled as "enabled!: bool", next_run_at as "next_run_at: DateTime<Utc>", last_run_at as "last_run_at: DateTime<Utc>", last_task_id as "last_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_schedules
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Enabled schedules whose next run is at or before `now`
+    pub async fn find_due(pool: &SqlitePool, now: DateTime<Utc>) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, rule, utc_offset, executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>", repos as "repos!: Json<Vec<ScheduleRepo>>", enabled as "enabled!: bool", next_run_at as "next_run_at: DateTime<Utc>", last_run_at as "last_run_at: DateTime<Utc>", last_task_id as "last_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_schedules
+               WHERE enabled = 1 AND next_run_at IS NOT NULL AND next_run_at <= $1
+               ORDER BY next_run_at ASC"#,
+            now
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateTaskSchedule,
+        id: Uuid,
+        created_at: DateTime<Utc>,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, sqlx::Error> {
+        let executor_profile_id = data.executor_profile_id.as_ref().map(Json);
+        let repos = Json(&data.repos);
+        let enabled = data.enabled.unwrap_or(true);
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"INSERT INTO task_schedules (id, project_id, title, description, rule, utc_offset, executor_profile_id, repos, enabled, next_run_at, created_at, updated_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $11)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, rule, utc_offset, executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>", repos as "repos!: Json<Vec<ScheduleRepo>>", enabled as "enabled!: bool", next_run_at as "next_run_at: DateTime<Utc>", last_run_at as "last_run_at: DateTime<Utc>", last_task_id as "last_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.title,
+            data.description,
+            data.rule,
+            data.utc_offset,
+            executor_profile_id,
+            repos,
+            enabled,
+            next_run_at,
+            created_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Apply `data` over the existing schedule, which the caller has already loaded to compute
+    /// `next_run_at` from the merged rule
+    pub async fn update(
+        pool: &SqlitePool,
+        existing: &TaskSchedule,
+        data: &UpdateTaskSchedule,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, sqlx::Error> {
+        let title = data.title.as_ref().unwrap_or(&existing.title);
+        let description = match data.description.as_deref() {
+            Some(s) if s.trim().is_empty() => None,
+            Some(s) => Some(s),
+            None => existing.description.as_deref(),
+        };
+        let rule = data.rule.as_ref().unwrap_or(&existing.rule);
+        let utc_offset = match data.utc_offset.as_deref() {
+            Some(s) if s.trim().is_empty() => None,
+            Some(s) => Some(s),
+            None => existing.utc_offset.as_deref(),
+        };
+        let executor_profile_id = if data.clear_executor_profile_id.unwrap_or(false) {
+            None
+        } else {
+            data.executor_profile_id
+                .as_ref()
+                .or(existing.executor_profile_id.as_ref().map(|id| &id.0))
+                .map(Json)
+        };
+        let repos = Json(data.repos.as_ref().unwrap_or(&existing.repos.0));
+        let enabled = data.enabled.unwrap_or(existing.enabled);
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"UPDATE task_schedules
+               SET title = $2, description = $3, rule = $4, utc_offset = $5, executor_profile_id = $6, repos = $7, enabled = $8, next_run_at = $9, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, rule, utc_offset, executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>", repos as "repos!: Json<Vec<ScheduleRepo>>", enabled as "enabled!: bool", next_run_at as "next_run_at: DateTime<Utc>", last_run_at as "last_run_at: DateTime<Utc>", last_task_id as "last_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            existing.id,
+            title,
+            description,
+            rule,
+            utc_offset,
+            executor_profile_id,
+            repos,
+            enabled,
+            next_run_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Record that the schedule ran at `ran_at`, creating `task_id`, and when it runs next
+    pub async fn record_run(
+        pool: &SqlitePool,
+        id: Uuid,
+        task_id: Option<Uuid>,
+        ran_at: DateTime<Utc>,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE task_schedules
+               SET last_run_at = $2, last_task_id = COALESCE($3, last_task_id), next_run_at = $4
+               WHERE id = $1"#,
+            id,
+            ran_at,
+            task_id,
+            next_run_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_schedules WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,258 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::{Task, TaskPriority, TaskStatus};
+
+#[derive(Debug, Error)]
+pub enum TaskSubtaskError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Task not found")]
+    TaskNotFound,
+    #[error("A task cannot be its own subtask")]
+    SelfParent,
+    #[error("Subtasks must be in the same project as their parent")]
+    DifferentProject,
+    #[error("'{0}' is already a subtask of this task")]
+    Cycle(String),
+}
+
+/// A task's parent, if it is a subtask, and its own subtasks in order
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskSubtasks {
+    pub parent: Option<Task>,
+    pub subtasks: Vec<Task>,
+}
+
+/// Status of a parent task given its subtasks' statuses, or `None` when it has none.
+/// The parent is done once nothing is left to do, in review once the rest is waiting on
+/// review, and in progress as soon as any subtask has started.
+pub fn rollup_status(statuses: &[TaskStatus]) -> Option<TaskStatus> {
+    if statuses.is_empty() {
+        return None;
+    }
+    let all = |allowed: &[TaskStatus]| statuses.iter().all(|status| allowed.contains(status));
+    let status = if all(&[TaskStatus::Cancelled]) {
+        TaskStatus::Cancelled
+    } else if all(&[TaskStatus::Done, TaskStatus::Cancelled]) {
+        TaskStatus::Done
+    } else if all(&[
+        TaskStatus::InReview,
+        TaskStatus::Done,
+        TaskStatus::Cancelled,
+    ]) {
+        TaskStatus::InReview
+    } else if all(&[TaskStatus::Todo, TaskStatus::Cancelled]) {
+        TaskStatus::Todo
+    } else {
+        TaskStatus::InProgress
+    };
+    Some(status)
+}
+
+pub struct TaskSubtask;
+
+impl TaskSubtask {
+    /// Make `task_id` the last subtask of `parent_task_id`, or a top-level task when `None`.
+    /// Both the old and the new parent's statuses are rolled up again.
+    pub async fn set_parent(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        parent_task_id: Option<Uuid>,
+    ) -> Result<(), TaskSubtaskError> {
+        let task = Task::find_by_id(pool, task_id)
+            .await?
+            .ok_or(TaskSubtaskError::TaskNotFound)?;
+        let old_parent_id = Self::find_parent_id(pool, task_id).await?;
+
+        match parent_task_id {
+            None => {
+                sqlx::query!("DELETE FROM task_subtasks WHERE task_id = $1", task_id)
+                    .execute(pool)
+                    .await?;
+            }
+            Some(parent_task_id) => {
+                if parent_task_id == task_id {
+                    return Err(TaskSubtaskError::SelfParent);
+                }
+                let parent = Task::find_by_id(pool, parent_task_id)
+                    .await?
+                    .ok_or(TaskSubtaskError::TaskNotFound)?;
+                if parent.project_id != task.project_id {
+                    return Err(TaskSubtaskError::DifferentProject);
+                }
+                if Self::is_descendant(pool, parent_task_id, task_id).await? {
+                    return Err(TaskSubtaskError::Cycle(parent.title));
+                }
+                sqlx::query!(
+                    r#"INSERT INTO task_subtasks (task_id, parent_task_id, position)
+                       VALUES ($1, $2, (SELECT COALESCE(MAX(position) + 1, 0) FROM task_subtasks WHERE parent_task_id = $2))
+                       ON CONFLICT(task_id) DO UPDATE SET parent_task_id = excluded.parent_task_id, position = excluded.position"#,
+                    task_id,
+                    parent_task_id
+                )
+                .execute(pool)
+                .await?;
+                Self::refresh_status(pool, parent_task_id).await?;
+            }
+        }
+
+        if let Some(old_parent_id) = old_parent_id
+            && Some(old_parent_id) != parent_task_id
+        {
+            Self::refresh_status(pool, old_parent_id).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn find_parent_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT parent_task_id as "parent_task_id!: Uuid" FROM task_subtasks WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Whether `task_id` sits anywhere below `ancestor_id`
+    async fn is_descendant(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        ancestor_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"WITH RECURSIVE ancestors(id) AS (
+                   SELECT parent_task_id FROM task_subtasks WHERE task_id = $1
+                   UNION
+                   SELECT s.parent_task_id FROM task_subtasks s JOIN ancestors a ON s.task_id = a.id
+               )
+               SELECT EXISTS(SELECT 1 FROM ancestors WHERE id = $2) as "exists!: bool""#,
+            task_id,
+            ancestor_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_subtasks(
+        pool: &SqlitePool,
+        parent_task_id: Uuid,
+    ) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.number, t.title, t.description, t.status as "status!: TaskStatus", t.column_id as "column_id: Uuid", t.priority as "priority!: TaskPriority", t.due_at as "due_at: DateTime<Utc>", t.parent_workspace_id as "parent_workspace_id: Uuid", t.shared_task_id as "shared_task_id: Uuid", t.archived_at as "archived_at: DateTime<Utc>", t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks t
+               JOIN task_subtasks s ON s.task_id = t.id
+               WHERE s.parent_task_id = $1
+               ORDER BY s.position ASC, t.created_at ASC"#,
+            parent_task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<TaskSubtasks, sqlx::Error> {
+        let parent = match Self::find_parent_id(pool, task_id).await? {
+            Some(parent_id) => Task::find_by_id(pool, parent_id).await?,
+            None => None,
+        };
+        let subtasks = Self::find_subtasks(pool, task_id).await?;
+        Ok(TaskSubtasks { parent, subtasks })
+    }
+
+    /// Order a parent's subtasks as listed. Subtasks left out keep their place after the
+    /// listed ones, and ids that aren't subtasks of the parent are ignored.
+    pub async fn reorder(
+        pool: &SqlitePool,
+        parent_task_id: Uuid,
+        task_ids: &[Uuid],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        let offset = task_ids.len() as i64;
+        sqlx::query!(
+            "UPDATE task_subtasks SET position = position + $2 WHERE parent_task_id = $1",
+            parent_task_id,
+            offset
+        )
+        .execute(&mut *tx)
+        .await?;
+        for (position, task_id) in task_ids.iter().enumerate() {
+            let position = position as i64;
+            sqlx::query!(
+                "UPDATE task_subtasks SET position = $3 WHERE parent_task_id = $1 AND task_id = $2",
+                parent_task_id,
+                task_id,
+                position
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Recompute the status of `task_id`'s parent, and of its parent in turn
+    pub async fn roll_up(pool: &SqlitePool, task_id: Uuid) -> Result<(), sqlx::Error> {
+        match Self::find_parent_id(pool, task_id).await? {
+            Some(parent_id) => Self::refresh_status(pool, parent_id).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Set a parent task's status from its subtasks, then roll the change up its ancestors
+    pub async fn refresh_status(
+        pool: &SqlitePool,
+        parent_task_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        let mut next = Some(parent_task_id);
+        // Bounded by the depth of the tree, which set_parent keeps acyclic
+        while let Some(parent_id) = next.take() {
+            let statuses: Vec<TaskStatus> = Self::find_subtasks(pool, parent_id)
+                .await?
+                .into_iter()
+                .map(|task| task.status)
+                .collect();
+            let Some(status) = rollup_status(&statuses) else {
+                break;
+            };
+            let result = sqlx::query!(
+                "UPDATE tasks SET status = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1 AND status != $2",
+                parent_id,
+                status
+            )
+            .execute(pool)
+            .await?;
+            if result.rows_affected() > 0 {
+                next = Self::find_parent_id(pool, parent_id).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rollup_status() {
+        use TaskStatus::*;
+
+        assert_eq!(rollup_status(&[]), None);
+        assert_eq!(rollup_status(&[Todo, Todo]), Some(Todo));
+        assert_eq!(rollup_status(&[Todo, Done]), Some(InProgress));
+        assert_eq!(rollup_status(&[InProgress, InReview]), Some(InProgress));
+        assert_eq!(rollup_status(&[InReview, Done, Cancelled]), Some(InReview));
+        assert_eq!(rollup_status(&[Done, Cancelled]), Some(Done));
+        assert_eq!(rollup_status(&[Cancelled, Cancelled]), Some(Cancelled));
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Reusable starting point for a task, available in one project or, without a project, in all
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskTemplate {
+    pub id: Uuid,
+    /// `None` for templates shared by every project
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    /// Task title; `{{variable}}` placeholders are filled in when the task is created
+    pub title_pattern: String,
+    /// Description boilerplate, with the same placeholders as the title
+    pub description: Option<String>,
+    #[ts(type = "Array<string>")]
+    pub labels: Json<Vec<String>>,
+    #[ts(type = "ExecutorProfileId | null")]
+    pub executor_profile_id: Option<Json<ExecutorProfileId>>,
+    /// Branch attempts started from the template target in each project repo
+    pub base_branch: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateTaskTemplate {
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    pub title_pattern: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    pub base_branch: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateTaskTemplate {
+    pub name: Option<String>,
+    pub title_pattern: Option<String>,
+    /// An empty string clears the description
+    pub description: Option<String>,
+    pub labels: Option<Vec<String>>,
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    pub clear_executor_profile_id: Option<bool>,
+    /// An empty string clears the base branch
+    pub base_branch: Option<String>,
+}
+
+/// Replace `{{name}}` placeholders with `variables`, returning the names left without a value
+pub fn render_template(
+    template: &str,
+    variables: &HashMap<String, String>,
+) -> Result<String, Vec<String>> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut missing = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        rendered.push_str(&rest[..start]);
+        let name = rest[start + 2..start + 2 + end].trim();
+        match variables.get(name) {
+            Some(value) => rendered.push_str(value),
+            None => {
+                if !missing.iter().any(|m| m == name) {
+                    missing.push(name.to_string());
+                }
+            }
+        }
+        rest = &rest[start + 2 + end + 2..];
+    }
+    rendered.push_str(rest);
+    if missing.is_empty() {
+        Ok(rendered)
+    } else {
+        Err(missing)
+    }
+}
+
+impl TaskTemplate {
+    /// Global templates, plus the project's own when `project_id` is set
+    pub async fn find_available(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id: Uuid", name, title_pattern, description, labels as "labels!: Json<Vec<String>>", executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>", base_branch, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_templates
+               WHERE project_id IS NULL OR project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id: Uuid", name, title_pattern, description, labels as "labels!: Json<Vec<String>>", executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>", base_branch, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_templates
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, data: &CreateTaskTemplate) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let labels = Json(&data.labels);
+        let executor_profile_id = data.executor_profile_id.as_ref().map(Json);
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"INSERT INTO task_templates (id, project_id, name, title_pattern, description, labels, executor_profile_id, base_branch)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+               RETURNING id as "id!: Uuid", project_id as "project_id: Uuid", name, title_pattern, description, labels as "labels!: Json<Vec<String>>", executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>", base_branch, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.name,
+            data.title_pattern,
+            data.description,
+            labels,
+            executor_profile_id,
+            data.base_branch
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateTaskTemplate,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let title_pattern = data
+            .title_pattern
+            .as_ref()
+            .unwrap_or(&existing.title_pattern);
+        let description = match data.description.as_deref() {
+            Some(s) if s.trim().is_empty() => None,
+            Some(s) => Some(s),
+            None => existing.description.as_deref(),
+        };
+        let labels = Json(data.labels.as_ref().unwrap_or(&existing.labels.0));
+        let executor_profile_id = if data.clear_executor_profile_id.unwrap_or(false) {
+            None
+        } else {
+            data.executor_profile_id
+                .as_ref()
+                .or(existing.executor_profile_id.as_ref().map(|id| &id.0))
+                .map(Json)
+        };
+        let base_branch = match data.base_branch.as_deref() {
+            Some(s) if s.trim().is_empty() => None,
+            Some(s) => Some(s),
+            None => existing.base_branch.as_deref(),
+        };
+
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"UPDATE task_templates
+               SET name = $2, title_pattern = $3, description = $4, labels = $5, executor_profile_id = $6, base_branch = $7, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id: Uuid", name, title_pattern, description, labels as "labels!: Json<Vec<String>>", executor_profile_id as "executor_profile_id: Json<ExecutorProfileId>", base_branch, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            title_pattern,
+            description,
+            labels,
+            executor_profile_id,
+            base_branch
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_templates WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let variables = HashMap::from([("test".to_string(), "login_spec".to_string())]);
+        assert_eq!(
+            render_template("Fix flaky test {{ test }} ({{test}})", &variables),
+            Ok("Fix flaky test login_spec (login_spec)".to_string())
+        );
+        assert_eq!(
+            render_template("{{test}} on {{branch}} via {{branch}}", &variables),
+            Err(vec!["branch".to_string()])
+        );
+        assert_eq!(
+            render_template("Unclosed {{test", &variables),
+            Ok("Unclosed {{test".to_string())
+        );
+    }
+}
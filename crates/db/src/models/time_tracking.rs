@@ -0,0 +1,200 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Time spent on one task attempt. Only setup, coding agent and cleanup processes count, so a
+/// dev server left running doesn't inflate it; running processes count up to now.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AttemptTime {
+    pub workspace_id: Uuid,
+    pub task_id: Uuid,
+    /// When the attempt's first process started; `None` if nothing has run yet
+    pub started_at: Option<DateTime<Utc>>,
+    /// From the first process starting to the last one finishing
+    pub wall_clock_seconds: i64,
+    /// Sum of the processes' own run times
+    pub active_seconds: i64,
+    pub running: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+pub struct TimeTotals {
+    pub attempts: i64,
+    pub wall_clock_seconds: i64,
+    pub active_seconds: i64,
+}
+
+impl TimeTotals {
+    pub fn sum<'a>(attempts: impl IntoIterator<Item = &'a AttemptTime>) -> Self {
+        attempts
+            .into_iter()
+            .fold(Self::default(), |mut totals, attempt| {
+                totals.attempts += 1;
+                totals.wall_clock_seconds += attempt.wall_clock_seconds;
+                totals.active_seconds += attempt.active_seconds;
+                totals
+            })
+    }
+}
+
+/// A task's attempts, newest first, with their totals
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskTime {
+    pub task_id: Uuid,
+    pub totals: TimeTotals,
+    pub attempts: Vec<AttemptTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TaskTimeTotals {
+    pub task_id: Uuid,
+    pub title: String,
+    pub totals: TimeTotals,
+}
+
+/// Time spent across a project, and per task for tasks with at least one attempt
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProjectTime {
+    pub project_id: Uuid,
+    pub totals: TimeTotals,
+    /// Most time spent first
+    pub tasks: Vec<TaskTimeTotals>,
+}
+
+/// `1h 5m`, `12m 30s` or `45s`
+pub fn format_seconds(seconds: i64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+impl AttemptTime {
+    /// Attempts matching every filter that is set, newest first
+    async fn find_where(
+        pool: &SqlitePool,
+        workspace_id: Option<Uuid>,
+        task_id: Option<Uuid>,
+        project_id: Option<Uuid>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptTime,
+            r#"SELECT
+                 w.id as "workspace_id!: Uuid",
+                 w.task_id as "task_id!: Uuid",
+                 MIN(ep.started_at) as "started_at: DateTime<Utc>",
+                 CAST(COALESCE(ROUND((julianday(MAX(COALESCE(ep.completed_at, $4))) - julianday(MIN(ep.started_at))) * 86400), 0) AS INTEGER) as "wall_clock_seconds!: i64",
+                 CAST(COALESCE(ROUND(SUM(julianday(COALESCE(ep.completed_at, $4)) - julianday(ep.started_at)) * 86400), 0) AS INTEGER) as "active_seconds!: i64",
+                 COALESCE(MAX(ep.status = 'running'), 0) as "running!: bool"
+               FROM workspaces w
+               JOIN tasks t ON t.id = w.task_id
+               LEFT JOIN sessions s ON s.workspace_id = w.id
+               LEFT JOIN execution_processes ep ON ep.session_id = s.id
+                 AND ep.run_reason IN ('setupscript', 'cleanupscript', 'codingagent')
+               WHERE ($1 IS NULL OR w.id = $1)
+                 AND ($2 IS NULL OR w.task_id = $2)
+                 AND ($3 IS NULL OR t.project_id = $3)
+               GROUP BY w.id
+               ORDER BY w.created_at DESC"#,
+            workspace_id,
+            task_id,
+            project_id,
+            now
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_for_workspace(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        Ok(
+            Self::find_where(pool, Some(workspace_id), None, None, Utc::now())
+                .await?
+                .into_iter()
+                .next(),
+        )
+    }
+
+    pub async fn find_for_task(pool: &SqlitePool, task_id: Uuid) -> Result<TaskTime, sqlx::Error> {
+        let attempts = Self::find_where(pool, None, Some(task_id), None, Utc::now()).await?;
+        Ok(TaskTime {
+            task_id,
+            totals: TimeTotals::sum(&attempts),
+            attempts,
+        })
+    }
+
+    pub async fn find_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<ProjectTime, sqlx::Error> {
+        let attempts = Self::find_where(pool, None, None, Some(project_id), Utc::now()).await?;
+        let titles = sqlx::query!(
+            r#"SELECT id as "id!: Uuid", title FROM tasks WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut tasks: Vec<TaskTimeTotals> = titles
+            .into_iter()
+            .filter_map(|task| {
+                let task_attempts: Vec<&AttemptTime> = attempts
+                    .iter()
+                    .filter(|attempt| attempt.task_id == task.id)
+                    .collect();
+                (!task_attempts.is_empty()).then(|| TaskTimeTotals {
+                    task_id: task.id,
+                    title: task.title,
+                    totals: TimeTotals::sum(task_attempts),
+                })
+            })
+            .collect();
+        tasks.sort_by_key(|task| std::cmp::Reverse(task.totals.active_seconds));
+
+        Ok(ProjectTime {
+            project_id,
+            totals: TimeTotals::sum(&attempts),
+            tasks,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_totals_and_format() {
+        let attempt = |wall_clock_seconds, active_seconds| AttemptTime {
+            workspace_id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            started_at: None,
+            wall_clock_seconds,
+            active_seconds,
+            running: false,
+        };
+        let attempts = [attempt(600, 420), attempt(3_000, 2_900)];
+        assert_eq!(
+            TimeTotals::sum(&attempts),
+            TimeTotals {
+                attempts: 2,
+                wall_clock_seconds: 3_600,
+                active_seconds: 3_320,
+            }
+        );
+
+        assert_eq!(format_seconds(45), "45s");
+        assert_eq!(format_seconds(750), "12m 30s");
+        assert_eq!(format_seconds(3_900), "1h 5m");
+    }
+}
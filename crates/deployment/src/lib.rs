@@ -6,38 +6,61 @@ use axum::response::sse::Event;
 use db::{
     DBService,
     models::{
+        assignment_rule::TaskSource,
+        epic::Epic,
+        execution_process::ExecutionProcess,
         project::{CreateProject, Project},
         project_repo::CreateProjectRepo,
-        workspace::WorkspaceError,
+        queued_attempt::QueuedAttempt,
+        task::{CreateTask, Task},
+        task_dependency::TaskDependency,
+        task_reference::TaskReference,
+        task_schedule::TaskSchedule,
+        time_tracking::format_seconds,
+        workspace::{Workspace, WorkspaceError},
+        workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
     },
 };
-use executors::executors::ExecutorError;
+use executors::{executors::ExecutorError, profile::ExecutorProfileId};
 use futures::{StreamExt, TryStreamExt};
 use git2::Error as Git2Error;
 use serde_json::Value;
 use services::services::{
     analytics::{AnalyticsContext, AnalyticsService},
     approvals::Approvals,
+    attachment::{AttachmentError, AttachmentService},
     auth::AuthContext,
-    config::{Config, ConfigError},
+    auto_assign,
+    budget,
+    config::{Config, ConfigError, ConfigWatcher, TaskUnblockedAction},
     container::{ContainerError, ContainerService},
+    escalation::EscalationService,
     events::{EventError, EventService},
     file_search_cache::FileSearchCache,
     filesystem::{FilesystemError, FilesystemService},
     filesystem_watcher::FilesystemWatcherError,
     git::{GitService, GitServiceError},
+    github_issue_sync::GitHubIssueSyncService,
     image::{ImageError, ImageService},
+    jira_sync::JiraSyncService,
+    linear_sync::LinearSyncService,
+    notification::{NotificationContext, NotificationEvent, i18n},
     pr_monitor::PrMonitorService,
     project::ProjectService,
     queued_message::QueuedMessageService,
+    recurrence,
     repo::RepoService,
+    run_queue::{self, AttemptStart, RunQueue, RunningAttempts},
     share::SharePublisher,
+    summary::SummaryService,
+    task_references,
+    wip::{self, WipViolation},
     worktree_manager::WorktreeError,
 };
 use sqlx::Error as SqlxError;
 use thiserror::Error;
 use tokio::sync::RwLock;
-use utils::sentry as sentry_utils;
+use utils::{assets::config_path, sentry as sentry_utils};
 
 #[derive(Debug, Clone, Copy, Error)]
 #[error("Remote client not configured")]
@@ -64,6 +87,8 @@ pub enum DeploymentError {
     #[error(transparent)]
     Image(#[from] ImageError),
     #[error(transparent)]
+    Attachment(#[from] AttachmentError),
+    #[error(transparent)]
     Filesystem(#[from] FilesystemError),
     #[error(transparent)]
     Worktree(#[from] WorktreeError),
@@ -77,6 +102,18 @@ pub enum DeploymentError {
     Other(#[from] AnyhowError),
 }
 
+/// Log how starting a queued attempt went
+fn log_queued_start(attempt: &QueuedAttempt, result: &Result<ExecutionProcess, ContainerError>) {
+    match result {
+        Ok(_) => tracing::info!("Started queued attempt for task {}", attempt.task_id),
+        Err(e) => tracing::error!(
+            "Failed to start queued attempt for task {}: {}",
+            attempt.task_id,
+            e
+        ),
+    }
+}
+
 #[async_trait]
 pub trait Deployment: Clone + Send + Sync + 'static {
     async fn new() -> Result<Self, DeploymentError>;
@@ -99,6 +136,8 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn image(&self) -> &ImageService;
 
+    fn attachment(&self) -> &AttachmentService;
+
     fn filesystem(&self) -> &FilesystemService;
 
     fn events(&self) -> &EventService;
@@ -109,6 +148,8 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn queued_message_service(&self) -> &QueuedMessageService;
 
+    fn run_queue(&self) -> &RunQueue;
+
     fn auth_context(&self) -> &AuthContext;
 
     fn share_publisher(&self) -> Result<SharePublisher, RemoteClientNotConfigured>;
@@ -133,7 +174,575 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                 analytics_service: analytics_service.clone(),
             });
         let publisher = self.share_publisher().ok();
-        PrMonitorService::spawn(db, analytics, publisher).await
+        let notification_service = self.container().notification_service().clone();
+        PrMonitorService::spawn(db, analytics, publisher, notification_service).await
+    }
+
+    async fn spawn_escalation_service(&self) -> tokio::task::JoinHandle<()> {
+        let db = self.db().clone();
+        let notification_service = self.container().notification_service().clone();
+        EscalationService::spawn(db, notification_service).await
+    }
+
+    async fn spawn_summary_service(&self) -> tokio::task::JoinHandle<()> {
+        let db = self.db().clone();
+        let notification_service = self.container().notification_service().clone();
+        SummaryService::spawn(db, notification_service).await
+    }
+
+    /// Resolve dependencies whose blockers are done and act on the tasks they unblock
+    async fn spawn_task_dependency_service(&self) -> tokio::task::JoinHandle<()> {
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(15));
+            loop {
+                interval.tick().await;
+                let unblocked = match TaskDependency::resolve_completed(&deployment.db().pool).await
+                {
+                    Ok(unblocked) => unblocked,
+                    Err(e) => {
+                        tracing::error!("Error resolving task dependencies: {}", e);
+                        continue;
+                    }
+                };
+                for task in unblocked {
+                    if let Err(e) = deployment.handle_unblocked_task(&task).await {
+                        tracing::error!("Failed to handle unblocked task {}: {}", task.id, e);
+                    }
+                }
+            }
+        })
+    }
+
+    async fn handle_unblocked_task(&self, task: &Task) -> Result<(), DeploymentError> {
+        let pool = &self.db().pool;
+        let Some(project) = task.parent_project(pool).await? else {
+            return Ok(());
+        };
+        let config = self.config().read().await.for_project(Some(project.id));
+        let mut message_key = "task-unblocked";
+        let mut workspace_id = None;
+
+        match config.on_task_unblocked {
+            TaskUnblockedAction::Nothing => return Ok(()),
+            TaskUnblockedAction::Notify => {}
+            TaskUnblockedAction::Start => {
+                // Start on the branches the most recent blocker attempt targeted
+                let mut latest: Option<Workspace> = None;
+                for blocker in TaskDependency::find_for_task(pool, task.id).await?.blockers {
+                    if let Some(workspace) = Workspace::fetch_all(pool, Some(blocker.id))
+                        .await?
+                        .into_iter()
+                        .next()
+                        && latest
+                            .as_ref()
+                            .is_none_or(|latest| workspace.created_at > latest.created_at)
+                    {
+                        latest = Some(workspace);
+                    }
+                }
+                if let Some(latest) = latest
+                    && !self.budget_blocks_start(project.id).await?
+                {
+                    let repos: Vec<CreateWorkspaceRepo> =
+                        WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, latest.id)
+                            .await?
+                            .into_iter()
+                            .map(|repo| CreateWorkspaceRepo {
+                                repo_id: repo.repo.id,
+                                target_branch: repo.target_branch,
+                            })
+                            .collect();
+                    let violations = self
+                        .wip_violations_for_start(project.id, Some(task), &config.executor_profile)
+                        .await?;
+                    if violations.is_empty() {
+                        let workspace =
+                            self.container().create_task_workspace(task, &repos).await?;
+                        message_key = match self
+                            .start_or_queue_attempt(&workspace, config.executor_profile.clone())
+                            .await?
+                        {
+                            AttemptStart::Started(_) => "task-unblocked-started",
+                            AttemptStart::Queued(_) => "task-unblocked-queued",
+                        };
+                        workspace_id = Some(workspace.id);
+                    } else {
+                        self.notify_wip_limit_exceeded(task, &violations, false)
+                            .await?;
+                    }
+                }
+            }
+        }
+
+        let mut context = NotificationContext::new(NotificationEvent::TaskUnblocked)
+            .with_task(project.id, task.id)
+            .with_names(&project.name, &task.title);
+        if let Some(workspace_id) = workspace_id {
+            context = context.with_workspace(workspace_id);
+        }
+        let notification_service = self.container().notification_service();
+        let locale = notification_service.locale().await;
+        let args = [("task", task.title.as_str())];
+        notification_service
+            .notify(
+                &context,
+                &i18n::t(locale, "task-unblocked-title", &args),
+                &i18n::t(locale, message_key, &args),
+            )
+            .await;
+        Ok(())
+    }
+
+    /// Notify once about each unfinished task that passes its due date
+    async fn spawn_task_due_service(&self) -> tokio::task::JoinHandle<()> {
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                let now = chrono::Utc::now();
+                let overdue = match Task::find_newly_overdue(&deployment.db().pool, now).await {
+                    Ok(overdue) => overdue,
+                    Err(e) => {
+                        tracing::error!("Error loading overdue tasks: {}", e);
+                        continue;
+                    }
+                };
+                for task in overdue {
+                    if let Err(e) = deployment.notify_task_overdue(&task, now).await {
+                        tracing::error!("Failed to notify about overdue task {}: {}", task.id, e);
+                    }
+                }
+            }
+        })
+    }
+
+    async fn notify_task_overdue(
+        &self,
+        task: &Task,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), DeploymentError> {
+        let pool = &self.db().pool;
+        // Marked first so a failing notification isn't retried every tick
+        Task::mark_overdue_notified(pool, task.id, now).await?;
+        let Some(project) = task.parent_project(pool).await? else {
+            return Ok(());
+        };
+        let due = task
+            .due_at
+            .map(|due_at| due_at.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_default();
+
+        let context = NotificationContext::new(NotificationEvent::TaskOverdue)
+            .with_task(project.id, task.id)
+            .with_names(&project.name, &task.title);
+        let notification_service = self.container().notification_service();
+        let locale = notification_service.locale().await;
+        let args = [("task", task.title.as_str()), ("due", due.as_str())];
+        notification_service
+            .notify(
+                &context,
+                &i18n::t(locale, "task-overdue-title", &args),
+                &i18n::t(locale, "task-overdue", &args),
+            )
+            .await;
+        Ok(())
+    }
+
+    /// Limits that starting an attempt with `executor_profile_id` would exceed; `task` is `None`
+    /// for a task that is yet to be created
+    async fn wip_violations_for_start(
+        &self,
+        project_id: uuid::Uuid,
+        task: Option<&Task>,
+        executor_profile_id: &ExecutorProfileId,
+    ) -> Result<Vec<WipViolation>, DeploymentError> {
+        let executor_limits = self.config().read().await.executor_wip_limits.clone();
+        Ok(wip::start_violations(
+            &self.db().pool,
+            &executor_limits,
+            project_id,
+            task,
+            &executor_profile_id.executor.to_string(),
+        )
+        .await?)
+    }
+
+    /// Start an attempt in the workspace if the concurrency limits leave room for it once the
+    /// attempts queued ahead of it have started, otherwise queue it
+    async fn start_or_queue_attempt(
+        &self,
+        workspace: &Workspace,
+        executor_profile_id: ExecutorProfileId,
+    ) -> Result<AttemptStart, DeploymentError> {
+        let queued =
+            QueuedAttempt::create(&self.db().pool, workspace.id, &executor_profile_id).await?;
+        // Attempts queued ahead of this one may start too; only this one is reported to the caller
+        let mut started = None;
+        for (attempt, result) in self.start_queued_attempts().await? {
+            if attempt.id == queued.id {
+                started = Some(result);
+            } else {
+                log_queued_start(&attempt, &result);
+            }
+        }
+        match started {
+            Some(result) => Ok(AttemptStart::Started(result?)),
+            None => Ok(AttemptStart::Queued(queued)),
+        }
+    }
+
+    /// Start the queued attempts that fit under the concurrency limits, returning how each start
+    /// went
+    async fn start_queued_attempts(
+        &self,
+    ) -> Result<Vec<(QueuedAttempt, Result<ExecutionProcess, ContainerError>)>, DeploymentError>
+    {
+        let _starting = self.run_queue().lock().await;
+        let pool = &self.db().pool;
+        let queue = QueuedAttempt::find_all(pool, None).await?;
+        if queue.is_empty() {
+            return Ok(Vec::new());
+        }
+        let config = self.config().read().await.clone();
+        let running = RunningAttempts::load(pool).await?;
+        let startable: Vec<QueuedAttempt> = run_queue::startable(
+            &queue,
+            running,
+            config.max_concurrent_attempts,
+            |project_id| {
+                config
+                    .for_project(Some(project_id))
+                    .max_concurrent_attempts_per_project
+            },
+        )
+        .into_iter()
+        .cloned()
+        .collect();
+
+        let mut results = Vec::new();
+        for attempt in startable {
+            // Dequeued first so a failing start isn't retried on every tick
+            QueuedAttempt::delete(pool, attempt.id).await?;
+            let Some(workspace) = Workspace::find_by_id(pool, attempt.workspace_id).await? else {
+                continue;
+            };
+            let result = self
+                .container()
+                .start_workspace(&workspace, attempt.executor_profile_id.0.clone())
+                .await;
+            results.push((attempt, result));
+        }
+        Ok(results)
+    }
+
+    /// Start queued attempts as running ones finish or limits are raised
+    async fn spawn_run_queue_service(&self) -> tokio::task::JoinHandle<()> {
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(5));
+            loop {
+                interval.tick().await;
+                let results = match deployment.start_queued_attempts().await {
+                    Ok(results) => results,
+                    Err(e) => {
+                        tracing::error!("Error starting queued attempts: {}", e);
+                        continue;
+                    }
+                };
+                for (attempt, result) in results {
+                    log_queued_start(&attempt, &result);
+                }
+            }
+        })
+    }
+
+    /// Whether a budget's hard limit keeps an automatic start in the project from happening
+    async fn budget_blocks_start(&self, project_id: uuid::Uuid) -> Result<bool, DeploymentError> {
+        let Some(status) =
+            budget::hard_limit_reached(&self.db().pool, project_id, chrono::Utc::now()).await?
+        else {
+            return Ok(false);
+        };
+        tracing::info!("Not starting an attempt: {}", budget::describe(&status));
+        Ok(true)
+    }
+
+    /// Tell the user that WIP limits held back an automatic start, or were overridden
+    async fn notify_wip_limit_exceeded(
+        &self,
+        task: &Task,
+        violations: &[WipViolation],
+        overridden: bool,
+    ) -> Result<(), DeploymentError> {
+        let Some(project) = task.parent_project(&self.db().pool).await? else {
+            return Ok(());
+        };
+        let context = NotificationContext::new(NotificationEvent::WipLimitExceeded)
+            .with_task(project.id, task.id)
+            .with_names(&project.name, &task.title);
+        let notification_service = self.container().notification_service();
+        let locale = notification_service.locale().await;
+        let limits = wip::counts(violations);
+        let args = [("task", task.title.as_str()), ("limits", limits.as_str())];
+        let message_key = if overridden {
+            "wip-limit-overridden"
+        } else {
+            "wip-limit-held"
+        };
+        notification_service
+            .notify(
+                &context,
+                &i18n::t(locale, "wip-limit-title", &args),
+                &i18n::t(locale, message_key, &args),
+            )
+            .await;
+        Ok(())
+    }
+
+    /// Archive finished tasks that have sat untouched longer than their project's auto-archive
+    /// policy allows
+    async fn spawn_task_archive_service(&self) -> tokio::task::JoinHandle<()> {
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+            loop {
+                interval.tick().await;
+                if let Err(e) = deployment.auto_archive_tasks(chrono::Utc::now()).await {
+                    tracing::error!("Failed to auto-archive tasks: {}", e);
+                }
+            }
+        })
+    }
+
+    async fn auto_archive_tasks(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), DeploymentError> {
+        let pool = &self.db().pool;
+        for project in Project::find_all(pool).await? {
+            let after_days = self
+                .config()
+                .read()
+                .await
+                .for_project(Some(project.id))
+                .auto_archive_after_days;
+            let Some(after_days) = after_days.filter(|&days| days > 0) else {
+                continue;
+            };
+            let before = now - chrono::Duration::days(after_days.into());
+            let archived = Task::archive_finished_before(pool, project.id, before, now).await?;
+            if archived > 0 {
+                tracing::info!("Auto-archived {} tasks in project {}", archived, project.id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Notify once each epic has every task done or cancelled, with its combined totals
+    async fn spawn_epic_service(&self) -> tokio::task::JoinHandle<()> {
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(15));
+            loop {
+                interval.tick().await;
+                let completed = match Epic::resolve_completed(&deployment.db().pool).await {
+                    Ok(completed) => completed,
+                    Err(e) => {
+                        tracing::error!("Error resolving completed epics: {}", e);
+                        continue;
+                    }
+                };
+                for epic in completed {
+                    let epic_id = epic.id;
+                    if let Err(e) = deployment.notify_epic_completed(epic).await {
+                        tracing::error!("Failed to notify about completed epic {}: {}", epic_id, e);
+                    }
+                }
+            }
+        })
+    }
+
+    async fn notify_epic_completed(&self, epic: Epic) -> Result<(), DeploymentError> {
+        let summary = epic.summary(&self.db().pool).await?;
+        let notification_service = self.container().notification_service();
+        let locale = notification_service.locale().await;
+        let tasks = summary.tasks.len().to_string();
+        let projects = summary.epic.project_count.to_string();
+        let attempts = summary.time.attempts.to_string();
+        let time = format_seconds(summary.time.active_seconds);
+        let args = [
+            ("epic", summary.epic.name.as_str()),
+            ("tasks", tasks.as_str()),
+            ("projects", projects.as_str()),
+            ("attempts", attempts.as_str()),
+            ("time", time.as_str()),
+        ];
+        let message = [
+            i18n::t(locale, "epic-completed", &args),
+            i18n::t(locale, "epic-totals", &args),
+        ]
+        .join("\n");
+        notification_service
+            .notify(
+                &NotificationContext::new(NotificationEvent::EpicCompleted),
+                &i18n::t(locale, "epic-completed-title", &args),
+                &message,
+            )
+            .await;
+        Ok(())
+    }
+
+    /// Resolve the references in task descriptions that changed, or that a new task may now
+    /// resolve, so links and backlinks stay current
+    async fn spawn_task_reference_service(&self) -> tokio::task::JoinHandle<()> {
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(10));
+            loop {
+                interval.tick().await;
+                let pool = &deployment.db().pool;
+                let task_ids = match TaskReference::find_unscanned_task_ids(pool, 50).await {
+                    Ok(task_ids) => task_ids,
+                    Err(e) => {
+                        tracing::error!("Error finding tasks to resolve references for: {}", e);
+                        continue;
+                    }
+                };
+                for task_id in task_ids {
+                    let task = match Task::find_by_id(pool, task_id).await {
+                        Ok(Some(task)) => task,
+                        Ok(None) => continue,
+                        Err(e) => {
+                            tracing::error!("Failed to load task {}: {}", task_id, e);
+                            continue;
+                        }
+                    };
+                    if let Err(e) = task_references::refresh(pool, deployment.git(), &task).await {
+                        tracing::error!("Failed to resolve references of task {}: {}", task_id, e);
+                    }
+                }
+            }
+        })
+    }
+
+    /// Keep tasks of projects with an enabled issue sync in line with their GitHub issues
+    async fn spawn_github_issue_sync_service(&self) -> tokio::task::JoinHandle<()> {
+        GitHubIssueSyncService::spawn(self.db().clone(), self.git().clone()).await
+    }
+
+    /// Import issues for projects with an enabled Jira sync and push completed tasks back
+    async fn spawn_jira_sync_service(&self) -> tokio::task::JoinHandle<()> {
+        JiraSyncService::spawn(self.db().clone(), self.config().clone()).await
+    }
+
+    /// Run first imports of Linear syncs and post attempt results and pull requests back
+    async fn spawn_linear_sync_service(&self) -> tokio::task::JoinHandle<()> {
+        LinearSyncService::spawn(self.db().clone(), self.config().clone()).await
+    }
+
+    /// Create the tasks of recurring schedules as they come due
+    async fn spawn_task_schedule_service(&self) -> tokio::task::JoinHandle<()> {
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+            loop {
+                interval.tick().await;
+                let now = chrono::Utc::now();
+                let due = match TaskSchedule::find_due(&deployment.db().pool, now).await {
+                    Ok(due) => due,
+                    Err(e) => {
+                        tracing::error!("Error loading due task schedules: {}", e);
+                        continue;
+                    }
+                };
+                for schedule in due {
+                    if let Err(e) = deployment.run_task_schedule(&schedule, now).await {
+                        tracing::error!("Failed to run task schedule {}: {}", schedule.id, e);
+                    }
+                }
+            }
+        })
+    }
+
+    /// Create a task from the schedule, start an attempt when it names an executor and repos and
+    /// WIP limits allow, and move the schedule on to its next run. Missed runs are not caught up.
+    async fn run_task_schedule(
+        &self,
+        schedule: &TaskSchedule,
+        ran_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Task, DeploymentError> {
+        let pool = &self.db().pool;
+        let utc_offset = schedule.utc_offset.as_deref();
+        let next_run_at =
+            recurrence::next_run(&schedule.rule, utc_offset, schedule.created_at, ran_at)
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Task schedule {} won't run again: {}", schedule.id, e);
+                    None
+                });
+        // Advance first so a failing schedule isn't retried on every tick
+        TaskSchedule::record_run(pool, schedule.id, None, ran_at, next_run_at).await?;
+
+        let date = recurrence::local_date(utc_offset, ran_at)
+            .unwrap_or_else(|_| ran_at.date_naive())
+            .to_string();
+        let render = |template: &str| template.replace("{{date}}", &date);
+        let create = CreateTask::from_title_description(
+            schedule.project_id,
+            render(&schedule.title),
+            schedule.description.as_deref().map(render),
+        );
+        let task = Task::create(pool, &create, uuid::Uuid::new_v4()).await?;
+        auto_assign::apply(pool, &task, TaskSource::Schedule).await?;
+        TaskSchedule::record_run(pool, schedule.id, Some(task.id), ran_at, next_run_at).await?;
+
+        if let Some(executor_profile_id) = &schedule.executor_profile_id
+            && !schedule.repos.is_empty()
+            && !self.budget_blocks_start(task.project_id).await?
+        {
+            let repos: Vec<CreateWorkspaceRepo> = schedule
+                .repos
+                .iter()
+                .map(|repo| CreateWorkspaceRepo {
+                    repo_id: repo.repo_id,
+                    target_branch: repo.target_branch.clone(),
+                })
+                .collect();
+            let violations = self
+                .wip_violations_for_start(task.project_id, Some(&task), &executor_profile_id.0)
+                .await?;
+            if violations.is_empty() {
+                let workspace = self
+                    .container()
+                    .create_task_workspace(&task, &repos)
+                    .await?;
+                self.start_or_queue_attempt(&workspace, executor_profile_id.0.clone())
+                    .await?;
+            } else {
+                self.notify_wip_limit_exceeded(&task, &violations, false)
+                    .await?;
+            }
+        }
+
+        tracing::info!("Task schedule {} created task {}", schedule.id, task.id);
+        Ok(task)
+    }
+
+    async fn spawn_config_watcher(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let msg_store = self.events().msg_store().clone();
+        match ConfigWatcher::spawn(
+            config_path(),
+            self.config().clone(),
+            msg_store,
+            self.db().clone(),
+        ) {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                tracing::warn!("Config file changes won't be picked up: {}", e);
+                None
+            }
+        }
     }
 
     async fn track_if_analytics_allowed(&self, event_name: &str, properties: Value) {
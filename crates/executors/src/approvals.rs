@@ -1,11 +1,15 @@
 use std::fmt;
 
 use async_trait::async_trait;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
+use ts_rs::TS;
 use workspace_utils::approvals::ApprovalStatus;
 
+use crate::executors::BaseCodingAgent;
+
 /// Errors emitted by executor approval services.
 #[derive(Debug, Error)]
 pub enum ExecutorApprovalError {
@@ -33,6 +37,11 @@ pub trait ExecutorApprovalService: Send + Sync {
         tool_input: Value,
         tool_call_id: &str,
     ) -> Result<ApprovalStatus, ExecutorApprovalError>;
+
+    /// Shell commands to ask about even when the agent otherwise runs without asking
+    fn command_gate(&self) -> Option<&CommandGate> {
+        None
+    }
 }
 
 #[derive(Debug, Default)]
@@ -50,7 +59,142 @@ impl ExecutorApprovalService for NoopExecutorApprovalService {
     }
 }
 
+/// Shell commands an agent must get approved before running them, even when it otherwise runs
+/// without asking. Only Claude Code can be gated this way; agents in their own approvals mode
+/// already ask about every command.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct ApprovalGates {
+    /// Regexes searched for in the command line, e.g. `rm\s+-rf` or `git\s+push`
+    pub command_patterns: Vec<String>,
+}
+
+impl ApprovalGates {
+    /// Why the gates can't hold for `agent`, when there are any and it isn't Claude Code
+    pub fn unenforced_warning(&self, agent: BaseCodingAgent) -> Option<String> {
+        (!self.command_patterns.is_empty() && agent != BaseCodingAgent::ClaudeCode).then(|| {
+            format!(
+                "Approval gates can't be enforced for {agent}: gated commands only need approval \
+                 when {agent} is set to ask about every command itself"
+            )
+        })
+    }
+}
+
+/// The approval gates with their patterns compiled
+#[derive(Debug, Clone)]
+pub struct CommandGate {
+    patterns: Vec<Regex>,
+}
+
+impl CommandGate {
+    /// `None` without any valid patterns; invalid ones are skipped
+    pub fn new(gates: &ApprovalGates) -> Option<Self> {
+        let patterns: Vec<Regex> = gates
+            .command_patterns
+            .iter()
+            .filter_map(|pattern| match Regex::new(pattern) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    tracing::warn!(
+                        "Skipping invalid approval gate pattern '{}': {}",
+                        pattern,
+                        e
+                    );
+                    None
+                }
+            })
+            .collect();
+        (!patterns.is_empty()).then_some(Self { patterns })
+    }
+
+    /// The first pattern matching the shell command in a tool call's input, if it has one
+    pub fn matching_pattern(&self, tool_input: &Value) -> Option<&str> {
+        let command = shell_command(tool_input)?;
+        self.patterns
+            .iter()
+            .find(|pattern| pattern.is_match(&command))
+            .map(Regex::as_str)
+    }
+}
+
+/// The command line of a shell tool call: a `command` string, as Claude Code sends, or an
+/// argument list, as Codex sends
+pub fn shell_command(tool_input: &Value) -> Option<String> {
+    match tool_input.get("command")? {
+        Value::String(command) => Some(command.clone()),
+        Value::Array(args) => Some(
+            args.iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ToolCallMetadata {
     pub tool_call_id: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_unenforced_warning() {
+        let gates = ApprovalGates {
+            command_patterns: vec![r"rm\s+-rf".to_string()],
+        };
+        assert!(
+            gates
+                .unenforced_warning(BaseCodingAgent::ClaudeCode)
+                .is_none()
+        );
+        assert!(gates.unenforced_warning(BaseCodingAgent::Codex).is_some());
+        assert!(
+            ApprovalGates::default()
+                .unenforced_warning(BaseCodingAgent::Codex)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_command_gate() {
+        let gate = CommandGate::new(&ApprovalGates {
+            command_patterns: vec![
+                r"rm\s+-rf".to_string(),
+                "(".to_string(),
+                r"git\s+push\b".to_string(),
+            ],
+        })
+        .unwrap();
+
+        assert_eq!(
+            gate.matching_pattern(&json!({ "command": "cd app && rm -rf node_modules" })),
+            Some(r"rm\s+-rf")
+        );
+        assert_eq!(
+            gate.matching_pattern(&json!({ "command": ["git", "push", "--force"] })),
+            Some(r"git\s+push\b")
+        );
+        assert_eq!(
+            gate.matching_pattern(&json!({ "command": "git pushd" })),
+            None
+        );
+        assert_eq!(
+            gate.matching_pattern(&json!({ "file_path": "rm -rf" })),
+            None
+        );
+
+        assert!(
+            CommandGate::new(&ApprovalGates {
+                command_patterns: vec!["(".to_string()],
+            })
+            .is_none()
+        );
+    }
+}
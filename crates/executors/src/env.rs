@@ -1,19 +1,43 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ffi::OsStr};
 
+use command_group::AsyncGroupChild;
 use tokio::process::Command;
 
-use crate::command::CmdOverrides;
+use crate::{command::CmdOverrides, process_limits::ProcessConfinement, sandbox::Sandbox};
 
 /// Environment variables to inject into executor processes
 #[derive(Debug, Clone, Default)]
 pub struct ExecutionEnv {
     pub vars: HashMap<String, String>,
+    /// Container the executor runs in instead of the host
+    pub sandbox: Option<Sandbox>,
+    /// Resource limits and priority for the executor and everything it starts
+    pub confinement: Option<ProcessConfinement>,
 }
 
 impl ExecutionEnv {
     pub fn new() -> Self {
         Self {
             vars: HashMap::new(),
+            sandbox: None,
+            confinement: None,
+        }
+    }
+
+    /// A command running `program`, under the process limits when there are any. A sandboxed
+    /// command is limited by its container instead.
+    pub fn command(&self, program: impl AsRef<OsStr>) -> Command {
+        match &self.confinement {
+            Some(confinement) if self.sandbox.is_none() => confinement.command(program),
+            _ => Command::new(program),
+        }
+    }
+
+    /// Apply the process limits that only take effect once the process is spawned
+    pub fn confine(&self, child: &mut AsyncGroupChild) -> std::io::Result<()> {
+        match &self.confinement {
+            Some(confinement) if self.sandbox.is_none() => confinement.confine(child),
+            _ => Ok(()),
         }
     }
 
@@ -43,11 +67,15 @@ impl ExecutionEnv {
         }
     }
 
-    /// Apply all environment variables to a Command
+    /// Apply all environment variables to a Command. With a sandbox, the command is then
+    /// replaced by one running it in the container, so this comes last before spawning.
     pub fn apply_to_command(&self, command: &mut Command) {
         for (key, value) in &self.vars {
             command.env(key, value);
         }
+        if let Some(sandbox) = &self.sandbox {
+            *command = sandbox.wrap(command);
+        }
     }
 
     pub fn contains_key(&self, key: &str) -> bool {
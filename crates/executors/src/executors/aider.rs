@@ -0,0 +1,261 @@
+use std::{path::Path, process::Stdio, sync::Arc};
+
+use async_trait::async_trait;
+use command_group::AsyncCommandGroup;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use strum_macros::AsRefStr;
+use ts_rs::TS;
+use workspace_utils::msg_store::MsgStore;
+
+use crate::{
+    command::{CmdOverrides, CommandBuilder, CommandParts, apply_overrides},
+    env::ExecutionEnv,
+    executors::{
+        AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+    },
+    local_llm::{LocalLlm, LocalLlmProvider},
+    logs::utils::EntryIndexProvider,
+    openrouter::OpenRouter,
+    stdout_dup,
+};
+
+pub mod normalize_logs;
+pub mod session;
+
+use self::{
+    normalize_logs::{SESSION_PREFIX, normalize_logs},
+    session::{AiderSession, exclude_aider_files},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema, AsRefStr)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+#[ts(rename = "AiderEditFormat")]
+pub enum EditFormat {
+    Diff,
+    DiffFenced,
+    Udiff,
+    Whole,
+}
+
+/// Aider executor configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+pub struct Aider {
+    #[serde(default)]
+    pub append_prompt: AppendPrompt,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Model",
+        description = "Model to use (e.g., sonnet, opus, gpt-5, gemini, deepseek, openrouter/anthropic/claude-sonnet-4.5)"
+    )]
+    pub model: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Edit Format",
+        description = "How the model describes edits; aider picks one for the model when unset"
+    )]
+    pub edit_format: Option<EditFormat>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Auto Commits",
+        description = "Commit each change aider makes (default: true)"
+    )]
+    pub auto_commits: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Local Model",
+        description = "Run a model served by Ollama or an OpenAI-compatible server; replaces Model"
+    )]
+    pub local_llm: Option<LocalLlm>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "OpenRouter",
+        description = "Route the model through OpenRouter with the OPENROUTER_API_KEY key; replaces Model"
+    )]
+    pub openrouter: Option<OpenRouter>,
+
+    #[serde(flatten)]
+    pub cmd: CmdOverrides,
+}
+
+impl Aider {
+    /// LiteLLM provider prefix of the local model
+    fn local_provider(local_llm: &LocalLlm) -> &'static str {
+        match local_llm.provider {
+            LocalLlmProvider::Ollama => "ollama_chat",
+            LocalLlmProvider::OpenaiCompatible => "openai",
+        }
+    }
+
+    fn model_name(&self) -> Option<String> {
+        match (&self.local_llm, &self.openrouter) {
+            (Some(local_llm), _) => Some(format!(
+                "{}/{}",
+                Self::local_provider(local_llm),
+                local_llm.model
+            )),
+            (None, Some(openrouter)) => Some(format!("openrouter/{}", openrouter.model)),
+            (None, None) => self.model.clone(),
+        }
+    }
+
+    fn build_command_builder(&self) -> CommandBuilder {
+        let mut builder = CommandBuilder::new("uvx --python 3.12 --from aider-chat@0.86.1 aider")
+            .params([
+                "--yes-always",
+                "--no-pretty",
+                "--no-fancy-input",
+                "--no-check-update",
+                "--no-show-release-notes",
+                "--analytics-disable",
+                "--no-gitignore",
+            ]);
+
+        if let Some(model) = self.model_name() {
+            builder = builder.extend_params(["--model".to_string(), model]);
+        }
+
+        if let Some(edit_format) = &self.edit_format {
+            builder = builder.extend_params(["--edit-format", edit_format.as_ref()]);
+        }
+
+        match self.auto_commits {
+            Some(true) => builder = builder.extend_params(["--auto-commits"]),
+            Some(false) => builder = builder.extend_params(["--no-auto-commits"]),
+            None => {}
+        }
+
+        apply_overrides(builder, &self.cmd)
+    }
+
+    async fn spawn_aider(
+        &self,
+        command_parts: CommandParts,
+        session: AiderSession,
+        prompt: &str,
+        current_dir: &Path,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        if let Err(e) = exclude_aider_files(current_dir) {
+            tracing::warn!("Failed to exclude aider files from git: {}", e);
+        }
+
+        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let message_file = session
+            .write_message(&combined_prompt)
+            .map_err(ExecutorError::Io)?;
+        let (program_path, args) = command_parts.into_resolved().await?;
+
+        let mut command = env.command(program_path);
+        command
+            .kill_on_drop(true)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(current_dir)
+            .args(&args)
+            .args(session.args(&message_file))
+            .env("NO_COLOR", "1");
+
+        if let Some(local_llm) = &self.local_llm {
+            match local_llm.provider {
+                LocalLlmProvider::Ollama => {
+                    command.env("OLLAMA_API_BASE", local_llm.base_url());
+                }
+                LocalLlmProvider::OpenaiCompatible => {
+                    // Local servers ignore the key, but LiteLLM refuses to call without one
+                    command
+                        .env("OPENAI_API_BASE", local_llm.openai_base_url())
+                        .env("OPENAI_API_KEY", "local");
+                }
+            }
+            if let (Some(context_window), Some(model)) =
+                (local_llm.context_window, self.model_name())
+            {
+                let metadata_file = session
+                    .write_model_metadata(&model, Self::local_provider(local_llm), context_window)
+                    .map_err(ExecutorError::Io)?;
+                command.arg("--model-metadata-file").arg(metadata_file);
+            }
+        }
+
+        env.clone()
+            .with_profile(&self.cmd)
+            .apply_to_command(&mut command);
+
+        let mut child = command.group_spawn()?;
+
+        let (_, appender) = stdout_dup::tee_stdout_with_appender(&mut child)?;
+        appender.append_line(format!("{SESSION_PREFIX}{}\n", session.id));
+
+        Ok(child.into())
+    }
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for Aider {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let session = AiderSession::new().map_err(ExecutorError::Io)?;
+        let command_parts = self.build_command_builder().build_initial()?;
+
+        self.spawn_aider(command_parts, session, prompt, current_dir, env)
+            .await
+    }
+
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let session = AiderSession::fork(session_id).map_err(|e| {
+            ExecutorError::FollowUpNotSupported(format!(
+                "Failed to fork Aider session {session_id}: {e}"
+            ))
+        })?;
+        let command_parts = self
+            .build_command_builder()
+            .build_follow_up(&["--restore-chat-history".to_string()])?;
+
+        self.spawn_aider(command_parts, session, prompt, current_dir, env)
+            .await
+    }
+
+    fn normalize_logs(&self, msg_store: Arc<MsgStore>, current_dir: &Path) {
+        normalize_logs(
+            msg_store.clone(),
+            current_dir,
+            EntryIndexProvider::start_from(&msg_store),
+        );
+    }
+
+    // Aider has no MCP support
+    fn default_mcp_config_path(&self) -> Option<std::path::PathBuf> {
+        None
+    }
+
+    fn get_availability_info(&self) -> AvailabilityInfo {
+        // Aider keeps its caches and analytics id in ~/.aider once it has run
+        let installation_indicator_found = dirs::home_dir()
+            .map(|home| home.join(".aider").exists())
+            .unwrap_or(false);
+
+        if installation_indicator_found {
+            AvailabilityInfo::InstallationFound
+        } else {
+            AvailabilityInfo::NotFound
+        }
+    }
+}
@@ -0,0 +1,446 @@
+use std::{path::Path, sync::Arc};
+
+use futures::StreamExt;
+use serde_json::json;
+use workspace_utils::{diff::create_unified_diff, msg_store::MsgStore};
+
+use crate::logs::{
+    ActionType, FileChange, NormalizedEntry, NormalizedEntryError, NormalizedEntryType, ToolStatus,
+    stderr_processor::normalize_stderr_logs,
+    utils::{EntryIndexProvider, patch::add_normalized_entry},
+};
+
+/// Prefix of the line carrying the session id, appended to stdout when aider is spawned
+pub const SESSION_PREFIX: &str = "[aider-session] ";
+
+pub fn normalize_logs(
+    msg_store: Arc<MsgStore>,
+    _worktree_path: &Path,
+    entry_index_provider: EntryIndexProvider,
+) {
+    normalize_stderr_logs(msg_store.clone(), entry_index_provider.clone());
+
+    tokio::spawn(async move {
+        let mut parser = AiderLogParser::default();
+        let mut stdout_lines = msg_store.stdout_lines_stream();
+
+        while let Some(Ok(line)) = stdout_lines.next().await {
+            if let Some(session_id) = line.strip_prefix(SESSION_PREFIX) {
+                msg_store.push_session_id(session_id.trim().to_string());
+                continue;
+            }
+            for entry in parser.push_line(&line) {
+                add_normalized_entry(&msg_store, &entry_index_provider, entry);
+            }
+        }
+        for entry in parser.finish() {
+            add_normalized_entry(&msg_store, &entry_index_provider, entry);
+        }
+    });
+}
+
+/// Lines aider prints about its own setup rather than the conversation
+const SYSTEM_PREFIXES: &[&str] = &[
+    "Aider v",
+    "Main model:",
+    "Weak model:",
+    "Editor model:",
+    "Model:",
+    "Git repo:",
+    "Repo-map:",
+    "Cur working dir:",
+    "Git working dir:",
+    "Restored previous conversation history.",
+    "Use /help",
+    "Warning:",
+    "https://aider.chat/",
+];
+
+const ERROR_PREFIXES: &[&str] = &[
+    "litellm.",
+    "The API provider",
+    "The LLM did not conform to the edit format.",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TextKind {
+    Assistant,
+    System,
+}
+
+/// A SEARCH/REPLACE block being read
+#[derive(Debug)]
+struct EditBlock {
+    path: String,
+    search: Vec<String>,
+    replace: Vec<String>,
+    in_replace: bool,
+}
+
+/// Turns aider's plain-text output (`--no-pretty`) into normalized entries, one line at a time.
+/// SEARCH/REPLACE blocks are lifted out of the assistant's text and reported as file edits once
+/// aider says it applied them; its auto-commits become commit entries.
+#[derive(Debug, Default)]
+pub struct AiderLogParser {
+    text: Vec<String>,
+    text_kind: Option<TextKind>,
+    block: Option<EditBlock>,
+    last_path: Option<String>,
+    /// Edits by path, waiting for aider to report whether it applied them
+    pending_edits: Vec<(String, Vec<FileChange>)>,
+    skip_closing_fence: bool,
+    /// A shell command was confirmed, so the next `Running ...` line is its start
+    expect_command: bool,
+}
+
+impl AiderLogParser {
+    pub fn push_line(&mut self, line: &str) -> Vec<NormalizedEntry> {
+        let line = strip_ansi_escapes::strip_str(line.trim_end_matches(['\r', '\n']));
+
+        if self.block.is_some() {
+            self.push_block_line(&line);
+            return vec![];
+        }
+        let trimmed = line.trim();
+        if std::mem::take(&mut self.skip_closing_fence) && trimmed.starts_with("```") {
+            return vec![];
+        }
+        if trimmed == "<<<<<<< SEARCH" {
+            self.start_block();
+            return vec![];
+        }
+
+        if trimmed.is_empty() {
+            if !self.text.is_empty() {
+                self.text.push(String::new());
+            }
+            return vec![];
+        }
+        if trimmed.contains("(Y)es/(N)o") {
+            self.expect_command =
+                trimmed.starts_with("Run shell command") && trimmed.ends_with(": y");
+            return vec![];
+        }
+        if SYSTEM_PREFIXES
+            .iter()
+            .any(|prefix| trimmed.starts_with(prefix))
+        {
+            return self.push_text(TextKind::System, trimmed);
+        }
+
+        let Some(entry) = self.marker_entry(trimmed) else {
+            return self.push_text(TextKind::Assistant, &line);
+        };
+        let mut entries = self.flush_text();
+        entries.extend(entry);
+        entries
+    }
+
+    /// Entries still buffered once the output ends
+    pub fn finish(&mut self) -> Vec<NormalizedEntry> {
+        self.flush_text()
+    }
+
+    /// The entry for a line that ends the current text, `None` for plain text. Lines that end
+    /// the text without showing anything, like token counts, give `Some(None)`.
+    fn marker_entry(&mut self, line: &str) -> Option<Option<NormalizedEntry>> {
+        if line.starts_with("Tokens: ") || line.starts_with("Cost: ") {
+            return Some(None);
+        }
+        if let Some(path) = line
+            .strip_prefix("Added ")
+            .and_then(|rest| rest.strip_suffix(" to the chat."))
+            .filter(|path| !path.ends_with("lines of output"))
+        {
+            return Some(Some(tool_entry(
+                "read",
+                ActionType::FileRead {
+                    path: path.to_string(),
+                },
+                path,
+            )));
+        }
+        if let Some(path) = line.strip_prefix("Applied edit to ") {
+            let changes = self
+                .pending_edits
+                .iter()
+                .position(|(pending, _)| pending == path)
+                .map(|index| self.pending_edits.remove(index).1)
+                .unwrap_or_default();
+            return Some(Some(tool_entry(
+                "edit",
+                ActionType::FileEdit {
+                    path: path.to_string(),
+                    changes,
+                },
+                path,
+            )));
+        }
+        if let Some((hash, message)) = parse_commit(line) {
+            return Some(Some(tool_entry(
+                "git commit",
+                ActionType::Tool {
+                    tool_name: "git commit".to_string(),
+                    arguments: Some(json!({ "commit": hash, "message": message })),
+                    result: None,
+                },
+                &format!("{hash} {message}"),
+            )));
+        }
+        if self.expect_command
+            && let Some(command) = line.strip_prefix("Running ")
+        {
+            self.expect_command = false;
+            return Some(Some(tool_entry(
+                "bash",
+                ActionType::CommandRun {
+                    command: command.to_string(),
+                    result: None,
+                },
+                command,
+            )));
+        }
+        if ERROR_PREFIXES.iter().any(|prefix| line.starts_with(prefix)) {
+            self.pending_edits.clear();
+            return Some(Some(NormalizedEntry {
+                timestamp: None,
+                entry_type: NormalizedEntryType::ErrorMessage {
+                    error_type: NormalizedEntryError::Other,
+                },
+                content: line.to_string(),
+                metadata: None,
+            }));
+        }
+        None
+    }
+
+    fn push_text(&mut self, kind: TextKind, line: &str) -> Vec<NormalizedEntry> {
+        let entries = if self.text_kind == Some(kind) {
+            vec![]
+        } else {
+            self.flush_text()
+        };
+        self.text_kind = Some(kind);
+        self.text.push(line.to_string());
+        entries
+    }
+
+    fn flush_text(&mut self) -> Vec<NormalizedEntry> {
+        let kind = self.text_kind.take();
+        let content = std::mem::take(&mut self.text).join("\n").trim().to_string();
+        if content.is_empty() {
+            return vec![];
+        }
+        let entry_type = match kind {
+            Some(TextKind::System) => NormalizedEntryType::SystemMessage,
+            _ => NormalizedEntryType::AssistantMessage,
+        };
+        vec![NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content,
+            metadata: None,
+        }]
+    }
+
+    /// The block's path is on the line before it, with the opening fence either before or after
+    /// the path depending on the edit format; both are taken out of the assistant's text
+    fn start_block(&mut self) {
+        let mut path = None;
+        if self.text_kind == Some(TextKind::Assistant) {
+            while let Some(line) = self.text.pop() {
+                let line = line.trim();
+                if line.starts_with("```") {
+                    if path.is_some() {
+                        break;
+                    }
+                    continue;
+                }
+                if path.is_some() || line.is_empty() {
+                    self.text.push(line.to_string());
+                    break;
+                }
+                path = Some(line.to_string());
+            }
+        }
+        let path = path.or_else(|| self.last_path.clone()).unwrap_or_default();
+        self.last_path = Some(path.clone());
+        self.block = Some(EditBlock {
+            path,
+            search: vec![],
+            replace: vec![],
+            in_replace: false,
+        });
+    }
+
+    fn push_block_line(&mut self, line: &str) {
+        let Some(block) = self.block.as_mut() else {
+            return;
+        };
+        match line.trim() {
+            "=======" if !block.in_replace => block.in_replace = true,
+            ">>>>>>> REPLACE" => {
+                let Some(block) = self.block.take() else {
+                    return;
+                };
+                let change = FileChange::Edit {
+                    unified_diff: create_unified_diff(
+                        &block.path,
+                        &join_lines(&block.search),
+                        &join_lines(&block.replace),
+                    ),
+                    has_line_numbers: false,
+                };
+                match self
+                    .pending_edits
+                    .iter_mut()
+                    .find(|(path, _)| *path == block.path)
+                {
+                    Some((_, changes)) => changes.push(change),
+                    None => self.pending_edits.push((block.path, vec![change])),
+                }
+                self.skip_closing_fence = true;
+            }
+            _ if block.in_replace => block.replace.push(line.to_string()),
+            _ => block.search.push(line.to_string()),
+        }
+    }
+}
+
+fn join_lines(lines: &[String]) -> String {
+    if lines.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", lines.join("\n"))
+    }
+}
+
+/// `Commit 1a2b3c4 feat: message`, as printed after each auto-commit
+fn parse_commit(line: &str) -> Option<(&str, &str)> {
+    let (hash, message) = line.strip_prefix("Commit ")?.split_once(' ')?;
+    let is_hash = hash.len() >= 7 && hash.chars().all(|c| c.is_ascii_hexdigit());
+    is_hash.then_some((hash, message.trim()))
+}
+
+fn tool_entry(tool_name: &str, action_type: ActionType, content: &str) -> NormalizedEntry {
+    NormalizedEntry {
+        timestamp: None,
+        entry_type: NormalizedEntryType::ToolUse {
+            tool_name: tool_name.to_string(),
+            action_type,
+            status: ToolStatus::Success,
+        },
+        content: content.to_string(),
+        metadata: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(output: &str) -> Vec<NormalizedEntry> {
+        let mut parser = AiderLogParser::default();
+        let mut entries: Vec<_> = output
+            .lines()
+            .flat_map(|line| parser.push_line(line))
+            .collect();
+        entries.extend(parser.finish());
+        entries
+    }
+
+    #[test]
+    fn test_edit_blocks_and_commits() {
+        let output = "Aider v0.86.1
+Main model: anthropic/claude-sonnet-4-20250514 with diff edit format
+Git repo: .git with 42 files
+
+I'll add a greeting.
+
+src/main.rs
+```rust
+<<<<<<< SEARCH
+fn main() {
+}
+=======
+fn main() {
+    println!(\"hello\");
+}
+>>>>>>> REPLACE
+```
+
+Tokens: 5.2k sent, 210 received. Cost: $0.02 message, $0.02 session.
+Applied edit to src/main.rs
+Commit 3f2a1bc feat: Print a greeting
+Run shell command? (Y)es/(N)o/(D)on't ask again [Yes]: y
+Running cargo test
+Done.";
+        let entries = parse(output);
+        assert_eq!(entries.len(), 6, "{entries:#?}");
+
+        assert!(matches!(
+            entries[0].entry_type,
+            NormalizedEntryType::SystemMessage
+        ));
+        assert!(entries[0].content.starts_with("Aider v0.86.1\nMain model:"));
+
+        assert!(matches!(
+            entries[1].entry_type,
+            NormalizedEntryType::AssistantMessage
+        ));
+        assert_eq!(entries[1].content, "I'll add a greeting.");
+
+        let NormalizedEntryType::ToolUse {
+            action_type: ActionType::FileEdit { path, changes },
+            ..
+        } = &entries[2].entry_type
+        else {
+            panic!("expected a file edit: {:?}", entries[2]);
+        };
+        assert_eq!(path, "src/main.rs");
+        let [FileChange::Edit { unified_diff, .. }] = changes.as_slice() else {
+            panic!("expected one edit: {changes:?}");
+        };
+        assert!(unified_diff.contains("+    println!(\"hello\");"));
+
+        let NormalizedEntryType::ToolUse {
+            action_type: ActionType::Tool { arguments, .. },
+            ..
+        } = &entries[3].entry_type
+        else {
+            panic!("expected a commit: {:?}", entries[3]);
+        };
+        assert_eq!(
+            arguments,
+            &Some(json!({ "commit": "3f2a1bc", "message": "feat: Print a greeting" }))
+        );
+
+        assert!(matches!(
+            &entries[4].entry_type,
+            NormalizedEntryType::ToolUse {
+                action_type: ActionType::CommandRun { command, .. },
+                ..
+            } if command == "cargo test"
+        ));
+        assert_eq!(entries[5].content, "Done.");
+    }
+
+    #[test]
+    fn test_errors_and_prompts() {
+        let output = "Add .aider* to .gitignore (recommended)? (Y)es/(N)o [Yes]: y
+litellm.AuthenticationError: AnthropicException - invalid x-api-key
+Commit message without a hash
+Running this again should work.";
+        let entries = parse(output);
+        assert_eq!(entries.len(), 2, "{entries:#?}");
+        assert!(matches!(
+            entries[0].entry_type,
+            NormalizedEntryType::ErrorMessage { .. }
+        ));
+        assert!(matches!(
+            entries[1].entry_type,
+            NormalizedEntryType::AssistantMessage
+        ));
+        assert!(entries[1].content.ends_with("should work."));
+    }
+}
@@ -0,0 +1,123 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use uuid::Uuid;
+use workspace_utils::path::get_vibe_kanban_temp_dir;
+
+/// Aider keeps a conversation only as its markdown chat history, so a session is a directory
+/// holding that history and the input history, kept outside the worktree
+pub struct AiderSession {
+    pub id: String,
+    dir: PathBuf,
+}
+
+impl AiderSession {
+    pub fn new() -> io::Result<Self> {
+        let id = Uuid::new_v4().to_string();
+        let dir = sessions_root().join(&id);
+        fs::create_dir_all(&dir)?;
+        Ok(Self { id, dir })
+    }
+
+    /// A new session starting from the history of `session_id`, which is left untouched
+    pub fn fork(session_id: &str) -> io::Result<Self> {
+        let source = sessions_root().join(session_id);
+        let history = fs::read(source.join(CHAT_HISTORY))?;
+        let session = Self::new()?;
+        fs::write(session.chat_history(), history)?;
+        if let Ok(input) = fs::read(source.join(INPUT_HISTORY)) {
+            fs::write(session.dir.join(INPUT_HISTORY), input)?;
+        }
+        Ok(session)
+    }
+
+    pub fn chat_history(&self) -> PathBuf {
+        self.dir.join(CHAT_HISTORY)
+    }
+
+    /// Writes the prompt to a file so it isn't limited by the command line length
+    pub fn write_message(&self, prompt: &str) -> io::Result<PathBuf> {
+        let path = self.dir.join(MESSAGE);
+        fs::write(&path, prompt)?;
+        Ok(path)
+    }
+
+    /// Writes a model metadata file so aider knows the context window of a local model
+    pub fn write_model_metadata(
+        &self,
+        model: &str,
+        litellm_provider: &str,
+        context_window: u32,
+    ) -> io::Result<PathBuf> {
+        let metadata = serde_json::json!({
+            model: {
+                "max_input_tokens": context_window,
+                "max_tokens": context_window,
+                "litellm_provider": litellm_provider,
+                "mode": "chat",
+                "input_cost_per_token": 0,
+                "output_cost_per_token": 0,
+            }
+        });
+        let path = self.dir.join(MODEL_METADATA);
+        fs::write(&path, metadata.to_string())?;
+        Ok(path)
+    }
+
+    pub fn args(&self, message_file: &Path) -> Vec<String> {
+        [
+            ("--chat-history-file", self.chat_history()),
+            ("--input-history-file", self.dir.join(INPUT_HISTORY)),
+            ("--message-file", message_file.to_path_buf()),
+        ]
+        .into_iter()
+        .flat_map(|(flag, path)| [flag.to_string(), path.to_string_lossy().into_owned()])
+        .collect()
+    }
+}
+
+const CHAT_HISTORY: &str = "chat.history.md";
+const INPUT_HISTORY: &str = "input.history";
+const MESSAGE: &str = "message.md";
+const MODEL_METADATA: &str = "model.metadata.json";
+
+fn sessions_root() -> PathBuf {
+    get_vibe_kanban_temp_dir().join("aider_sessions")
+}
+
+/// Aider keeps its repo-map cache (`.aider.tags.cache.*`) in the repository root. Ignore its
+/// files through the repository's exclude file, so they are never committed and `.gitignore` is
+/// left alone.
+pub fn exclude_aider_files(current_dir: &Path) -> io::Result<()> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--path-format=absolute", "--git-common-dir"])
+        .current_dir(current_dir)
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    let common_dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    let exclude = common_dir.join("info").join("exclude");
+
+    let contents = fs::read_to_string(&exclude).unwrap_or_default();
+    if contents.lines().any(|line| line.trim() == AIDER_PATTERN) {
+        return Ok(());
+    }
+    let mut updated = contents;
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated.push_str(AIDER_PATTERN);
+    updated.push('\n');
+    if let Some(parent) = exclude.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(exclude, updated)
+}
+
+const AIDER_PATTERN: &str = ".aider*";
@@ -56,7 +56,7 @@ impl StandardCodingAgentExecutor for Amp {
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
-        let mut command = Command::new(executable_path);
+        let mut command = env.command(executable_path);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -129,7 +129,7 @@ impl StandardCodingAgentExecutor for Amp {
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
-        let mut command = Command::new(continue_program);
+        let mut command = env.command(continue_program);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
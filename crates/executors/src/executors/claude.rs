@@ -10,7 +10,6 @@ use command_group::AsyncCommandGroup;
 use futures::StreamExt;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use tokio::process::Command;
 use ts_rs::TS;
 use workspace_utils::{
     approvals::ApprovalStatus, diff::create_unified_diff, log_msg::LogMsg, msg_store::MsgStore,
@@ -18,7 +17,7 @@ use workspace_utils::{
 };
 
 use self::{
-    client::{AUTO_APPROVE_CALLBACK_ID, ClaudeAgentClient},
+    client::{AUTO_APPROVE_CALLBACK_ID, COMMAND_GATE_CALLBACK_ID, ClaudeAgentClient},
     protocol::ProtocolPeer,
     types::PermissionMode,
 };
@@ -93,7 +92,7 @@ impl ClaudeCode {
         if plan && approvals {
             tracing::warn!("Both plan and approvals are enabled. Plan will take precedence.");
         }
-        if plan || approvals {
+        if plan || approvals || self.command_gated() {
             // Enable bypass at startup, otherwise we cannot change to it after exiting plan mode
             builder = builder.extend_params(["--permission-prompt-tool=stdio"]);
             builder = builder.extend_params([format!(
@@ -118,6 +117,17 @@ impl ClaudeCode {
         apply_overrides(builder, &self.cmd)
     }
 
+    /// Whether shell commands go through the command gate, which only happens when the agent
+    /// doesn't already ask about every tool call
+    fn command_gated(&self) -> bool {
+        !self.plan.unwrap_or(false)
+            && !self.approvals.unwrap_or(false)
+            && self
+                .approvals_service
+                .as_ref()
+                .is_some_and(|service| service.command_gate().is_some())
+    }
+
     pub fn permission_mode(&self) -> PermissionMode {
         if self.plan.unwrap_or(false) {
             PermissionMode::Plan
@@ -151,6 +161,15 @@ impl ClaudeCode {
                     }
                 ]
             }))
+        } else if self.command_gated() {
+            Some(serde_json::json!({
+                "PreToolUse": [
+                    {
+                        "matcher": "^Bash$",
+                        "hookCallbackIds": [COMMAND_GATE_CALLBACK_ID],
+                    }
+                ]
+            }))
         } else {
             None
         }
@@ -241,7 +260,7 @@ impl ClaudeCode {
         let (program_path, args) = command_parts.into_resolved().await?;
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
-        let mut command = Command::new(program_path);
+        let mut command = env.command(program_path);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
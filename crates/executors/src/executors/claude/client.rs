@@ -20,6 +20,7 @@ use crate::{
 
 const EXIT_PLAN_MODE_NAME: &str = "ExitPlanMode";
 pub const AUTO_APPROVE_CALLBACK_ID: &str = "AUTO_APPROVE_CALLBACK_ID";
+pub const COMMAND_GATE_CALLBACK_ID: &str = "COMMAND_GATE_CALLBACK_ID";
 
 /// Claude Agent client with control protocol support
 pub struct ClaudeAgentClient {
@@ -143,7 +144,7 @@ impl ClaudeAgentClient {
     pub async fn on_hook_callback(
         &self,
         callback_id: String,
-        _input: serde_json::Value,
+        input: serde_json::Value,
         _tool_use_id: Option<String>,
     ) -> Result<serde_json::Value, ExecutorError> {
         if self.auto_approve {
@@ -156,6 +157,27 @@ impl ClaudeAgentClient {
             }))
         } else {
             match callback_id.as_str() {
+                COMMAND_GATE_CALLBACK_ID => {
+                    // Commands the gate doesn't match run without asking
+                    let gated = self
+                        .approvals
+                        .as_ref()
+                        .and_then(|approvals| approvals.command_gate())
+                        .zip(input.get("tool_input"))
+                        .and_then(|(gate, tool_input)| gate.matching_pattern(tool_input))
+                        .is_some();
+                    Ok(serde_json::json!({
+                        "hookSpecificOutput": {
+                            "hookEventName": "PreToolUse",
+                            "permissionDecision": if gated { "ask" } else { "allow" },
+                            "permissionDecisionReason": if gated {
+                                "Command matches an approval gate"
+                            } else {
+                                "Approved by SDK"
+                            }
+                        }
+                    }))
+                }
                 AUTO_APPROVE_CALLBACK_ID => Ok(serde_json::json!({
                     "hookSpecificOutput": {
                         "hookEventName": "PreToolUse",
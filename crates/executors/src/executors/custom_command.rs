@@ -0,0 +1,371 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use futures::StreamExt;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use ts_rs::TS;
+use uuid::Uuid;
+use workspace_utils::{
+    msg_store::MsgStore, path::get_vibe_kanban_temp_dir, shell::resolve_executable_path_blocking,
+};
+
+use crate::{
+    command::{CmdOverrides, CommandBuildError, CommandBuilder, apply_overrides},
+    env::ExecutionEnv,
+    executors::{
+        AppendPrompt, AvailabilityInfo, ExecutorError, ExecutorExitResult, ExecutorExitSignal,
+        SpawnedChild, StandardCodingAgentExecutor,
+    },
+    logs::utils::EntryIndexProvider,
+    stdout_dup,
+};
+
+pub mod normalize_logs;
+
+use normalize_logs::{CustomOutputParser, normalize_logs};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, TS, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename = "CustomCommandOutputFormat")]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Jsonl,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename = "CustomCommandOutputKind")]
+pub enum OutputKind {
+    Assistant,
+    Thinking,
+    System,
+    Error,
+    Command,
+    FileRead,
+    FileEdit,
+    SessionId,
+    Ignore,
+}
+
+/// How a line of output is shown in the conversation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+#[ts(rename = "CustomCommandOutputRule")]
+pub struct OutputRule {
+    #[schemars(
+        title = "Pattern",
+        description = "Regex matched against the line, or against the field when one is set"
+    )]
+    pub pattern: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Field",
+        description = "JSON pointer of the value to match in JSONL output, e.g. /type"
+    )]
+    pub field: Option<String>,
+
+    #[schemars(title = "Kind", description = "What a matching line is shown as")]
+    pub kind: OutputKind,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Content",
+        description = "Shown text: $1 or ${name} for captures, {/pointer} for JSONL values; the whole line when unset"
+    )]
+    pub content: Option<String>,
+}
+
+/// Custom command executor configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+pub struct CustomCommand {
+    #[serde(default)]
+    pub append_prompt: AppendPrompt,
+
+    #[serde(default)]
+    #[schemars(
+        title = "Command",
+        description = "Command to run, with {worktree}, {prompt}, {prompt_file} and {model} placeholders"
+    )]
+    pub command: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Follow-up Command",
+        description = "Command to continue a session, which can also use {session_id}; follow-ups are not supported when unset"
+    )]
+    pub follow_up_command: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(title = "Model", description = "Value of the {model} placeholder")]
+    pub model: Option<String>,
+
+    #[serde(default)]
+    #[schemars(
+        title = "Prompt on Stdin",
+        description = "Also write the prompt to the command's stdin"
+    )]
+    pub prompt_on_stdin: bool,
+
+    #[serde(default)]
+    #[schemars(
+        title = "Output Format",
+        description = "text treats unmatched lines as assistant output; jsonl parses each line as JSON and drops unmatched events"
+    )]
+    pub output_format: OutputFormat,
+
+    #[serde(default)]
+    #[schemars(
+        title = "Output Rules",
+        description = "Rules tried in order on each line of output; the first match wins"
+    )]
+    pub output_rules: Vec<OutputRule>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Success Pattern",
+        description = "Regex that stops the run as successful when a line matches; otherwise exit code 0 means success"
+    )]
+    pub success_pattern: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Failure Pattern",
+        description = "Regex that stops the run as failed when a line matches"
+    )]
+    pub failure_pattern: Option<String>,
+
+    #[serde(flatten)]
+    pub cmd: CmdOverrides,
+}
+
+impl CustomCommand {
+    fn build_command_builder(&self, template: &str) -> CommandBuilder {
+        apply_overrides(CommandBuilder::new(template), &self.cmd)
+    }
+
+    fn compile_pattern(pattern: &Option<String>) -> Result<Option<Regex>, ExecutorError> {
+        pattern
+            .as_deref()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|e| {
+                    ExecutorError::InvalidConfig(format!("invalid pattern `{pattern}`: {e}"))
+                })
+            })
+            .transpose()
+    }
+
+    async fn spawn_custom(
+        &self,
+        template: &str,
+        session_id: Option<&str>,
+        prompt: &str,
+        current_dir: &Path,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        if template.trim().is_empty() {
+            return Err(CommandBuildError::EmptyCommand.into());
+        }
+        // Checked up front so a bad rule fails the run instead of hiding its output
+        CustomOutputParser::new(self.output_format, &self.output_rules, current_dir)
+            .map_err(|e| ExecutorError::InvalidConfig(format!("invalid output rule: {e}")))?;
+        let success = Self::compile_pattern(&self.success_pattern)?;
+        let failure = Self::compile_pattern(&self.failure_pattern)?;
+
+        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        // Placeholders are filled in after splitting, so values with spaces stay one argument
+        let command_parts = self.build_command_builder(template).build_initial()?;
+        let (program_path, args) = command_parts.into_resolved().await?;
+        let prompt_file = if args.iter().any(|arg| arg.contains("{prompt_file}")) {
+            Some(write_prompt_file(&combined_prompt).map_err(ExecutorError::Io)?)
+        } else {
+            None
+        };
+        let placeholders = Placeholders {
+            worktree: current_dir.to_string_lossy().to_string(),
+            prompt: &combined_prompt,
+            prompt_file: prompt_file
+                .map(|path| path.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            model: self.model.as_deref().unwrap_or_default(),
+            session_id: session_id.unwrap_or_default(),
+        };
+
+        let mut command = env.command(program_path);
+        command
+            .kill_on_drop(true)
+            .stdin(if self.prompt_on_stdin {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(current_dir)
+            .args(args.iter().map(|arg| placeholders.fill(arg)));
+
+        env.clone()
+            .with_profile(&self.cmd)
+            .apply_to_command(&mut command);
+
+        let mut child = command.group_spawn()?;
+
+        if self.prompt_on_stdin
+            && let Some(mut stdin) = child.inner().stdin.take()
+        {
+            stdin.write_all(combined_prompt.as_bytes()).await?;
+            stdin.shutdown().await?;
+        }
+
+        let exit_signal = if success.is_some() || failure.is_some() {
+            Some(watch_exit_patterns(&mut child, success, failure)?)
+        } else {
+            None
+        };
+
+        Ok(SpawnedChild {
+            child,
+            exit_signal,
+            interrupt_sender: None,
+        })
+    }
+}
+
+struct Placeholders<'a> {
+    worktree: String,
+    prompt: &'a str,
+    prompt_file: String,
+    model: &'a str,
+    session_id: &'a str,
+}
+
+impl Placeholders<'_> {
+    fn fill(&self, arg: &str) -> String {
+        arg.replace("{worktree}", &self.worktree)
+            .replace("{prompt_file}", &self.prompt_file)
+            .replace("{model}", self.model)
+            .replace("{session_id}", self.session_id)
+            // Last, so the prompt's own braces are left alone
+            .replace("{prompt}", self.prompt)
+    }
+}
+
+fn write_prompt_file(prompt: &str) -> std::io::Result<PathBuf> {
+    let dir = get_vibe_kanban_temp_dir()
+        .join("custom_command")
+        .join(Uuid::new_v4().to_string());
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("prompt.md");
+    std::fs::write(&path, prompt)?;
+    Ok(path)
+}
+
+/// Resolves once a line of stdout matches the success or failure pattern. Without a match the
+/// signal stays pending until the container drops it, so the exit code decides.
+fn watch_exit_patterns(
+    child: &mut AsyncGroupChild,
+    success: Option<Regex>,
+    failure: Option<Regex>,
+) -> Result<ExecutorExitSignal, ExecutorError> {
+    let mut stdout = stdout_dup::duplicate_stdout(child)?;
+    let (exit_tx, exit_rx) = tokio::sync::oneshot::channel::<ExecutorExitResult>();
+
+    tokio::spawn(async move {
+        let is_match = |regex: &Option<Regex>, line: &str| {
+            regex.as_ref().is_some_and(|regex| regex.is_match(line))
+        };
+        let mut buffer = String::new();
+        while let Some(Ok(chunk)) = stdout.next().await {
+            buffer.push_str(&chunk);
+            while let Some(end) = buffer.find('\n') {
+                let line: String = buffer.drain(..=end).collect();
+                let line = line.trim_end();
+                let result = if is_match(&failure, line) {
+                    ExecutorExitResult::Failure
+                } else if is_match(&success, line) {
+                    ExecutorExitResult::Success
+                } else {
+                    continue;
+                };
+                let _ = exit_tx.send(result);
+                return;
+            }
+        }
+        // Dropping the sender would count as success, so hold it until the run is over
+        let mut exit_tx = exit_tx;
+        exit_tx.closed().await;
+    });
+
+    Ok(exit_rx)
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for CustomCommand {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        self.spawn_custom(&self.command, None, prompt, current_dir, env)
+            .await
+    }
+
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let Some(template) = &self.follow_up_command else {
+            return Err(ExecutorError::FollowUpNotSupported(
+                "no follow-up command is configured".to_string(),
+            ));
+        };
+        self.spawn_custom(template, Some(session_id), prompt, current_dir, env)
+            .await
+    }
+
+    fn normalize_logs(&self, msg_store: Arc<MsgStore>, current_dir: &Path) {
+        let parser =
+            match CustomOutputParser::new(self.output_format, &self.output_rules, current_dir) {
+                Ok(parser) => parser,
+                Err(e) => {
+                    tracing::error!("Invalid custom command output rule: {}", e);
+                    return;
+                }
+            };
+        normalize_logs(
+            msg_store.clone(),
+            parser,
+            EntryIndexProvider::start_from(&msg_store),
+        );
+    }
+
+    fn default_mcp_config_path(&self) -> Option<std::path::PathBuf> {
+        None
+    }
+
+    fn get_availability_info(&self) -> AvailabilityInfo {
+        let binary_found = self
+            .command
+            .split_whitespace()
+            .next()
+            .and_then(resolve_executable_path_blocking)
+            .is_some();
+        if binary_found {
+            AvailabilityInfo::InstallationFound
+        } else {
+            AvailabilityInfo::NotFound
+        }
+    }
+}
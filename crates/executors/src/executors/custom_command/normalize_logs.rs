@@ -0,0 +1,410 @@
+use std::{
+    path::Path,
+    sync::{Arc, LazyLock},
+};
+
+use futures::StreamExt;
+use regex::Regex;
+use serde_json::Value;
+use workspace_utils::{msg_store::MsgStore, path::make_path_relative};
+
+use super::{OutputFormat, OutputKind, OutputRule};
+use crate::logs::{
+    ActionType, NormalizedEntry, NormalizedEntryError, NormalizedEntryType, ToolStatus,
+    stderr_processor::normalize_stderr_logs,
+    utils::{
+        EntryIndexProvider,
+        patch::{add_normalized_entry, replace_normalized_entry},
+    },
+};
+
+/// `{/pointer}` references to values of a JSON line
+static POINTER_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{(/[^}]*)\}").unwrap());
+
+pub fn normalize_logs(
+    msg_store: Arc<MsgStore>,
+    mut parser: CustomOutputParser,
+    entry_index_provider: EntryIndexProvider,
+) {
+    normalize_stderr_logs(msg_store.clone(), entry_index_provider.clone());
+
+    tokio::spawn(async move {
+        let mut last_index = None;
+        let mut stdout_lines = msg_store.stdout_lines_stream();
+
+        while let Some(Ok(line)) = stdout_lines.next().await {
+            for update in parser.process_line(&line) {
+                match update {
+                    OutputUpdate::Add(entry) => {
+                        last_index = Some(add_normalized_entry(
+                            &msg_store,
+                            &entry_index_provider,
+                            entry,
+                        ));
+                    }
+                    OutputUpdate::ReplaceLast(entry) => match last_index {
+                        Some(index) => replace_normalized_entry(&msg_store, index, entry),
+                        None => {
+                            last_index = Some(add_normalized_entry(
+                                &msg_store,
+                                &entry_index_provider,
+                                entry,
+                            ));
+                        }
+                    },
+                    OutputUpdate::SessionId(session_id) => msg_store.push_session_id(session_id),
+                }
+            }
+        }
+    });
+}
+
+#[derive(Debug)]
+pub enum OutputUpdate {
+    Add(NormalizedEntry),
+    /// Replaces the entry added last, which is still growing
+    ReplaceLast(NormalizedEntry),
+    SessionId(String),
+}
+
+struct CompiledRule {
+    regex: Regex,
+    field: Option<String>,
+    kind: OutputKind,
+    content: Option<String>,
+}
+
+/// Turns each line of a custom command's output into entries using the configured rules. In
+/// text mode consecutive unmatched lines are joined into one assistant message.
+pub struct CustomOutputParser {
+    format: OutputFormat,
+    rules: Vec<CompiledRule>,
+    worktree_path: String,
+    /// Unmatched text of the assistant message being built
+    text: Option<String>,
+}
+
+impl CustomOutputParser {
+    pub fn new(
+        format: OutputFormat,
+        rules: &[OutputRule],
+        worktree_path: &Path,
+    ) -> Result<Self, regex::Error> {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                Ok(CompiledRule {
+                    regex: Regex::new(&rule.pattern)?,
+                    field: rule.field.clone(),
+                    kind: rule.kind,
+                    content: rule.content.clone(),
+                })
+            })
+            .collect::<Result<_, regex::Error>>()?;
+        Ok(Self {
+            format,
+            rules,
+            worktree_path: worktree_path.to_string_lossy().to_string(),
+            text: None,
+        })
+    }
+
+    pub fn process_line(&mut self, line: &str) -> Vec<OutputUpdate> {
+        let line = strip_ansi_escapes::strip_str(line.trim_end());
+        let json = match self.format {
+            OutputFormat::Text => None,
+            OutputFormat::Jsonl => match serde_json::from_str::<Value>(line.trim()) {
+                Ok(json) => Some(json),
+                Err(_) if line.trim().is_empty() => return vec![],
+                Err(_) => {
+                    self.text = None;
+                    return vec![OutputUpdate::Add(entry(
+                        NormalizedEntryType::SystemMessage,
+                        line.trim().to_string(),
+                    ))];
+                }
+            },
+        };
+
+        let Some((kind, content)) = self.match_rules(&line, json.as_ref()) else {
+            return self.unmatched(&line);
+        };
+        self.text = None;
+
+        let content = content.trim().to_string();
+        let entry_type = match kind {
+            OutputKind::Ignore => return vec![],
+            OutputKind::SessionId if content.is_empty() => return vec![],
+            OutputKind::SessionId => return vec![OutputUpdate::SessionId(content)],
+            OutputKind::Assistant => NormalizedEntryType::AssistantMessage,
+            OutputKind::Thinking => NormalizedEntryType::Thinking,
+            OutputKind::System => NormalizedEntryType::SystemMessage,
+            OutputKind::Error => NormalizedEntryType::ErrorMessage {
+                error_type: NormalizedEntryError::Other,
+            },
+            OutputKind::Command => tool_use(
+                "bash",
+                ActionType::CommandRun {
+                    command: content.clone(),
+                    result: None,
+                },
+            ),
+            OutputKind::FileRead => tool_use(
+                "read",
+                ActionType::FileRead {
+                    path: make_path_relative(&content, &self.worktree_path),
+                },
+            ),
+            OutputKind::FileEdit => tool_use(
+                "edit",
+                ActionType::FileEdit {
+                    path: make_path_relative(&content, &self.worktree_path),
+                    changes: vec![],
+                },
+            ),
+        };
+        if content.is_empty() {
+            return vec![];
+        }
+        vec![OutputUpdate::Add(entry(entry_type, content))]
+    }
+
+    /// Kind and rendered content of the first rule matching the line
+    fn match_rules(&self, line: &str, json: Option<&Value>) -> Option<(OutputKind, String)> {
+        self.rules.iter().find_map(|rule| {
+            let haystack = match (&rule.field, json) {
+                (Some(field), Some(json)) => value_text(json.pointer(field)?),
+                (Some(_), None) => return None,
+                (None, _) => line.to_string(),
+            };
+            let captures = rule.regex.captures(&haystack)?;
+            let content = match &rule.content {
+                Some(template) => {
+                    let mut content = String::new();
+                    captures.expand(template, &mut content);
+                    match json {
+                        Some(json) => fill_pointers(&content, json),
+                        None => content,
+                    }
+                }
+                None => line.to_string(),
+            };
+            Some((rule.kind, content))
+        })
+    }
+
+    fn unmatched(&mut self, line: &str) -> Vec<OutputUpdate> {
+        // Events no rule asked for are dropped in JSONL mode
+        if self.format == OutputFormat::Jsonl {
+            return vec![];
+        }
+        match &mut self.text {
+            Some(text) => {
+                text.push('\n');
+                text.push_str(line);
+                vec![OutputUpdate::ReplaceLast(entry(
+                    NormalizedEntryType::AssistantMessage,
+                    text.trim_end().to_string(),
+                ))]
+            }
+            None if line.trim().is_empty() => vec![],
+            None => {
+                self.text = Some(line.to_string());
+                vec![OutputUpdate::Add(entry(
+                    NormalizedEntryType::AssistantMessage,
+                    line.to_string(),
+                ))]
+            }
+        }
+    }
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+fn fill_pointers(template: &str, json: &Value) -> String {
+    POINTER_RE
+        .replace_all(template, |captures: &regex::Captures| {
+            json.pointer(&captures[1])
+                .map(value_text)
+                .unwrap_or_default()
+        })
+        .into_owned()
+}
+
+fn tool_use(tool_name: &str, action_type: ActionType) -> NormalizedEntryType {
+    NormalizedEntryType::ToolUse {
+        tool_name: tool_name.to_string(),
+        action_type,
+        status: ToolStatus::Success,
+    }
+}
+
+fn entry(entry_type: NormalizedEntryType, content: String) -> NormalizedEntry {
+    NormalizedEntry {
+        timestamp: None,
+        entry_type,
+        content,
+        metadata: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(
+        pattern: &str,
+        field: Option<&str>,
+        kind: OutputKind,
+        content: Option<&str>,
+    ) -> OutputRule {
+        OutputRule {
+            pattern: pattern.to_string(),
+            field: field.map(str::to_string),
+            kind,
+            content: content.map(str::to_string),
+        }
+    }
+
+    fn process(parser: &mut CustomOutputParser, lines: &[&str]) -> Vec<OutputUpdate> {
+        lines
+            .iter()
+            .flat_map(|line| parser.process_line(line))
+            .collect()
+    }
+
+    #[test]
+    fn test_text_rules_and_unmatched_lines() {
+        let mut parser = CustomOutputParser::new(
+            OutputFormat::Text,
+            &[
+                rule(r"^session: (\S+)", None, OutputKind::SessionId, Some("$1")),
+                rule(r"^\$ (?<cmd>.+)", None, OutputKind::Command, Some("${cmd}")),
+                rule(r"^wrote (.+)", None, OutputKind::FileEdit, Some("$1")),
+                rule(r"^ERROR", None, OutputKind::Error, None),
+                rule(r"^debug:", None, OutputKind::Ignore, None),
+            ],
+            Path::new("/work/tree"),
+        )
+        .unwrap();
+        let updates = process(
+            &mut parser,
+            &[
+                "session: abc-123",
+                "Looking at the code.",
+                "It needs a fix.",
+                "debug: tokens=12",
+                "$ cargo test",
+                "wrote /work/tree/src/lib.rs",
+                "ERROR rate limited",
+                "Done.",
+            ],
+        );
+        assert_eq!(updates.len(), 7, "{updates:#?}");
+
+        assert!(matches!(&updates[0], OutputUpdate::SessionId(id) if id == "abc-123"));
+        assert!(matches!(&updates[1], OutputUpdate::Add(_)));
+        let OutputUpdate::ReplaceLast(text) = &updates[2] else {
+            panic!("expected the message to grow: {:?}", updates[2]);
+        };
+        assert_eq!(text.content, "Looking at the code.\nIt needs a fix.");
+
+        let OutputUpdate::Add(NormalizedEntry {
+            entry_type:
+                NormalizedEntryType::ToolUse {
+                    action_type: ActionType::CommandRun { command, .. },
+                    ..
+                },
+            ..
+        }) = &updates[3]
+        else {
+            panic!("expected a command: {:?}", updates[3]);
+        };
+        assert_eq!(command, "cargo test");
+
+        let OutputUpdate::Add(NormalizedEntry {
+            entry_type:
+                NormalizedEntryType::ToolUse {
+                    action_type: ActionType::FileEdit { path, .. },
+                    ..
+                },
+            ..
+        }) = &updates[4]
+        else {
+            panic!("expected an edit: {:?}", updates[4]);
+        };
+        assert_eq!(path, "src/lib.rs");
+
+        let OutputUpdate::Add(error) = &updates[5] else {
+            panic!("expected an error: {:?}", updates[5]);
+        };
+        assert_eq!(error.content, "ERROR rate limited");
+        // A matched line ends the running message, so this starts a new one
+        assert!(matches!(&updates[6], OutputUpdate::Add(entry) if entry.content == "Done."));
+    }
+
+    #[test]
+    fn test_jsonl_rules() {
+        let mut parser = CustomOutputParser::new(
+            OutputFormat::Jsonl,
+            &[
+                rule(
+                    "^init$",
+                    Some("/type"),
+                    OutputKind::SessionId,
+                    Some("{/session}"),
+                ),
+                rule(
+                    "^text$",
+                    Some("/type"),
+                    OutputKind::Assistant,
+                    Some("{/text}"),
+                ),
+                rule(
+                    "^tool$",
+                    Some("/type"),
+                    OutputKind::Command,
+                    Some("{/input/command}"),
+                ),
+            ],
+            Path::new("/work/tree"),
+        )
+        .unwrap();
+        let updates = process(
+            &mut parser,
+            &[
+                r#"{"type": "init", "session": "s-1"}"#,
+                r#"{"type": "usage", "tokens": 10}"#,
+                r#"{"type": "text", "text": "Running the tests."}"#,
+                r#"{"type": "tool", "input": {"command": "npm test"}}"#,
+                "not json",
+            ],
+        );
+        assert_eq!(updates.len(), 4, "{updates:#?}");
+
+        assert!(matches!(&updates[0], OutputUpdate::SessionId(id) if id == "s-1"));
+        assert!(
+            matches!(&updates[1], OutputUpdate::Add(entry) if entry.content == "Running the tests.")
+        );
+        assert!(matches!(&updates[2], OutputUpdate::Add(entry) if entry.content == "npm test"));
+        assert!(matches!(
+            &updates[3],
+            OutputUpdate::Add(NormalizedEntry {
+                entry_type: NormalizedEntryType::SystemMessage,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_rule_pattern() {
+        let rules = [rule("(", None, OutputKind::Assistant, None)];
+        assert!(CustomOutputParser::new(OutputFormat::Text, &rules, Path::new("/")).is_err());
+    }
+}
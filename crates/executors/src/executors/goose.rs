@@ -0,0 +1,325 @@
+use std::{path::Path, process::Stdio, sync::Arc};
+
+use async_trait::async_trait;
+use command_group::AsyncCommandGroup;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use strum_macros::AsRefStr;
+use tokio::{io::AsyncWriteExt, process::Command};
+use ts_rs::TS;
+use uuid::Uuid;
+use workspace_utils::msg_store::MsgStore;
+
+use crate::{
+    command::{CmdOverrides, CommandBuilder, CommandParts, apply_overrides},
+    env::ExecutionEnv,
+    executors::{
+        AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+    },
+    local_llm::{LocalLlm, LocalLlmProvider},
+    logs::utils::EntryIndexProvider,
+    openrouter::OpenRouter,
+    stdout_dup,
+};
+
+pub mod normalize_logs;
+
+use normalize_logs::{SESSION_PREFIX, normalize_logs};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, TS, JsonSchema, AsRefStr)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[ts(rename = "GooseMode")]
+pub enum Mode {
+    Auto,
+    SmartApprove,
+    Approve,
+    Chat,
+}
+
+fn default_mode() -> Mode {
+    Mode::Auto
+}
+
+/// Goose executor configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+pub struct Goose {
+    #[serde(default)]
+    pub append_prompt: AppendPrompt,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Provider",
+        description = "Provider to use (e.g., anthropic, openai, google, ollama, openrouter); goose's configured provider when unset"
+    )]
+    pub provider: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Model",
+        description = "Model to use (e.g., claude-sonnet-4-5, gpt-5, gemini-2.5-pro, qwen3-coder)"
+    )]
+    pub model: Option<String>,
+
+    #[serde(default = "default_mode")]
+    #[schemars(
+        title = "Mode",
+        description = "Tool permissions: auto runs every tool, smart_approve and approve hold back tools that need approval, chat uses no tools"
+    )]
+    pub mode: Mode,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Builtin Extensions",
+        description = "Builtin extensions to enable, e.g. developer, computercontroller, memory"
+    )]
+    pub builtins: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Stdio Extensions",
+        description = "Commands starting stdio extensions, e.g. \"npx -y @modelcontextprotocol/server-github\""
+    )]
+    pub extensions: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Streamable HTTP Extensions",
+        description = "URLs of streamable HTTP extensions"
+    )]
+    pub http_extensions: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Max Turns",
+        description = "Maximum number of turns without user input"
+    )]
+    pub max_turns: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Local Model",
+        description = "Run a model served by Ollama or an OpenAI-compatible server; replaces Provider and Model"
+    )]
+    pub local_llm: Option<LocalLlm>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "OpenRouter",
+        description = "Route the model through OpenRouter with the OPENROUTER_API_KEY key; replaces Provider and Model"
+    )]
+    pub openrouter: Option<OpenRouter>,
+
+    #[serde(flatten)]
+    pub cmd: CmdOverrides,
+}
+
+impl Goose {
+    fn build_command_builder(&self) -> CommandBuilder {
+        let mut builder = CommandBuilder::new("goose run").params([
+            "--output-format",
+            "stream-json",
+            "--instructions",
+            "-",
+        ]);
+
+        if let Some(local_llm) = &self.local_llm {
+            let provider = match local_llm.provider {
+                LocalLlmProvider::Ollama => "ollama",
+                LocalLlmProvider::OpenaiCompatible => "openai",
+            };
+            builder = builder.extend_params([
+                "--provider",
+                provider,
+                "--model",
+                local_llm.model.as_str(),
+            ]);
+        } else if let Some(openrouter) = &self.openrouter {
+            builder = builder.extend_params([
+                "--provider",
+                "openrouter",
+                "--model",
+                openrouter.model.as_str(),
+            ]);
+        } else {
+            if let Some(provider) = &self.provider {
+                builder = builder.extend_params(["--provider", provider]);
+            }
+
+            if let Some(model) = &self.model {
+                builder = builder.extend_params(["--model", model]);
+            }
+        }
+
+        if let Some(builtins) = self.builtins.as_ref().filter(|b| !b.is_empty()) {
+            builder = builder.extend_params(["--with-builtin".to_string(), builtins.join(",")]);
+        }
+
+        if let Some(max_turns) = self.max_turns {
+            builder = builder.extend_params(["--max-turns".to_string(), max_turns.to_string()]);
+        }
+
+        apply_overrides(builder, &self.cmd)
+    }
+
+    /// Extension arguments are added after resolving the command so that extension commands
+    /// keep their spaces
+    fn extension_args(&self) -> Vec<String> {
+        let stdio = self
+            .extensions
+            .iter()
+            .flatten()
+            .flat_map(|command| ["--with-extension".to_string(), command.clone()]);
+        let http = self
+            .http_extensions
+            .iter()
+            .flatten()
+            .flat_map(|url| ["--with-streamable-http-extension".to_string(), url.clone()]);
+        stdio.chain(http).collect()
+    }
+
+    async fn spawn_goose(
+        &self,
+        command_parts: CommandParts,
+        session_name: &str,
+        prompt: &str,
+        current_dir: &Path,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let (program_path, args) = command_parts.into_resolved().await?;
+        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+
+        let mut command = env.command(program_path);
+        command
+            .kill_on_drop(true)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(current_dir)
+            .args(&args)
+            .args(self.extension_args())
+            .args(["--name", session_name])
+            .env("GOOSE_MODE", self.mode.as_ref())
+            .env("NO_COLOR", "1");
+
+        if let Some(local_llm) = &self.local_llm {
+            apply_local_llm_env(local_llm, &mut command);
+        }
+
+        env.clone()
+            .with_profile(&self.cmd)
+            .apply_to_command(&mut command);
+
+        let mut child = command.group_spawn()?;
+
+        if let Some(mut stdin) = child.inner().stdin.take() {
+            stdin.write_all(combined_prompt.as_bytes()).await?;
+            stdin.shutdown().await?;
+        }
+
+        let (_, appender) = stdout_dup::tee_stdout_with_appender(&mut child)?;
+        appender.append_line(format!("{SESSION_PREFIX}{session_name}\n"));
+
+        Ok(child.into())
+    }
+}
+
+fn apply_local_llm_env(local_llm: &LocalLlm, command: &mut Command) {
+    match local_llm.provider {
+        LocalLlmProvider::Ollama => {
+            command.env("OLLAMA_HOST", local_llm.base_url());
+        }
+        LocalLlmProvider::OpenaiCompatible => {
+            // Goose takes the server root and the path of the completions endpoint separately
+            let base_url = local_llm.openai_base_url();
+            let path_start = base_url
+                .find("://")
+                .and_then(|scheme_end| {
+                    base_url[scheme_end + 3..]
+                        .find('/')
+                        .map(|i| scheme_end + 3 + i)
+                })
+                .unwrap_or(base_url.len());
+            let (host, path) = base_url.split_at(path_start);
+            let base_path = match path.trim_matches('/') {
+                "" => "chat/completions".to_string(),
+                path => format!("{path}/chat/completions"),
+            };
+            // Local servers ignore the key, but goose refuses to start without one
+            command
+                .env("OPENAI_HOST", host)
+                .env("OPENAI_BASE_PATH", base_path)
+                .env("OPENAI_API_KEY", "local");
+        }
+    }
+    if let Some(context_window) = local_llm.context_window {
+        command.env("GOOSE_CONTEXT_LIMIT", context_window.to_string());
+    }
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for Goose {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        // Named by us, so the session can be resumed without reading goose's session store
+        let session_name = format!("vibe-kanban-{}", Uuid::new_v4());
+        let command_parts = self.build_command_builder().build_initial()?;
+
+        self.spawn_goose(command_parts, &session_name, prompt, current_dir, env)
+            .await
+    }
+
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let command_parts = self
+            .build_command_builder()
+            .build_follow_up(&["--resume".to_string()])?;
+
+        self.spawn_goose(command_parts, session_id, prompt, current_dir, env)
+            .await
+    }
+
+    fn normalize_logs(&self, msg_store: Arc<MsgStore>, current_dir: &Path) {
+        normalize_logs(
+            msg_store.clone(),
+            current_dir,
+            EntryIndexProvider::start_from(&msg_store),
+        );
+    }
+
+    // Goose keeps extensions in its config.yaml rather than an MCP servers file; extra ones
+    // are set per profile
+    fn default_mcp_config_path(&self) -> Option<std::path::PathBuf> {
+        None
+    }
+
+    fn get_availability_info(&self) -> AvailabilityInfo {
+        // Written by `goose configure`
+        let config_found = dirs::config_dir()
+            .map(|config| config.join("goose").join("config.yaml").exists())
+            .unwrap_or(false)
+            || dirs::home_dir()
+                .map(|home| {
+                    home.join(".config")
+                        .join("goose")
+                        .join("config.yaml")
+                        .exists()
+                })
+                .unwrap_or(false);
+
+        if config_found {
+            AvailabilityInfo::InstallationFound
+        } else {
+            AvailabilityInfo::NotFound
+        }
+    }
+}
@@ -0,0 +1,412 @@
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+use futures::StreamExt;
+use serde_json::Value;
+use workspace_utils::{diff::create_unified_diff, msg_store::MsgStore, path::make_path_relative};
+
+use crate::logs::{
+    ActionType, CommandRunResult, FileChange, NormalizedEntry, NormalizedEntryError,
+    NormalizedEntryType, ToolResult, ToolStatus,
+    stderr_processor::normalize_stderr_logs,
+    utils::{
+        EntryIndexProvider,
+        patch::{add_normalized_entry, replace_normalized_entry},
+    },
+};
+
+/// Prefix of the line carrying the session name, appended to stdout when goose is spawned
+pub const SESSION_PREFIX: &str = "[goose-session] ";
+
+pub fn normalize_logs(
+    msg_store: Arc<MsgStore>,
+    worktree_path: &Path,
+    entry_index_provider: EntryIndexProvider,
+) {
+    normalize_stderr_logs(msg_store.clone(), entry_index_provider.clone());
+
+    let worktree_path = worktree_path.to_string_lossy().to_string();
+    tokio::spawn(async move {
+        let mut processor = GooseEventProcessor::new(worktree_path);
+        // Entry index of each message part that can still change, by key
+        let mut entries = HashMap::new();
+        let mut stdout_lines = msg_store.stdout_lines_stream();
+
+        while let Some(Ok(line)) = stdout_lines.next().await {
+            if let Some(session_id) = line.strip_prefix(SESSION_PREFIX) {
+                msg_store.push_session_id(session_id.trim().to_string());
+                continue;
+            }
+            let trimmed = line.trim();
+            let Ok(event) = serde_json::from_str::<Value>(trimmed) else {
+                if !trimmed.is_empty() {
+                    let entry = NormalizedEntry {
+                        timestamp: None,
+                        entry_type: NormalizedEntryType::SystemMessage,
+                        content: strip_ansi_escapes::strip_str(trimmed).to_string(),
+                        metadata: None,
+                    };
+                    add_normalized_entry(&msg_store, &entry_index_provider, entry);
+                }
+                continue;
+            };
+
+            for GooseUpdate { key, entry } in processor.process(&event) {
+                match key.as_ref().and_then(|key| entries.get(key)) {
+                    Some(index) => replace_normalized_entry(&msg_store, *index, entry),
+                    None => {
+                        let index = add_normalized_entry(&msg_store, &entry_index_provider, entry);
+                        if let Some(key) = key {
+                            entries.insert(key, index);
+                        }
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// An entry to add, or to replace the one added earlier under the same key
+#[derive(Debug)]
+pub struct GooseUpdate {
+    pub key: Option<String>,
+    pub entry: NormalizedEntry,
+}
+
+/// Maps `goose run --output-format stream-json` events to normalized entries. Assistant text
+/// arrives in chunks sharing a message id, which are joined into one entry; tool requests are
+/// updated in place when their response arrives.
+pub struct GooseEventProcessor {
+    worktree_path: String,
+    /// Text so far of the message being streamed, by message id
+    texts: HashMap<String, String>,
+    /// Tool entries waiting for their response, by request id
+    pending: HashMap<String, NormalizedEntry>,
+}
+
+impl GooseEventProcessor {
+    pub fn new(worktree_path: String) -> Self {
+        Self {
+            worktree_path,
+            texts: HashMap::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn process(&mut self, event: &Value) -> Vec<GooseUpdate> {
+        match event.get("type").and_then(Value::as_str) {
+            Some("message") => self.process_message(event.get("message").unwrap_or(&Value::Null)),
+            Some("error") => {
+                let error = event
+                    .get("error")
+                    .and_then(Value::as_str)
+                    .unwrap_or("Goose error")
+                    .to_string();
+                vec![GooseUpdate {
+                    key: None,
+                    entry: entry(
+                        NormalizedEntryType::ErrorMessage {
+                            error_type: NormalizedEntryError::Other,
+                        },
+                        error,
+                    ),
+                }]
+            }
+            _ => vec![],
+        }
+    }
+
+    fn process_message(&mut self, message: &Value) -> Vec<GooseUpdate> {
+        let is_assistant = message.get("role").and_then(Value::as_str) == Some("assistant");
+        let message_id = message
+            .get("id")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let parts = message
+            .get("content")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        let mut updates = vec![];
+        for part in &parts {
+            match part.get("type").and_then(Value::as_str) {
+                Some("text") if is_assistant => {
+                    let chunk = part.get("text").and_then(Value::as_str).unwrap_or_default();
+                    let text = self.texts.entry(message_id.clone()).or_default();
+                    text.push_str(chunk);
+                    if !text.trim().is_empty() {
+                        updates.push(GooseUpdate {
+                            key: Some(format!("text:{message_id}")),
+                            entry: entry(
+                                NormalizedEntryType::AssistantMessage,
+                                text.trim().to_string(),
+                            ),
+                        });
+                    }
+                }
+                Some("thinking") => {
+                    let thinking = part
+                        .get("thinking")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .trim();
+                    if !thinking.is_empty() {
+                        updates.push(GooseUpdate {
+                            key: None,
+                            entry: entry(NormalizedEntryType::Thinking, thinking.to_string()),
+                        });
+                    }
+                }
+                Some("toolRequest") => updates.extend(self.process_tool_request(part)),
+                Some("toolResponse") => updates.extend(self.process_tool_response(part)),
+                _ => {}
+            }
+        }
+        updates
+    }
+
+    fn process_tool_request(&mut self, part: &Value) -> Option<GooseUpdate> {
+        let id = part.get("id").and_then(Value::as_str)?.to_string();
+        let call = part.pointer("/toolCall/value");
+        let name = call
+            .and_then(|call| call.get("name"))
+            .and_then(Value::as_str)
+            .unwrap_or("tool");
+        let arguments = call
+            .and_then(|call| call.get("arguments"))
+            .cloned()
+            .unwrap_or(Value::Null);
+        let text = |key: &str| {
+            arguments
+                .get(key)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+        };
+
+        // Extension tools are named `<extension>__<tool>`
+        let tool = name.rsplit("__").next().unwrap_or(name);
+        let (action_type, content) = match tool {
+            "shell" => (
+                ActionType::CommandRun {
+                    command: text("command").to_string(),
+                    result: None,
+                },
+                text("command").to_string(),
+            ),
+            "text_editor" => {
+                let path = make_path_relative(text("path"), &self.worktree_path);
+                let changes = match text("command") {
+                    "view" => {
+                        return Some(self.start_tool(
+                            id,
+                            name,
+                            ActionType::FileRead { path: path.clone() },
+                            path,
+                        ));
+                    }
+                    "write" => vec![FileChange::Write {
+                        content: text("file_text").to_string(),
+                    }],
+                    "str_replace" => vec![FileChange::Edit {
+                        unified_diff: create_unified_diff(&path, text("old_str"), text("new_str")),
+                        has_line_numbers: false,
+                    }],
+                    "insert" => vec![FileChange::Edit {
+                        unified_diff: create_unified_diff(&path, "", text("new_str")),
+                        has_line_numbers: false,
+                    }],
+                    _ => vec![],
+                };
+                (
+                    ActionType::FileEdit {
+                        path: path.clone(),
+                        changes,
+                    },
+                    path,
+                )
+            }
+            _ => (
+                ActionType::Tool {
+                    tool_name: name.to_string(),
+                    arguments: Some(arguments.clone()),
+                    result: None,
+                },
+                name.to_string(),
+            ),
+        };
+        Some(self.start_tool(id, name, action_type, content))
+    }
+
+    fn start_tool(
+        &mut self,
+        id: String,
+        name: &str,
+        action_type: ActionType,
+        content: String,
+    ) -> GooseUpdate {
+        let entry = entry(
+            NormalizedEntryType::ToolUse {
+                tool_name: name.to_string(),
+                action_type,
+                status: ToolStatus::Created,
+            },
+            content,
+        );
+        self.pending.insert(id.clone(), entry.clone());
+        GooseUpdate {
+            key: Some(format!("tool:{id}")),
+            entry,
+        }
+    }
+
+    fn process_tool_response(&mut self, part: &Value) -> Option<GooseUpdate> {
+        let id = part.get("id").and_then(Value::as_str)?;
+        let mut entry = self.pending.remove(id)?;
+        let result = part.get("toolResult").unwrap_or(&Value::Null);
+        let value = result.get("value");
+        let failed = result.get("status").and_then(Value::as_str) == Some("error")
+            || value
+                .and_then(|value| value.get("isError"))
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+        // Older releases send the content list as the value, newer ones a call result
+        let content = value.and_then(|value| value.get("content")).or(value);
+        let mut output = content_text(content);
+        if output.is_empty()
+            && let Some(error) = result.get("error").and_then(Value::as_str)
+        {
+            output = error.to_string();
+        }
+
+        let NormalizedEntryType::ToolUse {
+            tool_name,
+            action_type,
+            ..
+        } = entry.entry_type
+        else {
+            return None;
+        };
+        let action_type = match action_type {
+            ActionType::CommandRun { command, .. } => ActionType::CommandRun {
+                command,
+                result: Some(CommandRunResult {
+                    exit_status: None,
+                    output: (!output.is_empty()).then_some(output),
+                }),
+            },
+            ActionType::Tool {
+                tool_name,
+                arguments,
+                ..
+            } => ActionType::Tool {
+                tool_name,
+                arguments,
+                result: (!output.is_empty()).then(|| ToolResult::markdown(output)),
+            },
+            action_type => action_type,
+        };
+        entry.entry_type = NormalizedEntryType::ToolUse {
+            tool_name,
+            action_type,
+            status: if failed {
+                ToolStatus::Failed
+            } else {
+                ToolStatus::Success
+            },
+        };
+        Some(GooseUpdate {
+            key: Some(format!("tool:{id}")),
+            entry,
+        })
+    }
+}
+
+/// Text of a list of `{"type": "text", "text": ...}` content items
+fn content_text(value: Option<&Value>) -> String {
+    value
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.get("text").and_then(Value::as_str))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+fn entry(entry_type: NormalizedEntryType, content: String) -> NormalizedEntry {
+    NormalizedEntry {
+        timestamp: None,
+        entry_type,
+        content,
+        metadata: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn process(events: &[Value]) -> Vec<GooseUpdate> {
+        let mut processor = GooseEventProcessor::new("/work/tree".to_string());
+        events
+            .iter()
+            .flat_map(|event| processor.process(event))
+            .collect()
+    }
+
+    #[test]
+    fn test_streamed_text_and_tool_calls() {
+        let updates = process(&[
+            json!({"type": "message", "message": {"id": "m1", "role": "user", "content": [{"type": "text", "text": "Fix it"}]}}),
+            json!({"type": "message", "message": {"id": "m2", "role": "assistant", "content": [{"type": "text", "text": "Let me "}]}}),
+            json!({"type": "message", "message": {"id": "m2", "role": "assistant", "content": [{"type": "text", "text": "look."}]}}),
+            json!({"type": "message", "message": {"id": "m3", "role": "assistant", "content": [{"type": "toolRequest", "id": "t1", "toolCall": {"status": "success", "value": {"name": "developer__shell", "arguments": {"command": "cargo test"}}}}]}}),
+            json!({"type": "message", "message": {"id": "m4", "role": "user", "content": [{"type": "toolResponse", "id": "t1", "toolResult": {"status": "success", "value": [{"type": "text", "text": "test result: ok"}]}}]}}),
+            json!({"type": "message", "message": {"id": "m5", "role": "assistant", "content": [{"type": "toolRequest", "id": "t2", "toolCall": {"status": "success", "value": {"name": "developer__text_editor", "arguments": {"command": "str_replace", "path": "/work/tree/src/lib.rs", "old_str": "a - b", "new_str": "a + b"}}}}]}}),
+            json!({"type": "message", "message": {"id": "m6", "role": "user", "content": [{"type": "toolResponse", "id": "t2", "toolResult": {"status": "error", "error": "no match"}}]}}),
+            json!({"type": "error", "error": "Provider rate limited"}),
+            json!({"type": "complete", "total_tokens": 1234}),
+        ]);
+        assert_eq!(updates.len(), 7, "{updates:#?}");
+
+        assert_eq!(updates[0].key, updates[1].key);
+        assert_eq!(updates[1].entry.content, "Let me look.");
+
+        assert_eq!(updates[2].key.as_deref(), Some("tool:t1"));
+        let NormalizedEntryType::ToolUse {
+            action_type: ActionType::CommandRun { result, .. },
+            status: ToolStatus::Success,
+            ..
+        } = &updates[3].entry.entry_type
+        else {
+            panic!("expected a finished command: {:?}", updates[3]);
+        };
+        assert_eq!(
+            result.as_ref().and_then(|result| result.output.as_deref()),
+            Some("test result: ok")
+        );
+
+        let NormalizedEntryType::ToolUse {
+            action_type: ActionType::FileEdit { path, changes },
+            status: ToolStatus::Failed,
+            ..
+        } = &updates[5].entry.entry_type
+        else {
+            panic!("expected a failed edit: {:?}", updates[5]);
+        };
+        assert_eq!(path, "src/lib.rs");
+        assert_eq!(changes.len(), 1);
+
+        assert!(matches!(
+            updates[6].entry.entry_type,
+            NormalizedEntryType::ErrorMessage { .. }
+        ));
+    }
+}
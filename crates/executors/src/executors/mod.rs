@@ -18,21 +18,28 @@ use crate::{
     command::CommandBuildError,
     env::ExecutionEnv,
     executors::{
-        amp::Amp, claude::ClaudeCode, codex::Codex, copilot::Copilot, cursor::CursorAgent,
-        droid::Droid, gemini::Gemini, opencode::Opencode, qwen::QwenCode,
+        aider::Aider, amp::Amp, claude::ClaudeCode, codex::Codex, copilot::Copilot,
+        cursor::CursorAgent, custom_command::CustomCommand, droid::Droid, gemini::Gemini,
+        goose::Goose, opencode::Opencode, openhands::Openhands, qwen::QwenCode,
     },
+    local_llm::LocalLlm,
     mcp_config::McpConfig,
+    openrouter::OpenRouter,
 };
 
 pub mod acp;
+pub mod aider;
 pub mod amp;
 pub mod claude;
 pub mod codex;
 pub mod copilot;
 pub mod cursor;
+pub mod custom_command;
 pub mod droid;
 pub mod gemini;
+pub mod goose;
 pub mod opencode;
+pub mod openhands;
 pub mod qwen;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
@@ -70,6 +77,8 @@ pub enum ExecutorError {
     SetupHelperNotSupported,
     #[error("Auth required: {0}")]
     AuthRequired(String),
+    #[error("Invalid executor configuration: {0}")]
+    InvalidConfig(String),
 }
 
 #[enum_dispatch]
@@ -100,6 +109,10 @@ pub enum CodingAgent {
     QwenCode,
     Copilot,
     Droid,
+    Aider,
+    Openhands,
+    Goose,
+    CustomCommand,
 }
 
 impl CodingAgent {
@@ -160,15 +173,112 @@ impl CodingAgent {
             | Self::Gemini(_)
             | Self::QwenCode(_)
             | Self::Droid(_)
-            | Self::Opencode(_) => vec![BaseAgentCapability::SessionFork],
+            | Self::Opencode(_)
+            | Self::Aider(_) => vec![BaseAgentCapability::SessionFork],
             Self::Codex(_) => vec![
                 BaseAgentCapability::SessionFork,
                 BaseAgentCapability::SetupHelper,
             ],
             Self::CursorAgent(_) => vec![BaseAgentCapability::SetupHelper],
-            Self::Copilot(_) => vec![],
+            Self::Copilot(_) | Self::Openhands(_) | Self::Goose(_) | Self::CustomCommand(_) => {
+                vec![]
+            }
         }
     }
+
+    /// Run a different model than the profile's for one task attempt. The model replaces the
+    /// local or OpenRouter model when the profile uses one; Amp and Qwen Code have no model
+    /// setting and are left as they are.
+    pub fn with_model_override(mut self, model: &str) -> Self {
+        let model = model.trim();
+        if model.is_empty() {
+            return self;
+        }
+        let routed = |local_llm: Option<&mut LocalLlm>,
+                      openrouter: Option<&mut OpenRouter>,
+                      plain: &mut Option<String>| {
+            match (local_llm, openrouter) {
+                (Some(local_llm), _) => local_llm.model = model.to_string(),
+                (None, Some(openrouter)) => openrouter.model = model.to_string(),
+                (None, None) => *plain = Some(model.to_string()),
+            }
+        };
+        match &mut self {
+            Self::Aider(aider) => routed(
+                aider.local_llm.as_mut(),
+                aider.openrouter.as_mut(),
+                &mut aider.model,
+            ),
+            Self::Openhands(openhands) => routed(
+                openhands.local_llm.as_mut(),
+                openhands.openrouter.as_mut(),
+                &mut openhands.model,
+            ),
+            Self::Goose(goose) => routed(
+                goose.local_llm.as_mut(),
+                goose.openrouter.as_mut(),
+                &mut goose.model,
+            ),
+            Self::ClaudeCode(claude) => claude.model = Some(model.to_string()),
+            Self::Codex(codex) => codex.model = Some(model.to_string()),
+            Self::Copilot(copilot) => copilot.model = Some(model.to_string()),
+            Self::CursorAgent(cursor) => cursor.model = Some(model.to_string()),
+            Self::Droid(droid) => droid.model = Some(model.to_string()),
+            Self::Gemini(gemini) => gemini.model = Some(model.to_string()),
+            Self::Opencode(opencode) => opencode.model = Some(model.to_string()),
+            Self::CustomCommand(custom) => custom.model = Some(model.to_string()),
+            Self::Amp(_) | Self::QwenCode(_) => {}
+        }
+        self
+    }
+
+    /// The model the agent runs, when its configuration names one
+    pub fn model(&self) -> Option<String> {
+        let routed = |local_llm: &Option<LocalLlm>,
+                      openrouter: &Option<OpenRouter>,
+                      plain: &Option<String>| {
+            match (local_llm, openrouter) {
+                (Some(local_llm), _) => Some(local_llm.model.clone()),
+                (None, Some(openrouter)) => Some(openrouter.model.clone()),
+                (None, None) => plain.clone(),
+            }
+        };
+        match self {
+            Self::Aider(aider) => routed(&aider.local_llm, &aider.openrouter, &aider.model),
+            Self::Openhands(openhands) => routed(
+                &openhands.local_llm,
+                &openhands.openrouter,
+                &openhands.model,
+            ),
+            Self::Goose(goose) => routed(&goose.local_llm, &goose.openrouter, &goose.model),
+            Self::ClaudeCode(claude) => claude.model.clone(),
+            Self::Codex(codex) => codex.model.clone(),
+            Self::Copilot(copilot) => copilot.model.clone(),
+            Self::CursorAgent(cursor) => cursor.model.clone(),
+            Self::Droid(droid) => droid.model.clone(),
+            Self::Gemini(gemini) => gemini.model.clone(),
+            Self::Opencode(opencode) => opencode.model.clone(),
+            Self::CustomCommand(custom) => custom.model.clone(),
+            Self::Amp(_) | Self::QwenCode(_) => None,
+        }
+    }
+
+    /// OpenRouter settings and API key when the agent's model is routed through OpenRouter
+    pub fn openrouter(&self) -> Option<(&OpenRouter, String)> {
+        let (openrouter, cmd) = match self {
+            Self::Aider(aider) if aider.local_llm.is_none() => {
+                (aider.openrouter.as_ref()?, &aider.cmd)
+            }
+            Self::Openhands(openhands) if openhands.local_llm.is_none() => {
+                (openhands.openrouter.as_ref()?, &openhands.cmd)
+            }
+            Self::Goose(goose) if goose.local_llm.is_none() => {
+                (goose.openrouter.as_ref()?, &goose.cmd)
+            }
+            _ => return None,
+        };
+        Some((openrouter, OpenRouter::api_key(cmd)?))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -321,4 +431,24 @@ mod tests {
         assert!(result.is_ok(), "CURSOR should deserialize via serde");
         assert_eq!(result.unwrap(), BaseCodingAgent::CursorAgent);
     }
+
+    #[test]
+    fn test_model_override() {
+        let goose: CodingAgent = serde_json::from_value(serde_json::json!({
+            "GOOSE": {"openrouter": {"model": "anthropic/claude-sonnet-4.5"}}
+        }))
+        .unwrap();
+        let CodingAgent::Goose(goose) = goose.with_model_override(" qwen/qwen3-coder ") else {
+            unreachable!()
+        };
+        assert_eq!(goose.model, None);
+        let goose = CodingAgent::Goose(goose);
+        assert_eq!(goose.model().as_deref(), Some("qwen/qwen3-coder"));
+
+        let codex: CodingAgent = serde_json::from_value(serde_json::json!({"CODEX": {}})).unwrap();
+        let CodingAgent::Codex(codex) = codex.with_model_override("gpt-5") else {
+            unreachable!()
+        };
+        assert_eq!(codex.model.as_deref(), Some("gpt-5"));
+    }
 }
@@ -0,0 +1,223 @@
+use std::{path::Path, process::Stdio, sync::Arc};
+
+use async_trait::async_trait;
+use command_group::AsyncCommandGroup;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use workspace_utils::msg_store::MsgStore;
+
+use crate::{
+    command::{CmdOverrides, CommandBuilder, CommandParts, apply_overrides},
+    env::ExecutionEnv,
+    executors::{
+        AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+    },
+    local_llm::{LocalLlm, LocalLlmProvider},
+    logs::utils::EntryIndexProvider,
+    openrouter::OpenRouter,
+};
+
+pub mod normalize_logs;
+
+use normalize_logs::normalize_logs;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+#[ts(rename = "OpenhandsConfirmationMode")]
+pub enum ConfirmationMode {
+    AlwaysApprove,
+    LlmApprove,
+}
+
+fn default_confirmation_mode() -> ConfirmationMode {
+    ConfirmationMode::AlwaysApprove
+}
+
+/// OpenHands executor configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+pub struct Openhands {
+    #[serde(default)]
+    pub append_prompt: AppendPrompt,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Model",
+        description = "LiteLLM model name (e.g., anthropic/claude-sonnet-4-5-20250929, openai/gpt-5, openhands/claude-sonnet-4-5-20250929); the CLI's saved settings are used when unset"
+    )]
+    pub model: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Base URL",
+        description = "Base URL of an OpenAI-compatible endpoint, for local or proxied models"
+    )]
+    pub base_url: Option<String>,
+
+    #[serde(default = "default_confirmation_mode")]
+    #[schemars(
+        title = "Confirmation Mode",
+        description = "always-approve runs every action; llm-approve lets the LLM security analyzer reject risky ones"
+    )]
+    pub confirmation_mode: ConfirmationMode,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Local Model",
+        description = "Run a model served by Ollama or an OpenAI-compatible server; replaces Model and Base URL"
+    )]
+    pub local_llm: Option<LocalLlm>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "OpenRouter",
+        description = "Route the model through OpenRouter with the OPENROUTER_API_KEY key; replaces Model and Base URL"
+    )]
+    pub openrouter: Option<OpenRouter>,
+
+    #[serde(flatten)]
+    pub cmd: CmdOverrides,
+}
+
+impl Openhands {
+    fn build_command_builder(&self) -> CommandBuilder {
+        let mut builder = CommandBuilder::new("uvx --python 3.12 openhands@1.3.0")
+            .params(["--headless", "--json"]);
+
+        builder = match self.confirmation_mode {
+            ConfirmationMode::AlwaysApprove => builder.extend_params(["--always-approve"]),
+            ConfirmationMode::LlmApprove => builder.extend_params(["--llm-approve"]),
+        };
+
+        // Model settings are passed as LLM_* variables, which the CLI only reads when asked to
+        if self.model.is_some()
+            || self.base_url.is_some()
+            || self.local_llm.is_some()
+            || self.openrouter.is_some()
+        {
+            builder = builder.extend_params(["--override-with-envs"]);
+        }
+
+        apply_overrides(builder, &self.cmd)
+    }
+
+    async fn spawn_openhands(
+        &self,
+        command_parts: CommandParts,
+        prompt: &str,
+        current_dir: &Path,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let (program_path, args) = command_parts.into_resolved().await?;
+        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+
+        let mut command = env.command(program_path);
+        command
+            .kill_on_drop(true)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(current_dir)
+            .args(&args)
+            .args(["--task", &combined_prompt])
+            .env("NO_COLOR", "1");
+
+        if let Some(local_llm) = &self.local_llm {
+            let (model, base_url) = match local_llm.provider {
+                LocalLlmProvider::Ollama => {
+                    (format!("ollama/{}", local_llm.model), local_llm.base_url())
+                }
+                LocalLlmProvider::OpenaiCompatible => (
+                    format!("openai/{}", local_llm.model),
+                    local_llm.openai_base_url(),
+                ),
+            };
+            // Local servers ignore the key, but LiteLLM refuses to call without one
+            command
+                .env("LLM_MODEL", model)
+                .env("LLM_BASE_URL", base_url)
+                .env("LLM_API_KEY", "local");
+            if let Some(context_window) = local_llm.context_window {
+                command.env("LLM_MAX_INPUT_TOKENS", context_window.to_string());
+            }
+        } else if let Some(openrouter) = &self.openrouter {
+            command.env("LLM_MODEL", format!("openrouter/{}", openrouter.model));
+            if let Some(api_key) = OpenRouter::api_key(&self.cmd) {
+                command.env("LLM_API_KEY", api_key);
+            }
+        } else {
+            if let Some(model) = &self.model {
+                command.env("LLM_MODEL", model);
+            }
+            if let Some(base_url) = &self.base_url {
+                command.env("LLM_BASE_URL", base_url);
+            }
+        }
+
+        env.clone()
+            .with_profile(&self.cmd)
+            .apply_to_command(&mut command);
+
+        let child = command.group_spawn()?;
+
+        Ok(child.into())
+    }
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for Openhands {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let command_parts = self.build_command_builder().build_initial()?;
+        self.spawn_openhands(command_parts, prompt, current_dir, env)
+            .await
+    }
+
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let command_parts = self
+            .build_command_builder()
+            .build_follow_up(&["--resume".to_string(), session_id.to_string()])?;
+        self.spawn_openhands(command_parts, prompt, current_dir, env)
+            .await
+    }
+
+    fn normalize_logs(&self, msg_store: Arc<MsgStore>, current_dir: &Path) {
+        normalize_logs(
+            msg_store.clone(),
+            current_dir,
+            EntryIndexProvider::start_from(&msg_store),
+        );
+    }
+
+    fn default_mcp_config_path(&self) -> Option<std::path::PathBuf> {
+        dirs::home_dir().map(|home| home.join(".openhands").join("mcp.json"))
+    }
+
+    fn get_availability_info(&self) -> AvailabilityInfo {
+        let mcp_config_found = self
+            .default_mcp_config_path()
+            .map(|p| p.exists())
+            .unwrap_or(false);
+
+        // Written by the CLI once a model and API key have been set up
+        let installation_indicator_found = dirs::home_dir()
+            .map(|home| home.join(".openhands").join("agent_settings.json").exists())
+            .unwrap_or(false);
+
+        if mcp_config_found || installation_indicator_found {
+            AvailabilityInfo::InstallationFound
+        } else {
+            AvailabilityInfo::NotFound
+        }
+    }
+}
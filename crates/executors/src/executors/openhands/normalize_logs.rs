@@ -0,0 +1,559 @@
+use std::{collections::HashMap, path::Path, sync::Arc};
+
+use futures::StreamExt;
+use serde_json::Value;
+use workspace_utils::{diff::create_unified_diff, msg_store::MsgStore, path::make_path_relative};
+
+use crate::logs::{
+    ActionType, CommandExitStatus, CommandRunResult, FileChange, NormalizedEntry,
+    NormalizedEntryError, NormalizedEntryType, TodoItem, ToolResult, ToolStatus,
+    stderr_processor::normalize_stderr_logs,
+    utils::{
+        EntryIndexProvider,
+        patch::{add_normalized_entry, replace_normalized_entry},
+    },
+};
+
+pub fn normalize_logs(
+    msg_store: Arc<MsgStore>,
+    worktree_path: &Path,
+    entry_index_provider: EntryIndexProvider,
+) {
+    normalize_stderr_logs(msg_store.clone(), entry_index_provider.clone());
+
+    let worktree_path = worktree_path.to_string_lossy().to_string();
+    tokio::spawn(async move {
+        let mut processor = OpenHandsEventProcessor::new(worktree_path);
+        let mut session_id_extracted = false;
+        // Entry index of each tool call, by tool call id
+        let mut tool_entries = HashMap::new();
+        let mut stdout_lines = msg_store.stdout_lines_stream();
+
+        while let Some(Ok(line)) = stdout_lines.next().await {
+            let trimmed = line.trim();
+            let Ok(event) = serde_json::from_str::<Value>(trimmed) else {
+                if !session_id_extracted && let Some(id) = parse_conversation_id(trimmed) {
+                    msg_store.push_session_id(id.to_string());
+                    session_id_extracted = true;
+                } else if !trimmed.is_empty() {
+                    let entry = NormalizedEntry {
+                        timestamp: None,
+                        entry_type: NormalizedEntryType::SystemMessage,
+                        content: strip_ansi_escapes::strip_str(trimmed).to_string(),
+                        metadata: None,
+                    };
+                    add_normalized_entry(&msg_store, &entry_index_provider, entry);
+                }
+                continue;
+            };
+
+            for update in processor.process(&event) {
+                match update {
+                    EntryUpdate::Add { call_id, entry } => {
+                        let index = add_normalized_entry(&msg_store, &entry_index_provider, entry);
+                        if let Some(call_id) = call_id {
+                            tool_entries.insert(call_id, index);
+                        }
+                    }
+                    EntryUpdate::Replace { call_id, entry } => match tool_entries.get(&call_id) {
+                        Some(index) => replace_normalized_entry(&msg_store, *index, entry),
+                        None => {
+                            add_normalized_entry(&msg_store, &entry_index_provider, entry);
+                        }
+                    },
+                }
+            }
+        }
+    });
+}
+
+/// The conversation id from the line the CLI prints to say how to resume it, e.g.
+/// `Conversation ID: 0f6c...` or `openhands --resume 0f6c...`
+pub fn parse_conversation_id(line: &str) -> Option<&str> {
+    let line = strip_prefix_ci(line, "conversation id:")
+        .or_else(|| line.split_once("--resume ").map(|(_, rest)| rest))?;
+    let id = line.split_whitespace().next()?;
+    let is_id = id.len() >= 32 && id.chars().all(|c| c.is_ascii_hexdigit() || c == '-');
+    is_id.then_some(id)
+}
+
+fn strip_prefix_ci<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    let start = line.to_ascii_lowercase().find(prefix)?;
+    Some(&line[start + prefix.len()..])
+}
+
+/// A change to the conversation, keyed by tool call so observations can update their action
+#[derive(Debug)]
+pub enum EntryUpdate {
+    Add {
+        call_id: Option<String>,
+        entry: NormalizedEntry,
+    },
+    Replace {
+        call_id: String,
+        entry: NormalizedEntry,
+    },
+}
+
+/// Maps the OpenHands CLI's JSON events (`--headless --json`) to normalized entries. Tools are
+/// recognised by name rather than event kind, which has changed between releases.
+pub struct OpenHandsEventProcessor {
+    worktree_path: String,
+    /// Tool entries waiting for their observation, by tool call id
+    pending: HashMap<String, NormalizedEntry>,
+}
+
+impl OpenHandsEventProcessor {
+    pub fn new(worktree_path: String) -> Self {
+        Self {
+            worktree_path,
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn process(&mut self, event: &Value) -> Vec<EntryUpdate> {
+        let kind = event
+            .get("kind")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        match kind {
+            "MessageEvent" => {
+                let is_agent = event.get("source").and_then(Value::as_str) == Some("agent");
+                let text = content_text(event.pointer("/llm_message/content"));
+                if !is_agent || text.is_empty() {
+                    return vec![];
+                }
+                vec![add(
+                    None,
+                    message(NormalizedEntryType::AssistantMessage, text),
+                )]
+            }
+            "ActionEvent" => self.process_action(event),
+            "ObservationEvent" => self.process_observation(event),
+            "UserRejectObservation" => {
+                let reason = event
+                    .get("rejection_reason")
+                    .and_then(Value::as_str)
+                    .map(str::to_string);
+                self.finish_call(event, ToolStatus::Denied { reason })
+                    .into_iter()
+                    .collect()
+            }
+            "AgentErrorEvent" => {
+                let error = event
+                    .get("error")
+                    .and_then(Value::as_str)
+                    .unwrap_or("Agent error")
+                    .to_string();
+                let mut updates: Vec<_> = self
+                    .finish_call(event, ToolStatus::Failed)
+                    .into_iter()
+                    .collect();
+                updates.push(add(None, error_message(error)));
+                updates
+            }
+            kind if kind.ends_with("ErrorEvent") => {
+                let detail = event
+                    .get("detail")
+                    .or_else(|| event.get("error"))
+                    .and_then(Value::as_str)
+                    .unwrap_or(kind)
+                    .to_string();
+                vec![add(None, error_message(detail))]
+            }
+            _ => vec![],
+        }
+    }
+
+    fn process_action(&mut self, event: &Value) -> Vec<EntryUpdate> {
+        let mut updates = vec![];
+        if let Some(reasoning) = event
+            .get("reasoning_content")
+            .and_then(Value::as_str)
+            .filter(|text| !text.trim().is_empty())
+        {
+            updates.push(add(
+                None,
+                message(NormalizedEntryType::Thinking, reasoning.trim().to_string()),
+            ));
+        }
+        let thought = content_text(event.get("thought"));
+        if !thought.is_empty() {
+            updates.push(add(
+                None,
+                message(NormalizedEntryType::AssistantMessage, thought),
+            ));
+        }
+
+        let action = event.get("action").cloned().unwrap_or(Value::Null);
+        let tool_name = event
+            .get("tool_name")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let call_id = event
+            .get("tool_call_id")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let text = |key: &str| action.get(key).and_then(Value::as_str).unwrap_or_default();
+
+        let (action_type, content) = match tool_name {
+            "finish" => {
+                let message_text = text("message").trim().to_string();
+                if !message_text.is_empty() {
+                    updates.push(add(
+                        None,
+                        message(NormalizedEntryType::AssistantMessage, message_text),
+                    ));
+                }
+                return updates;
+            }
+            "think" => {
+                let thought = text("thought").trim().to_string();
+                if !thought.is_empty() {
+                    updates.push(add(None, message(NormalizedEntryType::Thinking, thought)));
+                }
+                return updates;
+            }
+            "terminal" | "execute_bash" | "bash" => {
+                let command = text("command").to_string();
+                (
+                    ActionType::CommandRun {
+                        command: command.clone(),
+                        result: None,
+                    },
+                    command,
+                )
+            }
+            "file_editor" | "str_replace_editor" => {
+                let path = make_path_relative(text("path"), &self.worktree_path);
+                let changes = match text("command") {
+                    "view" => {
+                        return self.start_call(
+                            updates,
+                            call_id,
+                            tool_name,
+                            ActionType::FileRead { path: path.clone() },
+                            path,
+                        );
+                    }
+                    "create" => vec![FileChange::Write {
+                        content: text("file_text").to_string(),
+                    }],
+                    "str_replace" => vec![FileChange::Edit {
+                        unified_diff: create_unified_diff(&path, text("old_str"), text("new_str")),
+                        has_line_numbers: false,
+                    }],
+                    "insert" => vec![FileChange::Edit {
+                        unified_diff: create_unified_diff(&path, "", text("new_str")),
+                        has_line_numbers: false,
+                    }],
+                    _ => vec![],
+                };
+                (
+                    ActionType::FileEdit {
+                        path: path.clone(),
+                        changes,
+                    },
+                    path,
+                )
+            }
+            "task_tracker" => {
+                let todos = action
+                    .get("task_list")
+                    .and_then(Value::as_array)
+                    .map(|tasks| {
+                        tasks
+                            .iter()
+                            .map(|task| TodoItem {
+                                content: task
+                                    .get("title")
+                                    .and_then(Value::as_str)
+                                    .unwrap_or_default()
+                                    .to_string(),
+                                status: task
+                                    .get("status")
+                                    .and_then(Value::as_str)
+                                    .unwrap_or("todo")
+                                    .to_string(),
+                                priority: None,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (
+                    ActionType::TodoManagement {
+                        todos,
+                        operation: text("command").to_string(),
+                    },
+                    "Task list".to_string(),
+                )
+            }
+            _ => (
+                ActionType::Tool {
+                    tool_name: tool_name.to_string(),
+                    arguments: Some(action.clone()),
+                    result: None,
+                },
+                tool_name.to_string(),
+            ),
+        };
+        self.start_call(updates, call_id, tool_name, action_type, content)
+    }
+
+    fn start_call(
+        &mut self,
+        mut updates: Vec<EntryUpdate>,
+        call_id: Option<String>,
+        tool_name: &str,
+        action_type: ActionType,
+        content: String,
+    ) -> Vec<EntryUpdate> {
+        let entry = NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::ToolUse {
+                tool_name: tool_name.to_string(),
+                action_type,
+                status: ToolStatus::Created,
+            },
+            content,
+            metadata: None,
+        };
+        if let Some(call_id) = &call_id {
+            self.pending.insert(call_id.clone(), entry.clone());
+        }
+        updates.push(add(call_id, entry));
+        updates
+    }
+
+    fn process_observation(&mut self, event: &Value) -> Vec<EntryUpdate> {
+        let Some(call_id) = event.get("tool_call_id").and_then(Value::as_str) else {
+            return vec![];
+        };
+        let Some(mut entry) = self.pending.remove(call_id) else {
+            return vec![];
+        };
+        let observation = event.get("observation").cloned().unwrap_or(Value::Null);
+        let mut output = content_text(observation.get("content"));
+        if output.is_empty() {
+            output = content_text(observation.get("output"));
+        }
+        let exit_code = observation
+            .get("exit_code")
+            .and_then(Value::as_i64)
+            .map(|code| code as i32);
+        let failed = observation
+            .get("is_error")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+            || exit_code.is_some_and(|code| code != 0);
+
+        let NormalizedEntryType::ToolUse {
+            tool_name,
+            action_type,
+            ..
+        } = entry.entry_type
+        else {
+            return vec![];
+        };
+        let action_type = match action_type {
+            ActionType::CommandRun { command, .. } => ActionType::CommandRun {
+                command,
+                result: Some(CommandRunResult {
+                    exit_status: exit_code.map(|code| CommandExitStatus::ExitCode { code }),
+                    output: (!output.is_empty()).then_some(output),
+                }),
+            },
+            // The observation carries the whole file before and after, which gives a diff with
+            // real line numbers
+            ActionType::FileEdit { path, changes } => {
+                let old = observation.get("old_content").and_then(Value::as_str);
+                let new = observation.get("new_content").and_then(Value::as_str);
+                let changes = match (old, new) {
+                    (_, Some(new))
+                        if observation.get("prev_exist").and_then(Value::as_bool)
+                            == Some(false) =>
+                    {
+                        vec![FileChange::Write {
+                            content: new.to_string(),
+                        }]
+                    }
+                    (Some(old), Some(new)) => vec![FileChange::Edit {
+                        unified_diff: create_unified_diff(&path, old, new),
+                        has_line_numbers: true,
+                    }],
+                    _ => changes,
+                };
+                ActionType::FileEdit { path, changes }
+            }
+            ActionType::Tool {
+                tool_name,
+                arguments,
+                ..
+            } => ActionType::Tool {
+                tool_name,
+                arguments,
+                result: (!output.is_empty()).then(|| ToolResult::markdown(output)),
+            },
+            action_type => action_type,
+        };
+        entry.entry_type = NormalizedEntryType::ToolUse {
+            tool_name,
+            action_type,
+            status: if failed {
+                ToolStatus::Failed
+            } else {
+                ToolStatus::Success
+            },
+        };
+        vec![EntryUpdate::Replace {
+            call_id: call_id.to_string(),
+            entry,
+        }]
+    }
+
+    /// Marks the event's tool call as finished with `status`
+    fn finish_call(&mut self, event: &Value, status: ToolStatus) -> Option<EntryUpdate> {
+        let call_id = event.get("tool_call_id").and_then(Value::as_str)?;
+        let entry = self.pending.remove(call_id)?.with_tool_status(status)?;
+        Some(EntryUpdate::Replace {
+            call_id: call_id.to_string(),
+            entry,
+        })
+    }
+}
+
+/// Text of a string or of a list of `{"type": "text", "text": ...}` parts
+fn content_text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(text)) => text.trim().to_string(),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|part| part.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string(),
+        _ => String::new(),
+    }
+}
+
+fn add(call_id: Option<String>, entry: NormalizedEntry) -> EntryUpdate {
+    EntryUpdate::Add { call_id, entry }
+}
+
+fn message(entry_type: NormalizedEntryType, content: String) -> NormalizedEntry {
+    NormalizedEntry {
+        timestamp: None,
+        entry_type,
+        content,
+        metadata: None,
+    }
+}
+
+fn error_message(content: String) -> NormalizedEntry {
+    message(
+        NormalizedEntryType::ErrorMessage {
+            error_type: NormalizedEntryError::Other,
+        },
+        content,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn process(events: &[Value]) -> Vec<EntryUpdate> {
+        let mut processor = OpenHandsEventProcessor::new("/work/tree".to_string());
+        events
+            .iter()
+            .flat_map(|event| processor.process(event))
+            .collect()
+    }
+
+    #[test]
+    fn test_command_and_file_edit_calls() {
+        let updates = process(&[
+            json!({"kind": "MessageEvent", "source": "user", "llm_message": {"role": "user", "content": [{"type": "text", "text": "Fix it"}]}}),
+            json!({"kind": "ActionEvent", "source": "agent", "thought": [{"type": "text", "text": "Run the tests first."}], "tool_name": "terminal", "tool_call_id": "call_1", "action": {"kind": "TerminalAction", "command": "cargo test"}}),
+            json!({"kind": "ObservationEvent", "tool_name": "terminal", "tool_call_id": "call_1", "observation": {"kind": "TerminalObservation", "content": [{"type": "text", "text": "1 failed"}], "exit_code": 101}}),
+            json!({"kind": "ActionEvent", "source": "agent", "thought": [], "tool_name": "file_editor", "tool_call_id": "call_2", "action": {"kind": "FileEditorAction", "command": "str_replace", "path": "/work/tree/src/lib.rs", "old_str": "a - b", "new_str": "a + b"}}),
+            json!({"kind": "ObservationEvent", "tool_name": "file_editor", "tool_call_id": "call_2", "observation": {"kind": "FileEditorObservation", "path": "/work/tree/src/lib.rs", "prev_exist": true, "old_content": "fn add() {}\na - b\n", "new_content": "fn add() {}\na + b\n"}}),
+            json!({"kind": "ActionEvent", "source": "agent", "tool_name": "finish", "tool_call_id": "call_3", "action": {"kind": "FinishAction", "message": "Fixed the sign."}}),
+        ]);
+        assert_eq!(updates.len(), 6, "{updates:#?}");
+
+        let EntryUpdate::Add {
+            call_id: None,
+            entry,
+        } = &updates[0]
+        else {
+            panic!("expected the thought: {:?}", updates[0]);
+        };
+        assert_eq!(entry.content, "Run the tests first.");
+
+        let EntryUpdate::Replace { call_id, entry } = &updates[2] else {
+            panic!("expected the command result: {:?}", updates[2]);
+        };
+        assert_eq!(call_id, "call_1");
+        let NormalizedEntryType::ToolUse {
+            action_type: ActionType::CommandRun { result, .. },
+            status: ToolStatus::Failed,
+            ..
+        } = &entry.entry_type
+        else {
+            panic!("expected a failed command: {entry:?}");
+        };
+        let result = result.as_ref().unwrap();
+        assert_eq!(result.output.as_deref(), Some("1 failed"));
+        assert!(matches!(
+            result.exit_status,
+            Some(CommandExitStatus::ExitCode { code: 101 })
+        ));
+
+        let EntryUpdate::Replace { entry, .. } = &updates[4] else {
+            panic!("expected the edit result: {:?}", updates[4]);
+        };
+        let NormalizedEntryType::ToolUse {
+            action_type: ActionType::FileEdit { path, changes },
+            status: ToolStatus::Success,
+            ..
+        } = &entry.entry_type
+        else {
+            panic!("expected a file edit: {entry:?}");
+        };
+        assert_eq!(path, "src/lib.rs");
+        let [
+            FileChange::Edit {
+                unified_diff,
+                has_line_numbers: true,
+            },
+        ] = changes.as_slice()
+        else {
+            panic!("expected one edit: {changes:?}");
+        };
+        assert!(unified_diff.contains("+a + b"));
+
+        let EntryUpdate::Add { entry, .. } = &updates[5] else {
+            panic!("expected the final message: {:?}", updates[5]);
+        };
+        assert_eq!(entry.content, "Fixed the sign.");
+    }
+
+    #[test]
+    fn test_conversation_id() {
+        assert_eq!(
+            parse_conversation_id("Conversation ID: 6f1e0c2a9b8d4e7f8a1b2c3d4e5f6a7b"),
+            Some("6f1e0c2a9b8d4e7f8a1b2c3d4e5f6a7b")
+        );
+        assert_eq!(
+            parse_conversation_id(
+                "Resume with: openhands --resume 6f1e0c2a-9b8d-4e7f-8a1b-2c3d4e5f6a7b"
+            ),
+            Some("6f1e0c2a-9b8d-4e7f-8a1b-2c3d4e5f6a7b")
+        );
+        assert_eq!(parse_conversation_id("Conversation ID: pending"), None);
+    }
+}
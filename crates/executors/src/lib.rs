@@ -3,7 +3,12 @@ pub mod approvals;
 pub mod command;
 pub mod env;
 pub mod executors;
+pub mod local_llm;
 pub mod logs;
 pub mod mcp_config;
+pub mod openrouter;
+pub mod process_limits;
 pub mod profile;
+pub mod profile_test;
+pub mod sandbox;
 pub mod stdout_dup;
@@ -0,0 +1,208 @@
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use ts_rs::TS;
+
+/// First responses slower than this get a warning in the profile test
+pub const SLOW_RESPONSE_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// Context windows below this are too small for most coding agents' system prompts
+pub const MIN_CONTEXT_WINDOW: u32 = 16_384;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, TS, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalLlmProvider {
+    #[default]
+    Ollama,
+    OpenaiCompatible,
+}
+
+/// A locally hosted model, served by Ollama or an OpenAI-compatible server
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+pub struct LocalLlm {
+    #[serde(default)]
+    #[schemars(title = "Provider", description = "Server hosting the model")]
+    pub provider: LocalLlmProvider,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Base URL",
+        description = "Server URL; http://localhost:11434 for Ollama and http://localhost:8080/v1 for OpenAI-compatible servers when unset"
+    )]
+    pub base_url: Option<String>,
+
+    #[schemars(
+        title = "Model",
+        description = "Model name as the server knows it (e.g., qwen3-coder:30b, devstral)"
+    )]
+    pub model: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Context Window",
+        description = "Context window in tokens the model is served with"
+    )]
+    pub context_window: Option<u32>,
+}
+
+impl LocalLlm {
+    pub fn base_url(&self) -> String {
+        let default = match self.provider {
+            LocalLlmProvider::Ollama => "http://localhost:11434",
+            LocalLlmProvider::OpenaiCompatible => "http://localhost:8080/v1",
+        };
+        self.base_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .unwrap_or(default)
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    /// Base URL of the OpenAI-compatible API, which Ollama serves under `/v1`
+    pub fn openai_base_url(&self) -> String {
+        match self.provider {
+            LocalLlmProvider::Ollama => format!("{}/v1", self.base_url()),
+            LocalLlmProvider::OpenaiCompatible => self.base_url(),
+        }
+    }
+
+    /// Endpoint listing the models the server has
+    pub fn models_url(&self) -> String {
+        match self.provider {
+            LocalLlmProvider::Ollama => format!("{}/api/tags", self.base_url()),
+            LocalLlmProvider::OpenaiCompatible => format!("{}/models", self.base_url()),
+        }
+    }
+
+    pub fn chat_completions_url(&self) -> String {
+        format!("{}/chat/completions", self.openai_base_url())
+    }
+
+    /// Names of the models in a response from [`Self::models_url`]
+    pub fn parse_model_names(&self, response: &Value) -> Vec<String> {
+        let (list, key) = match self.provider {
+            LocalLlmProvider::Ollama => ("models", "name"),
+            LocalLlmProvider::OpenaiCompatible => ("data", "id"),
+        };
+        response
+            .get(list)
+            .and_then(Value::as_array)
+            .map(|models| {
+                models
+                    .iter()
+                    .filter_map(|model| model.get(key).and_then(Value::as_str))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Whether the configured model is among `names`. Ollama adds a `:latest` tag to models
+    /// pulled without one.
+    pub fn has_model(&self, names: &[String]) -> bool {
+        let model = self.model.trim();
+        names
+            .iter()
+            .any(|name| name == model || name.strip_suffix(":latest") == Some(model))
+    }
+
+    /// Warnings for a working endpoint, given how long a one-token completion took
+    pub fn warnings(&self, first_response: Option<Duration>) -> Vec<String> {
+        let mut warnings = vec![];
+        if let Some(elapsed) = first_response.filter(|elapsed| *elapsed > SLOW_RESPONSE_THRESHOLD) {
+            warnings.push(format!(
+                "The model took {:.1}s to answer a one-token request, so task attempts will be \
+                 slow. Keep the model loaded or use a smaller one.",
+                elapsed.as_secs_f64()
+            ));
+        }
+        if let Some(context_window) = self
+            .context_window
+            .filter(|context_window| *context_window < MIN_CONTEXT_WINDOW)
+        {
+            warnings.push(format!(
+                "A context window of {context_window} tokens is small for coding agents; \
+                 {MIN_CONTEXT_WINDOW} or more is recommended."
+            ));
+        }
+        warnings
+    }
+}
+
+/// Outcome of testing a local model endpoint from the profile settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct LocalLlmTestResult {
+    pub reachable: bool,
+    pub model_available: bool,
+    /// Time taken to list the server's models
+    pub endpoint_latency_ms: Option<u64>,
+    /// Time taken to answer a one-token completion, including loading the model
+    pub response_latency_ms: Option<u64>,
+    pub warnings: Vec<String>,
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn local_llm(provider: LocalLlmProvider, base_url: Option<&str>) -> LocalLlm {
+        LocalLlm {
+            provider,
+            base_url: base_url.map(str::to_string),
+            model: "qwen3-coder".to_string(),
+            context_window: None,
+        }
+    }
+
+    #[test]
+    fn test_endpoint_urls() {
+        let ollama = local_llm(LocalLlmProvider::Ollama, None);
+        assert_eq!(ollama.models_url(), "http://localhost:11434/api/tags");
+        assert_eq!(
+            ollama.chat_completions_url(),
+            "http://localhost:11434/v1/chat/completions"
+        );
+
+        let server = local_llm(
+            LocalLlmProvider::OpenaiCompatible,
+            Some("http://gpu-box:1234/v1/"),
+        );
+        assert_eq!(server.models_url(), "http://gpu-box:1234/v1/models");
+        assert_eq!(
+            server.chat_completions_url(),
+            "http://gpu-box:1234/v1/chat/completions"
+        );
+    }
+
+    #[test]
+    fn test_model_lookup() {
+        let ollama = local_llm(LocalLlmProvider::Ollama, None);
+        let names = ollama.parse_model_names(&json!({
+            "models": [{"name": "qwen3-coder:latest"}, {"name": "devstral:24b"}]
+        }));
+        assert!(ollama.has_model(&names));
+
+        let server = local_llm(LocalLlmProvider::OpenaiCompatible, None);
+        let names = server.parse_model_names(&json!({"data": [{"id": "devstral"}]}));
+        assert_eq!(names, vec!["devstral".to_string()]);
+        assert!(!server.has_model(&names));
+    }
+
+    #[test]
+    fn test_warnings() {
+        let mut config = local_llm(LocalLlmProvider::Ollama, None);
+        assert!(config.warnings(Some(Duration::from_secs(1))).is_empty());
+
+        config.context_window = Some(4096);
+        let warnings = config.warnings(Some(Duration::from_secs(12)));
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(warnings[0].contains("12.0s"));
+    }
+}
@@ -0,0 +1,73 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use ts_rs::TS;
+
+use crate::command::CmdOverrides;
+
+pub const API_KEY_ENV: &str = "OPENROUTER_API_KEY";
+
+/// Endpoint reporting the credits spent by the calling key
+pub const KEY_URL: &str = "https://openrouter.ai/api/v1/key";
+
+/// A model reached through OpenRouter, billed to the key in `OPENROUTER_API_KEY`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+pub struct OpenRouter {
+    #[schemars(
+        title = "Model",
+        description = "OpenRouter model slug (e.g., anthropic/claude-sonnet-4.5, openai/gpt-5, qwen/qwen3-coder)"
+    )]
+    pub model: String,
+}
+
+impl OpenRouter {
+    /// Key from the profile's environment variables, falling back to the server's
+    pub fn api_key(cmd: &CmdOverrides) -> Option<String> {
+        cmd.env
+            .as_ref()
+            .and_then(|env| env.get(API_KEY_ENV).cloned())
+            .or_else(|| std::env::var(API_KEY_ENV).ok())
+            .filter(|key| !key.trim().is_empty())
+    }
+}
+
+/// Credits in USD the key has spent, from a response of [`KEY_URL`]
+pub fn parse_key_usage(response: &Value) -> Option<f64> {
+    response.pointer("/data/usage").and_then(Value::as_f64)
+}
+
+/// Spend between two readings of the key's usage. OpenRouter settles a generation's cost
+/// shortly after it completes, so a reading can briefly lag and the delta is clamped at zero.
+pub fn usage_delta(before: f64, after: f64) -> f64 {
+    (after - before).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_key_usage() {
+        let response = json!({"data": {"label": "sk-or-v1-abc", "usage": 12.5, "limit": null}});
+        assert_eq!(parse_key_usage(&response), Some(12.5));
+        assert_eq!(parse_key_usage(&json!({"error": {"code": 401}})), None);
+        assert_eq!(usage_delta(12.5, 12.75), 0.25);
+        assert_eq!(usage_delta(12.5, 12.0), 0.0);
+    }
+
+    #[test]
+    fn test_api_key_prefers_profile_env() {
+        let cmd = CmdOverrides {
+            env: Some(HashMap::from([(
+                API_KEY_ENV.to_string(),
+                "sk-or-profile".to_string(),
+            )])),
+            ..Default::default()
+        };
+        assert_eq!(OpenRouter::api_key(&cmd).as_deref(), Some("sk-or-profile"));
+    }
+}
@@ -0,0 +1,270 @@
+//! CPU, memory and process-count limits and a lower scheduling priority for executor
+//! processes, so one attempt's builds can't starve the host or other attempts. Each execution
+//! process gets its own limits, shared with everything it starts: on Linux in a transient
+//! systemd scope (a cgroup), on Windows in a job object. Elsewhere only the priority applies.
+
+use std::{
+    ffi::{OsStr, OsString},
+    path::PathBuf,
+};
+
+use command_group::AsyncGroupChild;
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+use ts_rs::TS;
+
+/// Limits for each execution process on the host; sandboxed agents get the sandbox's instead
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct ProcessLimits {
+    /// CPU time the process and its children may use, in percent of one core; 150 is one and a
+    /// half cores
+    pub cpu_percent: Option<u32>,
+    pub memory_mb: Option<u32>,
+    /// Most processes running at once; on Linux threads count too
+    pub max_processes: Option<u32>,
+    /// Niceness from 0 to 19, higher running at lower priority. On Windows, up to 14 runs below
+    /// normal priority and 15 or more at idle priority.
+    pub nice: Option<u32>,
+}
+
+impl ProcessLimits {
+    pub fn has_resource_limits(&self) -> bool {
+        self.cpu_percent.is_some() || self.memory_mb.is_some() || self.max_processes.is_some()
+    }
+
+    fn nice(&self) -> Option<u32> {
+        self.nice.filter(|nice| *nice > 0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.has_resource_limits() && self.nice().is_none()
+    }
+
+    /// Program and arguments a command is prefixed with to run under the limits on Unix.
+    /// Without `systemd_run` only the priority is set.
+    fn unix_prefix(&self, systemd_run: Option<&OsStr>) -> Vec<OsString> {
+        let mut prefix: Vec<OsString> = Vec::new();
+        if let Some(systemd_run) = systemd_run
+            && self.has_resource_limits()
+        {
+            prefix.push(systemd_run.to_owned());
+            prefix.extend(["--user", "--scope", "--quiet", "--collect"].map(OsString::from));
+            if let Some(cpu_percent) = self.cpu_percent {
+                prefix.push(format!("--property=CPUQuota={cpu_percent}%").into());
+            }
+            if let Some(memory_mb) = self.memory_mb {
+                prefix.push(format!("--property=MemoryMax={memory_mb}M").into());
+            }
+            if let Some(max_processes) = self.max_processes {
+                prefix.push(format!("--property=TasksMax={max_processes}").into());
+            }
+            prefix.push("--".into());
+        }
+        if let Some(nice) = self.nice() {
+            prefix.extend(["nice", "-n"].map(OsString::from));
+            prefix.push(nice.to_string().into());
+        }
+        prefix
+    }
+}
+
+/// Process limits as this host can enforce them
+#[derive(Debug, Clone)]
+pub struct ProcessConfinement {
+    pub limits: ProcessLimits,
+    /// Runs commands in a cgroup with the resource limits; only on Linux with systemd
+    systemd_run: Option<PathBuf>,
+}
+
+impl ProcessConfinement {
+    pub async fn new(limits: ProcessLimits) -> Self {
+        let systemd_run = if cfg!(target_os = "linux") && limits.has_resource_limits() {
+            workspace_utils::shell::resolve_executable_path("systemd-run").await
+        } else {
+            None
+        };
+        if cfg!(unix) && systemd_run.is_none() && limits.has_resource_limits() {
+            tracing::warn!(
+                "CPU, memory and process limits need systemd-run, which isn't available; only the priority is lowered"
+            );
+        }
+        Self {
+            limits,
+            systemd_run,
+        }
+    }
+
+    /// A command that runs `program` under the limits. On Windows the limits are applied once
+    /// the process is spawned, by [`Self::confine`].
+    pub fn command(&self, program: impl AsRef<OsStr>) -> Command {
+        let prefix = if cfg!(unix) {
+            self.limits
+                .unix_prefix(self.systemd_run.as_deref().map(|path| path.as_os_str()))
+        } else {
+            Vec::new()
+        };
+        match prefix.split_first() {
+            Some((wrapper, wrapper_args)) => {
+                let mut command = Command::new(wrapper);
+                command.args(wrapper_args).arg(program);
+                command
+            }
+            None => Command::new(program),
+        }
+    }
+
+    /// Put a spawned process under the limits where that can't happen when it starts
+    pub fn confine(&self, child: &mut AsyncGroupChild) -> std::io::Result<()> {
+        #[cfg(windows)]
+        {
+            self.confine_windows(child)
+        }
+        #[cfg(not(windows))]
+        {
+            let _ = child;
+            Ok(())
+        }
+    }
+
+    /// Lowers the priority class and assigns the process to a job object holding the
+    /// resource limits. Processes it started before being assigned aren't limited.
+    #[cfg(windows)]
+    fn confine_windows(&self, child: &mut AsyncGroupChild) -> std::io::Result<()> {
+        use std::{ffi::c_void, io, mem, ptr};
+
+        use windows_sys::Win32::{
+            Foundation::CloseHandle,
+            System::{
+                JobObjects::{
+                    AssignProcessToJobObject, CreateJobObjectW, JOB_OBJECT_CPU_RATE_CONTROL_ENABLE,
+                    JOB_OBJECT_CPU_RATE_CONTROL_HARD_CAP, JOB_OBJECT_LIMIT_ACTIVE_PROCESS,
+                    JOB_OBJECT_LIMIT_JOB_MEMORY, JOBOBJECT_CPU_RATE_CONTROL_INFORMATION,
+                    JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JobObjectCpuRateControlInformation,
+                    JobObjectExtendedLimitInformation, SetInformationJobObject,
+                },
+                Threading::{BELOW_NORMAL_PRIORITY_CLASS, IDLE_PRIORITY_CLASS, SetPriorityClass},
+            },
+        };
+
+        // Already exited
+        let Some(process) = child.inner().raw_handle() else {
+            return Ok(());
+        };
+
+        unsafe {
+            if let Some(nice) = self.limits.nice() {
+                let class = if nice >= 15 {
+                    IDLE_PRIORITY_CLASS
+                } else {
+                    BELOW_NORMAL_PRIORITY_CLASS
+                };
+                if SetPriorityClass(process, class) == 0 {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+            if !self.limits.has_resource_limits() {
+                return Ok(());
+            }
+
+            let job = CreateJobObjectW(ptr::null(), ptr::null());
+            if job.is_null() {
+                return Err(io::Error::last_os_error());
+            }
+            let result = (|| {
+                let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = mem::zeroed();
+                if let Some(memory_mb) = self.limits.memory_mb {
+                    info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_JOB_MEMORY;
+                    info.JobMemoryLimit = memory_mb as usize * 1024 * 1024;
+                }
+                if let Some(max_processes) = self.limits.max_processes {
+                    info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_ACTIVE_PROCESS;
+                    info.BasicLimitInformation.ActiveProcessLimit = max_processes;
+                }
+                if info.BasicLimitInformation.LimitFlags != 0
+                    && SetInformationJobObject(
+                        job,
+                        JobObjectExtendedLimitInformation,
+                        &info as *const _ as *const c_void,
+                        mem::size_of_val(&info) as u32,
+                    ) == 0
+                {
+                    return Err(io::Error::last_os_error());
+                }
+
+                if let Some(cpu_percent) = self.limits.cpu_percent {
+                    // The rate is in hundredths of a percent of all cores
+                    let cores = std::thread::available_parallelism().map_or(1, |n| n.get()) as u32;
+                    let mut rate: JOBOBJECT_CPU_RATE_CONTROL_INFORMATION = mem::zeroed();
+                    rate.ControlFlags =
+                        JOB_OBJECT_CPU_RATE_CONTROL_ENABLE | JOB_OBJECT_CPU_RATE_CONTROL_HARD_CAP;
+                    rate.Anonymous.CpuRate =
+                        (cpu_percent.saturating_mul(100) / cores).clamp(1, 10_000);
+                    if SetInformationJobObject(
+                        job,
+                        JobObjectCpuRateControlInformation,
+                        &rate as *const _ as *const c_void,
+                        mem::size_of_val(&rate) as u32,
+                    ) == 0
+                    {
+                        return Err(io::Error::last_os_error());
+                    }
+                }
+
+                if AssignProcessToJobObject(job, process) == 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            })();
+            // The job lives on while the process is assigned to it
+            CloseHandle(job);
+            result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: Vec<OsString>) -> String {
+        args.iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_unix_prefix() {
+        let limits = ProcessLimits {
+            cpu_percent: Some(150),
+            memory_mb: Some(4096),
+            max_processes: Some(512),
+            nice: Some(10),
+        };
+        assert_eq!(
+            strings(limits.unix_prefix(Some(OsStr::new("/usr/bin/systemd-run")))),
+            "/usr/bin/systemd-run --user --scope --quiet --collect --property=CPUQuota=150% \
+             --property=MemoryMax=4096M --property=TasksMax=512 -- nice -n 10"
+        );
+        assert_eq!(strings(limits.unix_prefix(None)), "nice -n 10");
+
+        let limits = ProcessLimits {
+            memory_mb: Some(1024),
+            nice: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(
+            strings(limits.unix_prefix(Some(OsStr::new("systemd-run")))),
+            "systemd-run --user --scope --quiet --collect --property=MemoryMax=1024M --"
+        );
+        assert!(ProcessLimits::default().unix_prefix(None).is_empty());
+        assert!(
+            ProcessLimits {
+                nice: Some(0),
+                ..Default::default()
+            }
+            .is_empty()
+        );
+    }
+}
@@ -67,6 +67,10 @@ pub struct ExecutorProfileId {
     /// Optional variant name (e.g., "PLAN", "ROUTER")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub variant: Option<String>,
+    /// Model to run instead of the variant's, for one task attempt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub model_override: Option<String>,
 }
 
 // Convert legacy profile/executor names from kebab-case to SCREAMING_SNAKE_CASE, can be deleted 14 days from 3/9/25
@@ -87,6 +91,7 @@ impl ExecutorProfileId {
         Self {
             executor,
             variant: None,
+            model_override: None,
         }
     }
 
@@ -95,6 +100,7 @@ impl ExecutorProfileId {
         Self {
             executor,
             variant: Some(variant),
+            model_override: None,
         }
     }
 
@@ -399,6 +405,10 @@ impl ExecutorConfigs {
                 )
             })
             .cloned()
+            .map(|agent| match &executor_profile_id.model_override {
+                Some(model) => agent.with_model_override(model),
+                None => agent,
+            })
     }
 
     pub fn get_coding_agent_or_default(
@@ -480,5 +490,6 @@ pub fn to_default_variant(id: &ExecutorProfileId) -> ExecutorProfileId {
     ExecutorProfileId {
         executor: id.executor,
         variant: None,
+        model_override: None,
     }
 }
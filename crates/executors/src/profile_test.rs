@@ -0,0 +1,281 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{
+    executors::AvailabilityInfo,
+    logs::{NormalizedEntry, NormalizedEntryError, NormalizedEntryType, ToolStatus},
+};
+
+/// File the canned task asks the agent to read; it holds a fresh token per test
+pub const CHECK_FILE: &str = "vk-profile-check.txt";
+
+/// Task small enough to finish in seconds, but needing both the model and a file tool
+pub fn check_prompt() -> String {
+    format!(
+        "This is a quick check of your setup. Use your file reading tool to read `{CHECK_FILE}` \
+         in the current directory, then reply with its contents and nothing else. Do not create \
+         or modify any files."
+    )
+}
+
+/// Outcome of running the canned task against an executor profile
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProfileTestResult {
+    pub availability: AvailabilityInfo,
+    /// The model the profile names, if any
+    pub model: Option<String>,
+    /// `None` when the run did not get far enough to tell
+    pub auth_valid: Option<bool>,
+    pub model_available: Option<bool>,
+    /// Whether the agent could read a file with its tools
+    pub tool_access: Option<bool>,
+    /// Time from starting the agent to its first reply
+    pub first_response_ms: Option<u64>,
+    /// Time the whole task took
+    pub duration_ms: Option<u64>,
+    pub warnings: Vec<String>,
+    pub error: Option<String>,
+}
+
+impl ProfileTestResult {
+    pub fn new(availability: AvailabilityInfo, model: Option<String>) -> Self {
+        Self {
+            availability,
+            model,
+            auth_valid: None,
+            model_available: None,
+            tool_access: None,
+            first_response_ms: None,
+            duration_ms: None,
+            warnings: vec![],
+            error: None,
+        }
+    }
+
+    /// Fill in what the run's normalized log shows. `stderr` is only consulted when the agent
+    /// never replied.
+    pub fn record_run(&mut self, entries: &[NormalizedEntry], stderr: &str, token: &str) {
+        let replies = entries
+            .iter()
+            .filter(|entry| matches!(entry.entry_type, NormalizedEntryType::AssistantMessage))
+            .map(|entry| entry.content.trim())
+            .filter(|content| !content.is_empty())
+            .collect::<Vec<_>>();
+        let replied = !replies.is_empty();
+        let echoed_token = replies.iter().any(|reply| reply.contains(token));
+
+        let mut setup_required = false;
+        let mut errors = vec![];
+        for entry in entries {
+            if let NormalizedEntryType::ErrorMessage { error_type } = &entry.entry_type {
+                setup_required |= matches!(error_type, NormalizedEntryError::SetupRequired);
+                if !entry.content.trim().is_empty() {
+                    errors.push(entry.content.trim());
+                }
+            }
+        }
+        if !replied && !stderr.trim().is_empty() {
+            errors.push(stderr.trim());
+        }
+
+        let tool_statuses = entries.iter().filter_map(|entry| match &entry.entry_type {
+            NormalizedEntryType::ToolUse { status, .. } => Some(status),
+            _ => None,
+        });
+        let (mut tool_worked, mut tool_failed) = (false, false);
+        for status in tool_statuses {
+            match status {
+                ToolStatus::Failed | ToolStatus::Denied { .. } | ToolStatus::TimedOut => {
+                    tool_failed = true
+                }
+                _ => tool_worked = true,
+            }
+        }
+
+        let auth_error = errors.iter().find(|error| is_auth_error(error));
+        let model_error = errors.iter().find(|error| is_model_error(error));
+
+        self.auth_valid = if setup_required || auth_error.is_some() {
+            Some(false)
+        } else if replied {
+            Some(true)
+        } else {
+            None
+        };
+        self.model_available = if model_error.is_some() {
+            Some(false)
+        } else if replied {
+            Some(true)
+        } else {
+            None
+        };
+        self.tool_access = if echoed_token || tool_worked {
+            Some(true)
+        } else if replied || tool_failed {
+            Some(false)
+        } else {
+            None
+        };
+
+        if self.tool_access == Some(false) {
+            self.warnings.push(
+                "The agent could not read a file; check its permissions and tool settings"
+                    .to_string(),
+            );
+        } else if replied && !echoed_token {
+            self.warnings.push(
+                "The agent replied without the file's contents, so it may not follow instructions reliably"
+                    .to_string(),
+            );
+        }
+
+        self.error = auth_error
+            .or(model_error)
+            .or(errors.first())
+            .map(|error| error.to_string())
+            .or_else(|| {
+                (!replied && setup_required)
+                    .then(|| "The agent needs to be set up or logged in".to_string())
+            })
+            .or_else(|| (!replied).then(|| "The agent exited without replying".to_string()));
+    }
+}
+
+fn is_auth_error(error: &str) -> bool {
+    let error = error.to_lowercase();
+    [
+        "unauthorized",
+        "401",
+        "403",
+        "forbidden",
+        "api key",
+        "api_key",
+        "not logged in",
+        "log in",
+        "login",
+        "authenticat",
+        "credential",
+    ]
+    .iter()
+    .any(|needle| error.contains(needle))
+}
+
+fn is_model_error(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("model")
+        && [
+            "not found",
+            "does not exist",
+            "not available",
+            "unavailable",
+            "unknown",
+            "invalid",
+            "unsupported",
+            "not supported",
+            "no access",
+        ]
+        .iter()
+        .any(|needle| error.contains(needle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::ActionType;
+
+    fn entry(entry_type: NormalizedEntryType, content: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    fn read_tool(status: ToolStatus) -> NormalizedEntry {
+        entry(
+            NormalizedEntryType::ToolUse {
+                tool_name: "Read".to_string(),
+                action_type: ActionType::FileRead {
+                    path: CHECK_FILE.to_string(),
+                },
+                status,
+            },
+            CHECK_FILE,
+        )
+    }
+
+    fn untested() -> ProfileTestResult {
+        ProfileTestResult::new(AvailabilityInfo::InstallationFound, None)
+    }
+
+    #[test]
+    fn test_record_run_success() {
+        let mut result = untested();
+        result.record_run(
+            &[
+                read_tool(ToolStatus::Success),
+                entry(NormalizedEntryType::AssistantMessage, "abc123"),
+            ],
+            "",
+            "abc123",
+        );
+        assert_eq!(result.auth_valid, Some(true));
+        assert_eq!(result.model_available, Some(true));
+        assert_eq!(result.tool_access, Some(true));
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.error, None);
+    }
+
+    #[test]
+    fn test_record_run_without_tools() {
+        let mut result = untested();
+        result.record_run(
+            &[
+                read_tool(ToolStatus::Denied { reason: None }),
+                entry(NormalizedEntryType::AssistantMessage, "I can't read files"),
+            ],
+            "",
+            "abc123",
+        );
+        assert_eq!(result.auth_valid, Some(true));
+        assert_eq!(result.tool_access, Some(false));
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.error, None);
+    }
+
+    #[test]
+    fn test_record_run_errors() {
+        let mut result = untested();
+        result.record_run(&[], "Error: Invalid API key\n", "abc123");
+        assert_eq!(result.auth_valid, Some(false));
+        assert_eq!(result.model_available, None);
+        assert_eq!(result.tool_access, None);
+        assert_eq!(result.error.as_deref(), Some("Error: Invalid API key"));
+
+        let mut result = untested();
+        result.record_run(
+            &[entry(
+                NormalizedEntryType::ErrorMessage {
+                    error_type: NormalizedEntryError::Other,
+                },
+                "The model `gpt-9` does not exist",
+            )],
+            "",
+            "abc123",
+        );
+        assert_eq!(result.auth_valid, None);
+        assert_eq!(result.model_available, Some(false));
+        assert_eq!(
+            result.error.as_deref(),
+            Some("The model `gpt-9` does not exist")
+        );
+
+        let mut result = untested();
+        result.record_run(&[], "", "abc123");
+        assert_eq!(
+            result.error.as_deref(),
+            Some("The agent exited without replying")
+        );
+    }
+}
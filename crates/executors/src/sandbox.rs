@@ -0,0 +1,336 @@
+//! Coding agents can run in a Docker or Podman container instead of directly on the host. The
+//! workspace, its repositories and the app's temp directory are mounted at their host paths, so
+//! paths in prompts and logs stay valid. Host environment variables reach the container only
+//! when the executor sets them or they are on the allowlist.
+
+use std::{
+    collections::BTreeSet,
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf},
+    process::Stdio,
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+use ts_rs::TS;
+use workspace_utils::{assets::asset_dir, path::get_vibe_kanban_temp_dir};
+
+/// Where the sandbox home directory is mounted in the container
+const CONTAINER_HOME: &str = "/home/vibe-kanban";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(use_ts_enum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SandboxRuntime {
+    #[default]
+    Docker,
+    Podman,
+}
+
+impl SandboxRuntime {
+    pub fn program(&self) -> &'static str {
+        match self {
+            Self::Docker => "docker",
+            Self::Podman => "podman",
+        }
+    }
+}
+
+/// `Full` leaves the container on the runtime's default network. `None` cuts it off entirely,
+/// the agent's own API included, for agents whose model runs in the image.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(use_ts_enum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SandboxNetwork {
+    #[default]
+    Full,
+    None,
+}
+
+/// Running coding agents in a container; setup scripts and dev servers stay on the host
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct SandboxConfig {
+    pub enabled: bool,
+    pub runtime: SandboxRuntime,
+    /// Image with the coding agent CLIs installed
+    pub image: String,
+    /// Host environment variables passed into the container, e.g. `ANTHROPIC_API_KEY`
+    pub env_passthrough: Vec<String>,
+    /// CPUs the container may use, e.g. 1.5; unlimited when unset
+    pub cpus: Option<f64>,
+    /// Unlimited when unset
+    pub memory_mb: Option<u32>,
+    pub network: SandboxNetwork,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            runtime: SandboxRuntime::default(),
+            image: "node:22-bookworm".to_string(),
+            env_passthrough: Vec::new(),
+            cpus: None,
+            memory_mb: None,
+            network: SandboxNetwork::default(),
+        }
+    }
+}
+
+/// The container one execution runs in
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    pub config: SandboxConfig,
+    /// Unique per execution, so the container can be paused or removed by name
+    pub name: String,
+    /// Host directories mounted at the same path in the container
+    pub mounts: Vec<PathBuf>,
+    /// Host directory mounted as the container's home, so agent sessions and logins outlast
+    /// the container
+    pub home_dir: PathBuf,
+    /// `uid:gid` a Docker container runs as, so the files it writes belong to the host user.
+    /// Rootless Podman keeps the host user by itself.
+    pub user: Option<String>,
+}
+
+/// The mounts left once directories inside another mounted directory are dropped
+fn mount_points(mounts: &[PathBuf]) -> Vec<&Path> {
+    let mut sorted: Vec<&Path> = mounts.iter().map(PathBuf::as_path).collect();
+    sorted.sort();
+    let mut kept: Vec<&Path> = Vec::new();
+    for mount in sorted {
+        if !kept.iter().any(|parent| mount.starts_with(parent)) {
+            kept.push(mount);
+        }
+    }
+    kept
+}
+
+impl Sandbox {
+    /// A sandbox mounting `mounts` and the app's temp directory, running as the owner of
+    /// `owner_dir`
+    pub async fn new(
+        config: SandboxConfig,
+        name: String,
+        mut mounts: Vec<PathBuf>,
+        owner_dir: &Path,
+    ) -> std::io::Result<Self> {
+        mounts.push(get_vibe_kanban_temp_dir());
+
+        let home_dir = asset_dir().join("sandbox-home");
+        tokio::fs::create_dir_all(&home_dir).await?;
+
+        #[cfg(unix)]
+        let user = {
+            use std::os::unix::fs::MetadataExt;
+            let metadata = tokio::fs::metadata(owner_dir).await?;
+            Some(format!("{}:{}", metadata.uid(), metadata.gid()))
+        };
+        #[cfg(not(unix))]
+        let user = {
+            let _ = owner_dir;
+            None
+        };
+
+        Ok(Self {
+            config,
+            name,
+            mounts,
+            home_dir,
+            user,
+        })
+    }
+
+    /// Arguments for the runtime that run `program` with `args` in `current_dir`, setting the
+    /// variables named in `env_keys` from the runtime's own environment. The program is looked
+    /// up on the container's `PATH` by its file name.
+    pub fn run_args<'a>(
+        &'a self,
+        program: &OsStr,
+        args: impl IntoIterator<Item = &'a OsStr>,
+        current_dir: Option<&Path>,
+        env_keys: impl IntoIterator<Item = &'a OsStr>,
+    ) -> Vec<OsString> {
+        let mut run: Vec<OsString> = ["run", "--rm", "-i", "--init", "--name"]
+            .into_iter()
+            .map(OsString::from)
+            .collect();
+        run.push(self.name.clone().into());
+
+        match (self.config.runtime, &self.user) {
+            (SandboxRuntime::Docker, Some(user)) => {
+                run.push("--user".into());
+                run.push(user.into());
+            }
+            (SandboxRuntime::Podman, _) => run.push("--userns=keep-id".into()),
+            (SandboxRuntime::Docker, None) => {}
+        }
+        if self.config.network == SandboxNetwork::None {
+            run.push("--network=none".into());
+        }
+        if let Some(cpus) = self.config.cpus {
+            run.push(format!("--cpus={cpus}").into());
+        }
+        if let Some(memory_mb) = self.config.memory_mb {
+            run.push(format!("--memory={memory_mb}m").into());
+        }
+
+        for mount in mount_points(&self.mounts) {
+            let mut volume = mount.as_os_str().to_owned();
+            volume.push(":");
+            volume.push(mount);
+            run.push("-v".into());
+            run.push(volume);
+        }
+        let mut home = self.home_dir.as_os_str().to_owned();
+        home.push(format!(":{CONTAINER_HOME}"));
+        run.push("-v".into());
+        run.push(home);
+        run.push("-e".into());
+        run.push(format!("HOME={CONTAINER_HOME}").into());
+
+        if let Some(current_dir) = current_dir {
+            run.push("-w".into());
+            run.push(current_dir.into());
+        }
+        let env_keys: BTreeSet<&OsStr> = env_keys
+            .into_iter()
+            .chain(self.config.env_passthrough.iter().map(OsStr::new))
+            .filter(|key| *key != "HOME")
+            .collect();
+        for key in env_keys {
+            run.push("-e".into());
+            run.push(key.into());
+        }
+
+        run.push(self.config.image.clone().into());
+        run.push(Path::new(program).file_name().unwrap_or(program).to_owned());
+        run.extend(args.into_iter().map(OsStr::to_owned));
+        run
+    }
+
+    /// The command, built to run on the host, run in the container instead. Variables set on
+    /// it are passed in. Standard input is always piped.
+    pub fn wrap(&self, command: &Command) -> Command {
+        let command = command.as_std();
+        let env_keys = command
+            .get_envs()
+            .filter(|(_, value)| value.is_some())
+            .map(|(key, _)| key);
+        let run_args = self.run_args(
+            command.get_program(),
+            command.get_args(),
+            command.get_current_dir(),
+            env_keys,
+        );
+
+        let mut wrapped = Command::new(self.config.runtime.program());
+        wrapped
+            .kill_on_drop(true)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .args(run_args);
+        for (key, value) in command.get_envs() {
+            match value {
+                Some(value) => wrapped.env(key, value),
+                None => wrapped.env_remove(key),
+            };
+        }
+        if let Some(current_dir) = command.get_current_dir() {
+            wrapped.current_dir(current_dir);
+        }
+        wrapped
+    }
+
+    async fn control(&self, args: &[&str]) -> std::io::Result<()> {
+        let output = Command::new(self.config.runtime.program())
+            .args(args)
+            .arg(&self.name)
+            .stdin(Stdio::null())
+            .output()
+            .await?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(std::io::Error::other(format!(
+                "{} {} {}: {}",
+                self.config.runtime.program(),
+                args.join(" "),
+                self.name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )))
+        }
+    }
+
+    /// Signals sent to the runtime's client don't stop the container, so pausing and removing
+    /// go through the runtime
+    pub async fn pause(&self) -> std::io::Result<()> {
+        self.control(&["pause"]).await
+    }
+
+    pub async fn unpause(&self) -> std::io::Result<()> {
+        self.control(&["unpause"]).await
+    }
+
+    /// Needed when the client was killed before it could stop the container
+    pub async fn remove(&self) -> std::io::Result<()> {
+        self.control(&["rm", "-f"]).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sandbox(runtime: SandboxRuntime) -> Sandbox {
+        Sandbox {
+            config: SandboxConfig {
+                enabled: true,
+                runtime,
+                image: "agents:latest".to_string(),
+                env_passthrough: vec!["ANTHROPIC_API_KEY".to_string()],
+                cpus: Some(1.5),
+                memory_mb: Some(2048),
+                network: SandboxNetwork::None,
+            },
+            name: "vk-1".to_string(),
+            mounts: vec![
+                PathBuf::from("/work/ws"),
+                PathBuf::from("/repos/app"),
+                PathBuf::from("/work/ws/app"),
+            ],
+            home_dir: PathBuf::from("/data/sandbox-home"),
+            user: Some("1000:1000".to_string()),
+        }
+    }
+
+    fn strings(args: Vec<OsString>) -> Vec<String> {
+        args.into_iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_run_args() {
+        let args = sandbox(SandboxRuntime::Docker).run_args(
+            OsStr::new("/usr/local/bin/npx"),
+            ["-y", "@anthropic-ai/claude-code"].map(OsStr::new),
+            Some(Path::new("/work/ws/app")),
+            ["VK_TASK_ID", "HOME"].map(OsStr::new),
+        );
+        assert_eq!(
+            strings(args).join(" "),
+            "run --rm -i --init --name vk-1 --user 1000:1000 --network=none --cpus=1.5 \
+             --memory=2048m -v /repos/app:/repos/app -v /work/ws:/work/ws \
+             -v /data/sandbox-home:/home/vibe-kanban -e HOME=/home/vibe-kanban -w /work/ws/app \
+             -e ANTHROPIC_API_KEY -e VK_TASK_ID agents:latest npx -y @anthropic-ai/claude-code"
+        );
+
+        let args =
+            strings(sandbox(SandboxRuntime::Podman).run_args(OsStr::new("claude"), [], None, []));
+        assert!(args.contains(&"--userns=keep-id".to_string()));
+        assert!(!args.contains(&"--user".to_string()));
+    }
+}
@@ -42,3 +42,33 @@ pub async fn kill_process_group(child: &mut AsyncGroupChild) -> Result<(), Conta
     let _ = child.wait().await;
     Ok(())
 }
+
+/// Stop or continue the whole process group; stopped processes keep their state in memory
+pub fn set_process_group_paused(
+    child: &mut AsyncGroupChild,
+    paused: bool,
+) -> Result<(), ContainerError> {
+    #[cfg(unix)]
+    {
+        let pid = child.inner().id().ok_or_else(|| {
+            ContainerError::PauseFailed(std::io::Error::other("process has already exited"))
+        })?;
+        let pgid = getpgid(Some(Pid::from_raw(pid as i32)))
+            .map_err(|e| ContainerError::PauseFailed(std::io::Error::other(e)))?;
+        let signal = if paused {
+            Signal::SIGSTOP
+        } else {
+            Signal::SIGCONT
+        };
+        killpg(pgid, signal).map_err(|e| ContainerError::PauseFailed(std::io::Error::other(e)))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = (child, paused);
+        Err(ContainerError::PauseFailed(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "pausing needs macOS or Linux",
+        )))
+    }
+}
@@ -3,7 +3,7 @@ use std::{
     io,
     path::{Path, PathBuf},
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use anyhow::anyhow;
@@ -12,15 +12,23 @@ use command_group::AsyncGroupChild;
 use db::{
     DBService,
     models::{
+        attempt_patch::{AttemptDryRun, AttemptPatch},
+        attempt_plan::{AttemptPlan, AttemptPlanStatus},
+        attempt_review::{self, AttemptReviewRound, AttemptReviewer, ReviewVerdict, ReviewedRepo},
         coding_agent_turn::CodingAgentTurn,
+        execution_pause::ExecutionPause,
         execution_process::{
             ExecutionContext, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
         },
         execution_process_repo_state::ExecutionProcessRepoState,
+        execution_retry::ExecutionRetry,
+        execution_usage::ExecutionUsage,
         project_repo::ProjectRepo,
         repo::Repo,
         scratch::{DraftFollowUpData, Scratch, ScratchType},
+        session::{CreateSession, Session},
         task::{Task, TaskStatus},
+        task_checklist_item::TaskChecklistItem,
         workspace::Workspace,
         workspace_repo::WorkspaceRepo,
     },
@@ -32,28 +40,38 @@ use executors::{
         coding_agent_follow_up::CodingAgentFollowUpRequest,
         coding_agent_initial::CodingAgentInitialRequest,
     },
-    approvals::{ExecutorApprovalService, NoopExecutorApprovalService},
+    approvals::{CommandGate, ExecutorApprovalService, NoopExecutorApprovalService},
     env::ExecutionEnv,
     executors::{BaseCodingAgent, ExecutorExitResult, ExecutorExitSignal, InterruptSender},
     logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch},
-    profile::ExecutorProfileId,
+    process_limits::ProcessConfinement,
+    profile::{ExecutorConfigs, ExecutorProfileId},
+    sandbox::{Sandbox, SandboxConfig},
 };
 use futures::{FutureExt, TryStreamExt, stream::select};
 use serde_json::json;
 use services::services::{
     analytics::AnalyticsContext,
     approvals::{Approvals, executor_approvals::ExecutorApprovalBridge},
-    config::Config,
+    attachment::AttachmentService,
+    attempt_retry::{self, PlannedRetry},
+    config::{Config, NotificationEvent, StallAction},
     container::{ContainerError, ContainerRef, ContainerService},
     diff_stream::{self, DiffStreamHandle},
+    execution_watchdog::{self, WATCHDOG_LOG_MARKER, Watchdog, WatchdogAction},
     git::{Commit, GitCli, GitService},
     image::ImageService,
-    notification::NotificationService,
+    notification::{NotificationContext, NotificationService, i18n},
+    openrouter::{self, OpenRouterRuns},
     queued_message::QueuedMessageService,
     share::SharePublisher,
+    usage,
     workspace_manager::{RepoWorkspaceInput, WorkspaceManager},
 };
-use tokio::{sync::RwLock, task::JoinHandle};
+use tokio::{
+    sync::{RwLock, broadcast::error::RecvError},
+    task::JoinHandle,
+};
 use tokio_util::io::ReaderStream;
 use utils::{
     log_msg::LogMsg,
@@ -64,20 +82,26 @@ use uuid::Uuid;
 
 use crate::{command, copy};
 
+/// How often running coding agents are checked against their execution limits
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(30);
+
 #[derive(Clone)]
 pub struct LocalContainerService {
     db: DBService,
     child_store: Arc<RwLock<HashMap<Uuid, Arc<RwLock<AsyncGroupChild>>>>>,
     interrupt_senders: Arc<RwLock<HashMap<Uuid, InterruptSender>>>,
+    sandboxes: Arc<RwLock<HashMap<Uuid, Sandbox>>>,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
     config: Arc<RwLock<Config>>,
     git: GitService,
     image_service: ImageService,
+    attachment_service: AttachmentService,
     analytics: Option<AnalyticsContext>,
     approvals: Approvals,
     queued_message_service: QueuedMessageService,
     publisher: Result<SharePublisher, RemoteClientNotConfigured>,
     notification_service: NotificationService,
+    openrouter_runs: Arc<OpenRouterRuns>,
 }
 
 impl LocalContainerService {
@@ -88,6 +112,7 @@ impl LocalContainerService {
         config: Arc<RwLock<Config>>,
         git: GitService,
         image_service: ImageService,
+        attachment_service: AttachmentService,
         analytics: Option<AnalyticsContext>,
         approvals: Approvals,
         queued_message_service: QueuedMessageService,
@@ -95,21 +120,25 @@ impl LocalContainerService {
     ) -> Self {
         let child_store = Arc::new(RwLock::new(HashMap::new()));
         let interrupt_senders = Arc::new(RwLock::new(HashMap::new()));
-        let notification_service = NotificationService::new(config.clone());
+        let sandboxes = Arc::new(RwLock::new(HashMap::new()));
+        let notification_service = NotificationService::new(config.clone(), db.clone());
 
         let container = LocalContainerService {
             db,
             child_store,
             interrupt_senders,
+            sandboxes,
             msg_stores,
             config,
             git,
             image_service,
+            attachment_service,
             analytics,
             approvals,
             queued_message_service,
             publisher,
             notification_service,
+            openrouter_runs: Arc::new(OpenRouterRuns::default()),
         };
 
         container.spawn_workspace_cleanup().await;
@@ -142,6 +171,39 @@ impl LocalContainerService {
         map.remove(id)
     }
 
+    async fn get_sandbox(&self, id: &Uuid) -> Option<Sandbox> {
+        let map = self.sandboxes.read().await;
+        map.get(id).cloned()
+    }
+
+    /// Forget the execution's container, removing it in case its client was killed before it
+    /// could stop it
+    async fn remove_sandbox(&self, id: &Uuid) {
+        let sandbox = self.sandboxes.write().await.remove(id);
+        if let Some(sandbox) = sandbox
+            && let Err(e) = sandbox.remove().await
+        {
+            tracing::debug!("Sandbox container {} already gone: {}", sandbox.name, e);
+        }
+    }
+
+    /// The container a coding agent runs in: the workspace, its repositories (which hold the
+    /// worktrees' git data) and the app's temp directory are mounted, and it runs as the owner
+    /// of the workspace
+    async fn sandbox_for(
+        &self,
+        config: SandboxConfig,
+        workspace: &Workspace,
+        exec_id: Uuid,
+        workspace_dir: &Path,
+    ) -> Result<Sandbox, ContainerError> {
+        let repos = WorkspaceRepo::find_repos_for_workspace(&self.db.pool, workspace.id).await?;
+        let mut mounts = vec![workspace_dir.to_path_buf()];
+        mounts.extend(repos.into_iter().map(|repo| repo.path));
+        let name = format!("vibe-kanban-{exec_id}");
+        Ok(Sandbox::new(config, name, mounts, workspace_dir).await?)
+    }
+
     pub async fn cleanup_workspace(db: &DBService, workspace: &Workspace) {
         let Some(container_ref) = &workspace.container_ref else {
             return;
@@ -232,6 +294,422 @@ impl LocalContainerService {
         }
     }
 
+    /// Start a coding agent run that failed with a transient error again after a backoff, as far
+    /// as the retry policy allows. Returns whether a retry was scheduled, in which case the task
+    /// isn't finalized, or notified about, until the retries run out.
+    async fn schedule_retry(&self, ctx: &ExecutionContext) -> bool {
+        if ctx.execution_process.run_reason != ExecutionProcessRunReason::CodingAgent
+            || ctx.execution_process.status != ExecutionProcessStatus::Failed
+        {
+            return false;
+        }
+        let policy = self.config.read().await.attempt_retry.clone();
+        if policy.max_retries == 0 {
+            return false;
+        }
+
+        let retries_so_far = match ExecutionRetry::find_by_execution_process_id(
+            &self.db.pool,
+            ctx.execution_process.id,
+        )
+        .await
+        {
+            Ok(retry) => retry.map_or(0, |retry| retry.retry_number as u32),
+            Err(e) => {
+                tracing::warn!("Failed to look up earlier retries: {}", e);
+                return false;
+            }
+        };
+        let Some(msg_store) = self.get_msg_store_by_id(&ctx.execution_process.id).await else {
+            return false;
+        };
+        let output = attempt_retry::output_text(&msg_store.get_history());
+        let Some(retry) = attempt_retry::plan_retry(&policy, retries_so_far, &output) else {
+            return false;
+        };
+
+        let log_line = retry.log_line(&policy);
+        tracing::info!("{} for execution {}", log_line, ctx.execution_process.id);
+        msg_store.push_stderr(log_line);
+
+        let container = self.clone();
+        let failed_process_id = ctx.execution_process.id;
+        tokio::spawn(async move {
+            tokio::time::sleep(retry.delay).await;
+            container.start_retry(failed_process_id, retry).await;
+        });
+        true
+    }
+
+    async fn start_retry(&self, failed_process_id: Uuid, retry: PlannedRetry) {
+        let pool = &self.db.pool;
+        let ctx = match ExecutionProcess::load_context(pool, failed_process_id).await {
+            Ok(ctx) => ctx,
+            Err(e) => {
+                tracing::error!("Failed to load context for retry: {}", e);
+                return;
+            }
+        };
+
+        // A follow-up, rollback or status change during the backoff takes over from the retry
+        let latest = ExecutionProcess::find_latest_by_workspace_and_run_reason(
+            pool,
+            ctx.workspace.id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await;
+        let running = ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+            pool,
+            ctx.workspace.id,
+        )
+        .await;
+        let superseded = !matches!(latest, Ok(Some(latest)) if latest.id == failed_process_id)
+            || !matches!(running, Ok(false))
+            || ctx.task.status != TaskStatus::InProgress;
+        if superseded {
+            tracing::info!(
+                "Skipping retry of execution {}; the attempt moved on meanwhile",
+                failed_process_id
+            );
+            return;
+        }
+
+        let started = match ctx.execution_process.executor_action() {
+            Ok(action) => {
+                self.start_execution(
+                    &ctx.workspace,
+                    &ctx.session,
+                    action,
+                    &ExecutionProcessRunReason::CodingAgent,
+                )
+                .await
+            }
+            Err(e) => Err(ContainerError::Other(e)),
+        };
+        match started {
+            Ok(process) => {
+                if let Err(e) = ExecutionRetry::create(
+                    pool,
+                    process.id,
+                    failed_process_id,
+                    retry.retry as i64,
+                    retry.failure.as_str(),
+                )
+                .await
+                {
+                    tracing::warn!(
+                        "Failed to record retry of execution {}: {}",
+                        failed_process_id,
+                        e
+                    );
+                }
+            }
+            Err(e) => {
+                tracing::error!("Failed to retry execution {}: {}", failed_process_id, e);
+                self.finalize_task(self.publisher.as_ref().ok(), &ctx).await;
+            }
+        }
+    }
+
+    /// Have the attempt's reviewer review the agent's work once the agent has finished, as far
+    /// as the review rounds allow. Returns whether a review was started, in which case the task
+    /// is finalized once the reviewer is done.
+    async fn start_review(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError> {
+        if ctx.execution_process.status != ExecutionProcessStatus::Completed
+            || !matches!(
+                ctx.execution_process.run_reason,
+                ExecutionProcessRunReason::CodingAgent | ExecutionProcessRunReason::CleanupScript
+            )
+        {
+            return Ok(false);
+        }
+        let pool = &self.db.pool;
+        let Some(reviewer) = AttemptReviewer::find_by_workspace_id(pool, ctx.workspace.id).await?
+        else {
+            return Ok(false);
+        };
+        if reviewer.reviewer_session_id == Some(ctx.session.id) {
+            return Ok(false);
+        }
+        // A plan-first attempt is reviewed once the plan has been carried out
+        if AttemptPlan::find_by_workspace_id(pool, ctx.workspace.id)
+            .await?
+            .is_some_and(|plan| {
+                matches!(
+                    plan.status,
+                    AttemptPlanStatus::Drafting | AttemptPlanStatus::AwaitingApproval
+                )
+            })
+        {
+            return Ok(false);
+        }
+        let rounds = AttemptReviewRound::find_by_workspace_id(pool, ctx.workspace.id).await?;
+        let max_rounds = self.config.read().await.max_review_rounds;
+        if rounds.len() >= max_rounds as usize {
+            tracing::info!(
+                "Workspace {} used up its {} review rounds",
+                ctx.workspace.id,
+                max_rounds
+            );
+            return Ok(false);
+        }
+        let Some(reviewed) = ExecutionProcess::find_latest_by_session_and_run_reason(
+            pool,
+            ctx.session.id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?
+        else {
+            return Ok(false);
+        };
+
+        let session = match reviewer.reviewer_session_id {
+            Some(session_id) => Session::find_by_id(pool, session_id)
+                .await?
+                .ok_or(sqlx::Error::RowNotFound)?,
+            None => {
+                let session = Session::create(
+                    pool,
+                    &CreateSession {
+                        executor: Some(reviewer.executor_profile_id.executor.to_string()),
+                    },
+                    Uuid::new_v4(),
+                    ctx.workspace.id,
+                )
+                .await?;
+                AttemptReviewer::set_session(pool, ctx.workspace.id, session.id).await?;
+                session
+            }
+        };
+        let latest_agent_session_id =
+            ExecutionProcess::find_latest_coding_agent_turn_session_id(pool, session.id).await?;
+        let working_dir = ctx
+            .workspace
+            .agent_working_dir
+            .as_ref()
+            .filter(|dir| !dir.is_empty())
+            .cloned();
+        let executor_profile_id = reviewer.executor_profile_id.0;
+
+        let action_type = if let Some(agent_session_id) = latest_agent_session_id {
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt: attempt_review::re_review_prompt(),
+                session_id: agent_session_id,
+                executor_profile_id,
+                working_dir,
+            })
+        } else {
+            let repos =
+                WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, ctx.workspace.id)
+                    .await?;
+            let repos = repos
+                .iter()
+                .map(|repo| ReviewedRepo {
+                    name: &repo.repo.name,
+                    target_branch: &repo.target_branch,
+                })
+                .collect::<Vec<_>>();
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt: attempt_review::review_prompt(&ctx.task.to_prompt(), &repos),
+                executor_profile_id,
+                working_dir,
+            })
+        };
+        // The reviewer leaves the files alone, so there is nothing to clean up after it
+        let action = ExecutorAction::new(action_type, None);
+        let process = self
+            .start_execution(
+                &ctx.workspace,
+                &session,
+                &action,
+                &ExecutionProcessRunReason::CodingAgent,
+            )
+            .await?;
+        AttemptReviewRound::create(pool, ctx.workspace.id, reviewed.id, process.id).await?;
+        Ok(true)
+    }
+
+    /// Start the review of an attempt whose agent is done, or finalize the task when there is
+    /// none to start
+    async fn review_or_finalize(&self, ctx: &ExecutionContext) {
+        match self.start_review(ctx).await {
+            Ok(true) => {}
+            Ok(false) => self.finalize_task(self.publisher.as_ref().ok(), ctx).await,
+            Err(e) => {
+                tracing::error!("Failed to start review: {}", e);
+                self.finalize_task(self.publisher.as_ref().ok(), ctx).await;
+            }
+        }
+    }
+
+    /// Record the verdict of a finished reviewer run, and hand requested changes to the agent as
+    /// a follow-up. Returns whether the run was a reviewer's; the task is finalized here unless
+    /// the agent carries on.
+    async fn finish_review(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError> {
+        let pool = &self.db.pool;
+        let Some(round) =
+            AttemptReviewRound::find_by_reviewer_process_id(pool, ctx.execution_process.id).await?
+        else {
+            return Ok(false);
+        };
+
+        let (verdict, comments) =
+            if ctx.execution_process.status == ExecutionProcessStatus::Completed {
+                let message =
+                    CodingAgentTurn::find_by_execution_process_id(pool, ctx.execution_process.id)
+                        .await?
+                        .and_then(|turn| turn.summary)
+                        .unwrap_or_default();
+                attempt_review::parse_verdict(&message)
+            } else {
+                (ReviewVerdict::Failed, String::new())
+            };
+        let comments = (!comments.is_empty()).then_some(comments);
+        AttemptReviewRound::complete(pool, round.id, verdict.clone(), comments.as_deref()).await?;
+
+        if verdict == ReviewVerdict::ChangesRequested {
+            let feedback = attempt_review::feedback_prompt(
+                comments.as_deref().unwrap_or("No details were given."),
+            );
+            match self.start_follow_up(&ctx.workspace, feedback).await {
+                Ok(process) => {
+                    AttemptReviewRound::set_follow_up(pool, round.id, process.id).await?;
+                    return Ok(true);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to hand review comments to the agent: {}", e);
+                }
+            }
+        }
+        self.finalize_task(self.publisher.as_ref().ok(), ctx).await;
+        Ok(true)
+    }
+
+    /// Watch a coding agent run for its profile's maximum duration and for stalls
+    async fn spawn_watchdog(&self, workspace_id: Uuid, exec_id: Uuid, profile: &ExecutorProfileId) {
+        let Some(limits) =
+            execution_watchdog::limits_for(&self.config.read().await.execution_limits, profile)
+                .cloned()
+        else {
+            return;
+        };
+        // Only the receiver is kept, so the store can still be dropped once the run exits
+        let Some(mut log_rx) = self
+            .get_msg_store_by_id(&exec_id)
+            .await
+            .map(|msg_store| msg_store.get_receiver())
+        else {
+            return;
+        };
+
+        let container = self.clone();
+        tokio::spawn(async move {
+            let mut watchdog = Watchdog::new(limits, Instant::now());
+            let mut ticks = tokio::time::interval(WATCHDOG_INTERVAL);
+            loop {
+                tokio::select! {
+                    msg = log_rx.recv() => match msg {
+                        Ok(LogMsg::Finished) | Err(RecvError::Closed) => break,
+                        Ok(LogMsg::Stderr(line)) if line.starts_with(WATCHDOG_LOG_MARKER) => {}
+                        Ok(LogMsg::Stdout(_) | LogMsg::Stderr(_)) | Err(RecvError::Lagged(_)) => {
+                            watchdog.output(Instant::now());
+                        }
+                        Ok(_) => {}
+                    },
+                    _ = ticks.tick() => {
+                        let paused =
+                            ExecutionPause::find_by_workspace_id(&container.db.pool, workspace_id)
+                                .await
+                                .is_ok_and(|pauses| {
+                                    pauses.iter().any(|pause| pause.execution_process_id == exec_id)
+                                });
+                        if let Some(action) = watchdog.check(Instant::now(), paused)
+                            && container.handle_watchdog_action(exec_id, action).await
+                        {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Log, notify and act on a run that timed out or stalled. Returns whether the run was
+    /// stopped. A run stopped to be asked to continue ends as completed, so the queued follow-up
+    /// starts on its agent session.
+    async fn handle_watchdog_action(&self, exec_id: Uuid, action: WatchdogAction) -> bool {
+        let ctx = match ExecutionProcess::load_context(&self.db.pool, exec_id).await {
+            Ok(ctx) if ctx.execution_process.status == ExecutionProcessStatus::Running => ctx,
+            Ok(_) => return true,
+            Err(e) => {
+                tracing::warn!("Failed to load context for watchdog: {}", e);
+                return false;
+            }
+        };
+
+        let log_line = action.log_line();
+        tracing::info!("{} for execution {}", log_line, exec_id);
+        if let Some(msg_store) = self.get_msg_store_by_id(&exec_id).await {
+            msg_store.push_stderr(log_line);
+        }
+
+        let locale = self.notification_service.locale().await;
+        let (title_key, message_key) = action.notification_keys();
+        let minutes = action.minutes().to_string();
+        let args = [
+            ("task", ctx.task.title.as_str()),
+            ("minutes", minutes.as_str()),
+        ];
+        let context = NotificationContext::new(NotificationEvent::AttemptStalled)
+            .with_task(ctx.project.id, ctx.task.id)
+            .with_workspace(ctx.workspace.id)
+            .with_names(&ctx.project.name, &ctx.task.title);
+        self.notification_service
+            .notify(
+                &context,
+                &i18n::t(locale, title_key, &args),
+                &i18n::t(locale, message_key, &args),
+            )
+            .await;
+
+        let status = match action {
+            WatchdogAction::Stalled {
+                action: StallAction::Warn,
+                ..
+            } => return false,
+            WatchdogAction::Stalled {
+                action: StallAction::Continue,
+                ..
+            } => {
+                if !self.queued_message_service.has_queued(ctx.session.id) {
+                    let variant = ctx
+                        .execution_process
+                        .executor_action()
+                        .ok()
+                        .and_then(|action| action.executor_profile_id())
+                        .and_then(|profile| profile.variant.clone());
+                    self.queued_message_service.queue_message(
+                        ctx.session.id,
+                        DraftFollowUpData {
+                            message: execution_watchdog::CONTINUE_PROMPT.to_string(),
+                            variant,
+                        },
+                    );
+                }
+                ExecutionProcessStatus::Completed
+            }
+            WatchdogAction::TimedOut { .. }
+            | WatchdogAction::Stalled {
+                action: StallAction::Kill,
+                ..
+            } => ExecutionProcessStatus::Killed,
+        };
+        if let Err(e) = self.stop_execution(&ctx.execution_process, status).await {
+            tracing::error!("Watchdog failed to stop execution {}: {}", exec_id, e);
+        }
+        true
+    }
+
     /// Get the commit message based on the execution run reason.
     async fn get_commit_message(&self, ctx: &ExecutionContext) -> String {
         match ctx.execution_process.run_reason {
@@ -278,6 +756,35 @@ impl LocalContainerService {
         }
     }
 
+    /// Store each repo's uncommitted changes as the dry-run attempt's patch for it. Returns
+    /// whether there are any.
+    async fn capture_patches(
+        &self,
+        ctx: &ExecutionContext,
+        workspace_root: &Path,
+    ) -> Result<bool, ContainerError> {
+        let mut has_changes = false;
+        for repo in &ctx.repos {
+            let patch = self
+                .git()
+                .worktree_patch(&workspace_root.join(&repo.name))?;
+            if patch.trim().is_empty() {
+                AttemptPatch::delete(&self.db.pool, ctx.workspace.id, repo.id).await?;
+            } else {
+                AttemptPatch::upsert(
+                    &self.db.pool,
+                    ctx.workspace.id,
+                    repo.id,
+                    ctx.execution_process.id,
+                    &patch,
+                )
+                .await?;
+                has_changes = true;
+            }
+        }
+        Ok(has_changes)
+    }
+
     /// Check which repos have uncommitted changes. Fails if any repo is inaccessible.
     fn check_repos_for_changes(
         &self,
@@ -418,6 +925,36 @@ impl LocalContainerService {
                     tracing::warn!("Failed to update executor session summary: {}", e);
                 }
 
+                if let Err(e) = container.capture_plan(&ctx).await {
+                    tracing::warn!("Failed to capture attempt plan: {}", e);
+                }
+
+                // A reviewer run leaves nothing to commit; its verdict decides what comes next
+                let is_review = match container.finish_review(&ctx).await {
+                    Ok(is_review) => is_review,
+                    Err(e) => {
+                        tracing::error!("Failed to record review verdict: {}", e);
+                        false
+                    }
+                };
+                if is_review {
+                    finalized = true;
+                }
+
+                if matches!(
+                    ctx.execution_process.run_reason,
+                    ExecutionProcessRunReason::CodingAgent
+                ) && let Err(e) = container.update_task_checklist(&ctx).await
+                {
+                    tracing::warn!("Failed to update task checklist: {}", e);
+                }
+
+                if let Err(e) = container.record_execution_usage(&exec_id).await {
+                    tracing::warn!("Failed to record execution usage: {}", e);
+                } else if let Err(e) = container.notify_budget_limits(&ctx).await {
+                    tracing::warn!("Failed to check budget limits: {}", e);
+                }
+
                 let success = matches!(
                     ctx.execution_process.status,
                     ExecutionProcessStatus::Completed
@@ -431,7 +968,7 @@ impl LocalContainerService {
                     ExecutionProcessStatus::Running
                 );
 
-                if success || cleanup_done {
+                if !is_review && (success || cleanup_done) {
                     // Commit changes (if any) and get feedback about whether changes were made
                     let changes_committed = match container.try_commit_changes(&ctx).await {
                         Ok(committed) => committed,
@@ -442,6 +979,17 @@ impl LocalContainerService {
                         }
                     };
 
+                    // The agent's work is committed; record it before cleanup runs on top
+                    if matches!(
+                        ctx.execution_process.run_reason,
+                        ExecutionProcessRunReason::CodingAgent
+                    ) && let Err(e) = container
+                        .record_checkpoint(&ctx.workspace, &ctx.execution_process, None)
+                        .await
+                    {
+                        tracing::warn!("Failed to record checkpoint: {}", e);
+                    }
+
                     let should_start_next = if matches!(
                         ctx.execution_process.run_reason,
                         ExecutionProcessRunReason::CodingAgent
@@ -465,11 +1013,16 @@ impl LocalContainerService {
                         // Manually finalize task since we're bypassing normal execution flow
                         if !finalized {
                             finalized = true;
-                            container.finalize_task(publisher.as_ref().ok(), &ctx).await;
+                            container.review_or_finalize(&ctx).await;
                         }
                     }
                 }
 
+                // A retried run is finalized once its retries run out
+                if !finalized && container.schedule_retry(&ctx).await {
+                    finalized = true;
+                }
+
                 if !finalized && container.should_finalize(&ctx) {
                     // Only execute queued messages if the execution succeeded
                     // If it failed or was killed, just clear the queue and finalize
@@ -520,7 +1073,7 @@ impl LocalContainerService {
                             container.finalize_task(publisher.as_ref().ok(), &ctx).await;
                         }
                     } else {
-                        container.finalize_task(publisher.as_ref().ok(), &ctx).await;
+                        container.review_or_finalize(&ctx).await;
                     }
                 }
 
@@ -563,6 +1116,7 @@ impl LocalContainerService {
 
             // Cleanup child handle
             child_store.write().await.remove(&exec_id);
+            container.remove_sandbox(&exec_id).await;
         })
     }
 
@@ -702,7 +1256,92 @@ impl LocalContainerService {
         Ok(())
     }
 
-    /// Copy project files and images to the workspace.
+    /// Tick the task's checklist items that the agent's final message reports as done
+    async fn update_task_checklist(&self, ctx: &ExecutionContext) -> Result<(), anyhow::Error> {
+        let Some(message) = self.extract_last_assistant_message(&ctx.execution_process.id) else {
+            return Ok(());
+        };
+        let ticked =
+            TaskChecklistItem::apply_agent_report(&self.db.pool, ctx.task.id, &message).await?;
+        if !ticked.is_empty() {
+            tracing::info!(
+                "Agent ticked {} checklist items on task {}",
+                ticked.len(),
+                ctx.task.id
+            );
+        }
+        Ok(())
+    }
+
+    /// Store the tokens and spend the executor reported in its output, if any. Runs routed
+    /// through OpenRouter take their spend from the key's usage instead.
+    async fn record_execution_usage(&self, exec_id: &Uuid) -> Result<(), anyhow::Error> {
+        let stdout: String = {
+            let msg_stores = self.msg_stores.read().await;
+            let Some(msg_store) = msg_stores.get(exec_id) else {
+                return Ok(());
+            };
+            msg_store
+                .get_history()
+                .into_iter()
+                .filter_map(|msg| match msg {
+                    LogMsg::Stdout(chunk) => Some(chunk),
+                    _ => None,
+                })
+                .collect()
+        };
+        let mut usage = usage::read_usage(stdout.lines());
+
+        if let Some(api_key) = self.openrouter_runs.api_key(exec_id) {
+            // Only finish the run once its cost has settled, so a run starting on the same key
+            // meanwhile is seen as overlapping rather than adding to this one's spend
+            tokio::time::sleep(openrouter::SETTLE_DELAY).await;
+            let usage_after = openrouter::key_usage(&api_key).await;
+            let run = self.openrouter_runs.finish(exec_id);
+            match (run, usage_after) {
+                (Some(run), Ok(usage_after)) => match run.cost(usage_after) {
+                    Some(cost) => {
+                        let usage = usage.get_or_insert_with(Default::default);
+                        usage.model.get_or_insert_with(|| run.model.clone());
+                        usage.cost_usd = Some(cost);
+                        usage.cost_estimated = false;
+                    }
+                    None => tracing::debug!(
+                        "Another run shared the OpenRouter key of execution {}, so its cost is \
+                         not known",
+                        exec_id
+                    ),
+                },
+                (None, _) => {}
+                (_, Err(e)) => tracing::warn!("Failed to read OpenRouter key usage: {}", e),
+            }
+        }
+
+        if let Some(usage) = usage {
+            ExecutionUsage::record(&self.db.pool, *exec_id, &usage).await?;
+        }
+        Ok(())
+    }
+
+    /// Read the OpenRouter key's usage before an execution routed through it starts, so its
+    /// cost can be taken from the difference once it ends
+    async fn openrouter_usage_before(
+        &self,
+        executor_action: &ExecutorAction,
+    ) -> Option<(String, String, f64)> {
+        let agent = ExecutorConfigs::get_cached()
+            .get_coding_agent(executor_action.executor_profile_id()?)?;
+        let (config, api_key) = agent.openrouter()?;
+        match openrouter::key_usage(&api_key).await {
+            Ok(usage) => Some((api_key, config.model.clone(), usage)),
+            Err(e) => {
+                tracing::warn!("Failed to read OpenRouter key usage: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Copy project files, images and attachments to the workspace.
     /// Skips files/images that already exist (fast no-op if all exist).
     async fn copy_files_and_images(
         &self,
@@ -736,6 +1375,19 @@ impl LocalContainerService {
             tracing::warn!("Failed to copy task images to workspace: {}", e);
         }
 
+        let attachment_config = self.config.read().await.attachments.clone();
+        if let Err(e) = self
+            .attachment_service
+            .copy_attachments_by_task_to_worktree(
+                &attachment_config,
+                workspace_dir,
+                workspace.task_id,
+            )
+            .await
+        {
+            tracing::warn!("Failed to copy task attachments to workspace: {}", e);
+        }
+
         Ok(())
     }
 
@@ -812,6 +1464,7 @@ impl LocalContainerService {
         let executor_profile_id = ExecutorProfileId {
             executor: initial_executor_profile_id.executor,
             variant: queued_data.variant.clone(),
+            model_override: initial_executor_profile_id.model_override,
         };
 
         // Get latest agent session ID for session continuity (from coding agent turns)
@@ -894,8 +1547,13 @@ impl ContainerService for LocalContainerService {
         &self.notification_service
     }
 
-    async fn git_branch_prefix(&self) -> String {
-        self.config.read().await.git_branch_prefix.clone()
+    async fn git_branch_prefix(&self, project_id: Uuid) -> String {
+        let config = self.config.read().await;
+        config
+            .project_overrides
+            .get(&project_id)
+            .and_then(|overrides| overrides.git_branch_prefix.clone())
+            .unwrap_or_else(|| config.git_branch_prefix.clone())
     }
 
     fn workspace_to_current_dir(&self, workspace: &Workspace) -> PathBuf {
@@ -1066,9 +1724,19 @@ impl ContainerService for LocalContainerService {
                     self.db.clone(),
                     self.notification_service.clone(),
                     execution_process.id,
+                    CommandGate::new(&self.config.read().await.approval_gates),
                 ),
                 _ => Arc::new(NoopExecutorApprovalService {}),
             };
+        let gate_warning = match executor_action.base_executor() {
+            Some(agent) => self
+                .config
+                .read()
+                .await
+                .approval_gates
+                .unenforced_warning(agent),
+            None => None,
+        };
 
         // Build ExecutionEnv with VK_* variables
         let mut env = ExecutionEnv::new();
@@ -1091,6 +1759,27 @@ impl ContainerService for LocalContainerService {
         env.insert("VK_WORKSPACE_ID", workspace.id.to_string());
         env.insert("VK_WORKSPACE_BRANCH", &workspace.branch);
 
+        // Only coding agents are sandboxed; scripts and dev servers run on the host
+        let sandbox_config = self.config.read().await.sandbox.clone();
+        if sandbox_config.enabled && executor_action.executor_profile_id().is_some() {
+            let sandbox = self
+                .sandbox_for(
+                    sandbox_config,
+                    workspace,
+                    execution_process.id,
+                    &current_dir,
+                )
+                .await?;
+            env.sandbox = Some(sandbox);
+        }
+
+        let process_limits = self.config.read().await.process_limits.clone();
+        if !process_limits.is_empty() {
+            env.confinement = Some(ProcessConfinement::new(process_limits).await);
+        }
+
+        let openrouter_usage = self.openrouter_usage_before(executor_action).await;
+
         // Create the child and stream, add to execution tracker with timeout
         let mut spawned = tokio::time::timeout(
             Duration::from_secs(30),
@@ -1103,11 +1792,36 @@ impl ContainerService for LocalContainerService {
             ))
         })??;
 
+        if let Err(e) = env.confine(&mut spawned.child) {
+            tracing::warn!(
+                "Failed to apply process limits to execution {}: {}",
+                execution_process.id,
+                e
+            );
+        }
+
+        if let Some((api_key, model, usage_before)) = openrouter_usage {
+            self.openrouter_runs
+                .start(execution_process.id, api_key, model, usage_before);
+        }
+
         self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
             .await;
+        if let Some(warning) = gate_warning {
+            tracing::warn!("{} (execution {})", warning, execution_process.id);
+            if let Some(msg_store) = self.get_msg_store_by_id(&execution_process.id).await {
+                msg_store.push_stderr(warning);
+            }
+        }
 
         self.add_child_to_store(execution_process.id, spawned.child)
             .await;
+        if let Some(sandbox) = env.sandbox {
+            self.sandboxes
+                .write()
+                .await
+                .insert(execution_process.id, sandbox);
+        }
 
         // Store interrupt sender for graceful shutdown
         if let Some(interrupt_sender) = spawned.interrupt_sender {
@@ -1118,6 +1832,11 @@ impl ContainerService for LocalContainerService {
         // Spawn unified exit monitor: watches OS exit and optional executor signal
         let _hn = self.spawn_exit_monitor(&execution_process.id, spawned.exit_signal);
 
+        if let Some(profile) = executor_action.executor_profile_id() {
+            self.spawn_watchdog(workspace.id, execution_process.id, profile)
+                .await;
+        }
+
         Ok(())
     }
 
@@ -1138,6 +1857,22 @@ impl ContainerService for LocalContainerService {
             None
         };
 
+        // A paused process has to run again to handle the interrupt
+        if ExecutionPause::delete(&self.db.pool, execution_process.id).await? {
+            if let Some(sandbox) = self.get_sandbox(&execution_process.id).await
+                && let Err(e) = sandbox.unpause().await
+            {
+                tracing::warn!("Failed to unpause sandbox before stopping it: {}", e);
+            }
+            if let Err(e) = command::set_process_group_paused(&mut *child.write().await, false) {
+                tracing::warn!(
+                    "Failed to resume paused process {} before stopping it: {}",
+                    execution_process.id,
+                    e
+                );
+            }
+        }
+
         ExecutionProcess::update_completion(&self.db.pool, execution_process.id, status, exit_code)
             .await?;
 
@@ -1184,6 +1919,7 @@ impl ContainerService for LocalContainerService {
             }
         }
         self.remove_child_from_store(&execution_process.id).await;
+        self.remove_sandbox(&execution_process.id).await;
 
         // Mark the process finished in the MsgStore
         if let Some(msg) = self.msg_stores.write().await.remove(&execution_process.id) {
@@ -1226,6 +1962,45 @@ impl ContainerService for LocalContainerService {
         Ok(())
     }
 
+    async fn pause_execution(
+        &self,
+        execution_process: &ExecutionProcess,
+    ) -> Result<(), ContainerError> {
+        let child = self
+            .get_child_from_store(&execution_process.id)
+            .await
+            .ok_or_else(|| {
+                ContainerError::Other(anyhow!("Child process not found for execution"))
+            })?;
+        if let Some(sandbox) = self.get_sandbox(&execution_process.id).await {
+            sandbox.pause().await.map_err(ContainerError::PauseFailed)?;
+        }
+        command::set_process_group_paused(&mut *child.write().await, true)?;
+        ExecutionPause::create(&self.db.pool, execution_process.id).await?;
+        Ok(())
+    }
+
+    async fn resume_execution(
+        &self,
+        execution_process: &ExecutionProcess,
+    ) -> Result<(), ContainerError> {
+        let child = self
+            .get_child_from_store(&execution_process.id)
+            .await
+            .ok_or_else(|| {
+                ContainerError::Other(anyhow!("Child process not found for execution"))
+            })?;
+        if let Some(sandbox) = self.get_sandbox(&execution_process.id).await {
+            sandbox
+                .unpause()
+                .await
+                .map_err(ContainerError::PauseFailed)?;
+        }
+        command::set_process_group_paused(&mut *child.write().await, false)?;
+        ExecutionPause::delete(&self.db.pool, execution_process.id).await?;
+        Ok(())
+    }
+
     async fn stream_diff(
         &self,
         workspace: &Workspace,
@@ -1311,6 +2086,11 @@ impl ContainerService for LocalContainerService {
             .ok_or_else(|| ContainerError::Other(anyhow!("Container reference not found")))?;
         let workspace_root = PathBuf::from(container_ref);
 
+        // A dry run keeps its changes out of the branch, as patches to review
+        if AttemptDryRun::is_active(&self.db.pool, ctx.workspace.id).await? {
+            return self.capture_patches(ctx, &workspace_root).await;
+        }
+
         let repos_with_changes = self.check_repos_for_changes(&workspace_root, &ctx.repos)?;
         if repos_with_changes.is_empty() {
             tracing::debug!("No changes to commit in any repository");
@@ -7,6 +7,7 @@ use executors::profile::ExecutorConfigs;
 use services::services::{
     analytics::{AnalyticsConfig, AnalyticsContext, AnalyticsService, generate_user_id},
     approvals::Approvals,
+    attachment::AttachmentService,
     auth::AuthContext,
     config::{Config, load_config_from_file, save_config_to_file},
     container::ContainerService,
@@ -20,6 +21,7 @@ use services::services::{
     queued_message::QueuedMessageService,
     remote_client::{RemoteClient, RemoteClientError},
     repo::RepoService,
+    run_queue::RunQueue,
     share::{ShareConfig, SharePublisher},
 };
 use tokio::sync::RwLock;
@@ -46,11 +48,13 @@ pub struct LocalDeployment {
     project: ProjectService,
     repo: RepoService,
     image: ImageService,
+    attachment: AttachmentService,
     filesystem: FilesystemService,
     events: EventService,
     file_search_cache: Arc<FileSearchCache>,
     approvals: Approvals,
     queued_message_service: QueuedMessageService,
+    run_queue: RunQueue,
     share_publisher: Result<SharePublisher, RemoteClientNotConfigured>,
     share_config: Option<ShareConfig>,
     remote_client: Result<RemoteClient, RemoteClientNotConfigured>,
@@ -88,8 +92,11 @@ impl Deployment for LocalDeployment {
             }
         }
 
-        // Always save config (may have been migrated or version updated)
-        save_config_to_file(&raw_config, &config_path()).await?;
+        // Always save config (may have been migrated or version updated). A read-only config
+        // file is fine when everything is set through environment overrides.
+        if let Err(e) = save_config_to_file(&raw_config, &config_path()).await {
+            tracing::warn!("Failed to save config: {}", e);
+        }
 
         let config = Arc::new(RwLock::new(raw_config));
         let user_id = generate_user_id();
@@ -125,8 +132,11 @@ impl Deployment for LocalDeployment {
             });
         }
 
+        let attachment = AttachmentService::new(db.clone().pool)?;
+
         let approvals = Approvals::new(msg_stores.clone());
         let queued_message_service = QueuedMessageService::new();
+        let run_queue = RunQueue::new();
 
         let share_config = ShareConfig::from_env();
 
@@ -178,6 +188,7 @@ impl Deployment for LocalDeployment {
             config.clone(),
             git.clone(),
             image.clone(),
+            attachment.clone(),
             analytics_ctx,
             approvals.clone(),
             queued_message_service.clone(),
@@ -199,11 +210,13 @@ impl Deployment for LocalDeployment {
             project,
             repo,
             image,
+            attachment,
             filesystem,
             events,
             file_search_cache,
             approvals,
             queued_message_service,
+            run_queue,
             share_publisher,
             share_config: share_config.clone(),
             remote_client,
@@ -250,6 +263,10 @@ impl Deployment for LocalDeployment {
         &self.image
     }
 
+    fn attachment(&self) -> &AttachmentService {
+        &self.attachment
+    }
+
     fn filesystem(&self) -> &FilesystemService {
         &self.filesystem
     }
@@ -270,6 +287,10 @@ impl Deployment for LocalDeployment {
         &self.queued_message_service
     }
 
+    fn run_queue(&self) -> &RunQueue {
+        &self.run_queue
+    }
+
     fn share_publisher(&self) -> Result<SharePublisher, RemoteClientNotConfigured> {
         self.share_publisher.clone()
     }
@@ -18,6 +18,9 @@ fn generate_types_content() -> String {
         db::models::project::Project::decl(),
         db::models::project::CreateProject::decl(),
         db::models::project::UpdateProject::decl(),
+        db::models::project_column::ProjectColumn::decl(),
+        db::models::project_column::CreateProjectColumn::decl(),
+        db::models::project_column::UpdateProjectColumn::decl(),
         db::models::project::SearchResult::decl(),
         db::models::project::SearchMatchType::decl(),
         db::models::repo::Repo::decl(),
@@ -30,12 +33,111 @@ fn generate_types_content() -> String {
         db::models::tag::Tag::decl(),
         db::models::tag::CreateTag::decl(),
         db::models::tag::UpdateTag::decl(),
+        db::models::label::Label::decl(),
+        db::models::label::CreateLabel::decl(),
+        db::models::label::UpdateLabel::decl(),
         db::models::task::TaskStatus::decl(),
+        db::models::task::TaskPriority::decl(),
         db::models::task::Task::decl(),
         db::models::task::TaskWithAttemptStatus::decl(),
+        db::models::task_dependency::TaskDependency::decl(),
+        db::models::task_dependency::TaskDependencies::decl(),
+        db::models::task_relation::TaskRelationKind::decl(),
+        db::models::task_relation::TaskRelation::decl(),
+        db::models::task_relation::TaskRelations::decl(),
+        db::models::task_subtask::TaskSubtasks::decl(),
+        db::models::task_schedule::ScheduleRepo::decl(),
+        db::models::task_schedule::TaskSchedule::decl(),
+        db::models::task_schedule::CreateTaskSchedule::decl(),
+        db::models::task_schedule::UpdateTaskSchedule::decl(),
+        db::models::task_template::TaskTemplate::decl(),
+        db::models::task_template::CreateTaskTemplate::decl(),
+        db::models::task_template::UpdateTaskTemplate::decl(),
+        db::models::assignment_rule::TaskSource::decl(),
+        db::models::assignment_rule::AssignmentRule::decl(),
+        db::models::assignment_rule::CreateAssignmentRule::decl(),
+        db::models::assignment_rule::UpdateAssignmentRule::decl(),
+        db::models::assignment_rule::TaskRunDefaults::decl(),
+        db::models::saved_view::TaskSort::decl(),
+        db::models::saved_view::ViewFilters::decl(),
+        db::models::saved_view::SavedView::decl(),
+        db::models::saved_view::CreateSavedView::decl(),
+        db::models::saved_view::UpdateSavedView::decl(),
+        db::models::swimlane::SwimlaneKey::decl(),
+        db::models::swimlane::Swimlane::decl(),
+        db::models::task_comment::TaskComment::decl(),
+        db::models::task_comment::TaskCommentEdit::decl(),
+        db::models::task_comment::CreateTaskComment::decl(),
+        db::models::task_comment::UpdateTaskComment::decl(),
+        db::models::task_attachment::AttachmentStorage::decl(),
+        db::models::task_attachment::TaskAttachment::decl(),
+        db::models::task_checklist_item::TaskChecklistItem::decl(),
+        db::models::task_checklist_item::CreateTaskChecklistItem::decl(),
+        db::models::task_checklist_item::UpdateTaskChecklistItem::decl(),
+        server::routes::task_checklist::ReorderTaskChecklistRequest::decl(),
+        server::routes::run_queue::ReorderRunQueueRequest::decl(),
+        db::models::task_reference::TaskReferenceKind::decl(),
+        db::models::task_reference::TaskReference::decl(),
+        db::models::task_reference::TaskBacklink::decl(),
+        db::models::task_reference::TaskReferences::decl(),
+        db::models::github_issue::GitHubIssueState::decl(),
+        db::models::github_issue::GitHubIssueSync::decl(),
+        db::models::github_issue::UpsertGitHubIssueSync::decl(),
+        db::models::github_issue::GitHubIssueLink::decl(),
+        db::models::jira::JiraStatusCategory::decl(),
+        db::models::jira::JiraSync::decl(),
+        db::models::jira::UpsertJiraSync::decl(),
+        db::models::jira::JiraIssueLink::decl(),
+        db::models::linear::LinearSync::decl(),
+        db::models::linear::UpsertLinearSync::decl(),
+        db::models::linear::LinearIssueLink::decl(),
+        db::models::calendar_feed::CalendarFeed::decl(),
+        db::models::task_activity::TaskActivityKind::decl(),
+        db::models::task_activity::TaskActivity::decl(),
+        db::models::task_assignee::TaskAssigneeKind::decl(),
+        db::models::task_assignee::TaskAssignee::decl(),
         db::models::task::TaskRelationships::decl(),
         db::models::task::CreateTask::decl(),
         db::models::task::UpdateTask::decl(),
+        db::models::task::DueTasks::decl(),
+        db::models::time_tracking::AttemptTime::decl(),
+        db::models::time_tracking::TimeTotals::decl(),
+        db::models::time_tracking::TaskTime::decl(),
+        db::models::time_tracking::TaskTimeTotals::decl(),
+        db::models::time_tracking::ProjectTime::decl(),
+        db::models::execution_usage::ExecutionUsage::decl(),
+        db::models::execution_usage::UsageTotals::decl(),
+        db::models::execution_usage::TaskUsage::decl(),
+        db::models::execution_usage::TaskUsageTotals::decl(),
+        db::models::execution_usage::ModelUsageTotals::decl(),
+        db::models::execution_usage::ProfileUsageTotals::decl(),
+        db::models::execution_usage::DailyUsageTotals::decl(),
+        db::models::execution_usage::UsageStats::decl(),
+        db::models::queued_attempt::QueuedAttempt::decl(),
+        db::models::attempt_checkpoint::CheckpointCommit::decl(),
+        db::models::attempt_checkpoint::AttemptCheckpoint::decl(),
+        db::models::attempt_plan::AttemptPlanStatus::decl(),
+        db::models::attempt_plan::AttemptPlan::decl(),
+        db::models::attempt_review::AttemptReviewer::decl(),
+        db::models::attempt_review::ReviewVerdict::decl(),
+        db::models::attempt_review::AttemptReviewRound::decl(),
+        db::models::attempt_handoff::AttemptHandoff::decl(),
+        db::models::attempt_patch::AttemptDryRun::decl(),
+        db::models::attempt_patch::AttemptPatch::decl(),
+        db::models::attempt_comparison::AttemptComparison::decl(),
+        db::models::attempt_comparison::ComparisonVariant::decl(),
+        db::models::approval_decision::ApprovalDecisionStatus::decl(),
+        db::models::approval_decision::ApprovalDecision::decl(),
+        db::models::cost_budget::CostBudget::decl(),
+        db::models::cost_budget::SetCostBudget::decl(),
+        db::models::cost_budget::BudgetLevel::decl(),
+        services::services::budget::BudgetStatus::decl(),
+        db::models::search::SearchResultKind::decl(),
+        db::models::search::SearchResult::decl(),
+        db::models::notification::NotificationStatus::decl(),
+        db::models::notification::NotificationRecord::decl(),
+        db::models::config_audit::ConfigAuditEntry::decl(),
+        db::models::notification_escalation::NotificationEscalation::decl(),
         db::models::scratch::DraftFollowUpData::decl(),
         db::models::scratch::ScratchPayload::decl(),
         db::models::scratch::ScratchType::decl(),
@@ -91,9 +193,17 @@ fn generate_types_content() -> String {
         utils::api::projects::RemoteProjectMembersResponse::decl(),
         server::routes::projects::CreateRemoteProjectRequest::decl(),
         server::routes::projects::LinkToExistingRequest::decl(),
+        server::routes::projects::ProjectConfigResponse::decl(),
         server::routes::repo::RegisterRepoRequest::decl(),
         server::routes::repo::InitRepoRequest::decl(),
         server::routes::tags::TagSearchParams::decl(),
+        server::routes::notifications::NotificationHistoryQuery::decl(),
+        server::routes::notifications::NotificationHistoryPage::decl(),
+        server::routes::notifications::NotificationChannelHealth::decl(),
+        services::services::notification::ChannelTestResult::decl(),
+        server::routes::push::PushSubscriptionKeys::decl(),
+        server::routes::push::PushSubscribeRequest::decl(),
+        server::routes::push::PushUnsubscribeRequest::decl(),
         server::routes::oauth::TokenResponse::decl(),
         server::routes::config::UserSystemInfo::decl(),
         server::routes::config::Environment::decl(),
@@ -103,6 +213,12 @@ fn generate_types_content() -> String {
         server::routes::config::CheckEditorAvailabilityQuery::decl(),
         server::routes::config::CheckEditorAvailabilityResponse::decl(),
         server::routes::config::CheckAgentAvailabilityQuery::decl(),
+        server::routes::config::PreviewSoundRequest::decl(),
+        server::routes::config::ExportConfigQuery::decl(),
+        server::routes::config::ImportConfigRequest::decl(),
+        server::routes::config::ImportConfigResponse::decl(),
+        server::routes::config::ConfigAuditQuery::decl(),
+        server::routes::search::SearchQuery::decl(),
         server::routes::oauth::CurrentUserResponse::decl(),
         server::routes::sessions::CreateFollowUpAttempt::decl(),
         server::routes::task_attempts::ChangeTargetBranchRequest::decl(),
@@ -116,7 +232,62 @@ fn generate_types_content() -> String {
         server::routes::shared_tasks::AssignSharedTaskRequest::decl(),
         server::routes::tasks::ShareTaskResponse::decl(),
         server::routes::tasks::CreateAndStartTaskRequest::decl(),
+        server::routes::tasks::AddTaskDependencyRequest::decl(),
+        server::routes::tasks::AddTaskRelationRequest::decl(),
+        server::routes::tasks::CreateSubtaskRequest::decl(),
+        server::routes::tasks::ReorderSubtasksRequest::decl(),
+        server::routes::tasks::MoveTaskToColumnRequest::decl(),
+        server::routes::project_columns::ReorderProjectColumnsRequest::decl(),
+        services::services::wip::WipScope::decl(),
+        services::services::wip::WipViolation::decl(),
+        services::services::run_queue::RunQueueStatus::decl(),
+        db::models::milestone::Milestone::decl(),
+        db::models::milestone::CreateMilestone::decl(),
+        db::models::milestone::UpdateMilestone::decl(),
+        db::models::milestone::BurndownPoint::decl(),
+        db::models::milestone::MilestoneStats::decl(),
+        server::routes::milestones::AddMilestoneTasksRequest::decl(),
+        db::models::epic::Epic::decl(),
+        db::models::epic::CreateEpic::decl(),
+        db::models::epic::UpdateEpic::decl(),
+        db::models::epic::EpicTask::decl(),
+        db::models::epic::EpicSummary::decl(),
+        server::routes::epics::AddEpicTasksRequest::decl(),
+        server::routes::tasks::SetParentTaskRequest::decl(),
+        server::routes::tasks::CreateChecklistSubtasksRequest::decl(),
+        server::routes::tasks::CreateTaskFromTemplateRequest::decl(),
+        server::routes::tasks::CloneTaskRequest::decl(),
+        services::services::task_import::ImportFormat::decl(),
+        services::services::task_import::ImportMapping::decl(),
+        services::services::task_import::ImportedTask::decl(),
+        services::services::task_import::ImportRow::decl(),
+        server::routes::task_import::ImportTasksRequest::decl(),
+        server::routes::task_import::ImportTasksResponse::decl(),
+        server::routes::trello::TrelloImportSource::decl(),
+        server::routes::trello::TrelloImportRequest::decl(),
+        services::services::trello_import::TrelloImportResult::decl(),
+        services::services::project_bundle::ProjectImportResult::decl(),
+        services::services::github_issue_sync::GitHubIssueSyncResult::decl(),
+        services::services::jira_sync::JiraSyncResult::decl(),
+        services::services::linear::LinearTeam::decl(),
+        services::services::linear_sync::LinearSyncResult::decl(),
+        server::routes::calendar::CalendarFeedResponse::decl(),
+        server::routes::tasks::SetTaskLabelsRequest::decl(),
+        server::routes::tasks::SetTaskAssigneesRequest::decl(),
+        server::routes::tasks::TaskActivityQuery::decl(),
         server::routes::task_attempts::pr::CreateGitHubPrRequest::decl(),
+        server::routes::task_attempts::checkpoints::CreateCheckpointRequest::decl(),
+        server::routes::task_attempts::checkpoints::RollbackCheckpointRequest::decl(),
+        server::routes::task_attempts::plan::ApprovePlanRequest::decl(),
+        server::routes::task_attempts::plan::RejectPlanRequest::decl(),
+        server::routes::task_attempts::handoff::HandOffRequest::decl(),
+        server::routes::task_attempts::patches::AttemptPatches::decl(),
+        server::routes::task_attempts::comparison::AttemptComparisonQuery::decl(),
+        server::routes::task_attempts::comparison::CreateAttemptComparisonBody::decl(),
+        server::routes::task_attempts::comparison::ChooseComparisonVariantRequest::decl(),
+        server::routes::task_attempts::comparison::ComparisonRepoDiff::decl(),
+        server::routes::task_attempts::comparison::ComparisonVariantDetails::decl(),
+        server::routes::task_attempts::comparison::AttemptComparisonDetails::decl(),
         server::routes::images::ImageResponse::decl(),
         server::routes::images::ImageMetadata::decl(),
         server::routes::task_attempts::CreateTaskAttemptBody::decl(),
@@ -138,10 +309,31 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::pr::GetPrCommentsQuery::decl(),
         services::services::github::UnifiedPrComment::decl(),
         server::routes::task_attempts::RepoBranchStatus::decl(),
+        server::routes::task_attempts::AttemptReviews::decl(),
         services::services::filesystem::DirectoryEntry::decl(),
         services::services::filesystem::DirectoryListResponse::decl(),
         services::services::config::Config::decl(),
         services::services::config::NotificationConfig::decl(),
+        services::services::config::NotificationChannel::decl(),
+        services::services::config::NotificationEvent::decl(),
+        services::services::config::NotificationOverrides::decl(),
+        services::services::config::NotificationTemplate::decl(),
+        services::services::config::NotificationDigest::decl(),
+        services::services::config::NotificationDedup::decl(),
+        services::services::config::NotificationDedupMode::decl(),
+        services::services::config::NotificationRule::decl(),
+        services::services::config::NotificationOutcome::decl(),
+        services::services::config::NotificationSeverity::decl(),
+        services::services::config::NotificationPriority::decl(),
+        services::services::config::EscalationPolicy::decl(),
+        services::services::config::NotificationSummary::decl(),
+        services::services::config::SummaryFrequency::decl(),
+        services::services::config::SoundPlayback::decl(),
+        services::services::config::SoundTheme::decl(),
+        services::services::config::EventSound::decl(),
+        services::services::config::NotificationRetryPolicy::decl(),
+        services::services::config::QuietHours::decl(),
+        services::services::config::DayOfWeek::decl(),
         services::services::config::ThemeMode::decl(),
         services::services::config::EditorConfig::decl(),
         services::services::config::EditorType::decl(),
@@ -150,6 +342,25 @@ fn generate_types_content() -> String {
         services::services::config::SoundFile::decl(),
         services::services::config::UiLanguage::decl(),
         services::services::config::ShowcaseState::decl(),
+        services::services::config::ServerConfig::decl(),
+        services::services::config::ConfigProfile::decl(),
+        services::services::config::ProjectConfigOverrides::decl(),
+        services::services::config::ProjectSettings::decl(),
+        services::services::config::TaskUnblockedAction::decl(),
+        services::services::config::AttachmentConfig::decl(),
+        services::services::config::S3StorageConfig::decl(),
+        services::services::config::JiraConfig::decl(),
+        services::services::config::LinearConfig::decl(),
+        services::services::config::AttemptRetryPolicy::decl(),
+        services::services::config::StallAction::decl(),
+        services::services::config::ExecutionLimits::decl(),
+        executors::sandbox::SandboxRuntime::decl(),
+        executors::sandbox::SandboxNetwork::decl(),
+        executors::sandbox::SandboxConfig::decl(),
+        executors::process_limits::ProcessLimits::decl(),
+        executors::approvals::ApprovalGates::decl(),
+        services::services::config::ConfigIssue::decl(),
+        services::services::config::ConfigChange::decl(),
         services::services::git::GitBranch::decl(),
         services::services::share::SharedTaskDetails::decl(),
         services::services::queued_message::QueuedMessage::decl(),
@@ -185,6 +396,21 @@ fn generate_types_content() -> String {
         executors::executors::droid::Droid::decl(),
         executors::executors::droid::Autonomy::decl(),
         executors::executors::droid::ReasoningEffortLevel::decl(),
+        executors::executors::aider::Aider::decl(),
+        executors::executors::aider::EditFormat::decl(),
+        executors::executors::openhands::Openhands::decl(),
+        executors::executors::openhands::ConfirmationMode::decl(),
+        executors::executors::goose::Goose::decl(),
+        executors::executors::goose::Mode::decl(),
+        executors::executors::custom_command::CustomCommand::decl(),
+        executors::executors::custom_command::OutputFormat::decl(),
+        executors::executors::custom_command::OutputKind::decl(),
+        executors::executors::custom_command::OutputRule::decl(),
+        executors::local_llm::LocalLlmProvider::decl(),
+        executors::local_llm::LocalLlm::decl(),
+        executors::local_llm::LocalLlmTestResult::decl(),
+        executors::profile_test::ProfileTestResult::decl(),
+        executors::openrouter::OpenRouter::decl(),
         executors::executors::AppendPrompt::decl(),
         executors::actions::coding_agent_initial::CodingAgentInitialRequest::decl(),
         executors::actions::coding_agent_follow_up::CodingAgentFollowUpRequest::decl(),
@@ -286,6 +512,22 @@ fn generate_schemas() -> Result<HashMap<&'static str, String>, serde_json::Error
             "droid",
             generate_json_schema::<executors::executors::droid::Droid>()?,
         ),
+        (
+            "aider",
+            generate_json_schema::<executors::executors::aider::Aider>()?,
+        ),
+        (
+            "openhands",
+            generate_json_schema::<executors::executors::openhands::Openhands>()?,
+        ),
+        (
+            "goose",
+            generate_json_schema::<executors::executors::goose::Goose>()?,
+        ),
+        (
+            "custom_command",
+            generate_json_schema::<executors::executors::custom_command::CustomCommand>()?,
+        ),
     ]);
     println!(
         "✅ JSON schemas generated. {} schemas created.",
@@ -5,23 +5,36 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use db::models::{
-    execution_process::ExecutionProcessError, project::ProjectError,
-    project_repo::ProjectRepoError, repo::RepoError, scratch::ScratchError, session::SessionError,
-    workspace::WorkspaceError,
+    epic::EpicError, execution_process::ExecutionProcessError, milestone::MilestoneError,
+    project::ProjectError, project_column::ProjectColumnError, project_repo::ProjectRepoError,
+    repo::RepoError, scratch::ScratchError, session::SessionError,
+    task_dependency::TaskDependencyError, task_relation::TaskRelationError,
+    task_subtask::TaskSubtaskError, workspace::WorkspaceError,
 };
 use deployment::{DeploymentError, RemoteClientNotConfigured};
 use executors::executors::ExecutorError;
 use git2::Error as Git2Error;
 use services::services::{
+    attachment::AttachmentError,
+    calendar::CalendarError,
     config::{ConfigError, EditorOpenError},
     container::ContainerError,
     git::GitServiceError,
     github::GitHubServiceError,
+    github_issue_sync::GitHubIssueSyncError,
     image::ImageError,
+    jira_sync::JiraSyncError,
+    linear_sync::LinearSyncError,
+    notification::NotificationError,
     project::ProjectServiceError,
+    project_bundle::ProjectBundleError,
+    recurrence::RecurrenceError,
     remote_client::RemoteClientError,
     repo::RepoError as RepoServiceError,
     share::ShareError,
+    sound::SoundError,
+    task_import::TaskImportError,
+    trello_import::TrelloImportError,
     worktree_manager::WorktreeError,
 };
 use thiserror::Error;
@@ -60,6 +73,8 @@ pub enum ApiError {
     Config(#[from] ConfigError),
     #[error(transparent)]
     Image(#[from] ImageError),
+    #[error(transparent)]
+    Attachment(#[from] AttachmentError),
     #[error("Multipart error: {0}")]
     Multipart(#[from] MultipartError),
     #[error("IO error: {0}")]
@@ -68,6 +83,8 @@ pub enum ApiError {
     EditorOpen(#[from] EditorOpenError),
     #[error(transparent)]
     RemoteClient(#[from] RemoteClientError),
+    #[error(transparent)]
+    Notification(#[from] NotificationError),
     #[error("Unauthorized")]
     Unauthorized,
     #[error("Bad request: {0}")]
@@ -122,17 +139,36 @@ impl IntoResponse for ApiError {
             },
             ApiError::GitHubService(_) => (StatusCode::INTERNAL_SERVER_ERROR, "GitHubServiceError"),
             ApiError::Deployment(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DeploymentError"),
+            ApiError::Container(ContainerError::BudgetExceeded(_)) => {
+                (StatusCode::CONFLICT, "BudgetExceeded")
+            }
             ApiError::Container(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ContainerError"),
             ApiError::Executor(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ExecutorError"),
             ApiError::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DatabaseError"),
             ApiError::Worktree(_) => (StatusCode::INTERNAL_SERVER_ERROR, "WorktreeError"),
-            ApiError::Config(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ConfigError"),
+            ApiError::Config(err) => match err {
+                ConfigError::ValidationError(_) | ConfigError::Invalid(_) => {
+                    (StatusCode::BAD_REQUEST, "ConfigError")
+                }
+                ConfigError::ProfileNotFound(_) => (StatusCode::NOT_FOUND, "ConfigError"),
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "ConfigError"),
+            },
             ApiError::Image(img_err) => match img_err {
                 ImageError::InvalidFormat => (StatusCode::BAD_REQUEST, "InvalidImageFormat"),
                 ImageError::TooLarge(_, _) => (StatusCode::PAYLOAD_TOO_LARGE, "ImageTooLarge"),
                 ImageError::NotFound => (StatusCode::NOT_FOUND, "ImageNotFound"),
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "ImageError"),
             },
+            ApiError::Attachment(err) => match err {
+                AttachmentError::TooLarge(_, _) => {
+                    (StatusCode::PAYLOAD_TOO_LARGE, "AttachmentTooLarge")
+                }
+                AttachmentError::Empty => (StatusCode::BAD_REQUEST, "AttachmentError"),
+                AttachmentError::NotFound => (StatusCode::NOT_FOUND, "AttachmentNotFound"),
+                AttachmentError::S3NotConfigured => (StatusCode::CONFLICT, "AttachmentError"),
+                AttachmentError::Storage(_) => (StatusCode::BAD_GATEWAY, "AttachmentError"),
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "AttachmentError"),
+            },
             ApiError::Io(_) => (StatusCode::INTERNAL_SERVER_ERROR, "IoError"),
             ApiError::EditorOpen(err) => match err {
                 EditorOpenError::LaunchFailed { .. } => {
@@ -173,6 +209,10 @@ impl IntoResponse for ApiError {
                     (StatusCode::BAD_REQUEST, "RemoteClientError")
                 }
             },
+            ApiError::Notification(err) => match err {
+                NotificationError::Unknown(_) => (StatusCode::BAD_REQUEST, "NotificationError"),
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "NotificationError"),
+            },
             ApiError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized"),
             ApiError::BadRequest(_) => (StatusCode::BAD_REQUEST, "BadRequest"),
             ApiError::Conflict(_) => (StatusCode::CONFLICT, "ConflictError"),
@@ -192,6 +232,16 @@ impl IntoResponse for ApiError {
                     "Failed to process image. Please try again.".to_string()
                 }
             },
+            ApiError::Attachment(err) => match err {
+                AttachmentError::TooLarge(size, max) => format!(
+                    "This file is too large ({:.1} MB). Maximum attachment size is {:.1} MB.",
+                    *size as f64 / 1_048_576.0,
+                    *max as f64 / 1_048_576.0
+                ),
+                AttachmentError::Empty => "The attached file is empty.".to_string(),
+                AttachmentError::NotFound => "Attachment not found.".to_string(),
+                _ => err.to_string(),
+            },
             ApiError::GitService(git_err) => match git_err {
                 services::services::git::GitServiceError::MergeConflicts(msg) => msg.clone(),
                 services::services::git::GitServiceError::RebaseInProgress => {
@@ -253,6 +303,7 @@ impl IntoResponse for ApiError {
             ApiError::BadRequest(msg) => msg.clone(),
             ApiError::Conflict(msg) => msg.clone(),
             ApiError::Forbidden(msg) => msg.clone(),
+            ApiError::Container(ContainerError::BudgetExceeded(msg)) => msg.clone(),
             _ => format!("{}: {}", error_type, self),
         };
         let response = ApiResponse::<()>::error(&error_message);
@@ -260,6 +311,161 @@ impl IntoResponse for ApiError {
     }
 }
 
+impl From<TaskDependencyError> for ApiError {
+    fn from(err: TaskDependencyError) -> Self {
+        match err {
+            TaskDependencyError::Database(db_err) => ApiError::Database(db_err),
+            TaskDependencyError::Cycle(_) => ApiError::Conflict(err.to_string()),
+            TaskDependencyError::TaskNotFound
+            | TaskDependencyError::SelfDependency
+            | TaskDependencyError::DifferentProject => ApiError::BadRequest(err.to_string()),
+        }
+    }
+}
+
+impl From<TaskRelationError> for ApiError {
+    fn from(err: TaskRelationError) -> Self {
+        match err {
+            TaskRelationError::Database(db_err) => ApiError::Database(db_err),
+            TaskRelationError::AlreadyRelated(_) | TaskRelationError::Cycle(_) => {
+                ApiError::Conflict(err.to_string())
+            }
+            TaskRelationError::TaskNotFound | TaskRelationError::SelfRelation => {
+                ApiError::BadRequest(err.to_string())
+            }
+        }
+    }
+}
+
+impl From<TaskSubtaskError> for ApiError {
+    fn from(err: TaskSubtaskError) -> Self {
+        match err {
+            TaskSubtaskError::Database(db_err) => ApiError::Database(db_err),
+            TaskSubtaskError::Cycle(_) => ApiError::Conflict(err.to_string()),
+            TaskSubtaskError::TaskNotFound
+            | TaskSubtaskError::SelfParent
+            | TaskSubtaskError::DifferentProject => ApiError::BadRequest(err.to_string()),
+        }
+    }
+}
+
+impl From<ProjectColumnError> for ApiError {
+    fn from(err: ProjectColumnError) -> Self {
+        match err {
+            ProjectColumnError::Database(db_err) => ApiError::Database(db_err),
+            ProjectColumnError::WipLimitReached(_, _) | ProjectColumnError::NotEmpty(_) => {
+                ApiError::Conflict(err.to_string())
+            }
+            ProjectColumnError::EmptyName
+            | ProjectColumnError::InvalidWipLimit
+            | ProjectColumnError::NotFinished
+            | ProjectColumnError::DifferentProject => ApiError::BadRequest(err.to_string()),
+        }
+    }
+}
+
+impl From<MilestoneError> for ApiError {
+    fn from(err: MilestoneError) -> Self {
+        match err {
+            MilestoneError::Database(db_err) => ApiError::Database(db_err),
+            MilestoneError::EmptyName
+            | MilestoneError::InvalidDateRange
+            | MilestoneError::TaskNotFound
+            | MilestoneError::DifferentProject => ApiError::BadRequest(err.to_string()),
+        }
+    }
+}
+
+impl From<EpicError> for ApiError {
+    fn from(err: EpicError) -> Self {
+        match err {
+            EpicError::Database(db_err) => ApiError::Database(db_err),
+            EpicError::EmptyName | EpicError::TaskNotFound => ApiError::BadRequest(err.to_string()),
+        }
+    }
+}
+
+impl From<RecurrenceError> for ApiError {
+    fn from(err: RecurrenceError) -> Self {
+        ApiError::BadRequest(err.to_string())
+    }
+}
+
+impl From<TaskImportError> for ApiError {
+    fn from(err: TaskImportError) -> Self {
+        ApiError::BadRequest(err.to_string())
+    }
+}
+
+impl From<TrelloImportError> for ApiError {
+    fn from(err: TrelloImportError) -> Self {
+        match err {
+            TrelloImportError::Database(db_err) => ApiError::Database(db_err),
+            TrelloImportError::Column(err) => err.into(),
+            TrelloImportError::InvalidExport(_)
+            | TrelloImportError::InvalidBoard(_)
+            | TrelloImportError::Transport(_)
+            | TrelloImportError::Http { .. } => ApiError::BadRequest(err.to_string()),
+        }
+    }
+}
+
+impl From<GitHubIssueSyncError> for ApiError {
+    fn from(err: GitHubIssueSyncError) -> Self {
+        match err {
+            GitHubIssueSyncError::Database(db_err) => ApiError::Database(db_err),
+            GitHubIssueSyncError::GitHub(github_err) => ApiError::GitHubService(github_err),
+            GitHubIssueSyncError::Git(git_err) => ApiError::GitService(git_err),
+            GitHubIssueSyncError::RepoNotFound => ApiError::BadRequest(err.to_string()),
+        }
+    }
+}
+
+impl From<JiraSyncError> for ApiError {
+    fn from(err: JiraSyncError) -> Self {
+        match err {
+            JiraSyncError::Database(db_err) => ApiError::Database(db_err),
+            JiraSyncError::Jira(_) | JiraSyncError::NoDoneTransition(_) => {
+                ApiError::BadRequest(err.to_string())
+            }
+        }
+    }
+}
+
+impl From<LinearSyncError> for ApiError {
+    fn from(err: LinearSyncError) -> Self {
+        match err {
+            LinearSyncError::Database(db_err) => ApiError::Database(db_err),
+            LinearSyncError::Linear(_) => ApiError::BadRequest(err.to_string()),
+        }
+    }
+}
+
+impl From<CalendarError> for ApiError {
+    fn from(err: CalendarError) -> Self {
+        match err {
+            CalendarError::Database(db_err) => ApiError::Database(db_err),
+        }
+    }
+}
+
+impl From<ProjectBundleError> for ApiError {
+    fn from(err: ProjectBundleError) -> Self {
+        match err {
+            ProjectBundleError::Database(db_err) => ApiError::Database(db_err),
+            ProjectBundleError::Archive(_) | ProjectBundleError::UnsupportedVersion(_) => {
+                ApiError::BadRequest(err.to_string())
+            }
+            ProjectBundleError::Project(err) => err.into(),
+            ProjectBundleError::ProjectRepo(err) => err.into(),
+            ProjectBundleError::Column(err) => err.into(),
+            ProjectBundleError::Subtask(err) => err.into(),
+            ProjectBundleError::Dependency(err) => err.into(),
+            ProjectBundleError::Workspace(err) => ApiError::Workspace(err),
+        }
+    }
+}
+
 impl From<ShareError> for ApiError {
     fn from(err: ShareError) -> Self {
         match err {
@@ -313,6 +519,29 @@ impl From<ShareError> for ApiError {
     }
 }
 
+impl From<SoundError> for ApiError {
+    fn from(err: SoundError) -> Self {
+        match err {
+            SoundError::Io(io_err) => ApiError::Io(io_err),
+            SoundError::InvalidFormat => ApiError::BadRequest(
+                "This file type is not supported. Please upload a WAV, MP3, OGG or FLAC file."
+                    .to_string(),
+            ),
+            SoundError::TooLarge(size, max) => ApiError::BadRequest(format!(
+                "This sound is too large ({:.1} MB). Maximum file size is {:.1} MB.",
+                size as f64 / 1_048_576.0,
+                max as f64 / 1_048_576.0
+            )),
+            SoundError::InvalidName(name) => {
+                ApiError::BadRequest(format!("Invalid sound name: {}", name))
+            }
+            SoundError::NotFound(name) => {
+                ApiError::BadRequest(format!("Sound not found: {}", name))
+            }
+        }
+    }
+}
+
 impl From<ProjectServiceError> for ApiError {
     fn from(err: ProjectServiceError) -> Self {
         match err {
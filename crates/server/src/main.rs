@@ -63,6 +63,19 @@ async fn main() -> Result<(), VibeKanbanError> {
         .await
         .map_err(DeploymentError::from)?;
     deployment.spawn_pr_monitor_service().await;
+    deployment.spawn_escalation_service().await;
+    deployment.spawn_summary_service().await;
+    deployment.spawn_task_dependency_service().await;
+    deployment.spawn_task_schedule_service().await;
+    deployment.spawn_task_due_service().await;
+    deployment.spawn_run_queue_service().await;
+    deployment.spawn_task_archive_service().await;
+    deployment.spawn_epic_service().await;
+    deployment.spawn_task_reference_service().await;
+    deployment.spawn_github_issue_sync_service().await;
+    deployment.spawn_jira_sync_service().await;
+    deployment.spawn_linear_sync_service().await;
+    deployment.spawn_config_watcher().await;
     deployment
         .track_if_analytics_allowed("session_start", serde_json::json!({}))
         .await;
@@ -99,6 +99,14 @@ pub struct ListTasksRequest {
         description = "Optional status filter: 'todo', 'inprogress', 'inreview', 'done', 'cancelled'"
     )]
     pub status: Option<String>,
+    #[schemars(
+        description = "Optional comma-separated label names; only tasks with all of them are returned"
+    )]
+    pub labels: Option<String>,
+    #[schemars(
+        description = "Optional assignee filter: a person's name or handle, or an executor such as 'CLAUDE_CODE'"
+    )]
+    pub assignee: Option<String>,
     #[schemars(description = "Maximum number of tasks to return (default: 50)")]
     pub limit: Option<i32>,
 }
@@ -225,6 +233,8 @@ pub struct StartWorkspaceSessionRequest {
     pub executor: String,
     #[schemars(description = "Optional executor variant, if needed")]
     pub variant: Option<String>,
+    #[schemars(description = "Optional model to run instead of the variant's model")]
+    pub model: Option<String>,
     #[schemars(description = "Base branch for each repository in the project")]
     pub repos: Vec<McpWorkspaceRepoInput>,
 }
@@ -588,6 +598,8 @@ impl TaskServer {
         Parameters(ListTasksRequest {
             project_id,
             status,
+            labels,
+            assignee,
             limit,
         }): Parameters<ListTasksRequest>,
     ) -> Result<CallToolResult, ErrorData> {
@@ -606,11 +618,17 @@ impl TaskServer {
         };
 
         let url = self.url(&format!("/api/tasks?project_id={}", project_id));
-        let all_tasks: Vec<TaskWithAttemptStatus> =
-            match self.send_json(self.client.get(&url)).await {
-                Ok(t) => t,
-                Err(e) => return Ok(e),
-            };
+        let mut request = self.client.get(&url);
+        if let Some(labels) = &labels {
+            request = request.query(&[("labels", labels)]);
+        }
+        if let Some(assignee) = &assignee {
+            request = request.query(&[("assignee", assignee)]);
+        }
+        let all_tasks: Vec<TaskWithAttemptStatus> = match self.send_json(request).await {
+            Ok(t) => t,
+            Err(e) => return Ok(e),
+        };
 
         let task_limit = limit.unwrap_or(50).max(0) as usize;
         let filtered = all_tasks.into_iter().filter(|t| {
@@ -649,6 +667,7 @@ impl TaskServer {
             task_id,
             executor,
             variant,
+            model,
             repos,
         }): Parameters<StartWorkspaceSessionRequest>,
     ) -> Result<CallToolResult, ErrorData> {
@@ -675,18 +694,21 @@ impl TaskServer {
             }
         };
 
-        let variant = variant.and_then(|v| {
-            let trimmed = v.trim();
-            if trimmed.is_empty() {
-                None
-            } else {
-                Some(trimmed.to_string())
-            }
-        });
+        let non_empty = |value: Option<String>| {
+            value.and_then(|v| {
+                let trimmed = v.trim();
+                if trimmed.is_empty() {
+                    None
+                } else {
+                    Some(trimmed.to_string())
+                }
+            })
+        };
 
         let executor_profile_id = ExecutorProfileId {
             executor: base_executor,
-            variant,
+            variant: non_empty(variant),
+            model_override: non_empty(model),
         };
 
         let workspace_repos: Vec<WorkspaceRepoInput> = repos
@@ -701,6 +723,11 @@ impl TaskServer {
             task_id,
             executor_profile_id,
             repos: workspace_repos,
+            ignore_blockers: None,
+            ignore_wip_limits: None,
+            plan_first: None,
+            reviewer_profile_id: None,
+            dry_run: None,
         };
 
         let url = self.url("/api/task-attempts");
@@ -754,6 +781,9 @@ impl TaskServer {
             title,
             description: expanded_description,
             status,
+            priority: None,
+            due_at: None,
+            clear_due_at: None,
             parent_workspace_id: None,
             image_ids: None,
         };
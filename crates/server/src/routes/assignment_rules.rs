@@ -0,0 +1,109 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::assignment_rule::{AssignmentRule, CreateAssignmentRule, UpdateAssignmentRule};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct AssignmentRuleQuery {
+    /// Include this project's rules alongside the global ones
+    pub project_id: Option<Uuid>,
+}
+
+async fn find_rule(deployment: &DeploymentImpl, rule_id: Uuid) -> Result<AssignmentRule, ApiError> {
+    AssignmentRule::find_by_id(&deployment.db().pool, rule_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn get_assignment_rules(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<AssignmentRuleQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<AssignmentRule>>>, ApiError> {
+    let rules = AssignmentRule::find_available(&deployment.db().pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(rules)))
+}
+
+pub async fn create_assignment_rule(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateAssignmentRule>,
+) -> Result<ResponseJson<ApiResponse<AssignmentRule>>, ApiError> {
+    if payload.name.trim().is_empty() {
+        return Err(ApiError::BadRequest("Rule name is required".to_string()));
+    }
+    let rule = AssignmentRule::create(&deployment.db().pool, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "assignment_rule_created",
+            serde_json::json!({
+                "rule_id": rule.id.to_string(),
+                "global": rule.project_id.is_none(),
+                "has_keywords": !rule.title_keywords.is_empty(),
+                "has_sources": !rule.sources.is_empty(),
+                "has_path_prefixes": !rule.path_prefixes.is_empty(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+pub async fn get_assignment_rule(
+    State(deployment): State<DeploymentImpl>,
+    Path(rule_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<AssignmentRule>>, ApiError> {
+    let rule = find_rule(&deployment, rule_id).await?;
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+pub async fn update_assignment_rule(
+    State(deployment): State<DeploymentImpl>,
+    Path(rule_id): Path<Uuid>,
+    Json(payload): Json<UpdateAssignmentRule>,
+) -> Result<ResponseJson<ApiResponse<AssignmentRule>>, ApiError> {
+    if payload
+        .name
+        .as_deref()
+        .is_some_and(|name| name.trim().is_empty())
+    {
+        return Err(ApiError::BadRequest("Rule name is required".to_string()));
+    }
+    let rule = find_rule(&deployment, rule_id).await?;
+    let updated = AssignmentRule::update(&deployment.db().pool, rule.id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+pub async fn delete_assignment_rule(
+    State(deployment): State<DeploymentImpl>,
+    Path(rule_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = AssignmentRule::delete(&deployment.db().pool, rule_id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let inner = Router::new()
+        .route("/", get(get_assignment_rules).post(create_assignment_rule))
+        .route(
+            "/{rule_id}",
+            get(get_assignment_rule)
+                .put(update_assignment_rule)
+                .delete(delete_assignment_rule),
+        );
+
+    Router::new().nest("/assignment-rules", inner)
+}
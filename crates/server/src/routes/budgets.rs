@@ -0,0 +1,108 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::{delete, get},
+};
+use db::models::{
+    cost_budget::{CostBudget, SetCostBudget},
+    project::Project,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::budget::{self, BudgetStatus};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct BudgetStatusQuery {
+    /// Only budgets that apply to this project: its own and the one across every project
+    pub project_id: Option<Uuid>,
+}
+
+pub async fn get_budgets(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<CostBudget>>>, ApiError> {
+    let budgets = CostBudget::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(budgets)))
+}
+
+/// This month's spend against each budget
+pub async fn get_budget_status(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<BudgetStatusQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<BudgetStatus>>>, ApiError> {
+    let statuses =
+        budget::statuses(&deployment.db().pool, query.project_id, chrono::Utc::now()).await?;
+    Ok(ResponseJson(ApiResponse::success(statuses)))
+}
+
+/// Create or replace the budget of a project, or the one across every project
+pub async fn set_budget(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetCostBudget>,
+) -> Result<ResponseJson<ApiResponse<CostBudget>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if let Some(project_id) = payload.project_id {
+        Project::find_by_id(pool, project_id)
+            .await?
+            .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    }
+    if payload.soft_limit_usd.is_none() && payload.hard_limit_usd.is_none() {
+        return Err(ApiError::BadRequest(
+            "Set a soft limit, a hard limit or both".to_string(),
+        ));
+    }
+    if [payload.soft_limit_usd, payload.hard_limit_usd]
+        .into_iter()
+        .flatten()
+        .any(|limit| !limit.is_finite() || limit <= 0.0)
+    {
+        return Err(ApiError::BadRequest(
+            "Limits must be positive amounts in USD".to_string(),
+        ));
+    }
+    if let (Some(soft), Some(hard)) = (payload.soft_limit_usd, payload.hard_limit_usd)
+        && soft > hard
+    {
+        return Err(ApiError::BadRequest(
+            "The soft limit can't be above the hard limit".to_string(),
+        ));
+    }
+    let budget = CostBudget::set(pool, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "cost_budget_set",
+            serde_json::json!({
+                "project_scoped": budget.project_id.is_some(),
+                "has_soft_limit": budget.soft_limit_usd.is_some(),
+                "has_hard_limit": budget.hard_limit_usd.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(budget)))
+}
+
+pub async fn delete_budget(
+    State(deployment): State<DeploymentImpl>,
+    Path(budget_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let budget = CostBudget::find_by_id(pool, budget_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    CostBudget::delete(pool, budget.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/budgets", get(get_budgets).put(set_budget))
+        .route("/budgets/status", get(get_budget_status))
+        .route("/budgets/{budget_id}", delete(delete_budget))
+}
@@ -0,0 +1,135 @@
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Json as ResponseJson, Response},
+    routing::get,
+};
+use chrono::Utc;
+use db::models::{calendar_feed::CalendarFeed, project::Project};
+use deployment::Deployment;
+use serde::Serialize;
+use services::services::calendar;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Serialize, TS)]
+pub struct CalendarFeedResponse {
+    pub feed: CalendarFeed,
+    /// Path of the `.ics` feed on this server
+    pub path: String,
+    /// Full feed URL, when the server's public base URL is configured
+    pub url: Option<String>,
+}
+
+async fn public_base_url(deployment: &DeploymentImpl) -> Option<String> {
+    let configured = deployment
+        .config()
+        .read()
+        .await
+        .server
+        .public_base_url
+        .clone()?;
+    let url = configured.trim().trim_end_matches('/');
+    (!url.is_empty()).then(|| url.to_string())
+}
+
+async fn feed_response(deployment: &DeploymentImpl, feed: CalendarFeed) -> CalendarFeedResponse {
+    let path = format!("/api/calendar/{}.ics", feed.token);
+    let url = public_base_url(deployment)
+        .await
+        .map(|base| format!("{base}{path}"));
+    CalendarFeedResponse { feed, path, url }
+}
+
+async fn find_project(deployment: &DeploymentImpl, project_id: Uuid) -> Result<Project, ApiError> {
+    Project::find_by_id(&deployment.db().pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn get_calendar_feed(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Option<CalendarFeedResponse>>>, ApiError> {
+    let project = find_project(&deployment, project_id).await?;
+    let feed = CalendarFeed::find_by_project_id(&deployment.db().pool, project.id).await?;
+    let response = match feed {
+        Some(feed) => Some(feed_response(&deployment, feed).await),
+        None => None,
+    };
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+/// Turn the project's feed on, or give it a new URL so the old one stops working
+pub async fn rotate_calendar_feed(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<CalendarFeedResponse>>, ApiError> {
+    let project = find_project(&deployment, project_id).await?;
+    let feed =
+        CalendarFeed::rotate(&deployment.db().pool, project.id, &calendar::new_token()).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "calendar_feed_rotated",
+            serde_json::json!({ "project_id": project.id.to_string() }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        feed_response(&deployment, feed).await,
+    )))
+}
+
+pub async fn delete_calendar_feed(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = CalendarFeed::delete(&deployment.db().pool, project_id).await?;
+    if rows_affected == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// The `.ics` feed itself, read by calendar apps; the token in the file name is the only
+/// credential
+pub async fn serve_calendar_feed(
+    State(deployment): State<DeploymentImpl>,
+    Path(file): Path<String>,
+) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+    let Some(token) = file.strip_suffix(".ics") else {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    };
+    let Some(feed) = CalendarFeed::find_by_token(pool, token).await? else {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    };
+    let project = find_project(&deployment, feed.project_id).await?;
+    let base_url = public_base_url(&deployment).await;
+    let ics = calendar::project_feed(pool, &project, base_url.as_deref(), Utc::now()).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        ics,
+    )
+        .into_response())
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/projects/{project_id}/calendar-feed",
+            get(get_calendar_feed)
+                .post(rotate_calendar_feed)
+                .delete(delete_calendar_feed),
+        )
+        .route("/calendar/{file}", get(serve_calendar_feed))
+}
@@ -3,25 +3,34 @@ use std::collections::HashMap;
 use axum::{
     Json, Router,
     body::Body,
-    extract::{Path, Query, State},
+    extract::{DefaultBodyLimit, Multipart, Path, Query, State},
     http,
     response::{Json as ResponseJson, Response},
-    routing::{get, put},
+    routing::{get, post, put},
 };
+use db::models::config_audit::ConfigAuditEntry;
 use deployment::{Deployment, DeploymentError};
 use executors::{
     executors::{
         AvailabilityInfo, BaseAgentCapability, BaseCodingAgent, StandardCodingAgentExecutor,
     },
+    local_llm::{LocalLlm, LocalLlmTestResult},
     mcp_config::{McpConfig, read_agent_config, write_agent_config},
     profile::{ExecutorConfigs, ExecutorProfileId},
+    profile_test::ProfileTestResult,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use services::services::config::{
-    Config, ConfigError, SoundFile,
-    editor::{EditorConfig, EditorType},
-    save_config_to_file,
+use services::services::{
+    config::{
+        Config, ConfigChange, ConfigError, ConfigIssue, NotificationEvent, SoundFile, SoundTheme,
+        editor::{EditorConfig, EditorType},
+        export_config, parse_config, prepare_import, record_config_changes, save_config_to_file,
+    },
+    container::ContainerService,
+    local_llm::test_local_llm,
+    profile_test::test_executor_profile,
+    sound::{self, SoundError, SoundFormat},
 };
 use tokio::fs;
 use ts_rs::TS;
@@ -33,9 +42,30 @@ pub fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route("/info", get(get_user_system_info))
         .route("/config", put(update_config))
+        .route("/config/audit", get(get_config_audit))
+        .route("/config/export", get(export_config_file))
+        .route("/config/import", post(import_config_file))
+        .route(
+            "/config/profiles/{name}",
+            put(save_config_profile).delete(delete_config_profile),
+        )
+        .route(
+            "/config/profiles/{name}/activate",
+            post(activate_config_profile),
+        )
         .route("/sounds/{sound}", get(get_sound))
+        .route(
+            "/sounds/custom",
+            get(list_custom_sounds)
+                .post(upload_custom_sound)
+                .layer(DefaultBodyLimit::max(5 * 1024 * 1024)),
+        )
+        .route("/sounds/custom/{name}", get(get_custom_sound))
+        .route("/sounds/preview", post(preview_sound))
         .route("/mcp-config", get(get_mcp_servers).post(update_mcp_servers))
         .route("/profiles", get(get_profiles).put(update_profiles))
+        .route("/profiles/test-local-llm", post(test_local_llm_endpoint))
+        .route("/profiles/test", post(test_profile_endpoint))
         .route(
             "/editors/check-availability",
             get(check_editor_availability),
@@ -112,16 +142,19 @@ async fn get_user_system_info(
 
 async fn update_config(
     State(deployment): State<DeploymentImpl>,
-    Json(new_config): Json<Config>,
-) -> ResponseJson<ApiResponse<Config>> {
+    Json(new_config): Json<Value>,
+) -> ResponseJson<ApiResponse<Config, Vec<ConfigIssue>>> {
     let config_path = config_path();
 
-    // Validate git branch prefix
-    if !utils::git::is_valid_branch_prefix(&new_config.git_branch_prefix) {
-        return ResponseJson(ApiResponse::error(
-            "Invalid git branch prefix. Must be a valid git branch name component without slashes.",
-        ));
-    }
+    // Parsed here rather than by the extractor so every problem is reported with its path
+    let new_config = match parse_config(new_config) {
+        Ok(new_config) => new_config,
+        Err(ConfigError::Invalid(issues)) => {
+            let message = format!("Invalid config: {}", issues[0]);
+            return ResponseJson(ApiResponse::error_with_message_and_data(&message, issues));
+        }
+        Err(e) => return ResponseJson(ApiResponse::error(&e.to_string())),
+    };
 
     // Get old config state before updating
     let old_config = deployment.config().read().await.clone();
@@ -132,6 +165,13 @@ async fn update_config(
             *config = new_config.clone();
             drop(config);
 
+            record_config_changes(
+                &deployment.db().pool,
+                "PUT /api/config",
+                &old_config,
+                &new_config,
+            )
+            .await;
             // Track config events when fields transition from false → true and run side effects
             handle_config_events(&deployment, &old_config, &new_config).await;
 
@@ -141,6 +181,153 @@ async fn update_config(
     }
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct ExportConfigQuery {
+    /// Include credentials instead of redacting them
+    #[serde(default)]
+    pub include_secrets: bool,
+}
+
+async fn export_config_file(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ExportConfigQuery>,
+) -> Result<ResponseJson<ApiResponse<Value>>, ApiError> {
+    let config = deployment.config().read().await;
+    let exported = export_config(&config, query.include_secrets)?;
+    Ok(ResponseJson(ApiResponse::success(exported)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ImportConfigRequest {
+    /// A config from `GET /config/export`; redacted credentials keep their current values
+    pub config: Value,
+    /// Report what would change without applying it
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ImportConfigResponse {
+    pub changes: Vec<ConfigChange>,
+    pub applied: bool,
+}
+
+async fn import_config_file(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ImportConfigRequest>,
+) -> Result<ResponseJson<ApiResponse<ImportConfigResponse, Vec<ConfigIssue>>>, ApiError> {
+    let old_config = deployment.config().read().await.clone();
+    let (new_config, changes) = match prepare_import(&old_config, payload.config) {
+        Ok(prepared) => prepared,
+        Err(ConfigError::Invalid(issues)) => {
+            let message = format!("Invalid config: {}", issues[0]);
+            return Ok(ResponseJson(ApiResponse::error_with_message_and_data(
+                &message, issues,
+            )));
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let applied = !payload.dry_run && !changes.is_empty();
+    if applied {
+        let new_config = change_config(&deployment, "POST /api/config/import", |config| {
+            *config = new_config;
+            Ok(())
+        })
+        .await?;
+        handle_config_events(&deployment, &old_config, &new_config).await;
+    }
+
+    Ok(ResponseJson(ApiResponse::success(ImportConfigResponse {
+        changes,
+        applied,
+    })))
+}
+
+/// Apply `change` to a copy of the live config, then persist and swap it in, recording the
+/// changed keys in the audit log against `source`
+pub(crate) async fn change_config(
+    deployment: &DeploymentImpl,
+    source: &str,
+    change: impl FnOnce(&mut Config) -> Result<(), ConfigError>,
+) -> Result<Config, ApiError> {
+    let mut config = deployment.config().write().await;
+    let mut new_config = config.clone();
+    change(&mut new_config)?;
+    save_config_to_file(&new_config, &config_path()).await?;
+    let old_config = std::mem::replace(&mut *config, new_config.clone());
+    drop(config);
+    record_config_changes(&deployment.db().pool, source, &old_config, &new_config).await;
+    Ok(new_config)
+}
+
+const DEFAULT_AUDIT_PAGE_SIZE: i64 = 50;
+const MAX_AUDIT_PAGE_SIZE: i64 = 200;
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ConfigAuditQuery {
+    /// Only changes to this key or keys nested under it, e.g. `notifications`
+    pub path: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Config changes, most recent first
+async fn get_config_audit(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ConfigAuditQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ConfigAuditEntry>>>, ApiError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_AUDIT_PAGE_SIZE)
+        .clamp(1, MAX_AUDIT_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let entries = ConfigAuditEntry::find_page(
+        &deployment.db().pool,
+        query.path.as_deref().filter(|path| !path.is_empty()),
+        limit,
+        offset,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(entries)))
+}
+
+/// Save the current executor, notification and editor settings as a named profile
+async fn save_config_profile(
+    State(deployment): State<DeploymentImpl>,
+    Path(name): Path<String>,
+) -> Result<ResponseJson<ApiResponse<Config>>, ApiError> {
+    let config = change_config(&deployment, "PUT /api/config/profiles", |config| {
+        config.save_profile(&name)
+    })
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(config)))
+}
+
+async fn activate_config_profile(
+    State(deployment): State<DeploymentImpl>,
+    Path(name): Path<String>,
+) -> Result<ResponseJson<ApiResponse<Config>>, ApiError> {
+    let config = change_config(
+        &deployment,
+        "POST /api/config/profiles/activate",
+        |config| config.activate_profile(&name),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(config)))
+}
+
+async fn delete_config_profile(
+    State(deployment): State<DeploymentImpl>,
+    Path(name): Path<String>,
+) -> Result<ResponseJson<ApiResponse<Config>>, ApiError> {
+    let config = change_config(&deployment, "DELETE /api/config/profiles", |config| {
+        config.delete_profile(&name)
+    })
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(config)))
+}
+
 /// Track config events when fields transition from false → true
 async fn track_config_events(deployment: &DeploymentImpl, old: &Config, new: &Config) {
     let events = [
@@ -198,6 +385,72 @@ async fn get_sound(Path(sound): Path<SoundFile>) -> Result<Response, ApiError> {
     Ok(response)
 }
 
+async fn list_custom_sounds() -> Result<ResponseJson<ApiResponse<Vec<String>>>, ApiError> {
+    let sounds = sound::list_sounds().await?;
+    Ok(ResponseJson(ApiResponse::success(sounds)))
+}
+
+/// Store an audio file sent as the `sound` multipart field, returning its name
+async fn upload_custom_sound(
+    mut multipart: Multipart,
+) -> Result<ResponseJson<ApiResponse<String>>, ApiError> {
+    while let Some(field) = multipart.next_field().await? {
+        if field.name() == Some("sound") {
+            let filename = field.file_name().unwrap_or("sound").to_string();
+            let data = field.bytes().await?;
+            let name = sound::store_sound(&data, &filename).await?;
+            return Ok(ResponseJson(ApiResponse::success(name)));
+        }
+    }
+    Err(ApiError::BadRequest("Missing sound file".to_string()))
+}
+
+async fn get_custom_sound(Path(name): Path<String>) -> Result<Response, ApiError> {
+    let path = sound::uploaded_sound(&name)?;
+    let content_type = SoundFormat::from_path(&path)
+        .map(|format| format.mime_type())
+        .unwrap_or("application/octet-stream");
+    let data = fs::read(&path).await?;
+    let response = Response::builder()
+        .status(http::StatusCode::OK)
+        .header(http::header::CONTENT_TYPE, content_type)
+        .body(Body::from(data))
+        .unwrap();
+    Ok(response)
+}
+
+/// Sound to play on the server; omitted fields use the configured sound
+#[derive(Debug, Deserialize, TS)]
+pub struct PreviewSoundRequest {
+    pub sound_file: Option<SoundFile>,
+    pub custom_sound: Option<String>,
+    /// Theme to preview instead of the configured one
+    pub sound_theme: Option<SoundTheme>,
+    /// Preview the sound this event plays, when no sound is given explicitly
+    pub event: Option<NotificationEvent>,
+}
+
+async fn preview_sound(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<PreviewSoundRequest>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    // Surface a bad custom sound instead of silently playing the fallback
+    if let Some(custom_sound) = &payload.custom_sound {
+        sound::resolve_sound(custom_sound)?;
+    }
+    deployment
+        .container()
+        .notification_service()
+        .preview_sound(
+            payload.sound_file,
+            payload.custom_sound,
+            payload.sound_theme,
+            payload.event,
+        )
+        .await;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 #[derive(TS, Debug, Deserialize)]
 pub struct McpServerQuery {
     executor: BaseCodingAgent,
@@ -438,6 +691,27 @@ async fn update_profiles(
     }
 }
 
+/// Tests the local model settings of a profile as currently edited, before they are saved
+async fn test_local_llm_endpoint(
+    State(_deployment): State<DeploymentImpl>,
+    Json(config): Json<LocalLlm>,
+) -> ResponseJson<ApiResponse<LocalLlmTestResult>> {
+    ResponseJson(ApiResponse::success(test_local_llm(&config).await))
+}
+
+async fn test_profile_endpoint(
+    State(deployment): State<DeploymentImpl>,
+    Json(profile_id): Json<ExecutorProfileId>,
+) -> ResponseJson<ApiResponse<ProfileTestResult>> {
+    let (sandbox, process_limits) = {
+        let config = deployment.config().read().await;
+        (config.sandbox.clone(), config.process_limits.clone())
+    };
+    ResponseJson(ApiResponse::success(
+        test_executor_profile(&profile_id, sandbox, process_limits).await,
+    ))
+}
+
 #[derive(Debug, Serialize, Deserialize, TS)]
 pub struct CheckEditorAvailabilityQuery {
     editor_type: EditorType,
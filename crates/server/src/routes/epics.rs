@@ -0,0 +1,158 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::{delete, get},
+};
+use db::models::{
+    epic::{CreateEpic, Epic, EpicSummary, UpdateEpic},
+    task::Task,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+async fn find_epic(deployment: &DeploymentImpl, epic_id: Uuid) -> Result<Epic, ApiError> {
+    Epic::find_by_id(&deployment.db().pool, epic_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EpicQuery {
+    /// List only epics holding at least one of the project's tasks
+    pub project_id: Option<Uuid>,
+}
+
+pub async fn get_epics(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<EpicQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<Epic>>>, ApiError> {
+    let epics = Epic::find_all(&deployment.db().pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(epics)))
+}
+
+pub async fn get_epic(
+    State(deployment): State<DeploymentImpl>,
+    Path(epic_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Epic>>, ApiError> {
+    let epic = find_epic(&deployment, epic_id).await?;
+    Ok(ResponseJson(ApiResponse::success(epic)))
+}
+
+pub async fn create_epic(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateEpic>,
+) -> Result<ResponseJson<ApiResponse<Epic>>, ApiError> {
+    let epic = Epic::create(&deployment.db().pool, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "epic_created",
+            serde_json::json!({
+                "task_count": epic.task_count,
+                "project_count": epic.project_count,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(epic)))
+}
+
+pub async fn update_epic(
+    State(deployment): State<DeploymentImpl>,
+    Path(epic_id): Path<Uuid>,
+    Json(payload): Json<UpdateEpic>,
+) -> Result<ResponseJson<ApiResponse<Epic>>, ApiError> {
+    let epic = find_epic(&deployment, epic_id).await?;
+    let updated = Epic::update(&deployment.db().pool, &epic, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+/// Delete an epic; its tasks are kept
+pub async fn delete_epic(
+    State(deployment): State<DeploymentImpl>,
+    Path(epic_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let epic = find_epic(&deployment, epic_id).await?;
+    Epic::delete(&deployment.db().pool, epic.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn get_epic_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Path(epic_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let epic = find_epic(&deployment, epic_id).await?;
+    let tasks = epic.find_tasks(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(tasks)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct AddEpicTasksRequest {
+    /// Tasks from any project; ones already in the epic are skipped
+    pub task_ids: Vec<Uuid>,
+}
+
+pub async fn add_epic_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Path(epic_id): Path<Uuid>,
+    Json(payload): Json<AddEpicTasksRequest>,
+) -> Result<ResponseJson<ApiResponse<Epic>>, ApiError> {
+    let epic = find_epic(&deployment, epic_id).await?;
+    Epic::add_tasks(&deployment.db().pool, epic.id, &payload.task_ids).await?;
+    let epic = find_epic(&deployment, epic_id).await?;
+    Ok(ResponseJson(ApiResponse::success(epic)))
+}
+
+pub async fn remove_epic_task(
+    State(deployment): State<DeploymentImpl>,
+    Path((epic_id, task_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Epic>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let epic = find_epic(&deployment, epic_id).await?;
+    if epic.remove_task(pool, task_id).await? == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    let epic = find_epic(&deployment, epic_id).await?;
+    Ok(ResponseJson(ApiResponse::success(epic)))
+}
+
+/// Aggregate status, counts and agent time of the epic's tasks
+pub async fn get_epic_summary(
+    State(deployment): State<DeploymentImpl>,
+    Path(epic_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<EpicSummary>>, ApiError> {
+    let epic = find_epic(&deployment, epic_id).await?;
+    let summary = epic.summary(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(summary)))
+}
+
+/// The epic's summary as a Markdown report
+pub async fn get_epic_report(
+    State(deployment): State<DeploymentImpl>,
+    Path(epic_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<String>>, ApiError> {
+    let epic = find_epic(&deployment, epic_id).await?;
+    let summary = epic.summary(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(summary.report())))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let inner = Router::new()
+        .route("/", get(get_epics).post(create_epic))
+        .route(
+            "/{epic_id}",
+            get(get_epic).put(update_epic).delete(delete_epic),
+        )
+        .route("/{epic_id}/tasks", get(get_epic_tasks).post(add_epic_tasks))
+        .route("/{epic_id}/tasks/{task_id}", delete(remove_epic_task))
+        .route("/{epic_id}/summary", get(get_epic_summary))
+        .route("/{epic_id}/report", get(get_epic_report));
+
+    Router::new().nest("/epics", inner)
+}
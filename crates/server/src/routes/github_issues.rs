@@ -0,0 +1,128 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    github_issue::{GitHubIssueLink, GitHubIssueSync, UpsertGitHubIssueSync},
+    project::Project,
+    project_repo::ProjectRepo,
+    repo::Repo,
+};
+use deployment::Deployment;
+use services::services::github_issue_sync::{self, GitHubIssueSyncResult};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+async fn find_project(deployment: &DeploymentImpl, project_id: Uuid) -> Result<Project, ApiError> {
+    Project::find_by_id(&deployment.db().pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn get_github_issue_sync(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Option<GitHubIssueSync>>>, ApiError> {
+    let project = find_project(&deployment, project_id).await?;
+    let sync = GitHubIssueSync::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(sync)))
+}
+
+/// Turn on, change or pause the project's issue sync. The repo must belong to the project and
+/// have a GitHub remote.
+pub async fn put_github_issue_sync(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<UpsertGitHubIssueSync>,
+) -> Result<ResponseJson<ApiResponse<GitHubIssueSync>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = find_project(&deployment, project_id).await?;
+    if ProjectRepo::find_by_project_and_repo(pool, project.id, payload.repo_id)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::BadRequest(
+            "Repository not found in project".to_string(),
+        ));
+    }
+    let repo = Repo::find_by_id(pool, payload.repo_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    deployment.git().get_github_repo_info(&repo.path)?;
+
+    let sync = GitHubIssueSync::upsert(pool, project.id, &payload).await?;
+    deployment
+        .track_if_analytics_allowed(
+            "github_issue_sync_configured",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "enabled": sync.enabled,
+                "has_label": sync.label.is_some(),
+            }),
+        )
+        .await;
+    Ok(ResponseJson(ApiResponse::success(sync)))
+}
+
+/// Stop syncing; imported tasks keep their issue links
+pub async fn delete_github_issue_sync(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let project = find_project(&deployment, project_id).await?;
+    GitHubIssueSync::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Sync now instead of waiting for the next poll
+pub async fn run_github_issue_sync(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<GitHubIssueSyncResult>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = find_project(&deployment, project_id).await?;
+    let sync = GitHubIssueSync::find_by_project_id(pool, project.id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let result = github_issue_sync::sync_project(pool, deployment.git(), &sync).await?;
+    deployment
+        .track_if_analytics_allowed(
+            "github_issues_synced",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "tasks_created": result.tasks_created,
+                "tasks_updated": result.tasks_updated,
+                "issues_updated": result.issues_updated,
+            }),
+        )
+        .await;
+    Ok(ResponseJson(ApiResponse::success(result)))
+}
+
+/// The issue the task mirrors, if any
+pub async fn get_task_github_issue(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Option<GitHubIssueLink>>>, ApiError> {
+    let link = GitHubIssueLink::find_by_task_id(&deployment.db().pool, task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(link)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/projects/{project_id}/github-issues",
+            get(get_github_issue_sync)
+                .put(put_github_issue_sync)
+                .delete(delete_github_issue_sync),
+        )
+        .route(
+            "/projects/{project_id}/github-issues/sync",
+            post(run_github_issue_sync),
+        )
+        .route("/tasks/{task_id}/github-issue", get(get_task_github_issue))
+}
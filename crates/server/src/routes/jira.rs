@@ -0,0 +1,118 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::{
+    jira::{JiraIssueLink, JiraSync, UpsertJiraSync},
+    project::Project,
+};
+use deployment::Deployment;
+use services::services::{
+    jira::JiraClient,
+    jira_sync::{self, JiraSyncResult},
+};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+async fn find_project(deployment: &DeploymentImpl, project_id: Uuid) -> Result<Project, ApiError> {
+    Project::find_by_id(&deployment.db().pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn get_jira_sync(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Option<JiraSync>>>, ApiError> {
+    let project = find_project(&deployment, project_id).await?;
+    let sync = JiraSync::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(sync)))
+}
+
+/// Turn on, change or pause the project's Jira import. Jira credentials must be set in the config.
+pub async fn put_jira_sync(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<UpsertJiraSync>,
+) -> Result<ResponseJson<ApiResponse<JiraSync>>, ApiError> {
+    let project = find_project(&deployment, project_id).await?;
+    if payload.jql.trim().is_empty() {
+        return Err(ApiError::BadRequest("JQL filter is required".to_string()));
+    }
+    let jira = deployment.config().read().await.jira.clone();
+    JiraClient::new(jira.as_ref()).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    let sync = JiraSync::upsert(&deployment.db().pool, project.id, &payload).await?;
+    deployment
+        .track_if_analytics_allowed(
+            "jira_sync_configured",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "enabled": sync.enabled,
+                "has_done_transition": sync.done_transition.is_some(),
+            }),
+        )
+        .await;
+    Ok(ResponseJson(ApiResponse::success(sync)))
+}
+
+/// Stop syncing; imported tasks keep their issue links
+pub async fn delete_jira_sync(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let project = find_project(&deployment, project_id).await?;
+    JiraSync::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Sync now instead of waiting for the next poll
+pub async fn run_jira_sync(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<JiraSyncResult>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = find_project(&deployment, project_id).await?;
+    let sync = JiraSync::find_by_project_id(pool, project.id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let jira = deployment.config().read().await.jira.clone();
+    let result = jira_sync::sync_project(pool, jira.as_ref(), &sync).await?;
+    deployment
+        .track_if_analytics_allowed(
+            "jira_issues_synced",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "tasks_created": result.tasks_created,
+                "tasks_updated": result.tasks_updated,
+                "issues_transitioned": result.issues_transitioned,
+            }),
+        )
+        .await;
+    Ok(ResponseJson(ApiResponse::success(result)))
+}
+
+/// The Jira issue the task mirrors, if any
+pub async fn get_task_jira_issue(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Option<JiraIssueLink>>>, ApiError> {
+    let link = JiraIssueLink::find_by_task_id(&deployment.db().pool, task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(link)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/projects/{project_id}/jira",
+            get(get_jira_sync)
+                .put(put_jira_sync)
+                .delete(delete_jira_sync),
+        )
+        .route("/projects/{project_id}/jira/sync", post(run_jira_sync))
+        .route("/tasks/{task_id}/jira-issue", get(get_task_jira_issue))
+}
@@ -0,0 +1,103 @@
+use axum::{
+    Extension, Json, Router, extract::State, middleware::from_fn_with_state,
+    response::Json as ResponseJson, routing::get,
+};
+use db::models::label::{CreateLabel, Label, UpdateLabel};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_label_middleware};
+
+/// Reject an empty name, or one another label already uses
+async fn validate_name(
+    deployment: &DeploymentImpl,
+    name: &str,
+    current: Option<&Label>,
+) -> Result<(), ApiError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Label name cannot be empty".to_string(),
+        ));
+    }
+    if let Some(existing) = Label::find_by_name(&deployment.db().pool, name).await?
+        && current.is_none_or(|current| current.id != existing.id)
+    {
+        return Err(ApiError::Conflict(format!(
+            "A label named '{}' already exists",
+            existing.name
+        )));
+    }
+    Ok(())
+}
+
+pub async fn get_labels(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Label>>>, ApiError> {
+    let labels = Label::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+pub async fn create_label(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateLabel>,
+) -> Result<ResponseJson<ApiResponse<Label>>, ApiError> {
+    validate_name(&deployment, &payload.name, None).await?;
+    let label = Label::create(&deployment.db().pool, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "label_created",
+            serde_json::json!({
+                "label_id": label.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(label)))
+}
+
+pub async fn get_label(
+    Extension(label): Extension<Label>,
+) -> Result<ResponseJson<ApiResponse<Label>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(label)))
+}
+
+pub async fn update_label(
+    Extension(label): Extension<Label>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateLabel>,
+) -> Result<ResponseJson<ApiResponse<Label>>, ApiError> {
+    if let Some(name) = &payload.name {
+        validate_name(&deployment, name, Some(&label)).await?;
+    }
+    let updated = Label::update(&deployment.db().pool, &label, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+pub async fn delete_label(
+    Extension(label): Extension<Label>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = Label::delete(&deployment.db().pool, label.id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let label_router = Router::new()
+        .route("/", get(get_label).put(update_label).delete(delete_label))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_label_middleware,
+        ));
+
+    let inner = Router::new()
+        .route("/", get(get_labels).post(create_label))
+        .nest("/{label_id}", label_router);
+
+    Router::new().nest("/labels", inner)
+}
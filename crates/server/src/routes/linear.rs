@@ -0,0 +1,191 @@
+use axum::{
+    Json, Router,
+    body::Bytes,
+    extract::{Path, State},
+    http::HeaderMap,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use chrono::Utc;
+use db::models::{
+    linear::{LinearIssueLink, LinearSync, UpsertLinearSync},
+    project::Project,
+};
+use deployment::Deployment;
+use services::services::{
+    linear::{self, LinearClient, LinearTeam, LinearWebhook},
+    linear_sync::{self, LinearSyncResult},
+};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Webhooks sent longer ago than this are treated as replays
+const MAX_WEBHOOK_AGE_MS: i64 = 60_000;
+
+async fn find_project(deployment: &DeploymentImpl, project_id: Uuid) -> Result<Project, ApiError> {
+    Project::find_by_id(&deployment.db().pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+async fn client(deployment: &DeploymentImpl) -> Result<LinearClient, ApiError> {
+    let config = deployment.config().read().await.linear.clone();
+    LinearClient::new(config.as_ref()).map_err(|e| ApiError::BadRequest(e.to_string()))
+}
+
+/// Teams the API key can see, for picking the one a project syncs with
+pub async fn list_linear_teams(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<LinearTeam>>>, ApiError> {
+    let teams = client(&deployment)
+        .await?
+        .teams()
+        .await
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    Ok(ResponseJson(ApiResponse::success(teams)))
+}
+
+pub async fn get_linear_sync(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Option<LinearSync>>>, ApiError> {
+    let project = find_project(&deployment, project_id).await?;
+    let sync = LinearSync::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(sync)))
+}
+
+/// Turn on, change or pause the project's Linear sync. The team is looked up by key, so the API
+/// key must be set in the config.
+pub async fn put_linear_sync(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<UpsertLinearSync>,
+) -> Result<ResponseJson<ApiResponse<LinearSync>>, ApiError> {
+    let project = find_project(&deployment, project_id).await?;
+    let teams = client(&deployment)
+        .await?
+        .teams()
+        .await
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let team = teams
+        .iter()
+        .find(|team| team.key.eq_ignore_ascii_case(payload.team_key.trim()))
+        .ok_or_else(|| {
+            ApiError::BadRequest(format!("No Linear team with key {}", payload.team_key))
+        })?;
+
+    let sync = LinearSync::upsert(
+        &deployment.db().pool,
+        project.id,
+        &team.id,
+        &team.key,
+        payload.enabled,
+    )
+    .await?;
+    deployment
+        .track_if_analytics_allowed(
+            "linear_sync_configured",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "enabled": sync.enabled,
+            }),
+        )
+        .await;
+    Ok(ResponseJson(ApiResponse::success(sync)))
+}
+
+/// Stop syncing; imported tasks keep their issue links
+pub async fn delete_linear_sync(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let project = find_project(&deployment, project_id).await?;
+    LinearSync::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Fetch issues updated since the last sync and post pending updates, e.g. to catch up on
+/// webhooks missed while the app was offline
+pub async fn run_linear_sync(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<LinearSyncResult>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = find_project(&deployment, project_id).await?;
+    let sync = LinearSync::find_by_project_id(pool, project.id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let config = deployment.config().read().await.linear.clone();
+    let result = linear_sync::sync_project(pool, config.as_ref(), &sync).await?;
+    deployment
+        .track_if_analytics_allowed(
+            "linear_issues_synced",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "tasks_created": result.tasks_created,
+                "tasks_updated": result.tasks_updated,
+                "comments_posted": result.comments_posted,
+            }),
+        )
+        .await;
+    Ok(ResponseJson(ApiResponse::success(result)))
+}
+
+/// Receives Linear's webhooks. Deliveries must be signed with the configured webhook secret.
+pub async fn linear_webhook(
+    State(deployment): State<DeploymentImpl>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let secret = deployment
+        .config()
+        .read()
+        .await
+        .linear
+        .as_ref()
+        .and_then(|linear| linear.webhook_secret.clone())
+        .filter(|secret| !secret.is_empty())
+        .ok_or(ApiError::Unauthorized)?;
+    let signature = headers
+        .get(linear::SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .ok_or(ApiError::Unauthorized)?;
+    if !linear::verify_signature(&secret, &body, signature) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let webhook: LinearWebhook = serde_json::from_slice(&body)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid webhook payload: {e}")))?;
+    if let Some(sent_at) = webhook.webhook_timestamp
+        && (Utc::now().timestamp_millis() - sent_at).abs() > MAX_WEBHOOK_AGE_MS
+    {
+        return Err(ApiError::Unauthorized);
+    }
+    linear_sync::handle_webhook(&deployment.db().pool, webhook).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// The Linear issue the task mirrors, if any
+pub async fn get_task_linear_issue(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Option<LinearIssueLink>>>, ApiError> {
+    let link = LinearIssueLink::find_by_task_id(&deployment.db().pool, task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(link)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/linear/teams", get(list_linear_teams))
+        .route("/linear/webhook", post(linear_webhook))
+        .route(
+            "/projects/{project_id}/linear",
+            get(get_linear_sync)
+                .put(put_linear_sync)
+                .delete(delete_linear_sync),
+        )
+        .route("/projects/{project_id}/linear/sync", post(run_linear_sync))
+        .route("/tasks/{task_id}/linear-issue", get(get_task_linear_issue))
+}
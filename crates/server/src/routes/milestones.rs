@@ -0,0 +1,164 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{delete, get},
+};
+use db::models::{
+    milestone::{CreateMilestone, Milestone, MilestoneStats, UpdateMilestone},
+    project::Project,
+    task::Task,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// The milestone, provided it belongs to the project
+async fn find_milestone(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    milestone_id: Uuid,
+) -> Result<Milestone, ApiError> {
+    Milestone::find_by_id(&deployment.db().pool, milestone_id)
+        .await?
+        .filter(|milestone| milestone.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn get_milestones(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<Milestone>>>, ApiError> {
+    let milestones = Milestone::find_by_project_id(&deployment.db().pool, project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(milestones)))
+}
+
+pub async fn get_milestone(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, milestone_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Milestone>>, ApiError> {
+    let milestone = find_milestone(&deployment, project_id, milestone_id).await?;
+    Ok(ResponseJson(ApiResponse::success(milestone)))
+}
+
+pub async fn create_milestone(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<CreateMilestone>,
+) -> Result<ResponseJson<ApiResponse<Milestone>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let milestone = Milestone::create(pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "milestone_created",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "days": (milestone.end_date - milestone.start_date).num_days() + 1,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(milestone)))
+}
+
+pub async fn update_milestone(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, milestone_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateMilestone>,
+) -> Result<ResponseJson<ApiResponse<Milestone>>, ApiError> {
+    let milestone = find_milestone(&deployment, project_id, milestone_id).await?;
+    let updated = Milestone::update(&deployment.db().pool, &milestone, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+/// Delete a milestone; its tasks are kept
+pub async fn delete_milestone(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, milestone_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let milestone = find_milestone(&deployment, project_id, milestone_id).await?;
+    Milestone::delete(&deployment.db().pool, milestone.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn get_milestone_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, milestone_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let milestone = find_milestone(&deployment, project_id, milestone_id).await?;
+    let tasks = milestone.find_tasks(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(tasks)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct AddMilestoneTasksRequest {
+    /// Tasks in other milestones are moved to this one
+    pub task_ids: Vec<Uuid>,
+}
+
+pub async fn add_milestone_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, milestone_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<AddMilestoneTasksRequest>,
+) -> Result<ResponseJson<ApiResponse<Milestone>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let milestone = find_milestone(&deployment, project_id, milestone_id).await?;
+    milestone.add_tasks(pool, &payload.task_ids).await?;
+    let milestone = find_milestone(&deployment, project_id, milestone_id).await?;
+    Ok(ResponseJson(ApiResponse::success(milestone)))
+}
+
+pub async fn remove_milestone_task(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, milestone_id, task_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Milestone>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let milestone = find_milestone(&deployment, project_id, milestone_id).await?;
+    if milestone.remove_task(pool, task_id).await? == 0 {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    let milestone = find_milestone(&deployment, project_id, milestone_id).await?;
+    Ok(ResponseJson(ApiResponse::success(milestone)))
+}
+
+/// Progress by status and a daily burndown for the milestone
+pub async fn get_milestone_stats(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, milestone_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<MilestoneStats>>, ApiError> {
+    let milestone = find_milestone(&deployment, project_id, milestone_id).await?;
+    let stats = milestone
+        .stats(&deployment.db().pool, chrono::Utc::now().date_naive())
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(stats)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let inner = Router::new()
+        .route("/", get(get_milestones).post(create_milestone))
+        .route(
+            "/{milestone_id}",
+            get(get_milestone)
+                .put(update_milestone)
+                .delete(delete_milestone),
+        )
+        .route(
+            "/{milestone_id}/tasks",
+            get(get_milestone_tasks).post(add_milestone_tasks),
+        )
+        .route(
+            "/{milestone_id}/tasks/{task_id}",
+            delete(remove_milestone_task),
+        )
+        .route("/{milestone_id}/stats", get(get_milestone_stats));
+
+    Router::new().nest("/projects/{project_id}/milestones", inner)
+}
@@ -6,25 +6,50 @@ use axum::{
 use crate::DeploymentImpl;
 
 pub mod approvals;
+pub mod assignment_rules;
+pub mod budgets;
+pub mod calendar;
 pub mod config;
 pub mod containers;
 pub mod filesystem;
 // pub mod github;
+pub mod epics;
 pub mod events;
 pub mod execution_processes;
 pub mod frontend;
+pub mod github_issues;
 pub mod health;
 pub mod images;
+pub mod jira;
+pub mod labels;
+pub mod linear;
+pub mod milestones;
+pub mod notifications;
 pub mod oauth;
 pub mod organizations;
+pub mod project_bundle;
+pub mod project_columns;
 pub mod projects;
+pub mod push;
 pub mod repo;
+pub mod run_queue;
+pub mod saved_views;
 pub mod scratch;
+pub mod search;
 pub mod sessions;
 pub mod shared_tasks;
 pub mod tags;
+pub mod task_attachments;
 pub mod task_attempts;
+pub mod task_checklist;
+pub mod task_comments;
+pub mod task_import;
+pub mod task_references;
+pub mod task_schedules;
+pub mod task_templates;
 pub mod tasks;
+pub mod trello;
+pub mod usage;
 
 pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
     // Create routers with different middleware layers
@@ -33,18 +58,43 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(config::router())
         .merge(containers::router(&deployment))
         .merge(projects::router(&deployment))
+        .merge(project_bundle::router())
+        .merge(project_columns::router())
+        .merge(milestones::router())
+        .merge(epics::router())
+        .merge(saved_views::router())
         .merge(tasks::router(&deployment))
+        .merge(task_comments::router())
+        .merge(task_attachments::router())
+        .merge(task_checklist::router())
+        .merge(task_references::router())
+        .merge(task_import::router())
+        .merge(trello::router())
+        .merge(github_issues::router())
+        .merge(jira::router())
+        .merge(linear::router())
+        .merge(calendar::router())
         .merge(shared_tasks::router())
         .merge(task_attempts::router(&deployment))
         .merge(execution_processes::router(&deployment))
         .merge(tags::router(&deployment))
+        .merge(labels::router(&deployment))
+        .merge(task_schedules::router(&deployment))
+        .merge(task_templates::router(&deployment))
+        .merge(assignment_rules::router())
+        .merge(usage::router())
+        .merge(budgets::router())
+        .merge(run_queue::router())
         .merge(oauth::router())
         .merge(organizations::router())
         .merge(filesystem::router())
         .merge(repo::router())
         .merge(events::router(&deployment))
         .merge(approvals::router())
+        .merge(notifications::router())
+        .merge(push::router())
         .merge(scratch::router(&deployment))
+        .merge(search::router())
         .merge(sessions::router(&deployment))
         .nest("/images", images::routes())
         .with_state(deployment);
@@ -0,0 +1,212 @@
+use std::{collections::HashMap, str::FromStr};
+
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    response::{Json as ResponseJson, Redirect},
+    routing::{get, post},
+};
+use chrono::{DateTime, Utc};
+use db::models::{
+    notification::{NotificationRecord, NotificationStatus},
+    notification_escalation::NotificationEscalation,
+    task::Task,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    config::NotificationChannel, container::ContainerService, notification::ChannelTestResult,
+};
+use strum::IntoEnumIterator;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+#[derive(Debug, Deserialize, TS)]
+pub struct NotificationHistoryQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct NotificationHistoryPage {
+    pub notifications: Vec<NotificationRecord>,
+    pub total: i64,
+}
+
+pub async fn get_notifications(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<NotificationHistoryQuery>,
+) -> Result<ResponseJson<ApiResponse<NotificationHistoryPage>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let notifications = NotificationRecord::find_page(pool, limit, offset).await?;
+    let total = NotificationRecord::count(pool).await?;
+
+    Ok(ResponseJson(ApiResponse::success(
+        NotificationHistoryPage {
+            notifications,
+            total,
+        },
+    )))
+}
+
+/// Delivery health of one channel, from its notification history
+#[derive(Debug, Serialize, TS)]
+pub struct NotificationChannelHealth {
+    pub channel: NotificationChannel,
+    pub enabled: bool,
+    /// False when the channel's most recent delivery failed
+    pub healthy: bool,
+    /// Status of the most recent delivery that wasn't suppressed
+    pub last_status: Option<NotificationStatus>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<DateTime<Utc>>,
+}
+
+/// Enabled channels, plus disabled ones that have delivered before
+pub async fn get_notification_health(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<NotificationChannelHealth>>>, ApiError> {
+    let mut stats: HashMap<NotificationChannel, _> =
+        NotificationRecord::channel_stats(&deployment.db().pool)
+            .await?
+            .into_iter()
+            .filter_map(|stats| {
+                NotificationChannel::from_str(&stats.channel)
+                    .ok()
+                    .map(|channel| (channel, stats))
+            })
+            .collect();
+    let config = deployment.config().read().await.notifications.clone();
+
+    let health = NotificationChannel::iter()
+        .filter_map(|channel| {
+            let enabled = config.is_channel_enabled(channel);
+            let stats = stats.remove(&channel);
+            if !enabled && stats.is_none() {
+                return None;
+            }
+            Some(match stats {
+                Some(stats) => NotificationChannelHealth {
+                    channel,
+                    enabled,
+                    healthy: stats.last_status == NotificationStatus::Sent,
+                    last_status: Some(stats.last_status),
+                    last_attempt_at: Some(stats.last_attempt_at),
+                    last_success_at: stats.last_success_at,
+                    last_error: stats.last_error,
+                    last_error_at: stats.last_error_at,
+                },
+                // Nothing has gone wrong on a channel that hasn't been used yet
+                None => NotificationChannelHealth {
+                    channel,
+                    enabled,
+                    healthy: true,
+                    last_status: None,
+                    last_attempt_at: None,
+                    last_success_at: None,
+                    last_error: None,
+                    last_error_at: None,
+                },
+            })
+        })
+        .collect();
+
+    Ok(ResponseJson(ApiResponse::success(health)))
+}
+
+pub async fn resend_notification(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<NotificationRecord>>, ApiError> {
+    let record = NotificationRecord::find_by_id(&deployment.db().pool, id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Notification not found".to_string()))?;
+
+    let resent = deployment
+        .container()
+        .notification_service()
+        .resend(&record)
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(resent)))
+}
+
+pub async fn send_test_notification(
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<Vec<ChannelTestResult>>> {
+    let results = deployment
+        .container()
+        .notification_service()
+        .send_test()
+        .await;
+    ResponseJson(ApiResponse::success(results))
+}
+
+pub async fn acknowledge_escalation(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<NotificationEscalation>>, ApiError> {
+    let escalation = NotificationEscalation::acknowledge(&deployment.db().pool, id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Escalation not found".to_string()))?;
+    Ok(ResponseJson(ApiResponse::success(escalation)))
+}
+
+/// Target of the links in failure notifications: acknowledges, then opens the task
+pub async fn acknowledge_escalation_link(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<Redirect, ApiError> {
+    let escalation = NotificationEscalation::acknowledge(&deployment.db().pool, id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Escalation not found".to_string()))?;
+    Ok(Redirect::to(escalation.task_url.as_deref().unwrap_or("/")))
+}
+
+/// Target of the shortened task links in size-limited notifications such as IRC
+pub async fn open_short_task_link(
+    State(deployment): State<DeploymentImpl>,
+    Path(short_id): Path<String>,
+) -> Result<Redirect, ApiError> {
+    if short_id.len() < 8 || !short_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ApiError::BadRequest("Invalid task link".to_string()));
+    }
+    let task = Task::find_by_short_id(&deployment.db().pool, &short_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Task not found".to_string()))?;
+    Ok(Redirect::to(&format!(
+        "/projects/{}/tasks/{}",
+        task.project_id, task.id
+    )))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/notifications", get(get_notifications))
+        .route("/notifications/test", post(send_test_notification))
+        .route("/notifications/health", get(get_notification_health))
+        .route("/notifications/{id}/resend", post(resend_notification))
+        .route(
+            "/notifications/escalations/{id}/acknowledge",
+            post(acknowledge_escalation),
+        )
+        .route(
+            "/notifications/escalations/{id}/ack",
+            get(acknowledge_escalation_link),
+        )
+        .route("/t/{short_id}", get(open_short_task_link))
+}
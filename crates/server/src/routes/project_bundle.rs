@@ -0,0 +1,127 @@
+use axum::{
+    Router,
+    body::Body,
+    extract::{DefaultBodyLimit, Multipart, Path, Query, State},
+    http::{StatusCode, header},
+    response::{Json as ResponseJson, Response},
+    routing::{get, post},
+};
+use db::models::{project::Project, task_attachment::sanitize_file_name};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::project_bundle::{self, ProjectImportResult};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::config::change_config};
+
+const MAX_BUNDLE_BYTES: usize = 256 * 1024 * 1024;
+
+/// Download the whole project as a `.tar.gz` bundle
+pub async fn export_project(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let settings = deployment
+        .config()
+        .read()
+        .await
+        .project_settings(project.id);
+    let bundle = project_bundle::export(pool, &project, settings).await?;
+    let data = project_bundle::write_archive(&bundle)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_exported",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "tasks": bundle.tasks.len(),
+            }),
+        )
+        .await;
+
+    let disposition = format!(
+        "attachment; filename=\"{}.tar.gz\"",
+        sanitize_file_name(&project.name)
+    );
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/gzip")
+        .header(header::CONTENT_LENGTH, data.len())
+        .header(header::CONTENT_DISPOSITION, disposition)
+        .body(Body::from(data))
+        .map_err(|e| ApiError::Io(std::io::Error::other(e)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportProjectQuery {
+    /// Name for the new project; defaults to the bundled project's
+    pub name: Option<String>,
+}
+
+/// Create a new project from a bundle uploaded as the `bundle` field
+pub async fn import_project(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ImportProjectQuery>,
+    mut multipart: Multipart,
+) -> Result<ResponseJson<ApiResponse<ProjectImportResult>>, ApiError> {
+    let mut data = None;
+    while let Some(field) = multipart.next_field().await? {
+        if field.name() == Some("bundle") {
+            data = Some(field.bytes().await?);
+            break;
+        }
+    }
+    let data = data.ok_or_else(|| ApiError::BadRequest("Missing bundle file".to_string()))?;
+    let bundle = project_bundle::read_archive(&data)?;
+
+    let mut result = project_bundle::import(
+        &deployment.db().pool,
+        deployment.project(),
+        deployment.repo(),
+        &bundle,
+        query.name.filter(|name| !name.trim().is_empty()),
+    )
+    .await?;
+
+    let project_id = result.project.id;
+    let settings = bundle.settings;
+    result.settings_applied =
+        match change_config(&deployment, "POST /api/projects/import", |config| {
+            config.set_project_settings(project_id, settings)
+        })
+        .await
+        {
+            Ok(_) => true,
+            Err(e) => {
+                tracing::warn!("Skipped bundled settings for project {project_id}: {e}");
+                false
+            }
+        };
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_imported",
+            serde_json::json!({
+                "project_id": project_id.to_string(),
+                "tasks": result.tasks,
+                "skipped_repos": result.skipped_repos.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(result)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/projects/{project_id}/export", get(export_project))
+        .route(
+            "/projects/import",
+            post(import_project).layer(DefaultBodyLimit::max(MAX_BUNDLE_BYTES)),
+        )
+}
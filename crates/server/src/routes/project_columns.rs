@@ -0,0 +1,123 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    project::Project,
+    project_column::{CreateProjectColumn, ProjectColumn, UpdateProjectColumn},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::wip::{self, WipViolation};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// The column, provided it belongs to the project
+async fn find_column(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    column_id: Uuid,
+) -> Result<ProjectColumn, ApiError> {
+    ProjectColumn::find_by_id(&deployment.db().pool, column_id)
+        .await?
+        .filter(|column| column.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn get_project_columns(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectColumn>>>, ApiError> {
+    let columns = ProjectColumn::find_by_project_id(&deployment.db().pool, project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(columns)))
+}
+
+pub async fn create_project_column(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<CreateProjectColumn>,
+) -> Result<ResponseJson<ApiResponse<ProjectColumn>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let column = ProjectColumn::create(pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_column_created",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "status": column.status,
+                "has_wip_limit": column.wip_limit.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(column)))
+}
+
+pub async fn update_project_column(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, column_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateProjectColumn>,
+) -> Result<ResponseJson<ApiResponse<ProjectColumn>>, ApiError> {
+    let column = find_column(&deployment, project_id, column_id).await?;
+    let updated = ProjectColumn::update(&deployment.db().pool, &column, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+pub async fn delete_project_column(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, column_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let column = find_column(&deployment, project_id, column_id).await?;
+    ProjectColumn::delete(&deployment.db().pool, &column).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Column and executor WIP limits that are currently exceeded, e.g. after an override
+pub async fn get_wip_violations(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<WipViolation>>>, ApiError> {
+    let executor_limits = deployment.config().read().await.executor_wip_limits.clone();
+    let violations =
+        wip::current_violations(&deployment.db().pool, &executor_limits, project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(violations)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ReorderProjectColumnsRequest {
+    /// Column ids in their new board order
+    pub column_ids: Vec<Uuid>,
+}
+
+pub async fn reorder_project_columns(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<ReorderProjectColumnsRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectColumn>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    ProjectColumn::reorder(pool, project_id, &payload.column_ids).await?;
+    let columns = ProjectColumn::find_by_project_id(pool, project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(columns)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let inner = Router::new()
+        .route("/", get(get_project_columns).post(create_project_column))
+        .route("/order", put(reorder_project_columns))
+        .route("/wip", get(get_wip_violations))
+        .route(
+            "/{column_id}",
+            put(update_project_column).delete(delete_project_column),
+        );
+
+    Router::new().nest("/projects/{project_id}/columns", inner)
+}
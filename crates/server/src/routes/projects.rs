@@ -16,12 +16,15 @@ use db::models::{
     project::{CreateProject, Project, ProjectError, SearchResult, UpdateProject},
     project_repo::{CreateProjectRepo, ProjectRepo, UpdateProjectRepo},
     repo::Repo,
+    time_tracking::{AttemptTime, ProjectTime},
 };
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::Deserialize;
 use services::services::{
-    file_search_cache::SearchQuery, project::ProjectServiceError,
+    config::{Config, ProjectSettings},
+    file_search_cache::SearchQuery,
+    project::ProjectServiceError,
     remote_client::CreateRemoteProjectPayload,
 };
 use ts_rs::TS;
@@ -31,7 +34,10 @@ use utils::{
 };
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+use crate::{
+    DeploymentImpl, error::ApiError, middleware::load_project_middleware,
+    routes::config::change_config,
+};
 
 #[derive(Deserialize, TS)]
 pub struct LinkToExistingRequest {
@@ -279,6 +285,46 @@ pub async fn update_project(
     }
 }
 
+#[derive(Debug, serde::Serialize, TS)]
+pub struct ProjectConfigResponse {
+    pub settings: ProjectSettings,
+    /// Global config with the project's overrides applied
+    pub effective: Config,
+}
+
+impl ProjectConfigResponse {
+    fn new(config: &Config, project_id: Uuid) -> Self {
+        Self {
+            settings: config.project_settings(project_id),
+            effective: config.for_project(Some(project_id)),
+        }
+    }
+}
+
+pub async fn get_project_config(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<ProjectConfigResponse>> {
+    let config = deployment.config().read().await;
+    ResponseJson(ApiResponse::success(ProjectConfigResponse::new(
+        &config, project.id,
+    )))
+}
+
+pub async fn update_project_config(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(settings): Json<ProjectSettings>,
+) -> Result<ResponseJson<ApiResponse<ProjectConfigResponse>>, ApiError> {
+    let config = change_config(&deployment, "PUT /api/projects/config", |config| {
+        config.set_project_settings(project.id, settings)
+    })
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(
+        ProjectConfigResponse::new(&config, project.id),
+    )))
+}
+
 pub async fn delete_project(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -582,13 +628,26 @@ pub async fn update_project_repository(
     }
 }
 
+pub async fn get_project_time(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ProjectTime>>, ApiError> {
+    let time = AttemptTime::find_for_project(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(time)))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let project_id_router = Router::new()
         .route(
             "/",
             get(get_project).put(update_project).delete(delete_project),
         )
+        .route(
+            "/config",
+            get(get_project_config).put(update_project_config),
+        )
         .route("/remote/members", get(get_project_remote_members))
+        .route("/time", get(get_project_time))
         .route("/search", get(search_project_files))
         .route("/open-editor", post(open_project_in_editor))
         .route(
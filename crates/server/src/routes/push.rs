@@ -0,0 +1,78 @@
+use axum::{
+    Json, Router,
+    extract::State,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::models::push_subscription::PushSubscription;
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::notification::{
+    NotificationError,
+    web_push::{self, VapidKeys},
+};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Keys from the browser's `PushSubscription.toJSON()`
+#[derive(Debug, Deserialize, TS)]
+pub struct PushSubscriptionKeys {
+    pub p256dh: String,
+    pub auth: String,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct PushSubscribeRequest {
+    pub endpoint: String,
+    pub keys: PushSubscriptionKeys,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct PushUnsubscribeRequest {
+    pub endpoint: String,
+}
+
+/// Application server key the frontend passes to `PushManager.subscribe`
+pub async fn get_vapid_public_key() -> Result<ResponseJson<ApiResponse<String>>, ApiError> {
+    let keys = VapidKeys::get().map_err(NotificationError::from)?;
+    Ok(ResponseJson(ApiResponse::success(keys.public_key())))
+}
+
+pub async fn subscribe(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<PushSubscribeRequest>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    if !payload.endpoint.starts_with("https://") {
+        return Err(ApiError::BadRequest(
+            "Push endpoint must be an https URL".to_string(),
+        ));
+    }
+    web_push::validate_subscription_keys(&payload.keys.p256dh, &payload.keys.auth)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    PushSubscription::upsert(
+        &deployment.db().pool,
+        &payload.endpoint,
+        &payload.keys.p256dh,
+        &payload.keys.auth,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn unsubscribe(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<PushUnsubscribeRequest>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    PushSubscription::delete_by_endpoint(&deployment.db().pool, &payload.endpoint).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/push/vapid-public-key", get(get_vapid_public_key))
+        .route("/push/subscribe", post(subscribe))
+        .route("/push/unsubscribe", post(unsubscribe))
+}
@@ -0,0 +1,80 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::{delete, get, put},
+};
+use db::models::queued_attempt::QueuedAttempt;
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::run_queue::{self, RunQueueStatus};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct RunQueueQuery {
+    /// Only this project's queued attempts, against its own limit
+    pub project_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ReorderRunQueueRequest {
+    /// Queued attempt ids in their new order; the order applies within each priority band
+    pub queued_attempt_ids: Vec<Uuid>,
+}
+
+pub async fn get_run_queue(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<RunQueueQuery>,
+) -> Result<ResponseJson<ApiResponse<RunQueueStatus>>, ApiError> {
+    let config = deployment.config().read().await.clone();
+    let status = run_queue::status(&deployment.db().pool, &config, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(status)))
+}
+
+pub async fn reorder_run_queue(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ReorderRunQueueRequest>,
+) -> Result<ResponseJson<ApiResponse<RunQueueStatus>>, ApiError> {
+    let pool = &deployment.db().pool;
+    QueuedAttempt::reorder(pool, &payload.queued_attempt_ids).await?;
+    let config = deployment.config().read().await.clone();
+    let status = run_queue::status(pool, &config, None).await?;
+    Ok(ResponseJson(ApiResponse::success(status)))
+}
+
+/// Take an attempt off the queue. Its workspace stays, with nothing run in it.
+pub async fn cancel_queued_attempt(
+    State(deployment): State<DeploymentImpl>,
+    Path(queued_attempt_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let attempt = QueuedAttempt::find_by_id(pool, queued_attempt_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    QueuedAttempt::delete(pool, attempt.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "queued_attempt_cancelled",
+            serde_json::json!({
+                "task_id": attempt.task_id.to_string(),
+                "workspace_id": attempt.workspace_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let inner = Router::new()
+        .route("/", get(get_run_queue))
+        .route("/order", put(reorder_run_queue))
+        .route("/{queued_attempt_id}", delete(cancel_queued_attempt));
+
+    Router::new().nest("/run-queue", inner)
+}
@@ -0,0 +1,140 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::{
+    project::Project,
+    project_column::ProjectColumn,
+    saved_view::{CreateSavedView, SavedView, UpdateSavedView},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// The view, provided it belongs to the project
+async fn find_view(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    view_id: Uuid,
+) -> Result<SavedView, ApiError> {
+    SavedView::find_by_id(&deployment.db().pool, view_id)
+        .await?
+        .filter(|view| view.project_id == project_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+/// Reject a blank name, or columns that aren't the project's
+async fn validate_view(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    name: Option<&str>,
+    columns: Option<&[Uuid]>,
+) -> Result<(), ApiError> {
+    if name.is_some_and(|name| name.trim().is_empty()) {
+        return Err(ApiError::BadRequest("View name is required".to_string()));
+    }
+    if let Some(columns) = columns.filter(|columns| !columns.is_empty()) {
+        let project_columns =
+            ProjectColumn::find_by_project_id(&deployment.db().pool, project_id).await?;
+        if let Some(unknown) = columns
+            .iter()
+            .find(|id| !project_columns.iter().any(|column| column.id == **id))
+        {
+            return Err(ApiError::BadRequest(format!(
+                "Column {unknown} is not on this project's board"
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub async fn get_saved_views(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<SavedView>>>, ApiError> {
+    let views = SavedView::find_by_project_id(&deployment.db().pool, project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(views)))
+}
+
+pub async fn get_saved_view(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, view_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<SavedView>>, ApiError> {
+    let view = find_view(&deployment, project_id, view_id).await?;
+    Ok(ResponseJson(ApiResponse::success(view)))
+}
+
+pub async fn create_saved_view(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<CreateSavedView>,
+) -> Result<ResponseJson<ApiResponse<SavedView>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    validate_view(
+        &deployment,
+        project.id,
+        Some(&payload.name),
+        Some(&payload.columns),
+    )
+    .await?;
+    let view = SavedView::create(pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "saved_view_created",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "has_sort": view.sort.is_some(),
+                "columns": view.columns.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(view)))
+}
+
+pub async fn update_saved_view(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, view_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateSavedView>,
+) -> Result<ResponseJson<ApiResponse<SavedView>>, ApiError> {
+    let view = find_view(&deployment, project_id, view_id).await?;
+    validate_view(
+        &deployment,
+        project_id,
+        payload.name.as_deref(),
+        payload.columns.as_deref(),
+    )
+    .await?;
+    let updated = SavedView::update(&deployment.db().pool, &view, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+pub async fn delete_saved_view(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, view_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let view = find_view(&deployment, project_id, view_id).await?;
+    SavedView::delete(&deployment.db().pool, view.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let inner = Router::new()
+        .route("/", get(get_saved_views).post(create_saved_view))
+        .route(
+            "/{view_id}",
+            get(get_saved_view)
+                .put(update_saved_view)
+                .delete(delete_saved_view),
+        );
+
+    Router::new().nest("/projects/{project_id}/views", inner)
+}
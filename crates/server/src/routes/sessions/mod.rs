@@ -119,9 +119,11 @@ pub async fn follow_up(
     let initial_executor_profile_id =
         ExecutionProcess::latest_executor_profile_for_session(pool, session.id).await?;
 
+    // Follow-ups keep the model the attempt was started with
     let executor_profile_id = ExecutorProfileId {
         executor: initial_executor_profile_id.executor,
         variant: payload.variant,
+        model_override: initial_executor_profile_id.model_override,
     };
 
     // Get parent task
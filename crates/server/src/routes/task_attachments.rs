@@ -0,0 +1,140 @@
+use axum::{
+    Router,
+    body::Body,
+    extract::{DefaultBodyLimit, Multipart, Path, State},
+    http::{StatusCode, header},
+    response::{Json as ResponseJson, Response},
+    routing::get,
+};
+use db::models::{
+    task::Task,
+    task_attachment::{TaskAttachment, sanitize_file_name},
+};
+use deployment::Deployment;
+use services::services::attachment::AttachmentError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Upper bound on the request body; the configured attachment size limit is enforced when storing
+const MAX_UPLOAD_BODY_BYTES: usize = 512 * 1024 * 1024;
+
+/// The attachment, provided it belongs to the task
+async fn find_attachment(
+    deployment: &DeploymentImpl,
+    task_id: Uuid,
+    attachment_id: Uuid,
+) -> Result<TaskAttachment, ApiError> {
+    TaskAttachment::find_by_id(&deployment.db().pool, attachment_id)
+        .await?
+        .filter(|attachment| attachment.task_id == task_id)
+        .ok_or(ApiError::Attachment(AttachmentError::NotFound))
+}
+
+pub async fn get_task_attachments(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskAttachment>>>, ApiError> {
+    let attachments = TaskAttachment::find_by_task_id(&deployment.db().pool, task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(attachments)))
+}
+
+pub async fn upload_task_attachment(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> Result<ResponseJson<ApiResponse<TaskAttachment>>, ApiError> {
+    let task = Task::find_by_id(&deployment.db().pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let config = deployment.config().read().await.attachments.clone();
+
+    while let Some(field) = multipart.next_field().await? {
+        if field.name() == Some("file") {
+            let original_name = field
+                .file_name()
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| "attachment".to_string());
+            let mime_type = field.content_type().map(|s| s.to_string());
+
+            let data = field.bytes().await?;
+            let attachment = deployment
+                .attachment()
+                .store(&config, task.id, &original_name, mime_type, &data)
+                .await?;
+
+            deployment
+                .track_if_analytics_allowed(
+                    "task_attachment_uploaded",
+                    serde_json::json!({
+                        "task_id": task.id.to_string(),
+                        "project_id": task.project_id.to_string(),
+                        "size_bytes": attachment.size_bytes,
+                        "mime_type": attachment.mime_type,
+                        "storage": attachment.storage,
+                    }),
+                )
+                .await;
+
+            return Ok(ResponseJson(ApiResponse::success(attachment)));
+        }
+    }
+
+    Err(ApiError::BadRequest(
+        "Missing 'file' field in upload".to_string(),
+    ))
+}
+
+/// Download the attachment's contents
+pub async fn download_task_attachment(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Response, ApiError> {
+    let attachment = find_attachment(&deployment, task_id, attachment_id).await?;
+    let config = deployment.config().read().await.attachments.clone();
+    let data = deployment.attachment().read(&config, &attachment).await?;
+
+    let content_type = attachment
+        .mime_type
+        .as_deref()
+        .unwrap_or("application/octet-stream");
+    let disposition = format!(
+        "attachment; filename=\"{}\"",
+        sanitize_file_name(&attachment.original_name)
+    );
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::CONTENT_LENGTH, data.len())
+        .header(header::CONTENT_DISPOSITION, disposition)
+        .body(Body::from(data))
+        .map_err(|e| ApiError::Attachment(AttachmentError::Storage(e.to_string())))
+}
+
+pub async fn delete_task_attachment(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let attachment = find_attachment(&deployment, task_id, attachment_id).await?;
+    let config = deployment.config().read().await.attachments.clone();
+    deployment.attachment().delete(&config, &attachment).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let inner = Router::new()
+        .route(
+            "/",
+            get(get_task_attachments)
+                .post(upload_task_attachment)
+                .layer(DefaultBodyLimit::max(MAX_UPLOAD_BODY_BYTES)),
+        )
+        .route(
+            "/{attachment_id}",
+            get(download_task_attachment).delete(delete_task_attachment),
+        );
+
+    Router::new().nest("/tasks/{task_id}/attachments", inner)
+}
@@ -1,7 +1,12 @@
+pub mod checkpoints;
 pub mod codex_setup;
+pub mod comparison;
 pub mod cursor_setup;
 pub mod gh_cli_setup;
+pub mod handoff;
 pub mod images;
+pub mod patches;
+pub mod plan;
 pub mod pr;
 pub mod util;
 
@@ -22,13 +27,19 @@ use axum::{
     routing::{get, post},
 };
 use db::models::{
+    approval_decision::ApprovalDecision,
+    attempt_patch::AttemptDryRun,
+    attempt_plan::AttemptPlan,
+    attempt_review::{AttemptReviewRound, AttemptReviewer},
     execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
     merge::{Merge, MergeStatus, PrMerge, PullRequestInfo},
     project_repo::ProjectRepo,
     repo::{Repo, RepoError},
     session::{CreateSession, Session},
     task::{Task, TaskRelationships, TaskStatus},
-    workspace::{CreateWorkspace, Workspace, WorkspaceError},
+    task_dependency::TaskDependency,
+    time_tracking::AttemptTime,
+    workspace::{Workspace, WorkspaceError},
     workspace_repo::{CreateWorkspaceRepo, RepoWithTargetBranch, WorkspaceRepo},
 };
 use deployment::Deployment;
@@ -43,9 +54,14 @@ use executors::{
 use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
+    budget,
+    config::NotificationEvent,
     container::ContainerService,
     git::{ConflictOp, GitCliError, GitServiceError},
     github::GitHubService,
+    notification::{NotificationContext, i18n},
+    run_queue::AttemptStart,
+    wip::{self, WipViolation},
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
@@ -103,11 +119,100 @@ pub async fn get_task_attempt(
     Ok(ResponseJson(ApiResponse::success(workspace)))
 }
 
+pub async fn get_task_attempt_time(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<AttemptTime>>, ApiError> {
+    let time = AttemptTime::find_for_workspace(&deployment.db().pool, workspace.id)
+        .await?
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    Ok(ResponseJson(ApiResponse::success(time)))
+}
+
 #[derive(Debug, Serialize, Deserialize, ts_rs::TS)]
 pub struct CreateTaskAttemptBody {
     pub task_id: Uuid,
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
+    /// Start even though tasks blocking this one aren't done yet
+    #[ts(optional)]
+    pub ignore_blockers: Option<bool>,
+    /// Start even though a column or executor is at its WIP limit
+    #[ts(optional)]
+    pub ignore_wip_limits: Option<bool>,
+    /// Have the agent write a plan first, and wait for it to be approved before carrying it out
+    #[ts(optional)]
+    pub plan_first: Option<bool>,
+    /// Have this executor profile review the agent's work once it finishes, feeding requested
+    /// changes back to the agent
+    #[ts(optional)]
+    pub reviewer_profile_id: Option<ExecutorProfileId>,
+    /// Keep the agent's changes as reviewable patches instead of committing or pushing them
+    #[ts(optional)]
+    pub dry_run: Option<bool>,
+}
+
+/// Refuse to start attempts in a project whose monthly budget's hard limit is reached
+pub async fn check_budget(deployment: &DeploymentImpl, project_id: Uuid) -> Result<(), ApiError> {
+    if let Some(status) =
+        budget::hard_limit_reached(&deployment.db().pool, project_id, chrono::Utc::now()).await?
+    {
+        return Err(ApiError::Conflict(budget::describe(&status)));
+    }
+    Ok(())
+}
+
+/// Refuse to commit to, merge, push or rebase a dry-run attempt's branch before its patches
+/// are promoted to it
+pub async fn ensure_not_dry_run(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+) -> Result<(), ApiError> {
+    if AttemptDryRun::is_active(&deployment.db().pool, workspace.id).await? {
+        return Err(ApiError::Conflict(
+            "This is a dry-run attempt; promote its patches to the branch first".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// WIP limits that starting would exceed, which are an error unless the caller overrides them.
+/// `task` is `None` for a task that is yet to be created.
+pub async fn check_wip_limits(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    task: Option<&Task>,
+    executor_profile_id: &ExecutorProfileId,
+    ignore_wip_limits: Option<bool>,
+) -> Result<Vec<WipViolation>, ApiError> {
+    let violations = deployment
+        .wip_violations_for_start(project_id, task, executor_profile_id)
+        .await?;
+    if !violations.is_empty() && !ignore_wip_limits.unwrap_or(false) {
+        return Err(ApiError::Conflict(wip::describe(&violations)));
+    }
+    Ok(violations)
+}
+
+/// Report WIP limits a start went over, once it has happened
+pub async fn notify_overridden_wip_limits(
+    deployment: &DeploymentImpl,
+    task: &Task,
+    violations: &[WipViolation],
+) {
+    if violations.is_empty() {
+        return;
+    }
+    if let Err(e) = deployment
+        .notify_wip_limit_exceeded(task, violations, true)
+        .await
+    {
+        tracing::warn!(
+            "Failed to notify about WIP limits for task {}: {}",
+            task.id,
+            e
+        );
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, ts_rs::TS)]
@@ -142,31 +247,24 @@ pub async fn create_task_attempt(
         .await?
         .ok_or(SqlxError::RowNotFound)?;
 
-    let project = task
-        .parent_project(pool)
-        .await?
-        .ok_or(SqlxError::RowNotFound)?;
-
-    let agent_working_dir = project
-        .default_agent_working_dir
-        .as_ref()
-        .filter(|dir| !dir.is_empty())
-        .cloned();
-
-    let attempt_id = Uuid::new_v4();
-    let git_branch_name = deployment
-        .container()
-        .git_branch_from_workspace(&attempt_id, &task.title)
-        .await;
-
-    let workspace = Workspace::create(
-        pool,
-        &CreateWorkspace {
-            branch: git_branch_name.clone(),
-            agent_working_dir,
-        },
-        attempt_id,
-        payload.task_id,
+    let blockers = TaskDependency::find_incomplete_blockers(pool, task.id).await?;
+    if !blockers.is_empty() && !payload.ignore_blockers.unwrap_or(false) {
+        let titles: Vec<String> = blockers
+            .iter()
+            .map(|blocker| format!("'{}'", blocker.title))
+            .collect();
+        return Err(ApiError::Conflict(format!(
+            "Task is blocked by {}",
+            titles.join(", ")
+        )));
+    }
+    check_budget(&deployment, task.project_id).await?;
+    let violations = check_wip_limits(
+        &deployment,
+        task.project_id,
+        Some(&task),
+        &executor_profile_id,
+        payload.ignore_wip_limits,
     )
     .await?;
 
@@ -178,15 +276,31 @@ pub async fn create_task_attempt(
             target_branch: r.target_branch.clone(),
         })
         .collect();
-
-    WorkspaceRepo::create_many(pool, workspace.id, &workspace_repos).await?;
-    if let Err(err) = deployment
+    let workspace = deployment
         .container()
-        .start_workspace(&workspace, executor_profile_id.clone())
+        .create_task_workspace(&task, &workspace_repos)
+        .await?;
+    if payload.plan_first.unwrap_or(false) {
+        AttemptPlan::create(&deployment.db().pool, workspace.id).await?;
+    }
+    if payload.dry_run.unwrap_or(false) {
+        AttemptDryRun::create(&deployment.db().pool, workspace.id).await?;
+    }
+    if let Some(reviewer_profile_id) = &payload.reviewer_profile_id {
+        AttemptReviewer::create(&deployment.db().pool, workspace.id, reviewer_profile_id).await?;
+    }
+
+    match deployment
+        .start_or_queue_attempt(&workspace, executor_profile_id.clone())
         .await
     {
-        tracing::error!("Failed to start task attempt: {}", err);
+        Ok(AttemptStart::Started(_)) => {}
+        Ok(AttemptStart::Queued(_)) => {
+            tracing::info!("Queued attempt for task {} until a slot frees up", task.id)
+        }
+        Err(err) => tracing::error!("Failed to start task attempt: {}", err),
     }
+    notify_overridden_wip_limits(&deployment, &task, &violations).await;
 
     deployment
         .track_if_analytics_allowed(
@@ -197,6 +311,9 @@ pub async fn create_task_attempt(
                 "executor": &executor_profile_id.executor,
                 "workspace_id": workspace.id.to_string(),
                 "repository_count": payload.repos.len(),
+                "plan_first": payload.plan_first.unwrap_or(false),
+                "reviewer": payload.reviewer_profile_id.is_some(),
+                "dry_run": payload.dry_run.unwrap_or(false),
             }),
         )
         .await;
@@ -316,6 +433,7 @@ pub async fn merge_task_attempt(
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<MergeTaskAttemptRequest>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ensure_not_dry_run(&deployment, &workspace).await?;
     let pool = &deployment.db().pool;
 
     let workspace_repo =
@@ -428,6 +546,7 @@ pub async fn push_task_attempt_branch(
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<PushTaskAttemptRequest>,
 ) -> Result<ResponseJson<ApiResponse<(), PushError>>, ApiError> {
+    ensure_not_dry_run(&deployment, &workspace).await?;
     let pool = &deployment.db().pool;
 
     let github_service = GitHubService::new()?;
@@ -466,6 +585,7 @@ pub async fn force_push_task_attempt_branch(
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<PushTaskAttemptRequest>,
 ) -> Result<ResponseJson<ApiResponse<(), PushError>>, ApiError> {
+    ensure_not_dry_run(&deployment, &workspace).await?;
     let pool = &deployment.db().pool;
 
     let github_service = GitHubService::new()?;
@@ -972,6 +1092,7 @@ pub async fn rebase_task_attempt(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<RebaseTaskAttemptRequest>,
 ) -> Result<ResponseJson<ApiResponse<(), GitOperationError>>, ApiError> {
+    ensure_not_dry_run(&deployment, &workspace).await?;
     let pool = &deployment.db().pool;
 
     let workspace_repo =
@@ -1031,15 +1152,45 @@ pub async fn rebase_task_attempt(
     if let Err(e) = result {
         use services::services::git::GitServiceError;
         return match e {
-            GitServiceError::MergeConflicts(msg) => Ok(ResponseJson(ApiResponse::<
-                (),
-                GitOperationError,
-            >::error_with_data(
-                GitOperationError::MergeConflicts {
-                    message: msg,
-                    op: ConflictOp::Rebase,
-                },
-            ))),
+            GitServiceError::MergeConflicts(msg) => {
+                if let Some(task) = workspace.parent_task(pool).await?
+                    && let Some(project) = task.parent_project(pool).await?
+                {
+                    let context = NotificationContext::new(NotificationEvent::MergeConflict)
+                        .with_task(project.id, task.id)
+                        .with_workspace(workspace.id)
+                        .with_names(&project.name, &task.title);
+                    let notification_service = deployment.container().notification_service();
+                    let locale = notification_service.locale().await;
+                    notification_service
+                        .notify(
+                            &context,
+                            &i18n::t(
+                                locale,
+                                "merge-conflict-title",
+                                &[("task", task.title.as_str())],
+                            ),
+                            &i18n::t(
+                                locale,
+                                "merge-conflict",
+                                &[
+                                    ("branch", workspace.branch.as_str()),
+                                    ("base", new_base_branch.as_str()),
+                                    ("repo", repo.name.as_str()),
+                                ],
+                            ),
+                        )
+                        .await;
+                }
+                Ok(ResponseJson(
+                    ApiResponse::<(), GitOperationError>::error_with_data(
+                        GitOperationError::MergeConflicts {
+                            message: msg,
+                            op: ConflictOp::Rebase,
+                        },
+                    ),
+                ))
+            }
             GitServiceError::RebaseInProgress => Ok(ResponseJson(ApiResponse::<
                 (),
                 GitOperationError,
@@ -1252,6 +1403,48 @@ pub async fn stop_task_attempt_execution(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Suspend the attempt's running setup scripts and coding agent. A paused attempt frees its
+/// run queue slot.
+pub async fn pause_task_attempt(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    if deployment.container().pause_workspace(&workspace).await? == 0 {
+        return Err(ApiError::Conflict("No running agent to pause".to_string()));
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_paused",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn resume_task_attempt(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    if deployment.container().resume_workspace(&workspace).await? == 0 {
+        return Err(ApiError::Conflict("Attempt isn't paused".to_string()));
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_resumed",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 #[derive(Debug, Serialize, Deserialize, TS)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
@@ -1477,9 +1670,41 @@ pub async fn get_task_attempt_repos(
     Ok(ResponseJson(ApiResponse::success(repos)))
 }
 
+/// Tool calls the attempt's agents asked to have approved, and the decisions
+pub async fn get_task_attempt_approvals(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ApprovalDecision>>>, ApiError> {
+    let decisions =
+        ApprovalDecision::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(decisions)))
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct AttemptReviews {
+    /// Absent when the attempt has no reviewer stage
+    pub reviewer: Option<AttemptReviewer>,
+    pub rounds: Vec<AttemptReviewRound>,
+}
+
+/// The attempt's reviewer and its review rounds, oldest first
+pub async fn get_task_attempt_reviews(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<AttemptReviews>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let reviewer = AttemptReviewer::find_by_workspace_id(pool, workspace.id).await?;
+    let rounds = AttemptReviewRound::find_by_workspace_id(pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(AttemptReviews {
+        reviewer,
+        rounds,
+    })))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_attempt_id_router = Router::new()
         .route("/", get(get_task_attempt))
+        .route("/time", get(get_task_attempt_time))
         .route("/run-agent-setup", post(run_agent_setup))
         .route("/gh-cli-setup", post(gh_cli_setup_handler))
         .route("/start-dev-server", post(start_dev_server))
@@ -1498,9 +1723,28 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/open-editor", post(open_task_attempt_in_editor))
         .route("/children", get(get_task_attempt_children))
         .route("/stop", post(stop_task_attempt_execution))
+        .route("/pause", post(pause_task_attempt))
+        .route("/resume", post(resume_task_attempt))
+        .route(
+            "/checkpoints",
+            get(checkpoints::get_checkpoints).post(checkpoints::create_checkpoint),
+        )
+        .route(
+            "/checkpoints/{checkpoint_id}/rollback",
+            post(checkpoints::rollback_to_checkpoint),
+        )
+        .route("/plan", get(plan::get_plan))
+        .route("/plan/approve", post(plan::approve_plan))
+        .route("/plan/reject", post(plan::reject_plan))
         .route("/change-target-branch", post(change_target_branch))
         .route("/rename-branch", post(rename_branch))
         .route("/repos", get(get_task_attempt_repos))
+        .route("/approvals", get(get_task_attempt_approvals))
+        .route("/reviews", get(get_task_attempt_reviews))
+        .route("/handoffs", get(handoff::get_handoffs))
+        .route("/handoff", post(handoff::hand_off))
+        .route("/patches", get(patches::get_patches))
+        .route("/patches/promote", post(patches::promote_patches))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_workspace_middleware,
@@ -1511,5 +1755,15 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .nest("/{id}", task_attempt_id_router)
         .nest("/{id}/images", images::router(deployment));
 
-    Router::new().nest("/task-attempts", task_attempts_router)
+    let comparisons_router = Router::new()
+        .route(
+            "/",
+            get(comparison::get_comparisons).post(comparison::create_comparison),
+        )
+        .route("/{comparison_id}", get(comparison::get_comparison))
+        .route("/{comparison_id}/choose", post(comparison::choose_variant));
+
+    Router::new()
+        .nest("/task-attempts", task_attempts_router)
+        .nest("/attempt-comparisons", comparisons_router)
 }
@@ -0,0 +1,168 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    attempt_checkpoint::AttemptCheckpoint,
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::container::ContainerService;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::task_attempts::ensure_not_dry_run};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateCheckpointRequest {
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct RollbackCheckpointRequest {
+    /// Discard uncommitted changes in the worktrees
+    pub force_when_dirty: Option<bool>,
+}
+
+pub async fn get_checkpoints(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptCheckpoint>>>, ApiError> {
+    let checkpoints =
+        AttemptCheckpoint::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(checkpoints)))
+}
+
+/// Commit any uncommitted changes and record a checkpoint after the latest agent turn
+pub async fn create_checkpoint(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateCheckpointRequest>,
+) -> Result<ResponseJson<ApiResponse<AttemptCheckpoint>>, ApiError> {
+    let pool = &deployment.db().pool;
+    ensure_not_dry_run(&deployment, &workspace).await?;
+
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Err(ApiError::Conflict(
+            "Wait for the attempt to finish, or stop it, before taking a checkpoint".to_string(),
+        ));
+    }
+    let process = ExecutionProcess::find_latest_by_workspace_and_run_reason(
+        pool,
+        workspace.id,
+        &ExecutionProcessRunReason::CodingAgent,
+    )
+    .await?
+    .ok_or_else(|| ApiError::Conflict("The agent hasn't run in this attempt yet".to_string()))?;
+
+    deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let label = payload
+        .label
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty());
+    let message = match &label {
+        Some(label) => format!("Checkpoint: {label}"),
+        None => "Checkpoint".to_string(),
+    };
+    let workspace_root = deployment.container().workspace_to_current_dir(&workspace);
+    for repo in WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await? {
+        deployment
+            .git()
+            .commit(&workspace_root.join(&repo.name), &message)?;
+    }
+
+    let checkpoint = deployment
+        .container()
+        .record_checkpoint(&workspace, &process, label)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_checkpoint_created",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "checkpoint_id": checkpoint.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(checkpoint)))
+}
+
+/// Reset the worktrees to a checkpoint and drop the processes after it, so the next follow-up
+/// resumes the agent session as it was there
+pub async fn rollback_to_checkpoint(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_, checkpoint_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<RollbackCheckpointRequest>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let checkpoint = AttemptCheckpoint::find_by_id(pool, checkpoint_id)
+        .await?
+        .filter(|checkpoint| checkpoint.workspace_id == workspace.id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let process = ExecutionProcess::find_by_id(pool, checkpoint.execution_process_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    if process.dropped {
+        return Err(ApiError::Conflict(
+            "This checkpoint was already rolled back past".to_string(),
+        ));
+    }
+
+    deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let force_when_dirty = payload.force_when_dirty.unwrap_or(false);
+    if !force_when_dirty
+        && !deployment
+            .container()
+            .is_container_clean(&workspace)
+            .await?
+    {
+        return Err(ApiError::Conflict(
+            "The worktree has uncommitted changes; force the rollback to discard them".to_string(),
+        ));
+    }
+
+    deployment.container().try_stop(&workspace, false).await;
+
+    let workspace_root = deployment.container().workspace_to_current_dir(&workspace);
+    let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+    for commit in checkpoint.repo_commits.iter() {
+        if let Some(repo) = repos.iter().find(|repo| repo.id == commit.repo_id) {
+            deployment.git().reset_worktree_to_commit(
+                &workspace_root.join(&repo.name),
+                &commit.commit,
+                true,
+            )?;
+        }
+    }
+
+    ExecutionProcess::drop_after(pool, checkpoint.session_id, checkpoint.execution_process_id)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_rolled_back",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "checkpoint_id": checkpoint.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
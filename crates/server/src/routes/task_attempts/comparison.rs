@@ -0,0 +1,432 @@
+use std::path::PathBuf;
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    attempt_comparison::{AttemptComparison, ComparisonVariant, lowest},
+    execution_usage::{ExecutionUsage, UsageTotals},
+    task::Task,
+    task_dependency::TaskDependency,
+    time_tracking::AttemptTime,
+    workspace::Workspace,
+    workspace_repo::{CreateWorkspaceRepo, RepoWithTargetBranch, WorkspaceRepo},
+};
+use deployment::Deployment;
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use services::services::{container::ContainerService, git::DiffTarget, run_queue::AttemptStart};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::{
+    diff::{Diff, compute_line_change_counts},
+    response::ApiResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::task_attempts::{
+        WorkspaceRepoInput, check_budget, check_wip_limits, notify_overridden_wip_limits,
+    },
+};
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct AttemptComparisonQuery {
+    pub task_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct CreateAttemptComparisonBody {
+    pub task_id: Uuid,
+    /// Two or more different profiles, each run in an attempt and branch of its own
+    pub executor_profile_ids: Vec<ExecutorProfileId>,
+    pub repos: Vec<WorkspaceRepoInput>,
+    /// Start even though tasks blocking this one aren't done yet
+    #[ts(optional)]
+    pub ignore_blockers: Option<bool>,
+    /// Start even though a column or executor is at its WIP limit
+    #[ts(optional)]
+    pub ignore_wip_limits: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct ChooseComparisonVariantRequest {
+    pub workspace_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct ComparisonRepoDiff {
+    pub repo_name: String,
+    pub diffs: Vec<Diff>,
+}
+
+/// One variant's changes, time and cost, for showing side by side with the others
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct ComparisonVariantDetails {
+    pub workspace: Workspace,
+    pub executor_profile_id: ExecutorProfileId,
+    /// `None` before anything has run
+    pub time: Option<AttemptTime>,
+    pub usage: UsageTotals,
+    pub files_changed: i64,
+    pub additions: i64,
+    pub deletions: i64,
+    /// Changes against the target branch, uncommitted ones included
+    pub repos: Vec<ComparisonRepoDiff>,
+    /// Took the least time among the finished variants
+    pub fastest: bool,
+    /// Cost the least among the finished variants whose runs all have a cost
+    pub cheapest: bool,
+    pub chosen: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct AttemptComparisonDetails {
+    pub comparison: AttemptComparison,
+    /// In the order they were requested
+    pub variants: Vec<ComparisonVariantDetails>,
+}
+
+/// The task's comparisons, newest first
+pub async fn get_comparisons(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<AttemptComparisonQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptComparison>>>, ApiError> {
+    let comparisons =
+        AttemptComparison::find_by_task_id(&deployment.db().pool, query.task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(comparisons)))
+}
+
+/// Remove workspaces created for a comparison that couldn't be set up
+async fn discard_workspaces(
+    deployment: &DeploymentImpl,
+    variants: &[(Workspace, ExecutorProfileId)],
+) {
+    for (workspace, _) in variants {
+        if let Err(e) = Workspace::delete(&deployment.db().pool, workspace.id).await {
+            tracing::error!(
+                "Failed to remove workspace {} of an abandoned comparison: {}",
+                workspace.id,
+                e
+            );
+        }
+    }
+}
+
+/// Start the task against each profile at once, in separate workspaces
+pub async fn create_comparison(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateAttemptComparisonBody>,
+) -> Result<ResponseJson<ApiResponse<AttemptComparison>>, ApiError> {
+    if payload.repos.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one repository is required".to_string(),
+        ));
+    }
+    let profiles = &payload.executor_profile_ids;
+    if profiles.len() < 2 {
+        return Err(ApiError::BadRequest(
+            "Pick at least two executor profiles to compare".to_string(),
+        ));
+    }
+    if let Some(duplicate) = profiles
+        .iter()
+        .enumerate()
+        .find(|(i, profile)| profiles[..*i].contains(profile))
+        .map(|(_, profile)| profile)
+    {
+        return Err(ApiError::BadRequest(format!(
+            "{duplicate} is picked more than once"
+        )));
+    }
+
+    let pool = &deployment.db().pool;
+    let task = Task::find_by_id(pool, payload.task_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let blockers = TaskDependency::find_incomplete_blockers(pool, task.id).await?;
+    if !blockers.is_empty() && !payload.ignore_blockers.unwrap_or(false) {
+        let titles: Vec<String> = blockers
+            .iter()
+            .map(|blocker| format!("'{}'", blocker.title))
+            .collect();
+        return Err(ApiError::Conflict(format!(
+            "Task is blocked by {}",
+            titles.join(", ")
+        )));
+    }
+    check_budget(&deployment, task.project_id).await?;
+    let mut violations = vec![];
+    for profile in profiles {
+        violations.extend(
+            check_wip_limits(
+                &deployment,
+                task.project_id,
+                Some(&task),
+                profile,
+                payload.ignore_wip_limits,
+            )
+            .await?,
+        );
+    }
+
+    let workspace_repos: Vec<CreateWorkspaceRepo> = payload
+        .repos
+        .iter()
+        .map(|r| CreateWorkspaceRepo {
+            repo_id: r.repo_id,
+            target_branch: r.target_branch.clone(),
+        })
+        .collect();
+    let mut variants = Vec::with_capacity(profiles.len());
+    for profile in profiles {
+        match deployment
+            .container()
+            .create_task_workspace(&task, &workspace_repos)
+            .await
+        {
+            Ok(workspace) => variants.push((workspace, profile.clone())),
+            Err(err) => {
+                discard_workspaces(&deployment, &variants).await;
+                return Err(err.into());
+            }
+        }
+    }
+    let comparison = match AttemptComparison::create(
+        pool,
+        task.id,
+        &variants
+            .iter()
+            .map(|(workspace, profile)| (workspace.id, profile.clone()))
+            .collect::<Vec<_>>(),
+    )
+    .await
+    {
+        Ok(comparison) => comparison,
+        Err(err) => {
+            discard_workspaces(&deployment, &variants).await;
+            return Err(err.into());
+        }
+    };
+
+    for (workspace, profile) in &variants {
+        match deployment
+            .start_or_queue_attempt(workspace, profile.clone())
+            .await
+        {
+            Ok(AttemptStart::Started(_)) => {}
+            Ok(AttemptStart::Queued(_)) => tracing::info!(
+                "Queued {} variant of comparison {} until a slot frees up",
+                profile,
+                comparison.id
+            ),
+            Err(err) => tracing::error!(
+                "Failed to start {} variant of comparison {}: {}",
+                profile,
+                comparison.id,
+                err
+            ),
+        }
+    }
+    notify_overridden_wip_limits(&deployment, &task, &violations).await;
+
+    deployment
+        .track_if_analytics_allowed(
+            "attempt_comparison_started",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "comparison_id": comparison.id.to_string(),
+                "executors": profiles.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                "repository_count": payload.repos.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(comparison)))
+}
+
+/// Every variant's diff, time and cost
+pub async fn get_comparison(
+    State(deployment): State<DeploymentImpl>,
+    Path(comparison_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<AttemptComparisonDetails>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let comparison = AttemptComparison::find_by_id(pool, comparison_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let runs = ExecutionUsage::find_where(pool, Some(comparison.task_id), None, None, None).await?;
+
+    let mut variants = vec![];
+    for variant in ComparisonVariant::find_by_comparison_id(pool, comparison.id).await? {
+        // The variant's attempt was deleted
+        let Some(workspace) = Workspace::find_by_id(pool, variant.workspace_id).await? else {
+            continue;
+        };
+        let time = AttemptTime::find_for_workspace(pool, workspace.id).await?;
+        let usage = UsageTotals::sum(runs.iter().filter(|run| run.workspace_id == workspace.id));
+        let repos = variant_diffs(&deployment, &workspace).await?;
+        let (mut files_changed, mut additions, mut deletions) = (0, 0, 0);
+        for diff in repos.iter().flat_map(|repo| &repo.diffs) {
+            let (added, deleted) = diff_line_counts(diff);
+            files_changed += 1;
+            additions += added as i64;
+            deletions += deleted as i64;
+        }
+        variants.push(ComparisonVariantDetails {
+            chosen: comparison.chosen_workspace_id == Some(workspace.id),
+            workspace,
+            executor_profile_id: variant.executor_profile_id.0,
+            time,
+            usage,
+            files_changed,
+            additions,
+            deletions,
+            repos,
+            fastest: false,
+            cheapest: false,
+        });
+    }
+
+    let finished = |variant: &ComparisonVariantDetails| {
+        variant
+            .time
+            .as_ref()
+            .filter(|time| time.started_at.is_some() && !time.running)
+            .map(|time| time.wall_clock_seconds as f64)
+    };
+    let durations: Vec<Option<f64>> = variants.iter().map(finished).collect();
+    let costs: Vec<Option<f64>> = variants
+        .iter()
+        .map(|variant| {
+            (finished(variant).is_some()
+                && variant.usage.runs > 0
+                && variant.usage.unpriced_runs == 0)
+                .then_some(variant.usage.cost_usd)
+        })
+        .collect();
+    if let Some(fastest) = lowest(&durations) {
+        variants[fastest].fastest = true;
+    }
+    if let Some(cheapest) = lowest(&costs) {
+        variants[cheapest].cheapest = true;
+    }
+
+    Ok(ResponseJson(ApiResponse::success(
+        AttemptComparisonDetails {
+            comparison,
+            variants,
+        },
+    )))
+}
+
+/// Record which variant won
+pub async fn choose_variant(
+    State(deployment): State<DeploymentImpl>,
+    Path(comparison_id): Path<Uuid>,
+    Json(payload): Json<ChooseComparisonVariantRequest>,
+) -> Result<ResponseJson<ApiResponse<AttemptComparison>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let variants = ComparisonVariant::find_by_comparison_id(pool, comparison_id).await?;
+    let Some(variant) = variants
+        .iter()
+        .find(|variant| variant.workspace_id == payload.workspace_id)
+    else {
+        return Err(ApiError::BadRequest(
+            "The attempt is not part of this comparison".to_string(),
+        ));
+    };
+    let comparison = AttemptComparison::choose(pool, comparison_id, payload.workspace_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "attempt_comparison_variant_chosen",
+            serde_json::json!({
+                "task_id": comparison.task_id.to_string(),
+                "comparison_id": comparison.id.to_string(),
+                "workspace_id": variant.workspace_id.to_string(),
+                "executor": variant.executor_profile_id.0.to_string(),
+                "variant_count": variants.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(comparison)))
+}
+
+/// Changes per repo against the target branch: the worktree's when it still exists, otherwise
+/// the branch's commits
+async fn variant_diffs(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+) -> Result<Vec<ComparisonRepoDiff>, ApiError> {
+    let worktree_root = workspace
+        .container_ref
+        .as_ref()
+        .map(PathBuf::from)
+        .filter(|path| path.exists());
+    let repos = WorkspaceRepo::find_repos_with_target_branch_for_workspace(
+        &deployment.db().pool,
+        workspace.id,
+    )
+    .await?;
+
+    let mut diffs = Vec::with_capacity(repos.len());
+    for RepoWithTargetBranch {
+        repo,
+        target_branch,
+    } in repos
+    {
+        let repo_diffs = match &worktree_root {
+            Some(root) => deployment
+                .git()
+                .get_base_commit(&repo.path, &workspace.branch, &target_branch)
+                .and_then(|base_commit| {
+                    deployment.git().get_diffs(
+                        DiffTarget::Worktree {
+                            worktree_path: &root.join(&repo.name),
+                            base_commit: &base_commit,
+                        },
+                        None,
+                    )
+                }),
+            None => deployment.git().get_diffs(
+                DiffTarget::Branch {
+                    repo_path: &repo.path,
+                    branch_name: &workspace.branch,
+                    base_branch: &target_branch,
+                },
+                None,
+            ),
+        };
+        match repo_diffs {
+            Ok(repo_diffs) => diffs.push(ComparisonRepoDiff {
+                repo_name: repo.name,
+                diffs: repo_diffs,
+            }),
+            Err(e) => tracing::warn!(
+                "Failed to diff repo {} for workspace {}: {}",
+                repo.name,
+                workspace.id,
+                e
+            ),
+        }
+    }
+    Ok(diffs)
+}
+
+fn diff_line_counts(diff: &Diff) -> (usize, usize) {
+    if diff.content_omitted {
+        return (diff.additions.unwrap_or(0), diff.deletions.unwrap_or(0));
+    }
+    compute_line_change_counts(
+        diff.old_content.as_deref().unwrap_or(""),
+        diff.new_content.as_deref().unwrap_or(""),
+    )
+}
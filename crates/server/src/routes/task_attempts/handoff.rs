@@ -0,0 +1,74 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    attempt_handoff::AttemptHandoff, execution_process::ExecutionProcess, workspace::Workspace,
+};
+use deployment::Deployment;
+use executors::profile::ExecutorProfileId;
+use serde::Deserialize;
+use services::services::container::ContainerService;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct HandOffRequest {
+    /// The executor profile that continues the attempt
+    pub executor_profile_id: ExecutorProfileId,
+    /// Why the attempt is handed off, passed on to the new executor
+    pub reason: Option<String>,
+}
+
+/// Handoffs of the attempt, oldest first
+pub async fn get_handoffs(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptHandoff>>>, ApiError> {
+    let handoffs =
+        AttemptHandoff::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(handoffs)))
+}
+
+/// Continue the attempt with a different executor profile, in the same worktree
+pub async fn hand_off(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<HandOffRequest>,
+) -> Result<ResponseJson<ApiResponse<AttemptHandoff>>, ApiError> {
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(
+        &deployment.db().pool,
+        workspace.id,
+    )
+    .await?
+    {
+        return Err(ApiError::Conflict(
+            "Wait for the attempt to finish, or stop it, first".to_string(),
+        ));
+    }
+    let reason = payload
+        .reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+
+    deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let handoff = deployment
+        .container()
+        .hand_off(&workspace, payload.executor_profile_id, reason)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_handed_off",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "from_executor": &handoff.from_executor_profile_id.executor,
+                "to_executor": &handoff.to_executor_profile_id.executor,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(handoff)))
+}
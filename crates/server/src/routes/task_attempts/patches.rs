@@ -0,0 +1,70 @@
+use axum::{Extension, extract::State, response::Json as ResponseJson};
+use db::models::{
+    attempt_patch::{AttemptDryRun, AttemptPatch},
+    execution_process::ExecutionProcess,
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use serde::Serialize;
+use services::services::container::ContainerService;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Serialize, TS)]
+pub struct AttemptPatches {
+    /// Absent unless the attempt was started as a dry run
+    pub dry_run: Option<AttemptDryRun>,
+    pub patches: Vec<AttemptPatch>,
+}
+
+pub async fn get_patches(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<AttemptPatches>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let dry_run = AttemptDryRun::find_by_workspace_id(pool, workspace.id).await?;
+    let patches = AttemptPatch::find_by_workspace_id(pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(AttemptPatches {
+        dry_run,
+        patches,
+    })))
+}
+
+/// Commit a dry-run attempt's patches to its branch
+pub async fn promote_patches(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if !AttemptDryRun::is_active(pool, workspace.id).await? {
+        return Err(ApiError::Conflict(
+            "This attempt isn't a dry run, or was already promoted".to_string(),
+        ));
+    }
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Err(ApiError::Conflict(
+            "Wait for the attempt to finish, or stop it, first".to_string(),
+        ));
+    }
+
+    deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    deployment.container().promote_patches(&workspace).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_patches_promoted",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
@@ -0,0 +1,157 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    attempt_plan::{self, AttemptPlan, AttemptPlanStatus},
+    execution_process::ExecutionProcess,
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::container::ContainerService;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ApprovePlanRequest {
+    /// The plan as edited by the reviewer; the agent's draft is used when absent
+    pub plan: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct RejectPlanRequest {
+    /// What to change; with feedback the agent revises the plan, without it the plan is dropped
+    pub feedback: Option<String>,
+}
+
+pub async fn get_plan(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<AttemptPlan>>>, ApiError> {
+    let plan = AttemptPlan::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(plan)))
+}
+
+/// The plan when it can be decided on, which needs the planning run to have finished
+async fn plan_awaiting_approval(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+) -> Result<AttemptPlan, ApiError> {
+    let pool = &deployment.db().pool;
+    let plan = AttemptPlan::find_by_workspace_id(pool, workspace.id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::Conflict("This attempt wasn't started in plan-first mode".to_string())
+        })?;
+    if plan.status != AttemptPlanStatus::AwaitingApproval {
+        return Err(ApiError::Conflict(
+            "The plan isn't awaiting approval".to_string(),
+        ));
+    }
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Err(ApiError::Conflict(
+            "Wait for the attempt to finish, or stop it, first".to_string(),
+        ));
+    }
+    Ok(plan)
+}
+
+/// Approve the plan and start the agent carrying it out
+pub async fn approve_plan(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ApprovePlanRequest>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    let plan = plan_awaiting_approval(&deployment, &workspace).await?;
+    let text = payload
+        .plan
+        .map(|plan| plan.trim().to_string())
+        .filter(|plan| !plan.is_empty())
+        .or(plan.plan)
+        .unwrap_or_default();
+    if !AttemptPlan::decide(
+        &deployment.db().pool,
+        workspace.id,
+        AttemptPlanStatus::Approved,
+        Some(&text),
+    )
+    .await?
+    {
+        return Err(ApiError::Conflict(
+            "The plan was already decided on".to_string(),
+        ));
+    }
+
+    deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let execution_process = deployment
+        .container()
+        .start_follow_up(&workspace, attempt_plan::execution_prompt(&text))
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_plan_approved",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
+}
+
+/// Send the plan back for revision, or drop it when there is no feedback
+pub async fn reject_plan(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RejectPlanRequest>,
+) -> Result<ResponseJson<ApiResponse<Option<ExecutionProcess>>>, ApiError> {
+    plan_awaiting_approval(&deployment, &workspace).await?;
+    let feedback = payload
+        .feedback
+        .map(|feedback| feedback.trim().to_string())
+        .filter(|feedback| !feedback.is_empty());
+    let status = if feedback.is_some() {
+        AttemptPlanStatus::Drafting
+    } else {
+        AttemptPlanStatus::Rejected
+    };
+    if !AttemptPlan::decide(&deployment.db().pool, workspace.id, status, None).await? {
+        return Err(ApiError::Conflict(
+            "The plan was already decided on".to_string(),
+        ));
+    }
+
+    let execution_process = match feedback {
+        Some(feedback) => {
+            deployment
+                .container()
+                .ensure_container_exists(&workspace)
+                .await?;
+            Some(
+                deployment
+                    .container()
+                    .start_follow_up(&workspace, attempt_plan::revision_prompt(&feedback))
+                    .await?,
+            )
+        }
+        None => None,
+    };
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_plan_rejected",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+                "revise": execution_process.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
+}
@@ -7,10 +7,12 @@ use axum::{
 };
 use db::models::{
     execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    github_issue::GitHubIssueLink,
     merge::{Merge, MergeStatus},
     repo::{Repo, RepoError},
     session::{CreateSession, Session},
     task::{Task, TaskStatus},
+    time_tracking::{AttemptTime, format_seconds},
     workspace::{Workspace, WorkspaceError},
     workspace_repo::WorkspaceRepo,
 };
@@ -22,15 +24,18 @@ use executors::actions::{
 use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
+    config::NotificationEvent,
     container::ContainerService,
     git::{GitCliError, GitServiceError},
     github::{CreatePrRequest, GitHubService, GitHubServiceError, UnifiedPrComment},
+    github_issue_sync,
+    notification::{NotificationContext, i18n},
 };
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{DeploymentImpl, error::ApiError, routes::task_attempts::ensure_not_dry_run};
 
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct CreateGitHubPrRequest {
@@ -95,6 +100,7 @@ Analyze the changes in this branch and write:
    - What changes were made
    - Why they were made (based on the task context)
    - Any important implementation details
+   - The time the agent spent on this attempt: {time_spent}
    - At the end, include a note: "This PR was written using [Vibe Kanban](https://vibekanban.com)"
 
 Use `gh pr edit` to update the PR."#;
@@ -109,15 +115,25 @@ async fn trigger_pr_description_follow_up(
     let config = deployment.config().read().await;
     let prompt_template = config
         .pr_auto_description_prompt
-        .as_deref()
-        .unwrap_or(DEFAULT_PR_DESCRIPTION_PROMPT);
+        .clone()
+        .unwrap_or_else(|| DEFAULT_PR_DESCRIPTION_PROMPT.to_string());
+    drop(config); // Release the lock before async operations
+
+    let time_spent =
+        match AttemptTime::find_for_workspace(&deployment.db().pool, workspace.id).await? {
+            Some(time) => format!(
+                "{} active over {} wall-clock",
+                format_seconds(time.active_seconds),
+                format_seconds(time.wall_clock_seconds)
+            ),
+            None => "unknown".to_string(),
+        };
 
     // Replace placeholders in prompt
     let prompt = prompt_template
         .replace("{pr_number}", &pr_number.to_string())
-        .replace("{pr_url}", pr_url);
-
-    drop(config); // Release the lock before async operations
+        .replace("{pr_url}", pr_url)
+        .replace("{time_spent}", &time_spent);
 
     // Get or create a session for this follow-up
     let session =
@@ -188,6 +204,7 @@ pub async fn create_github_pr(
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<CreateGitHubPrRequest>,
 ) -> Result<ResponseJson<ApiResponse<String, CreatePrError>>, ApiError> {
+    ensure_not_dry_run(&deployment, &workspace).await?;
     let pool = &deployment.db().pool;
 
     let workspace_repo =
@@ -278,16 +295,26 @@ pub async fn create_github_pr(
     } else {
         target_branch
     };
+    // Use GitService to get the remote URL, then create GitHubRepoInfo
+    let repo_info = deployment.git().get_github_repo_info(&repo_path)?;
+
+    // Tasks imported from an issue close it when the PR is merged
+    let body = match GitHubIssueLink::find_by_task_id(pool, workspace.task_id).await? {
+        Some(link) => Some(github_issue_sync::with_closing_reference(
+            request.body.as_deref(),
+            &github_issue_sync::closing_reference(&link, &repo_info),
+        )),
+        None => request.body.clone(),
+    };
+
     // Create the PR using GitHub service
     let pr_request = CreatePrRequest {
         title: request.title.clone(),
-        body: request.body.clone(),
+        body,
         head_branch: workspace.branch.clone(),
         base_branch: norm_target_branch_name.clone(),
         draft: request.draft,
     };
-    // Use GitService to get the remote URL, then create GitHubRepoInfo
-    let repo_info = deployment.git().get_github_repo_info(&repo_path)?;
 
     // Use GitHubService to create the PR
     let github_service = GitHubService::new()?;
@@ -311,6 +338,28 @@ pub async fn create_github_pr(
             if let Err(e) = utils::browser::open_browser(&pr_info.url).await {
                 tracing::warn!("Failed to open PR in browser: {}", e);
             }
+
+            if let Some(task) = workspace.parent_task(pool).await?
+                && let Some(project) = task.parent_project(pool).await?
+            {
+                let context = NotificationContext::new(NotificationEvent::PrOpened)
+                    .with_task(project.id, task.id)
+                    .with_workspace(workspace.id)
+                    .with_names(&project.name, &task.title);
+                let notification_service = deployment.container().notification_service();
+                let locale = notification_service.locale().await;
+                notification_service
+                    .notify(
+                        &context,
+                        &i18n::t(
+                            locale,
+                            "pr-opened-title",
+                            &[("number", pr_info.number.to_string().as_str())],
+                        ),
+                        &format!("{}\n{}", task.title, pr_info.url),
+                    )
+                    .await;
+            }
             deployment
                 .track_if_analytics_allowed(
                     "github_pr_created",
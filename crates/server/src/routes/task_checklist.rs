@@ -0,0 +1,151 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, post, put},
+};
+use db::models::{
+    task::Task,
+    task_checklist_item::{CreateTaskChecklistItem, TaskChecklistItem, UpdateTaskChecklistItem},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::{response::ApiResponse, text::checklist_states};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+fn validate_text(text: &str) -> Result<(), ApiError> {
+    if text.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Checklist item text cannot be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The item, provided it belongs to the task
+async fn find_item(
+    deployment: &DeploymentImpl,
+    task_id: Uuid,
+    item_id: Uuid,
+) -> Result<TaskChecklistItem, ApiError> {
+    TaskChecklistItem::find_by_id(&deployment.db().pool, item_id)
+        .await?
+        .filter(|item| item.task_id == task_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn get_task_checklist(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskChecklistItem>>>, ApiError> {
+    let items = TaskChecklistItem::find_by_task_id(&deployment.db().pool, task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(items)))
+}
+
+pub async fn create_task_checklist_item(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<CreateTaskChecklistItem>,
+) -> Result<ResponseJson<ApiResponse<TaskChecklistItem>>, ApiError> {
+    validate_text(&payload.text)?;
+    let pool = &deployment.db().pool;
+    let task = Task::find_by_id(pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let item =
+        TaskChecklistItem::create(pool, task.id, &payload.text, payload.done.unwrap_or(false))
+            .await?;
+    Ok(ResponseJson(ApiResponse::success(item)))
+}
+
+/// Add the task-list items in the task's description, ticked ones included, to its checklist
+pub async fn create_checklist_from_description(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskChecklistItem>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = Task::find_by_id(pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let states = checklist_states(task.description.as_deref().unwrap_or_default());
+    if states.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Task description has no checklist items".to_string(),
+        ));
+    }
+
+    for (text, done) in &states {
+        TaskChecklistItem::create(pool, task.id, text, *done).await?;
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_checklist_created_from_description",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "item_count": states.len(),
+            }),
+        )
+        .await;
+
+    let items = TaskChecklistItem::find_by_task_id(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(items)))
+}
+
+pub async fn update_task_checklist_item(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, item_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateTaskChecklistItem>,
+) -> Result<ResponseJson<ApiResponse<TaskChecklistItem>>, ApiError> {
+    if let Some(text) = &payload.text {
+        validate_text(text)?;
+    }
+    let item = find_item(&deployment, task_id, item_id).await?;
+    let updated = TaskChecklistItem::update(&deployment.db().pool, &item, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+pub async fn delete_task_checklist_item(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, item_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let item = find_item(&deployment, task_id, item_id).await?;
+    TaskChecklistItem::delete(&deployment.db().pool, item.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ReorderTaskChecklistRequest {
+    /// Item ids in their new order
+    pub item_ids: Vec<Uuid>,
+}
+
+pub async fn reorder_task_checklist(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<ReorderTaskChecklistRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskChecklistItem>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    TaskChecklistItem::reorder(pool, task_id, &payload.item_ids).await?;
+    let items = TaskChecklistItem::find_by_task_id(pool, task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(items)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let inner = Router::new()
+        .route(
+            "/",
+            get(get_task_checklist).post(create_task_checklist_item),
+        )
+        .route("/from-description", post(create_checklist_from_description))
+        .route("/order", put(reorder_task_checklist))
+        .route(
+            "/{item_id}",
+            put(update_task_checklist_item).delete(delete_task_checklist_item),
+        );
+
+    Router::new().nest("/tasks/{task_id}/checklist", inner)
+}
@@ -0,0 +1,183 @@
+use axum::{
+    Json, Router,
+    extract::{
+        Path, State,
+        ws::{WebSocket, WebSocketUpgrade},
+    },
+    response::{IntoResponse, Json as ResponseJson},
+    routing::{get, put},
+};
+use db::models::{
+    task::Task,
+    task_comment::{CreateTaskComment, TaskComment, TaskCommentEdit, UpdateTaskComment},
+};
+use deployment::Deployment;
+use futures_util::{SinkExt, StreamExt, TryStreamExt};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+const DEFAULT_AUTHOR: &str = "user";
+
+fn validate_body(body: &str) -> Result<(), ApiError> {
+    if body.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Comment body cannot be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The comment, provided it belongs to the task
+async fn find_comment(
+    deployment: &DeploymentImpl,
+    task_id: Uuid,
+    comment_id: Uuid,
+) -> Result<TaskComment, ApiError> {
+    TaskComment::find_by_id(&deployment.db().pool, comment_id)
+        .await?
+        .filter(|comment| comment.task_id == task_id)
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))
+}
+
+pub async fn get_task_comments(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskComment>>>, ApiError> {
+    let comments = TaskComment::find_by_task_id(&deployment.db().pool, task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(comments)))
+}
+
+pub async fn create_task_comment(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+    Json(payload): Json<CreateTaskComment>,
+) -> Result<ResponseJson<ApiResponse<TaskComment>>, ApiError> {
+    validate_body(&payload.body)?;
+    let pool = &deployment.db().pool;
+    let task = Task::find_by_id(pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+
+    let author = match payload
+        .author
+        .as_deref()
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+    {
+        Some(author) => author.to_string(),
+        None => deployment
+            .config()
+            .read()
+            .await
+            .github
+            .username
+            .clone()
+            .unwrap_or_else(|| DEFAULT_AUTHOR.to_string()),
+    };
+    let comment = TaskComment::create(pool, task.id, &author, &payload.body).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_comment_created",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(comment)))
+}
+
+pub async fn update_task_comment(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, comment_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateTaskComment>,
+) -> Result<ResponseJson<ApiResponse<TaskComment>>, ApiError> {
+    validate_body(&payload.body)?;
+    let comment = find_comment(&deployment, task_id, comment_id).await?;
+    let updated = TaskComment::update(&deployment.db().pool, &comment, &payload.body).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+pub async fn delete_task_comment(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let comment = find_comment(&deployment, task_id, comment_id).await?;
+    let rows_affected = TaskComment::delete(&deployment.db().pool, comment.id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+/// Earlier versions of the comment, newest first
+pub async fn get_task_comment_history(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskCommentEdit>>>, ApiError> {
+    let comment = find_comment(&deployment, task_id, comment_id).await?;
+    let edits = TaskComment::find_edits(&deployment.db().pool, comment.id).await?;
+    Ok(ResponseJson(ApiResponse::success(edits)))
+}
+
+pub async fn stream_task_comments_ws(
+    ws: WebSocketUpgrade,
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| async move {
+        if let Err(e) = handle_task_comments_ws(socket, deployment, task_id).await {
+            tracing::warn!("task comments WS closed: {}", e);
+        }
+    })
+}
+
+async fn handle_task_comments_ws(
+    socket: WebSocket,
+    deployment: DeploymentImpl,
+    task_id: Uuid,
+) -> anyhow::Result<()> {
+    let mut stream = deployment
+        .events()
+        .stream_task_comments_raw(task_id)
+        .await?
+        .map_ok(|msg| msg.to_ws_message_unchecked());
+
+    let (mut sender, mut receiver) = socket.split();
+
+    // Drain (and ignore) any client->server messages so pings/pongs work
+    tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
+
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(msg) => {
+                if sender.send(msg).await.is_err() {
+                    break; // client disconnected
+                }
+            }
+            Err(e) => {
+                tracing::error!("stream error: {}", e);
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    let inner = Router::new()
+        .route("/", get(get_task_comments).post(create_task_comment))
+        .route("/stream/ws", get(stream_task_comments_ws))
+        .route(
+            "/{comment_id}",
+            put(update_task_comment).delete(delete_task_comment),
+        )
+        .route("/{comment_id}/history", get(get_task_comment_history));
+
+    Router::new().nest("/tasks/{task_id}/comments", inner)
+}
@@ -0,0 +1,121 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::post,
+};
+use db::models::{
+    assignment_rule::TaskSource,
+    label::Label,
+    project::Project,
+    task::{CreateTask, Task},
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    auto_assign,
+    task_import::{self, ImportFormat, ImportMapping, ImportRow},
+};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ImportTasksRequest {
+    pub format: ImportFormat,
+    /// The CSV or JSON text
+    pub content: String,
+    #[serde(default)]
+    #[ts(optional)]
+    pub mapping: Option<ImportMapping>,
+    /// Only validate and return the preview; nothing is created
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Also create rows whose title matches an existing task or an earlier row
+    #[serde(default)]
+    pub include_duplicates: bool,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ImportTasksResponse {
+    pub rows: Vec<ImportRow>,
+    /// Tasks created, in row order; empty for a dry run
+    pub created: Vec<Task>,
+}
+
+/// Load tasks in bulk from CSV or JSON. Rows with errors, and duplicates unless included, are
+/// skipped; the preview lists why.
+pub async fn import_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<ImportTasksRequest>,
+) -> Result<ResponseJson<ApiResponse<ImportTasksResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+
+    let existing = Task::find_by_project_id_with_attempt_status(pool, project.id, true).await?;
+    let existing: Vec<(Uuid, &str)> = existing
+        .iter()
+        .map(|task| (task.id, task.title.as_str()))
+        .collect();
+    let rows = task_import::preview(
+        payload.format,
+        &payload.content,
+        &payload.mapping.unwrap_or_default(),
+        &existing,
+    )?;
+
+    let mut created = Vec::new();
+    if !payload.dry_run {
+        for row in &rows {
+            let Some(imported) = &row.task else {
+                continue;
+            };
+            if row.is_duplicate() && !payload.include_duplicates {
+                continue;
+            }
+            let create = CreateTask {
+                status: Some(imported.status.clone()),
+                priority: Some(imported.priority),
+                due_at: imported.due_at,
+                ..CreateTask::from_title_description(
+                    project.id,
+                    imported.title.clone(),
+                    imported.description.clone(),
+                )
+            };
+            let task = Task::create(pool, &create, Uuid::new_v4()).await?;
+            for name in &imported.labels {
+                let label = Label::find_or_create_by_name(pool, name).await?;
+                Label::add_to_task(pool, task.id, label.id).await?;
+            }
+            auto_assign::apply(pool, &task, TaskSource::Import).await?;
+            created.push(task);
+        }
+
+        deployment
+            .track_if_analytics_allowed(
+                "tasks_imported",
+                serde_json::json!({
+                    "project_id": project.id.to_string(),
+                    "format": payload.format,
+                    "rows": rows.len(),
+                    "created": created.len(),
+                }),
+            )
+            .await;
+    }
+
+    Ok(ResponseJson(ApiResponse::success(ImportTasksResponse {
+        rows,
+        created,
+    })))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/projects/{project_id}/import", post(import_tasks))
+}
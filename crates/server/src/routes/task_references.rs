@@ -0,0 +1,30 @@
+use axum::{
+    Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::{task::Task, task_reference::TaskReferences};
+use deployment::Deployment;
+use services::services::task_references;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Tasks, files and commits the task's description refers to, and the tasks referring to it
+pub async fn get_task_references(
+    State(deployment): State<DeploymentImpl>,
+    Path(task_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<TaskReferences>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = Task::find_by_id(pool, task_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    let references = task_references::find_for_task(pool, deployment.git(), &task).await?;
+    Ok(ResponseJson(ApiResponse::success(references)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/tasks/{task_id}/references", get(get_task_references))
+}
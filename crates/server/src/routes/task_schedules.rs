@@ -0,0 +1,145 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use chrono::Utc;
+use db::models::{
+    task::Task,
+    task_schedule::{CreateTaskSchedule, TaskSchedule, UpdateTaskSchedule},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::recurrence;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_task_schedule_middleware};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct TaskScheduleQuery {
+    pub project_id: Option<Uuid>,
+}
+
+pub async fn get_task_schedules(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskScheduleQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskSchedule>>>, ApiError> {
+    let schedules = TaskSchedule::find_all(&deployment.db().pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(schedules)))
+}
+
+pub async fn create_task_schedule(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskSchedule>,
+) -> Result<ResponseJson<ApiResponse<TaskSchedule>>, ApiError> {
+    if payload.executor_profile_id.is_some() && payload.repos.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one repository is required to start scheduled attempts".to_string(),
+        ));
+    }
+    let now = Utc::now();
+    let next_run_at = recurrence::next_run(&payload.rule, payload.utc_offset.as_deref(), now, now)?;
+    let schedule = TaskSchedule::create(
+        &deployment.db().pool,
+        &payload,
+        Uuid::new_v4(),
+        now,
+        next_run_at,
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_schedule_created",
+            serde_json::json!({
+                "schedule_id": schedule.id.to_string(),
+                "project_id": schedule.project_id.to_string(),
+                "starts_attempt": schedule.executor_profile_id.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(schedule)))
+}
+
+pub async fn get_task_schedule(
+    Extension(schedule): Extension<TaskSchedule>,
+) -> Result<ResponseJson<ApiResponse<TaskSchedule>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(schedule)))
+}
+
+pub async fn update_task_schedule(
+    Extension(schedule): Extension<TaskSchedule>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateTaskSchedule>,
+) -> Result<ResponseJson<ApiResponse<TaskSchedule>>, ApiError> {
+    let starts_attempt = !payload.clear_executor_profile_id.unwrap_or(false)
+        && (payload.executor_profile_id.is_some() || schedule.executor_profile_id.is_some());
+    let has_repos = payload
+        .repos
+        .as_ref()
+        .map_or(!schedule.repos.is_empty(), |repos| !repos.is_empty());
+    if starts_attempt && !has_repos {
+        return Err(ApiError::BadRequest(
+            "At least one repository is required to start scheduled attempts".to_string(),
+        ));
+    }
+
+    let rule = payload.rule.as_deref().unwrap_or(&schedule.rule);
+    let utc_offset = match payload.utc_offset.as_deref() {
+        Some(s) if s.trim().is_empty() => None,
+        Some(s) => Some(s),
+        None => schedule.utc_offset.as_deref(),
+    };
+    // Recomputed from now, so re-enabling a schedule doesn't fire the runs it missed
+    let next_run_at = recurrence::next_run(rule, utc_offset, schedule.created_at, Utc::now())?;
+    let updated =
+        TaskSchedule::update(&deployment.db().pool, &schedule, &payload, next_run_at).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+pub async fn delete_task_schedule(
+    Extension(schedule): Extension<TaskSchedule>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = TaskSchedule::delete(&deployment.db().pool, schedule.id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+/// Run the schedule now, outside its rule
+pub async fn run_task_schedule(
+    Extension(schedule): Extension<TaskSchedule>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let task = deployment.run_task_schedule(&schedule, Utc::now()).await?;
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let schedule_router = Router::new()
+        .route(
+            "/",
+            get(get_task_schedule)
+                .put(update_task_schedule)
+                .delete(delete_task_schedule),
+        )
+        .route("/run", post(run_task_schedule))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_task_schedule_middleware,
+        ));
+
+    let inner = Router::new()
+        .route("/", get(get_task_schedules).post(create_task_schedule))
+        .nest("/{schedule_id}", schedule_router);
+
+    Router::new().nest("/task-schedules", inner)
+}
@@ -1,10 +1,10 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf, str::FromStr};
 
 use anyhow;
 use axum::{
     Extension, Json, Router,
     extract::{
-        Query, State,
+        Path, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
     http::StatusCode,
@@ -13,46 +13,201 @@ use axum::{
     routing::{delete, get, post, put},
 };
 use db::models::{
-    image::TaskImage,
-    project::{Project, ProjectError},
+    assignment_rule::{AssignmentRule, TaskRunDefaults, TaskSource},
+    execution_process::ExecutionProcess,
+    execution_usage::{ExecutionUsage, TaskUsage},
+    image::{Image, TaskImage},
+    label::Label,
+    milestone::Milestone,
+    project_column::ProjectColumn,
+    project_repo::ProjectRepo,
     repo::Repo,
-    task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
-    workspace::{CreateWorkspace, Workspace},
+    saved_view::{SavedView, TaskSort, ViewFilters},
+    session::Session,
+    swimlane::{self, Swimlane, SwimlaneKey},
+    task::{
+        CreateTask, DueTasks, Task, TaskPriority, TaskStatus, TaskWithAttemptStatus, UpdateTask,
+    },
+    task_activity::TaskActivity,
+    task_assignee::TaskAssignee,
+    task_attachment::TaskAttachment,
+    task_checklist_item::TaskChecklistItem,
+    task_dependency::{TaskDependencies, TaskDependency},
+    task_relation::{TaskRelation, TaskRelationKind, TaskRelations},
+    task_subtask::{TaskSubtask, TaskSubtasks},
+    task_template::{TaskTemplate, render_template},
+    time_tracking::{AttemptTime, TaskTime},
+    workspace::Workspace,
     workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
 };
 use deployment::Deployment;
-use executors::profile::ExecutorProfileId;
+use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use services::services::{
-    container::ContainerService, share::ShareError, workspace_manager::WorkspaceManager,
+    auto_assign,
+    container::ContainerService,
+    run_queue::AttemptStart,
+    share::ShareError,
+    wip::{self, WipViolation},
+    workspace_manager::WorkspaceManager,
 };
-use sqlx::Error as SqlxError;
+use sqlx::{Error as SqlxError, SqlitePool};
 use ts_rs::TS;
-use utils::{api::oauth::LoginStatus, response::ApiResponse};
+use utils::{api::oauth::LoginStatus, response::ApiResponse, text::checklist_items};
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_task_middleware,
-    routes::task_attempts::WorkspaceRepoInput,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_task_middleware,
+    routes::task_attempts::{
+        WorkspaceRepoInput, check_budget, check_wip_limits, notify_overridden_wip_limits,
+    },
 };
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TaskQuery {
     pub project_id: Uuid,
+    /// Start from a saved view's filters and sort; the other parameters override them
+    pub view_id: Option<Uuid>,
+    /// Comma-separated label names; only tasks carrying all of them are listed
+    pub labels: Option<String>,
+    pub status: Option<TaskStatus>,
+    pub priority: Option<TaskPriority>,
+    pub sort: Option<TaskSort>,
+    /// List only archived tasks instead of the board's
+    pub archived: Option<bool>,
+    /// List only the milestone's tasks
+    pub milestone_id: Option<Uuid>,
+    /// List only tasks assigned to this person or agent
+    pub assignee: Option<String>,
+    /// List only tasks whose latest attempt ran on this executor
+    pub executor: Option<String>,
+    /// List only tasks with this text in the title or description
+    pub text: Option<String>,
+}
+
+/// The project's tasks that pass the query's filters, in its order
+async fn list_tasks(
+    pool: &SqlitePool,
+    query: TaskQuery,
+) -> Result<Vec<TaskWithAttemptStatus>, ApiError> {
+    let view = match query.view_id {
+        Some(view_id) => Some(
+            SavedView::find_by_id(pool, view_id)
+                .await?
+                .filter(|view| view.project_id == query.project_id)
+                .ok_or(ApiError::Database(SqlxError::RowNotFound))?,
+        ),
+        None => None,
+    };
+    let (mut filters, view_sort) = match view {
+        Some(view) => (view.filters.0, view.sort),
+        None => (ViewFilters::default(), None),
+    };
+    if let Some(labels) = &query.labels {
+        filters.labels = labels
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+    if let Some(status) = &query.status {
+        filters.statuses = vec![status.clone()];
+    }
+    filters.priority = query.priority.or(filters.priority);
+    filters.assignee = query.assignee.or(filters.assignee);
+    filters.executor = query.executor.or(filters.executor);
+    filters.text = query.text.or(filters.text);
+
+    let archived = query.archived.unwrap_or(false);
+    let mut tasks =
+        Task::find_by_project_id_with_attempt_status(pool, query.project_id, archived).await?;
+    if archived {
+        tasks.retain(|task| task.archived_at.is_some());
+    }
+
+    for name in &filters.labels {
+        let task_ids = Label::task_ids_for_name(pool, name).await?;
+        tasks.retain(|task| task_ids.contains(&task.id));
+    }
+    if !filters.statuses.is_empty() {
+        tasks.retain(|task| filters.statuses.contains(&task.status));
+    }
+    if let Some(priority) = filters.priority {
+        tasks.retain(|task| task.priority == priority);
+    }
+    if let Some(milestone_id) = query.milestone_id {
+        let task_ids = Milestone::task_ids(pool, milestone_id).await?;
+        tasks.retain(|task| task_ids.contains(&task.id));
+    }
+    if let Some(assignee) = filters.assignee.as_deref().map(str::trim) {
+        let task_ids = TaskAssignee::task_ids_for_name(pool, assignee).await?;
+        tasks.retain(|task| task_ids.contains(&task.id));
+    }
+    tasks.retain(|task| {
+        filters.matches_executor(&task.executor)
+            && filters.matches_text(&task.title, task.description.as_deref())
+    });
+    if let Some(TaskSort::Priority) = query.sort.or(view_sort) {
+        // Stable, so the newest-first order holds within each priority
+        tasks.sort_by_key(|task| task.priority);
+    }
+    Ok(tasks)
 }
 
 pub async fn get_tasks(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<TaskQuery>,
 ) -> Result<ResponseJson<ApiResponse<Vec<TaskWithAttemptStatus>>>, ApiError> {
-    let tasks =
-        Task::find_by_project_id_with_attempt_status(&deployment.db().pool, query.project_id)
-            .await?;
-
+    let tasks = list_tasks(&deployment.db().pool, query).await?;
     Ok(ResponseJson(ApiResponse::success(tasks)))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SwimlaneQuery {
+    pub by: SwimlaneKey,
+}
+
+/// The tasks `get_tasks` lists, bucketed into swimlanes by label, assignee or epic
+pub async fn get_task_swimlanes(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskQuery>,
+    Query(lanes): Query<SwimlaneQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<Swimlane>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project_id = query.project_id;
+    let tasks = list_tasks(pool, query).await?;
+    let lanes = swimlane::swimlanes(pool, project_id, lanes.by, tasks).await?;
+    Ok(ResponseJson(ApiResponse::success(lanes)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DueTasksQuery {
+    pub project_id: Option<Uuid>,
+    /// How far ahead counts as due soon; defaults to 24 hours
+    pub within_hours: Option<u32>,
+    /// Only tasks assigned to this person or agent
+    pub assignee: Option<String>,
+}
+
+pub async fn get_due_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<DueTasksQuery>,
+) -> Result<ResponseJson<ApiResponse<DueTasks>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let within = chrono::Duration::hours(query.within_hours.unwrap_or(24).into());
+    let mut due = Task::find_due(pool, query.project_id, chrono::Utc::now(), within).await?;
+    if let Some(assignee) = query.assignee.as_deref().map(str::trim) {
+        let task_ids = TaskAssignee::task_ids_for_name(pool, assignee).await?;
+        due.overdue.retain(|task| task_ids.contains(&task.id));
+        due.due_soon.retain(|task| task_ids.contains(&task.id));
+    }
+    Ok(ResponseJson(ApiResponse::success(due)))
+}
+
 pub async fn stream_tasks_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -124,6 +279,13 @@ pub async fn create_task(
     if let Some(image_ids) = &payload.image_ids {
         TaskImage::associate_many_dedup(&deployment.db().pool, task.id, image_ids).await?;
     }
+    auto_assign::apply_with_profile(
+        &deployment.db().pool,
+        &task,
+        TaskSource::Manual,
+        payload.executor_profile_id.as_ref(),
+    )
+    .await?;
 
     deployment
         .track_if_analytics_allowed(
@@ -145,6 +307,9 @@ pub struct CreateAndStartTaskRequest {
     pub task: CreateTask,
     pub executor_profile_id: ExecutorProfileId,
     pub repos: Vec<WorkspaceRepoInput>,
+    /// Start even though a column or executor is at its WIP limit
+    #[ts(optional)]
+    pub ignore_wip_limits: Option<bool>,
 }
 
 pub async fn create_task_and_start(
@@ -158,6 +323,15 @@ pub async fn create_task_and_start(
     }
 
     let pool = &deployment.db().pool;
+    check_budget(&deployment, payload.task.project_id).await?;
+    let violations = check_wip_limits(
+        &deployment,
+        payload.task.project_id,
+        None,
+        &payload.executor_profile_id,
+        payload.ignore_wip_limits,
+    )
+    .await?;
 
     let task_id = Uuid::new_v4();
     let task = Task::create(pool, &payload.task, task_id).await?;
@@ -165,6 +339,14 @@ pub async fn create_task_and_start(
     if let Some(image_ids) = &payload.task.image_ids {
         TaskImage::associate_many_dedup(pool, task.id, image_ids).await?;
     }
+    // Later attempts default to the profile this one starts with
+    auto_assign::apply_with_profile(
+        pool,
+        &task,
+        TaskSource::Manual,
+        Some(&payload.executor_profile_id),
+    )
+    .await?;
 
     deployment
         .track_if_analytics_allowed(
@@ -178,33 +360,6 @@ pub async fn create_task_and_start(
         )
         .await;
 
-    let project = Project::find_by_id(pool, task.project_id)
-        .await?
-        .ok_or(ProjectError::ProjectNotFound)?;
-
-    let attempt_id = Uuid::new_v4();
-    let git_branch_name = deployment
-        .container()
-        .git_branch_from_workspace(&attempt_id, &task.title)
-        .await;
-
-    let agent_working_dir = project
-        .default_agent_working_dir
-        .as_ref()
-        .filter(|dir: &&String| !dir.is_empty())
-        .cloned();
-
-    let workspace = Workspace::create(
-        pool,
-        &CreateWorkspace {
-            branch: git_branch_name,
-            agent_working_dir,
-        },
-        attempt_id,
-        task.id,
-    )
-    .await?;
-
     let workspace_repos: Vec<CreateWorkspaceRepo> = payload
         .repos
         .iter()
@@ -213,14 +368,17 @@ pub async fn create_task_and_start(
             target_branch: r.target_branch.clone(),
         })
         .collect();
-    WorkspaceRepo::create_many(&deployment.db().pool, workspace.id, &workspace_repos).await?;
+    let workspace = deployment
+        .container()
+        .create_task_workspace(&task, &workspace_repos)
+        .await?;
 
     let is_attempt_running = deployment
-        .container()
-        .start_workspace(&workspace, payload.executor_profile_id.clone())
+        .start_or_queue_attempt(&workspace, payload.executor_profile_id.clone())
         .await
         .inspect_err(|err| tracing::error!("Failed to start task attempt: {}", err))
-        .is_ok();
+        .is_ok_and(|start| matches!(start, AttemptStart::Started(_)));
+    notify_overridden_wip_limits(&deployment, &task, &violations).await;
     deployment
         .track_if_analytics_allowed(
             "task_attempt_started",
@@ -241,11 +399,367 @@ pub async fn create_task_and_start(
     Ok(ResponseJson(ApiResponse::success(TaskWithAttemptStatus {
         task,
         has_in_progress_attempt: is_attempt_running,
+        has_paused_attempt: false,
         last_attempt_failed: false,
         executor: payload.executor_profile_id.executor.to_string(),
+        checklist_total: 0,
+        checklist_done: 0,
     })))
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateTaskFromTemplateRequest {
+    pub template_id: Uuid,
+    pub project_id: Uuid,
+    /// Values for the template's `{{variable}}` placeholders
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    /// Start an attempt once the task is created
+    #[serde(default)]
+    pub start: bool,
+    /// Overrides the template's executor profile
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    /// Overrides the template's base branch with explicit repos
+    pub repos: Option<Vec<WorkspaceRepoInput>>,
+    /// Start even though a column or executor is at its WIP limit
+    #[ts(optional)]
+    pub ignore_wip_limits: Option<bool>,
+}
+
+fn render_or_bad_request(
+    template: &str,
+    variables: &HashMap<String, String>,
+) -> Result<String, ApiError> {
+    render_template(template, variables).map_err(|missing| {
+        ApiError::BadRequest(format!(
+            "Missing template variables: {}",
+            missing.join(", ")
+        ))
+    })
+}
+
+pub async fn create_task_from_template(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskFromTemplateRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskWithAttemptStatus>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let template = TaskTemplate::find_by_id(pool, payload.template_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Task template not found".to_string()))?;
+    if template
+        .project_id
+        .is_some_and(|project_id| project_id != payload.project_id)
+    {
+        return Err(ApiError::BadRequest(
+            "Task template belongs to another project".to_string(),
+        ));
+    }
+
+    let title = render_or_bad_request(&template.title_pattern, &payload.variables)?;
+    let description = template
+        .description
+        .as_deref()
+        .map(|description| render_or_bad_request(description, &payload.variables))
+        .transpose()?;
+
+    let requested_profile = payload
+        .executor_profile_id
+        .or(template.executor_profile_id.map(|id| id.0));
+
+    // Resolve what to start with before creating anything
+    let start = if payload.start {
+        let rules = AssignmentRule::find_available(pool, Some(payload.project_id)).await?;
+        let assigned =
+            auto_assign::resolve(&rules, &title, description.as_deref(), TaskSource::Template)
+                .unwrap_or_default();
+        let executor_profile_id = match requested_profile.clone().or(assigned.executor_profile_id) {
+            Some(executor_profile_id) => executor_profile_id,
+            None => {
+                deployment
+                    .config()
+                    .read()
+                    .await
+                    .for_project(Some(payload.project_id))
+                    .executor_profile
+            }
+        };
+        let base_branch = template.base_branch.clone().or(assigned.base_branch);
+        let repos: Vec<CreateWorkspaceRepo> = match (payload.repos, &base_branch) {
+            (Some(repos), _) => repos
+                .into_iter()
+                .map(|r| CreateWorkspaceRepo {
+                    repo_id: r.repo_id,
+                    target_branch: r.target_branch,
+                })
+                .collect(),
+            (None, Some(base_branch)) => ProjectRepo::find_by_project_id(pool, payload.project_id)
+                .await?
+                .into_iter()
+                .map(|project_repo| CreateWorkspaceRepo {
+                    repo_id: project_repo.repo_id,
+                    target_branch: base_branch.clone(),
+                })
+                .collect(),
+            (None, None) => Vec::new(),
+        };
+        if repos.is_empty() {
+            return Err(ApiError::BadRequest(
+                "Starting needs repos, or a base branch from the template or an assignment rule"
+                    .to_string(),
+            ));
+        }
+        check_budget(&deployment, payload.project_id).await?;
+        let violations = check_wip_limits(
+            &deployment,
+            payload.project_id,
+            None,
+            &executor_profile_id,
+            payload.ignore_wip_limits,
+        )
+        .await?;
+        Some((executor_profile_id, repos, violations))
+    } else {
+        None
+    };
+
+    let create = CreateTask::from_title_description(payload.project_id, title, description);
+    let task = Task::create(pool, &create, Uuid::new_v4()).await?;
+    for name in template
+        .labels
+        .iter()
+        .filter(|name| !name.trim().is_empty())
+    {
+        let label = Label::find_or_create_by_name(pool, name).await?;
+        Label::add_to_task(pool, task.id, label.id).await?;
+    }
+    auto_assign::apply_with_profile(
+        pool,
+        &task,
+        TaskSource::Template,
+        requested_profile.as_ref(),
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_created_from_template",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "template_id": template.id.to_string(),
+                "project_id": task.project_id,
+                "started": start.is_some(),
+            }),
+        )
+        .await;
+
+    let Some((executor_profile_id, repos, violations)) = start else {
+        return Ok(ResponseJson(ApiResponse::success(TaskWithAttemptStatus {
+            task,
+            has_in_progress_attempt: false,
+            has_paused_attempt: false,
+            last_attempt_failed: false,
+            executor: String::new(),
+            checklist_total: 0,
+            checklist_done: 0,
+        })));
+    };
+
+    let task = start_new_task(&deployment, task, &repos, executor_profile_id, &violations).await?;
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
+/// Start the first attempt of a task that was just created
+async fn start_new_task(
+    deployment: &DeploymentImpl,
+    task: Task,
+    repos: &[CreateWorkspaceRepo],
+    executor_profile_id: ExecutorProfileId,
+    violations: &[WipViolation],
+) -> Result<TaskWithAttemptStatus, ApiError> {
+    let workspace = deployment
+        .container()
+        .create_task_workspace(&task, repos)
+        .await?;
+    let is_attempt_running = deployment
+        .start_or_queue_attempt(&workspace, executor_profile_id.clone())
+        .await
+        .inspect_err(|err| tracing::error!("Failed to start task attempt: {}", err))
+        .is_ok_and(|start| matches!(start, AttemptStart::Started(_)));
+    notify_overridden_wip_limits(deployment, &task, violations).await;
+
+    let task = Task::find_by_id(&deployment.db().pool, task.id)
+        .await?
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    Ok(TaskWithAttemptStatus {
+        task,
+        has_in_progress_attempt: is_attempt_running,
+        has_paused_attempt: false,
+        last_attempt_failed: false,
+        executor: executor_profile_id.executor.to_string(),
+        checklist_total: 0,
+        checklist_done: 0,
+    })
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CloneTaskRequest {
+    /// Defaults to the original's title
+    pub title: Option<String>,
+    /// Start an attempt on the clone once it's created
+    #[serde(default)]
+    pub start: bool,
+    /// Overrides the executor profile of the original's latest attempt
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    /// Base the attempt on the branch of the original's latest attempt instead of that attempt's
+    /// base branch, to build on its work
+    #[serde(default)]
+    pub from_branch: bool,
+    /// Overrides the repos and branches of the original's latest attempt
+    pub repos: Option<Vec<WorkspaceRepoInput>>,
+    /// Start even though a column or executor is at its WIP limit
+    #[ts(optional)]
+    pub ignore_wip_limits: Option<bool>,
+}
+
+/// Copy a task, with its labels, images and checklist, to respin a failed or variant piece of
+/// work; optionally start it the way its latest attempt was started
+pub async fn clone_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CloneTaskRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskWithAttemptStatus>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let title = payload.title.unwrap_or_else(|| task.title.clone());
+    if title.trim().is_empty() {
+        return Err(ApiError::BadRequest("Title cannot be empty".to_string()));
+    }
+
+    // Resolve what to start with before creating anything
+    let start = if payload.start {
+        let latest_workspace = Workspace::fetch_all(pool, Some(task.id))
+            .await?
+            .into_iter()
+            .next();
+        let executor_profile_id = match payload.executor_profile_id {
+            Some(executor_profile_id) => Some(executor_profile_id),
+            None => match &latest_workspace {
+                Some(workspace) => {
+                    match Session::find_latest_by_workspace_id(pool, workspace.id).await? {
+                        Some(session) => {
+                            ExecutionProcess::latest_executor_profile_for_session(pool, session.id)
+                                .await
+                                .ok()
+                        }
+                        None => None,
+                    }
+                }
+                None => None,
+            },
+        };
+        let executor_profile_id = match executor_profile_id {
+            Some(executor_profile_id) => executor_profile_id,
+            None => {
+                deployment
+                    .config()
+                    .read()
+                    .await
+                    .for_project(Some(task.project_id))
+                    .executor_profile
+            }
+        };
+        let repos: Vec<CreateWorkspaceRepo> = match (payload.repos, &latest_workspace) {
+            (Some(repos), _) => repos
+                .into_iter()
+                .map(|r| CreateWorkspaceRepo {
+                    repo_id: r.repo_id,
+                    target_branch: r.target_branch,
+                })
+                .collect(),
+            (None, Some(workspace)) => {
+                WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, workspace.id)
+                    .await?
+                    .into_iter()
+                    .map(|r| CreateWorkspaceRepo {
+                        repo_id: r.repo.id,
+                        target_branch: if payload.from_branch {
+                            workspace.branch.clone()
+                        } else {
+                            r.target_branch
+                        },
+                    })
+                    .collect()
+            }
+            (None, None) => Vec::new(),
+        };
+        if repos.is_empty() {
+            return Err(ApiError::BadRequest(
+                "Starting needs repos, or an earlier attempt to take them from".to_string(),
+            ));
+        }
+        check_budget(&deployment, task.project_id).await?;
+        let violations = check_wip_limits(
+            &deployment,
+            task.project_id,
+            None,
+            &executor_profile_id,
+            payload.ignore_wip_limits,
+        )
+        .await?;
+        Some((executor_profile_id, repos, violations))
+    } else {
+        None
+    };
+
+    let create = CreateTask {
+        priority: Some(task.priority),
+        parent_workspace_id: task.parent_workspace_id,
+        ..CreateTask::from_title_description(task.project_id, title, task.description.clone())
+    };
+    let clone = Task::create(pool, &create, Uuid::new_v4()).await?;
+    let image_ids: Vec<Uuid> = Image::find_by_task_id(pool, task.id)
+        .await?
+        .into_iter()
+        .map(|image| image.id)
+        .collect();
+    TaskImage::associate_many_dedup(pool, clone.id, &image_ids).await?;
+    for label in Label::find_for_task(pool, task.id).await? {
+        Label::add_to_task(pool, clone.id, label.id).await?;
+    }
+    TaskAssignee::copy_to_task(pool, task.id, clone.id).await?;
+    for item in TaskChecklistItem::find_by_task_id(pool, task.id).await? {
+        TaskChecklistItem::create(pool, clone.id, &item.text, false).await?;
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_cloned",
+            serde_json::json!({
+                "task_id": clone.id.to_string(),
+                "source_task_id": task.id.to_string(),
+                "project_id": clone.project_id,
+                "started": start.is_some(),
+                "from_branch": payload.from_branch,
+            }),
+        )
+        .await;
+
+    let Some((executor_profile_id, repos, violations)) = start else {
+        return Ok(ResponseJson(ApiResponse::success(TaskWithAttemptStatus {
+            task: clone,
+            has_in_progress_attempt: false,
+            has_paused_attempt: false,
+            last_attempt_failed: false,
+            executor: String::new(),
+            checklist_total: 0,
+            checklist_done: 0,
+        })));
+    };
+
+    let clone =
+        start_new_task(&deployment, clone, &repos, executor_profile_id, &violations).await?;
+    Ok(ResponseJson(ApiResponse::success(clone)))
+}
+
 pub async fn update_task(
     Extension(existing_task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
@@ -262,6 +776,12 @@ pub async fn update_task(
         None => existing_task.description,      // Field omitted = keep existing
     };
     let status = payload.status.unwrap_or(existing_task.status);
+    let priority = payload.priority.unwrap_or(existing_task.priority);
+    let due_at = if payload.clear_due_at.unwrap_or(false) {
+        None
+    } else {
+        payload.due_at.or(existing_task.due_at)
+    };
     let parent_workspace_id = payload
         .parent_workspace_id
         .or(existing_task.parent_workspace_id);
@@ -273,6 +793,8 @@ pub async fn update_task(
         title,
         description,
         status,
+        priority,
+        due_at,
         parent_workspace_id,
     )
     .await?;
@@ -293,6 +815,121 @@ pub async fn update_task(
     Ok(ResponseJson(ApiResponse::success(task)))
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct MoveTaskToColumnRequest {
+    pub column_id: Uuid,
+    /// Move even though the column is at its WIP limit
+    #[ts(optional)]
+    pub ignore_wip_limit: Option<bool>,
+}
+
+/// Move a task to another board column, taking on the column's status. Overriding the column's
+/// WIP limit is reported through notifications.
+pub async fn move_task_to_column(
+    Extension(existing_task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<MoveTaskToColumnRequest>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    ensure_shared_task_auth(&existing_task, &deployment).await?;
+
+    let pool = &deployment.db().pool;
+    let column = ProjectColumn::find_by_id(pool, payload.column_id)
+        .await?
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    let violation =
+        wip::column_violation(&column).filter(|_| existing_task.column_id != Some(column.id));
+    column
+        .move_task(
+            pool,
+            &existing_task,
+            payload.ignore_wip_limit.unwrap_or(false),
+        )
+        .await?;
+    let task = Task::find_by_id(pool, existing_task.id)
+        .await?
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+
+    if let Some(violation) = violation
+        && let Err(e) = deployment
+            .notify_wip_limit_exceeded(&task, &[violation], true)
+            .await
+    {
+        tracing::warn!(
+            "Failed to notify about WIP limit for task {}: {}",
+            task.id,
+            e
+        );
+    }
+
+    if task.shared_task_id.is_some() {
+        let Ok(publisher) = deployment.share_publisher() else {
+            return Err(ShareError::MissingConfig("share publisher unavailable").into());
+        };
+        publisher.update_shared_task(&task).await?;
+    }
+
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
+/// Take a task off the board without deleting it; it stays searchable and can be restored
+pub async fn archive_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    if deployment
+        .container()
+        .has_running_processes(task.id)
+        .await?
+    {
+        return Err(ApiError::Conflict(
+            "Task has running execution processes. Stop them before archiving the task."
+                .to_string(),
+        ));
+    }
+
+    let pool = &deployment.db().pool;
+    Task::archive(pool, task.id, chrono::Utc::now()).await?;
+    let task = Task::find_by_id(pool, task.id)
+        .await?
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_archived",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id,
+                "status": task.status,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
+pub async fn restore_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let pool = &deployment.db().pool;
+    Task::restore(pool, task.id).await?;
+    let task = Task::find_by_id(pool, task.id)
+        .await?
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_restored",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
 async fn ensure_shared_task_auth(
     existing_task: &Task,
     deployment: &local_deployment::LocalDeployment,
@@ -324,6 +961,7 @@ pub async fn delete_task(
     }
 
     let pool = &deployment.db().pool;
+    let parent_task_id = TaskSubtask::find_parent_id(pool, task.id).await?;
 
     // Gather task attempts data needed for background cleanup
     let attempts = Workspace::fetch_all(pool, Some(task.id))
@@ -335,6 +973,9 @@ pub async fn delete_task(
 
     let repositories = WorkspaceRepo::find_unique_repos_for_task(pool, task.id).await?;
 
+    // Attachment rows go with the task, but their stored files need removing separately
+    let attachments = TaskAttachment::find_by_task_id(pool, task.id).await?;
+
     // Collect workspace directories that need cleanup
     let workspace_dirs: Vec<PathBuf> = attempts
         .iter()
@@ -370,6 +1011,10 @@ pub async fn delete_task(
     // Commit the transaction - if this fails, all changes are rolled back
     tx.commit().await?;
 
+    if let Some(parent_task_id) = parent_task_id {
+        TaskSubtask::refresh_status(pool, parent_task_id).await?;
+    }
+
     if total_children_affected > 0 {
         tracing::info!(
             "Nullified {} child task references before deleting task {}",
@@ -391,6 +1036,8 @@ pub async fn delete_task(
 
     let task_id = task.id;
     let pool = pool.clone();
+    let attachment_service = deployment.attachment().clone();
+    let attachment_config = deployment.config().read().await.attachments.clone();
     tokio::spawn(async move {
         tracing::info!(
             "Starting background cleanup for task {} ({} workspaces, {} repos)",
@@ -411,6 +1058,20 @@ pub async fn delete_task(
             }
         }
 
+        for attachment in &attachments {
+            if let Err(e) = attachment_service
+                .delete_file(&attachment_config, attachment)
+                .await
+            {
+                tracing::error!(
+                    "Failed to delete attachment {} for task {}: {}",
+                    attachment.id,
+                    task_id,
+                    e
+                );
+            }
+        }
+
         match Repo::delete_orphaned(&pool).await {
             Ok(count) if count > 0 => {
                 tracing::info!("Deleted {} orphaned repo records", count);
@@ -460,11 +1121,441 @@ pub async fn share_task(
     })))
 }
 
+pub async fn get_task_usage(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskUsage>>, ApiError> {
+    let usage = ExecutionUsage::find_for_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(usage)))
+}
+
+pub async fn get_task_time(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskTime>>, ApiError> {
+    let time = AttemptTime::find_for_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(time)))
+}
+
+const DEFAULT_ACTIVITY_PAGE_SIZE: i64 = 50;
+const MAX_ACTIVITY_PAGE_SIZE: i64 = 200;
+
+#[derive(Debug, Deserialize, TS)]
+pub struct TaskActivityQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// The task's audit history, most recent first
+pub async fn get_task_activity(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskActivityQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskActivity>>>, ApiError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_ACTIVITY_PAGE_SIZE)
+        .clamp(1, MAX_ACTIVITY_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let activity =
+        TaskActivity::find_by_task_id(&deployment.db().pool, task.id, limit, offset).await?;
+    Ok(ResponseJson(ApiResponse::success(activity)))
+}
+
+pub async fn get_task_labels(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Label>>>, ApiError> {
+    let labels = Label::find_for_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct SetTaskLabelsRequest {
+    /// Replaces the task's labels
+    pub label_ids: Vec<Uuid>,
+}
+
+pub async fn set_task_labels(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetTaskLabelsRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<Label>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    for label_id in &payload.label_ids {
+        if Label::find_by_id(pool, *label_id).await?.is_none() {
+            return Err(ApiError::BadRequest(format!("Label {label_id} not found")));
+        }
+    }
+    Label::set_for_task(pool, task.id, &payload.label_ids).await?;
+    let labels = Label::find_for_task(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+pub async fn get_task_assignees(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskAssignee>>>, ApiError> {
+    let assignees = TaskAssignee::find_for_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(assignees)))
+}
+
+/// Executor profile and base branch requested or picked by the assignment rules when the task
+/// was created, to preselect when starting an attempt
+pub async fn get_task_run_defaults(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<TaskRunDefaults>>>, ApiError> {
+    let defaults = TaskRunDefaults::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(defaults)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct SetTaskAssigneesRequest {
+    /// Names or handles of the people who own the task
+    pub humans: Vec<String>,
+    /// Executor that runs the task's attempts, e.g. `CLAUDE_CODE`
+    pub agent: Option<String>,
+}
+
+/// Replace the task's human assignees and agent
+pub async fn set_task_assignees(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetTaskAssigneesRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskAssignee>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let humans: Vec<String> = payload
+        .humans
+        .iter()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+    let agent = match payload.agent.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => {
+            let normalized = name.replace('-', "_").to_ascii_uppercase();
+            let executor = BaseCodingAgent::from_str(&normalized)
+                .map_err(|_| ApiError::BadRequest(format!("Unknown executor '{name}'")))?;
+            Some(executor.to_string())
+        }
+        _ => None,
+    };
+    TaskAssignee::set_for_task(pool, task.id, &humans, agent.as_deref()).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_assignees_set",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "humans": humans.len(),
+                "agent": agent,
+            }),
+        )
+        .await;
+
+    let assignees = TaskAssignee::find_for_task(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(assignees)))
+}
+
+pub async fn get_task_dependencies(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskDependencies>>, ApiError> {
+    let dependencies = TaskDependency::find_for_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(dependencies)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct AddTaskDependencyRequest {
+    /// Task that must be done before this one can start
+    pub blocker_id: Uuid,
+}
+
+pub async fn add_task_dependency(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<AddTaskDependencyRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskDependencies>>, ApiError> {
+    let pool = &deployment.db().pool;
+    TaskDependency::create(pool, task.id, payload.blocker_id).await?;
+    let dependencies = TaskDependency::find_for_task(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(dependencies)))
+}
+
+pub async fn remove_task_dependency(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, blocker_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<TaskDependencies>>, ApiError> {
+    let pool = &deployment.db().pool;
+    TaskDependency::delete(pool, task_id, blocker_id).await?;
+    let dependencies = TaskDependency::find_for_task(pool, task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(dependencies)))
+}
+
+pub async fn get_task_relations(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskRelations>>, ApiError> {
+    let relations = TaskRelation::find_for_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(relations)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct AddTaskRelationRequest {
+    pub related_id: Uuid,
+    /// How this task relates to the other, e.g. `duplicates` when this task is the duplicate
+    pub kind: TaskRelationKind,
+    /// Cancel the duplicate, or the task being superseded, once linked
+    #[serde(default)]
+    #[ts(optional)]
+    pub close: Option<bool>,
+}
+
+pub async fn add_task_relation(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<AddTaskRelationRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskRelations>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let closed_id = match (payload.kind, payload.close.unwrap_or(false)) {
+        (_, false) => None,
+        (TaskRelationKind::Duplicates, true) => Some(task.id),
+        (TaskRelationKind::Supersedes, true) => Some(payload.related_id),
+        (TaskRelationKind::RelatesTo, true) => {
+            return Err(ApiError::BadRequest(
+                "Only a duplicate or superseded task can be closed".to_string(),
+            ));
+        }
+    };
+    TaskRelation::create(pool, task.id, payload.related_id, payload.kind).await?;
+    if let Some(closed_id) = closed_id {
+        Task::update_status(pool, closed_id, TaskStatus::Cancelled).await?;
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_relation_added",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "kind": payload.kind,
+                "closed": closed_id.is_some(),
+            }),
+        )
+        .await;
+
+    let relations = TaskRelation::find_for_task(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(relations)))
+}
+
+pub async fn remove_task_relation(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, related_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<TaskRelations>>, ApiError> {
+    let pool = &deployment.db().pool;
+    TaskRelation::delete(pool, task_id, related_id).await?;
+    let relations = TaskRelation::find_for_task(pool, task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(relations)))
+}
+
+pub async fn get_task_subtasks(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskSubtasks>>, ApiError> {
+    let subtasks = TaskSubtask::find_for_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(subtasks)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateSubtaskRequest {
+    pub title: String,
+    pub description: Option<String>,
+}
+
+pub async fn create_subtask(
+    Extension(parent): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateSubtaskRequest>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let create =
+        CreateTask::from_title_description(parent.project_id, payload.title, payload.description);
+    let task = Task::create(pool, &create, Uuid::new_v4()).await?;
+    TaskSubtask::set_parent(pool, task.id, Some(parent.id)).await?;
+    auto_assign::apply(pool, &task, TaskSource::Manual).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "subtask_created",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "parent_task_id": parent.id.to_string(),
+                "project_id": task.project_id,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ReorderSubtasksRequest {
+    /// Subtask ids in their new order
+    pub task_ids: Vec<Uuid>,
+}
+
+pub async fn reorder_subtasks(
+    Extension(parent): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ReorderSubtasksRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskSubtasks>>, ApiError> {
+    let pool = &deployment.db().pool;
+    TaskSubtask::reorder(pool, parent.id, &payload.task_ids).await?;
+    let subtasks = TaskSubtask::find_for_task(pool, parent.id).await?;
+    Ok(ResponseJson(ApiResponse::success(subtasks)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct SetParentTaskRequest {
+    /// Task to nest this one under, or `null` to make it a top-level task again
+    pub parent_task_id: Option<Uuid>,
+}
+
+/// Convert a task into a subtask of another, or a subtask back into a top-level task
+pub async fn set_parent_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetParentTaskRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskSubtasks>>, ApiError> {
+    let pool = &deployment.db().pool;
+    TaskSubtask::set_parent(pool, task.id, payload.parent_task_id).await?;
+    let subtasks = TaskSubtask::find_for_task(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(subtasks)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateChecklistSubtasksRequest {
+    /// Start an attempt on each new subtask with this profile; requires `repos`
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    pub repos: Option<Vec<WorkspaceRepoInput>>,
+}
+
+/// Create a subtask for each unchecked `- [ ]` item in the task's description, skipping items
+/// that already have a subtask of the same title, and optionally start an attempt on each that
+/// WIP limits allow
+pub async fn create_checklist_subtasks(
+    Extension(parent): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateChecklistSubtasksRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let repos: Vec<CreateWorkspaceRepo> = payload
+        .repos
+        .unwrap_or_default()
+        .into_iter()
+        .map(|r| CreateWorkspaceRepo {
+            repo_id: r.repo_id,
+            target_branch: r.target_branch,
+        })
+        .collect();
+    if payload.executor_profile_id.is_some() && repos.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one repository is required to start subtasks".to_string(),
+        ));
+    }
+
+    let existing = TaskSubtask::find_subtasks(pool, parent.id).await?;
+    let mut created = Vec::new();
+    for item in checklist_items(parent.description.as_deref().unwrap_or_default()) {
+        if existing
+            .iter()
+            .chain(&created)
+            .any(|task: &Task| task.title == item)
+        {
+            continue;
+        }
+        let create = CreateTask::from_title_description(parent.project_id, item, None);
+        let task = Task::create(pool, &create, Uuid::new_v4()).await?;
+        TaskSubtask::set_parent(pool, task.id, Some(parent.id)).await?;
+        auto_assign::apply(pool, &task, TaskSource::Manual).await?;
+        created.push(task);
+    }
+
+    if let Some(executor_profile_id) = &payload.executor_profile_id {
+        for task in &created {
+            // Subtasks that would go over a WIP limit are left in todo
+            let violations = deployment
+                .wip_violations_for_start(task.project_id, Some(task), executor_profile_id)
+                .await?;
+            if !violations.is_empty() {
+                if let Err(e) = deployment
+                    .notify_wip_limit_exceeded(task, &violations, false)
+                    .await
+                {
+                    tracing::warn!(
+                        "Failed to notify about WIP limits for task {}: {}",
+                        task.id,
+                        e
+                    );
+                }
+                continue;
+            }
+            let workspace = deployment
+                .container()
+                .create_task_workspace(task, &repos)
+                .await?;
+            if let Err(err) = deployment
+                .start_or_queue_attempt(&workspace, executor_profile_id.clone())
+                .await
+            {
+                tracing::error!("Failed to start attempt for subtask {}: {}", task.id, err);
+            }
+        }
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "checklist_subtasks_created",
+            serde_json::json!({
+                "parent_task_id": parent.id.to_string(),
+                "count": created.len(),
+                "started": payload.executor_profile_id.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(created)))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_actions_router = Router::new()
         .route("/", put(update_task))
         .route("/", delete(delete_task))
-        .route("/share", post(share_task));
+        .route("/share", post(share_task))
+        .route("/labels", get(get_task_labels).put(set_task_labels))
+        .route(
+            "/assignees",
+            get(get_task_assignees).put(set_task_assignees),
+        )
+        .route("/run-defaults", get(get_task_run_defaults))
+        .route("/time", get(get_task_time))
+        .route("/usage", get(get_task_usage))
+        .route("/activity", get(get_task_activity))
+        .route("/column", put(move_task_to_column))
+        .route("/archive", post(archive_task))
+        .route("/restore", post(restore_task))
+        .route("/clone", post(clone_task))
+        .route(
+            "/dependencies",
+            get(get_task_dependencies).post(add_task_dependency),
+        )
+        .route(
+            "/relations",
+            get(get_task_relations).post(add_task_relation),
+        )
+        .route("/subtasks", get(get_task_subtasks).post(create_subtask))
+        .route("/subtasks/order", put(reorder_subtasks))
+        .route("/subtasks/from-checklist", post(create_checklist_subtasks))
+        .route("/parent", put(set_parent_task));
 
     let task_id_router = Router::new()
         .route("/", get(get_task))
@@ -474,7 +1565,18 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let inner = Router::new()
         .route("/", get(get_tasks).post(create_task))
         .route("/stream/ws", get(stream_tasks_ws))
+        .route("/due", get(get_due_tasks))
+        .route("/swimlanes", get(get_task_swimlanes))
         .route("/create-and-start", post(create_task_and_start))
+        .route("/from-template", post(create_task_from_template))
+        .route(
+            "/{task_id}/dependencies/{blocker_id}",
+            delete(remove_task_dependency),
+        )
+        .route(
+            "/{task_id}/relations/{related_id}",
+            delete(remove_task_relation),
+        )
         .nest("/{task_id}", task_id_router);
 
     // mount under /projects/:project_id/tasks
@@ -0,0 +1,88 @@
+use axum::{
+    Json, Router,
+    extract::{DefaultBodyLimit, Path, State},
+    response::Json as ResponseJson,
+    routing::post,
+};
+use db::models::project::Project;
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::trello_import::{self, TrelloImportResult};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Board exports of large boards with long histories run to tens of megabytes
+const MAX_EXPORT_BYTES: usize = 64 * 1024 * 1024;
+
+#[derive(Debug, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum TrelloImportSource {
+    /// The JSON from the board menu's "Print, export and share" > "Export as JSON"
+    Export { content: String },
+    /// Fetch the board with a Trello API key and token; neither is stored
+    Api {
+        /// Board URL or id
+        board: String,
+        api_key: String,
+        token: String,
+    },
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct TrelloImportRequest {
+    pub source: TrelloImportSource,
+    /// Also import archived lists and cards
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
+/// Import a Trello board into the project: lists become columns, cards become tasks, and card
+/// labels and checklists carry over
+pub async fn import_trello_board(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<TrelloImportRequest>,
+) -> Result<ResponseJson<ApiResponse<TrelloImportResult>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+
+    let (board, source) = match &payload.source {
+        TrelloImportSource::Export { content } => (trello_import::parse_export(content)?, "export"),
+        TrelloImportSource::Api {
+            board,
+            api_key,
+            token,
+        } => (
+            trello_import::fetch_board(board, api_key, token).await?,
+            "api",
+        ),
+    };
+    let result = trello_import::import(pool, project.id, &board, payload.include_archived).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "trello_imported",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "source": source,
+                "columns_created": result.columns_created,
+                "tasks_created": result.tasks_created,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(result)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/projects/{project_id}/import/trello",
+        post(import_trello_board).layer(DefaultBodyLimit::max(MAX_EXPORT_BYTES)),
+    )
+}
@@ -0,0 +1,51 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use chrono::{DateTime, Utc};
+use db::models::execution_usage::{ExecutionUsage, UsageStats};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UsageQuery {
+    /// Only runs of this project's tasks
+    pub project_id: Option<Uuid>,
+    /// Only runs recorded at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Only runs recorded before this time
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Token usage and API spend per task, model and day
+pub async fn get_usage_stats(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<UsageQuery>,
+) -> Result<ResponseJson<ApiResponse<UsageStats>>, ApiError> {
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from >= to
+    {
+        return Err(ApiError::BadRequest(
+            "'from' must be before 'to'".to_string(),
+        ));
+    }
+    let stats = ExecutionUsage::stats(
+        &deployment.db().pool,
+        query.project_id,
+        query.from,
+        query.to,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(stats)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/stats/usage", get(get_usage_stats))
+}
@@ -1,19 +1,30 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use db::{self, DBService};
-use executors::approvals::{ExecutorApprovalError, ExecutorApprovalService};
+use db::{
+    self, DBService,
+    models::{
+        approval_decision::{ApprovalDecision, CreateApprovalDecision},
+        execution_process::ExecutionProcess,
+    },
+};
+use executors::approvals::{CommandGate, ExecutorApprovalError, ExecutorApprovalService};
 use serde_json::Value;
 use utils::approvals::{ApprovalRequest, ApprovalStatus, CreateApprovalRequest};
 use uuid::Uuid;
 
-use crate::services::{approvals::Approvals, notification::NotificationService};
+use crate::services::{
+    approvals::Approvals,
+    config::NotificationEvent,
+    notification::{NotificationContext, NotificationService, i18n},
+};
 
 pub struct ExecutorApprovalBridge {
     approvals: Approvals,
     db: DBService,
     notification_service: NotificationService,
     execution_process_id: Uuid,
+    command_gate: Option<CommandGate>,
 }
 
 impl ExecutorApprovalBridge {
@@ -22,12 +33,14 @@ impl ExecutorApprovalBridge {
         db: DBService,
         notification_service: NotificationService,
         execution_process_id: Uuid,
+        command_gate: Option<CommandGate>,
     ) -> Arc<Self> {
         Arc::new(Self {
             approvals,
             db,
             notification_service,
             execution_process_id,
+            command_gate,
         })
     }
 }
@@ -42,6 +55,11 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
     ) -> Result<ApprovalStatus, ExecutorApprovalError> {
         super::ensure_task_in_review(&self.db.pool, self.execution_process_id).await;
 
+        let gate_pattern = self
+            .command_gate
+            .as_ref()
+            .and_then(|gate| gate.matching_pattern(&tool_input))
+            .map(str::to_string);
         let request = ApprovalRequest::from_create(
             CreateApprovalRequest {
                 tool_name: tool_name.to_string(),
@@ -51,22 +69,54 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
             self.execution_process_id,
         );
 
-        let (_, waiter) = self
+        let (request, waiter) = self
             .approvals
             .create_with_waiter(request)
             .await
             .map_err(ExecutorApprovalError::request_failed)?;
 
+        if let Err(e) = ApprovalDecision::create(
+            &self.db.pool,
+            &CreateApprovalDecision {
+                approval_id: request.id.clone(),
+                execution_process_id: self.execution_process_id,
+                tool_name: request.tool_name.clone(),
+                tool_input: request.tool_input.clone(),
+                gate_pattern,
+            },
+        )
+        .await
+        {
+            tracing::warn!("Failed to record approval request {}: {}", request.id, e);
+        }
+
+        let mut context = NotificationContext::new(NotificationEvent::ApprovalNeeded);
+        if let Ok(ctx) =
+            ExecutionProcess::load_context(&self.db.pool, self.execution_process_id).await
+        {
+            context = context
+                .with_task(ctx.project.id, ctx.task.id)
+                .with_workspace(ctx.workspace.id)
+                .with_names(&ctx.project.name, &ctx.task.title);
+            context.executor = ctx.session.executor;
+        }
         // Play notification sound when approval is needed
+        let locale = self.notification_service.locale().await;
         self.notification_service
             .notify(
-                "Approval Needed",
-                &format!("Tool '{}' requires approval", tool_name),
+                &context,
+                &i18n::t(locale, "approval-needed-title", &[]),
+                &i18n::t(locale, "approval-needed", &[("tool", tool_name)]),
             )
             .await;
 
         let status = waiter.clone().await;
 
+        if let Err(e) = ApprovalDecision::record_outcome(&self.db.pool, &request.id, &status).await
+        {
+            tracing::warn!("Failed to record approval decision {}: {}", request.id, e);
+        }
+
         if matches!(status, ApprovalStatus::Pending) {
             return Err(ExecutorApprovalError::request_failed(
                 "approval finished in pending state",
@@ -75,4 +125,8 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
 
         Ok(status)
     }
+
+    fn command_gate(&self) -> Option<&CommandGate> {
+        self.command_gate.as_ref()
+    }
 }
@@ -0,0 +1,242 @@
+use std::path::{Path, PathBuf};
+
+use aws_credential_types::Credentials;
+use aws_sdk_s3::{
+    Client,
+    config::{Builder as S3ConfigBuilder, Region},
+    primitives::ByteStream,
+};
+use db::models::task_attachment::{
+    AttachmentStorage, CreateTaskAttachment, TaskAttachment, sanitize_file_name,
+};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::services::config::{AttachmentConfig, S3StorageConfig};
+
+#[derive(Debug, thiserror::Error)]
+pub enum AttachmentError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Attachment too large: {0} bytes (max: {1} bytes)")]
+    TooLarge(u64, u64),
+
+    #[error("Attachment is empty")]
+    Empty,
+
+    #[error("Attachment not found")]
+    NotFound,
+
+    #[error("Attachment is stored in S3, but no S3 storage is configured")]
+    S3NotConfigured,
+
+    #[error("S3 storage error: {0}")]
+    Storage(String),
+}
+
+/// Stores task attachments in the app data directory, or in an S3-compatible bucket when one is
+/// configured
+#[derive(Clone)]
+pub struct AttachmentService {
+    cache_dir: PathBuf,
+    pool: SqlitePool,
+}
+
+fn s3_client(config: &S3StorageConfig) -> Client {
+    let credentials = Credentials::new(
+        &config.access_key_id,
+        &config.secret_access_key,
+        None,
+        None,
+        "vibe-kanban-attachments",
+    );
+    let region = config.region.clone().unwrap_or_else(|| "auto".to_string());
+    let s3_config = S3ConfigBuilder::new()
+        .region(Region::new(region))
+        .endpoint_url(&config.endpoint)
+        .credentials_provider(credentials)
+        .force_path_style(true)
+        .build();
+    Client::from_conf(s3_config)
+}
+
+fn s3_key(config: &S3StorageConfig, file_name: &str) -> String {
+    let prefix = config.prefix.as_deref().unwrap_or_default();
+    format!("{prefix}attachments/{file_name}")
+}
+
+impl AttachmentService {
+    pub fn new(pool: SqlitePool) -> Result<Self, AttachmentError> {
+        let cache_dir = utils::cache_dir().join("attachments");
+        std::fs::create_dir_all(&cache_dir)?;
+        Ok(Self { cache_dir, pool })
+    }
+
+    pub async fn store(
+        &self,
+        config: &AttachmentConfig,
+        task_id: Uuid,
+        original_name: &str,
+        mime_type: Option<String>,
+        data: &[u8],
+    ) -> Result<TaskAttachment, AttachmentError> {
+        let size = data.len() as u64;
+        let max_size = config.max_size_mb * 1024 * 1024;
+        if size > max_size {
+            return Err(AttachmentError::TooLarge(size, max_size));
+        }
+        if size == 0 {
+            return Err(AttachmentError::Empty);
+        }
+
+        let id = Uuid::new_v4();
+        let file_name = format!("{id}-{}", sanitize_file_name(original_name));
+        let (storage, storage_key) = match &config.s3 {
+            Some(s3) => {
+                let key = s3_key(s3, &file_name);
+                s3_client(s3)
+                    .put_object()
+                    .bucket(&s3.bucket)
+                    .key(&key)
+                    .set_content_type(mime_type.clone())
+                    .body(ByteStream::from(data.to_vec()))
+                    .send()
+                    .await
+                    .map_err(|e| AttachmentError::Storage(e.to_string()))?;
+                (AttachmentStorage::S3, key)
+            }
+            None => {
+                tokio::fs::write(self.cache_dir.join(&file_name), data).await?;
+                (AttachmentStorage::Local, file_name)
+            }
+        };
+
+        let attachment = TaskAttachment::create(
+            &self.pool,
+            &CreateTaskAttachment {
+                id,
+                task_id,
+                original_name: original_name.to_string(),
+                mime_type,
+                size_bytes: size as i64,
+                storage,
+                storage_key,
+            },
+        )
+        .await?;
+        Ok(attachment)
+    }
+
+    pub async fn read(
+        &self,
+        config: &AttachmentConfig,
+        attachment: &TaskAttachment,
+    ) -> Result<Vec<u8>, AttachmentError> {
+        match attachment.storage {
+            AttachmentStorage::Local => {
+                let path = self.cache_dir.join(&attachment.storage_key);
+                if !path.exists() {
+                    return Err(AttachmentError::NotFound);
+                }
+                Ok(tokio::fs::read(path).await?)
+            }
+            AttachmentStorage::S3 => {
+                let s3 = config.s3.as_ref().ok_or(AttachmentError::S3NotConfigured)?;
+                let object = s3_client(s3)
+                    .get_object()
+                    .bucket(&s3.bucket)
+                    .key(&attachment.storage_key)
+                    .send()
+                    .await
+                    .map_err(|e| AttachmentError::Storage(e.to_string()))?;
+                let bytes = object
+                    .body
+                    .collect()
+                    .await
+                    .map_err(|e| AttachmentError::Storage(e.to_string()))?;
+                Ok(bytes.into_bytes().to_vec())
+            }
+        }
+    }
+
+    /// Remove the attachment's stored file; the database row is left to the caller
+    pub async fn delete_file(
+        &self,
+        config: &AttachmentConfig,
+        attachment: &TaskAttachment,
+    ) -> Result<(), AttachmentError> {
+        match attachment.storage {
+            AttachmentStorage::Local => {
+                let path = self.cache_dir.join(&attachment.storage_key);
+                if path.exists() {
+                    tokio::fs::remove_file(path).await?;
+                }
+            }
+            AttachmentStorage::S3 => {
+                let s3 = config.s3.as_ref().ok_or(AttachmentError::S3NotConfigured)?;
+                s3_client(s3)
+                    .delete_object()
+                    .bucket(&s3.bucket)
+                    .key(&attachment.storage_key)
+                    .send()
+                    .await
+                    .map_err(|e| AttachmentError::Storage(e.to_string()))?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn delete(
+        &self,
+        config: &AttachmentConfig,
+        attachment: &TaskAttachment,
+    ) -> Result<(), AttachmentError> {
+        self.delete_file(config, attachment).await?;
+        TaskAttachment::delete(&self.pool, attachment.id).await?;
+        Ok(())
+    }
+
+    /// Copy the task's attachments into the worktree's attachments directory, where the coding
+    /// agent prompt points to them. Skips attachments that already exist at the target.
+    pub async fn copy_attachments_by_task_to_worktree(
+        &self,
+        config: &AttachmentConfig,
+        worktree_path: &Path,
+        task_id: Uuid,
+    ) -> Result<(), AttachmentError> {
+        let attachments = TaskAttachment::find_by_task_id(&self.pool, task_id).await?;
+        if attachments.is_empty() {
+            return Ok(());
+        }
+
+        let attachments_dir = worktree_path.join(utils::path::VIBE_ATTACHMENTS_DIR);
+        tokio::fs::create_dir_all(&attachments_dir).await?;
+
+        // Create .gitignore to ignore all files in this directory
+        let gitignore_path = attachments_dir.join(".gitignore");
+        if !gitignore_path.exists() {
+            tokio::fs::write(&gitignore_path, "*\n").await?;
+        }
+
+        for attachment in attachments {
+            let dst = attachments_dir.join(attachment.workspace_file_name());
+            if dst.exists() {
+                continue;
+            }
+            match self.read(config, &attachment).await {
+                Ok(data) => tokio::fs::write(&dst, data).await?,
+                Err(e) => tracing::warn!(
+                    "Failed to copy attachment {} to worktree: {}",
+                    attachment.id,
+                    e
+                ),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,206 @@
+//! Coding agent runs that fail with a transient error are started again after a backoff, up to
+//! the configured number of retries. Notifications wait for the final failure.
+
+use std::time::Duration;
+
+use utils::log_msg::LogMsg;
+
+use crate::services::config::AttemptRetryPolicy;
+
+/// Prefix of the log line announcing a retry
+pub const RETRY_LOG_MARKER: &str = "[auto-retry]";
+
+/// Only the end of the output is checked: the error that ended the run is there, while earlier
+/// tool output may mention timeouts or rate limits in passing
+const OUTPUT_TAIL_BYTES: usize = 8 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransientFailure {
+    RateLimit,
+    Network,
+    ToolTimeout,
+}
+
+impl TransientFailure {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RateLimit => "rate_limit",
+            Self::Network => "network",
+            Self::ToolTimeout => "tool_timeout",
+        }
+    }
+
+    fn describe(&self) -> &'static str {
+        match self {
+            Self::RateLimit => "Rate limited",
+            Self::Network => "Network error",
+            Self::ToolTimeout => "Tool timed out",
+        }
+    }
+}
+
+const PATTERNS: &[(TransientFailure, &[&str])] = &[
+    (
+        TransientFailure::RateLimit,
+        &[
+            "rate limit",
+            "rate_limit",
+            "ratelimit",
+            "too many requests",
+            "status 429",
+            "status code 429",
+            "overloaded",
+        ],
+    ),
+    (
+        TransientFailure::Network,
+        &[
+            "econnreset",
+            "econnrefused",
+            "etimedout",
+            "enotfound",
+            "eai_again",
+            "socket hang up",
+            "connection reset",
+            "connection refused",
+            "network error",
+            "fetch failed",
+            "service unavailable",
+            "bad gateway",
+        ],
+    ),
+    (
+        TransientFailure::ToolTimeout,
+        &[
+            "tool timed out",
+            "tool call timed out",
+            "timed out waiting for",
+        ],
+    ),
+];
+
+/// The transient error a failed run's output ends with, if any
+pub fn classify_failure(output: &str) -> Option<TransientFailure> {
+    let mut start = output.len().saturating_sub(OUTPUT_TAIL_BYTES);
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    let tail = output[start..].to_lowercase();
+    PATTERNS
+        .iter()
+        .find(|(_, patterns)| patterns.iter().any(|pattern| tail.contains(pattern)))
+        .map(|(failure, _)| *failure)
+}
+
+/// The stdout and stderr a run produced
+pub fn output_text(history: &[LogMsg]) -> String {
+    let mut output = String::new();
+    for msg in history {
+        if let LogMsg::Stdout(text) | LogMsg::Stderr(text) = msg {
+            output.push_str(text);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// How long to wait before retry number `retry` (1-based)
+pub fn retry_delay(policy: &AttemptRetryPolicy, retry: u32) -> Duration {
+    let factor = 2u64.saturating_pow(retry.saturating_sub(1));
+    let secs = (policy.initial_delay_secs as u64)
+        .saturating_mul(factor)
+        .min(policy.max_delay_secs as u64);
+    Duration::from_secs(secs)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedRetry {
+    pub failure: TransientFailure,
+    /// 1-based
+    pub retry: u32,
+    pub delay: Duration,
+}
+
+impl PlannedRetry {
+    pub fn log_line(&self, policy: &AttemptRetryPolicy) -> String {
+        format!(
+            "{RETRY_LOG_MARKER} {}; retrying in {}s (retry {} of {})",
+            self.failure.describe(),
+            self.delay.as_secs(),
+            self.retry,
+            policy.max_retries
+        )
+    }
+}
+
+/// Whether a run that failed with `output`, after `retries_so_far` retries, is retried
+pub fn plan_retry(
+    policy: &AttemptRetryPolicy,
+    retries_so_far: u32,
+    output: &str,
+) -> Option<PlannedRetry> {
+    if retries_so_far >= policy.max_retries {
+        return None;
+    }
+    let failure = classify_failure(output)?;
+    let retry = retries_so_far + 1;
+    Some(PlannedRetry {
+        failure,
+        retry,
+        delay: retry_delay(policy, retry),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(max_retries: u32) -> AttemptRetryPolicy {
+        AttemptRetryPolicy {
+            max_retries,
+            initial_delay_secs: 30,
+            max_delay_secs: 100,
+        }
+    }
+
+    #[test]
+    fn test_classify_failure() {
+        assert_eq!(
+            classify_failure(r#"{"type":"error","error":{"type":"rate_limit_error"}}"#),
+            Some(TransientFailure::RateLimit)
+        );
+        assert_eq!(
+            classify_failure("Error: read ECONNRESET"),
+            Some(TransientFailure::Network)
+        );
+        assert_eq!(
+            classify_failure("MCP tool call timed out after 60s"),
+            Some(TransientFailure::ToolTimeout)
+        );
+        assert_eq!(classify_failure("error[E0308]: mismatched types"), None);
+
+        // A rate limit mentioned long before the run ended doesn't count
+        let output = format!("rate limit\n{}", "compiling\n".repeat(1000));
+        assert_eq!(classify_failure(&output), None);
+    }
+
+    #[test]
+    fn test_plan_retry() {
+        let output = "429 Too Many Requests";
+        assert_eq!(plan_retry(&policy(0), 0, output), None);
+        assert_eq!(plan_retry(&policy(2), 0, "syntax error"), None);
+
+        let delays: Vec<u64> = (0..3)
+            .filter_map(|retries| plan_retry(&policy(3), retries, output))
+            .map(|retry| retry.delay.as_secs())
+            .collect();
+        assert_eq!(delays, vec![30, 60, 100]);
+        assert_eq!(plan_retry(&policy(3), 3, output), None);
+
+        let retry = plan_retry(&policy(3), 0, output).unwrap();
+        assert_eq!(
+            retry.log_line(&policy(3)),
+            "[auto-retry] Rate limited; retrying in 30s (retry 1 of 3)"
+        );
+    }
+}
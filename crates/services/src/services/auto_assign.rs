@@ -0,0 +1,315 @@
+//! Assignment rules pick labels, an executor profile and a base branch for new tasks from their
+//! title, the integration that created them, and file paths mentioned in their description.
+
+use db::models::{
+    assignment_rule::{AssignmentRule, TaskRunDefaults, TaskSource},
+    label::Label,
+    task::Task,
+    task_assignee::{TaskAssignee, TaskAssigneeKind},
+};
+use executors::profile::ExecutorProfileId;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// What the matching rules picked for a task
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Assignment {
+    pub labels: Vec<String>,
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    pub base_branch: Option<String>,
+    /// The first matching rule that set an executor profile or base branch
+    pub rule_id: Option<Uuid>,
+}
+
+/// Words that may be paths: description text split on whitespace, quotes, brackets and
+/// punctuation that commonly surrounds a path, without a leading `./` or `/`
+fn path_candidates(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| {
+        c.is_whitespace()
+            || matches!(
+                c,
+                '`' | '\'' | '"' | '(' | ')' | '[' | ']' | '<' | '>' | ',' | ';'
+            )
+    })
+    .map(normalize_path)
+    .filter(|word| !word.is_empty())
+}
+
+fn normalize_path(path: &str) -> &str {
+    let path = path.trim();
+    let path = path.strip_prefix("./").unwrap_or(path);
+    path.trim_start_matches('/')
+}
+
+/// Whether the rule applies to a task. A rule matches when every condition it sets matches; a
+/// rule without conditions matches every new task.
+pub fn matches(
+    rule: &AssignmentRule,
+    title: &str,
+    description: Option<&str>,
+    source: TaskSource,
+) -> bool {
+    if !rule.enabled {
+        return false;
+    }
+    let keywords: Vec<String> = rule
+        .title_keywords
+        .iter()
+        .map(|keyword| keyword.trim().to_lowercase())
+        .filter(|keyword| !keyword.is_empty())
+        .collect();
+    if !keywords.is_empty() {
+        let title = title.to_lowercase();
+        if !keywords.iter().any(|keyword| title.contains(keyword)) {
+            return false;
+        }
+    }
+    if !rule.sources.is_empty() && !rule.sources.contains(&source) {
+        return false;
+    }
+    let prefixes: Vec<&str> = rule
+        .path_prefixes
+        .iter()
+        .map(|prefix| normalize_path(prefix))
+        .filter(|prefix| !prefix.is_empty())
+        .collect();
+    if !prefixes.is_empty() {
+        let text = description.unwrap_or_default();
+        let mentioned = path_candidates(text)
+            .any(|word| prefixes.iter().any(|prefix| word.starts_with(prefix)));
+        if !mentioned {
+            return false;
+        }
+    }
+    true
+}
+
+/// Combine the matching rules, tried in order: labels from all of them, the executor profile and
+/// base branch from the first that sets each. `None` when no rule matches.
+pub fn resolve(
+    rules: &[AssignmentRule],
+    title: &str,
+    description: Option<&str>,
+    source: TaskSource,
+) -> Option<Assignment> {
+    let mut assignment: Option<Assignment> = None;
+    for rule in rules
+        .iter()
+        .filter(|rule| matches(rule, title, description, source))
+    {
+        let assignment = assignment.get_or_insert_with(Assignment::default);
+        for label in rule.labels.iter().map(|label| label.trim()) {
+            if !label.is_empty()
+                && !assignment
+                    .labels
+                    .iter()
+                    .any(|existing| existing.eq_ignore_ascii_case(label))
+            {
+                assignment.labels.push(label.to_string());
+            }
+        }
+        let mut picked = false;
+        if assignment.executor_profile_id.is_none()
+            && let Some(profile) = &rule.executor_profile_id
+        {
+            assignment.executor_profile_id = Some(profile.0.clone());
+            picked = true;
+        }
+        if assignment.base_branch.is_none()
+            && let Some(branch) = rule.base_branch.as_deref().map(str::trim)
+            && !branch.is_empty()
+        {
+            assignment.base_branch = Some(branch.to_string());
+            picked = true;
+        }
+        if picked && assignment.rule_id.is_none() {
+            assignment.rule_id = Some(rule.id);
+        }
+    }
+    assignment
+}
+
+/// Apply the project's rules to a task just created from `source`: add the labels, remember the
+/// executor profile and base branch for its attempts, and assign the executor as the task's agent
+/// unless it already has one
+pub async fn apply(
+    pool: &SqlitePool,
+    task: &Task,
+    source: TaskSource,
+) -> Result<Option<Assignment>, sqlx::Error> {
+    apply_with_profile(pool, task, source, None).await
+}
+
+/// Like [`apply`], with the executor profile the task was created with taking the place of the
+/// one the rules pick
+pub async fn apply_with_profile(
+    pool: &SqlitePool,
+    task: &Task,
+    source: TaskSource,
+    requested: Option<&ExecutorProfileId>,
+) -> Result<Option<Assignment>, sqlx::Error> {
+    let rules = AssignmentRule::find_available(pool, Some(task.project_id)).await?;
+    let mut assignment = resolve(&rules, &task.title, task.description.as_deref(), source);
+    if let Some(profile) = requested {
+        let assignment = assignment.get_or_insert_with(Assignment::default);
+        assignment.executor_profile_id = Some(profile.clone());
+        if assignment.base_branch.is_none() {
+            assignment.rule_id = None;
+        }
+    }
+    let Some(assignment) = assignment else {
+        return Ok(None);
+    };
+
+    for name in &assignment.labels {
+        let label = Label::find_or_create_by_name(pool, name).await?;
+        Label::add_to_task(pool, task.id, label.id).await?;
+    }
+    if assignment.executor_profile_id.is_some() || assignment.base_branch.is_some() {
+        TaskRunDefaults::set(
+            pool,
+            task.id,
+            assignment.executor_profile_id.as_ref(),
+            assignment.base_branch.as_deref(),
+            assignment.rule_id,
+        )
+        .await?;
+    }
+    if let Some(profile) = &assignment.executor_profile_id {
+        let assignees = TaskAssignee::find_for_task(pool, task.id).await?;
+        if !assignees
+            .iter()
+            .any(|assignee| assignee.kind == TaskAssigneeKind::Agent)
+        {
+            let humans: Vec<String> = assignees.into_iter().map(|a| a.name).collect();
+            let agent = profile.executor.to_string();
+            TaskAssignee::set_for_task(pool, task.id, &humans, Some(&agent)).await?;
+        }
+    }
+
+    tracing::debug!(
+        "Assignment rules applied to task {}: {:?}",
+        task.id,
+        assignment
+    );
+    Ok(Some(assignment))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use executors::executors::BaseCodingAgent;
+    use sqlx::types::Json;
+
+    use super::*;
+
+    fn rule(name: &str) -> AssignmentRule {
+        AssignmentRule {
+            id: Uuid::new_v4(),
+            project_id: None,
+            name: name.to_string(),
+            enabled: true,
+            position: 0,
+            title_keywords: Json(Vec::new()),
+            sources: Json(Vec::new()),
+            path_prefixes: Json(Vec::new()),
+            labels: Json(Vec::new()),
+            executor_profile_id: None,
+            base_branch: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_matches_conditions() {
+        let mut docs = rule("docs");
+        docs.path_prefixes = Json(vec!["./docs/".to_string()]);
+        assert!(matches(
+            &docs,
+            "Fix typo",
+            Some("See `docs/getting-started.md` (line 4)"),
+            TaskSource::Manual
+        ));
+        assert!(matches(
+            &docs,
+            "Fix typo",
+            Some("in /docs/api.md"),
+            TaskSource::Manual
+        ));
+        assert!(!matches(
+            &docs,
+            "Fix typo",
+            Some("mydocs/api.md"),
+            TaskSource::Manual
+        ));
+        assert!(!matches(&docs, "Fix docs/api.md", None, TaskSource::Manual));
+
+        let mut github_bugs = rule("bugs");
+        github_bugs.title_keywords = Json(vec!["Bug".to_string(), "crash".to_string()]);
+        github_bugs.sources = Json(vec![TaskSource::GitHub]);
+        assert!(matches(
+            &github_bugs,
+            "App CRASHES on start",
+            None,
+            TaskSource::GitHub
+        ));
+        assert!(!matches(
+            &github_bugs,
+            "App crashes on start",
+            None,
+            TaskSource::Jira
+        ));
+        assert!(!matches(
+            &github_bugs,
+            "Add dark mode",
+            None,
+            TaskSource::GitHub
+        ));
+
+        github_bugs.enabled = false;
+        assert!(!matches(&github_bugs, "Bug", None, TaskSource::GitHub));
+        assert!(matches(
+            &rule("default"),
+            "Anything",
+            None,
+            TaskSource::Schedule
+        ));
+    }
+
+    #[test]
+    fn test_resolve_merges_rules_in_order() {
+        let cheap = ExecutorProfileId::new(BaseCodingAgent::Gemini);
+        let mut docs = rule("docs");
+        docs.path_prefixes = Json(vec!["docs/".to_string()]);
+        docs.labels = Json(vec!["docs".to_string()]);
+        docs.executor_profile_id = Some(Json(cheap.clone()));
+        let mut fallback = rule("fallback");
+        fallback.labels = Json(vec!["Docs".to_string(), "triage".to_string()]);
+        fallback.executor_profile_id =
+            Some(Json(ExecutorProfileId::new(BaseCodingAgent::ClaudeCode)));
+        fallback.base_branch = Some("develop".to_string());
+        let rules = vec![docs.clone(), fallback.clone()];
+
+        let assignment = resolve(
+            &rules,
+            "Update guide",
+            Some("docs/guide.md"),
+            TaskSource::Manual,
+        )
+        .unwrap();
+        assert_eq!(assignment.labels, vec!["docs", "triage"]);
+        assert_eq!(assignment.executor_profile_id, Some(cheap));
+        assert_eq!(assignment.base_branch.as_deref(), Some("develop"));
+        assert_eq!(assignment.rule_id, Some(docs.id));
+
+        let assignment = resolve(&rules, "Refactor", None, TaskSource::Manual).unwrap();
+        assert_eq!(assignment.labels, vec!["Docs", "triage"]);
+        assert_eq!(assignment.rule_id, Some(fallback.id));
+
+        assert_eq!(
+            resolve(&rules[..1], "Refactor", None, TaskSource::Manual),
+            None
+        );
+    }
+}
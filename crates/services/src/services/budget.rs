@@ -0,0 +1,162 @@
+//! Monthly API spend budgets. Spend is what the execution usage records cost in the current UTC
+//! calendar month; reaching a soft limit sends a notification, reaching a hard limit also refuses
+//! new attempts.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    cost_budget::{self, BudgetLevel, CostBudget},
+    project::Project,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A budget with this month's spend against it
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BudgetStatus {
+    pub budget: CostBudget,
+    /// `None` for the budget across every project
+    pub project_name: Option<String>,
+    /// `YYYY-MM`
+    pub month: String,
+    pub spent_usd: f64,
+    pub level: BudgetLevel,
+    /// Left before the hard limit; `None` without one
+    pub remaining_usd: Option<f64>,
+}
+
+impl BudgetStatus {
+    pub fn new(
+        budget: CostBudget,
+        project_name: Option<String>,
+        spent_usd: f64,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            level: budget.level(spent_usd),
+            remaining_usd: budget
+                .hard_limit_usd
+                .map(|limit| (limit - spent_usd).max(0.0)),
+            month: cost_budget::month_key(now),
+            spent_usd,
+            project_name,
+            budget,
+        }
+    }
+
+    /// The limit the spend reached, if any
+    pub fn reached_limit(&self) -> Option<f64> {
+        match self.level {
+            BudgetLevel::Under => None,
+            BudgetLevel::Soft => self.budget.soft_limit_usd,
+            BudgetLevel::Hard => self.budget.hard_limit_usd,
+        }
+    }
+}
+
+impl fmt::Display for BudgetStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.project_name {
+            Some(name) => write!(f, "monthly budget for '{name}'")?,
+            None => write!(f, "monthly budget across all projects")?,
+        }
+        write!(f, ": ${:.2} spent in {}", self.spent_usd, self.month)?;
+        if let Some(limit) = self.reached_limit() {
+            write!(f, ", limit ${limit:.2}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A reached hard limit as one sentence, for API errors
+pub fn describe(status: &BudgetStatus) -> String {
+    format!("Budget limit reached: {status}")
+}
+
+async fn status_of(
+    pool: &SqlitePool,
+    budget: CostBudget,
+    now: DateTime<Utc>,
+) -> Result<BudgetStatus, sqlx::Error> {
+    let since = cost_budget::month_start(now);
+    let spent = CostBudget::spend_since(pool, budget.project_id, since).await?;
+    let project_name = match budget.project_id {
+        Some(project_id) => Project::find_by_id(pool, project_id)
+            .await?
+            .map(|project| project.name),
+        None => None,
+    };
+    Ok(BudgetStatus::new(budget, project_name, spent, now))
+}
+
+/// Budgets that apply to the project, the one across every project first; every budget when
+/// `project_id` is `None`
+pub async fn statuses(
+    pool: &SqlitePool,
+    project_id: Option<Uuid>,
+    now: DateTime<Utc>,
+) -> Result<Vec<BudgetStatus>, sqlx::Error> {
+    let budgets = match project_id {
+        Some(project_id) => CostBudget::find_for_project(pool, project_id).await?,
+        None => CostBudget::find_all(pool).await?,
+    };
+    let mut statuses = Vec::with_capacity(budgets.len());
+    for budget in budgets {
+        statuses.push(status_of(pool, budget, now).await?);
+    }
+    Ok(statuses)
+}
+
+/// A budget applying to the project whose hard limit this month's spend has reached
+pub async fn hard_limit_reached(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<Option<BudgetStatus>, sqlx::Error> {
+    Ok(statuses(pool, Some(project_id), now)
+        .await?
+        .into_iter()
+        .find(|status| status.level == BudgetLevel::Hard))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_status_and_description() {
+        let budget = CostBudget {
+            id: Uuid::new_v4(),
+            project_id: Some(Uuid::new_v4()),
+            soft_limit_usd: Some(40.0),
+            hard_limit_usd: Some(50.0),
+            soft_notified_month: None,
+            hard_notified_month: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let now = Utc.with_ymd_and_hms(2026, 10, 15, 12, 0, 0).unwrap();
+
+        let status = BudgetStatus::new(budget.clone(), Some("Web".to_string()), 12.5, now);
+        assert_eq!(status.level, BudgetLevel::Under);
+        assert_eq!(status.remaining_usd, Some(37.5));
+        assert_eq!(
+            status.to_string(),
+            "monthly budget for 'Web': $12.50 spent in 2026-10"
+        );
+
+        let status = BudgetStatus::new(budget, None, 51.0, now);
+        assert_eq!(status.level, BudgetLevel::Hard);
+        assert_eq!(status.remaining_usd, Some(0.0));
+        assert_eq!(
+            describe(&status),
+            "Budget limit reached: monthly budget across all projects: $51.00 spent in 2026-10, \
+             limit $50.00"
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Read-only iCalendar (RFC 5545) feed of a project's task due dates and upcoming scheduled runs,
+//! for subscribing to from Google, Apple or Outlook calendars.
+
+use chrono::{DateTime, Duration, Utc};
+use db::models::{
+    project::Project,
+    task::{Task, TaskStatus},
+    task_schedule::TaskSchedule,
+};
+use rand::{RngCore, rngs::OsRng};
+use sqlx::SqlitePool;
+use thiserror::Error;
+
+use super::recurrence;
+
+/// How far ahead scheduled runs are listed
+const SCHEDULE_HORIZON_DAYS: i64 = 90;
+/// Most runs listed per schedule, so rules firing every few minutes don't flood the calendar
+const MAX_RUNS_PER_SCHEDULE: usize = 100;
+/// Events are points in time; calendars show them as short blocks
+const EVENT_MINUTES: i64 = 30;
+/// Longest content line in octets before it is folded
+const MAX_LINE_OCTETS: usize = 75;
+
+#[derive(Debug, Error)]
+pub enum CalendarError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+/// One VEVENT in the feed
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    /// Stable across refreshes, so calendars update events instead of duplicating them
+    pub uid: String,
+    pub start: DateTime<Utc>,
+    pub summary: String,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub cancelled: bool,
+}
+
+/// A new random feed token, 32 bytes as hex
+pub fn new_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Escape a TEXT value
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Append a content line, folded into continuation lines of at most 75 octets without
+/// splitting characters
+fn push_line(ics: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        // Continuation lines start with a space, which counts towards their length
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            ics.push_str("\r\n ");
+            width = 1;
+        }
+        ics.push(c);
+        width += c.len_utf8();
+    }
+    ics.push_str("\r\n");
+}
+
+fn format_time(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Render the events as an iCalendar document named `name`
+pub fn render(name: &str, events: &[CalendarEvent], now: DateTime<Utc>) -> String {
+    let mut ics = String::new();
+    push_line(&mut ics, "BEGIN:VCALENDAR");
+    push_line(&mut ics, "VERSION:2.0");
+    push_line(&mut ics, "PRODID:-//Vibe Kanban//Project calendar//EN");
+    push_line(&mut ics, "CALSCALE:GREGORIAN");
+    push_line(&mut ics, "METHOD:PUBLISH");
+    push_line(&mut ics, &format!("X-WR-CALNAME:{}", escape_text(name)));
+    for event in events {
+        push_line(&mut ics, "BEGIN:VEVENT");
+        push_line(&mut ics, &format!("UID:{}", event.uid));
+        push_line(&mut ics, &format!("DTSTAMP:{}", format_time(now)));
+        push_line(&mut ics, &format!("DTSTART:{}", format_time(event.start)));
+        push_line(
+            &mut ics,
+            &format!(
+                "DTEND:{}",
+                format_time(event.start + Duration::minutes(EVENT_MINUTES))
+            ),
+        );
+        push_line(
+            &mut ics,
+            &format!("SUMMARY:{}", escape_text(&event.summary)),
+        );
+        if let Some(description) = &event.description {
+            push_line(
+                &mut ics,
+                &format!("DESCRIPTION:{}", escape_text(description)),
+            );
+        }
+        if let Some(url) = &event.url {
+            push_line(&mut ics, &format!("URL:{url}"));
+        }
+        if event.cancelled {
+            push_line(&mut ics, "STATUS:CANCELLED");
+        }
+        push_line(&mut ics, "TRANSP:TRANSPARENT");
+        push_line(&mut ics, "END:VEVENT");
+    }
+    push_line(&mut ics, "END:VCALENDAR");
+    ics
+}
+
+/// The event for a task's due date, linking to the task when `base_url` is known
+pub fn task_event(task: &Task, base_url: Option<&str>) -> Option<CalendarEvent> {
+    let due_at = task.due_at?;
+    let summary = match task.status {
+        TaskStatus::Done => format!("✓ Due: {}", task.title),
+        _ => format!("Due: {}", task.title),
+    };
+    Some(CalendarEvent {
+        uid: format!("task-{}@vibe-kanban", task.id),
+        start: due_at,
+        summary,
+        description: task.description.clone().filter(|d| !d.trim().is_empty()),
+        url: base_url.map(|base| format!("{base}/projects/{}/tasks/{}", task.project_id, task.id)),
+        cancelled: task.status == TaskStatus::Cancelled,
+    })
+}
+
+/// Events for the schedule's runs from its next one until `until`. Disabled schedules and rules
+/// that no longer parse have none.
+pub fn schedule_events(schedule: &TaskSchedule, until: DateTime<Utc>) -> Vec<CalendarEvent> {
+    if !schedule.enabled {
+        return Vec::new();
+    }
+    let utc_offset = schedule.utc_offset.as_deref();
+    let mut events = Vec::new();
+    let mut next = schedule.next_run_at;
+    while let Some(run_at) = next.filter(|run_at| *run_at <= until) {
+        if events.len() == MAX_RUNS_PER_SCHEDULE {
+            break;
+        }
+        let date = recurrence::local_date(utc_offset, run_at)
+            .unwrap_or_else(|_| run_at.date_naive())
+            .to_string();
+        events.push(CalendarEvent {
+            uid: format!(
+                "schedule-{}-{}@vibe-kanban",
+                schedule.id,
+                run_at.timestamp()
+            ),
+            start: run_at,
+            summary: format!("Scheduled: {}", schedule.title.replace("{{date}}", &date)),
+            description: Some(format!("Repeats: {}", schedule.rule)),
+            url: None,
+            cancelled: false,
+        });
+        next = recurrence::next_run(&schedule.rule, utc_offset, schedule.created_at, run_at)
+            .ok()
+            .flatten();
+    }
+    events
+}
+
+/// The project's feed: due dates of its unarchived tasks and its schedules' upcoming runs
+pub async fn project_feed(
+    pool: &SqlitePool,
+    project: &Project,
+    base_url: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<String, CalendarError> {
+    let mut events: Vec<CalendarEvent> = Task::find_with_due_date(pool, project.id)
+        .await?
+        .iter()
+        .filter_map(|task| task_event(task, base_url))
+        .collect();
+    let until = now + Duration::days(SCHEDULE_HORIZON_DAYS);
+    for schedule in TaskSchedule::find_all(pool, Some(project.id)).await? {
+        events.extend(schedule_events(&schedule, until));
+    }
+    events.sort_by_key(|event| event.start);
+    Ok(render(&project.name, &events, now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_escapes_and_folds() {
+        let start = DateTime::parse_from_rfc3339("2026-03-02T09:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let event = CalendarEvent {
+            uid: "task-1@vibe-kanban".to_string(),
+            start,
+            summary: "Due: Ship v2; update docs, changelog".to_string(),
+            description: Some(format!("Line one\n{}", "ü".repeat(60))),
+            url: None,
+            cancelled: true,
+        };
+        let ics = render("Roadmap", &[event], start);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("SUMMARY:Due: Ship v2\\; update docs\\, changelog\r\n"));
+        assert!(ics.contains("DTSTART:20260302T090000Z\r\nDTEND:20260302T093000Z\r\n"));
+        assert!(ics.contains("STATUS:CANCELLED\r\n"));
+        assert!(ics.contains("DESCRIPTION:Line one\\n"));
+        for line in ics.split("\r\n") {
+            assert!(line.len() <= MAX_LINE_OCTETS, "{line}");
+        }
+        // Unfolding restores the description
+        let unfolded = ics.replace("\r\n ", "");
+        assert!(unfolded.contains(&format!("DESCRIPTION:Line one\\n{}\r\n", "ü".repeat(60))));
+    }
+}
@@ -0,0 +1,30 @@
+use db::models::config_audit::{ConfigAuditEntry, CreateConfigAuditEntry};
+use sqlx::SqlitePool;
+
+use super::{Config, config_changes};
+
+/// Record each key that differs between `old` and `new` in the config audit log. Failures are
+/// logged rather than returned, as the change itself has already been applied.
+pub async fn record_config_changes(pool: &SqlitePool, source: &str, old: &Config, new: &Config) {
+    let changes = match config_changes(old, new) {
+        Ok(changes) => changes,
+        Err(e) => {
+            tracing::warn!("Failed to diff config for the audit log: {}", e);
+            return;
+        }
+    };
+    if changes.is_empty() {
+        return;
+    }
+    let entries: Vec<CreateConfigAuditEntry> = changes
+        .into_iter()
+        .map(|change| CreateConfigAuditEntry {
+            path: change.path,
+            old_value: change.old.map(|value| value.to_string()),
+            new_value: change.new.map(|value| value.to_string()),
+        })
+        .collect();
+    if let Err(e) = ConfigAuditEntry::create_many(pool, source, &entries).await {
+        tracing::warn!("Failed to record config changes: {}", e);
+    }
+}
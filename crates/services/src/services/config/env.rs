@@ -0,0 +1,191 @@
+use serde_json::{Map, Value};
+
+use super::Config;
+
+/// Prefix of variables that override config keys, with `__` between nested keys,
+/// e.g. `VK__NOTIFICATIONS__SLACK_WEBHOOK_URL`
+const PREFIX: &str = "VK__";
+
+/// Lowercased config key path and the raw value to set there
+type Override = (Vec<String>, String);
+
+fn parse_overrides(vars: impl Iterator<Item = (String, String)>) -> Vec<Override> {
+    let mut overrides: Vec<Override> = vars
+        .filter_map(|(name, value)| {
+            let path: Vec<String> = name
+                .strip_prefix(PREFIX)?
+                .split("__")
+                .map(str::to_lowercase)
+                .collect();
+            (!path.iter().any(String::is_empty)).then_some((path, value))
+        })
+        .collect();
+    // Environment order is unspecified, so apply overrides in a stable order
+    overrides.sort();
+    overrides
+}
+
+fn env_overrides() -> Vec<Override> {
+    parse_overrides(std::env::vars())
+}
+
+fn var_name(path: &[String]) -> String {
+    format!("{PREFIX}{}", path.join("__").to_uppercase())
+}
+
+/// Matches keys case-insensitively, so `SLACK` finds both `slack` fields and `"SLACK"` map keys
+fn find_key(map: &Map<String, Value>, segment: &str) -> Option<String> {
+    map.keys()
+        .find(|key| key.eq_ignore_ascii_case(segment))
+        .cloned()
+}
+
+fn get_path<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |value, segment| {
+        let map = value.as_object()?;
+        map.get(&find_key(map, segment)?)
+    })
+}
+
+fn get_path_mut<'a>(value: &'a mut Value, path: &[String]) -> Option<&'a mut Value> {
+    path.iter().try_fold(value, |value, segment| {
+        let map = value.as_object_mut()?;
+        let key = find_key(map, segment)?;
+        map.get_mut(&key)
+    })
+}
+
+/// Set `VK__*` environment variables on top of `config`.
+/// Values are read as JSON where the key accepts it (booleans, numbers, lists) and as plain
+/// strings otherwise; keys that don't exist in the config are skipped with a warning.
+pub fn apply_env_overrides(config: Config) -> Config {
+    apply_overrides(config, &env_overrides())
+}
+
+fn apply_overrides(config: Config, overrides: &[Override]) -> Config {
+    if overrides.is_empty() {
+        return config;
+    }
+    let Ok(mut value) = serde_json::to_value(&config) else {
+        return config;
+    };
+
+    for (path, raw) in overrides {
+        if get_path(&value, path).is_none() {
+            tracing::warn!("Ignoring {}: no such config key", var_name(path));
+            continue;
+        }
+        let mut candidates = vec![Value::String(raw.clone())];
+        if let Ok(parsed) = serde_json::from_str::<Value>(raw)
+            && !parsed.is_string()
+        {
+            candidates.insert(0, parsed);
+        }
+        let applied = candidates.into_iter().find_map(|candidate| {
+            let mut next = value.clone();
+            *get_path_mut(&mut next, path)? = candidate;
+            serde_json::from_value::<Config>(next.clone())
+                .is_ok()
+                .then_some(next)
+        });
+        match applied {
+            Some(next) => value = next,
+            None => tracing::warn!(
+                "Ignoring {}: not a valid value for that config key",
+                var_name(path)
+            ),
+        }
+    }
+
+    serde_json::from_value(value).unwrap_or(config)
+}
+
+/// Restore overridden keys to their value in `on_disk`, or the default, so values that came
+/// from the environment aren't written to the config file
+pub fn strip_env_overrides(value: &mut Value, on_disk: Option<&Value>) {
+    strip_overrides(value, on_disk, &env_overrides())
+}
+
+fn strip_overrides(value: &mut Value, on_disk: Option<&Value>, overrides: &[Override]) {
+    if overrides.is_empty() {
+        return;
+    }
+    let default = serde_json::to_value(Config::default()).ok();
+    for (path, _) in overrides {
+        let original = on_disk
+            .and_then(|on_disk| get_path(on_disk, path))
+            .or_else(|| default.as_ref().and_then(|default| get_path(default, path)))
+            .cloned();
+        if let Some(original) = original
+            && let Some(slot) = get_path_mut(value, path)
+        {
+            *slot = original;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overrides(vars: &[(&str, &str)]) -> Vec<Override> {
+        parse_overrides(
+            vars.iter()
+                .map(|(name, value)| (name.to_string(), value.to_string())),
+        )
+    }
+
+    #[test]
+    fn test_parse_overrides() {
+        assert_eq!(
+            overrides(&[
+                ("VK__NOTIFICATIONS__SLACK_WEBHOOK_URL", "https://hooks"),
+                ("VK_PORT", "3000"),
+                ("VK__BROKEN__", "x"),
+            ]),
+            vec![(
+                vec!["notifications".to_string(), "slack_webhook_url".to_string()],
+                "https://hooks".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_apply_overrides_by_type() {
+        let config = apply_overrides(
+            Config::default(),
+            &overrides(&[
+                ("VK__NOTIFICATIONS__SLACK_WEBHOOK_URL", "https://hooks"),
+                ("VK__NOTIFICATIONS__SOUND_ENABLED", "false"),
+                ("VK__GIT_BRANCH_PREFIX", "123"),
+                ("VK__ANALYTICS_ENABLED", "maybe"),
+                ("VK__NO_SUCH_KEY", "1"),
+            ]),
+        );
+        assert_eq!(
+            config.notifications.slack_webhook_url.as_deref(),
+            Some("https://hooks")
+        );
+        assert!(!config.notifications.sound_enabled);
+        // Numbers are kept as text for string keys
+        assert_eq!(config.git_branch_prefix, "123");
+        // Invalid values leave the key as it was
+        assert!(config.analytics_enabled);
+    }
+
+    #[test]
+    fn test_strip_overrides_restores_file_values() {
+        let overrides = overrides(&[("VK__GIT_BRANCH_PREFIX", "ci")]);
+        let config = apply_overrides(Config::default(), &overrides);
+        assert_eq!(config.git_branch_prefix, "ci");
+
+        let mut value = serde_json::to_value(&config).unwrap();
+        let on_disk = serde_json::json!({ "git_branch_prefix": "mine" });
+        strip_overrides(&mut value, Some(&on_disk), &overrides);
+        assert_eq!(value["git_branch_prefix"], "mine");
+
+        let mut value = serde_json::to_value(&config).unwrap();
+        strip_overrides(&mut value, None, &overrides);
+        assert_eq!(value["git_branch_prefix"], "vk");
+    }
+}
@@ -0,0 +1,117 @@
+use anyhow::{Error, anyhow, bail};
+use serde_json::Value;
+
+use super::versions::{v2, v3, v4, v5, v6, v7, v8};
+
+/// Upgrades a config file from one schema version to the next
+type Migration = fn(&str) -> Result<Value, Error>;
+
+/// Applied in order like DB migrations: entry `i` upgrades `v{i + 1}` to `v{i + 2}`.
+/// Schema changes append a step here; existing steps are never edited.
+const MIGRATIONS: &[Migration] = &[
+    |raw| {
+        Ok(serde_json::to_value(v2::Config::from_previous_version(
+            raw,
+        )?)?)
+    },
+    |raw| {
+        Ok(serde_json::to_value(v3::Config::from_previous_version(
+            raw,
+        )?)?)
+    },
+    |raw| {
+        Ok(serde_json::to_value(v4::Config::from_previous_version(
+            raw,
+        )?)?)
+    },
+    |raw| {
+        Ok(serde_json::to_value(v5::Config::from_previous_version(
+            raw,
+        )?)?)
+    },
+    |raw| {
+        Ok(serde_json::to_value(v6::Config::from_previous_version(
+            raw,
+        )?)?)
+    },
+    |raw| {
+        Ok(serde_json::to_value(v7::Config::from_previous_version(
+            raw,
+        )?)?)
+    },
+    |raw| {
+        let old_config = serde_json::from_str::<v7::Config>(raw)?;
+        Ok(serde_json::to_value(v8::Config::from_v7_config(
+            old_config,
+        ))?)
+    },
+];
+
+/// Schema version this app reads and writes
+pub const CURRENT_VERSION: usize = MIGRATIONS.len() + 1;
+
+/// Version recorded in the file; the first schema had no `config_version`
+fn file_version(value: &Value) -> Option<usize> {
+    match value.get("config_version") {
+        None => Some(1),
+        Some(version) => version
+            .as_str()?
+            .strip_prefix('v')?
+            .parse()
+            .ok()
+            .filter(|version| *version >= 1),
+    }
+}
+
+/// Upgrade `value` to [`CURRENT_VERSION`] one step at a time, returning it with the version
+/// it started at. Files from a newer release are returned unchanged.
+pub(super) fn migrate(mut value: Value) -> Result<(Value, usize), Error> {
+    let from = file_version(&value).ok_or_else(|| anyhow!("Unrecognized config version"))?;
+    if from > CURRENT_VERSION {
+        bail!("Config version v{from} is newer than the supported v{CURRENT_VERSION}");
+    }
+    for (index, step) in MIGRATIONS.iter().enumerate().skip(from - 1) {
+        value = step(&value.to_string())
+            .map_err(|e| anyhow!("Migrating config to v{} failed: {e}", index + 2))?;
+        tracing::info!("Migrated config from v{} to v{}", index + 1, index + 2);
+    }
+    Ok((value, from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::config::Config;
+
+    #[test]
+    fn test_current_version_matches_config() {
+        assert_eq!(
+            Config::default().config_version,
+            format!("v{CURRENT_VERSION}")
+        );
+    }
+
+    #[test]
+    fn test_migrates_each_step_from_file_version() {
+        let v6 = serde_json::to_value(v6::Config::default()).unwrap();
+        let (value, from) = migrate(v6).unwrap();
+        assert_eq!(from, 6);
+        assert_eq!(value["config_version"], "v8");
+        assert!(serde_json::from_value::<Config>(value).is_ok());
+
+        let current = serde_json::to_value(Config::default()).unwrap();
+        assert_eq!(
+            migrate(current.clone()).unwrap(),
+            (current, CURRENT_VERSION)
+        );
+    }
+
+    #[test]
+    fn test_rejects_unknown_versions() {
+        let mut value = serde_json::to_value(Config::default()).unwrap();
+        value["config_version"] = "v99".into();
+        assert!(migrate(value.clone()).is_err());
+        value["config_version"] = "latest".into();
+        assert!(migrate(value).is_err());
+    }
+}
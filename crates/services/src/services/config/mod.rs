@@ -1,11 +1,26 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
+mod audit;
 pub mod editor;
+mod env;
+mod migrations;
+mod profiles;
+mod project;
+mod secrets;
+mod transfer;
+mod validation;
 mod versions;
+mod watcher;
 
+pub use audit::record_config_changes;
 pub use editor::EditorOpenError;
+pub use env::apply_env_overrides;
+pub use project::ProjectSettings;
+pub use transfer::{ConfigChange, config_changes, export_config, prepare_import};
+pub use validation::{ConfigIssue, parse_config, validate_config};
+pub use watcher::ConfigWatcher;
 
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -13,12 +28,38 @@ pub enum ConfigError {
     Io(#[from] std::io::Error),
     #[error(transparent)]
     Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Watch(#[from] notify::Error),
     #[error("Validation error: {0}")]
     ValidationError(String),
+    #[error("Config profile '{0}' not found")]
+    ProfileNotFound(String),
+    #[error("Invalid config: {}", validation::format_issues(.0))]
+    Invalid(Vec<ConfigIssue>),
 }
 
 pub type Config = versions::v8::Config;
 pub type NotificationConfig = versions::v8::NotificationConfig;
+pub type NotificationDigest = versions::v8::NotificationDigest;
+pub type NotificationDedup = versions::v8::NotificationDedup;
+pub type NotificationDedupMode = versions::v8::NotificationDedupMode;
+pub type NotificationRule = versions::v8::NotificationRule;
+pub type NotificationOutcome = versions::v8::NotificationOutcome;
+pub type NotificationSeverity = versions::v8::NotificationSeverity;
+pub type NotificationPriority = versions::v8::NotificationPriority;
+pub type EscalationPolicy = versions::v8::EscalationPolicy;
+pub type NotificationSummary = versions::v8::NotificationSummary;
+pub type SummaryFrequency = versions::v8::SummaryFrequency;
+pub type SoundPlayback = versions::v8::SoundPlayback;
+pub type SoundTheme = versions::v8::SoundTheme;
+pub type EventSound = versions::v8::EventSound;
+pub type NotificationChannel = versions::v8::NotificationChannel;
+pub type NotificationEvent = versions::v8::NotificationEvent;
+pub type NotificationOverrides = versions::v8::NotificationOverrides;
+pub type NotificationTemplate = versions::v8::NotificationTemplate;
+pub type NotificationRetryPolicy = versions::v8::NotificationRetryPolicy;
+pub type QuietHours = versions::v8::QuietHours;
+pub type DayOfWeek = versions::v8::DayOfWeek;
 pub type EditorConfig = versions::v8::EditorConfig;
 pub type ThemeMode = versions::v8::ThemeMode;
 pub type SoundFile = versions::v8::SoundFile;
@@ -26,24 +67,89 @@ pub type EditorType = versions::v8::EditorType;
 pub type GitHubConfig = versions::v8::GitHubConfig;
 pub type UiLanguage = versions::v8::UiLanguage;
 pub type ShowcaseState = versions::v8::ShowcaseState;
+pub type ServerConfig = versions::v8::ServerConfig;
+pub type ConfigProfile = versions::v8::ConfigProfile;
+pub type ProjectConfigOverrides = versions::v8::ProjectConfigOverrides;
+pub type TaskUnblockedAction = versions::v8::TaskUnblockedAction;
+pub type AttachmentConfig = versions::v8::AttachmentConfig;
+pub type S3StorageConfig = versions::v8::S3StorageConfig;
+pub type JiraConfig = versions::v8::JiraConfig;
+pub type LinearConfig = versions::v8::LinearConfig;
+pub type AttemptRetryPolicy = versions::v8::AttemptRetryPolicy;
+pub type ExecutionLimits = versions::v8::ExecutionLimits;
+pub type StallAction = versions::v8::StallAction;
+
+/// Copy the config file aside before it's rewritten in another schema
+fn backup_config_file(config_path: &Path, label: &str) {
+    let mut backup_name = config_path.as_os_str().to_owned();
+    backup_name.push(format!(".{label}.bak"));
+    let backup_path = PathBuf::from(backup_name);
+    match std::fs::copy(config_path, &backup_path) {
+        Ok(_) => tracing::info!("Backed up config to {}", backup_path.display()),
+        Err(e) => tracing::warn!("Failed to back up config: {}", e),
+    }
+}
+
+/// Migrate the config to the current schema, resetting malformed sections to their defaults
+/// rather than dropping the whole file
+fn load_config(raw_config: String, config_path: &Path) -> Config {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(&raw_config) else {
+        return Config::from(raw_config);
+    };
+    let value = match migrations::migrate(value.clone()) {
+        Ok((migrated, from)) => {
+            if from < migrations::CURRENT_VERSION {
+                backup_config_file(config_path, &format!("v{from}"));
+            }
+            migrated
+        }
+        Err(e) => {
+            tracing::warn!("{}, loading what can be read", e);
+            backup_config_file(config_path, "unsupported");
+            value
+        }
+    };
 
-/// Will always return config, trying old schemas or eventually returning default
+    let (config, issues) = validation::deserialize_repairing(value);
+    for issue in &issues {
+        tracing::warn!("Config issue, using the default instead: {}", issue);
+    }
+    match config {
+        Some(mut config) => {
+            // Whatever was salvaged is now in the current schema
+            config.config_version = format!("v{}", migrations::CURRENT_VERSION);
+            config
+        }
+        None => Config::from(raw_config),
+    }
+}
+
+/// Will always return config, migrating old schemas or eventually returning default.
+/// `VK__*` environment overrides are applied on top.
 pub async fn load_config_from_file(config_path: &PathBuf) -> Config {
-    match std::fs::read_to_string(config_path) {
-        Ok(raw_config) => Config::from(raw_config),
+    let config = match std::fs::read_to_string(config_path) {
+        Ok(raw_config) => load_config(secrets::reveal(raw_config), config_path),
         Err(_) => {
             tracing::info!("No config file found, creating one");
             Config::default()
         }
-    }
+    };
+    apply_env_overrides(config)
 }
 
-/// Saves the config to the given path
+/// Saves the config to the given path, with credentials encrypted
 pub async fn save_config_to_file(
     config: &Config,
     config_path: &PathBuf,
 ) -> Result<(), ConfigError> {
-    let raw_config = serde_json::to_string_pretty(config)?;
+    let mut value = serde_json::to_value(config)?;
+    // Keys overridden from the environment keep the value they have in the file
+    let on_disk = std::fs::read_to_string(config_path)
+        .ok()
+        .and_then(|raw_config| serde_json::from_str(&raw_config).ok());
+    env::strip_env_overrides(&mut value, on_disk.as_ref());
+    secrets::conceal(&mut value);
+    let raw_config = serde_json::to_string_pretty(&value)?;
     std::fs::write(config_path, raw_config)?;
     Ok(())
 }
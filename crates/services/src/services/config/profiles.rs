@@ -0,0 +1,125 @@
+use super::{Config, ConfigError, ConfigProfile};
+
+impl ConfigProfile {
+    fn capture(config: &Config) -> Self {
+        Self {
+            executor_profile: config.executor_profile.clone(),
+            notifications: config.notifications.clone(),
+            editor: config.editor.clone(),
+        }
+    }
+
+    fn apply_to(&self, config: &mut Config) {
+        config.executor_profile = self.executor_profile.clone();
+        config.notifications = self.notifications.clone();
+        config.editor = self.editor.clone();
+    }
+}
+
+fn profile_name(name: &str) -> Result<&str, ConfigError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ConfigError::ValidationError(
+            "Config profile name cannot be empty".to_string(),
+        ));
+    }
+    Ok(name)
+}
+
+impl Config {
+    /// Store the current executor, notification and editor settings as profile `name`,
+    /// replacing any profile with that name, and mark it active
+    pub fn save_profile(&mut self, name: &str) -> Result<(), ConfigError> {
+        let name = profile_name(name)?;
+        self.config_profiles
+            .insert(name.to_string(), ConfigProfile::capture(self));
+        self.active_config_profile = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Switch to profile `name`, first saving the current settings back into the active profile
+    /// so edits made since the last switch are kept
+    pub fn activate_profile(&mut self, name: &str) -> Result<(), ConfigError> {
+        let name = profile_name(name)?;
+        let profile = self
+            .config_profiles
+            .get(name)
+            .cloned()
+            .ok_or_else(|| ConfigError::ProfileNotFound(name.to_string()))?;
+
+        let current = ConfigProfile::capture(self);
+        if let Some(active) = self.active_config_profile.as_deref()
+            && active != name
+            && let Some(stored) = self.config_profiles.get_mut(active)
+        {
+            *stored = current;
+        }
+
+        profile.apply_to(self);
+        self.active_config_profile = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Remove profile `name`, leaving the current settings as they are
+    pub fn delete_profile(&mut self, name: &str) -> Result<(), ConfigError> {
+        let name = profile_name(name)?;
+        if self.config_profiles.remove(name).is_none() {
+            return Err(ConfigError::ProfileNotFound(name.to_string()));
+        }
+        if self.active_config_profile.as_deref() == Some(name) {
+            self.active_config_profile = None;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+
+    use super::*;
+
+    #[test]
+    fn test_switching_profiles_keeps_edits() {
+        let mut config = Config::default();
+        config.save_profile("work").unwrap();
+        config.executor_profile = ExecutorProfileId::new(BaseCodingAgent::Codex);
+        config.save_profile("personal").unwrap();
+
+        config.activate_profile("work").unwrap();
+        assert_eq!(config.active_config_profile.as_deref(), Some("work"));
+        assert_eq!(
+            config.executor_profile,
+            ExecutorProfileId::new(BaseCodingAgent::ClaudeCode)
+        );
+
+        // Edited while "work" is active, then kept across a round trip
+        config.notifications.sound_enabled = false;
+        config.activate_profile("personal").unwrap();
+        assert_eq!(
+            config.executor_profile,
+            ExecutorProfileId::new(BaseCodingAgent::Codex)
+        );
+        assert!(config.notifications.sound_enabled);
+        config.activate_profile("work").unwrap();
+        assert!(!config.notifications.sound_enabled);
+    }
+
+    #[test]
+    fn test_unknown_and_deleted_profiles() {
+        let mut config = Config::default();
+        assert!(matches!(
+            config.activate_profile("demo"),
+            Err(ConfigError::ProfileNotFound(_))
+        ));
+        assert!(matches!(
+            config.save_profile("  "),
+            Err(ConfigError::ValidationError(_))
+        ));
+
+        config.save_profile("demo").unwrap();
+        config.delete_profile("demo").unwrap();
+        assert!(config.config_profiles.is_empty());
+        assert_eq!(config.active_config_profile, None);
+    }
+}
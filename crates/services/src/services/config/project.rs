@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{Config, ConfigError, NotificationOverrides, ProjectConfigOverrides, validate_config};
+
+/// Everything a project overrides, edited together from the project's settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct ProjectSettings {
+    pub overrides: ProjectConfigOverrides,
+    pub notifications: NotificationOverrides,
+}
+
+impl ProjectConfigOverrides {
+    fn is_empty(&self) -> bool {
+        self.executor_profile.is_none()
+            && self.git_branch_prefix.is_none()
+            && self.auto_archive_after_days.is_none()
+            && self.max_concurrent_attempts.is_none()
+    }
+
+    fn apply_to(&self, config: &mut Config) {
+        if let Some(executor_profile) = &self.executor_profile {
+            config.executor_profile = executor_profile.clone();
+        }
+        if let Some(git_branch_prefix) = &self.git_branch_prefix {
+            config.git_branch_prefix = git_branch_prefix.clone();
+        }
+        if let Some(days) = self.auto_archive_after_days {
+            config.auto_archive_after_days = (days > 0).then_some(days);
+        }
+        if let Some(limit) = self.max_concurrent_attempts {
+            config.max_concurrent_attempts_per_project = (limit > 0).then_some(limit);
+        }
+    }
+}
+
+impl Config {
+    /// Effective settings for a project, with its overrides (if any) applied
+    pub fn for_project(&self, project_id: Option<Uuid>) -> Config {
+        let mut resolved = self.clone();
+        if let Some(overrides) = project_id.and_then(|id| self.project_overrides.get(&id)) {
+            overrides.apply_to(&mut resolved);
+        }
+        resolved.notifications = self.notifications.for_project(project_id);
+        resolved
+    }
+
+    pub fn project_settings(&self, project_id: Uuid) -> ProjectSettings {
+        ProjectSettings {
+            overrides: self
+                .project_overrides
+                .get(&project_id)
+                .cloned()
+                .unwrap_or_default(),
+            notifications: self
+                .notifications
+                .project_overrides
+                .get(&project_id)
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Replace a project's overrides, rejecting them if the project's effective settings
+    /// would be invalid
+    pub fn set_project_settings(
+        &mut self,
+        project_id: Uuid,
+        settings: ProjectSettings,
+    ) -> Result<(), ConfigError> {
+        let mut updated = self.clone();
+        if settings.overrides.is_empty() {
+            updated.project_overrides.remove(&project_id);
+        } else {
+            updated
+                .project_overrides
+                .insert(project_id, settings.overrides);
+        }
+        updated
+            .notifications
+            .project_overrides
+            .insert(project_id, settings.notifications);
+
+        validate_config(&updated.for_project(Some(project_id)))?;
+        *self = updated;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+
+    use super::*;
+
+    #[test]
+    fn test_for_project_layers_overrides() {
+        let project_id = Uuid::new_v4();
+        let mut config = Config::default();
+        let mut settings = ProjectSettings::default();
+        settings.overrides.git_branch_prefix = Some("team".to_string());
+        settings.notifications.sound_enabled = Some(false);
+        config.set_project_settings(project_id, settings).unwrap();
+
+        let resolved = config.for_project(Some(project_id));
+        assert_eq!(resolved.git_branch_prefix, "team");
+        assert!(!resolved.notifications.sound_enabled);
+        assert_eq!(resolved.executor_profile, config.executor_profile);
+
+        let other = config.for_project(Some(Uuid::new_v4()));
+        assert_eq!(other.git_branch_prefix, config.git_branch_prefix);
+        assert!(other.notifications.sound_enabled);
+    }
+
+    #[test]
+    fn test_auto_archive_override() {
+        let project_id = Uuid::new_v4();
+        let mut config = Config {
+            auto_archive_after_days: Some(30),
+            ..Config::default()
+        };
+        let mut settings = ProjectSettings::default();
+        settings.overrides.auto_archive_after_days = Some(0);
+        config.set_project_settings(project_id, settings).unwrap();
+
+        assert_eq!(
+            config.for_project(Some(project_id)).auto_archive_after_days,
+            None
+        );
+        assert_eq!(
+            config
+                .for_project(Some(Uuid::new_v4()))
+                .auto_archive_after_days,
+            Some(30)
+        );
+    }
+
+    #[test]
+    fn test_concurrency_override() {
+        let project_id = Uuid::new_v4();
+        let mut config = Config {
+            max_concurrent_attempts_per_project: Some(2),
+            ..Config::default()
+        };
+        let mut settings = ProjectSettings::default();
+        settings.overrides.max_concurrent_attempts = Some(0);
+        config.set_project_settings(project_id, settings).unwrap();
+
+        assert_eq!(
+            config
+                .for_project(Some(project_id))
+                .max_concurrent_attempts_per_project,
+            None
+        );
+        assert_eq!(
+            config
+                .for_project(Some(Uuid::new_v4()))
+                .max_concurrent_attempts_per_project,
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_set_project_settings() {
+        let project_id = Uuid::new_v4();
+        let mut config = Config::default();
+        let mut settings = ProjectSettings::default();
+        settings.overrides.executor_profile = Some(ExecutorProfileId::new(BaseCodingAgent::Amp));
+        config
+            .set_project_settings(project_id, settings.clone())
+            .unwrap();
+        assert_eq!(
+            config
+                .project_settings(project_id)
+                .overrides
+                .executor_profile,
+            Some(ExecutorProfileId::new(BaseCodingAgent::Amp))
+        );
+
+        settings.overrides.git_branch_prefix = Some("bad/prefix".to_string());
+        assert!(matches!(
+            config.set_project_settings(project_id, settings),
+            Err(ConfigError::Invalid(_))
+        ));
+        assert!(
+            config.project_overrides[&project_id]
+                .git_branch_prefix
+                .is_none()
+        );
+
+        config
+            .set_project_settings(project_id, ProjectSettings::default())
+            .unwrap();
+        assert!(!config.project_overrides.contains_key(&project_id));
+    }
+}
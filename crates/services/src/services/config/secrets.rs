@@ -0,0 +1,464 @@
+use std::{io::Write, sync::OnceLock};
+
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce, aead::Aead};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use hmac::{Hmac, Mac};
+use rand::{RngCore, rngs::OsRng};
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use sha2::Sha256;
+
+/// Config keys holding credentials, encrypted wherever they appear in the config file
+const SECRET_KEYS: &[&str] = &[
+    "slack_webhook_url",
+    "slack_bot_token",
+    "webhook_urls",
+    "webhook_secret",
+    "ntfy_token",
+    "pushover_app_token",
+    "pushover_user_key",
+    "gotify_app_token",
+    "matrix_access_token",
+    "mattermost_webhook_url",
+    "rocketchat_webhook_url",
+    "zulip_api_key",
+    "irc_sasl_password",
+    "api_token",
+    "api_key",
+    "access_key_id",
+    "secret_access_key",
+];
+
+/// Stands in for credentials left out of an export
+pub const REDACTED: &str = "[redacted]";
+
+/// Marks an encrypted value: `enc:v1:` followed by base64 of the nonce and ciphertext
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+const NONCE_LEN: usize = 12;
+
+/// Takes precedence over the keychain, e.g. for headless or containerized setups
+const PASSPHRASE_VAR: &str = "VK_SECRETS_PASSPHRASE";
+const PASSPHRASE_SALT: &[u8] = b"vibe-kanban-config-secrets";
+const PASSPHRASE_ROUNDS: u32 = 210_000;
+
+const KEYCHAIN_SERVICE: &str = "vibe-kanban";
+const KEYCHAIN_ACCOUNT: &str = "config-secrets";
+
+static CIPHER: OnceLock<Option<Aes256Gcm>> = OnceLock::new();
+
+fn pbkdf2_sha256(passphrase: &[u8], salt: &[u8], rounds: u32) -> [u8; 32] {
+    let mac =
+        <Hmac<Sha256> as Mac>::new_from_slice(passphrase).expect("HMAC accepts any key length");
+    let mut block = mac.clone();
+    block.update(salt);
+    block.update(&1u32.to_be_bytes());
+    let mut u = block.finalize().into_bytes();
+    let mut key = u;
+    for _ in 1..rounds {
+        let mut block = mac.clone();
+        block.update(&u);
+        u = block.finalize().into_bytes();
+        key.iter_mut().zip(u.iter()).for_each(|(k, b)| *k ^= b);
+    }
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&key);
+    out
+}
+
+fn random_key() -> [u8; 32] {
+    let mut key = [0u8; 32];
+    OsRng.fill_bytes(&mut key);
+    key
+}
+
+fn decode_key(encoded: &str) -> Option<[u8; 32]> {
+    STANDARD.decode(encoded.trim()).ok()?.try_into().ok()
+}
+
+#[cfg(target_os = "macos")]
+fn keychain_key() -> Option<[u8; 32]> {
+    use std::process::Command;
+
+    let lookup = Command::new("security")
+        .args(["find-generic-password", "-s", KEYCHAIN_SERVICE])
+        .args(["-a", KEYCHAIN_ACCOUNT, "-w"])
+        .output()
+        .ok()?;
+    if lookup.status.success() {
+        return decode_key(&String::from_utf8_lossy(&lookup.stdout));
+    }
+
+    let key = random_key();
+    let stored = Command::new("security")
+        .args(["add-generic-password", "-s", KEYCHAIN_SERVICE])
+        .args(["-a", KEYCHAIN_ACCOUNT, "-w", &STANDARD.encode(key)])
+        .status()
+        .ok()?;
+    stored.success().then_some(key)
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn keychain_key() -> Option<[u8; 32]> {
+    use std::process::{Command, Stdio};
+
+    // libsecret's CLI, backed by GNOME Keyring or KWallet
+    let lookup = Command::new("secret-tool")
+        .args(["lookup", "service", KEYCHAIN_SERVICE])
+        .args(["account", KEYCHAIN_ACCOUNT])
+        .output()
+        .ok()?;
+    if lookup.status.success() && !lookup.stdout.is_empty() {
+        return decode_key(&String::from_utf8_lossy(&lookup.stdout));
+    }
+
+    let key = random_key();
+    let mut store = Command::new("secret-tool")
+        .args(["store", "--label=Vibe Kanban config secrets"])
+        .args(["service", KEYCHAIN_SERVICE, "account", KEYCHAIN_ACCOUNT])
+        .stdin(Stdio::piped())
+        .spawn()
+        .ok()?;
+    store
+        .stdin
+        .take()?
+        .write_all(STANDARD.encode(key).as_bytes())
+        .ok()?;
+    store.wait().ok()?.success().then_some(key)
+}
+
+#[cfg(not(unix))]
+fn keychain_key() -> Option<[u8; 32]> {
+    None
+}
+
+/// Key kept next to the config, for systems without a usable keychain
+fn key_file_key() -> Option<[u8; 32]> {
+    let path = utils::assets::secrets_key_path();
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => decode_key(&contents),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let key = random_key();
+            let mut options = std::fs::OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            let mut file = options.open(&path).ok()?;
+            file.write_all(STANDARD.encode(key).as_bytes()).ok()?;
+            tracing::info!("Generated config secrets key at {}", path.display());
+            Some(key)
+        }
+        Err(e) => {
+            tracing::warn!("Failed to read config secrets key: {}", e);
+            None
+        }
+    }
+}
+
+fn resolve_key() -> Option<[u8; 32]> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_VAR)
+        && !passphrase.is_empty()
+    {
+        return Some(pbkdf2_sha256(
+            passphrase.as_bytes(),
+            PASSPHRASE_SALT,
+            PASSPHRASE_ROUNDS,
+        ));
+    }
+    keychain_key().or_else(key_file_key)
+}
+
+fn cipher() -> Option<&'static Aes256Gcm> {
+    CIPHER
+        .get_or_init(|| {
+            let cipher = resolve_key().map(|key| Aes256Gcm::new(&key.into()));
+            if cipher.is_none() {
+                tracing::warn!("No key for config secrets available, storing them in plaintext");
+            }
+            cipher
+        })
+        .as_ref()
+}
+
+fn encrypt(cipher: &Aes256Gcm, plaintext: &str) -> Option<String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    let ciphertext = cipher
+        .encrypt(&Nonce::from(nonce), plaintext.as_bytes())
+        .ok()?;
+    Some(format!(
+        "{ENCRYPTED_PREFIX}{}",
+        STANDARD.encode([nonce.as_slice(), &ciphertext].concat())
+    ))
+}
+
+fn decrypt(cipher: &Aes256Gcm, encrypted: &str) -> Option<String> {
+    let bytes = STANDARD
+        .decode(encrypted.strip_prefix(ENCRYPTED_PREFIX)?)
+        .ok()?;
+    if bytes.len() < NONCE_LEN {
+        return None;
+    }
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    let nonce: [u8; NONCE_LEN] = nonce.try_into().ok()?;
+    let plaintext = cipher.decrypt(&Nonce::from(nonce), ciphertext).ok()?;
+    String::from_utf8(plaintext).ok()
+}
+
+/// Apply `f` to each string under a secret key, dropping strings it returns `None` for
+fn map_secrets(value: &mut Value, f: &mut impl FnMut(&str) -> Option<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if !is_secret_key(key) {
+                    map_secrets(value, f);
+                    continue;
+                }
+                match value {
+                    Value::String(secret) => {
+                        *value = f(secret).map(Value::String).unwrap_or(Value::Null)
+                    }
+                    Value::Array(items) => {
+                        *items = items
+                            .iter()
+                            .filter_map(|item| match item {
+                                Value::String(secret) => f(secret).map(Value::String),
+                                other => Some(other.clone()),
+                            })
+                            .collect()
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| map_secrets(item, f)),
+        _ => {}
+    }
+}
+
+fn conceal_with(value: &mut Value, cipher: &Aes256Gcm) {
+    map_secrets(value, &mut |secret| {
+        if secret.starts_with(ENCRYPTED_PREFIX) {
+            Some(secret.to_string())
+        } else {
+            encrypt(cipher, secret)
+        }
+    });
+}
+
+fn reveal_with(value: &mut Value, cipher: Option<&Aes256Gcm>) {
+    map_secrets(value, &mut |secret| {
+        if !secret.starts_with(ENCRYPTED_PREFIX) {
+            return Some(secret.to_string());
+        }
+        let plaintext = cipher.and_then(|cipher| decrypt(cipher, secret));
+        if plaintext.is_none() {
+            tracing::warn!("Could not decrypt a config secret, it needs to be entered again");
+        }
+        plaintext
+    });
+}
+
+/// For credentials that aren't optional: a secret that couldn't be decrypted is read back as
+/// `null`, and becomes blank rather than failing the section it belongs to
+pub(super) fn blank_if_lost<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<String, D::Error> {
+    Ok(Option::<String>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+pub fn is_secret_key(key: &str) -> bool {
+    SECRET_KEYS.contains(&key)
+}
+
+/// Replace credentials with [`REDACTED`]
+pub fn redact(value: &mut Value) {
+    map_secrets(value, &mut |_| Some(REDACTED.to_string()));
+}
+
+fn is_redacted(value: &Value) -> bool {
+    match value {
+        Value::String(secret) => secret == REDACTED,
+        Value::Array(items) => items.iter().any(is_redacted),
+        _ => false,
+    }
+}
+
+/// Put back `current`'s credentials wherever `imported` has a redacted placeholder
+pub fn restore_redacted(imported: &mut Value, current: Option<&Value>) {
+    match imported {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let current = current.and_then(|current| current.get(key));
+                if !is_secret_key(key) || !is_redacted(value) {
+                    restore_redacted(value, current);
+                    continue;
+                }
+                let missing = if value.is_array() {
+                    Value::Array(Vec::new())
+                } else {
+                    Value::Null
+                };
+                *value = current.cloned().unwrap_or(missing);
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                restore_redacted(item, current.and_then(|current| current.get(index)));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Encrypt credentials in a serialized config before it's written to disk
+pub fn conceal(value: &mut Value) {
+    if let Some(cipher) = cipher() {
+        conceal_with(value, cipher);
+    }
+}
+
+/// Decrypt credentials in raw config file contents, leaving anything that isn't JSON as is
+pub fn reveal(raw_config: String) -> String {
+    let Ok(mut value) = serde_json::from_str::<Value>(&raw_config) else {
+        return raw_config;
+    };
+    reveal_with(&mut value, cipher());
+    serde_json::to_string(&value).unwrap_or(raw_config)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn test_cipher(seed: u8) -> Aes256Gcm {
+        Aes256Gcm::new(&[seed; 32].into())
+    }
+
+    #[test]
+    fn test_secrets_round_trip() {
+        let cipher = test_cipher(1);
+        let plain = json!({
+            "theme": "DARK",
+            "notifications": {
+                "slack_webhook_url": "https://hooks.slack.com/x",
+                "webhook_urls": ["https://a", "https://b"],
+                "ntfy_token": null,
+            },
+            "config_profiles": { "work": { "notifications": { "zulip_api_key": "k" } } },
+        });
+
+        let mut value = plain.clone();
+        conceal_with(&mut value, &cipher);
+        let slack = value["notifications"]["slack_webhook_url"]
+            .as_str()
+            .unwrap();
+        assert!(slack.starts_with(ENCRYPTED_PREFIX));
+        assert!(
+            value["notifications"]["webhook_urls"][1]
+                .as_str()
+                .unwrap()
+                .starts_with(ENCRYPTED_PREFIX)
+        );
+        assert_eq!(value["theme"], "DARK");
+
+        // Already encrypted values are left alone
+        let mut again = value.clone();
+        conceal_with(&mut again, &cipher);
+        assert_eq!(again, value);
+
+        reveal_with(&mut value, Some(&cipher));
+        assert_eq!(value, plain);
+    }
+
+    #[test]
+    fn test_s3_credentials_are_secret() {
+        let plain = json!({
+            "attachments": {
+                "s3": {
+                    "endpoint": "https://s3.us-east-1.amazonaws.com",
+                    "bucket": "attachments",
+                    "access_key_id": "AKIAEXAMPLE",
+                    "secret_access_key": "wJalrXUtnFEMI",
+                },
+            },
+        });
+
+        let mut redacted = plain.clone();
+        redact(&mut redacted);
+        assert_eq!(redacted["attachments"]["s3"]["access_key_id"], REDACTED);
+        assert_eq!(redacted["attachments"]["s3"]["secret_access_key"], REDACTED);
+        assert_eq!(redacted["attachments"]["s3"]["bucket"], "attachments");
+        restore_redacted(&mut redacted, Some(&plain));
+        assert_eq!(redacted, plain);
+
+        let cipher = test_cipher(1);
+        let mut value = plain.clone();
+        conceal_with(&mut value, &cipher);
+        for key in ["access_key_id", "secret_access_key"] {
+            assert!(
+                value["attachments"]["s3"][key]
+                    .as_str()
+                    .unwrap()
+                    .starts_with(ENCRYPTED_PREFIX)
+            );
+        }
+        reveal_with(&mut value, Some(&cipher));
+        assert_eq!(value, plain);
+    }
+
+    #[test]
+    fn test_wrong_key_drops_secrets() {
+        let mut value = json!({ "webhook_secret": "s", "webhook_urls": ["https://a"] });
+        conceal_with(&mut value, &test_cipher(1));
+        reveal_with(&mut value, Some(&test_cipher(2)));
+        assert_eq!(value, json!({ "webhook_secret": null, "webhook_urls": [] }));
+    }
+
+    #[test]
+    fn test_wrong_key_keeps_credential_sections() {
+        use crate::services::config::versions::v8::{JiraConfig, LinearConfig, S3StorageConfig};
+
+        let mut value = json!({
+            "s3": {
+                "endpoint": "https://s3.us-east-1.amazonaws.com",
+                "bucket": "attachments",
+                "access_key_id": "AKIAEXAMPLE",
+                "secret_access_key": "wJalrXUtnFEMI",
+            },
+            "jira": {
+                "base_url": "https://team.atlassian.net",
+                "email": "me@example.com",
+                "api_token": "t",
+            },
+            "linear": { "api_key": "k" },
+        });
+        conceal_with(&mut value, &test_cipher(1));
+        reveal_with(&mut value, Some(&test_cipher(2)));
+
+        let s3: S3StorageConfig = serde_json::from_value(value["s3"].clone()).unwrap();
+        assert_eq!(s3.bucket, "attachments");
+        assert_eq!(s3.endpoint, "https://s3.us-east-1.amazonaws.com");
+        assert!(s3.access_key_id.is_empty() && s3.secret_access_key.is_empty());
+        let jira: JiraConfig = serde_json::from_value(value["jira"].clone()).unwrap();
+        assert_eq!(jira.base_url, "https://team.atlassian.net");
+        assert_eq!(jira.email, "me@example.com");
+        assert!(jira.api_token.is_empty());
+        let linear: LinearConfig = serde_json::from_value(value["linear"].clone()).unwrap();
+        assert!(linear.api_key.is_empty());
+    }
+
+    #[test]
+    fn test_pbkdf2_sha256() {
+        // RFC 7914 section 11 test vector
+        assert_eq!(
+            pbkdf2_sha256(b"passwd", b"salt", 1),
+            [
+                0x55, 0xac, 0x04, 0x6e, 0x56, 0xe3, 0x08, 0x9f, 0xec, 0x16, 0x91, 0xc2, 0x25, 0x44,
+                0xb6, 0x05, 0xf9, 0x41, 0x85, 0x21, 0x6d, 0xde, 0x04, 0x65, 0xe6, 0x8b, 0x9d, 0x57,
+                0xc2, 0x0d, 0xac, 0xbc
+            ]
+        );
+    }
+}
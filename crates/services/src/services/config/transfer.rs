@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use ts_rs::TS;
+
+use super::{Config, ConfigError, apply_env_overrides, migrations, parse_config, secrets};
+
+/// A key whose value differs between two configs; credentials are shown redacted
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ConfigChange {
+    /// Dotted path to the key, e.g. `notifications.sound_enabled`
+    pub path: String,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+/// The config as JSON for moving to another machine, with credentials redacted unless
+/// `include_secrets` is set
+pub fn export_config(config: &Config, include_secrets: bool) -> Result<Value, ConfigError> {
+    let mut value = serde_json::to_value(config)?;
+    if !include_secrets {
+        secrets::redact(&mut value);
+    }
+    Ok(value)
+}
+
+fn diff(
+    path: &mut Vec<String>,
+    old: Option<&Value>,
+    new: Option<&Value>,
+    out: &mut Vec<ConfigChange>,
+) {
+    if let (Some(Value::Object(old)), Some(Value::Object(new))) = (old, new) {
+        let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        for key in keys {
+            path.push(key.clone());
+            diff(path, old.get(key), new.get(key), out);
+            path.pop();
+        }
+        return;
+    }
+    if old == new {
+        return;
+    }
+    let shown = |value: Option<&Value>| {
+        value.map(|value| {
+            if path.iter().any(|key| secrets::is_secret_key(key)) {
+                Value::String(secrets::REDACTED.to_string())
+            } else {
+                value.clone()
+            }
+        })
+    };
+    out.push(ConfigChange {
+        path: path.join("."),
+        old: shown(old),
+        new: shown(new),
+    });
+}
+
+fn changes(old: &Value, new: &Value) -> Vec<ConfigChange> {
+    let mut out = Vec::new();
+    diff(&mut Vec::new(), Some(old), Some(new), &mut out);
+    out
+}
+
+/// Every key whose value differs between `old` and `new`
+pub fn config_changes(old: &Config, new: &Config) -> Result<Vec<ConfigChange>, ConfigError> {
+    Ok(changes(
+        &serde_json::to_value(old)?,
+        &serde_json::to_value(new)?,
+    ))
+}
+
+/// Validate an exported config against `current`, returning the config to apply and what it
+/// would change. Redacted credentials keep their current values, and the import may come from
+/// an older version.
+pub fn prepare_import(
+    current: &Config,
+    imported: Value,
+) -> Result<(Config, Vec<ConfigChange>), ConfigError> {
+    let current_value = serde_json::to_value(current)?;
+    let (mut imported, _) =
+        migrations::migrate(imported).map_err(|e| ConfigError::ValidationError(e.to_string()))?;
+    secrets::restore_redacted(&mut imported, Some(&current_value));
+
+    let new_config = apply_env_overrides(parse_config(imported)?);
+    let changes = changes(&current_value, &serde_json::to_value(&new_config)?);
+    Ok((new_config, changes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_keeps_redacted_secrets() {
+        let mut current = Config::default();
+        current.notifications.slack_webhook_url = Some("https://hooks.slack.com/x".to_string());
+        current.notifications.webhook_urls = vec!["https://example.com/hook".to_string()];
+
+        let exported = export_config(&current, false).unwrap();
+        assert_eq!(
+            exported["notifications"]["slack_webhook_url"],
+            secrets::REDACTED
+        );
+
+        let (imported, changes) = prepare_import(&current, exported).unwrap();
+        assert!(changes.is_empty());
+        assert_eq!(
+            imported.notifications.slack_webhook_url,
+            current.notifications.slack_webhook_url
+        );
+        assert_eq!(
+            imported.notifications.webhook_urls,
+            current.notifications.webhook_urls
+        );
+    }
+
+    #[test]
+    fn test_reports_changes() {
+        let current = Config::default();
+        let mut exported = export_config(&current, true).unwrap();
+        exported["git_branch_prefix"] = "team".into();
+        exported["notifications"]["slack_webhook_url"] = "https://hooks.slack.com/y".into();
+
+        let (_, changes) = prepare_import(&current, exported).unwrap();
+        let paths: Vec<&str> = changes.iter().map(|change| change.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["git_branch_prefix", "notifications.slack_webhook_url"]
+        );
+        assert_eq!(changes[0].new, Some("team".into()));
+        assert_eq!(changes[1].new, Some(secrets::REDACTED.into()));
+    }
+}
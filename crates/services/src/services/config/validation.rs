@@ -0,0 +1,484 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_path_to_error::Segment;
+use ts_rs::TS;
+
+use super::{Config, ConfigError};
+
+/// Bounds the repair loop; each pass fixes one key
+const MAX_REPAIRS: usize = 100;
+
+/// A problem with one config key, with enough detail to fix it
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ConfigIssue {
+    /// Dotted path to the key, e.g. `notifications.sound_file`
+    pub path: String,
+    pub message: String,
+    /// What the key accepts, when known
+    pub expected: Option<String>,
+    pub suggestion: Option<String>,
+}
+
+impl std::fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " ({suggestion})")?;
+        }
+        Ok(())
+    }
+}
+
+pub(super) fn format_issues(issues: &[ConfigIssue]) -> String {
+    issues
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[derive(Debug, Clone)]
+enum Step {
+    Key(String),
+    Index(usize),
+}
+
+fn steps(path: &serde_path_to_error::Path) -> Vec<Step> {
+    path.iter()
+        .filter_map(|segment| match segment {
+            Segment::Seq { index } => Some(Step::Index(*index)),
+            Segment::Map { key } | Segment::Enum { variant: key } => Some(Step::Key(key.clone())),
+            Segment::Unknown => None,
+        })
+        .collect()
+}
+
+fn display_path(steps: &[Step]) -> String {
+    if steps.is_empty() {
+        return "(root)".to_string();
+    }
+    steps
+        .iter()
+        .map(|step| match step {
+            Step::Key(key) => key.clone(),
+            Step::Index(index) => index.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn get<'a>(value: &'a Value, steps: &[Step]) -> Option<&'a Value> {
+    steps.iter().try_fold(value, |value, step| match step {
+        Step::Key(key) => value.get(key),
+        Step::Index(index) => value.get(index),
+    })
+}
+
+fn get_mut<'a>(value: &'a mut Value, steps: &[Step]) -> Option<&'a mut Value> {
+    steps.iter().try_fold(value, |value, step| match step {
+        Step::Key(key) => value.get_mut(key),
+        Step::Index(index) => value.get_mut(index),
+    })
+}
+
+/// Text between the first pair of backticks after `marker`
+fn quoted_after<'a>(message: &'a str, marker: &str) -> Option<&'a str> {
+    let rest = &message[message.find(marker)? + marker.len()..];
+    let rest = rest.strip_prefix('`')?;
+    Some(&rest[..rest.find('`')?])
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(!ca.eq_ignore_ascii_case(cb));
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(previous + 1).min(row[j] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Closest of the backticked options in a serde "expected one of" list
+fn closest_option(given: &str, expected: &str) -> Option<String> {
+    expected
+        .split('`')
+        .skip(1)
+        .step_by(2)
+        .map(|option| (edit_distance(given, option), option))
+        .filter(|(distance, option)| *distance <= option.len().max(given.len()) / 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, option)| option.to_string())
+}
+
+fn issue_for(mut steps: Vec<Step>, error: &serde_json::Error) -> (Vec<Step>, ConfigIssue) {
+    let message = error.to_string();
+    let expected = message
+        .split_once(", expected ")
+        .map(|(_, expected)| expected.to_string());
+
+    if let Some(field) = quoted_after(&message, "missing field ") {
+        steps.push(Step::Key(field.to_string()));
+    }
+    let suggestion = if let Some(given) = quoted_after(&message, "unknown variant ") {
+        expected
+            .as_deref()
+            .and_then(|expected| closest_option(given, expected))
+            .map(|option| format!("Did you mean `{option}`?"))
+    } else if message.starts_with("missing field") {
+        Some("Add the key, or remove its section to use the defaults".to_string())
+    } else {
+        // Struct and enum expectations name Rust types, which doesn't help in a JSON file
+        expected
+            .as_deref()
+            .filter(|expected| expected.starts_with("a ") || expected.starts_with("an "))
+            .map(|expected| format!("Use {expected}"))
+    };
+
+    let issue = ConfigIssue {
+        path: display_path(&steps),
+        message,
+        expected,
+        suggestion,
+    };
+    (steps, issue)
+}
+
+/// Replace the value at `steps` with its default, or drop it when there is no default
+/// (e.g. a map entry). Returns false when nothing could be changed.
+fn repair(value: &mut Value, defaults: &Value, steps: &[Step]) -> bool {
+    let Some((last, parent_steps)) = steps.split_last() else {
+        *value = defaults.clone();
+        return true;
+    };
+    let default = get(defaults, steps).cloned();
+    let Some(parent) = get_mut(value, parent_steps) else {
+        return false;
+    };
+    match (parent, last, default) {
+        (Value::Object(map), Step::Key(key), Some(default)) => {
+            map.insert(key.clone(), default);
+            true
+        }
+        (Value::Object(map), Step::Key(key), None) => map.remove(key).is_some(),
+        (Value::Array(items), Step::Index(index), _) if *index < items.len() => {
+            items.remove(*index);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Deserialize `value`, resetting each malformed key to its default so the rest still loads.
+/// Returns the config, if it could be salvaged, and an issue for every key that was reset.
+pub(super) fn deserialize_repairing(mut value: Value) -> (Option<Config>, Vec<ConfigIssue>) {
+    let defaults = serde_json::to_value(Config::default()).unwrap_or(Value::Null);
+    let mut issues = Vec::new();
+    for _ in 0..MAX_REPAIRS {
+        match serde_path_to_error::deserialize::<_, Config>(&value) {
+            Ok(config) => return (Some(config), issues),
+            Err(error) => {
+                let (steps, issue) = issue_for(steps(error.path()), error.inner());
+                issues.push(issue);
+                if !repair(&mut value, &defaults, &steps) {
+                    break;
+                }
+            }
+        }
+    }
+    (None, issues)
+}
+
+fn is_http_url(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+/// Settings that deserialize fine but would break the app if applied
+fn semantic_issues(config: &Config) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+
+    if !utils::git::is_valid_branch_prefix(&config.git_branch_prefix) {
+        issues.push(ConfigIssue {
+            path: "git_branch_prefix".to_string(),
+            message: "Invalid git branch prefix".to_string(),
+            expected: Some("a valid git branch name component without slashes".to_string()),
+            suggestion: None,
+        });
+    }
+
+    if let Some(url) = config.server.public_base_url.as_deref().map(str::trim)
+        && !url.is_empty()
+        && !is_http_url(url)
+    {
+        issues.push(ConfigIssue {
+            path: "server.public_base_url".to_string(),
+            message: "Invalid public base URL".to_string(),
+            expected: Some("a URL starting with http:// or https://".to_string()),
+            suggestion: Some(format!("Try `https://{url}`")),
+        });
+    }
+
+    if let Some(jira) = &config.jira
+        && !is_http_url(jira.base_url.trim())
+    {
+        issues.push(ConfigIssue {
+            path: "jira.base_url".to_string(),
+            message: "Invalid Jira site URL".to_string(),
+            expected: Some(
+                "a URL starting with https://, e.g. https://team.atlassian.net".to_string(),
+            ),
+            suggestion: None,
+        });
+    }
+
+    if config.auto_archive_after_days == Some(0) {
+        issues.push(ConfigIssue {
+            path: "auto_archive_after_days".to_string(),
+            message: "Auto-archiving needs at least one day".to_string(),
+            expected: Some("a number of days of at least 1, or null to turn it off".to_string()),
+            suggestion: None,
+        });
+    }
+
+    for (path, limit) in [
+        ("max_concurrent_attempts", config.max_concurrent_attempts),
+        (
+            "max_concurrent_attempts_per_project",
+            config.max_concurrent_attempts_per_project,
+        ),
+    ] {
+        if limit == Some(0) {
+            issues.push(ConfigIssue {
+                path: path.to_string(),
+                message: "A concurrency limit needs to allow at least one attempt".to_string(),
+                expected: Some("a number of at least 1, or null for no limit".to_string()),
+                suggestion: None,
+            });
+        }
+    }
+
+    if config.attempt_retry.max_delay_secs < config.attempt_retry.initial_delay_secs {
+        issues.push(ConfigIssue {
+            path: "attempt_retry.max_delay_secs".to_string(),
+            message: "The longest retry delay is shorter than the first".to_string(),
+            expected: Some(format!(
+                "a number of seconds of at least {}",
+                config.attempt_retry.initial_delay_secs
+            )),
+            suggestion: None,
+        });
+    }
+
+    for (profile, limits) in &config.execution_limits {
+        for (field, minutes) in [
+            ("max_duration_minutes", limits.max_duration_minutes),
+            ("stall_after_minutes", limits.stall_after_minutes),
+        ] {
+            if minutes == Some(0) {
+                issues.push(ConfigIssue {
+                    path: format!("execution_limits.{profile}.{field}"),
+                    message: "A run limit needs at least one minute".to_string(),
+                    expected: Some(
+                        "a number of minutes of at least 1, or null for no limit".to_string(),
+                    ),
+                    suggestion: None,
+                });
+            }
+        }
+    }
+
+    if config.sandbox.enabled && config.sandbox.image.trim().is_empty() {
+        issues.push(ConfigIssue {
+            path: "sandbox.image".to_string(),
+            message: "The sandbox needs an image to run agents in".to_string(),
+            expected: Some("an image name, e.g. node:22-bookworm".to_string()),
+            suggestion: None,
+        });
+    }
+    if config.sandbox.cpus.is_some_and(|cpus| cpus <= 0.0) {
+        issues.push(ConfigIssue {
+            path: "sandbox.cpus".to_string(),
+            message: "The sandbox needs some CPU to run".to_string(),
+            expected: Some("a number above 0, or null for no limit".to_string()),
+            suggestion: None,
+        });
+    }
+    if config
+        .sandbox
+        .memory_mb
+        .is_some_and(|memory_mb| memory_mb < 6)
+    {
+        issues.push(ConfigIssue {
+            path: "sandbox.memory_mb".to_string(),
+            message: "Docker and Podman need at least 6 MB to start a container".to_string(),
+            expected: Some("a number of megabytes of at least 6, or null for no limit".to_string()),
+            suggestion: None,
+        });
+    }
+
+    let limits = &config.process_limits;
+    for (field, value, unit) in [
+        (
+            "cpu_percent",
+            limits.cpu_percent,
+            "a percentage of one core",
+        ),
+        ("memory_mb", limits.memory_mb, "a number of megabytes"),
+        (
+            "max_processes",
+            limits.max_processes,
+            "a number of processes",
+        ),
+    ] {
+        if value == Some(0) {
+            issues.push(ConfigIssue {
+                path: format!("process_limits.{field}"),
+                message: "A limit of 0 would stop every process from running".to_string(),
+                expected: Some(format!("{unit} above 0, or null for no limit")),
+                suggestion: None,
+            });
+        }
+    }
+    if limits.nice.is_some_and(|nice| nice > 19) {
+        issues.push(ConfigIssue {
+            path: "process_limits.nice".to_string(),
+            message: "Niceness goes up to 19".to_string(),
+            expected: Some("a number from 0 to 19".to_string()),
+            suggestion: Some("19".to_string()),
+        });
+    }
+
+    for (index, pattern) in config.approval_gates.command_patterns.iter().enumerate() {
+        if let Err(e) = regex::Regex::new(pattern) {
+            issues.push(ConfigIssue {
+                path: format!("approval_gates.command_patterns.{index}"),
+                message: format!("Invalid pattern: {e}"),
+                expected: Some("a regular expression, e.g. rm\\s+-rf".to_string()),
+                suggestion: None,
+            });
+        }
+    }
+
+    if config.max_review_rounds == 0 {
+        issues.push(ConfigIssue {
+            path: "max_review_rounds".to_string(),
+            message: "Attempts with a reviewer would never be reviewed".to_string(),
+            expected: Some("a number of rounds of at least 1".to_string()),
+            suggestion: Some("3".to_string()),
+        });
+    }
+
+    for (index, url) in config.notifications.webhook_urls.iter().enumerate() {
+        if !is_http_url(url.trim()) {
+            issues.push(ConfigIssue {
+                path: format!("notifications.webhook_urls.{index}"),
+                message: "Invalid webhook URL".to_string(),
+                expected: Some("a URL starting with http:// or https://".to_string()),
+                suggestion: None,
+            });
+        }
+    }
+
+    issues
+}
+
+/// Check settings that deserialize fine but would break the app if applied
+pub fn validate_config(config: &Config) -> Result<(), ConfigError> {
+    // Not an error, since other profiles can still be gated
+    if let Some(warning) = config
+        .approval_gates
+        .unenforced_warning(config.executor_profile.executor)
+    {
+        tracing::warn!("{} (the default profile)", warning);
+    }
+    let issues = semantic_issues(config);
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(ConfigError::Invalid(issues))
+    }
+}
+
+/// Strictly parse and validate a config, reporting every problem found
+pub fn parse_config(value: Value) -> Result<Config, ConfigError> {
+    let (config, issues) = deserialize_repairing(value);
+    match config {
+        Some(config) if issues.is_empty() => {
+            validate_config(&config)?;
+            Ok(config)
+        }
+        Some(config) => {
+            let mut issues = issues;
+            issues.extend(semantic_issues(&config));
+            Err(ConfigError::Invalid(issues))
+        }
+        None => Err(ConfigError::Invalid(issues)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn config_with(path: &[&str], value: Value) -> Value {
+        let mut config = serde_json::to_value(Config::default()).unwrap();
+        let (last, parents) = path.split_last().unwrap();
+        let parent = parents
+            .iter()
+            .fold(&mut config, |config, key| config.get_mut(*key).unwrap());
+        parent[*last] = value;
+        config
+    }
+
+    #[test]
+    fn test_reports_every_malformed_key() {
+        let mut value = config_with(&["notifications", "sound_enabled"], json!("yes"));
+        value["theme"] = json!("DRAK");
+        let Err(ConfigError::Invalid(issues)) = parse_config(value) else {
+            panic!("expected issues");
+        };
+        let issue = |path: &str| issues.iter().find(|issue| issue.path == path).unwrap();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(
+            issue("theme").suggestion.as_deref(),
+            Some("Did you mean `DARK`?")
+        );
+        let sound_enabled = issue("notifications.sound_enabled");
+        assert_eq!(sound_enabled.expected.as_deref(), Some("a boolean"));
+        assert_eq!(sound_enabled.suggestion.as_deref(), Some("Use a boolean"));
+    }
+
+    #[test]
+    fn test_repair_keeps_valid_sections() {
+        let mut value = config_with(&["notifications", "sound_enabled"], json!(false));
+        value["editor"] = json!(42);
+        value.as_object_mut().unwrap().remove("theme");
+
+        let (config, issues) = deserialize_repairing(value);
+        let config = config.unwrap();
+        assert!(!config.notifications.sound_enabled);
+        let mut paths: Vec<&str> = issues.iter().map(|issue| issue.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["editor", "theme"]);
+    }
+
+    #[test]
+    fn test_semantic_issues() {
+        let mut config = Config::default();
+        config.server.public_base_url = Some("kanban.example.com".to_string());
+        let Err(ConfigError::Invalid(issues)) = validate_config(&config) else {
+            panic!("expected issues");
+        };
+        assert_eq!(issues[0].path, "server.public_base_url");
+        assert_eq!(
+            issues[0].suggestion.as_deref(),
+            Some("Try `https://kanban.example.com`")
+        );
+    }
+}
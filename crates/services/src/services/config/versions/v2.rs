@@ -1,10 +1,11 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use anyhow::Error;
 use serde::{Deserialize, Serialize};
-use strum_macros::EnumString;
+use strum_macros::{Display, EnumIter, EnumString};
 use ts_rs::TS;
 use utils::{assets::SoundAssets, cache_dir};
+use uuid::Uuid;
 
 // Re-export editor config from the dedicated editor module
 pub use crate::services::config::editor::{EditorConfig, EditorType};
@@ -156,10 +157,152 @@ pub struct NotificationConfig {
     pub sound_enabled: bool,
     pub push_enabled: bool,
     pub sound_file: SoundFile,
+    /// User-provided sound played instead of `sound_file`: an uploaded sound's name or an
+    /// absolute path
+    #[serde(default)]
+    pub custom_sound: Option<String>,
+    #[serde(default)]
+    pub sound_playback: SoundPlayback,
+    /// Pack that picks different sounds for successes, failures and events needing attention
+    #[serde(default)]
+    pub sound_theme: SoundTheme,
+    /// Per-event sounds that take precedence over the theme
+    #[serde(default)]
+    pub event_sounds: HashMap<NotificationEvent, EventSound>,
     #[serde(default)]
     pub slack_enabled: bool,
     #[serde(default)]
     pub slack_webhook_url: Option<String>,
+    /// Bot token for `chat.postMessage`; when set with `slack_channel` it replaces the webhook
+    /// and follow-ups for the same task attempt are posted as thread replies
+    #[serde(default)]
+    pub slack_bot_token: Option<String>,
+    /// Channel ID the bot posts to
+    #[serde(default)]
+    pub slack_channel: Option<String>,
+    /// Member ID, or a handle from `slack_member_ids`, mentioned on failure notifications
+    #[serde(default)]
+    pub slack_on_call: Option<String>,
+    /// Handles mapped to Slack member IDs such as `U0123ABCD`
+    #[serde(default)]
+    pub slack_member_ids: HashMap<String, String>,
+    #[serde(default)]
+    pub webhook_enabled: bool,
+    #[serde(default)]
+    pub webhook_urls: Vec<String>,
+    #[serde(default)]
+    pub webhook_secret: Option<String>,
+    #[serde(default)]
+    pub ntfy_enabled: bool,
+    #[serde(default)]
+    pub ntfy_topic_url: Option<String>,
+    #[serde(default)]
+    pub ntfy_token: Option<String>,
+    #[serde(default)]
+    pub ntfy_priority: Option<u8>,
+    #[serde(default)]
+    pub pushover_enabled: bool,
+    #[serde(default)]
+    pub pushover_app_token: Option<String>,
+    #[serde(default)]
+    pub pushover_user_key: Option<String>,
+    #[serde(default)]
+    pub pushover_priority: Option<i8>,
+    /// Deliver to browsers subscribed through the frontend, even when the tab is closed
+    #[serde(default)]
+    pub web_push_enabled: bool,
+    #[serde(default)]
+    pub gotify_enabled: bool,
+    #[serde(default)]
+    pub gotify_server_url: Option<String>,
+    #[serde(default)]
+    pub gotify_app_token: Option<String>,
+    #[serde(default)]
+    pub matrix_enabled: bool,
+    #[serde(default)]
+    pub matrix_homeserver_url: Option<String>,
+    #[serde(default)]
+    pub matrix_access_token: Option<String>,
+    #[serde(default)]
+    pub matrix_room_id: Option<String>,
+    #[serde(default)]
+    pub mattermost_enabled: bool,
+    #[serde(default)]
+    pub mattermost_webhook_url: Option<String>,
+    /// Channel name to post in instead of the webhook's default; needs channel overrides
+    /// enabled on the webhook
+    #[serde(default)]
+    pub mattermost_channel: Option<String>,
+    #[serde(default)]
+    pub mattermost_icon_url: Option<String>,
+    #[serde(default)]
+    pub rocketchat_enabled: bool,
+    #[serde(default)]
+    pub rocketchat_webhook_url: Option<String>,
+    /// `#channel` or `@user` to post to instead of the integration's default
+    #[serde(default)]
+    pub rocketchat_channel: Option<String>,
+    #[serde(default)]
+    pub rocketchat_avatar_url: Option<String>,
+    #[serde(default)]
+    pub zulip_enabled: bool,
+    #[serde(default)]
+    pub zulip_site_url: Option<String>,
+    #[serde(default)]
+    pub zulip_bot_email: Option<String>,
+    #[serde(default)]
+    pub zulip_api_key: Option<String>,
+    #[serde(default)]
+    pub zulip_stream: Option<String>,
+    /// Topic within `zulip_stream`; defaults to the project name
+    #[serde(default)]
+    pub zulip_topic: Option<String>,
+    #[serde(default)]
+    pub irc_enabled: bool,
+    /// Host, optionally with `:port`; the port defaults to 6697 with TLS and 6667 without
+    #[serde(default)]
+    pub irc_server: Option<String>,
+    /// Connect over TLS; on unless set to false
+    #[serde(default)]
+    pub irc_tls: Option<bool>,
+    #[serde(default)]
+    pub irc_nick: Option<String>,
+    #[serde(default)]
+    pub irc_channel: Option<String>,
+    /// SASL PLAIN credentials, for networks that only accept registered nicks
+    #[serde(default)]
+    pub irc_sasl_username: Option<String>,
+    #[serde(default)]
+    pub irc_sasl_password: Option<String>,
+    /// Per-channel event toggles; events missing from a channel's map use their default
+    #[serde(default)]
+    pub event_toggles: HashMap<NotificationChannel, HashMap<NotificationEvent, bool>>,
+    /// Per-channel minimum severity, applied on top of the event toggles; channels missing
+    /// from the map get every severity
+    #[serde(default)]
+    pub min_severity: HashMap<NotificationChannel, NotificationSeverity>,
+    /// Project-scoped settings layered over the global ones when notifying about that project
+    #[serde(default)]
+    pub project_overrides: HashMap<Uuid, NotificationOverrides>,
+    /// Custom title/message per event, rendered with `{{variable}}` substitution
+    #[serde(default)]
+    pub templates: HashMap<NotificationEvent, NotificationTemplate>,
+    #[serde(default)]
+    pub quiet_hours: QuietHours,
+    #[serde(default)]
+    pub retry: NotificationRetryPolicy,
+    #[serde(default)]
+    pub digest: NotificationDigest,
+    #[serde(default)]
+    pub dedup: NotificationDedup,
+    /// Checked in order before the per-channel event toggles; the first matching rule decides
+    /// which channels fire
+    #[serde(default)]
+    pub rules: Vec<NotificationRule>,
+    #[serde(default)]
+    pub escalation: EscalationPolicy,
+    #[serde(default)]
+    pub summary: NotificationSummary,
 }
 
 impl From<v1::Config> for NotificationConfig {
@@ -168,8 +311,67 @@ impl From<v1::Config> for NotificationConfig {
             sound_enabled: old.sound_alerts,
             push_enabled: old.push_notifications,
             sound_file: SoundFile::from(old.sound_file), // Now SCREAMING_SNAKE_CASE
+            custom_sound: None,
+            sound_playback: SoundPlayback::default(),
+            sound_theme: SoundTheme::default(),
+            event_sounds: HashMap::new(),
             slack_enabled: false,
             slack_webhook_url: None,
+            slack_bot_token: None,
+            slack_channel: None,
+            slack_on_call: None,
+            slack_member_ids: HashMap::new(),
+            webhook_enabled: false,
+            webhook_urls: Vec::new(),
+            webhook_secret: None,
+            ntfy_enabled: false,
+            ntfy_topic_url: None,
+            ntfy_token: None,
+            ntfy_priority: None,
+            pushover_enabled: false,
+            pushover_app_token: None,
+            pushover_user_key: None,
+            pushover_priority: None,
+            web_push_enabled: false,
+            gotify_enabled: false,
+            gotify_server_url: None,
+            gotify_app_token: None,
+            matrix_enabled: false,
+            matrix_homeserver_url: None,
+            matrix_access_token: None,
+            matrix_room_id: None,
+            mattermost_enabled: false,
+            mattermost_webhook_url: None,
+            mattermost_channel: None,
+            mattermost_icon_url: None,
+            rocketchat_enabled: false,
+            rocketchat_webhook_url: None,
+            rocketchat_channel: None,
+            rocketchat_avatar_url: None,
+            zulip_enabled: false,
+            zulip_site_url: None,
+            zulip_bot_email: None,
+            zulip_api_key: None,
+            zulip_stream: None,
+            zulip_topic: None,
+            irc_enabled: false,
+            irc_server: None,
+            irc_tls: None,
+            irc_nick: None,
+            irc_channel: None,
+            irc_sasl_username: None,
+            irc_sasl_password: None,
+            event_toggles: HashMap::new(),
+            min_severity: HashMap::new(),
+            project_overrides: HashMap::new(),
+            templates: HashMap::new(),
+            quiet_hours: QuietHours::default(),
+            retry: NotificationRetryPolicy::default(),
+            digest: NotificationDigest::default(),
+            dedup: NotificationDedup::default(),
+            rules: Vec::new(),
+            escalation: EscalationPolicy::default(),
+            summary: NotificationSummary::default(),
         }
     }
 }
@@ -180,12 +382,784 @@ impl Default for NotificationConfig {
             sound_enabled: true,
             push_enabled: true,
             sound_file: SoundFile::CowMooing,
+            custom_sound: None,
+            sound_playback: SoundPlayback::default(),
+            sound_theme: SoundTheme::default(),
+            event_sounds: HashMap::new(),
             slack_enabled: false,
             slack_webhook_url: None,
+            slack_bot_token: None,
+            slack_channel: None,
+            slack_on_call: None,
+            slack_member_ids: HashMap::new(),
+            webhook_enabled: false,
+            webhook_urls: Vec::new(),
+            webhook_secret: None,
+            ntfy_enabled: false,
+            ntfy_topic_url: None,
+            ntfy_token: None,
+            ntfy_priority: None,
+            pushover_enabled: false,
+            pushover_app_token: None,
+            pushover_user_key: None,
+            pushover_priority: None,
+            web_push_enabled: false,
+            gotify_enabled: false,
+            gotify_server_url: None,
+            gotify_app_token: None,
+            matrix_enabled: false,
+            matrix_homeserver_url: None,
+            matrix_access_token: None,
+            matrix_room_id: None,
+            mattermost_enabled: false,
+            mattermost_webhook_url: None,
+            mattermost_channel: None,
+            mattermost_icon_url: None,
+            rocketchat_enabled: false,
+            rocketchat_webhook_url: None,
+            rocketchat_channel: None,
+            rocketchat_avatar_url: None,
+            zulip_enabled: false,
+            zulip_site_url: None,
+            zulip_bot_email: None,
+            zulip_api_key: None,
+            zulip_stream: None,
+            zulip_topic: None,
+            irc_enabled: false,
+            irc_server: None,
+            irc_tls: None,
+            irc_nick: None,
+            irc_channel: None,
+            irc_sasl_username: None,
+            irc_sasl_password: None,
+            event_toggles: HashMap::new(),
+            min_severity: HashMap::new(),
+            project_overrides: HashMap::new(),
+            templates: HashMap::new(),
+            quiet_hours: QuietHours::default(),
+            retry: NotificationRetryPolicy::default(),
+            digest: NotificationDigest::default(),
+            dedup: NotificationDedup::default(),
+            rules: Vec::new(),
+            escalation: EscalationPolicy::default(),
+            summary: NotificationSummary::default(),
         }
     }
 }
 
+impl NotificationConfig {
+    /// Whether `event` should be delivered through `channel`
+    pub fn is_event_enabled(&self, channel: NotificationChannel, event: NotificationEvent) -> bool {
+        let min_severity = self.min_severity.get(&channel).copied().unwrap_or_default();
+        event.severity() >= min_severity
+            && self
+                .event_toggles
+                .get(&channel)
+                .and_then(|events| events.get(&event))
+                .copied()
+                .unwrap_or_else(|| event.enabled_by_default())
+    }
+
+    /// Whether `channel` is switched on, regardless of event toggles
+    pub fn is_channel_enabled(&self, channel: NotificationChannel) -> bool {
+        match channel {
+            NotificationChannel::Sound => self.sound_enabled,
+            NotificationChannel::Push => self.push_enabled,
+            NotificationChannel::Slack => self.slack_enabled,
+            NotificationChannel::Webhook => self.webhook_enabled,
+            NotificationChannel::Ntfy => self.ntfy_enabled,
+            NotificationChannel::Pushover => self.pushover_enabled,
+            NotificationChannel::WebPush => self.web_push_enabled,
+            NotificationChannel::Gotify => self.gotify_enabled,
+            NotificationChannel::Matrix => self.matrix_enabled,
+            NotificationChannel::Mattermost => self.mattermost_enabled,
+            NotificationChannel::RocketChat => self.rocketchat_enabled,
+            NotificationChannel::Zulip => self.zulip_enabled,
+            NotificationChannel::Irc => self.irc_enabled,
+        }
+    }
+
+    /// Slack member ID to mention for the on-call handle, if one is configured
+    pub fn slack_mention(&self) -> Option<String> {
+        let handle = self
+            .slack_on_call
+            .as_deref()
+            .map(str::trim)
+            .filter(|handle| !handle.is_empty())?;
+        let member_id = self
+            .slack_member_ids
+            .get(handle)
+            .map(String::as_str)
+            .unwrap_or(handle);
+        Some(member_id.trim_start_matches('@').to_string())
+    }
+
+    /// Effective settings for a project, with its overrides (if any) applied
+    pub fn for_project(&self, project_id: Option<Uuid>) -> NotificationConfig {
+        let mut resolved = self.clone();
+        if let Some(overrides) = project_id.and_then(|id| self.project_overrides.get(&id)) {
+            overrides.apply_to(&mut resolved);
+        }
+        resolved
+    }
+}
+
+/// Coalesces bursts of notifications into one summary per channel
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct NotificationDigest {
+    /// How long to collect notifications after the first one arrives
+    pub window_secs: u32,
+    /// Channels that batch their notifications; all others deliver immediately
+    pub channels: Vec<NotificationChannel>,
+}
+
+impl Default for NotificationDigest {
+    fn default() -> Self {
+        Self {
+            window_secs: 300,
+            channels: Vec::new(),
+        }
+    }
+}
+
+/// Suppresses repeats of the same event for the same task on a channel, e.g. when an executor
+/// keeps failing
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct NotificationDedup {
+    /// How long repeats are suppressed after a notification goes out; 0 disables deduplication
+    pub window_secs: u32,
+    /// `Drop` discards repeats; `Count` sends one follow-up with the number of repeats once
+    /// the window closes
+    pub mode: NotificationDedupMode,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(use_ts_enum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NotificationDedupMode {
+    #[default]
+    Drop,
+    Count,
+}
+
+/// Routes notifications matching every condition to a fixed set of channels; empty conditions
+/// match anything
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct NotificationRule {
+    pub name: String,
+    pub events: Vec<NotificationEvent>,
+    pub project_ids: Vec<Uuid>,
+    /// Matches tasks carrying any of these labels
+    pub labels: Vec<String>,
+    /// Executor names such as `CLAUDE_CODE`
+    pub executors: Vec<String>,
+    /// Matches tasks assigned to any of these people or agents
+    pub assignees: Vec<String>,
+    pub outcome: Option<NotificationOutcome>,
+    /// Channels to deliver through, regardless of their event toggles; each must still be
+    /// enabled and configured
+    pub channels: Vec<NotificationChannel>,
+    /// Urgency for channels that support it (ntfy, Pushover and Gotify)
+    pub priority: Option<NotificationPriority>,
+    /// Slack channel ID to post to instead of `slack_channel`, when posting with a bot token
+    pub slack_channel: Option<String>,
+}
+
+/// Result of the work an event reports on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(use_ts_enum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NotificationOutcome {
+    Success,
+    Failure,
+}
+
+/// How serious an event is: `Info` for progress and successes, `Warning` for events waiting on
+/// someone, `Failure` for errors and merge conflicts
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, TS,
+)]
+#[ts(use_ts_enum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NotificationSeverity {
+    #[default]
+    Info,
+    Warning,
+    Failure,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(use_ts_enum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NotificationPriority {
+    Low,
+    Normal,
+    High,
+    Urgent,
+}
+
+/// Re-sends failures that nobody acknowledged in time through other channels
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct EscalationPolicy {
+    pub enabled: bool,
+    /// How long to wait for an acknowledgment before escalating
+    pub after_minutes: u32,
+    /// Channels for the escalated notification; each must be enabled and configured
+    pub channels: Vec<NotificationChannel>,
+}
+
+impl Default for EscalationPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            after_minutes: 15,
+            channels: Vec::new(),
+        }
+    }
+}
+
+/// Scheduled roll-up of task activity, sent through every enabled channel
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct NotificationSummary {
+    pub enabled: bool,
+    pub frequency: SummaryFrequency,
+    /// Send time as `HH:MM`
+    pub time: String,
+    /// Day the weekly summary goes out; ignored for daily summaries
+    pub weekday: DayOfWeek,
+    /// Fixed UTC offset such as `+02:00`; the system's local time is used when unset
+    pub utc_offset: Option<String>,
+}
+
+impl Default for NotificationSummary {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            frequency: SummaryFrequency::Daily,
+            time: "09:00".to_string(),
+            weekday: DayOfWeek::Monday,
+            utc_offset: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(use_ts_enum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SummaryFrequency {
+    #[default]
+    Daily,
+    Weekly,
+}
+
+/// Bundled sound packs; `Classic` plays `custom_sound` or `sound_file` for every event
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(use_ts_enum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SoundTheme {
+    #[default]
+    Classic,
+    Abstract,
+    Farmyard,
+}
+
+impl SoundTheme {
+    /// The pack's sound for a kind of event, if it has its own
+    pub fn sound_for(&self, category: SoundCategory) -> Option<SoundFile> {
+        match (self, category) {
+            (SoundTheme::Classic, _) => None,
+            (SoundTheme::Abstract, SoundCategory::Success) => Some(SoundFile::AbstractSound3),
+            (SoundTheme::Abstract, SoundCategory::Failure) => Some(SoundFile::AbstractSound4),
+            (SoundTheme::Abstract, SoundCategory::Attention) => Some(SoundFile::AbstractSound1),
+            (SoundTheme::Farmyard, SoundCategory::Success) => Some(SoundFile::Rooster),
+            (SoundTheme::Farmyard, SoundCategory::Failure) => Some(SoundFile::CowMooing),
+            (SoundTheme::Farmyard, SoundCategory::Attention) => Some(SoundFile::PhoneVibration),
+        }
+    }
+}
+
+/// Sound for a single event; `custom_sound` wins over `sound_file` like the global settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct EventSound {
+    pub sound_file: Option<SoundFile>,
+    /// An uploaded sound's name or an absolute path
+    pub custom_sound: Option<String>,
+}
+
+/// How loud and how long notification sounds play
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct SoundPlayback {
+    /// Percentage of full volume, from 0 to 100
+    pub volume: u8,
+    /// Cut sounds off after this long; unset plays them to the end
+    pub max_duration_ms: Option<u32>,
+}
+
+impl SoundPlayback {
+    /// Volume as a linear gain between 0.0 and 1.0
+    pub fn gain(&self) -> f32 {
+        f32::from(self.volume.min(100)) / 100.0
+    }
+
+    pub fn max_duration(&self) -> Option<std::time::Duration> {
+        self.max_duration_ms
+            .map(|ms| std::time::Duration::from_millis(ms.into()))
+    }
+}
+
+impl Default for SoundPlayback {
+    fn default() -> Self {
+        Self {
+            volume: 100,
+            max_duration_ms: None,
+        }
+    }
+}
+
+/// Retry policy for channels delivered over HTTP
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct NotificationRetryPolicy {
+    /// Total attempts, including the first one
+    pub max_attempts: u32,
+    pub initial_delay_ms: u32,
+    pub max_delay_ms: u32,
+    /// Randomize delays so many failing sends don't retry in lockstep
+    pub jitter: bool,
+}
+
+impl Default for NotificationRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay_ms: 1000,
+            max_delay_ms: 30_000,
+            jitter: true,
+        }
+    }
+}
+
+/// Do-not-disturb window during which sound and push-style notifications (desktop, ntfy,
+/// Pushover, Gotify and web push) are suppressed
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct QuietHours {
+    pub enabled: bool,
+    /// Start of the window as `HH:MM`
+    pub start: String,
+    /// End of the window as `HH:MM`; earlier than `start` means the window spans midnight
+    pub end: String,
+    /// IANA timezone such as `Europe/Berlin`; the system's local time is used when unset
+    #[serde(alias = "utc_offset")]
+    pub timezone: Option<String>,
+    /// Days on which the window starts; every day when empty
+    pub days: Vec<DayOfWeek>,
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: "22:00".to_string(),
+            end: "07:00".to_string(),
+            timezone: None,
+            days: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS, EnumString)]
+#[ts(use_ts_enum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum DayOfWeek {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<chrono::Weekday> for DayOfWeek {
+    fn from(weekday: chrono::Weekday) -> Self {
+        match weekday {
+            chrono::Weekday::Mon => DayOfWeek::Monday,
+            chrono::Weekday::Tue => DayOfWeek::Tuesday,
+            chrono::Weekday::Wed => DayOfWeek::Wednesday,
+            chrono::Weekday::Thu => DayOfWeek::Thursday,
+            chrono::Weekday::Fri => DayOfWeek::Friday,
+            chrono::Weekday::Sat => DayOfWeek::Saturday,
+            chrono::Weekday::Sun => DayOfWeek::Sunday,
+        }
+    }
+}
+
+/// Title and message templates for one event; unset parts keep the built-in text.
+///
+/// Available variables: `event`, `title`, `message`, `url`, `project.id`,
+/// `project.name`, `task.id`, `task.title`, `task.labels`, `task.assignees` and
+/// `attempt.duration`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct NotificationTemplate {
+    pub title: Option<String>,
+    pub message: Option<String>,
+}
+
+/// Per-project notification settings; unset fields inherit the global value
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct NotificationOverrides {
+    pub sound_enabled: Option<bool>,
+    pub push_enabled: Option<bool>,
+    pub sound_file: Option<SoundFile>,
+    pub custom_sound: Option<String>,
+    pub sound_playback: Option<SoundPlayback>,
+    pub sound_theme: Option<SoundTheme>,
+    pub slack_enabled: Option<bool>,
+    pub slack_webhook_url: Option<String>,
+    pub slack_bot_token: Option<String>,
+    pub slack_channel: Option<String>,
+    pub slack_on_call: Option<String>,
+    pub webhook_enabled: Option<bool>,
+    pub webhook_urls: Option<Vec<String>>,
+    pub webhook_secret: Option<String>,
+    pub ntfy_enabled: Option<bool>,
+    pub ntfy_topic_url: Option<String>,
+    pub ntfy_token: Option<String>,
+    pub ntfy_priority: Option<u8>,
+    pub pushover_enabled: Option<bool>,
+    pub pushover_app_token: Option<String>,
+    pub pushover_user_key: Option<String>,
+    pub pushover_priority: Option<i8>,
+    pub web_push_enabled: Option<bool>,
+    pub gotify_enabled: Option<bool>,
+    pub gotify_server_url: Option<String>,
+    pub gotify_app_token: Option<String>,
+    pub matrix_enabled: Option<bool>,
+    pub matrix_homeserver_url: Option<String>,
+    pub matrix_access_token: Option<String>,
+    pub matrix_room_id: Option<String>,
+    pub mattermost_enabled: Option<bool>,
+    pub mattermost_webhook_url: Option<String>,
+    pub mattermost_channel: Option<String>,
+    pub mattermost_icon_url: Option<String>,
+    pub rocketchat_enabled: Option<bool>,
+    pub rocketchat_webhook_url: Option<String>,
+    pub rocketchat_channel: Option<String>,
+    pub rocketchat_avatar_url: Option<String>,
+    pub zulip_enabled: Option<bool>,
+    pub zulip_site_url: Option<String>,
+    pub zulip_bot_email: Option<String>,
+    pub zulip_api_key: Option<String>,
+    pub zulip_stream: Option<String>,
+    pub zulip_topic: Option<String>,
+    pub irc_enabled: Option<bool>,
+    pub irc_server: Option<String>,
+    pub irc_tls: Option<bool>,
+    pub irc_nick: Option<String>,
+    pub irc_channel: Option<String>,
+    pub irc_sasl_username: Option<String>,
+    pub irc_sasl_password: Option<String>,
+    /// Merged into the global toggles event by event
+    pub event_toggles: HashMap<NotificationChannel, HashMap<NotificationEvent, bool>>,
+    /// Merged into the global minimum severities channel by channel
+    pub min_severity: HashMap<NotificationChannel, NotificationSeverity>,
+}
+
+impl NotificationOverrides {
+    fn apply_to(&self, config: &mut NotificationConfig) {
+        config.sound_enabled = self.sound_enabled.unwrap_or(config.sound_enabled);
+        config.push_enabled = self.push_enabled.unwrap_or(config.push_enabled);
+        if let Some(sound_file) = &self.sound_file {
+            config.sound_file = sound_file.clone();
+        }
+        if self.custom_sound.is_some() {
+            config.custom_sound = self.custom_sound.clone();
+        }
+        if let Some(sound_playback) = &self.sound_playback {
+            config.sound_playback = sound_playback.clone();
+        }
+        config.sound_theme = self.sound_theme.unwrap_or(config.sound_theme);
+        config.slack_enabled = self.slack_enabled.unwrap_or(config.slack_enabled);
+        if self.slack_webhook_url.is_some() {
+            config.slack_webhook_url = self.slack_webhook_url.clone();
+        }
+        if self.slack_bot_token.is_some() {
+            config.slack_bot_token = self.slack_bot_token.clone();
+        }
+        if self.slack_channel.is_some() {
+            config.slack_channel = self.slack_channel.clone();
+        }
+        if self.slack_on_call.is_some() {
+            config.slack_on_call = self.slack_on_call.clone();
+        }
+        config.webhook_enabled = self.webhook_enabled.unwrap_or(config.webhook_enabled);
+        if let Some(urls) = &self.webhook_urls {
+            config.webhook_urls = urls.clone();
+        }
+        if self.webhook_secret.is_some() {
+            config.webhook_secret = self.webhook_secret.clone();
+        }
+        config.ntfy_enabled = self.ntfy_enabled.unwrap_or(config.ntfy_enabled);
+        if self.ntfy_topic_url.is_some() {
+            config.ntfy_topic_url = self.ntfy_topic_url.clone();
+        }
+        if self.ntfy_token.is_some() {
+            config.ntfy_token = self.ntfy_token.clone();
+        }
+        config.ntfy_priority = self.ntfy_priority.or(config.ntfy_priority);
+        config.pushover_enabled = self.pushover_enabled.unwrap_or(config.pushover_enabled);
+        if self.pushover_app_token.is_some() {
+            config.pushover_app_token = self.pushover_app_token.clone();
+        }
+        if self.pushover_user_key.is_some() {
+            config.pushover_user_key = self.pushover_user_key.clone();
+        }
+        config.pushover_priority = self.pushover_priority.or(config.pushover_priority);
+        config.web_push_enabled = self.web_push_enabled.unwrap_or(config.web_push_enabled);
+        config.gotify_enabled = self.gotify_enabled.unwrap_or(config.gotify_enabled);
+        if self.gotify_server_url.is_some() {
+            config.gotify_server_url = self.gotify_server_url.clone();
+        }
+        if self.gotify_app_token.is_some() {
+            config.gotify_app_token = self.gotify_app_token.clone();
+        }
+        config.matrix_enabled = self.matrix_enabled.unwrap_or(config.matrix_enabled);
+        if self.matrix_homeserver_url.is_some() {
+            config.matrix_homeserver_url = self.matrix_homeserver_url.clone();
+        }
+        if self.matrix_access_token.is_some() {
+            config.matrix_access_token = self.matrix_access_token.clone();
+        }
+        if self.matrix_room_id.is_some() {
+            config.matrix_room_id = self.matrix_room_id.clone();
+        }
+        config.mattermost_enabled = self.mattermost_enabled.unwrap_or(config.mattermost_enabled);
+        if self.mattermost_webhook_url.is_some() {
+            config.mattermost_webhook_url = self.mattermost_webhook_url.clone();
+        }
+        if self.mattermost_channel.is_some() {
+            config.mattermost_channel = self.mattermost_channel.clone();
+        }
+        if self.mattermost_icon_url.is_some() {
+            config.mattermost_icon_url = self.mattermost_icon_url.clone();
+        }
+        config.rocketchat_enabled = self.rocketchat_enabled.unwrap_or(config.rocketchat_enabled);
+        if self.rocketchat_webhook_url.is_some() {
+            config.rocketchat_webhook_url = self.rocketchat_webhook_url.clone();
+        }
+        if self.rocketchat_channel.is_some() {
+            config.rocketchat_channel = self.rocketchat_channel.clone();
+        }
+        if self.rocketchat_avatar_url.is_some() {
+            config.rocketchat_avatar_url = self.rocketchat_avatar_url.clone();
+        }
+        config.zulip_enabled = self.zulip_enabled.unwrap_or(config.zulip_enabled);
+        if self.zulip_site_url.is_some() {
+            config.zulip_site_url = self.zulip_site_url.clone();
+        }
+        if self.zulip_bot_email.is_some() {
+            config.zulip_bot_email = self.zulip_bot_email.clone();
+        }
+        if self.zulip_api_key.is_some() {
+            config.zulip_api_key = self.zulip_api_key.clone();
+        }
+        if self.zulip_stream.is_some() {
+            config.zulip_stream = self.zulip_stream.clone();
+        }
+        if self.zulip_topic.is_some() {
+            config.zulip_topic = self.zulip_topic.clone();
+        }
+        config.irc_enabled = self.irc_enabled.unwrap_or(config.irc_enabled);
+        if self.irc_server.is_some() {
+            config.irc_server = self.irc_server.clone();
+        }
+        config.irc_tls = self.irc_tls.or(config.irc_tls);
+        if self.irc_nick.is_some() {
+            config.irc_nick = self.irc_nick.clone();
+        }
+        if self.irc_channel.is_some() {
+            config.irc_channel = self.irc_channel.clone();
+        }
+        if self.irc_sasl_username.is_some() {
+            config.irc_sasl_username = self.irc_sasl_username.clone();
+        }
+        if self.irc_sasl_password.is_some() {
+            config.irc_sasl_password = self.irc_sasl_password.clone();
+        }
+        for (channel, events) in &self.event_toggles {
+            config
+                .event_toggles
+                .entry(*channel)
+                .or_default()
+                .extend(events.iter().map(|(event, enabled)| (*event, *enabled)));
+        }
+        config.min_severity.extend(self.min_severity.iter());
+    }
+}
+
+/// Delivery channels that can be toggled per event
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    TS,
+    EnumString,
+    EnumIter,
+    Display,
+)]
+#[ts(use_ts_enum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum NotificationChannel {
+    Sound,
+    Push,
+    Slack,
+    Webhook,
+    Ntfy,
+    Pushover,
+    WebPush,
+    Gotify,
+    Matrix,
+    Mattermost,
+    RocketChat,
+    Zulip,
+    Irc,
+}
+
+/// Kinds of events that can trigger a notification
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    TS,
+    EnumString,
+    EnumIter,
+    Display,
+)]
+#[ts(use_ts_enum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum NotificationEvent {
+    TaskStarted,
+    AttemptCompleted,
+    AttemptFailed,
+    ApprovalNeeded,
+    PrOpened,
+    PrMerged,
+    MergeConflict,
+    Summary,
+    TaskUnblocked,
+    TaskOverdue,
+    WipLimitExceeded,
+    EpicCompleted,
+    BudgetLimitReached,
+    AttemptStalled,
+}
+
+impl NotificationEvent {
+    /// User-initiated events are opt-in so existing setups don't get noisier
+    pub fn enabled_by_default(&self) -> bool {
+        !matches!(
+            self,
+            NotificationEvent::TaskStarted | NotificationEvent::PrOpened
+        )
+    }
+
+    /// Events that need someone to step in
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            NotificationEvent::AttemptFailed | NotificationEvent::MergeConflict
+        )
+    }
+
+    /// Outcome reported by the event; started, approval, PR opened, summary, unblocked, overdue,
+    /// WIP limit, budget and stall events have none
+    pub fn outcome(&self) -> Option<NotificationOutcome> {
+        match self {
+            NotificationEvent::AttemptCompleted
+            | NotificationEvent::PrMerged
+            | NotificationEvent::EpicCompleted => Some(NotificationOutcome::Success),
+            NotificationEvent::AttemptFailed | NotificationEvent::MergeConflict => {
+                Some(NotificationOutcome::Failure)
+            }
+            NotificationEvent::TaskStarted
+            | NotificationEvent::ApprovalNeeded
+            | NotificationEvent::PrOpened
+            | NotificationEvent::Summary
+            | NotificationEvent::TaskUnblocked
+            | NotificationEvent::TaskOverdue
+            | NotificationEvent::WipLimitExceeded
+            | NotificationEvent::BudgetLimitReached
+            | NotificationEvent::AttemptStalled => None,
+        }
+    }
+
+    pub fn severity(&self) -> NotificationSeverity {
+        if self.is_failure() {
+            NotificationSeverity::Failure
+        } else if matches!(
+            self,
+            NotificationEvent::ApprovalNeeded
+                | NotificationEvent::TaskOverdue
+                | NotificationEvent::WipLimitExceeded
+                | NotificationEvent::BudgetLimitReached
+                | NotificationEvent::AttemptStalled
+        ) {
+            NotificationSeverity::Warning
+        } else {
+            NotificationSeverity::Info
+        }
+    }
+
+    /// Which of a sound theme's sounds the event plays; informational events use the
+    /// success sound
+    pub fn sound_category(&self) -> SoundCategory {
+        if self.is_failure() {
+            SoundCategory::Failure
+        } else if matches!(
+            self,
+            NotificationEvent::ApprovalNeeded
+                | NotificationEvent::TaskOverdue
+                | NotificationEvent::WipLimitExceeded
+                | NotificationEvent::BudgetLimitReached
+                | NotificationEvent::AttemptStalled
+        ) {
+            SoundCategory::Attention
+        } else {
+            SoundCategory::Success
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundCategory {
+    Success,
+    Failure,
+    Attention,
+}
+
 impl Default for GitHubConfig {
     fn default() -> Self {
         Self {
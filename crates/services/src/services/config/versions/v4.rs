@@ -1,7 +1,14 @@
 use anyhow::Error;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
-pub use v3::{EditorConfig, EditorType, GitHubConfig, NotificationConfig, SoundFile, ThemeMode};
+pub use v3::{
+    DayOfWeek, EditorConfig, EditorType, EscalationPolicy, EventSound, GitHubConfig,
+    NotificationChannel, NotificationConfig, NotificationDedup, NotificationDedupMode,
+    NotificationDigest, NotificationEvent, NotificationOutcome, NotificationOverrides,
+    NotificationPriority, NotificationRetryPolicy, NotificationRule, NotificationSeverity,
+    NotificationSummary, NotificationTemplate, QuietHours, SoundFile, SoundPlayback, SoundTheme,
+    SummaryFrequency, ThemeMode,
+};
 
 use crate::services::config::versions::v3;
 
@@ -5,7 +5,14 @@ use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use utils;
-pub use v5::{EditorConfig, EditorType, GitHubConfig, NotificationConfig, SoundFile, ThemeMode};
+pub use v5::{
+    DayOfWeek, EditorConfig, EditorType, EscalationPolicy, EventSound, GitHubConfig,
+    NotificationChannel, NotificationConfig, NotificationDedup, NotificationDedupMode,
+    NotificationDigest, NotificationEvent, NotificationOutcome, NotificationOverrides,
+    NotificationPriority, NotificationRetryPolicy, NotificationRule, NotificationSeverity,
+    NotificationSummary, NotificationTemplate, QuietHours, SoundFile, SoundPlayback, SoundTheme,
+    SummaryFrequency, ThemeMode,
+};
 
 use crate::services::config::versions::v5;
 
@@ -3,7 +3,14 @@ use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
 use serde::{Deserialize, Serialize};
 use strum_macros::EnumString;
 use ts_rs::TS;
-pub use v6::{EditorConfig, EditorType, GitHubConfig, NotificationConfig, SoundFile, UiLanguage};
+pub use v6::{
+    DayOfWeek, EditorConfig, EditorType, EscalationPolicy, EventSound, GitHubConfig,
+    NotificationChannel, NotificationConfig, NotificationDedup, NotificationDedupMode,
+    NotificationDigest, NotificationEvent, NotificationOutcome, NotificationOverrides,
+    NotificationPriority, NotificationRetryPolicy, NotificationRule, NotificationSeverity,
+    NotificationSummary, NotificationTemplate, QuietHours, SoundFile, SoundPlayback, SoundTheme,
+    SummaryFrequency, UiLanguage,
+};
 
 use crate::services::config::versions::v6;
 
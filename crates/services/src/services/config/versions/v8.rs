@@ -1,13 +1,23 @@
+use std::collections::HashMap;
+
 use anyhow::Error;
-use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+use executors::{
+    approvals::ApprovalGates, executors::BaseCodingAgent, process_limits::ProcessLimits,
+    profile::ExecutorProfileId, sandbox::SandboxConfig,
+};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use uuid::Uuid;
 pub use v7::{
-    EditorConfig, EditorType, GitHubConfig, NotificationConfig, ShowcaseState, SoundFile,
-    ThemeMode, UiLanguage,
+    DayOfWeek, EditorConfig, EditorType, EscalationPolicy, EventSound, GitHubConfig,
+    NotificationChannel, NotificationConfig, NotificationDedup, NotificationDedupMode,
+    NotificationDigest, NotificationEvent, NotificationOutcome, NotificationOverrides,
+    NotificationPriority, NotificationRetryPolicy, NotificationRule, NotificationSeverity,
+    NotificationSummary, NotificationTemplate, QuietHours, ShowcaseState, SoundFile, SoundPlayback,
+    SoundTheme, SummaryFrequency, ThemeMode, UiLanguage,
 };
 
-use crate::services::config::versions::v7;
+use crate::services::config::{secrets::blank_if_lost, versions::v7};
 
 fn default_git_branch_prefix() -> String {
     "vk".to_string()
@@ -17,6 +27,160 @@ fn default_pr_auto_description_enabled() -> bool {
     true
 }
 
+fn default_max_review_rounds() -> u32 {
+    3
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+pub struct ServerConfig {
+    /// URL the app is reached at from outside, e.g. behind a reverse proxy or tunnel.
+    /// Used for links in notifications ahead of `SERVER_PUBLIC_BASE_URL` and port discovery.
+    #[serde(default)]
+    pub public_base_url: Option<String>,
+}
+
+fn default_attachment_max_size_mb() -> u64 {
+    25
+}
+
+/// Where task attachments are kept and how large they may be
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct AttachmentConfig {
+    #[serde(default = "default_attachment_max_size_mb")]
+    pub max_size_mb: u64,
+    /// Store new attachments in an S3-compatible bucket instead of the app data directory.
+    /// Attachments already stored keep their original location.
+    #[serde(default)]
+    pub s3: Option<S3StorageConfig>,
+}
+
+impl Default for AttachmentConfig {
+    fn default() -> Self {
+        Self {
+            max_size_mb: default_attachment_max_size_mb(),
+            s3: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct S3StorageConfig {
+    /// e.g. `https://s3.us-east-1.amazonaws.com`, or a MinIO/R2 endpoint
+    pub endpoint: String,
+    /// Defaults to `auto`
+    #[serde(default)]
+    pub region: Option<String>,
+    pub bucket: String,
+    /// Key prefix objects are stored under, e.g. `vibe-kanban/`
+    #[serde(default)]
+    pub prefix: Option<String>,
+    #[serde(deserialize_with = "blank_if_lost")]
+    pub access_key_id: String,
+    #[serde(deserialize_with = "blank_if_lost")]
+    pub secret_access_key: String,
+}
+
+/// Jira Cloud site and the account tasks are imported and transitioned as
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct JiraConfig {
+    /// e.g. `https://your-team.atlassian.net`
+    pub base_url: String,
+    pub email: String,
+    /// API token created at id.atlassian.com for `email`
+    #[serde(deserialize_with = "blank_if_lost")]
+    pub api_token: String,
+}
+
+/// Linear workspace access; the team each project syncs with is set per project
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct LinearConfig {
+    /// Personal API key from Linear's security settings
+    #[serde(deserialize_with = "blank_if_lost")]
+    pub api_key: String,
+    /// Signing secret of the webhook pointed at `/api/linear/webhook`; webhooks are rejected
+    /// while unset
+    #[serde(default)]
+    pub webhook_secret: Option<String>,
+}
+
+/// Named set of executor, notification and editor settings that can be switched to at once
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct ConfigProfile {
+    pub executor_profile: ExecutorProfileId,
+    pub notifications: NotificationConfig,
+    pub editor: EditorConfig,
+}
+
+/// Settings a project uses in place of the global ones; unset fields inherit the global value.
+/// Notification overrides live in `notifications.project_overrides`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct ProjectConfigOverrides {
+    pub executor_profile: Option<ExecutorProfileId>,
+    pub git_branch_prefix: Option<String>,
+    /// 0 turns auto-archiving off for the project
+    pub auto_archive_after_days: Option<u32>,
+    /// Most attempts running at once in the project; 0 lifts the per-project limit
+    pub max_concurrent_attempts: Option<u32>,
+}
+
+/// Retries of coding agent runs that fail with a transient error: a rate limit, a network
+/// error or a timed-out tool
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct AttemptRetryPolicy {
+    /// Retries after the first failure; 0 turns automatic retries off
+    pub max_retries: u32,
+    /// Wait before the first retry, doubling with each retry after it
+    pub initial_delay_secs: u32,
+    pub max_delay_secs: u32,
+}
+
+impl Default for AttemptRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            initial_delay_secs: 30,
+            max_delay_secs: 600,
+        }
+    }
+}
+
+/// What happens once a coding agent has gone quiet. `Warn` logs it and notifies; `Continue`
+/// stops the run and sends the agent a follow-up asking it to carry on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(use_ts_enum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StallAction {
+    #[default]
+    Warn,
+    Continue,
+    Kill,
+}
+
+/// Limits on how long one executor profile's coding agent may run
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct ExecutionLimits {
+    /// The run is killed once it has gone on this long, not counting time paused
+    pub max_duration_minutes: Option<u32>,
+    /// Minutes without new output before the run counts as stalled
+    pub stall_after_minutes: Option<u32>,
+    pub on_stall: StallAction,
+}
+
+/// What happens to a to-do task once every task blocking it is done. `Start` also notifies, and
+/// starts an attempt on the branches the last blocker's attempt targeted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(use_ts_enum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TaskUnblockedAction {
+    Nothing,
+    #[default]
+    Notify,
+    Start,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, TS)]
 pub struct Config {
     pub config_version: String,
@@ -41,10 +205,59 @@ pub struct Config {
     pub pr_auto_description_enabled: bool,
     #[serde(default)]
     pub pr_auto_description_prompt: Option<String>,
+    #[serde(default)]
+    pub server: ServerConfig,
+    #[serde(default)]
+    pub config_profiles: HashMap<String, ConfigProfile>,
+    #[serde(default)]
+    pub active_config_profile: Option<String>,
+    #[serde(default)]
+    pub project_overrides: HashMap<Uuid, ProjectConfigOverrides>,
+    #[serde(default)]
+    pub on_task_unblocked: TaskUnblockedAction,
+    #[serde(default)]
+    pub attachments: AttachmentConfig,
+    /// Most coding agents of each executor, e.g. `CLAUDE_CODE`, that may run at once; executors
+    /// not listed are unlimited
+    #[serde(default)]
+    pub executor_wip_limits: HashMap<String, u32>,
+    /// Most attempts that may run at once across all projects; further attempts wait in the run
+    /// queue. Unlimited when unset
+    #[serde(default)]
+    pub max_concurrent_attempts: Option<u32>,
+    /// Most attempts that may run at once in each project, unless the project sets its own
+    #[serde(default)]
+    pub max_concurrent_attempts_per_project: Option<u32>,
+    /// Archive tasks that have been done or cancelled, untouched, for this many days; off when
+    /// unset
+    #[serde(default)]
+    pub auto_archive_after_days: Option<u32>,
+    /// Credentials for projects that sync with Jira; the JQL filters are set per project
+    #[serde(default)]
+    pub jira: Option<JiraConfig>,
+    /// Credentials for projects that sync with a Linear team
+    #[serde(default)]
+    pub linear: Option<LinearConfig>,
+    #[serde(default)]
+    pub attempt_retry: AttemptRetryPolicy,
+    /// Run limits per executor profile, keyed like `CLAUDE_CODE` or `CLAUDE_CODE:PLAN`; a
+    /// variant's entry takes precedence over its executor's
+    #[serde(default)]
+    pub execution_limits: HashMap<String, ExecutionLimits>,
+    #[serde(default)]
+    pub sandbox: SandboxConfig,
+    #[serde(default)]
+    pub process_limits: ProcessLimits,
+    #[serde(default)]
+    pub approval_gates: ApprovalGates,
+    /// Most times an attempt's reviewer reviews the agent's work before the task goes to review
+    /// anyway
+    #[serde(default = "default_max_review_rounds")]
+    pub max_review_rounds: u32,
 }
 
 impl Config {
-    fn from_v7_config(old_config: v7::Config) -> Self {
+    pub fn from_v7_config(old_config: v7::Config) -> Self {
         // Convert Option<bool> to bool: None or Some(true) become true, Some(false) stays false
         let analytics_enabled = old_config.analytics_enabled.unwrap_or(true);
 
@@ -66,6 +279,24 @@ impl Config {
             showcases: old_config.showcases,
             pr_auto_description_enabled: true,
             pr_auto_description_prompt: None,
+            server: ServerConfig::default(),
+            config_profiles: HashMap::new(),
+            active_config_profile: None,
+            project_overrides: HashMap::new(),
+            on_task_unblocked: TaskUnblockedAction::default(),
+            attachments: AttachmentConfig::default(),
+            executor_wip_limits: HashMap::new(),
+            max_concurrent_attempts: None,
+            max_concurrent_attempts_per_project: None,
+            auto_archive_after_days: None,
+            jira: None,
+            linear: None,
+            attempt_retry: AttemptRetryPolicy::default(),
+            execution_limits: HashMap::new(),
+            sandbox: SandboxConfig::default(),
+            process_limits: ProcessLimits::default(),
+            approval_gates: ApprovalGates::default(),
+            max_review_rounds: default_max_review_rounds(),
         }
     }
 
@@ -116,6 +347,24 @@ impl Default for Config {
             showcases: ShowcaseState::default(),
             pr_auto_description_enabled: true,
             pr_auto_description_prompt: None,
+            server: ServerConfig::default(),
+            config_profiles: HashMap::new(),
+            active_config_profile: None,
+            project_overrides: HashMap::new(),
+            on_task_unblocked: TaskUnblockedAction::default(),
+            attachments: AttachmentConfig::default(),
+            executor_wip_limits: HashMap::new(),
+            max_concurrent_attempts: None,
+            max_concurrent_attempts_per_project: None,
+            auto_archive_after_days: None,
+            jira: None,
+            linear: None,
+            attempt_retry: AttemptRetryPolicy::default(),
+            execution_limits: HashMap::new(),
+            sandbox: SandboxConfig::default(),
+            process_limits: ProcessLimits::default(),
+            approval_gates: ApprovalGates::default(),
+            max_review_rounds: default_max_review_rounds(),
         }
     }
 }
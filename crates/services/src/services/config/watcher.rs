@@ -0,0 +1,119 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use db::DBService;
+use notify::RecursiveMode;
+use notify_debouncer_full::{DebounceEventResult, new_debouncer};
+use tokio::sync::{RwLock, mpsc};
+use tracing::{info, warn};
+use utils::msg_store::MsgStore;
+
+use super::{
+    Config, ConfigError, apply_env_overrides, parse_config, record_config_changes, secrets,
+};
+use crate::services::events::config_patch;
+
+/// Reloads the shared config when the config file is edited on disk
+pub struct ConfigWatcher {
+    config_path: PathBuf,
+    config: Arc<RwLock<Config>>,
+    msg_store: Arc<MsgStore>,
+    db: DBService,
+}
+
+impl ConfigWatcher {
+    /// Watch `config_path`, pushing a config patch to `msg_store` and recording the changed
+    /// keys in the audit log after each applied change
+    pub fn spawn(
+        config_path: PathBuf,
+        config: Arc<RwLock<Config>>,
+        msg_store: Arc<MsgStore>,
+        db: DBService,
+    ) -> Result<tokio::task::JoinHandle<()>, ConfigError> {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut debouncer = new_debouncer(
+            Duration::from_millis(500),
+            None,
+            move |res: DebounceEventResult| {
+                tx.send(res).ok();
+            },
+        )?;
+        // Watch the directory rather than the file, since editors often save by replacing it
+        let dir = config_path
+            .parent()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("."));
+        debouncer.watch(&dir, RecursiveMode::NonRecursive)?;
+
+        let watcher = Self {
+            config_path,
+            config,
+            msg_store,
+            db,
+        };
+        Ok(tokio::spawn(async move {
+            // Dropping the debouncer stops the watch
+            let _debouncer = debouncer;
+            while let Some(res) = rx.recv().await {
+                match res {
+                    Ok(events) => {
+                        if events
+                            .iter()
+                            .any(|event| watcher.touches_config(&event.paths))
+                        {
+                            watcher.reload().await;
+                        }
+                    }
+                    Err(errors) => {
+                        for e in errors {
+                            warn!("Config watcher error: {}", e);
+                        }
+                    }
+                }
+            }
+        }))
+    }
+
+    fn touches_config(&self, paths: &[PathBuf]) -> bool {
+        // Compare names only, as some platforms report canonicalized paths
+        paths
+            .iter()
+            .any(|path| path.file_name() == self.config_path.file_name())
+    }
+
+    async fn reload(&self) {
+        let raw_config = match tokio::fs::read_to_string(&self.config_path).await {
+            Ok(raw_config) => raw_config,
+            Err(e) => {
+                warn!("Failed to read changed config file: {}", e);
+                return;
+            }
+        };
+        // Unlike at startup, a broken file must not fall back to defaults
+        let value = match serde_json::from_str(&secrets::reveal(raw_config)) {
+            Ok(value) => value,
+            Err(e) => {
+                warn!("Ignoring config file change, invalid JSON: {}", e);
+                return;
+            }
+        };
+        let new_config = match parse_config(value) {
+            Ok(new_config) => apply_env_overrides(new_config),
+            Err(e) => {
+                warn!("Ignoring config file change: {}", e);
+                return;
+            }
+        };
+
+        let mut config = self.config.write().await;
+        // Saves through the settings API also land here, and are already applied
+        if serde_json::to_value(&*config).ok() == serde_json::to_value(&new_config).ok() {
+            return;
+        }
+        let old_config = std::mem::replace(&mut *config, new_config.clone());
+        drop(config);
+        info!("Reloaded config from {}", self.config_path.display());
+        self.msg_store
+            .push_patch(config_patch::replace(&new_config));
+        record_config_changes(&self.db.pool, "config file", &old_config, &new_config).await;
+    }
+}
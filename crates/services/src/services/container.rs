@@ -9,7 +9,13 @@ use async_trait::async_trait;
 use db::{
     DBService,
     models::{
+        attempt_checkpoint::{AttemptCheckpoint, CheckpointCommit, CreateAttemptCheckpoint},
+        attempt_handoff::{self, AttemptHandoff, CreateAttemptHandoff, HandoffRepoChanges},
+        attempt_patch::{AttemptDryRun, AttemptPatch},
+        attempt_plan::{self, AttemptPlan, AttemptPlanStatus},
         coding_agent_turn::{CodingAgentTurn, CreateCodingAgentTurn},
+        cost_budget::{BudgetLevel, CostBudget},
+        execution_pause::ExecutionPause,
         execution_process::{
             CreateExecutionProcess, ExecutionContext, ExecutionProcess, ExecutionProcessRunReason,
             ExecutionProcessStatus,
@@ -18,18 +24,23 @@ use db::{
         execution_process_repo_state::{
             CreateExecutionProcessRepoState, ExecutionProcessRepoState,
         },
+        execution_profile::ExecutionProfile,
         project::{Project, UpdateProject},
         project_repo::{ProjectRepo, ProjectRepoWithName},
         repo::Repo,
         session::{CreateSession, Session, SessionError},
         task::{Task, TaskStatus},
-        workspace::{Workspace, WorkspaceError},
-        workspace_repo::WorkspaceRepo,
+        task_attachment::{self, TaskAttachment},
+        task_checklist_item::{self, TaskChecklistItem},
+        task_comment::{self, TaskComment},
+        workspace::{CreateWorkspace, Workspace, WorkspaceError},
+        workspace_repo::{CreateWorkspaceRepo, RepoWithTargetBranch, WorkspaceRepo},
     },
 };
 use executors::{
     actions::{
         ExecutorAction, ExecutorActionType,
+        coding_agent_follow_up::CodingAgentFollowUpRequest,
         coding_agent_initial::CodingAgentInitialRequest,
         script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
     },
@@ -42,6 +53,7 @@ use sqlx::Error as SqlxError;
 use thiserror::Error;
 use tokio::{sync::RwLock, task::JoinHandle};
 use utils::{
+    diff::DiffChangeKind,
     log_msg::LogMsg,
     msg_store::MsgStore,
     text::{git_branch_id, short_uuid},
@@ -49,8 +61,10 @@ use utils::{
 use uuid::Uuid;
 
 use crate::services::{
-    git::{GitService, GitServiceError},
-    notification::NotificationService,
+    budget,
+    config::NotificationEvent,
+    git::{DiffTarget, GitService, GitServiceError},
+    notification::{NotificationContext, NotificationService, i18n},
     share::SharePublisher,
     workspace_manager::WorkspaceError as WorkspaceManagerError,
     worktree_manager::WorktreeError,
@@ -77,6 +91,10 @@ pub enum ContainerError {
     Io(#[from] std::io::Error),
     #[error("Failed to kill process: {0}")]
     KillFailed(std::io::Error),
+    #[error("Failed to pause or resume process: {0}")]
+    PauseFailed(std::io::Error),
+    #[error("{0}")]
+    BudgetExceeded(String),
     #[error(transparent)]
     Other(#[from] AnyhowError), // Catches any unclassified errors
 }
@@ -201,21 +219,14 @@ pub trait ContainerService {
             .kanban_task_url(ctx.project.id, ctx.task.id)
             .await;
 
-        let message = match ctx.execution_process.status {
-            ExecutionProcessStatus::Completed => {
-                let status = "✅";
-                match task_url {
-                    Some(url) => format!("状态: {status}\n点击查看: {url}"),
-                    None => format!("状态: {status}"),
-                }
-            }
-            ExecutionProcessStatus::Failed => {
-                let status = "❌";
-                match task_url {
-                    Some(url) => format!("状态: {status}\n点击查看: {url}"),
-                    None => format!("状态: {status}"),
-                }
-            }
+        let event = match ctx.execution_process.status {
+            ExecutionProcessStatus::Failed => NotificationEvent::AttemptFailed,
+            _ => NotificationEvent::AttemptCompleted,
+        };
+        let locale = self.notification_service().locale().await;
+        let status = match ctx.execution_process.status {
+            ExecutionProcessStatus::Completed => i18n::t(locale, "status-completed", &[]),
+            ExecutionProcessStatus::Failed => i18n::t(locale, "status-failed", &[]),
             _ => {
                 tracing::warn!(
                     "Tried to notify workspace completion for {} but process is still running!",
@@ -224,7 +235,71 @@ pub trait ContainerService {
                 return;
             }
         };
-        self.notification_service().notify(&title, &message).await;
+        let message = match task_url {
+            Some(url) => format!(
+                "{status}\n{}",
+                i18n::t(locale, "view-task", &[("url", url.as_str())])
+            ),
+            None => status,
+        };
+        let duration = ctx
+            .execution_process
+            .completed_at
+            .unwrap_or_else(chrono::Utc::now)
+            - ctx.execution_process.started_at;
+        let mut context = NotificationContext::new(event)
+            .with_task(ctx.project.id, ctx.task.id)
+            .with_workspace(ctx.workspace.id)
+            .with_names(&ctx.project.name, &ctx.task.title)
+            .with_attempt_duration(duration);
+        context.executor = ctx.session.executor.clone();
+        self.notification_service()
+            .notify(&context, &title, &message)
+            .await;
+    }
+
+    /// Report budgets of the execution's project whose soft or hard limit this month's spend
+    /// has reached, once per limit and month
+    async fn notify_budget_limits(&self, ctx: &ExecutionContext) -> Result<(), ContainerError> {
+        let pool = &self.db().pool;
+        let now = chrono::Utc::now();
+        for status in budget::statuses(pool, Some(ctx.project.id), now).await? {
+            if status.budget.notified(status.level, &status.month) {
+                continue;
+            }
+            // Marked first so a failing notification isn't repeated on every execution
+            CostBudget::mark_notified(pool, status.budget.id, status.level, &status.month).await?;
+
+            let locale = self.notification_service().locale().await;
+            let scope = status
+                .project_name
+                .clone()
+                .unwrap_or_else(|| i18n::t(locale, "budget-all-projects", &[]));
+            let spent = format!("{:.2}", status.spent_usd);
+            let limit = format!("{:.2}", status.reached_limit().unwrap_or_default());
+            let args = [
+                ("scope", scope.as_str()),
+                ("spent", spent.as_str()),
+                ("limit", limit.as_str()),
+                ("month", status.month.as_str()),
+            ];
+            let message_key = if status.level == BudgetLevel::Hard {
+                "budget-limit-hard"
+            } else {
+                "budget-limit-soft"
+            };
+            let context = NotificationContext::new(NotificationEvent::BudgetLimitReached)
+                .with_task(ctx.project.id, ctx.task.id)
+                .with_names(&ctx.project.name, &ctx.task.title);
+            self.notification_service()
+                .notify(
+                    &context,
+                    &i18n::t(locale, "budget-limit-title", &args),
+                    &i18n::t(locale, message_key, &args),
+                )
+                .await;
+        }
+        Ok(())
     }
 
     /// Cleanup executions marked as running in the db, call at startup
@@ -602,6 +677,361 @@ pub trait ContainerService {
         status: ExecutionProcessStatus,
     ) -> Result<(), ContainerError>;
 
+    /// Suspend a running execution's processes, keeping the agent and its context in memory
+    /// until it is resumed
+    async fn pause_execution(
+        &self,
+        execution_process: &ExecutionProcess,
+    ) -> Result<(), ContainerError>;
+
+    async fn resume_execution(
+        &self,
+        execution_process: &ExecutionProcess,
+    ) -> Result<(), ContainerError>;
+
+    /// Pause the workspace's running setup scripts and coding agent, returning how many were
+    /// paused
+    async fn pause_workspace(&self, workspace: &Workspace) -> Result<usize, ContainerError> {
+        let pool = &self.db().pool;
+        let paused: Vec<Uuid> = ExecutionPause::find_by_workspace_id(pool, workspace.id)
+            .await?
+            .into_iter()
+            .map(|pause| pause.execution_process_id)
+            .collect();
+        let mut count = 0;
+        for session in Session::find_by_workspace_id(pool, workspace.id).await? {
+            for process in ExecutionProcess::find_by_session_id(pool, session.id, false).await? {
+                if process.status == ExecutionProcessStatus::Running
+                    && matches!(
+                        process.run_reason,
+                        ExecutionProcessRunReason::SetupScript
+                            | ExecutionProcessRunReason::CodingAgent
+                    )
+                    && !paused.contains(&process.id)
+                {
+                    self.pause_execution(&process).await?;
+                    count += 1;
+                }
+            }
+        }
+        Ok(count)
+    }
+
+    /// Resume the workspace's paused executions, returning how many were resumed
+    async fn resume_workspace(&self, workspace: &Workspace) -> Result<usize, ContainerError> {
+        let pool = &self.db().pool;
+        let mut count = 0;
+        for pause in ExecutionPause::find_by_workspace_id(pool, workspace.id).await? {
+            if let Some(process) =
+                ExecutionProcess::find_by_id(pool, pause.execution_process_id).await?
+            {
+                self.resume_execution(&process).await?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Record each repo's HEAD and the agent session after a coding agent process, as a point
+    /// to roll back to
+    async fn record_checkpoint(
+        &self,
+        workspace: &Workspace,
+        execution_process: &ExecutionProcess,
+        label: Option<String>,
+    ) -> Result<AttemptCheckpoint, ContainerError> {
+        let pool = &self.db().pool;
+        let workspace_root = self.workspace_to_current_dir(workspace);
+        let mut repo_commits = Vec::new();
+        for repo in WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await? {
+            let head = self.git().get_head_info(&workspace_root.join(&repo.name))?;
+            repo_commits.push(CheckpointCommit {
+                repo_id: repo.id,
+                commit: head.oid,
+            });
+        }
+        let agent_session_id =
+            CodingAgentTurn::find_by_execution_process_id(pool, execution_process.id)
+                .await?
+                .and_then(|turn| turn.agent_session_id);
+
+        Ok(AttemptCheckpoint::create(
+            pool,
+            &CreateAttemptCheckpoint {
+                workspace_id: workspace.id,
+                session_id: execution_process.session_id,
+                execution_process_id: execution_process.id,
+                agent_session_id,
+                label,
+                repo_commits,
+            },
+        )
+        .await?)
+    }
+
+    /// Store the final message of a plan-first attempt's planning run as its plan, to await
+    /// approval
+    async fn capture_plan(&self, ctx: &ExecutionContext) -> Result<(), ContainerError> {
+        if ctx.execution_process.run_reason != ExecutionProcessRunReason::CodingAgent
+            || ctx.execution_process.status != ExecutionProcessStatus::Completed
+        {
+            return Ok(());
+        }
+        let pool = &self.db().pool;
+        if !AttemptPlan::find_by_workspace_id(pool, ctx.workspace.id)
+            .await?
+            .is_some_and(|plan| plan.status == AttemptPlanStatus::Drafting)
+        {
+            return Ok(());
+        }
+        let Some(plan) =
+            CodingAgentTurn::find_by_execution_process_id(pool, ctx.execution_process.id)
+                .await?
+                .and_then(|turn| turn.summary)
+        else {
+            tracing::warn!(
+                "Planning run {} finished without a final message",
+                ctx.execution_process.id
+            );
+            return Ok(());
+        };
+        AttemptPlan::submit_draft(pool, ctx.workspace.id, ctx.execution_process.id, &plan).await?;
+        Ok(())
+    }
+
+    /// Continue the workspace's latest session with `prompt`, using the executor of its latest
+    /// coding agent run
+    async fn start_follow_up(
+        &self,
+        workspace: &Workspace,
+        prompt: String,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        let pool = &self.db().pool;
+        let session = Session::find_latest_by_workspace_id(pool, workspace.id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let executor_profile_id =
+            ExecutionProcess::latest_executor_profile_for_session(pool, session.id)
+                .await
+                .map_err(|e| {
+                    ContainerError::Other(anyhow!("Failed to get executor profile: {e}"))
+                })?;
+        let latest_agent_session_id =
+            ExecutionProcess::find_latest_coding_agent_turn_session_id(pool, session.id).await?;
+
+        let task = workspace
+            .parent_task(pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let project_repos =
+            ProjectRepo::find_by_project_id_with_names(pool, task.project_id).await?;
+        let cleanup_action = self.cleanup_actions_for_repos(&project_repos);
+        let working_dir = workspace
+            .agent_working_dir
+            .as_ref()
+            .filter(|dir| !dir.is_empty())
+            .cloned();
+
+        let action_type = if let Some(agent_session_id) = latest_agent_session_id {
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt,
+                session_id: agent_session_id,
+                executor_profile_id,
+                working_dir,
+            })
+        } else {
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt,
+                executor_profile_id,
+                working_dir,
+            })
+        };
+        let action = ExecutorAction::new(action_type, cleanup_action.map(Box::new));
+
+        self.start_execution(
+            workspace,
+            &session,
+            &action,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await
+    }
+
+    /// Files each of the workspace's repos changed against its target branch, for a handoff
+    async fn handoff_changes(
+        &self,
+        workspace: &Workspace,
+    ) -> Result<Vec<HandoffRepoChanges>, ContainerError> {
+        let workspace_root = workspace
+            .container_ref
+            .as_ref()
+            .map(PathBuf::from)
+            .ok_or_else(|| ContainerError::Other(anyhow!("Container ref not found")))?;
+        let repos = WorkspaceRepo::find_repos_with_target_branch_for_workspace(
+            &self.db().pool,
+            workspace.id,
+        )
+        .await?;
+
+        let mut changes = Vec::with_capacity(repos.len());
+        for RepoWithTargetBranch {
+            repo,
+            target_branch,
+        } in repos
+        {
+            let diffs = self
+                .git()
+                .get_base_commit(&repo.path, &workspace.branch, &target_branch)
+                .and_then(|base_commit| {
+                    self.git().get_diffs(
+                        DiffTarget::Worktree {
+                            worktree_path: &workspace_root.join(&repo.name),
+                            base_commit: &base_commit,
+                        },
+                        None,
+                    )
+                });
+            let diffs = match diffs {
+                Ok(diffs) => diffs,
+                Err(e) => {
+                    tracing::warn!("Failed to diff repo {} for handoff: {}", repo.name, e);
+                    continue;
+                }
+            };
+            let files = diffs
+                .iter()
+                .map(|diff| {
+                    let path = GitService::diff_path(diff);
+                    let change = match diff.change {
+                        DiffChangeKind::Added => "added",
+                        DiffChangeKind::Deleted => "deleted",
+                        DiffChangeKind::Modified => "modified",
+                        DiffChangeKind::Renamed => "renamed",
+                        DiffChangeKind::Copied => "copied",
+                        DiffChangeKind::PermissionChange => "permissions changed",
+                    };
+                    format!("{path} ({change})")
+                })
+                .collect();
+            changes.push(HandoffRepoChanges {
+                name: repo.name,
+                target_branch,
+                files,
+            });
+        }
+        Ok(changes)
+    }
+
+    /// Continue the attempt with a different executor profile, in a new session that starts from
+    /// the conversation so far and the changes made, and record the handoff
+    async fn hand_off(
+        &self,
+        workspace: &Workspace,
+        executor_profile_id: ExecutorProfileId,
+        reason: Option<String>,
+    ) -> Result<AttemptHandoff, ContainerError> {
+        let pool = &self.db().pool;
+        let from_session = Session::find_latest_by_workspace_id(pool, workspace.id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let from_executor_profile_id =
+            ExecutionProcess::latest_executor_profile_for_session(pool, from_session.id)
+                .await
+                .map_err(|e| {
+                    ContainerError::Other(anyhow!("Failed to get executor profile: {e}"))
+                })?;
+        let turns = CodingAgentTurn::find_by_session_id(pool, from_session.id).await?;
+        let changes = self.handoff_changes(workspace).await?;
+
+        let task = workspace
+            .parent_task(pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let prompt = attempt_handoff::handoff_prompt(
+            &task.to_prompt(),
+            &from_executor_profile_id,
+            &turns,
+            &changes,
+            reason.as_deref(),
+        );
+
+        let to_session = Session::create(
+            pool,
+            &CreateSession {
+                executor: Some(executor_profile_id.executor.to_string()),
+            },
+            Uuid::new_v4(),
+            workspace.id,
+        )
+        .await?;
+        let project_repos =
+            ProjectRepo::find_by_project_id_with_names(pool, task.project_id).await?;
+        let cleanup_action = self.cleanup_actions_for_repos(&project_repos);
+        let working_dir = workspace
+            .agent_working_dir
+            .as_ref()
+            .filter(|dir| !dir.is_empty())
+            .cloned();
+        let action = ExecutorAction::new(
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt,
+                executor_profile_id: executor_profile_id.clone(),
+                working_dir,
+            }),
+            cleanup_action.map(Box::new),
+        );
+        let execution_process = self
+            .start_execution(
+                workspace,
+                &to_session,
+                &action,
+                &ExecutionProcessRunReason::CodingAgent,
+            )
+            .await?;
+
+        Ok(AttemptHandoff::create(
+            pool,
+            &CreateAttemptHandoff {
+                workspace_id: workspace.id,
+                from_session_id: from_session.id,
+                to_session_id: to_session.id,
+                from_executor_profile_id,
+                to_executor_profile_id: executor_profile_id,
+                reason,
+                execution_process_id: execution_process.id,
+            },
+        )
+        .await?)
+    }
+
+    /// Commit a dry-run attempt's patches to its branch, after which the attempt commits as
+    /// usual. The worktrees are reset to exactly the patches first.
+    async fn promote_patches(&self, workspace: &Workspace) -> Result<(), ContainerError> {
+        let pool = &self.db().pool;
+        let workspace_root = workspace
+            .container_ref
+            .as_ref()
+            .map(PathBuf::from)
+            .ok_or_else(|| ContainerError::Other(anyhow!("Container ref not found")))?;
+        let task = workspace
+            .parent_task(pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let repos = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+        let message = format!("{}\n\nPromoted from a dry-run attempt", task.title);
+
+        for patch in AttemptPatch::find_by_workspace_id(pool, workspace.id).await? {
+            let Some(repo) = repos.iter().find(|repo| repo.id == patch.repo_id) else {
+                continue;
+            };
+            let worktree_path = workspace_root.join(&repo.name);
+            self.git().apply_patch(&worktree_path, &patch.patch)?;
+            self.git().commit(&worktree_path, &message)?;
+        }
+        AttemptDryRun::mark_promoted(pool, workspace.id).await?;
+        Ok(())
+    }
+
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError>;
 
     async fn copy_project_files(
@@ -624,11 +1054,17 @@ pub trait ContainerService {
         map.get(uuid).cloned()
     }
 
-    async fn git_branch_prefix(&self) -> String;
+    /// Branch prefix for a project, with its override (if any) applied
+    async fn git_branch_prefix(&self, project_id: Uuid) -> String;
 
-    async fn git_branch_from_workspace(&self, workspace_id: &Uuid, task_title: &str) -> String {
+    async fn git_branch_from_workspace(
+        &self,
+        project_id: Uuid,
+        workspace_id: &Uuid,
+        task_title: &str,
+    ) -> String {
         let task_title_id = git_branch_id(task_title);
-        let prefix = self.git_branch_prefix().await;
+        let prefix = self.git_branch_prefix(project_id).await;
 
         if prefix.is_empty() {
             format!("{}-{}", short_uuid(workspace_id), task_title_id)
@@ -895,20 +1331,63 @@ pub trait ContainerService {
         })
     }
 
+    /// Create a workspace for `task` targeting `repos`, with the project's branch prefix and
+    /// agent working directory. The agent is started separately with `start_workspace`.
+    async fn create_task_workspace(
+        &self,
+        task: &Task,
+        repos: &[CreateWorkspaceRepo],
+    ) -> Result<Workspace, ContainerError> {
+        let pool = &self.db().pool;
+        let project = task
+            .parent_project(pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let agent_working_dir = project
+            .default_agent_working_dir
+            .as_ref()
+            .filter(|dir| !dir.is_empty())
+            .cloned();
+
+        let workspace_id = Uuid::new_v4();
+        let branch = self
+            .git_branch_from_workspace(task.project_id, &workspace_id, &task.title)
+            .await;
+        let workspace = Workspace::create(
+            pool,
+            &CreateWorkspace {
+                branch,
+                agent_working_dir,
+            },
+            workspace_id,
+            task.id,
+        )
+        .await?;
+        WorkspaceRepo::create_many(pool, workspace.id, repos).await?;
+        Ok(workspace)
+    }
+
     async fn start_workspace(
         &self,
         workspace: &Workspace,
         executor_profile_id: ExecutorProfileId,
     ) -> Result<ExecutionProcess, ContainerError> {
-        // Create container
-        self.create(workspace).await?;
-
         // Get parent task
         let task = workspace
             .parent_task(&self.db().pool)
             .await?
             .ok_or(SqlxError::RowNotFound)?;
 
+        // New attempts stay off once a hard budget limit is reached for the month
+        if let Some(status) =
+            budget::hard_limit_reached(&self.db().pool, task.project_id, chrono::Utc::now()).await?
+        {
+            return Err(ContainerError::BudgetExceeded(budget::describe(&status)));
+        }
+
+        // Create container
+        self.create(workspace).await?;
+
         // Get parent project
         let project = task
             .parent_project(&self.db().pool)
@@ -933,7 +1412,27 @@ pub trait ContainerService {
         )
         .await?;
 
-        let prompt = task.to_prompt();
+        // The checklist, comments and attachments carry context humans left for the agent
+        let checklist = TaskChecklistItem::find_by_task_id(&self.db().pool, task.id).await?;
+        let comments = TaskComment::find_by_task_id(&self.db().pool, task.id).await?;
+        let attachments = TaskAttachment::find_by_task_id(&self.db().pool, task.id).await?;
+        let prompt = [
+            Some(task.to_prompt()),
+            task_checklist_item::checklist_prompt(&checklist),
+            task_comment::comments_prompt(&comments),
+            task_attachment::attachments_prompt(&attachments),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n\n");
+        // A plan-first attempt's first run only writes the plan
+        let prompt = match AttemptPlan::find_by_workspace_id(&self.db().pool, workspace.id).await? {
+            Some(plan) if plan.status == AttemptPlanStatus::Drafting => {
+                attempt_plan::planning_prompt(&prompt)
+            }
+            _ => prompt,
+        };
 
         let repos_with_setup: Vec<_> = project_repos
             .iter()
@@ -994,6 +1493,24 @@ pub trait ContainerService {
             .await?
         };
 
+        let context = NotificationContext::new(NotificationEvent::TaskStarted)
+            .with_task(project.id, task.id)
+            .with_workspace(workspace.id)
+            .with_names(&project.name, &task.title)
+            .with_executor(executor_profile_id.executor.to_string());
+        let locale = self.notification_service().locale().await;
+        self.notification_service()
+            .notify(
+                &context,
+                &format!("[{}]: {}", project.name, task.title),
+                &i18n::t(
+                    locale,
+                    "task-started",
+                    &[("executor", executor_profile_id.to_string().as_str())],
+                ),
+            )
+            .await;
+
         Ok(execution_process)
     }
 
@@ -1089,6 +1606,19 @@ pub trait ContainerService {
             .await?;
         }
 
+        if let Some(executor_profile_id) = executor_action.executor_profile_id() {
+            let model = ExecutorConfigs::get_cached()
+                .get_coding_agent(executor_profile_id)
+                .and_then(|agent| agent.model());
+            ExecutionProfile::record(
+                &self.db().pool,
+                execution_process.id,
+                executor_profile_id,
+                model.as_deref(),
+            )
+            .await?;
+        }
+
         if let Err(start_error) = self
             .start_execution_inner(workspace, &execution_process, executor_action)
             .await
@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use db::{DBService, models::notification_escalation::NotificationEscalation};
+use tokio::time::interval;
+use tracing::{error, info};
+
+use crate::services::notification::NotificationService;
+
+/// Background scheduler that escalates failure notifications nobody acknowledged in time
+pub struct EscalationService {
+    db: DBService,
+    poll_interval: Duration,
+    notification_service: NotificationService,
+}
+
+impl EscalationService {
+    pub async fn spawn(
+        db: DBService,
+        notification_service: NotificationService,
+    ) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            poll_interval: Duration::from_secs(30),
+            notification_service,
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting notification escalation service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+
+        loop {
+            interval.tick().await;
+            let due = match NotificationEscalation::find_due(&self.db.pool).await {
+                Ok(due) => due,
+                Err(e) => {
+                    error!("Error loading due notification escalations: {}", e);
+                    continue;
+                }
+            };
+            for escalation in due {
+                if let Err(e) = self.notification_service.escalate(&escalation).await {
+                    error!("Failed to escalate notification {}: {}", escalation.id, e);
+                }
+            }
+        }
+    }
+}
@@ -4,7 +4,7 @@ use db::{
     DBService,
     models::{
         execution_process::ExecutionProcess, project::Project, scratch::Scratch, task::Task,
-        workspace::Workspace,
+        task_comment::TaskComment, workspace::Workspace,
     },
 };
 use serde_json::json;
@@ -21,7 +21,8 @@ mod streams;
 pub mod types;
 
 pub use patches::{
-    execution_process_patch, project_patch, scratch_patch, task_patch, workspace_patch,
+    config_patch, execution_process_patch, project_patch, scratch_patch, task_comment_patch,
+    task_patch, workspace_patch,
 };
 pub use types::{EventError, EventPatch, EventPatchInner, HookTables, RecordTypes};
 
@@ -49,7 +50,8 @@ impl EventService {
         task_id: Uuid,
     ) -> Result<(), SqlxError> {
         if let Some(task) = Task::find_by_id(pool, task_id).await? {
-            let tasks = Task::find_by_project_id_with_attempt_status(pool, task.project_id).await?;
+            let tasks =
+                Task::find_by_project_id_with_attempt_status(pool, task.project_id, false).await?;
 
             if let Some(task_with_status) = tasks
                 .into_iter()
@@ -137,6 +139,14 @@ impl EventService {
                                     msg_store_for_preupdate.push_patch(patch);
                                 }
                             }
+                            "task_comments" => {
+                                if let Ok(value) = preupdate.get_old_column_value(0)
+                                    && let Ok(comment_id) = <Uuid as Decode<Sqlite>>::decode(value)
+                                {
+                                    let patch = task_comment_patch::remove(comment_id);
+                                    msg_store_for_preupdate.push_patch(patch);
+                                }
+                            }
                             "scratch" => {
                                 // Composite key: need both id (column 0) and scratch_type (column 1)
                                 if let Ok(id_val) = preupdate.get_old_column_value(0)
@@ -168,7 +178,8 @@ impl EventService {
                                 | (HookTables::Projects, SqliteOperation::Delete)
                                 | (HookTables::Workspaces, SqliteOperation::Delete)
                                 | (HookTables::ExecutionProcesses, SqliteOperation::Delete)
-                                | (HookTables::Scratch, SqliteOperation::Delete) => {
+                                | (HookTables::Scratch, SqliteOperation::Delete)
+                                | (HookTables::TaskComments, SqliteOperation::Delete) => {
                                     // Deletions handled in preupdate hook for reliable data capture
                                     return;
                                 }
@@ -246,6 +257,22 @@ impl EventService {
                                         }
                                     }
                                 }
+                                (HookTables::TaskComments, _) => {
+                                    match TaskComment::find_by_rowid(&db.pool, rowid).await {
+                                        Ok(Some(comment)) => RecordTypes::TaskComment(comment),
+                                        Ok(None) => RecordTypes::DeletedTaskComment {
+                                            rowid,
+                                            comment_id: None,
+                                        },
+                                        Err(e) => {
+                                            tracing::error!(
+                                                "Failed to fetch task comment: {:?}",
+                                                e
+                                            );
+                                            return;
+                                        }
+                                    }
+                                }
                             };
 
                             let db_op: &str = match hook.operation {
@@ -263,17 +290,23 @@ impl EventService {
                                         Task::find_by_project_id_with_attempt_status(
                                             &db.pool,
                                             task.project_id,
+                                            true,
                                         )
                                         .await
                                         && let Some(task_with_status) =
                                             task_list.into_iter().find(|t| t.id == task.id)
                                     {
                                         let patch = match hook.operation {
+                                            // Archived tasks leave the board
+                                            _ if task_with_status.archived_at.is_some() => {
+                                                task_patch::remove(task.id)
+                                            }
                                             SqliteOperation::Insert => {
                                                 task_patch::add(&task_with_status)
                                             }
+                                            // `add` also replaces, and brings restored tasks back
                                             SqliteOperation::Update => {
-                                                task_patch::replace(&task_with_status)
+                                                task_patch::add(&task_with_status)
                                             }
                                             _ => task_patch::replace(&task_with_status), // fallback
                                         };
@@ -307,6 +340,17 @@ impl EventService {
                                     msg_store_for_hook.push_patch(patch);
                                     return;
                                 }
+                                RecordTypes::TaskComment(comment) => {
+                                    let patch = match hook.operation {
+                                        SqliteOperation::Insert => task_comment_patch::add(comment),
+                                        SqliteOperation::Update => {
+                                            task_comment_patch::replace(comment)
+                                        }
+                                        _ => task_comment_patch::replace(comment),
+                                    };
+                                    msg_store_for_hook.push_patch(patch);
+                                    return;
+                                }
                                 RecordTypes::DeletedScratch {
                                     scratch_id: Some(scratch_id),
                                     scratch_type: Some(scratch_type_str),
@@ -324,6 +368,7 @@ impl EventService {
                                             Task::find_by_project_id_with_attempt_status(
                                                 &db.pool,
                                                 task.project_id,
+                                                false,
                                             )
                                             .await
                                         && let Some(task_with_status) =
@@ -345,6 +390,7 @@ impl EventService {
                                             Task::find_by_project_id_with_attempt_status(
                                                 &db.pool,
                                                 task.project_id,
+                                                false,
                                             )
                                             .await
                                         && let Some(task_with_status) =
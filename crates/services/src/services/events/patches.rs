@@ -1,6 +1,6 @@
 use db::models::{
     execution_process::ExecutionProcess, project::Project, scratch::Scratch,
-    task::TaskWithAttemptStatus, workspace::Workspace,
+    task::TaskWithAttemptStatus, task_comment::TaskComment, workspace::Workspace,
 };
 use json_patch::{AddOperation, Patch, PatchOperation, RemoveOperation, ReplaceOperation};
 use uuid::Uuid;
@@ -48,6 +48,49 @@ pub mod task_patch {
     }
 }
 
+/// Helper functions for creating task comment patches
+pub mod task_comment_patch {
+    use super::*;
+
+    fn task_comment_path(comment_id: Uuid) -> String {
+        format!(
+            "/task_comments/{}",
+            escape_pointer_segment(&comment_id.to_string())
+        )
+    }
+
+    /// Create patch for adding a new comment
+    pub fn add(comment: &TaskComment) -> Patch {
+        Patch(vec![PatchOperation::Add(AddOperation {
+            path: task_comment_path(comment.id)
+                .try_into()
+                .expect("Task comment path should be valid"),
+            value: serde_json::to_value(comment)
+                .expect("Task comment serialization should not fail"),
+        })])
+    }
+
+    /// Create patch for updating an existing comment
+    pub fn replace(comment: &TaskComment) -> Patch {
+        Patch(vec![PatchOperation::Replace(ReplaceOperation {
+            path: task_comment_path(comment.id)
+                .try_into()
+                .expect("Task comment path should be valid"),
+            value: serde_json::to_value(comment)
+                .expect("Task comment serialization should not fail"),
+        })])
+    }
+
+    /// Create patch for removing a comment
+    pub fn remove(comment_id: Uuid) -> Patch {
+        Patch(vec![PatchOperation::Remove(RemoveOperation {
+            path: task_comment_path(comment_id)
+                .try_into()
+                .expect("Task comment path should be valid"),
+        })])
+    }
+}
+
 /// Helper functions for creating project-specific patches
 pub mod project_patch {
     use super::*;
@@ -217,3 +260,19 @@ pub mod scratch_patch {
         })])
     }
 }
+
+/// Helper functions for creating config patches
+pub mod config_patch {
+    use super::*;
+    use crate::services::config::Config;
+
+    const CONFIG_PATH: &str = "/config";
+
+    /// Create patch for a config that changed outside the settings API
+    pub fn replace(config: &Config) -> Patch {
+        Patch(vec![PatchOperation::Add(AddOperation {
+            path: CONFIG_PATH.try_into().expect("Config path should be valid"),
+            value: serde_json::to_value(config).expect("Config serialization should not fail"),
+        })])
+    }
+}
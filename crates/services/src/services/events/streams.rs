@@ -4,6 +4,7 @@ use db::models::{
     scratch::Scratch,
     session::Session,
     task::{Task, TaskWithAttemptStatus},
+    task_comment::TaskComment,
 };
 use futures::StreamExt;
 use serde_json::json;
@@ -25,7 +26,8 @@ impl EventService {
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, EventError>
     {
         // Get initial snapshot of tasks
-        let tasks = Task::find_by_project_id_with_attempt_status(&self.db.pool, project_id).await?;
+        let tasks =
+            Task::find_by_project_id_with_attempt_status(&self.db.pool, project_id, false).await?;
 
         // Convert task array to object keyed by task ID
         let tasks_map: serde_json::Map<String, serde_json::Value> = tasks
@@ -369,6 +371,70 @@ impl EventService {
     }
 
     /// Stream a single scratch item with initial snapshot (raw LogMsg format for WebSocket)
+    /// Stream a task's comments with initial snapshot (raw LogMsg format for WebSocket)
+    pub async fn stream_task_comments_raw(
+        &self,
+        task_id: Uuid,
+    ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, EventError>
+    {
+        let comments = TaskComment::find_by_task_id(&self.db.pool, task_id).await?;
+        let comments_map: serde_json::Map<String, serde_json::Value> = comments
+            .into_iter()
+            .map(|comment| {
+                (
+                    comment.id.to_string(),
+                    serde_json::to_value(comment).unwrap(),
+                )
+            })
+            .collect();
+
+        let initial_patch = json!([{
+            "op": "replace",
+            "path": "/task_comments",
+            "value": comments_map
+        }]);
+        let initial_msg = LogMsg::JsonPatch(serde_json::from_value(initial_patch).unwrap());
+
+        let filtered_stream =
+            BroadcastStream::new(self.msg_store.get_receiver()).filter_map(move |msg_result| {
+                async move {
+                    match msg_result {
+                        Ok(LogMsg::JsonPatch(patch)) => {
+                            if let Some(op) = patch.0.first()
+                                && op.path().starts_with("/task_comments/")
+                            {
+                                let value = match op {
+                                    json_patch::PatchOperation::Add(a) => Some(&a.value),
+                                    json_patch::PatchOperation::Replace(r) => Some(&r.value),
+                                    // Removals carry no task id; let the client ignore
+                                    // comments it doesn't have
+                                    json_patch::PatchOperation::Remove(_) => {
+                                        return Some(Ok(LogMsg::JsonPatch(patch)));
+                                    }
+                                    _ => None,
+                                };
+                                if value
+                                    .and_then(|v| {
+                                        serde_json::from_value::<TaskComment>(v.clone()).ok()
+                                    })
+                                    .is_some_and(|comment| comment.task_id == task_id)
+                                {
+                                    return Some(Ok(LogMsg::JsonPatch(patch)));
+                                }
+                            }
+                            None
+                        }
+                        Ok(other) => Some(Ok(other)),
+                        Err(_) => None,
+                    }
+                }
+            });
+
+        let initial_stream = futures::stream::once(async move { Ok(initial_msg) });
+        let combined_stream = initial_stream.chain(filtered_stream).boxed();
+        Ok(combined_stream)
+    }
+
     pub async fn stream_scratch_raw(
         &self,
         scratch_id: Uuid,
@@ -0,0 +1,212 @@
+//! Coding agent runs are watched for running past their profile's maximum duration and for
+//! going quiet: no new output for the profile's stall period. A stall is acted on once, then
+//! again only after output resumes and stops again.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use executors::profile::ExecutorProfileId;
+
+use crate::services::config::{ExecutionLimits, StallAction};
+
+/// Prefix of the log lines the watchdog adds to a run's output
+pub const WATCHDOG_LOG_MARKER: &str = "[watchdog]";
+
+/// Follow-up sent to a stalled agent when its profile's stall action is `Continue`
+pub const CONTINUE_PROMPT: &str = "You stopped producing output for a while. Continue with the task where you left off; if you are stuck, explain what is blocking you.";
+
+/// The limits for a profile: its variant's entry if there is one, otherwise its executor's
+pub fn limits_for<'a>(
+    limits: &'a HashMap<String, ExecutionLimits>,
+    profile: &ExecutorProfileId,
+) -> Option<&'a ExecutionLimits> {
+    limits
+        .get(&profile.to_string())
+        .or_else(|| limits.get(&profile.executor.to_string()))
+        .filter(|limits| {
+            limits.max_duration_minutes.is_some() || limits.stall_after_minutes.is_some()
+        })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogAction {
+    /// Ran past the maximum duration
+    TimedOut {
+        minutes: u32,
+    },
+    Stalled {
+        minutes: u32,
+        action: StallAction,
+    },
+}
+
+impl WatchdogAction {
+    pub fn minutes(&self) -> u32 {
+        match self {
+            Self::TimedOut { minutes } | Self::Stalled { minutes, .. } => *minutes,
+        }
+    }
+
+    /// Keys of the notification's title and message
+    pub fn notification_keys(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::TimedOut { .. } => ("attempt-timed-out-title", "attempt-timed-out"),
+            Self::Stalled { action, .. } => (
+                "attempt-stalled-title",
+                match action {
+                    StallAction::Warn => "attempt-stalled",
+                    StallAction::Continue => "attempt-stalled-continued",
+                    StallAction::Kill => "attempt-stalled-killed",
+                },
+            ),
+        }
+    }
+
+    pub fn log_line(&self) -> String {
+        match self {
+            Self::TimedOut { minutes } => {
+                format!("{WATCHDOG_LOG_MARKER} Ran past the {minutes}-minute limit; stopping")
+            }
+            Self::Stalled { minutes, action } => {
+                let then = match action {
+                    StallAction::Warn => "",
+                    StallAction::Continue => "; asking the agent to continue",
+                    StallAction::Kill => "; stopping",
+                };
+                format!("{WATCHDOG_LOG_MARKER} No output for {minutes} minutes{then}")
+            }
+        }
+    }
+}
+
+fn minutes(minutes: u32) -> Duration {
+    Duration::from_secs(minutes as u64 * 60)
+}
+
+/// Tracks one run's running time and last output
+#[derive(Debug)]
+pub struct Watchdog {
+    limits: ExecutionLimits,
+    /// Time the run has spent running, paused time left out
+    running: Duration,
+    last_check: Instant,
+    last_output: Instant,
+    stalled: bool,
+}
+
+impl Watchdog {
+    pub fn new(limits: ExecutionLimits, now: Instant) -> Self {
+        Self {
+            limits,
+            running: Duration::ZERO,
+            last_check: now,
+            last_output: now,
+            stalled: false,
+        }
+    }
+
+    pub fn output(&mut self, now: Instant) {
+        self.last_output = now;
+        self.stalled = false;
+    }
+
+    /// Called periodically. Time spent paused counts neither toward the maximum duration nor
+    /// toward a stall.
+    pub fn check(&mut self, now: Instant, paused: bool) -> Option<WatchdogAction> {
+        let since_last_check = now.saturating_duration_since(self.last_check);
+        self.last_check = now;
+        if paused {
+            self.last_output = now;
+            return None;
+        }
+        self.running += since_last_check;
+
+        if let Some(max) = self.limits.max_duration_minutes
+            && self.running >= minutes(max)
+        {
+            return Some(WatchdogAction::TimedOut { minutes: max });
+        }
+        if let Some(stall) = self.limits.stall_after_minutes
+            && !self.stalled
+            && now.saturating_duration_since(self.last_output) >= minutes(stall)
+        {
+            self.stalled = true;
+            return Some(WatchdogAction::Stalled {
+                minutes: stall,
+                action: self.limits.on_stall,
+            });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+
+    fn limits(max: Option<u32>, stall: Option<u32>) -> ExecutionLimits {
+        ExecutionLimits {
+            max_duration_minutes: max,
+            stall_after_minutes: stall,
+            on_stall: StallAction::Warn,
+        }
+    }
+
+    #[test]
+    fn test_limits_for_prefers_variant() {
+        let configured = HashMap::from([
+            ("CLAUDE_CODE".to_string(), limits(Some(60), None)),
+            ("CLAUDE_CODE:PLAN".to_string(), limits(Some(10), None)),
+            ("AMP".to_string(), limits(None, None)),
+        ]);
+        let mut profile = ExecutorProfileId::new(BaseCodingAgent::ClaudeCode);
+        let max = |profile: &ExecutorProfileId| {
+            limits_for(&configured, profile).and_then(|limits| limits.max_duration_minutes)
+        };
+
+        assert_eq!(max(&profile), Some(60));
+        profile.variant = Some("PLAN".to_string());
+        assert_eq!(max(&profile), Some(10));
+        profile.variant = Some("ROUTER".to_string());
+        assert_eq!(max(&profile), Some(60));
+        // An entry without limits is the same as none
+        assert!(limits_for(&configured, &ExecutorProfileId::new(BaseCodingAgent::Amp)).is_none());
+    }
+
+    #[test]
+    fn test_check() {
+        let start = Instant::now();
+        let at = |mins: u64| start + Duration::from_secs(mins * 60);
+        let mut watchdog = Watchdog::new(limits(Some(60), Some(10)), start);
+
+        assert_eq!(watchdog.check(at(5), false), None);
+        let stalled = watchdog.check(at(10), false);
+        assert_eq!(
+            stalled,
+            Some(WatchdogAction::Stalled {
+                minutes: 10,
+                action: StallAction::Warn
+            })
+        );
+        assert_eq!(
+            stalled.unwrap().log_line(),
+            "[watchdog] No output for 10 minutes"
+        );
+        // Reported once until output resumes
+        assert_eq!(watchdog.check(at(15), false), None);
+        watchdog.output(at(16));
+        assert_eq!(watchdog.check(at(20), false), None);
+
+        // Paused time counts toward neither limit
+        assert_eq!(watchdog.check(at(50), true), None);
+        assert_eq!(watchdog.check(at(55), false), None);
+        assert_eq!(
+            watchdog.check(at(90), false),
+            Some(WatchdogAction::TimedOut { minutes: 60 })
+        );
+    }
+}
@@ -1068,6 +1068,21 @@ impl GitService {
         Ok(commit.summary().unwrap_or("(no subject)").to_string())
     }
 
+    /// Full SHA of the commit an abbreviated or full SHA names, or `None` if the repo has no such
+    /// commit or the abbreviation is ambiguous
+    pub fn resolve_commit_sha(
+        &self,
+        repo_path: &Path,
+        commit_sha: &str,
+    ) -> Result<Option<String>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        Ok(repo
+            .revparse_single(commit_sha)
+            .and_then(|object| object.peel_to_commit())
+            .ok()
+            .map(|commit| commit.id().to_string()))
+    }
+
     /// Compare two OIDs and return (ahead, behind) counts: how many commits
     /// `from_oid` is ahead of and behind `to_oid`.
     pub fn ahead_behind_commits_by_oid(
@@ -1161,6 +1176,25 @@ impl GitService {
         Ok(())
     }
 
+    /// The worktree's uncommitted changes as a patch against HEAD, untracked files included
+    pub fn worktree_patch(&self, worktree_path: &Path) -> Result<String, GitServiceError> {
+        GitCli::new()
+            .worktree_patch(worktree_path)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git diff failed: {e}")))
+    }
+
+    /// Replace the worktree's uncommitted changes with `patch`, leaving them uncommitted
+    pub fn apply_patch(&self, worktree_path: &Path, patch: &str) -> Result<(), GitServiceError> {
+        let cli = GitCli::new();
+        cli.git(worktree_path, ["reset", "--hard", "HEAD"])
+            .and_then(|_| cli.git(worktree_path, ["clean", "-fd"]))
+            .map_err(|e| {
+                GitServiceError::InvalidRepository(format!("Failed to clean worktree: {e}"))
+            })?;
+        cli.apply_patch(worktree_path, patch)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git apply failed: {e}")))
+    }
+
     /// Add a worktree for a branch, optionally creating the branch
     pub fn add_worktree(
         &self,
@@ -181,7 +181,30 @@ impl GitCli {
         )];
 
         // Use a temp index from HEAD to accurately track renames in untracked files
-        let _ = self.git_with_env(worktree_path, ["read-tree", "HEAD"], &envs)?;
+        self.stage_worktree(worktree_path, &envs)?;
+        // git diff --cached
+        let mut args: Vec<OsString> = vec![
+            "-c".into(),
+            "core.quotepath=false".into(),
+            "diff".into(),
+            "--cached".into(),
+            "-M".into(),
+            "--name-status".into(),
+            OsString::from(base_commit.to_string()),
+        ];
+        args = Self::apply_pathspec_filter(args, opts.path_filter.as_ref());
+        let out = self.git_with_env(worktree_path, args, &envs)?;
+        Ok(Self::parse_name_status(&out))
+    }
+
+    /// Stage every change in the worktree, untracked files included, into the index `envs`
+    /// points `GIT_INDEX_FILE` at, starting from HEAD
+    fn stage_worktree(
+        &self,
+        worktree_path: &Path,
+        envs: &[(OsString, OsString)],
+    ) -> Result<(), GitCliError> {
+        let _ = self.git_with_env(worktree_path, ["read-tree", "HEAD"], envs)?;
 
         // Stage changed and untracked files explicitly, which is faster than `git add -A` for large repos.
         // Use raw paths from `get_worktree_status` to avoid lossy UTF-8 conversions for odd filenames.
@@ -210,21 +233,42 @@ impl GitCli {
                 OsString::from("--pathspec-from-file=-"),
                 OsString::from("--pathspec-file-nul"),
             ];
-            self.git_with_stdin(worktree_path, args, Some(&envs), &input)?;
+            self.git_with_stdin(worktree_path, args, Some(envs), &input)?;
         }
-        // git diff --cached
-        let mut args: Vec<OsString> = vec![
-            "-c".into(),
-            "core.quotepath=false".into(),
-            "diff".into(),
-            "--cached".into(),
-            "-M".into(),
-            "--name-status".into(),
-            OsString::from(base_commit.to_string()),
-        ];
-        args = Self::apply_pathspec_filter(args, opts.path_filter.as_ref());
-        let out = self.git_with_env(worktree_path, args, &envs)?;
-        Ok(Self::parse_name_status(&out))
+        Ok(())
+    }
+
+    /// The worktree's changes against HEAD as a patch, untracked files included, without
+    /// touching the real index
+    pub fn worktree_patch(&self, worktree_path: &Path) -> Result<String, GitCliError> {
+        let tmp_dir = tempfile::TempDir::new()
+            .map_err(|e| GitCliError::CommandFailed(format!("temp dir create failed: {e}")))?;
+        let tmp_index = tmp_dir.path().join("index");
+        let envs = vec![(
+            OsString::from("GIT_INDEX_FILE"),
+            tmp_index.as_os_str().to_os_string(),
+        )];
+        self.stage_worktree(worktree_path, &envs)?;
+        let args = Self::apply_default_excludes([
+            "diff",
+            "--cached",
+            "--binary",
+            "--no-color",
+            "-M",
+            "HEAD",
+        ]);
+        self.git_with_env(worktree_path, args, &envs)
+    }
+
+    /// Apply a patch from [`Self::worktree_patch`] to the worktree
+    pub fn apply_patch(&self, worktree_path: &Path, patch: &str) -> Result<(), GitCliError> {
+        self.git_with_stdin(
+            worktree_path,
+            ["apply", "--whitespace=nowarn", "-"],
+            None,
+            patch.as_bytes(),
+        )?;
+        Ok(())
     }
 
     /// Return `git status --porcelain` parsed into a structured summary
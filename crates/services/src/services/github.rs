@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use backon::{ExponentialBuilder, Retryable};
 use chrono::{DateTime, Utc};
-use db::models::merge::PullRequestInfo;
+use db::models::{github_issue::GitHubIssueState, merge::PullRequestInfo};
 use regex::Regex;
 use serde::Serialize;
 use thiserror::Error;
@@ -13,7 +13,7 @@ use ts_rs::TS;
 mod cli;
 
 use cli::{GhCli, GhCliError, PrComment, PrReviewComment};
-pub use cli::{PrCommentAuthor, ReviewCommentUser};
+pub use cli::{Issue, IssueComment, IssueLabel, PrCommentAuthor, ReviewCommentUser};
 
 /// Unified PR comment that can be either a general comment or review comment
 #[derive(Debug, Clone, Serialize, TS)]
@@ -450,4 +450,112 @@ impl GitHubService {
         })
         .await
     }
+
+    /// List a repo's issues (without pull requests): open ones, or with `since` those in any
+    /// state updated since then
+    pub async fn list_issues(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        since: Option<DateTime<Utc>>,
+        label: Option<&str>,
+    ) -> Result<Vec<Issue>, GitHubServiceError> {
+        let (owner, repo) = (repo_info.owner.clone(), repo_info.repo_name.clone());
+        let label = label.map(str::to_string);
+        self.run_cli("listing issues", move |cli| {
+            cli.list_issues(&owner, &repo, since, label.as_deref())
+        })
+        .await
+    }
+
+    pub async fn list_issue_comments(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        issue_number: i64,
+    ) -> Result<Vec<IssueComment>, GitHubServiceError> {
+        let (owner, repo) = (repo_info.owner.clone(), repo_info.repo_name.clone());
+        self.run_cli("fetching issue comments", move |cli| {
+            cli.list_issue_comments(&owner, &repo, issue_number)
+        })
+        .await
+    }
+
+    /// Open or close an issue; `reason` is `completed` or `not_planned` when closing
+    pub async fn set_issue_state(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        issue_number: i64,
+        state: GitHubIssueState,
+        reason: Option<&'static str>,
+    ) -> Result<(), GitHubServiceError> {
+        let (owner, repo) = (repo_info.owner.clone(), repo_info.repo_name.clone());
+        self.run_cli("updating issue state", move |cli| {
+            cli.set_issue_state(&owner, &repo, issue_number, state, reason)
+        })
+        .await
+    }
+
+    /// Replace an issue's labels
+    pub async fn set_issue_labels(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        issue_number: i64,
+        labels: &[String],
+    ) -> Result<(), GitHubServiceError> {
+        let (owner, repo) = (repo_info.owner.clone(), repo_info.repo_name.clone());
+        let labels = labels.to_vec();
+        self.run_cli("updating issue labels", move |cli| {
+            cli.set_issue_labels(&owner, &repo, issue_number, &labels)
+        })
+        .await
+    }
+
+    pub async fn create_issue_comment(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        issue_number: i64,
+        body: &str,
+    ) -> Result<IssueComment, GitHubServiceError> {
+        let (owner, repo) = (repo_info.owner.clone(), repo_info.repo_name.clone());
+        let body = body.to_string();
+        self.run_cli("commenting on an issue", move |cli| {
+            cli.create_issue_comment(&owner, &repo, issue_number, &body)
+        })
+        .await
+    }
+
+    /// Run a GitHub CLI call off the async runtime, retrying failures that may be transient
+    async fn run_cli<T, F>(&self, action: &str, call: F) -> Result<T, GitHubServiceError>
+    where
+        T: Send + 'static,
+        F: Fn(&GhCli) -> Result<T, GhCliError> + Clone + Send + 'static,
+    {
+        (|| async {
+            let cli = self.gh_cli.clone();
+            let call = call.clone();
+            task::spawn_blocking(move || call(&cli))
+                .await
+                .map_err(|err| {
+                    GitHubServiceError::Repository(format!(
+                        "Failed to execute GitHub CLI for {action}: {err}"
+                    ))
+                })?
+                .map_err(GitHubServiceError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHubServiceError| e.should_retry())
+        .notify(|err: &GitHubServiceError, dur: Duration| {
+            tracing::warn!(
+                "GitHub API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
 }
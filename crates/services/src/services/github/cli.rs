@@ -9,9 +9,12 @@ use std::{
     process::Command,
 };
 
-use chrono::{DateTime, Utc};
-use db::models::merge::{MergeStatus, PullRequestInfo};
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, SecondsFormat, Utc};
+use db::models::{
+    github_issue::GitHubIssueState,
+    merge::{MergeStatus, PullRequestInfo},
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json::Value;
 use thiserror::Error;
 use ts_rs::TS;
@@ -58,6 +61,38 @@ pub struct PrReviewComment {
     pub author_association: String,
 }
 
+/// A label on an issue (from gh api)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueLabel {
+    pub name: String,
+}
+
+/// An issue as returned by the REST API. The issues endpoints also list pull requests, which
+/// carry `pull_request`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Issue {
+    pub number: i64,
+    pub title: String,
+    pub body: Option<String>,
+    pub state: GitHubIssueState,
+    /// `completed` or `not_planned` once closed
+    pub state_reason: Option<String>,
+    pub html_url: String,
+    pub labels: Vec<IssueLabel>,
+    pub updated_at: DateTime<Utc>,
+    pub pull_request: Option<Value>,
+}
+
+/// A comment on an issue (from gh api)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueComment {
+    pub id: i64,
+    pub user: ReviewCommentUser,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub html_url: String,
+}
+
 /// High-level errors originating from the GitHub CLI.
 #[derive(Debug, Error)]
 pub enum GhCliError {
@@ -245,6 +280,130 @@ impl GhCli {
         ])?;
         Self::parse_pr_review_comments(&raw)
     }
+
+    /// List a repo's issues, leaving out pull requests. Without `since` only open issues are
+    /// listed; with it, issues in any state updated since then.
+    pub fn list_issues(
+        &self,
+        owner: &str,
+        repo: &str,
+        since: Option<DateTime<Utc>>,
+        label: Option<&str>,
+    ) -> Result<Vec<Issue>, GhCliError> {
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        query.append_pair("per_page", "100");
+        match since {
+            Some(since) => query
+                .append_pair("state", "all")
+                .append_pair("since", &since.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            None => query.append_pair("state", "open"),
+        };
+        if let Some(label) = label {
+            query.append_pair("labels", label);
+        }
+        let raw = self.run([
+            "api",
+            "--paginate",
+            &format!("repos/{owner}/{repo}/issues?{}", query.finish()),
+        ])?;
+        let issues: Vec<Issue> = Self::parse_paginated(&raw)?;
+        Ok(issues
+            .into_iter()
+            .filter(|issue| issue.pull_request.is_none())
+            .collect())
+    }
+
+    /// Fetch all comments on an issue, oldest first.
+    pub fn list_issue_comments(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue_number: i64,
+    ) -> Result<Vec<IssueComment>, GhCliError> {
+        let raw = self.run([
+            "api",
+            "--paginate",
+            &format!("repos/{owner}/{repo}/issues/{issue_number}/comments?per_page=100"),
+        ])?;
+        Self::parse_paginated(&raw)
+    }
+
+    /// Open or close an issue; `reason` is `completed` or `not_planned` when closing.
+    pub fn set_issue_state(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue_number: i64,
+        state: GitHubIssueState,
+        reason: Option<&str>,
+    ) -> Result<(), GhCliError> {
+        let state = match state {
+            GitHubIssueState::Open => "state=open",
+            GitHubIssueState::Closed => "state=closed",
+        };
+        let mut args = vec![
+            "api".to_string(),
+            "-X".to_string(),
+            "PATCH".to_string(),
+            format!("repos/{owner}/{repo}/issues/{issue_number}"),
+            "-f".to_string(),
+            state.to_string(),
+        ];
+        if let Some(reason) = reason {
+            args.push("-f".to_string());
+            args.push(format!("state_reason={reason}"));
+        }
+        self.run(args)?;
+        Ok(())
+    }
+
+    /// Replace an issue's labels; GitHub creates labels the repo doesn't have yet.
+    pub fn set_issue_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue_number: i64,
+        labels: &[String],
+    ) -> Result<(), GhCliError> {
+        let endpoint = format!("repos/{owner}/{repo}/issues/{issue_number}/labels");
+        if labels.is_empty() {
+            self.run(["api", "-X", "DELETE", &endpoint])?;
+            return Ok(());
+        }
+        let mut args = vec![
+            "api".to_string(),
+            "-X".to_string(),
+            "PUT".to_string(),
+            endpoint,
+        ];
+        for label in labels {
+            args.push("-f".to_string());
+            args.push(format!("labels[]={label}"));
+        }
+        self.run(args)?;
+        Ok(())
+    }
+
+    /// Post a comment on an issue.
+    pub fn create_issue_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue_number: i64,
+        body: &str,
+    ) -> Result<IssueComment, GhCliError> {
+        let raw = self.run([
+            "api",
+            &format!("repos/{owner}/{repo}/issues/{issue_number}/comments"),
+            "-f",
+            &format!("body={body}"),
+        ])?;
+        serde_json::from_str(raw.trim()).map_err(|err| {
+            GhCliError::UnexpectedOutput(format!(
+                "Failed to parse issue comment API response: {err}; raw: {raw}"
+            ))
+        })
+    }
 }
 
 impl GhCli {
@@ -357,6 +516,20 @@ impl GhCli {
         })
     }
 
+    /// `gh api --paginate` prints one JSON array per page back to back
+    fn parse_paginated<T: DeserializeOwned>(raw: &str) -> Result<Vec<T>, GhCliError> {
+        let mut items = Vec::new();
+        for page in serde_json::Deserializer::from_str(raw.trim()).into_iter::<Vec<T>>() {
+            let page = page.map_err(|err| {
+                GhCliError::UnexpectedOutput(format!(
+                    "Failed to parse paginated API response: {err}; raw: {raw}"
+                ))
+            })?;
+            items.extend(page);
+        }
+        Ok(items)
+    }
+
     fn extract_pr_info(value: &Value) -> Option<PullRequestInfo> {
         let number = value.get("number")?.as_i64()?;
         let url = value.get("url")?.as_str()?.to_string();
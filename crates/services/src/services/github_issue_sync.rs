@@ -0,0 +1,486 @@
+//! Two-way sync between a project's tasks and the GitHub issues of one of its repos. Open issues
+//! are imported as tasks; afterwards status, labels and comments follow whichever side changed
+//! since the last sync. GitHub is polled, since a local install can't receive webhooks.
+
+use std::{collections::HashSet, time::Duration};
+
+use chrono::Utc;
+use db::{
+    DBService,
+    models::{
+        assignment_rule::TaskSource,
+        github_issue::{GitHubIssueLink, GitHubIssueState, GitHubIssueSync, NewGitHubIssueLink},
+        label::Label,
+        repo::Repo,
+        task::{CreateTask, Task, TaskStatus},
+        task_comment::TaskComment,
+    },
+};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::time::interval;
+use tracing::{error, info};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{
+    auto_assign,
+    git::{GitService, GitServiceError},
+    github::{GitHubRepoInfo, GitHubService, GitHubServiceError, Issue},
+};
+
+#[derive(Debug, Error)]
+pub enum GitHubIssueSyncError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    GitHub(#[from] GitHubServiceError),
+    #[error(transparent)]
+    Git(#[from] GitServiceError),
+    #[error("The synced repository is no longer part of the project")]
+    RepoNotFound,
+}
+
+/// What one sync changed
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct GitHubIssueSyncResult {
+    pub tasks_created: usize,
+    pub tasks_updated: usize,
+    pub issues_updated: usize,
+    pub comments_imported: usize,
+    pub comments_posted: usize,
+}
+
+/// Task status for an issue that was opened or closed on GitHub. Reopening only moves finished
+/// tasks back to `todo`, so work in progress keeps its status.
+pub fn status_for_issue(
+    state: GitHubIssueState,
+    state_reason: Option<&str>,
+    current: &TaskStatus,
+) -> TaskStatus {
+    match state {
+        GitHubIssueState::Closed if state_reason == Some("not_planned") => TaskStatus::Cancelled,
+        GitHubIssueState::Closed => TaskStatus::Done,
+        GitHubIssueState::Open => match current {
+            TaskStatus::Done | TaskStatus::Cancelled => TaskStatus::Todo,
+            status => status.clone(),
+        },
+    }
+}
+
+/// Issue state, and the reason when closed, for a task's status
+pub fn issue_state_for_status(status: &TaskStatus) -> (GitHubIssueState, Option<&'static str>) {
+    match status {
+        TaskStatus::Done => (GitHubIssueState::Closed, Some("completed")),
+        TaskStatus::Cancelled => (GitHubIssueState::Closed, Some("not_planned")),
+        _ => (GitHubIssueState::Open, None),
+    }
+}
+
+fn label_key(label: &str) -> String {
+    label.trim().to_lowercase()
+}
+
+/// Whether two label lists hold the same labels, ignoring order and case
+pub fn same_labels(a: &[String], b: &[String]) -> bool {
+    let a: HashSet<_> = a.iter().map(|label| label_key(label)).collect();
+    let b: HashSet<_> = b.iter().map(|label| label_key(label)).collect();
+    a == b
+}
+
+/// Three-way merge of label sets against the last synced ones: labels added on either side are
+/// kept, labels removed on either side are dropped. Names are compared case-insensitively.
+pub fn merge_labels(base: &[String], local: &[String], remote: &[String]) -> Vec<String> {
+    let keys = |labels: &[String]| -> HashSet<String> {
+        labels.iter().map(|label| label_key(label)).collect()
+    };
+    let (base, local_keys, remote_keys) = (keys(base), keys(local), keys(remote));
+    let mut merged: Vec<String> = Vec::new();
+    let mut seen = HashSet::new();
+    for label in local.iter().chain(remote) {
+        let key = label_key(label);
+        let keep =
+            (local_keys.contains(&key) && remote_keys.contains(&key)) || !base.contains(&key);
+        if keep && seen.insert(key) {
+            merged.push(label.trim().to_string());
+        }
+    }
+    merged
+}
+
+/// How a pull request refers to the issue so merging it closes the issue
+pub fn closing_reference(link: &GitHubIssueLink, pr_repo: &GitHubRepoInfo) -> String {
+    let same_repo = link.owner.eq_ignore_ascii_case(&pr_repo.owner)
+        && link.repo.eq_ignore_ascii_case(&pr_repo.repo_name);
+    if same_repo {
+        format!("Fixes #{}", link.issue_number)
+    } else {
+        format!("Fixes {}/{}#{}", link.owner, link.repo, link.issue_number)
+    }
+}
+
+/// A pull request body ending with the closing reference, unless it already mentions it
+pub fn with_closing_reference(body: Option<&str>, reference: &str) -> String {
+    let body = body.unwrap_or_default().trim_end();
+    if body.contains(reference) {
+        body.to_string()
+    } else if body.is_empty() {
+        reference.to_string()
+    } else {
+        format!("{body}\n\n{reference}")
+    }
+}
+
+fn issue_labels(issue: &Issue) -> Vec<String> {
+    issue
+        .labels
+        .iter()
+        .map(|label| label.name.clone())
+        .collect()
+}
+
+async fn import_comments(
+    pool: &SqlitePool,
+    github: &GitHubService,
+    repo_info: &GitHubRepoInfo,
+    task_id: Uuid,
+    issue_number: i64,
+    result: &mut GitHubIssueSyncResult,
+) -> Result<(), GitHubIssueSyncError> {
+    let known: HashSet<i64> = GitHubIssueLink::find_comments(pool, task_id)
+        .await?
+        .into_iter()
+        .map(|comment| comment.github_comment_id)
+        .collect();
+    for comment in github.list_issue_comments(repo_info, issue_number).await? {
+        if known.contains(&comment.id) {
+            continue;
+        }
+        let task_comment =
+            TaskComment::create(pool, task_id, &comment.user.login, &comment.body).await?;
+        GitHubIssueLink::record_comment(pool, task_id, task_comment.id, comment.id).await?;
+        result.comments_imported += 1;
+    }
+    Ok(())
+}
+
+async fn post_comments(
+    pool: &SqlitePool,
+    github: &GitHubService,
+    repo_info: &GitHubRepoInfo,
+    task_id: Uuid,
+    issue_number: i64,
+    result: &mut GitHubIssueSyncResult,
+) -> Result<(), GitHubIssueSyncError> {
+    let mirrored: HashSet<Uuid> = GitHubIssueLink::find_comments(pool, task_id)
+        .await?
+        .into_iter()
+        .map(|comment| comment.task_comment_id)
+        .collect();
+    for comment in TaskComment::find_by_task_id(pool, task_id).await? {
+        if mirrored.contains(&comment.id) {
+            continue;
+        }
+        let body = format!("**{}** wrote:\n\n{}", comment.author, comment.body);
+        let posted = github
+            .create_issue_comment(repo_info, issue_number, &body)
+            .await?;
+        GitHubIssueLink::record_comment(pool, task_id, comment.id, posted.id).await?;
+        result.comments_posted += 1;
+    }
+    Ok(())
+}
+
+async fn import_issue(
+    pool: &SqlitePool,
+    github: &GitHubService,
+    repo_info: &GitHubRepoInfo,
+    project_id: Uuid,
+    issue: &Issue,
+    result: &mut GitHubIssueSyncResult,
+) -> Result<(), GitHubIssueSyncError> {
+    let description = issue.body.clone().filter(|body| !body.trim().is_empty());
+    let create = CreateTask {
+        status: Some(TaskStatus::Todo),
+        ..CreateTask::from_title_description(project_id, issue.title.clone(), description)
+    };
+    let task = Task::create(pool, &create, Uuid::new_v4()).await?;
+    let labels = issue_labels(issue);
+    for name in &labels {
+        let label = Label::find_or_create_by_name(pool, name).await?;
+        Label::add_to_task(pool, task.id, label.id).await?;
+    }
+    auto_assign::apply(pool, &task, TaskSource::GitHub).await?;
+    GitHubIssueLink::create(
+        pool,
+        &NewGitHubIssueLink {
+            task_id: task.id,
+            project_id,
+            owner: repo_info.owner.clone(),
+            repo: repo_info.repo_name.clone(),
+            issue_number: issue.number,
+            issue_url: issue.html_url.clone(),
+            state: issue.state,
+            status: task.status.clone(),
+            labels,
+        },
+    )
+    .await?;
+    result.tasks_created += 1;
+    import_comments(pool, github, repo_info, task.id, issue.number, result).await
+}
+
+/// Bring a linked task and its issue back in line. `issue` is `None` when the issue hasn't
+/// changed since the last sync, in which case its last synced state stands in for it.
+async fn reconcile(
+    pool: &SqlitePool,
+    github: &GitHubService,
+    repo_info: &GitHubRepoInfo,
+    link: &GitHubIssueLink,
+    issue: Option<&Issue>,
+    result: &mut GitHubIssueSyncResult,
+) -> Result<(), GitHubIssueSyncError> {
+    let Some(task) = Task::find_by_id(pool, link.task_id).await? else {
+        return Ok(());
+    };
+
+    let remote_state = issue.map_or(link.synced_state, |issue| issue.state);
+    let mut state = remote_state;
+    let mut status = task.status.clone();
+    if task.status != link.synced_status {
+        let (wanted, reason) = issue_state_for_status(&task.status);
+        if wanted != remote_state {
+            github
+                .set_issue_state(repo_info, link.issue_number, wanted, reason)
+                .await?;
+            state = wanted;
+            result.issues_updated += 1;
+        }
+    } else if remote_state != link.synced_state {
+        let reason = issue.and_then(|issue| issue.state_reason.as_deref());
+        let wanted = status_for_issue(remote_state, reason, &task.status);
+        if wanted != task.status {
+            Task::update_status(pool, task.id, wanted.clone()).await?;
+            status = wanted;
+            result.tasks_updated += 1;
+        }
+    }
+
+    let local_labels = Label::names_for_task(pool, task.id).await?;
+    let remote_labels = issue.map_or_else(|| link.synced_labels.0.clone(), issue_labels);
+    let labels = merge_labels(&link.synced_labels, &local_labels, &remote_labels);
+    if !same_labels(&labels, &local_labels) {
+        let mut label_ids = Vec::with_capacity(labels.len());
+        for name in &labels {
+            label_ids.push(Label::find_or_create_by_name(pool, name).await?.id);
+        }
+        Label::set_for_task(pool, task.id, &label_ids).await?;
+        result.tasks_updated += 1;
+    }
+    if !same_labels(&labels, &remote_labels) {
+        github
+            .set_issue_labels(repo_info, link.issue_number, &labels)
+            .await?;
+        result.issues_updated += 1;
+    }
+
+    if issue.is_some() {
+        import_comments(pool, github, repo_info, task.id, link.issue_number, result).await?;
+    }
+    post_comments(pool, github, repo_info, task.id, link.issue_number, result).await?;
+
+    GitHubIssueLink::mark_synced(pool, task.id, state, &status, &labels).await?;
+    Ok(())
+}
+
+/// Run one sync for a project. Failures on single issues don't stop the others; the first is
+/// returned once all have been tried, and the next sync starts from the same point.
+pub async fn sync_project(
+    pool: &SqlitePool,
+    git: &GitService,
+    sync: &GitHubIssueSync,
+) -> Result<GitHubIssueSyncResult, GitHubIssueSyncError> {
+    let started_at = Utc::now();
+    let outcome = run_sync(pool, git, sync).await;
+    let error = outcome.as_ref().err().map(|e| e.to_string());
+    GitHubIssueSync::record_result(
+        pool,
+        sync.project_id,
+        outcome.is_ok().then_some(started_at),
+        error.as_deref(),
+    )
+    .await?;
+    outcome
+}
+
+async fn run_sync(
+    pool: &SqlitePool,
+    git: &GitService,
+    sync: &GitHubIssueSync,
+) -> Result<GitHubIssueSyncResult, GitHubIssueSyncError> {
+    let repo = Repo::find_by_id(pool, sync.repo_id)
+        .await?
+        .ok_or(GitHubIssueSyncError::RepoNotFound)?;
+    let repo_info = git.get_github_repo_info(&repo.path)?;
+    let github = GitHubService::new()?;
+    let issues = github
+        .list_issues(&repo_info, sync.last_synced_at, sync.label.as_deref())
+        .await?;
+    let links: Vec<GitHubIssueLink> = GitHubIssueLink::find_by_project_id(pool, sync.project_id)
+        .await?
+        .into_iter()
+        .filter(|link| {
+            link.owner.eq_ignore_ascii_case(&repo_info.owner)
+                && link.repo.eq_ignore_ascii_case(&repo_info.repo_name)
+        })
+        .collect();
+
+    let mut result = GitHubIssueSyncResult::default();
+    let mut first_error = None;
+    for issue in &issues {
+        let linked = links.iter().any(|link| link.issue_number == issue.number);
+        if linked || issue.state != GitHubIssueState::Open {
+            continue;
+        }
+        if let Err(e) = import_issue(
+            pool,
+            &github,
+            &repo_info,
+            sync.project_id,
+            issue,
+            &mut result,
+        )
+        .await
+        {
+            error!("Failed to import issue #{}: {}", issue.number, e);
+            first_error.get_or_insert(e);
+        }
+    }
+    for link in &links {
+        let issue = issues
+            .iter()
+            .find(|issue| issue.number == link.issue_number);
+        if let Err(e) = reconcile(pool, &github, &repo_info, link, issue, &mut result).await {
+            error!("Failed to sync issue #{}: {}", link.issue_number, e);
+            first_error.get_or_insert(e);
+        }
+    }
+
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(result),
+    }
+}
+
+/// Service that periodically syncs every project with an enabled issue sync
+pub struct GitHubIssueSyncService {
+    db: DBService,
+    git: GitService,
+    poll_interval: Duration,
+}
+
+impl GitHubIssueSyncService {
+    pub async fn spawn(db: DBService, git: GitService) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            git,
+            poll_interval: Duration::from_secs(120),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting GitHub issue sync service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            let syncs = match GitHubIssueSync::find_enabled(&self.db.pool).await {
+                Ok(syncs) => syncs,
+                Err(e) => {
+                    error!("Error loading GitHub issue syncs: {}", e);
+                    continue;
+                }
+            };
+            for sync in syncs {
+                if let Err(e) = sync_project(&self.db.pool, &self.git, &sync).await {
+                    error!(
+                        "Error syncing GitHub issues for project {}: {}",
+                        sync.project_id, e
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_merge_labels() {
+        // Added locally, removed remotely, and a case-only difference that isn't a change
+        let merged = merge_labels(
+            &labels(&["bug", "ui"]),
+            &labels(&["Bug", "ui", "urgent"]),
+            &labels(&["bug", "docs"]),
+        );
+        assert_eq!(merged, labels(&["Bug", "urgent", "docs"]));
+        assert!(same_labels(&merged, &labels(&["docs", "BUG", "urgent"])));
+        assert!(merge_labels(&labels(&["a"]), &[], &labels(&["a"])).is_empty());
+    }
+
+    #[test]
+    fn test_status_mapping() {
+        let closed = GitHubIssueState::Closed;
+        let open = GitHubIssueState::Open;
+        assert_eq!(
+            status_for_issue(closed, Some("completed"), &TaskStatus::InProgress),
+            TaskStatus::Done
+        );
+        assert_eq!(
+            status_for_issue(closed, Some("not_planned"), &TaskStatus::Todo),
+            TaskStatus::Cancelled
+        );
+        assert_eq!(
+            status_for_issue(open, None, &TaskStatus::Done),
+            TaskStatus::Todo
+        );
+        assert_eq!(
+            status_for_issue(open, None, &TaskStatus::InReview),
+            TaskStatus::InReview
+        );
+        assert_eq!(
+            issue_state_for_status(&TaskStatus::Cancelled),
+            (closed, Some("not_planned"))
+        );
+        assert_eq!(
+            issue_state_for_status(&TaskStatus::InProgress),
+            (open, None)
+        );
+    }
+
+    #[test]
+    fn test_with_closing_reference() {
+        assert_eq!(with_closing_reference(None, "Fixes #4"), "Fixes #4");
+        assert_eq!(
+            with_closing_reference(Some("Adds X.\n"), "Fixes #4"),
+            "Adds X.\n\nFixes #4"
+        );
+        assert_eq!(
+            with_closing_reference(Some("Adds X. Fixes #4"), "Fixes #4"),
+            "Adds X. Fixes #4"
+        );
+    }
+}
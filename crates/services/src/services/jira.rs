@@ -0,0 +1,314 @@
+//! Minimal Jira Cloud REST client: searching issues by JQL and moving them through their
+//! workflow, authenticated with an account email and API token.
+
+use std::time::Duration;
+
+use backon::{ExponentialBuilder, Retryable};
+use db::models::jira::JiraStatusCategory;
+use reqwest::{Client, Method, StatusCode};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+use thiserror::Error;
+use tracing::warn;
+
+use super::config::JiraConfig;
+
+const SEARCH_PAGE_SIZE: u32 = 100;
+const SEARCH_FIELDS: &[&str] = &["summary", "description", "status", "updated"];
+
+#[derive(Debug, Error)]
+pub enum JiraError {
+    #[error("network error: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("Jira rejected the credentials; check the email and API token")]
+    Auth,
+    #[error("Jira returned {status}: {body}")]
+    Http { status: u16, body: String },
+    #[error("Jira isn't set up; add a site URL, email and API token in settings")]
+    NotConfigured,
+}
+
+impl JiraError {
+    fn should_retry(&self) -> bool {
+        match self {
+            Self::Transport(e) => e.is_timeout() || e.is_connect(),
+            Self::Http { status, .. } => {
+                *status == StatusCode::TOO_MANY_REQUESTS.as_u16() || (500..=599).contains(status)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JiraStatusCategoryRef {
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JiraStatus {
+    pub name: String,
+    #[serde(rename = "statusCategory")]
+    pub status_category: JiraStatusCategoryRef,
+}
+
+impl JiraStatus {
+    /// Statuses in a category Jira adds later are treated as not started
+    pub fn category(&self) -> JiraStatusCategory {
+        match self.status_category.key.as_str() {
+            "done" => JiraStatusCategory::Done,
+            "indeterminate" => JiraStatusCategory::Indeterminate,
+            _ => JiraStatusCategory::New,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JiraIssueFields {
+    pub summary: String,
+    /// Atlassian Document Format; see [`adf_to_text`]
+    #[serde(default)]
+    pub description: Option<Value>,
+    pub status: JiraStatus,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JiraIssue {
+    pub key: String,
+    pub fields: JiraIssueFields,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JiraTransition {
+    pub id: String,
+    pub name: String,
+    pub to: JiraStatus,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchPage {
+    #[serde(default)]
+    issues: Vec<JiraIssue>,
+    #[serde(default)]
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchRequest<'a> {
+    jql: &'a str,
+    fields: &'a [&'a str],
+    max_results: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_page_token: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TransitionsResponse {
+    transitions: Vec<JiraTransition>,
+}
+
+/// Plain text of an Atlassian Document Format value: the text of its nodes, with blocks on
+/// their own lines. Plain strings, as older sites return, are passed through.
+pub fn adf_to_text(value: &Value) -> String {
+    const INLINE: &[&str] = &["text", "hardBreak", "mention", "emoji", "inlineCard"];
+
+    // `in_list_item` keeps a list item's first block on the line of its bullet
+    fn collect(node: &Value, out: &mut String, in_list_item: bool) {
+        if let Some(text) = node.get("text").and_then(Value::as_str) {
+            out.push_str(text);
+        }
+        match node.get("type").and_then(Value::as_str) {
+            Some("hardBreak") => out.push('\n'),
+            Some("mention" | "emoji") => {
+                if let Some(text) = node.pointer("/attrs/text").and_then(Value::as_str) {
+                    out.push_str(text);
+                }
+            }
+            _ => {}
+        }
+        let Some(content) = node.get("content").and_then(Value::as_array) else {
+            return;
+        };
+        for (index, child) in content.iter().enumerate() {
+            let kind = child
+                .get("type")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let block = !INLINE.contains(&kind);
+            let continues_bullet = in_list_item && index == 0;
+            if block && !continues_bullet && !out.is_empty() && !out.ends_with('\n') {
+                out.push('\n');
+            }
+            if kind == "listItem" {
+                out.push_str("- ");
+            }
+            collect(child, out, kind == "listItem");
+        }
+    }
+
+    if let Some(text) = value.as_str() {
+        return text.trim().to_string();
+    }
+    let mut out = String::new();
+    collect(value, &mut out, false);
+    out.trim().to_string()
+}
+
+#[derive(Clone)]
+pub struct JiraClient {
+    http: Client,
+    base_url: String,
+    email: String,
+    api_token: String,
+}
+
+impl JiraClient {
+    pub fn new(config: Option<&JiraConfig>) -> Result<Self, JiraError> {
+        let config = config
+            .filter(|config| {
+                !config.base_url.trim().is_empty()
+                    && !config.email.trim().is_empty()
+                    && !config.api_token.trim().is_empty()
+            })
+            .ok_or(JiraError::NotConfigured)?;
+        let http = Client::builder().timeout(Duration::from_secs(30)).build()?;
+        Ok(Self {
+            http,
+            base_url: config.base_url.trim().trim_end_matches('/').to_string(),
+            email: config.email.trim().to_string(),
+            api_token: config.api_token.trim().to_string(),
+        })
+    }
+
+    /// Link to the issue in the Jira web UI
+    pub fn issue_url(&self, key: &str) -> String {
+        format!("{}/browse/{}", self.base_url, key)
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<Option<T>, JiraError> {
+        let url = format!("{}{}", self.base_url, path);
+        (|| async {
+            let mut request = self
+                .http
+                .request(method.clone(), &url)
+                .basic_auth(&self.email, Some(&self.api_token));
+            if let Some(body) = body {
+                request = request.json(body);
+            }
+            let response = request.send().await?;
+            match response.status() {
+                StatusCode::NO_CONTENT => Ok(None),
+                status if status.is_success() => Ok(Some(response.json().await?)),
+                StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(JiraError::Auth),
+                status => Err(JiraError::Http {
+                    status: status.as_u16(),
+                    body: response.text().await.unwrap_or_default(),
+                }),
+            }
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &JiraError| e.should_retry())
+        .notify(|e, dur| {
+            warn!(
+                "Jira call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                e
+            )
+        })
+        .await
+    }
+
+    /// Every issue matching `jql`, following pagination
+    pub async fn search(&self, jql: &str) -> Result<Vec<JiraIssue>, JiraError> {
+        let mut issues = Vec::new();
+        let mut next_page_token: Option<String> = None;
+        loop {
+            let request = serde_json::to_value(SearchRequest {
+                jql,
+                fields: SEARCH_FIELDS,
+                max_results: SEARCH_PAGE_SIZE,
+                next_page_token: next_page_token.as_deref(),
+            })
+            .expect("search request serializes");
+            let page: Option<SearchPage> = self
+                .send(Method::POST, "/rest/api/3/search/jql", Some(&request))
+                .await?;
+            let Some(page) = page else { break };
+            issues.extend(page.issues);
+            match page.next_page_token {
+                Some(token) => next_page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(issues)
+    }
+
+    /// Transitions the issue's workflow allows from its current status
+    pub async fn transitions(&self, key: &str) -> Result<Vec<JiraTransition>, JiraError> {
+        let response: Option<TransitionsResponse> = self
+            .send(
+                Method::GET,
+                &format!("/rest/api/3/issue/{key}/transitions"),
+                None,
+            )
+            .await?;
+        Ok(response.map(|r| r.transitions).unwrap_or_default())
+    }
+
+    pub async fn transition(&self, key: &str, transition_id: &str) -> Result<(), JiraError> {
+        self.send::<Value>(
+            Method::POST,
+            &format!("/rest/api/3/issue/{key}/transitions"),
+            Some(&json!({ "transition": { "id": transition_id } })),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adf_to_text() {
+        let doc = json!({
+            "type": "doc",
+            "version": 1,
+            "content": [
+                {"type": "paragraph", "content": [
+                    {"type": "text", "text": "Login fails for "},
+                    {"type": "mention", "attrs": {"text": "@sam"}},
+                    {"type": "hardBreak"},
+                    {"type": "text", "text": "since Monday."}
+                ]},
+                {"type": "bulletList", "content": [
+                    {"type": "listItem", "content": [
+                        {"type": "paragraph", "content": [{"type": "text", "text": "Chrome"}]}
+                    ]},
+                    {"type": "listItem", "content": [
+                        {"type": "paragraph", "content": [{"type": "text", "text": "Safari"}]}
+                    ]}
+                ]}
+            ]
+        });
+        assert_eq!(
+            adf_to_text(&doc),
+            "Login fails for @sam\nsince Monday.\n- Chrome\n- Safari"
+        );
+        assert_eq!(adf_to_text(&json!(" wiki text ")), "wiki text");
+    }
+}
@@ -0,0 +1,339 @@
+//! Import of the Jira issues a JQL filter matches as tasks of a project. Jira stays the system of
+//! record: status changes there are mirrored onto tasks, and the only change pushed back is
+//! completion, when a task is marked done or its pull request merges. Jira is polled, since a
+//! local install can't receive webhooks.
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use db::{
+    DBService,
+    models::{
+        assignment_rule::TaskSource,
+        jira::{JiraIssueLink, JiraStatusCategory, JiraSync, NewJiraIssueLink},
+        task::{CreateTask, Task, TaskStatus},
+    },
+};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::{sync::RwLock, time::interval};
+use tracing::{error, info};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{
+    auto_assign,
+    config::{Config, JiraConfig},
+    jira::{JiraClient, JiraError, JiraIssue, JiraTransition, adf_to_text},
+};
+
+#[derive(Debug, Error)]
+pub enum JiraSyncError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Jira(#[from] JiraError),
+    #[error("No transition completes {0}; set the done transition to one its workflow allows")]
+    NoDoneTransition(String),
+}
+
+/// What one sync changed
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct JiraSyncResult {
+    pub tasks_created: usize,
+    pub tasks_updated: usize,
+    pub issues_transitioned: usize,
+}
+
+/// Task status for an issue whose status category changed in Jira. Reopening only moves finished
+/// tasks back to `todo`, so work in progress keeps its status.
+pub fn status_for_category(category: JiraStatusCategory, current: &TaskStatus) -> TaskStatus {
+    match category {
+        JiraStatusCategory::Done => TaskStatus::Done,
+        JiraStatusCategory::New | JiraStatusCategory::Indeterminate => match current {
+            TaskStatus::Done | TaskStatus::Cancelled => TaskStatus::Todo,
+            status => status.clone(),
+        },
+    }
+}
+
+/// The transition that completes an issue: the one named `preferred`, matched against the
+/// transition or its target status, else the first leading to a done status
+pub fn pick_done_transition<'a>(
+    transitions: &'a [JiraTransition],
+    preferred: Option<&str>,
+) -> Option<&'a JiraTransition> {
+    match preferred {
+        Some(name) => transitions.iter().find(|transition| {
+            transition.name.eq_ignore_ascii_case(name)
+                || transition.to.name.eq_ignore_ascii_case(name)
+        }),
+        None => transitions
+            .iter()
+            .find(|transition| transition.to.category() == JiraStatusCategory::Done),
+    }
+}
+
+/// `jql` narrowed to issues updated since `since`, with a minute of slack. The relative form
+/// keeps the query independent of the Jira user's timezone.
+pub fn scoped_jql(jql: &str, since: Option<DateTime<Utc>>, now: DateTime<Utc>) -> String {
+    let Some(since) = since else {
+        return jql.to_string();
+    };
+    let minutes = (now - since).num_minutes().max(0) + 1;
+    let (filter, order) = match jql.to_ascii_lowercase().rfind("order by") {
+        Some(index) => (jql[..index].trim(), Some(jql[index..].trim())),
+        None => (jql.trim(), None),
+    };
+    let mut scoped = if filter.is_empty() {
+        format!("updated >= -{minutes}m")
+    } else {
+        format!("({filter}) AND updated >= -{minutes}m")
+    };
+    if let Some(order) = order {
+        scoped.push(' ');
+        scoped.push_str(order);
+    }
+    scoped
+}
+
+async fn import_issue(
+    pool: &SqlitePool,
+    jira: &JiraClient,
+    project_id: Uuid,
+    issue: &JiraIssue,
+    result: &mut JiraSyncResult,
+) -> Result<(), JiraSyncError> {
+    let description = issue
+        .fields
+        .description
+        .as_ref()
+        .map(adf_to_text)
+        .filter(|text| !text.is_empty());
+    let create = CreateTask {
+        status: Some(TaskStatus::Todo),
+        ..CreateTask::from_title_description(
+            project_id,
+            format!("{}: {}", issue.key, issue.fields.summary),
+            description,
+        )
+    };
+    let task = Task::create(pool, &create, Uuid::new_v4()).await?;
+    auto_assign::apply(pool, &task, TaskSource::Jira).await?;
+    JiraIssueLink::create(
+        pool,
+        &NewJiraIssueLink {
+            task_id: task.id,
+            project_id,
+            issue_key: issue.key.clone(),
+            issue_url: jira.issue_url(&issue.key),
+            jira_status: issue.fields.status.name.clone(),
+            jira_category: issue.fields.status.category(),
+            status: task.status.clone(),
+        },
+    )
+    .await?;
+    result.tasks_created += 1;
+    Ok(())
+}
+
+/// Bring a linked task and its issue back in line. `issue` is `None` when the issue hasn't
+/// changed since the last sync, in which case its last synced status stands in for it.
+async fn reconcile(
+    pool: &SqlitePool,
+    jira: &JiraClient,
+    sync: &JiraSync,
+    link: &JiraIssueLink,
+    issue: Option<&JiraIssue>,
+    result: &mut JiraSyncResult,
+) -> Result<(), JiraSyncError> {
+    let Some(task) = Task::find_by_id(pool, link.task_id).await? else {
+        return Ok(());
+    };
+
+    let mut jira_status = issue.map_or_else(
+        || link.jira_status.clone(),
+        |issue| issue.fields.status.name.clone(),
+    );
+    let mut jira_category =
+        issue.map_or(link.jira_category, |issue| issue.fields.status.category());
+    let mut status = task.status.clone();
+    if task.status != link.synced_status {
+        if task.status == TaskStatus::Done && jira_category != JiraStatusCategory::Done {
+            let transitions = jira.transitions(&link.issue_key).await?;
+            let transition = pick_done_transition(&transitions, sync.done_transition.as_deref())
+                .ok_or_else(|| JiraSyncError::NoDoneTransition(link.issue_key.clone()))?;
+            jira.transition(&link.issue_key, &transition.id).await?;
+            jira_status = transition.to.name.clone();
+            jira_category = transition.to.category();
+            result.issues_transitioned += 1;
+        }
+    } else if jira_category != link.jira_category {
+        let wanted = status_for_category(jira_category, &task.status);
+        if wanted != task.status {
+            Task::update_status(pool, task.id, wanted.clone()).await?;
+            status = wanted;
+            result.tasks_updated += 1;
+        }
+    }
+
+    JiraIssueLink::mark_synced(pool, task.id, &jira_status, jira_category, &status).await?;
+    Ok(())
+}
+
+/// Run one sync for a project. Failures on single issues don't stop the others; the first is
+/// returned once all have been tried, and the next sync starts from the same point.
+pub async fn sync_project(
+    pool: &SqlitePool,
+    config: Option<&JiraConfig>,
+    sync: &JiraSync,
+) -> Result<JiraSyncResult, JiraSyncError> {
+    let started_at = Utc::now();
+    let outcome = run_sync(pool, config, sync).await;
+    let error = outcome.as_ref().err().map(|e| e.to_string());
+    JiraSync::record_result(
+        pool,
+        sync.project_id,
+        outcome.is_ok().then_some(started_at),
+        error.as_deref(),
+    )
+    .await?;
+    outcome
+}
+
+async fn run_sync(
+    pool: &SqlitePool,
+    config: Option<&JiraConfig>,
+    sync: &JiraSync,
+) -> Result<JiraSyncResult, JiraSyncError> {
+    let jira = JiraClient::new(config)?;
+    let jql = scoped_jql(&sync.jql, sync.last_synced_at, Utc::now());
+    let issues = jira.search(&jql).await?;
+    let links = JiraIssueLink::find_by_project_id(pool, sync.project_id).await?;
+
+    let mut result = JiraSyncResult::default();
+    let mut first_error = None;
+    for issue in &issues {
+        let linked = links.iter().any(|link| link.issue_key == issue.key);
+        if linked || issue.fields.status.category() == JiraStatusCategory::Done {
+            continue;
+        }
+        if let Err(e) = import_issue(pool, &jira, sync.project_id, issue, &mut result).await {
+            error!("Failed to import Jira issue {}: {}", issue.key, e);
+            first_error.get_or_insert(e);
+        }
+    }
+    for link in &links {
+        let issue = issues.iter().find(|issue| issue.key == link.issue_key);
+        if let Err(e) = reconcile(pool, &jira, sync, link, issue, &mut result).await {
+            error!("Failed to sync Jira issue {}: {}", link.issue_key, e);
+            first_error.get_or_insert(e);
+        }
+    }
+
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(result),
+    }
+}
+
+/// Service that periodically syncs every project with an enabled Jira sync
+pub struct JiraSyncService {
+    db: DBService,
+    config: Arc<RwLock<Config>>,
+    poll_interval: Duration,
+}
+
+impl JiraSyncService {
+    pub async fn spawn(db: DBService, config: Arc<RwLock<Config>>) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            config,
+            poll_interval: Duration::from_secs(120),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting Jira sync service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            let syncs = match JiraSync::find_enabled(&self.db.pool).await {
+                Ok(syncs) => syncs,
+                Err(e) => {
+                    error!("Error loading Jira syncs: {}", e);
+                    continue;
+                }
+            };
+            if syncs.is_empty() {
+                continue;
+            }
+            let jira = self.config.read().await.jira.clone();
+            for sync in syncs {
+                if let Err(e) = sync_project(&self.db.pool, jira.as_ref(), &sync).await {
+                    error!(
+                        "Error syncing Jira issues for project {}: {}",
+                        sync.project_id, e
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_scoped_jql() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 15, 12, 0, 0).unwrap();
+        let since = Some(Utc.with_ymd_and_hms(2026, 1, 15, 11, 30, 20).unwrap());
+        assert_eq!(scoped_jql("project = APP", None, now), "project = APP");
+        assert_eq!(
+            scoped_jql("project = APP ORDER BY rank", since, now),
+            "(project = APP) AND updated >= -30m ORDER BY rank"
+        );
+        assert_eq!(
+            scoped_jql("order by created DESC", since, now),
+            "updated >= -30m order by created DESC"
+        );
+    }
+
+    #[test]
+    fn test_pick_done_transition() {
+        let transitions: Vec<JiraTransition> = serde_json::from_value(json!([
+            {"id": "11", "name": "Start", "to": {"name": "In Progress", "statusCategory": {"key": "indeterminate"}}},
+            {"id": "31", "name": "Resolve", "to": {"name": "Resolved", "statusCategory": {"key": "done"}}},
+            {"id": "41", "name": "Close", "to": {"name": "Closed", "statusCategory": {"key": "done"}}}
+        ]))
+        .unwrap();
+        assert_eq!(pick_done_transition(&transitions, None).unwrap().id, "31");
+        assert_eq!(
+            pick_done_transition(&transitions, Some("closed"))
+                .unwrap()
+                .id,
+            "41"
+        );
+        assert!(pick_done_transition(&transitions, Some("Won't do")).is_none());
+        assert_eq!(
+            status_for_category(JiraStatusCategory::New, &TaskStatus::Done),
+            TaskStatus::Todo
+        );
+        assert_eq!(
+            status_for_category(JiraStatusCategory::Indeterminate, &TaskStatus::InReview),
+            TaskStatus::InReview
+        );
+    }
+}
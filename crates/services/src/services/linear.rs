@@ -0,0 +1,294 @@
+//! Minimal Linear GraphQL client: listing teams and issues and commenting on issues, plus
+//! verification of Linear's signed webhooks.
+
+use std::time::Duration;
+
+use backon::{ExponentialBuilder, Retryable};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+use sha2::Sha256;
+use thiserror::Error;
+use tracing::warn;
+use ts_rs::TS;
+
+use super::config::LinearConfig;
+
+const API_URL: &str = "https://api.linear.app/graphql";
+const ISSUE_PAGE_SIZE: u32 = 100;
+
+/// Header carrying the hex HMAC-SHA256 of a webhook's body
+pub const SIGNATURE_HEADER: &str = "linear-signature";
+
+const ISSUE_FIELDS: &str = "id identifier title description url state { name type }";
+
+#[derive(Debug, Error)]
+pub enum LinearError {
+    #[error("network error: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("Linear rejected the API key")]
+    Auth,
+    #[error("Linear returned {status}: {body}")]
+    Http { status: u16, body: String },
+    #[error("Linear API error: {0}")]
+    Api(String),
+    #[error("Linear isn't set up; add an API key in settings")]
+    NotConfigured,
+}
+
+impl LinearError {
+    fn should_retry(&self) -> bool {
+        match self {
+            Self::Transport(e) => e.is_timeout() || e.is_connect(),
+            Self::Http { status, .. } => {
+                *status == StatusCode::TOO_MANY_REQUESTS.as_u16() || (500..=599).contains(status)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct LinearTeam {
+    pub id: String,
+    pub key: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LinearState {
+    pub name: String,
+    /// `triage`, `backlog`, `unstarted`, `started`, `completed` or `canceled`
+    #[serde(rename = "type")]
+    pub state_type: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinearIssue {
+    pub id: String,
+    pub identifier: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub url: String,
+    pub state: LinearState,
+    /// Only present in webhook payloads
+    #[serde(default)]
+    pub team_id: Option<String>,
+}
+
+/// A webhook delivery; `data` is the changed entity, an issue when `kind` is `Issue`
+#[derive(Debug, Clone, Deserialize)]
+pub struct LinearWebhook {
+    /// `create`, `update` or `remove`
+    pub action: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub data: Value,
+    /// Milliseconds since the epoch when Linear sent the webhook
+    #[serde(default, rename = "webhookTimestamp")]
+    pub webhook_timestamp: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Connection<T> {
+    nodes: Vec<T>,
+    page_info: Option<PageInfo>,
+}
+
+/// Whether `signature` is the hex HMAC-SHA256 of `body` under the webhook's signing secret
+pub fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Ok(expected) = hex::decode(signature.trim()) else {
+        return false;
+    };
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+#[derive(Clone)]
+pub struct LinearClient {
+    http: Client,
+    api_key: String,
+}
+
+impl LinearClient {
+    pub fn new(config: Option<&LinearConfig>) -> Result<Self, LinearError> {
+        let api_key = config
+            .map(|config| config.api_key.trim())
+            .filter(|key| !key.is_empty())
+            .ok_or(LinearError::NotConfigured)?;
+        let http = Client::builder().timeout(Duration::from_secs(30)).build()?;
+        Ok(Self {
+            http,
+            api_key: api_key.to_string(),
+        })
+    }
+
+    async fn query<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+    ) -> Result<T, LinearError> {
+        let body = json!({ "query": query, "variables": variables });
+        let response: GraphQlResponse<T> = (|| async {
+            let response = self
+                .http
+                .post(API_URL)
+                // Personal API keys go in the header as they are, without a scheme
+                .header(reqwest::header::AUTHORIZATION, &self.api_key)
+                .json(&body)
+                .send()
+                .await?;
+            match response.status() {
+                status if status.is_success() => Ok(response.json().await?),
+                StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(LinearError::Auth),
+                status => Err(LinearError::Http {
+                    status: status.as_u16(),
+                    body: response.text().await.unwrap_or_default(),
+                }),
+            }
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &LinearError| e.should_retry())
+        .notify(|e, dur| {
+            warn!(
+                "Linear call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                e
+            )
+        })
+        .await?;
+
+        if let Some(error) = response.errors.into_iter().next() {
+            return Err(LinearError::Api(error.message));
+        }
+        response
+            .data
+            .ok_or_else(|| LinearError::Api("empty response".to_string()))
+    }
+
+    pub async fn teams(&self) -> Result<Vec<LinearTeam>, LinearError> {
+        #[derive(Deserialize)]
+        struct Data {
+            teams: Connection<LinearTeam>,
+        }
+        let data: Data = self
+            .query(
+                "query { teams(first: 250) { nodes { id key name } } }",
+                json!({}),
+            )
+            .await?;
+        Ok(data.teams.nodes)
+    }
+
+    /// The team's issues, only those updated since `since` when given, following pagination
+    pub async fn issues(
+        &self,
+        team_id: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<LinearIssue>, LinearError> {
+        #[derive(Deserialize)]
+        struct Data {
+            issues: Connection<LinearIssue>,
+        }
+        let query = format!(
+            "query($filter: IssueFilter, $first: Int, $after: String) {{
+                issues(filter: $filter, first: $first, after: $after) {{
+                    nodes {{ {ISSUE_FIELDS} }}
+                    pageInfo {{ hasNextPage endCursor }}
+                }}
+            }}"
+        );
+        let mut filter = json!({ "team": { "id": { "eq": team_id } } });
+        if let Some(since) = since {
+            filter["updatedAt"] = json!({ "gt": since.to_rfc3339() });
+        }
+
+        let mut issues = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let variables = json!({ "filter": filter, "first": ISSUE_PAGE_SIZE, "after": after });
+            let data: Data = self.query(&query, variables).await?;
+            issues.extend(data.issues.nodes);
+            match data.issues.page_info {
+                Some(PageInfo {
+                    has_next_page: true,
+                    end_cursor: Some(cursor),
+                }) => after = Some(cursor),
+                _ => break,
+            }
+        }
+        Ok(issues)
+    }
+
+    pub async fn create_comment(&self, issue_id: &str, body: &str) -> Result<(), LinearError> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Data {
+            comment_create: Success,
+        }
+        #[derive(Deserialize)]
+        struct Success {
+            success: bool,
+        }
+        let data: Data = self
+            .query(
+                "mutation($input: CommentCreateInput!) { commentCreate(input: $input) { success } }",
+                json!({ "input": { "issueId": issue_id, "body": body } }),
+            )
+            .await?;
+        if !data.comment_create.success {
+            return Err(LinearError::Api("comment was not created".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_signature() {
+        let body = br#"{"action":"update","type":"Issue"}"#;
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(body);
+        let signature = hex::encode(mac.finalize().into_bytes());
+
+        assert!(verify_signature("secret", body, &signature));
+        assert!(!verify_signature("other", body, &signature));
+        assert!(!verify_signature("secret", b"{}", &signature));
+        assert!(!verify_signature("secret", body, "not hex"));
+    }
+}
@@ -0,0 +1,400 @@
+//! Sync between a project's tasks and the issues of a Linear team. The first sync imports the
+//! team's open issues; after that Linear's webhooks bring new issues and state changes in as they
+//! happen. Attempt results and pull requests of linked tasks are posted back as issue comments.
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::Utc;
+use db::{
+    DBService,
+    models::{
+        assignment_rule::TaskSource,
+        coding_agent_turn::CodingAgentTurn,
+        linear::{LinearIssueLink, LinearSync, NewLinearIssueLink},
+        task::{CreateTask, Task, TaskStatus},
+        task_activity::{TaskActivity, TaskActivityKind},
+    },
+};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::{sync::RwLock, time::interval};
+use tracing::{error, info};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{
+    auto_assign,
+    config::{Config, LinearConfig},
+    linear::{LinearClient, LinearError, LinearIssue, LinearWebhook},
+};
+
+/// Longest attempt summary quoted in a comment
+const MAX_SUMMARY_CHARS: usize = 2000;
+
+#[derive(Debug, Error)]
+pub enum LinearSyncError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Linear(#[from] LinearError),
+}
+
+/// What one sync changed
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct LinearSyncResult {
+    pub tasks_created: usize,
+    pub tasks_updated: usize,
+    pub comments_posted: usize,
+}
+
+fn is_closed(state_type: &str) -> bool {
+    matches!(state_type, "completed" | "canceled")
+}
+
+/// Task status for an issue that moved to a state of `state_type`. Reopening only moves finished
+/// tasks back to `todo`, so work in progress keeps its status.
+pub fn status_for_state(state_type: &str, current: &TaskStatus) -> TaskStatus {
+    match state_type {
+        "completed" => TaskStatus::Done,
+        "canceled" => TaskStatus::Cancelled,
+        _ => match current {
+            TaskStatus::Done | TaskStatus::Cancelled => TaskStatus::Todo,
+            status => status.clone(),
+        },
+    }
+}
+
+/// The comment posted for a task activity entry, if it's one worth telling the issue about.
+/// `summary` is the agent's final message for `attempt_stopped`.
+pub fn comment_for_activity(activity: &TaskActivity, summary: Option<&str>) -> Option<String> {
+    match activity.kind {
+        TaskActivityKind::AttemptStopped => {
+            let outcome = match activity.new_value.as_deref() {
+                Some("completed") => "completed",
+                Some("failed") => "failed",
+                _ => return None,
+            };
+            let mut comment = format!("Coding agent attempt {outcome}.");
+            if let Some(summary) = summary.map(str::trim).filter(|s| !s.is_empty()) {
+                let mut quoted: String = summary.chars().take(MAX_SUMMARY_CHARS).collect();
+                if quoted.len() < summary.len() {
+                    quoted.push('…');
+                }
+                comment.push_str("\n\n");
+                for line in quoted.lines() {
+                    comment.push_str("> ");
+                    comment.push_str(line);
+                    comment.push('\n');
+                }
+            }
+            Some(comment.trim_end().to_string())
+        }
+        TaskActivityKind::PrOpened => activity
+            .new_value
+            .as_deref()
+            .map(|url| format!("Pull request opened: {url}")),
+        TaskActivityKind::PrStatusChanged if activity.new_value.as_deref() == Some("merged") => {
+            Some("Pull request merged.".to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Import the issue as a task, or bring the linked task's status in line with it
+async fn apply_issue(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    issue: &LinearIssue,
+    result: &mut LinearSyncResult,
+) -> Result<(), LinearSyncError> {
+    let state = issue.state.state_type.as_str();
+    let Some(link) = LinearIssueLink::find_by_issue(pool, project_id, &issue.id).await? else {
+        if is_closed(state) {
+            return Ok(());
+        }
+        let description = issue
+            .description
+            .clone()
+            .filter(|description| !description.trim().is_empty());
+        let create = CreateTask {
+            status: Some(TaskStatus::Todo),
+            ..CreateTask::from_title_description(
+                project_id,
+                format!("{}: {}", issue.identifier, issue.title),
+                description,
+            )
+        };
+        let task = Task::create(pool, &create, Uuid::new_v4()).await?;
+        auto_assign::apply(pool, &task, TaskSource::Linear).await?;
+        LinearIssueLink::create(
+            pool,
+            &NewLinearIssueLink {
+                task_id: task.id,
+                project_id,
+                issue_id: issue.id.clone(),
+                identifier: issue.identifier.clone(),
+                issue_url: issue.url.clone(),
+                state: state.to_string(),
+            },
+        )
+        .await?;
+        result.tasks_created += 1;
+        return Ok(());
+    };
+
+    if link.synced_state == state {
+        return Ok(());
+    }
+    if let Some(task) = Task::find_by_id(pool, link.task_id).await? {
+        let wanted = status_for_state(state, &task.status);
+        if wanted != task.status {
+            Task::update_status(pool, task.id, wanted).await?;
+            result.tasks_updated += 1;
+        }
+    }
+    LinearIssueLink::mark_synced(pool, link.task_id, state).await?;
+    Ok(())
+}
+
+/// Post the attempt results and pull requests of the project's linked tasks that haven't been
+/// posted yet
+async fn post_updates(
+    pool: &SqlitePool,
+    linear: &LinearClient,
+    project_id: Uuid,
+    result: &mut LinearSyncResult,
+) -> Result<(), LinearSyncError> {
+    for link in LinearIssueLink::find_by_project_id(pool, project_id).await? {
+        for activity in TaskActivity::find_after(pool, link.task_id, link.posted_through).await? {
+            let summary = match activity.execution_process_id {
+                Some(process_id) if activity.kind == TaskActivityKind::AttemptStopped => {
+                    CodingAgentTurn::find_by_execution_process_id(pool, process_id)
+                        .await?
+                        .and_then(|turn| turn.summary)
+                }
+                _ => None,
+            };
+            if let Some(comment) = comment_for_activity(&activity, summary.as_deref()) {
+                linear.create_comment(&link.issue_id, &comment).await?;
+                result.comments_posted += 1;
+            }
+            LinearIssueLink::mark_posted(pool, link.task_id, activity.created_at).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Import issues updated since the last sync, all open ones the first time, and post pending
+/// updates back. Failures on single issues don't stop the others; the first is returned once all
+/// have been tried.
+pub async fn sync_project(
+    pool: &SqlitePool,
+    config: Option<&LinearConfig>,
+    sync: &LinearSync,
+) -> Result<LinearSyncResult, LinearSyncError> {
+    let started_at = Utc::now();
+    let outcome = run_sync(pool, config, sync).await;
+    let error = outcome.as_ref().err().map(|e| e.to_string());
+    LinearSync::record_result(
+        pool,
+        sync.project_id,
+        outcome.is_ok().then_some(started_at),
+        error.as_deref(),
+    )
+    .await?;
+    outcome
+}
+
+async fn run_sync(
+    pool: &SqlitePool,
+    config: Option<&LinearConfig>,
+    sync: &LinearSync,
+) -> Result<LinearSyncResult, LinearSyncError> {
+    let linear = LinearClient::new(config)?;
+    let issues = linear.issues(&sync.team_id, sync.last_synced_at).await?;
+
+    let mut result = LinearSyncResult::default();
+    let mut first_error = None;
+    for issue in &issues {
+        if let Err(e) = apply_issue(pool, sync.project_id, issue, &mut result).await {
+            error!("Failed to sync Linear issue {}: {}", issue.identifier, e);
+            first_error.get_or_insert(e);
+        }
+    }
+    if let Err(e) = post_updates(pool, &linear, sync.project_id, &mut result).await {
+        first_error.get_or_insert(e);
+    }
+
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(result),
+    }
+}
+
+/// Apply an issue webhook to every project synced with the issue's team. Other entities are
+/// ignored, as are removed issues, whose tasks are kept.
+pub async fn handle_webhook(
+    pool: &SqlitePool,
+    webhook: LinearWebhook,
+) -> Result<LinearSyncResult, LinearSyncError> {
+    let mut result = LinearSyncResult::default();
+    if webhook.kind != "Issue" || !matches!(webhook.action.as_str(), "create" | "update") {
+        return Ok(result);
+    }
+    let issue: LinearIssue = match serde_json::from_value(webhook.data) {
+        Ok(issue) => issue,
+        Err(e) => {
+            error!("Unreadable Linear issue webhook: {}", e);
+            return Ok(result);
+        }
+    };
+    let Some(team_id) = issue.team_id.as_deref() else {
+        return Ok(result);
+    };
+    for sync in LinearSync::find_enabled_by_team_id(pool, team_id).await? {
+        // Until the first import has run, it will pick the issue up
+        if sync.last_synced_at.is_some() {
+            apply_issue(pool, sync.project_id, &issue, &mut result).await?;
+        }
+    }
+    Ok(result)
+}
+
+/// Service that runs the first import of new syncs and posts task updates back to Linear
+pub struct LinearSyncService {
+    db: DBService,
+    config: Arc<RwLock<Config>>,
+    poll_interval: Duration,
+}
+
+impl LinearSyncService {
+    pub async fn spawn(db: DBService, config: Arc<RwLock<Config>>) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            config,
+            poll_interval: Duration::from_secs(60),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting Linear sync service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            let syncs = match LinearSync::find_enabled(&self.db.pool).await {
+                Ok(syncs) => syncs,
+                Err(e) => {
+                    error!("Error loading Linear syncs: {}", e);
+                    continue;
+                }
+            };
+            if syncs.is_empty() {
+                continue;
+            }
+            let config = self.config.read().await.linear.clone();
+            for sync in syncs {
+                if let Err(e) = self.tick(config.as_ref(), &sync).await {
+                    error!(
+                        "Error syncing Linear issues for project {}: {}",
+                        sync.project_id, e
+                    );
+                }
+            }
+        }
+    }
+
+    async fn tick(
+        &self,
+        config: Option<&LinearConfig>,
+        sync: &LinearSync,
+    ) -> Result<(), LinearSyncError> {
+        let pool = &self.db.pool;
+        if sync.last_synced_at.is_none() {
+            sync_project(pool, config, sync).await?;
+            return Ok(());
+        }
+        let linear = LinearClient::new(config)?;
+        post_updates(
+            pool,
+            &linear,
+            sync.project_id,
+            &mut LinearSyncResult::default(),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activity(kind: TaskActivityKind, new_value: &str) -> TaskActivity {
+        TaskActivity {
+            id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            kind,
+            field: None,
+            old_value: None,
+            new_value: Some(new_value.to_string()),
+            workspace_id: None,
+            execution_process_id: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_comment_for_activity() {
+        assert_eq!(
+            comment_for_activity(
+                &activity(TaskActivityKind::AttemptStopped, "completed"),
+                Some("Fixed the login bug.\nAdded a test.")
+            )
+            .unwrap(),
+            "Coding agent attempt completed.\n\n> Fixed the login bug.\n> Added a test."
+        );
+        assert!(
+            comment_for_activity(&activity(TaskActivityKind::AttemptStopped, "killed"), None)
+                .is_none()
+        );
+        assert_eq!(
+            comment_for_activity(
+                &activity(TaskActivityKind::PrOpened, "https://github.com/o/r/pull/1"),
+                None
+            )
+            .unwrap(),
+            "Pull request opened: https://github.com/o/r/pull/1"
+        );
+        assert!(
+            comment_for_activity(&activity(TaskActivityKind::PrStatusChanged, "closed"), None)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_status_for_state() {
+        assert_eq!(
+            status_for_state("completed", &TaskStatus::InReview),
+            TaskStatus::Done
+        );
+        assert_eq!(
+            status_for_state("canceled", &TaskStatus::Todo),
+            TaskStatus::Cancelled
+        );
+        assert_eq!(
+            status_for_state("started", &TaskStatus::Done),
+            TaskStatus::Todo
+        );
+        assert_eq!(
+            status_for_state("backlog", &TaskStatus::InProgress),
+            TaskStatus::InProgress
+        );
+    }
+}
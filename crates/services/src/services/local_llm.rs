@@ -0,0 +1,104 @@
+use std::time::{Duration, Instant};
+
+use executors::local_llm::{LocalLlm, LocalLlmTestResult};
+use serde_json::{Value, json};
+
+const LIST_MODELS_TIMEOUT: Duration = Duration::from_secs(5);
+/// Generous, since the server may have to load the model first
+const COMPLETION_TIMEOUT: Duration = Duration::from_secs(120);
+/// Model names listed when the configured one is missing
+const LISTED_MODELS: usize = 10;
+
+/// Checks that a local model endpoint answers and serves the model, and times a one-token
+/// completion so slow setups can be flagged before a task attempt runs on them
+pub async fn test_local_llm(config: &LocalLlm) -> LocalLlmTestResult {
+    let client = reqwest::Client::new();
+    let mut result = LocalLlmTestResult {
+        reachable: false,
+        model_available: false,
+        endpoint_latency_ms: None,
+        response_latency_ms: None,
+        warnings: vec![],
+        error: None,
+    };
+
+    let models_url = config.models_url();
+    let started = Instant::now();
+    let response = match client
+        .get(&models_url)
+        .timeout(LIST_MODELS_TIMEOUT)
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            result.error = Some(format!("Could not reach {models_url}: {e}"));
+            return result;
+        }
+    };
+    result.endpoint_latency_ms = Some(started.elapsed().as_millis() as u64);
+    result.reachable = true;
+
+    if !response.status().is_success() {
+        result.error = Some(format!(
+            "{models_url} returned {}; check the base URL",
+            response.status()
+        ));
+        return result;
+    }
+    let names = match response.json::<Value>().await {
+        Ok(body) => config.parse_model_names(&body),
+        Err(e) => {
+            result.error = Some(format!("Unexpected response from {models_url}: {e}"));
+            return result;
+        }
+    };
+    if !config.has_model(&names) {
+        let listed = names
+            .iter()
+            .take(LISTED_MODELS)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        result.error = Some(if listed.is_empty() {
+            format!(
+                "Model `{}` not found; the server has no models",
+                config.model
+            )
+        } else {
+            format!(
+                "Model `{}` not found; the server has: {listed}",
+                config.model
+            )
+        });
+        return result;
+    }
+    result.model_available = true;
+
+    let started = Instant::now();
+    let completion = client
+        .post(config.chat_completions_url())
+        .bearer_auth("local")
+        .timeout(COMPLETION_TIMEOUT)
+        .json(&json!({
+            "model": config.model,
+            "messages": [{"role": "user", "content": "Reply with OK."}],
+            "max_tokens": 1,
+            "stream": false,
+        }))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+    let elapsed = started.elapsed();
+    match completion {
+        Ok(_) => {
+            result.response_latency_ms = Some(elapsed.as_millis() as u64);
+            result.warnings = config.warnings(Some(elapsed));
+        }
+        Err(e) => {
+            result.error = Some(format!("The model did not answer a test request: {e}"));
+            result.warnings = config.warnings(None);
+        }
+    }
+    result
+}
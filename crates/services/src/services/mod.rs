@@ -1,24 +1,49 @@
 pub mod analytics;
 pub mod approvals;
+pub mod attachment;
+pub mod attempt_retry;
 pub mod auth;
+pub mod auto_assign;
+pub mod budget;
+pub mod calendar;
 pub mod config;
 pub mod container;
 pub mod diff_stream;
+pub mod escalation;
 pub mod events;
+pub mod execution_watchdog;
 pub mod file_ranker;
 pub mod file_search_cache;
 pub mod filesystem;
 pub mod filesystem_watcher;
 pub mod git;
 pub mod github;
+pub mod github_issue_sync;
 pub mod image;
+pub mod jira;
+pub mod jira_sync;
+pub mod linear;
+pub mod linear_sync;
+pub mod local_llm;
 pub mod notification;
 pub mod oauth_credentials;
+pub mod openrouter;
 pub mod pr_monitor;
+pub mod profile_test;
 pub mod project;
+pub mod project_bundle;
 pub mod queued_message;
+pub mod recurrence;
 pub mod remote_client;
 pub mod repo;
+pub mod run_queue;
 pub mod share;
+pub mod sound;
+pub mod summary;
+pub mod task_import;
+pub mod task_references;
+pub mod trello_import;
+pub mod usage;
+pub mod wip;
 pub mod workspace_manager;
 pub mod worktree_manager;
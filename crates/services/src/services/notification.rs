@@ -1,110 +1,1238 @@
-use std::sync::{Arc, OnceLock};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    str::FromStr,
+    sync::{Arc, Mutex, OnceLock},
+};
 
-use serde::Deserialize;
-use serde_json::json;
+use backon::{ExponentialBuilder, Retryable};
+use chrono::Duration;
+use db::{
+    DBService,
+    models::{
+        label::Label,
+        notification::{CreateNotificationRecord, NotificationRecord, NotificationStatus},
+        notification_escalation::{CreateNotificationEscalation, NotificationEscalation},
+        push_subscription::PushSubscription,
+        slack_thread::SlackThread,
+        task::TaskActivitySummary,
+        task_assignee::TaskAssignee,
+    },
+};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use thiserror::Error;
 use tokio::sync::RwLock;
+use ts_rs::TS;
 use utils;
 use uuid::Uuid;
 
-use crate::services::config::{Config, NotificationConfig, SoundFile};
+use crate::services::{
+    config::{
+        Config, NotificationChannel, NotificationConfig, NotificationDedup, NotificationDedupMode,
+        NotificationEvent, NotificationPriority, NotificationRetryPolicy, NotificationSummary,
+        SoundFile, SoundPlayback, SoundTheme, SummaryFrequency,
+    },
+    sound,
+};
+
+#[cfg(feature = "sound-playback")]
+mod audio;
+mod chat_webhook;
+mod dedup;
+mod digest;
+mod gotify;
+pub mod i18n;
+mod irc;
+mod matrix;
+mod ntfy;
+mod pushover;
+pub(crate) mod quiet_hours;
+mod rules;
+pub mod schedule;
+mod slack;
+mod template;
+pub mod web_push;
+pub mod webhook;
+mod zulip;
+
+use chat_webhook::{ChatWebhookFlavor, ChatWebhookTarget};
+use dedup::{DedupFilter, DedupKey};
+use digest::{DigestBuffer, PendingNotification};
+use gotify::GotifyTarget;
+use i18n::Locale;
+use irc::IrcTarget;
+use matrix::MatrixTarget;
+use ntfy::NtfyTarget;
+use pushover::PushoverTarget;
+use slack::SlackBot;
+use web_push::{VapidKeys, WebPushError, WebPushPayload};
+use webhook::WebhookPayload;
+use zulip::ZulipTarget;
 
 #[derive(Debug, Deserialize)]
 struct DevPorts {
     frontend: u16,
 }
 
-/// Service for handling cross-platform notifications including sound alerts and push notifications
+/// Describes what triggered a notification so channels can filter and route on it
 #[derive(Debug, Clone)]
+pub struct NotificationContext {
+    pub event: NotificationEvent,
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    /// Task attempt the event belongs to; Slack threads replies per workspace
+    pub workspace_id: Option<Uuid>,
+    /// Deep link to the task, resolved by `NotificationService::notify` when not provided
+    pub task_url: Option<String>,
+    pub project_name: Option<String>,
+    pub task_title: Option<String>,
+    pub attempt_duration: Option<Duration>,
+    /// Executor that ran the attempt, as stored on its session
+    pub executor: Option<String>,
+    /// Label names on the task, loaded by `NotificationService::notify` when not provided
+    pub labels: Vec<String>,
+    /// Names of the people and agent the task is assigned to, loaded by
+    /// `NotificationService::notify` when not provided
+    pub assignees: Vec<String>,
+    /// Urgency set by the matching notification rule
+    pub priority: Option<NotificationPriority>,
+    /// Language for text added by channels, set from the config by `NotificationService::notify`
+    pub locale: Locale,
+}
+
+impl NotificationContext {
+    pub fn new(event: NotificationEvent) -> Self {
+        Self {
+            event,
+            project_id: None,
+            task_id: None,
+            workspace_id: None,
+            task_url: None,
+            project_name: None,
+            task_title: None,
+            attempt_duration: None,
+            executor: None,
+            labels: Vec::new(),
+            assignees: Vec::new(),
+            priority: None,
+            locale: Locale::default(),
+        }
+    }
+
+    pub fn with_task(mut self, project_id: Uuid, task_id: Uuid) -> Self {
+        self.project_id = Some(project_id);
+        self.task_id = Some(task_id);
+        self
+    }
+
+    pub fn with_workspace(mut self, workspace_id: Uuid) -> Self {
+        self.workspace_id = Some(workspace_id);
+        self
+    }
+
+    /// Names exposed to notification templates as `{{project.name}}` and `{{task.title}}`
+    pub fn with_names(
+        mut self,
+        project_name: impl Into<String>,
+        task_title: impl Into<String>,
+    ) -> Self {
+        self.project_name = Some(project_name.into());
+        self.task_title = Some(task_title.into());
+        self
+    }
+
+    pub fn with_attempt_duration(mut self, duration: Duration) -> Self {
+        self.attempt_duration = Some(duration);
+        self
+    }
+
+    pub fn with_executor(mut self, executor: impl Into<String>) -> Self {
+        self.executor = Some(executor.into());
+        self
+    }
+
+    /// Variables available to notification templates
+    fn template_vars(&self, title: &str, message: &str) -> HashMap<&'static str, String> {
+        let event = serde_json::to_value(self.event)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+
+        HashMap::from([
+            ("event", event),
+            ("title", title.to_string()),
+            ("message", message.to_string()),
+            ("url", optional(&self.task_url)),
+            (
+                "project.id",
+                self.project_id.map(|id| id.to_string()).unwrap_or_default(),
+            ),
+            ("project.name", optional(&self.project_name)),
+            (
+                "task.id",
+                self.task_id.map(|id| id.to_string()).unwrap_or_default(),
+            ),
+            ("task.title", optional(&self.task_title)),
+            ("task.labels", self.labels.join(", ")),
+            ("task.assignees", self.assignees.join(", ")),
+            (
+                "attempt.duration",
+                self.attempt_duration
+                    .map(template::format_duration)
+                    .unwrap_or_default(),
+            ),
+        ])
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum NotificationError {
+    #[error("{0} is not configured")]
+    NotConfigured(&'static str),
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("{0}")]
+    Delivery(String),
+    #[error("Gave up after {attempts} attempts: {message}")]
+    RetriesExhausted { attempts: u32, message: String },
+    #[error("Suppressed during quiet hours")]
+    QuietHours,
+    #[error("Suppressed as a repeat of a recent notification")]
+    Duplicate,
+    #[error("Unknown notification channel or event: {0}")]
+    Unknown(String),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    WebPush(#[from] WebPushError),
+}
+
+/// Outcome of sending a test notification through one channel
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ChannelTestResult {
+    pub channel: NotificationChannel,
+    pub success: bool,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+/// Service for handling cross-platform notifications including sound alerts and push notifications
+#[derive(Clone)]
 pub struct NotificationService {
     config: Arc<RwLock<Config>>,
+    db: DBService,
+    digest: DigestBuffer,
+    dedup: DedupFilter,
 }
 
 /// Cache for WSL root path from PowerShell
 static WSL_ROOT_PATH_CACHE: OnceLock<Option<String>> = OnceLock::new();
 
+/// Windows paths of WSL directories already converted by `wslpath`
+static WSLPATH_DIR_CACHE: OnceLock<Mutex<HashMap<PathBuf, String>>> = OnceLock::new();
+
 impl NotificationService {
-    pub fn new(config: Arc<RwLock<Config>>) -> Self {
-        Self { config }
+    pub fn new(config: Arc<RwLock<Config>>, db: DBService) -> Self {
+        Self {
+            config,
+            db,
+            digest: DigestBuffer::default(),
+            dedup: DedupFilter::default(),
+        }
     }
 
     pub async fn kanban_task_url(&self, project_id: Uuid, task_id: Uuid) -> Option<String> {
-        let base_url = Self::resolve_kanban_base_url().await?;
+        let base_url = self.resolve_kanban_base_url().await?;
         Some(format!("{base_url}/projects/{project_id}/tasks/{task_id}"))
     }
 
-    /// Send both sound and push notifications if enabled
-    pub async fn notify(&self, title: &str, message: &str) {
-        let config = self.config.read().await.notifications.clone();
-        Self::send_notification(&config, title, message).await;
+    /// Compact task link for size-limited channels, redirected by the server to the task
+    async fn short_task_url(&self, task_id: Uuid) -> Option<String> {
+        let base_url = self.resolve_kanban_base_url().await?;
+        let short_id = &task_id.simple().to_string()[..8];
+        Some(format!("{base_url}/api/t/{short_id}"))
     }
 
-    /// Internal method to send notifications with a given config
-    async fn send_notification(config: &NotificationConfig, title: &str, message: &str) {
-        if config.sound_enabled {
-            Self::play_sound_notification(&config.sound_file).await;
+    /// Locale notification text should be written in, following the UI language
+    pub async fn locale(&self) -> Locale {
+        Locale::resolve(&self.config.read().await.language)
+    }
+
+    /// Send notifications through every enabled channel
+    pub async fn notify(&self, context: &NotificationContext, title: &str, message: &str) {
+        let (mut config, locale) = {
+            let config = self.config.read().await;
+            (
+                config.notifications.for_project(context.project_id),
+                Locale::resolve(&config.language),
+            )
+        };
+        let mut context = context.clone();
+        context.locale = locale;
+        if context.task_url.is_none()
+            && let (Some(project_id), Some(task_id)) = (context.project_id, context.task_id)
+        {
+            context.task_url = self.kanban_task_url(project_id, task_id).await;
+        }
+        if context.labels.is_empty()
+            && let Some(task_id) = context.task_id
+        {
+            context.labels = Label::names_for_task(&self.db.pool, task_id)
+                .await
+                .unwrap_or_default();
+        }
+        if context.assignees.is_empty()
+            && let Some(task_id) = context.task_id
+        {
+            context.assignees = TaskAssignee::names_for_task(&self.db.pool, task_id)
+                .await
+                .unwrap_or_default();
         }
 
-        if config.push_enabled {
-            Self::send_push_notification(title, message).await;
+        let (title, message) = match config.templates.get(&context.event) {
+            Some(templates) => {
+                let vars = context.template_vars(title, message);
+                let render = |template: &Option<String>, fallback: &str| match template {
+                    Some(template) if !template.trim().is_empty() => {
+                        template::render(template, &vars)
+                    }
+                    _ => fallback.to_string(),
+                };
+                (
+                    render(&templates.title, title),
+                    render(&templates.message, message),
+                )
+            }
+            None => (title.to_string(), message.to_string()),
+        };
+
+        let rule = rules::find_rule(&config.rules, &context).cloned();
+        if let Some(rule) = &rule {
+            tracing::debug!("Notification '{}' matched rule '{}'", title, rule.name);
+            context.priority = rule.priority;
+            if rule.slack_channel.is_some() {
+                config.slack_channel = rule.slack_channel.clone();
+            }
         }
 
-        if config.slack_enabled {
-            if let Some(webhook_url) = config
-                .slack_webhook_url
-                .as_ref()
-                .map(|url| url.trim())
-                .filter(|url| !url.is_empty())
+        if context.event.is_failure()
+            && config.escalation.enabled
+            && !config.escalation.channels.is_empty()
+            && let Some(task_id) = context.task_id
+        {
+            match self
+                .open_escalation(&config, &context, task_id, &title, &message)
+                .await
             {
-                Self::send_slack_notification(webhook_url.to_string(), title, message).await;
-            } else {
-                tracing::warn!(
-                    "Slack notifications enabled but webhook URL is missing"
+                // Following the link acknowledges the failure
+                Ok(escalation) => {
+                    if let Some(url) = self.acknowledge_url(escalation.id).await {
+                        context.task_url = Some(url);
+                    }
+                }
+                Err(e) => tracing::error!("Failed to schedule notification escalation: {}", e),
+            }
+        }
+
+        let routed = rule.as_ref().map(|rule| rule.channels.as_slice());
+        self.send_notification(&config, &context, &title, &message, routed)
+            .await;
+    }
+
+    /// Start the acknowledgment timer for a failure, reusing the task's pending one if any
+    async fn open_escalation(
+        &self,
+        config: &NotificationConfig,
+        context: &NotificationContext,
+        task_id: Uuid,
+        title: &str,
+        message: &str,
+    ) -> Result<NotificationEscalation, NotificationError> {
+        let pool = &self.db.pool;
+        if let Some(open) = NotificationEscalation::find_open_for_task(pool, task_id).await? {
+            return Ok(open);
+        }
+        let escalation = NotificationEscalation::create(
+            pool,
+            &CreateNotificationEscalation {
+                event: context.event.to_string(),
+                project_id: context.project_id,
+                task_id: Some(task_id),
+                workspace_id: context.workspace_id,
+                title: title.to_string(),
+                message: message.to_string(),
+                task_url: context.task_url.clone(),
+                delay_minutes: config.escalation.after_minutes.into(),
+            },
+        )
+        .await?;
+        Ok(escalation)
+    }
+
+    /// Link that acknowledges an escalation and then redirects to the task
+    async fn acknowledge_url(&self, escalation_id: Uuid) -> Option<String> {
+        let base_url = self.resolve_kanban_base_url().await?;
+        Some(format!(
+            "{base_url}/api/notifications/escalations/{escalation_id}/ack"
+        ))
+    }
+
+    /// Re-send an unacknowledged failure through the escalation channels and mark it escalated
+    pub async fn escalate(
+        &self,
+        escalation: &NotificationEscalation,
+    ) -> Result<(), NotificationError> {
+        let event = NotificationEvent::from_str(&escalation.event)
+            .map_err(|_| NotificationError::Unknown(escalation.event.clone()))?;
+        let mut context = NotificationContext::new(event);
+        context.project_id = escalation.project_id;
+        context.task_id = escalation.task_id;
+        context.workspace_id = escalation.workspace_id;
+        context.task_url = escalation.task_url.clone();
+        context.locale = self.locale().await;
+        let pending = PendingNotification {
+            title: i18n::t(
+                context.locale,
+                "unacknowledged",
+                &[("title", escalation.title.as_str())],
+            ),
+            context,
+            message: escalation.message.clone(),
+        };
+
+        let config = self
+            .config
+            .read()
+            .await
+            .notifications
+            .for_project(escalation.project_id);
+        // The policy may have been switched off since the failure
+        if config.escalation.enabled {
+            for channel in config.escalation.channels.iter().copied() {
+                if config.is_channel_enabled(channel) {
+                    self.deliver_and_record(&config, channel, &pending).await;
+                }
+            }
+        }
+        NotificationEscalation::mark_escalated(&self.db.pool, escalation.id).await?;
+        Ok(())
+    }
+
+    pub async fn summary_settings(&self) -> NotificationSummary {
+        self.config.read().await.notifications.summary.clone()
+    }
+
+    /// Send the scheduled activity summary like any other notification
+    pub async fn send_summary(&self, frequency: SummaryFrequency, activity: &TaskActivitySummary) {
+        let locale = self.locale().await;
+        let title = i18n::t(
+            locale,
+            match frequency {
+                SummaryFrequency::Daily => "summary-daily-title",
+                SummaryFrequency::Weekly => "summary-weekly-title",
+            },
+            &[],
+        );
+        let mut lines: Vec<String> = [
+            ("summary-completed", activity.completed),
+            ("summary-failed", activity.failed),
+            ("summary-awaiting-review", activity.awaiting_review),
+        ]
+        .iter()
+        .map(|(key, count)| i18n::t(locale, key, &[("count", count.to_string().as_str())]))
+        .collect();
+        if activity.cost_usd > 0.0 {
+            let cost = format!("{:.2}", activity.cost_usd);
+            lines.push(i18n::t(locale, "summary-cost", &[("cost", cost.as_str())]));
+        }
+        let message = lines.join("\n");
+        self.notify(
+            &NotificationContext::new(NotificationEvent::Summary),
+            &title,
+            &message,
+        )
+        .await;
+    }
+
+    /// Send a sample notification through every enabled channel and report how each one went.
+    ///
+    /// Test sends skip event toggles, quiet hours and digests, and are not recorded in history.
+    pub async fn send_test(&self) -> Vec<ChannelTestResult> {
+        let config = self.config.read().await.notifications.clone();
+        let locale = self.locale().await;
+        let title = i18n::t(locale, "test-title", &[]);
+        let mut context = NotificationContext::new(NotificationEvent::AttemptCompleted)
+            .with_names("Vibe Kanban", title.as_str());
+        context.locale = locale;
+        let title = title.as_str();
+
+        let sends = NotificationChannel::iter()
+            .filter(|channel| config.is_channel_enabled(*channel))
+            .map(|channel| {
+                let config = &config;
+                let context = &context;
+                async move {
+                    let message = i18n::t(
+                        context.locale,
+                        "test-message",
+                        &[("channel", channel.to_string().as_str())],
+                    );
+                    let started = std::time::Instant::now();
+                    let result = self
+                        .deliver(config, channel, context, title, &message)
+                        .await;
+                    ChannelTestResult {
+                        channel,
+                        success: result.is_ok(),
+                        latency_ms: started.elapsed().as_millis() as u64,
+                        error: result.err().map(|e| e.to_string()),
+                    }
+                }
+            });
+        futures::future::join_all(sends).await
+    }
+
+    /// Re-deliver a previously recorded notification through the same channel
+    pub async fn resend(
+        &self,
+        record: &NotificationRecord,
+    ) -> Result<NotificationRecord, NotificationError> {
+        let channel = NotificationChannel::from_str(&record.channel)
+            .map_err(|_| NotificationError::Unknown(record.channel.clone()))?;
+        let event = NotificationEvent::from_str(&record.event)
+            .map_err(|_| NotificationError::Unknown(record.event.clone()))?;
+        let mut context = NotificationContext::new(event);
+        context.project_id = record.project_id;
+        context.task_id = record.task_id;
+        context.task_url = record.task_url.clone();
+        context.locale = self.locale().await;
+
+        let config = self
+            .config
+            .read()
+            .await
+            .notifications
+            .for_project(record.project_id);
+        let result = self
+            .deliver(&config, channel, &context, &record.title, &record.message)
+            .await;
+        self.record(channel, &context, &record.title, &record.message, result)
+            .await
+    }
+
+    /// Dispatch to every enabled channel, recording each outcome.
+    ///
+    /// `routed` lists the channels picked by a notification rule, which replace the event toggles.
+    async fn send_notification(
+        &self,
+        config: &NotificationConfig,
+        context: &NotificationContext,
+        title: &str,
+        message: &str,
+        routed: Option<&[NotificationChannel]>,
+    ) {
+        // Quiet hours silence sound and push-style alerts; chat and webhook channels are still
+        // delivered
+        let quiet = quiet_hours::is_quiet(&config.quiet_hours, chrono::Utc::now());
+
+        for channel in NotificationChannel::iter() {
+            let selected = match routed {
+                Some(channels) => channels.contains(&channel),
+                None => config.is_event_enabled(channel, context.event),
+            };
+            if !config.is_channel_enabled(channel) || !selected {
+                continue;
+            }
+
+            if quiet && quiet_hours::silences(channel) {
+                tracing::debug!(
+                    "Quiet hours active, suppressing {} for '{}'",
+                    channel,
+                    title
                 );
+                if let Err(e) = self
+                    .record(
+                        channel,
+                        context,
+                        title,
+                        message,
+                        Err(NotificationError::QuietHours),
+                    )
+                    .await
+                {
+                    tracing::error!("Failed to record suppressed notification: {}", e);
+                }
+                continue;
             }
+
+            let pending = PendingNotification {
+                context: context.clone(),
+                title: title.to_string(),
+                message: message.to_string(),
+            };
+
+            if config.dedup.window_secs > 0 {
+                let key = (context.event, context.task_id, channel);
+                if !self.dedup.admit(key, &pending).await {
+                    tracing::debug!("Suppressing repeated {} notification '{}'", channel, title);
+                    if let Err(e) = self
+                        .record(
+                            channel,
+                            context,
+                            title,
+                            message,
+                            Err(NotificationError::Duplicate),
+                        )
+                        .await
+                    {
+                        tracing::error!("Failed to record suppressed notification: {}", e);
+                    }
+                    continue;
+                }
+                self.schedule_dedup_close(key, config.dedup.clone());
+            }
+
+            if config.digest.window_secs > 0 && config.digest.channels.contains(&channel) {
+                self.queue_digest(channel, pending, config.digest.window_secs)
+                    .await;
+                continue;
+            }
+
+            // Fire-and-forget so slow endpoints don't hold up the caller
+            let service = self.clone();
+            let config = config.clone();
+            tokio::spawn(async move {
+                service.deliver_and_record(&config, channel, &pending).await;
+            });
         }
     }
 
-    /// Play a system sound notification across platforms
-    async fn play_sound_notification(sound_file: &SoundFile) {
-        let file_path = match sound_file.get_path().await {
-            Ok(path) => path,
+    /// Close a dedup window once it expires, sending the repeat count in count mode
+    fn schedule_dedup_close(&self, key: DedupKey, dedup: NotificationDedup) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_secs(dedup.window_secs.into())).await;
+            let Some(entry) = service.dedup.close(&key).await else {
+                return;
+            };
+            if dedup.mode != NotificationDedupMode::Count {
+                return;
+            }
+            let Some(summary) = dedup::summarize(entry) else {
+                return;
+            };
+            let (_, _, channel) = key;
+            let config = service
+                .config
+                .read()
+                .await
+                .notifications
+                .for_project(summary.context.project_id);
+            if config.is_channel_enabled(channel) {
+                service.deliver_and_record(&config, channel, &summary).await;
+            }
+        });
+    }
+
+    /// Hold a notification back, opening a digest window for the channel if none is open
+    async fn queue_digest(
+        &self,
+        channel: NotificationChannel,
+        pending: PendingNotification,
+        window_secs: u32,
+    ) {
+        if !self.digest.push(channel, pending).await {
+            return;
+        }
+
+        let service = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_secs(window_secs.into())).await;
+            let Some(summary) = digest::summarize(service.digest.take(channel).await) else {
+                return;
+            };
+            // Settings may have changed while the window was open
+            let config = service
+                .config
+                .read()
+                .await
+                .notifications
+                .for_project(summary.context.project_id);
+            if config.is_channel_enabled(channel) {
+                service.deliver_and_record(&config, channel, &summary).await;
+            }
+        });
+    }
+
+    async fn deliver_and_record(
+        &self,
+        config: &NotificationConfig,
+        channel: NotificationChannel,
+        pending: &PendingNotification,
+    ) {
+        let PendingNotification {
+            context,
+            title,
+            message,
+        } = pending;
+        let result = self.deliver(config, channel, context, title, message).await;
+        if let Err(e) = &result {
+            tracing::error!("Failed to send {} notification: {}", channel, e);
+        }
+        if let Err(e) = self.record(channel, context, title, message, result).await {
+            tracing::error!("Failed to record {} notification: {}", channel, e);
+        }
+    }
+
+    /// Persist the outcome of a delivery attempt to the notification history
+    async fn record(
+        &self,
+        channel: NotificationChannel,
+        context: &NotificationContext,
+        title: &str,
+        message: &str,
+        result: Result<(), NotificationError>,
+    ) -> Result<NotificationRecord, NotificationError> {
+        let (status, error) = match result {
+            Ok(()) => (NotificationStatus::Sent, None),
+            Err(NotificationError::QuietHours) => (NotificationStatus::Suppressed, None),
+            Err(e @ NotificationError::Duplicate) => {
+                (NotificationStatus::Suppressed, Some(e.to_string()))
+            }
+            Err(e @ NotificationError::RetriesExhausted { .. }) => {
+                (NotificationStatus::DeadLetter, Some(e.to_string()))
+            }
+            Err(e) => (NotificationStatus::Failed, Some(e.to_string())),
+        };
+        let record = NotificationRecord::create(
+            &self.db.pool,
+            &CreateNotificationRecord {
+                channel: channel.to_string(),
+                event: context.event.to_string(),
+                project_id: context.project_id,
+                task_id: context.task_id,
+                title: title.to_string(),
+                message: message.to_string(),
+                task_url: context.task_url.clone(),
+                status,
+                error,
+            },
+        )
+        .await?;
+        Ok(record)
+    }
+
+    /// Deliver a notification through a single channel
+    async fn deliver(
+        &self,
+        config: &NotificationConfig,
+        channel: NotificationChannel,
+        context: &NotificationContext,
+        title: &str,
+        message: &str,
+    ) -> Result<(), NotificationError> {
+        match channel {
+            NotificationChannel::Sound => {
+                Self::play_sound_notification(config, Some(context.event)).await;
+                Ok(())
+            }
+            NotificationChannel::Push => {
+                let group = context.project_id.map(|id| id.to_string());
+                Self::send_push_notification(
+                    title,
+                    message,
+                    context.task_url.as_deref(),
+                    group.as_deref(),
+                )
+                .await;
+                Ok(())
+            }
+            NotificationChannel::Slack => {
+                let mention = context
+                    .event
+                    .is_failure()
+                    .then(|| config.slack_mention())
+                    .flatten();
+                if let (Some(token), Some(channel)) = (
+                    non_empty(&config.slack_bot_token),
+                    non_empty(&config.slack_channel),
+                ) {
+                    let bot = SlackBot { token, channel };
+                    return self
+                        .post_slack_threaded(
+                            &bot,
+                            config,
+                            context,
+                            title,
+                            message,
+                            mention.as_deref(),
+                        )
+                        .await;
+                }
+                let webhook_url = non_empty(&config.slack_webhook_url)
+                    .ok_or(NotificationError::NotConfigured("Slack webhook URL"))?;
+                slack::send_slack(
+                    &webhook_url,
+                    context,
+                    title,
+                    message,
+                    mention.as_deref(),
+                    &config.retry,
+                )
+                .await
+            }
+            NotificationChannel::Webhook => {
+                let urls: Vec<String> = config
+                    .webhook_urls
+                    .iter()
+                    .map(|url| url.trim())
+                    .filter(|url| !url.is_empty())
+                    .map(|url| url.to_string())
+                    .collect();
+                if urls.is_empty() {
+                    return Err(NotificationError::NotConfigured("Webhook URL"));
+                }
+                let payload = WebhookPayload {
+                    event: context.event,
+                    project_id: context.project_id,
+                    task_id: context.task_id,
+                    labels: context.labels.clone(),
+                    title: title.to_string(),
+                    message: message.to_string(),
+                    timestamp: chrono::Utc::now(),
+                };
+                let secret = non_empty(&config.webhook_secret);
+                webhook::send_webhooks(&urls, secret, &payload, &config.retry).await
+            }
+            NotificationChannel::Ntfy => {
+                let target = NtfyTarget {
+                    topic_url: non_empty(&config.ntfy_topic_url)
+                        .ok_or(NotificationError::NotConfigured("ntfy topic URL"))?,
+                    token: non_empty(&config.ntfy_token),
+                    priority: context
+                        .priority
+                        .map(ntfy::priority_level)
+                        .or(config.ntfy_priority),
+                };
+                let click = context.task_url.as_deref();
+                ntfy::send_ntfy(&target, title, message, click, &config.retry).await
+            }
+            NotificationChannel::Pushover => {
+                let (Some(app_token), Some(user_key)) = (
+                    non_empty(&config.pushover_app_token),
+                    non_empty(&config.pushover_user_key),
+                ) else {
+                    return Err(NotificationError::NotConfigured(
+                        "Pushover app token or user key",
+                    ));
+                };
+                let target = PushoverTarget {
+                    app_token,
+                    user_key,
+                    priority: context
+                        .priority
+                        .map(pushover::priority_level)
+                        .or(config.pushover_priority),
+                };
+                let url = context.task_url.as_deref();
+                pushover::send_pushover(&target, title, message, url, &config.retry).await
+            }
+            NotificationChannel::Gotify => {
+                let (Some(server_url), Some(app_token)) = (
+                    non_empty(&config.gotify_server_url),
+                    non_empty(&config.gotify_app_token),
+                ) else {
+                    return Err(NotificationError::NotConfigured(
+                        "Gotify server URL or app token",
+                    ));
+                };
+                let target = GotifyTarget {
+                    server_url,
+                    app_token,
+                };
+                let click = context.task_url.as_deref();
+                gotify::send_gotify(
+                    &target,
+                    context.event,
+                    context.priority,
+                    title,
+                    message,
+                    click,
+                    &config.retry,
+                )
+                .await
+            }
+            NotificationChannel::Matrix => {
+                let (Some(homeserver_url), Some(access_token), Some(room_id)) = (
+                    non_empty(&config.matrix_homeserver_url),
+                    non_empty(&config.matrix_access_token),
+                    non_empty(&config.matrix_room_id),
+                ) else {
+                    return Err(NotificationError::NotConfigured(
+                        "Matrix homeserver URL, access token or room ID",
+                    ));
+                };
+                let target = MatrixTarget {
+                    homeserver_url,
+                    access_token,
+                    room_id,
+                };
+                let task_url = context.task_url.as_deref();
+                matrix::send_matrix(
+                    &target,
+                    context.locale,
+                    title,
+                    message,
+                    task_url,
+                    &config.retry,
+                )
+                .await
+            }
+            NotificationChannel::Mattermost => {
+                let target = ChatWebhookTarget {
+                    flavor: ChatWebhookFlavor::Mattermost,
+                    webhook_url: non_empty(&config.mattermost_webhook_url)
+                        .ok_or(NotificationError::NotConfigured("Mattermost webhook URL"))?,
+                    channel: non_empty(&config.mattermost_channel),
+                    icon_url: non_empty(&config.mattermost_icon_url),
+                };
+                let task_url = context.task_url.as_deref();
+                chat_webhook::send_chat_webhook(
+                    &target,
+                    context.event,
+                    title,
+                    message,
+                    task_url,
+                    &config.retry,
+                )
+                .await
+            }
+            NotificationChannel::RocketChat => {
+                let target = ChatWebhookTarget {
+                    flavor: ChatWebhookFlavor::RocketChat,
+                    webhook_url: non_empty(&config.rocketchat_webhook_url)
+                        .ok_or(NotificationError::NotConfigured("Rocket.Chat webhook URL"))?,
+                    channel: non_empty(&config.rocketchat_channel),
+                    icon_url: non_empty(&config.rocketchat_avatar_url),
+                };
+                let task_url = context.task_url.as_deref();
+                chat_webhook::send_chat_webhook(
+                    &target,
+                    context.event,
+                    title,
+                    message,
+                    task_url,
+                    &config.retry,
+                )
+                .await
+            }
+            NotificationChannel::Zulip => {
+                let (Some(site_url), Some(bot_email), Some(api_key), Some(stream)) = (
+                    non_empty(&config.zulip_site_url),
+                    non_empty(&config.zulip_bot_email),
+                    non_empty(&config.zulip_api_key),
+                    non_empty(&config.zulip_stream),
+                ) else {
+                    return Err(NotificationError::NotConfigured(
+                        "Zulip site URL, bot email, API key or stream",
+                    ));
+                };
+                let target = ZulipTarget {
+                    site_url,
+                    bot_email,
+                    api_key,
+                    stream,
+                    topic: non_empty(&config.zulip_topic),
+                };
+                zulip::send_zulip(
+                    &target,
+                    context.locale,
+                    context.project_name.as_deref(),
+                    title,
+                    message,
+                    context.task_url.as_deref(),
+                    &config.retry,
+                )
+                .await
+            }
+            NotificationChannel::Irc => {
+                let (Some(server), Some(nick), Some(channel)) = (
+                    non_empty(&config.irc_server),
+                    non_empty(&config.irc_nick),
+                    non_empty(&config.irc_channel),
+                ) else {
+                    return Err(NotificationError::NotConfigured(
+                        "IRC server, nick or channel",
+                    ));
+                };
+                let target = IrcTarget {
+                    server,
+                    tls: config.irc_tls.unwrap_or(true),
+                    nick,
+                    channel,
+                    sasl: non_empty(&config.irc_sasl_username)
+                        .zip(non_empty(&config.irc_sasl_password)),
+                };
+                // IRC lines are short, so link through the short redirect instead
+                let task_url = match context.task_id {
+                    Some(task_id) => self.short_task_url(task_id).await,
+                    None => context.task_url.clone(),
+                };
+                irc::send_irc(&target, title, message, task_url.as_deref()).await
+            }
+            NotificationChannel::WebPush => {
+                self.send_web_push(config, context, title, message).await
+            }
+        }
+    }
+
+    /// Push to every subscribed browser, dropping subscriptions the push service says are gone
+    async fn send_web_push(
+        &self,
+        config: &NotificationConfig,
+        context: &NotificationContext,
+        title: &str,
+        message: &str,
+    ) -> Result<(), NotificationError> {
+        let pool = &self.db.pool;
+        let subscriptions = PushSubscription::find_all(pool).await?;
+        if subscriptions.is_empty() {
+            return Err(NotificationError::NotConfigured("Web Push subscription"));
+        }
+
+        let keys = VapidKeys::get()?;
+        let payload = serde_json::to_vec(&WebPushPayload {
+            title: title.to_string(),
+            body: message.to_string(),
+            url: context.task_url.clone(),
+            tag: context.task_id.map(|id| id.to_string()),
+        })
+        .map_err(|e| NotificationError::Delivery(format!("Invalid push payload: {e}")))?;
+
+        let client = reqwest::Client::new();
+        let mut failures = Vec::new();
+        let mut gave_up_after = None;
+        for (index, subscription) in subscriptions.iter().enumerate() {
+            let body = web_push::encrypt(&subscription.p256dh, &subscription.auth, &payload)?;
+            let request = client
+                .post(&subscription.endpoint)
+                .header(
+                    reqwest::header::AUTHORIZATION,
+                    keys.authorization(&subscription.endpoint)?,
+                )
+                .header(reqwest::header::CONTENT_ENCODING, "aes128gcm")
+                .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+                .header("TTL", "86400")
+                .body(body);
+            match send_checked(request, &config.retry).await {
+                Ok(()) => {}
+                Err(NotificationError::Http(e))
+                    if matches!(
+                        e.status(),
+                        Some(reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::GONE)
+                    ) =>
+                {
+                    tracing::info!("Removing expired Web Push subscription #{}", index + 1);
+                    PushSubscription::delete_by_endpoint(pool, &subscription.endpoint).await?;
+                }
+                Err(e) => {
+                    if let NotificationError::RetriesExhausted { attempts, .. } = &e {
+                        gave_up_after = Some(*attempts);
+                    }
+                    // Endpoints are capability URLs, so refer to them by position
+                    failures.push(format!("subscription #{}: {}", index + 1, e));
+                }
+            }
+        }
+
+        match (failures.is_empty(), gave_up_after) {
+            (true, _) => Ok(()),
+            (false, Some(attempts)) => Err(NotificationError::RetriesExhausted {
+                attempts,
+                message: failures.join("; "),
+            }),
+            (false, None) => Err(NotificationError::Delivery(failures.join("; "))),
+        }
+    }
+
+    /// Post through the Slack bot, replying to the workspace's first message when there is one
+    async fn post_slack_threaded(
+        &self,
+        bot: &SlackBot,
+        config: &NotificationConfig,
+        context: &NotificationContext,
+        title: &str,
+        message: &str,
+        mention: Option<&str>,
+    ) -> Result<(), NotificationError> {
+        let Some(workspace_id) = context.workspace_id else {
+            slack::post_message(bot, None, context, title, message, mention, &config.retry).await?;
+            return Ok(());
+        };
+
+        let pool = &self.db.pool;
+        let thread = SlackThread::find(pool, workspace_id, &bot.channel).await?;
+        let thread_ts = thread.as_ref().map(|thread| thread.thread_ts.as_str());
+        let ts = slack::post_message(
+            bot,
+            thread_ts,
+            context,
+            title,
+            message,
+            mention,
+            &config.retry,
+        )
+        .await?;
+        if thread.is_none() {
+            SlackThread::create(pool, workspace_id, &bot.channel, &ts).await?;
+        }
+        Ok(())
+    }
+
+    /// Play the configured sound, or a different one, so users can try it out. With an
+    /// `event` and no explicit sound, this plays that event's sound under the theme.
+    pub async fn preview_sound(
+        &self,
+        sound_file: Option<SoundFile>,
+        custom_sound: Option<String>,
+        sound_theme: Option<SoundTheme>,
+        event: Option<NotificationEvent>,
+    ) {
+        let mut config = self.config.read().await.notifications.clone();
+        let explicit = sound_file.is_some() || custom_sound.is_some();
+        if let Some(sound_file) = sound_file {
+            config.sound_file = sound_file;
+            config.custom_sound = None;
+        }
+        if custom_sound.is_some() {
+            config.custom_sound = custom_sound;
+        }
+        if let Some(sound_theme) = sound_theme {
+            config.sound_theme = sound_theme;
+        }
+        Self::play_sound_notification(&config, event.filter(|_| !explicit)).await;
+    }
+
+    /// Path of the sound to play for `event`, checking its own sound, then the theme, then the
+    /// global sound. A missing custom sound falls through to the next choice.
+    async fn resolve_sound_path(
+        config: &NotificationConfig,
+        event: Option<NotificationEvent>,
+    ) -> Option<PathBuf> {
+        let event_sound = event.and_then(|event| config.event_sounds.get(&event));
+        let custom_sounds = [
+            event_sound.and_then(|sound| non_empty(&sound.custom_sound)),
+            non_empty(&config.custom_sound),
+        ];
+        let themed = event.and_then(|event| config.sound_theme.sound_for(event.sound_category()));
+        let sound_file = event_sound
+            .and_then(|sound| sound.sound_file.clone())
+            .or(themed);
+
+        // The global custom sound only applies when neither the event nor the theme has one
+        let candidates = match sound_file {
+            Some(_) => &custom_sounds[..1],
+            None => &custom_sounds[..],
+        };
+        for custom_sound in candidates.iter().flatten() {
+            match sound::resolve_sound(custom_sound) {
+                Ok(path) => return Some(path),
+                Err(e) => tracing::warn!("Custom sound '{}' unavailable: {}", custom_sound, e),
+            }
+        }
+
+        let sound_file = sound_file.unwrap_or_else(|| config.sound_file.clone());
+        match sound_file.get_path().await {
+            Ok(path) => Some(path),
             Err(e) => {
                 tracing::error!("Failed to create cached sound file: {}", e);
-                return;
+                None
             }
+        }
+    }
+
+    /// Play a system sound notification across platforms
+    async fn play_sound_notification(
+        config: &NotificationConfig,
+        event: Option<NotificationEvent>,
+    ) {
+        let Some(file_path) = Self::resolve_sound_path(config, event).await else {
+            return;
         };
+        let playback = config.sound_playback.clone();
+        if playback.volume == 0 {
+            return;
+        }
+
+        // WSL2 usually has no audio device of its own, so play through the Windows host. Builds
+        // without in-process playback always use the system player.
+        if !cfg!(feature = "sound-playback") || utils::is_wsl2() {
+            Self::play_sound_with_command(&file_path, &playback).await;
+            return;
+        }
+
+        // Fire-and-forget: playback blocks until the sound ends
+        #[cfg(feature = "sound-playback")]
+        tokio::spawn(async move {
+            let path = file_path.clone();
+            let settings = playback.clone();
+            match tokio::task::spawn_blocking(move || audio::play_blocking(&path, &settings)).await
+            {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    tracing::debug!(
+                        "In-process audio playback failed, falling back to system player: {}",
+                        e
+                    );
+                    Self::play_sound_with_command(&file_path, &playback).await;
+                }
+                Err(e) => tracing::error!("Audio playback task failed: {}", e),
+            }
+        });
+    }
+
+    /// Play a sound by shelling out to the platform's audio player.
+    ///
+    /// Volume is applied where the player supports it (afplay and paplay); the duration limit
+    /// applies everywhere.
+    async fn play_sound_with_command(file_path: &std::path::Path, playback: &SoundPlayback) {
+        use tokio::process::Command;
 
-        // Use platform-specific sound notification
-        // Note: spawn() calls are intentionally not awaited - sound notifications should be fire-and-forget
+        let max_duration = playback.max_duration();
+        // Note: players are intentionally not awaited - sound notifications should be fire-and-forget
         if cfg!(target_os = "macos") {
-            let _ = tokio::process::Command::new("afplay")
-                .arg(&file_path)
-                .spawn();
+            let mut command = Command::new("afplay");
+            command.arg("-v").arg(playback.gain().to_string());
+            if let Some(max_duration) = max_duration {
+                command
+                    .arg("-t")
+                    .arg(max_duration.as_secs_f32().to_string());
+            }
+            let _ = command.arg(file_path).spawn();
         } else if cfg!(target_os = "linux") && !utils::is_wsl2() {
+            // paplay volume is linear, with 65536 as 100%
+            let paplay_volume = (playback.gain() * 65536.0).round() as u32;
             // Try different Linux audio players
-            if tokio::process::Command::new("paplay")
-                .arg(&file_path)
-                .spawn()
-                .is_ok()
-            {
-                // Success with paplay
-            } else if tokio::process::Command::new("aplay")
-                .arg(&file_path)
+            if let Ok(child) = Command::new("paplay")
+                .arg(format!("--volume={paplay_volume}"))
+                .arg(file_path)
                 .spawn()
-                .is_ok()
             {
-                // Success with aplay
+                Self::stop_after(child, max_duration);
+            } else if let Ok(child) = Command::new("aplay").arg(file_path).spawn() {
+                Self::stop_after(child, max_duration);
             } else {
                 // Try system bell as fallback
-                let _ = tokio::process::Command::new("echo")
-                    .arg("-e")
-                    .arg("\\a")
-                    .spawn();
+                let _ = Command::new("echo").arg("-e").arg("\\a").spawn();
             }
         } else if cfg!(target_os = "windows") || (cfg!(target_os = "linux") && utils::is_wsl2()) {
             // Convert WSL path to Windows path if in WSL2
             let file_path = if utils::is_wsl2() {
-                if let Some(windows_path) = Self::wsl_to_windows_path(&file_path).await {
+                if let Some(windows_path) = Self::wsl_to_windows_path(file_path).await {
                     windows_path
                 } else {
                     file_path.to_string_lossy().to_string()
@@ -113,33 +1241,99 @@ impl NotificationService {
                 file_path.to_string_lossy().to_string()
             };
 
-            let _ = tokio::process::Command::new("powershell.exe")
+            if let Ok(child) = Command::new("powershell.exe")
                 .arg("-c")
                 .arg(format!(
-                    r#"(New-Object Media.SoundPlayer "{file_path}").PlaySync()"#
+                    "(New-Object Media.SoundPlayer {}).PlaySync()",
+                    powershell_quote(&file_path)
                 ))
-                .spawn();
+                .spawn()
+            {
+                Self::stop_after(child, max_duration);
+            }
         }
     }
 
-    /// Send a cross-platform push notification
-    async fn send_push_notification(title: &str, message: &str) {
+    /// Kill a player that is still running once the playback limit has passed
+    fn stop_after(mut child: tokio::process::Child, max_duration: Option<std::time::Duration>) {
+        let Some(max_duration) = max_duration else {
+            return;
+        };
+        tokio::spawn(async move {
+            if tokio::time::timeout(max_duration, child.wait())
+                .await
+                .is_err()
+            {
+                let _ = child.kill().await;
+            }
+        });
+    }
+
+    /// Send a cross-platform push notification; clicking it opens `task_url` when set.
+    /// Notifications sharing a `group` (the project id) replace each other instead of
+    /// piling up in the notification center.
+    async fn send_push_notification(
+        title: &str,
+        message: &str,
+        task_url: Option<&str>,
+        group: Option<&str>,
+    ) {
         if cfg!(target_os = "macos") {
-            Self::send_macos_notification(title, message).await;
+            Self::send_macos_notification(title, message, task_url, group).await;
         } else if cfg!(target_os = "linux") && !utils::is_wsl2() {
-            Self::send_linux_notification(title, message).await;
-        } else if cfg!(target_os = "windows") || (cfg!(target_os = "linux") && utils::is_wsl2()) {
-            Self::send_windows_notification(title, message).await;
+            Self::send_linux_notification(title, message, task_url, group).await;
+        } else if cfg!(target_os = "linux") && utils::is_wsl2() {
+            Self::send_windows_notification(title, message, task_url, group).await;
+        } else if cfg!(target_os = "windows") {
+            Self::send_windows_toast(title, message, task_url).await;
         }
     }
 
-    /// Send macOS notification using osascript
-    async fn send_macos_notification(title: &str, message: &str) {
-        let script = format!(
-            r#"display notification "{message}" with title "{title}" sound name "Glass""#,
-            message = message.replace('"', r#"\""#),
-            title = title.replace('"', r#"\""#)
-        );
+    /// Send macOS notification using terminal-notifier when installed, otherwise osascript
+    ///
+    /// `display notification` banners can't be grouped or run anything when clicked, so
+    /// osascript notifications with a task link use an alert with an Open button that
+    /// times out like a banner.
+    async fn send_macos_notification(
+        title: &str,
+        message: &str,
+        task_url: Option<&str>,
+        group: Option<&str>,
+    ) {
+        if let Some(notifier) = utils::shell::resolve_executable_path("terminal-notifier").await {
+            let mut command = tokio::process::Command::new(notifier);
+            command
+                .arg("-title")
+                .arg(title)
+                .arg("-message")
+                .arg(message)
+                .arg("-sound")
+                .arg("Glass");
+            if let Some(group) = group {
+                command.arg("-group").arg(format!("vibe-kanban-{group}"));
+            }
+            if let Some(url) = task_url {
+                command.arg("-open").arg(url);
+            }
+            let _ = command.spawn();
+            return;
+        }
+
+        let escape = |s: &str| s.replace('\\', r"\\").replace('"', r#"\""#);
+        let script = match task_url {
+            Some(url) => format!(
+                r#"set answer to display alert "{title}" message "{message}" buttons {{"Dismiss", "Open"}} default button "Open" giving up after 10
+if button returned of answer is "Open" then open location "{url}""#,
+                title = escape(title),
+                message = escape(message),
+                url = escape(url)
+            ),
+            None => format!(
+                r#"display notification "{message}" with title "{title}" sound name "Glass""#,
+                message = escape(message),
+                title = escape(title)
+            ),
+        };
 
         let _ = tokio::process::Command::new("osascript")
             .arg("-e")
@@ -148,27 +1342,109 @@ impl NotificationService {
     }
 
     /// Send Linux notification using notify-rust
-    async fn send_linux_notification(title: &str, message: &str) {
-        use notify_rust::Notification;
+    #[cfg(all(unix, not(target_os = "macos")))]
+    async fn send_linux_notification(
+        title: &str,
+        message: &str,
+        task_url: Option<&str>,
+        group: Option<&str>,
+    ) {
+        use notify_rust::{Hint, Notification};
 
         let title = title.to_string();
         let message = message.to_string();
+        let task_url = task_url.map(str::to_string);
+        let group = group.map(|group| format!("vibe-kanban-{group}"));
+        let runtime = tokio::runtime::Handle::current();
 
         let _handle = tokio::task::spawn_blocking(move || {
-            if let Err(e) = Notification::new()
-                .summary(&title)
-                .body(&message)
-                .timeout(10000)
-                .show()
-            {
-                tracing::error!("Failed to send Linux notification: {}", e);
+            let mut notification = Notification::new();
+            notification.summary(&title).body(&message).timeout(10000);
+            if let Some(group) = group {
+                // Servers that support stack tags (e.g. dunst) replace earlier notifications
+                notification.hint(Hint::Custom("x-dunst-stack-tag".to_string(), group));
+            }
+            if task_url.is_some() {
+                // "default" is the action servers invoke when the notification body is clicked
+                notification.action("default", "Open");
+            }
+            let handle = match notification.show() {
+                Ok(handle) => handle,
+                Err(e) => {
+                    tracing::error!("Failed to send Linux notification: {}", e);
+                    return;
+                }
+            };
+            if let Some(url) = task_url {
+                handle.wait_for_action(|action| {
+                    if action == "default" {
+                        runtime.spawn(async move {
+                            if let Err(e) = utils::browser::open_browser(&url).await {
+                                tracing::warn!("Failed to open task URL {}: {}", url, e);
+                            }
+                        });
+                    }
+                });
+            }
+        });
+        drop(_handle); // Don't await, fire-and-forget
+    }
+
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    async fn send_linux_notification(
+        _title: &str,
+        _message: &str,
+        _task_url: Option<&str>,
+        _group: Option<&str>,
+    ) {
+    }
+
+    /// Send Windows notification through the WinRT toast API
+    ///
+    /// The toast builder doesn't expose tag/group, so these toasts aren't grouped by project.
+    #[cfg(windows)]
+    async fn send_windows_toast(title: &str, message: &str, task_url: Option<&str>) {
+        use tauri_winrt_notification::{Duration, Toast};
+
+        let title = title.to_string();
+        let message = message.to_string();
+        let task_url = task_url.map(str::to_string);
+        let runtime = tokio::runtime::Handle::current();
+
+        let _handle = tokio::task::spawn_blocking(move || {
+            // Toasts from unregistered app IDs are dropped, so borrow PowerShell's
+            let mut toast = Toast::new(Toast::POWERSHELL_APP_ID)
+                .title(&title)
+                .text1(&message)
+                .duration(Duration::Short);
+            if let Some(url) = task_url {
+                toast = toast.on_activated(move |_| {
+                    let url = url.clone();
+                    runtime.spawn(async move {
+                        if let Err(e) = utils::browser::open_browser(&url).await {
+                            tracing::warn!("Failed to open task URL {}: {}", url, e);
+                        }
+                    });
+                    Ok(())
+                });
+            }
+            if let Err(e) = toast.show() {
+                tracing::error!("Failed to send Windows notification: {}", e);
             }
         });
         drop(_handle); // Don't await, fire-and-forget
     }
 
-    /// Send Windows/WSL notification using PowerShell toast script
-    async fn send_windows_notification(title: &str, message: &str) {
+    #[cfg(not(windows))]
+    async fn send_windows_toast(_title: &str, _message: &str, _task_url: Option<&str>) {}
+
+    /// Send WSL2 notification through the Windows host's PowerShell toast script
+    async fn send_windows_notification(
+        title: &str,
+        message: &str,
+        task_url: Option<&str>,
+        group: Option<&str>,
+    ) {
         let script_path = match utils::get_powershell_script().await {
             Ok(path) => path,
             Err(e) => {
@@ -198,98 +1474,12 @@ impl NotificationService {
             .arg(title)
             .arg("-Message")
             .arg(message)
+            .args(task_url.map(|url| ["-Url", url]).into_iter().flatten())
+            .args(group.map(|group| ["-Group", group]).into_iter().flatten())
             .spawn();
     }
 
-    /// Send Slack notification using incoming webhook
-    async fn send_slack_notification(webhook_url: String, title: &str, message: &str) {
-        fn escape_mrkdwn(s: &str) -> String {
-            s.replace('\\', r"\\")
-                .replace('*', r"\*")
-                .replace('_', r"\_")
-                .replace('~', r"\~")
-                .replace('`', r"\`")
-        }
-
-        fn extract_url(s: &str) -> Option<String> {
-            let s = s.trim();
-
-            // Slack-style: <url|text> or <url>
-            if let Some(stripped) = s.strip_prefix('<')
-                && let Some(close) = stripped.find('>')
-            {
-                let inner = &stripped[..close];
-                let url = inner.split('|').next().unwrap_or("").trim();
-                if url.starts_with("http://") || url.starts_with("https://") {
-                    return Some(url.to_string());
-                }
-            }
-
-            // Markdown-style: [text](url)
-            if let Some(open) = s.find("](")
-                && s.starts_with('[')
-                && s.ends_with(')')
-            {
-                let url = &s[open + 2..s.len() - 1];
-                let url = url.trim();
-                if url.starts_with("http://") || url.starts_with("https://") {
-                    return Some(url.to_string());
-                }
-            }
-
-            // Raw URL token
-            for token in s.split_whitespace() {
-                if token.starts_with("http://") || token.starts_with("https://") {
-                    return Some(token.trim_matches(|c: char| c == ')' || c == ']').to_string());
-                }
-            }
-
-            None
-        }
-
-        fn format_slack_message(message: &str) -> String {
-            let mut out = Vec::new();
-            for line in message.lines() {
-                let trimmed = line.trim();
-                if let Some(rest) = trimmed.strip_prefix("点击查看:") {
-                    if let Some(url) = extract_url(rest) {
-                        out.push(format!("<{url}|点击查看>"));
-                        continue;
-                    }
-                }
-                out.push(trimmed.to_string());
-            }
-            out.join("\n")
-        }
-
-        let title = escape_mrkdwn(title);
-        let message = format_slack_message(message);
-        let text = format!("*{title}*\n{message}");
-
-        tokio::spawn(async move {
-            let client = reqwest::Client::new();
-            let response = client
-                .post(&webhook_url)
-                .json(&json!({ "text": text, "mrkdwn": true }))
-                .send()
-                .await;
-
-            match response {
-                Ok(resp) if resp.status().is_success() => {}
-                Ok(resp) => {
-                    tracing::error!(
-                        "Slack notification failed with status: {}",
-                        resp.status()
-                    );
-                }
-                Err(err) => {
-                    tracing::error!("Failed to send Slack notification: {}", err);
-                }
-            }
-        });
-    }
-
-    async fn resolve_kanban_base_url() -> Option<String> {
+    async fn resolve_kanban_base_url(&self) -> Option<String> {
         fn normalize(s: String) -> Option<String> {
             let trimmed = s.trim().trim_end_matches('/').trim();
             if trimmed.is_empty() {
@@ -299,6 +1489,11 @@ impl NotificationService {
             }
         }
 
+        let configured = self.config.read().await.server.public_base_url.clone();
+        if let Some(url) = configured.and_then(normalize) {
+            return Some(url);
+        }
+
         if let Ok(url) = std::env::var("SERVER_PUBLIC_BASE_URL")
             && let Some(url) = normalize(url)
         {
@@ -369,7 +1564,51 @@ impl NotificationService {
         None
     }
 
-    /// Convert WSL path to Windows UNC path for PowerShell
+    /// Ask `wslpath` for the Windows form of `wsl_path`, converting and caching its directory
+    /// so files in the same directory don't need another process
+    async fn wslpath_windows_path(wsl_path: &std::path::Path) -> Option<String> {
+        let (dir, file_name) = (wsl_path.parent()?, wsl_path.file_name()?);
+        let cache = WSLPATH_DIR_CACHE.get_or_init(Default::default);
+        let cached = cache.lock().ok()?.get(dir).cloned();
+
+        let windows_dir = match cached {
+            Some(windows_dir) => windows_dir,
+            None => {
+                let output = tokio::process::Command::new("wslpath")
+                    .arg("-w")
+                    .arg(dir)
+                    .output()
+                    .await
+                    .inspect_err(|e| tracing::debug!("Failed to run wslpath: {}", e))
+                    .ok()?;
+                if !output.status.success() {
+                    tracing::debug!(
+                        "wslpath could not convert {}: {}",
+                        dir.display(),
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    );
+                    return None;
+                }
+                let windows_dir = String::from_utf8(output.stdout).ok()?.trim().to_string();
+                if windows_dir.is_empty() {
+                    return None;
+                }
+                cache
+                    .lock()
+                    .ok()?
+                    .insert(dir.to_path_buf(), windows_dir.clone());
+                windows_dir
+            }
+        };
+        Some(format!(
+            "{}\\{}",
+            windows_dir.trim_end_matches('\\'),
+            file_name.to_string_lossy()
+        ))
+    }
+
+    /// Convert WSL path to a Windows path for PowerShell, using `wslpath` when available and
+    /// the PowerShell working directory as a fallback
     async fn wsl_to_windows_path(wsl_path: &std::path::Path) -> Option<String> {
         let path_str = wsl_path.to_string_lossy();
 
@@ -379,6 +1618,11 @@ impl NotificationService {
             return Some(path_str.to_string());
         }
 
+        if let Some(windows_path) = Self::wslpath_windows_path(wsl_path).await {
+            tracing::debug!("WSL path converted: {} -> {}", path_str, windows_path);
+            return Some(windows_path);
+        }
+
         // Get cached WSL root path from PowerShell
         if let Some(wsl_root) = Self::get_wsl_root_path().await {
             // Simply concatenate WSL root with the absolute path - PowerShell doesn't mind /
@@ -394,3 +1638,84 @@ impl NotificationService {
         }
     }
 }
+
+/// Single-quoted PowerShell string literal, in which only `'` needs escaping (by doubling)
+fn powershell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Network errors, timeouts, rate limiting and server errors are worth retrying
+fn is_transient(err: &reqwest::Error) -> bool {
+    if err.is_timeout() || err.is_connect() {
+        return true;
+    }
+    err.status().is_some_and(|status| {
+        status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+    })
+}
+
+/// Send a request, retrying transient failures, and treat non-2xx responses as failures.
+///
+/// The URL is stripped from errors since webhook URLs usually embed credentials.
+async fn send_checked(
+    request: reqwest::RequestBuilder,
+    policy: &NotificationRetryPolicy,
+) -> Result<(), NotificationError> {
+    send_with_retry(request, policy).await.map(|_| ())
+}
+
+/// Like [`send_checked`], but hands back the successful response for callers that need the body
+async fn send_with_retry(
+    request: reqwest::RequestBuilder,
+    policy: &NotificationRetryPolicy,
+) -> Result<reqwest::Response, NotificationError> {
+    let mut backoff = ExponentialBuilder::default()
+        .with_min_delay(std::time::Duration::from_millis(
+            policy.initial_delay_ms.into(),
+        ))
+        .with_max_delay(std::time::Duration::from_millis(policy.max_delay_ms.into()))
+        .with_max_times(policy.max_attempts.saturating_sub(1) as usize);
+    if policy.jitter {
+        backoff = backoff.with_jitter();
+    }
+
+    let mut attempts = 1;
+    let result = (|| async {
+        request
+            .try_clone()
+            .expect("notification requests have buffered bodies")
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+    })
+    .retry(backoff)
+    .when(is_transient)
+    .notify(|err, dur| {
+        attempts += 1;
+        tracing::warn!(
+            "Notification request failed, retrying after {:.2}s: {}",
+            dur.as_secs_f64(),
+            err.without_url()
+        );
+    })
+    .await;
+
+    match result {
+        Ok(response) => Ok(response),
+        // A transient error at this point means every attempt was used up
+        Err(err) if is_transient(&err) => Err(NotificationError::RetriesExhausted {
+            attempts,
+            message: err.without_url().to_string(),
+        }),
+        Err(err) => Err(err.without_url().into()),
+    }
+}
+
+/// Trimmed value of an optional config string, treating blank values as unset
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
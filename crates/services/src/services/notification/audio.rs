@@ -0,0 +1,33 @@
+use std::{fs::File, io::BufReader, path::Path};
+
+use rodio::{Decoder, OutputStream, Sink, Source};
+use thiserror::Error;
+
+use crate::services::config::SoundPlayback;
+
+#[derive(Debug, Error)]
+pub enum AudioError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Stream(#[from] rodio::StreamError),
+    #[error(transparent)]
+    Play(#[from] rodio::PlayError),
+    #[error(transparent)]
+    Decode(#[from] rodio::decoder::DecoderError),
+}
+
+/// Play a sound on the default output device, blocking until it finishes
+pub fn play_blocking(path: &Path, playback: &SoundPlayback) -> Result<(), AudioError> {
+    // The stream must stay alive for as long as the sink is playing
+    let (_stream, handle) = OutputStream::try_default()?;
+    let sink = Sink::try_new(&handle)?;
+    sink.set_volume(playback.gain());
+    let source = Decoder::new(BufReader::new(File::open(path)?))?;
+    match playback.max_duration() {
+        Some(max_duration) => sink.append(source.take_duration(max_duration)),
+        None => sink.append(source),
+    }
+    sink.sleep_until_end();
+    Ok(())
+}
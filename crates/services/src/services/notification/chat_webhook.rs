@@ -0,0 +1,149 @@
+use serde_json::{Value, json};
+
+use super::{NotificationError, send_checked};
+use crate::services::config::{NotificationEvent, NotificationRetryPolicy};
+
+/// Display name used for posts when the server allows overriding it
+const BOT_NAME: &str = "Vibe Kanban";
+
+/// Slack-like incoming webhooks whose payloads differ from Slack's in the details
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatWebhookFlavor {
+    Mattermost,
+    RocketChat,
+}
+
+/// Incoming webhook plus the optional per-post overrides both servers support
+#[derive(Debug, Clone)]
+pub struct ChatWebhookTarget {
+    pub flavor: ChatWebhookFlavor,
+    pub webhook_url: String,
+    /// Channel to post in instead of the webhook's default
+    pub channel: Option<String>,
+    pub icon_url: Option<String>,
+}
+
+/// Attachment side bar colour for the event
+fn color(event: NotificationEvent) -> &'static str {
+    match event {
+        NotificationEvent::AttemptFailed | NotificationEvent::MergeConflict => "#d9534f",
+        NotificationEvent::ApprovalNeeded
+        | NotificationEvent::TaskOverdue
+        | NotificationEvent::WipLimitExceeded
+        | NotificationEvent::BudgetLimitReached
+        | NotificationEvent::AttemptStalled => "#f0ad4e",
+        NotificationEvent::AttemptCompleted
+        | NotificationEvent::PrMerged
+        | NotificationEvent::EpicCompleted => "#5cb85c",
+        NotificationEvent::TaskStarted
+        | NotificationEvent::PrOpened
+        | NotificationEvent::Summary
+        | NotificationEvent::TaskUnblocked => "#5bc0de",
+    }
+}
+
+fn build_payload(
+    target: &ChatWebhookTarget,
+    event: NotificationEvent,
+    title: &str,
+    message: &str,
+    task_url: Option<&str>,
+) -> Value {
+    let mut attachment = json!({
+        "fallback": format!("{title}\n{message}"),
+        "color": color(event),
+        "title": title,
+        "text": message,
+    });
+    if let Some(url) = task_url {
+        attachment["title_link"] = json!(url);
+    }
+
+    let mut payload = json!({ "attachments": [attachment] });
+    match target.flavor {
+        // Mattermost drops attachment-only posts from push notifications unless `text` is set
+        ChatWebhookFlavor::Mattermost => {
+            payload["text"] = json!(format!("**{title}**"));
+            payload["username"] = json!(BOT_NAME);
+            if let Some(icon_url) = &target.icon_url {
+                payload["icon_url"] = json!(icon_url);
+            }
+        }
+        // Rocket.Chat calls the display name `alias` and the icon `avatar`, and ignores
+        // `fallback`, so the title goes in `text` for notifications
+        ChatWebhookFlavor::RocketChat => {
+            payload["text"] = json!(title);
+            payload["alias"] = json!(BOT_NAME);
+            if let Some(icon_url) = &target.icon_url {
+                payload["avatar"] = json!(icon_url);
+            }
+        }
+    }
+    if let Some(channel) = &target.channel {
+        payload["channel"] = json!(channel);
+    }
+    payload
+}
+
+pub async fn send_chat_webhook(
+    target: &ChatWebhookTarget,
+    event: NotificationEvent,
+    title: &str,
+    message: &str,
+    task_url: Option<&str>,
+    retry: &NotificationRetryPolicy,
+) -> Result<(), NotificationError> {
+    let payload = build_payload(target, event, title, message, task_url);
+    let client = reqwest::Client::new();
+    send_checked(client.post(&target.webhook_url).json(&payload), retry).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(flavor: ChatWebhookFlavor) -> ChatWebhookTarget {
+        ChatWebhookTarget {
+            flavor,
+            webhook_url: "https://chat.example.com/hooks/abc".to_string(),
+            channel: Some("town-square".to_string()),
+            icon_url: Some("https://example.com/icon.png".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_mattermost_payload() {
+        let payload = build_payload(
+            &target(ChatWebhookFlavor::Mattermost),
+            NotificationEvent::AttemptFailed,
+            "Fix login",
+            "Tests failed",
+            Some("http://localhost:3000/t/1"),
+        );
+        assert_eq!(payload["channel"], "town-square");
+        assert_eq!(payload["icon_url"], "https://example.com/icon.png");
+        assert_eq!(payload["username"], BOT_NAME);
+        assert_eq!(payload["attachments"][0]["color"], "#d9534f");
+        assert_eq!(
+            payload["attachments"][0]["title_link"],
+            "http://localhost:3000/t/1"
+        );
+        assert!(payload.get("avatar").is_none());
+    }
+
+    #[test]
+    fn test_rocketchat_payload() {
+        let payload = build_payload(
+            &target(ChatWebhookFlavor::RocketChat),
+            NotificationEvent::AttemptCompleted,
+            "Fix login",
+            "Done",
+            None,
+        );
+        assert_eq!(payload["text"], "Fix login");
+        assert_eq!(payload["alias"], BOT_NAME);
+        assert_eq!(payload["avatar"], "https://example.com/icon.png");
+        assert!(payload.get("icon_url").is_none());
+        assert!(payload["attachments"][0].get("title_link").is_none());
+    }
+}
@@ -0,0 +1,124 @@
+use std::{collections::HashMap, sync::Arc};
+
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use super::{digest::PendingNotification, i18n};
+use crate::services::config::{NotificationChannel, NotificationEvent};
+
+/// Notifications with the same key within a window count as repeats
+pub type DedupKey = (NotificationEvent, Option<Uuid>, NotificationChannel);
+
+/// An open suppression window
+#[derive(Debug, Clone)]
+pub struct DedupEntry {
+    /// Repeats dropped since the window opened
+    pub suppressed: u32,
+    /// Most recent repeat, used for the follow-up in count mode
+    pub latest: Option<PendingNotification>,
+}
+
+/// Open suppression windows, closed by a timer started when each one opens
+#[derive(Debug, Clone, Default)]
+pub struct DedupFilter {
+    windows: Arc<Mutex<HashMap<DedupKey, DedupEntry>>>,
+}
+
+impl DedupFilter {
+    /// Returns `true` when the notification should go out, opening a window for its key.
+    /// Repeats inside an open window are counted and return `false`.
+    pub async fn admit(&self, key: DedupKey, pending: &PendingNotification) -> bool {
+        let mut windows = self.windows.lock().await;
+        match windows.get_mut(&key) {
+            Some(entry) => {
+                entry.suppressed += 1;
+                entry.latest = Some(pending.clone());
+                false
+            }
+            None => {
+                windows.insert(
+                    key,
+                    DedupEntry {
+                        suppressed: 0,
+                        latest: None,
+                    },
+                );
+                true
+            }
+        }
+    }
+
+    pub async fn close(&self, key: &DedupKey) -> Option<DedupEntry> {
+        self.windows.lock().await.remove(key)
+    }
+}
+
+/// Follow-up for a closed window that swallowed repeats
+pub fn summarize(entry: DedupEntry) -> Option<PendingNotification> {
+    let mut latest = entry.latest?;
+    let repeated = if entry.suppressed == 1 {
+        i18n::t(latest.context.locale, "repeated-once", &[])
+    } else {
+        i18n::t(
+            latest.context.locale,
+            "repeated",
+            &[("count", entry.suppressed.to_string().as_str())],
+        )
+    };
+    latest.message = format!("{}\n\n{repeated}", latest.message);
+    Some(latest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::notification::NotificationContext;
+
+    fn pending(message: &str) -> PendingNotification {
+        PendingNotification {
+            context: NotificationContext::new(NotificationEvent::AttemptFailed),
+            title: "Fix login".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeats_are_counted_until_closed() {
+        let filter = DedupFilter::default();
+        let key = (
+            NotificationEvent::AttemptFailed,
+            Some(Uuid::new_v4()),
+            NotificationChannel::Slack,
+        );
+
+        assert!(filter.admit(key, &pending("first")).await);
+        assert!(!filter.admit(key, &pending("second")).await);
+        assert!(!filter.admit(key, &pending("third")).await);
+        // Other channels have their own windows
+        assert!(
+            filter
+                .admit(
+                    (key.0, key.1, NotificationChannel::Sound),
+                    &pending("first")
+                )
+                .await
+        );
+
+        let entry = filter.close(&key).await.unwrap();
+        assert_eq!(entry.suppressed, 2);
+        assert_eq!(
+            summarize(entry).unwrap().message,
+            "third\n\nRepeated 2 times since the last notification"
+        );
+        assert!(filter.admit(key, &pending("fourth")).await);
+    }
+
+    #[test]
+    fn test_summarize_without_repeats() {
+        let entry = DedupEntry {
+            suppressed: 0,
+            latest: None,
+        };
+        assert!(summarize(entry).is_none());
+    }
+}
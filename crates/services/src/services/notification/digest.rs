@@ -0,0 +1,148 @@
+use std::{collections::HashMap, sync::Arc};
+
+use tokio::sync::Mutex;
+
+use super::{NotificationContext, i18n};
+use crate::services::config::NotificationChannel;
+
+/// A notification held back until its channel's digest window closes
+#[derive(Debug, Clone)]
+pub struct PendingNotification {
+    pub context: NotificationContext,
+    pub title: String,
+    pub message: String,
+}
+
+/// Per-channel queues of notifications waiting to be coalesced
+#[derive(Debug, Clone, Default)]
+pub struct DigestBuffer {
+    pending: Arc<Mutex<HashMap<NotificationChannel, Vec<PendingNotification>>>>,
+}
+
+impl DigestBuffer {
+    /// Queue a notification, returning `true` when it opened a new window for the channel
+    pub async fn push(&self, channel: NotificationChannel, item: PendingNotification) -> bool {
+        let mut pending = self.pending.lock().await;
+        let queue = pending.entry(channel).or_default();
+        queue.push(item);
+        queue.len() == 1
+    }
+
+    pub async fn take(&self, channel: NotificationChannel) -> Vec<PendingNotification> {
+        self.pending
+            .lock()
+            .await
+            .remove(&channel)
+            .unwrap_or_default()
+    }
+}
+
+/// Fold queued notifications into a single one.
+///
+/// Task and project details are kept only when every item shares them.
+pub fn summarize(mut items: Vec<PendingNotification>) -> Option<PendingNotification> {
+    if items.len() <= 1 {
+        return items.pop();
+    }
+
+    let first = &items[0].context;
+    let same_project = items
+        .iter()
+        .all(|item| item.context.project_id == first.project_id);
+    let same_task = same_project
+        && items
+            .iter()
+            .all(|item| item.context.task_id == first.task_id);
+
+    let mut context = NotificationContext::new(first.event);
+    context.locale = first.locale;
+    if same_project {
+        context.project_id = first.project_id;
+        context.project_name = first.project_name.clone();
+    }
+    if same_task {
+        context.task_id = first.task_id;
+        context.task_title = first.task_title.clone();
+        context.task_url = first.task_url.clone();
+        if items
+            .iter()
+            .all(|item| item.context.workspace_id == first.workspace_id)
+        {
+            context.workspace_id = first.workspace_id;
+        }
+    }
+
+    let count = i18n::t(
+        context.locale,
+        "digest-title",
+        &[("count", items.len().to_string().as_str())],
+    );
+    let title = match &context.project_name {
+        Some(project_name) => format!("[{project_name}]: {count}"),
+        None => count,
+    };
+    let message = items
+        .iter()
+        .map(|item| match item.message.lines().next() {
+            Some(line) if !line.trim().is_empty() => format!("• {} — {}", item.title, line.trim()),
+            _ => format!("• {}", item.title),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Some(PendingNotification {
+        context,
+        title,
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::services::config::NotificationEvent;
+
+    fn item(project_id: Uuid, task_id: Uuid, title: &str) -> PendingNotification {
+        let context = NotificationContext::new(NotificationEvent::AttemptCompleted)
+            .with_task(project_id, task_id)
+            .with_names("Kanban", title);
+        PendingNotification {
+            context,
+            title: title.to_string(),
+            message: "Status: done\nmore details".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_summarize_single_item_is_unchanged() {
+        let single = item(Uuid::new_v4(), Uuid::new_v4(), "Fix login");
+        let summary = summarize(vec![single.clone()]).unwrap();
+        assert_eq!(summary.title, single.title);
+        assert_eq!(summary.message, single.message);
+    }
+
+    #[test]
+    fn test_summarize_keeps_shared_project_only() {
+        let project_id = Uuid::new_v4();
+        let summary = summarize(vec![
+            item(project_id, Uuid::new_v4(), "Fix login"),
+            item(project_id, Uuid::new_v4(), "Add search"),
+        ])
+        .unwrap();
+
+        assert_eq!(summary.title, "[Kanban]: 2 notifications");
+        assert_eq!(
+            summary.message,
+            "• Fix login — Status: done\n• Add search — Status: done"
+        );
+        assert_eq!(summary.context.project_id, Some(project_id));
+        assert_eq!(summary.context.task_id, None);
+    }
+
+    #[test]
+    fn test_summarize_empty() {
+        assert!(summarize(Vec::new()).is_none());
+    }
+}
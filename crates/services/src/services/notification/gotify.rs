@@ -0,0 +1,113 @@
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use super::{NotificationError, send_checked};
+use crate::services::config::{NotificationEvent, NotificationPriority, NotificationRetryPolicy};
+
+/// Server and application token for publishing Gotify messages
+#[derive(Debug, Clone)]
+pub struct GotifyTarget {
+    pub server_url: String,
+    pub app_token: String,
+}
+
+/// Message body, see https://gotify.net/api-docs#/message/createMessage
+#[derive(Debug, Serialize)]
+struct GotifyMessage<'a> {
+    title: &'a str,
+    message: &'a str,
+    priority: u8,
+    extras: Value,
+}
+
+/// Gotify clients treat 8 and above as high priority and 1-3 as low.
+///
+/// A rule's urgency wins over the event's default.
+fn priority(event: NotificationEvent, rule_priority: Option<NotificationPriority>) -> u8 {
+    if let Some(rule_priority) = rule_priority {
+        return match rule_priority {
+            NotificationPriority::Low => 3,
+            NotificationPriority::Normal => 5,
+            NotificationPriority::High => 8,
+            NotificationPriority::Urgent => 10,
+        };
+    }
+    match event {
+        NotificationEvent::AttemptFailed | NotificationEvent::MergeConflict => 8,
+        NotificationEvent::ApprovalNeeded
+        | NotificationEvent::TaskOverdue
+        | NotificationEvent::WipLimitExceeded
+        | NotificationEvent::BudgetLimitReached
+        | NotificationEvent::AttemptStalled => 7,
+        NotificationEvent::AttemptCompleted
+        | NotificationEvent::PrMerged
+        | NotificationEvent::EpicCompleted => 5,
+        NotificationEvent::TaskStarted
+        | NotificationEvent::PrOpened
+        | NotificationEvent::Summary
+        | NotificationEvent::TaskUnblocked => 3,
+    }
+}
+
+fn extras(click: Option<&str>) -> Value {
+    let mut extras = json!({
+        "client::display": { "contentType": "text/plain" },
+    });
+    if let Some(url) = click {
+        extras["client::notification"] = json!({ "click": { "url": url } });
+    }
+    extras
+}
+
+pub async fn send_gotify(
+    target: &GotifyTarget,
+    event: NotificationEvent,
+    rule_priority: Option<NotificationPriority>,
+    title: &str,
+    message: &str,
+    click: Option<&str>,
+    retry: &NotificationRetryPolicy,
+) -> Result<(), NotificationError> {
+    let body = GotifyMessage {
+        title,
+        message,
+        priority: priority(event, rule_priority),
+        extras: extras(click),
+    };
+
+    let url = format!("{}/message", target.server_url.trim_end_matches('/'));
+    let client = reqwest::Client::new();
+    let request = client
+        .post(url)
+        .header("X-Gotify-Key", &target.app_token)
+        .json(&body);
+    send_checked(request, retry).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failures_are_high_priority() {
+        assert!(priority(NotificationEvent::AttemptFailed, None) >= 8);
+        assert!(priority(NotificationEvent::TaskStarted, None) <= 3);
+        assert_eq!(
+            priority(
+                NotificationEvent::TaskStarted,
+                Some(NotificationPriority::Urgent)
+            ),
+            10
+        );
+    }
+
+    #[test]
+    fn test_extras_include_click_url() {
+        let extras = extras(Some("http://localhost:3000/t/1"));
+        assert_eq!(
+            extras["client::notification"]["click"]["url"],
+            "http://localhost:3000/t/1"
+        );
+        assert!(super::extras(None).get("client::notification").is_none());
+    }
+}
@@ -0,0 +1,704 @@
+use std::collections::HashMap;
+
+use super::template;
+use crate::services::config::{NotificationEvent, UiLanguage};
+
+/// Language notification text is rendered in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    En,
+    Ja,
+    Es,
+    Ko,
+    ZhHans,
+}
+
+impl Locale {
+    /// Locale for the configured UI language; `Browser` falls back to the system locale since
+    /// notifications are rendered without a browser
+    pub fn resolve(language: &UiLanguage) -> Self {
+        match language {
+            UiLanguage::Browser => Self::system(),
+            UiLanguage::En => Self::En,
+            UiLanguage::Ja => Self::Ja,
+            UiLanguage::Es => Self::Es,
+            UiLanguage::Ko => Self::Ko,
+            UiLanguage::ZhHans => Self::ZhHans,
+        }
+    }
+
+    /// Parse POSIX (`ja_JP.UTF-8`) and BCP 47 (`zh-Hans`) tags by their language part
+    fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag.split(['_', '-', '.', '@']).next()?.to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(Self::En),
+            "ja" => Some(Self::Ja),
+            "es" => Some(Self::Es),
+            "ko" => Some(Self::Ko),
+            "zh" => Some(Self::ZhHans),
+            _ => None,
+        }
+    }
+
+    fn system() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::from_tag(&value))
+            .unwrap_or_default()
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::En => EN,
+            Self::Ja => JA,
+            Self::Es => ES,
+            Self::Ko => KO,
+            Self::ZhHans => ZH_HANS,
+        }
+    }
+}
+
+const EN: &[(&str, &str)] = &[
+    ("status-completed", "Status: ✅"),
+    ("status-failed", "Status: ❌"),
+    ("view-task", "View: {{url}}"),
+    ("task-started", "Started with {{executor}}"),
+    ("approval-needed-title", "Approval needed"),
+    ("approval-needed", "Tool '{{tool}}' requires approval"),
+    ("pr-opened-title", "PR #{{number}} opened"),
+    ("pr-merged-title", "PR #{{number}} merged"),
+    ("pr-merged", "'{{task}}' has been moved to done"),
+    ("task-unblocked-title", "Unblocked: {{task}}"),
+    ("task-unblocked", "Every task blocking '{{task}}' is done"),
+    (
+        "task-unblocked-started",
+        "Every task blocking '{{task}}' is done, so it has been started",
+    ),
+    (
+        "task-unblocked-queued",
+        "Every task blocking '{{task}}' is done, so it is queued to start",
+    ),
+    ("task-overdue-title", "Overdue: {{task}}"),
+    ("task-overdue", "'{{task}}' was due {{due}} and isn't done"),
+    ("wip-limit-title", "WIP limit reached: {{task}}"),
+    (
+        "wip-limit-held",
+        "'{{task}}' wasn't started automatically: {{limits}}",
+    ),
+    (
+        "wip-limit-overridden",
+        "'{{task}}' went over a WIP limit: {{limits}}",
+    ),
+    ("budget-limit-title", "Budget limit reached: {{scope}}"),
+    (
+        "budget-limit-soft",
+        "${{spent}} spent in {{month}}, past the ${{limit}} soft limit",
+    ),
+    (
+        "budget-limit-hard",
+        "${{spent}} spent in {{month}}, the ${{limit}} hard limit. New attempts are refused until next month or the limit is raised",
+    ),
+    ("budget-all-projects", "All projects"),
+    ("epic-completed-title", "Epic done: {{epic}}"),
+    (
+        "epic-completed",
+        "Every task in '{{epic}}' is done or cancelled",
+    ),
+    (
+        "epic-totals",
+        "Tasks: {{tasks}}, projects: {{projects}}, attempts: {{attempts}}, agent time: {{time}}",
+    ),
+    ("merge-conflict-title", "Merge conflict: {{task}}"),
+    (
+        "merge-conflict",
+        "Rebasing {{branch}} onto {{base}} hit conflicts in {{repo}}",
+    ),
+    ("attempt-stalled-title", "Agent stalled: {{task}}"),
+    (
+        "attempt-stalled",
+        "No output from the agent for {{minutes}} minutes",
+    ),
+    (
+        "attempt-stalled-continued",
+        "No output from the agent for {{minutes}} minutes; it was asked to continue",
+    ),
+    (
+        "attempt-stalled-killed",
+        "No output from the agent for {{minutes}} minutes; it was stopped",
+    ),
+    ("attempt-timed-out-title", "Agent timed out: {{task}}"),
+    (
+        "attempt-timed-out",
+        "The agent ran past the {{minutes}}-minute limit and was stopped",
+    ),
+    ("test-title", "Test notification"),
+    ("test-message", "{{channel}} notifications are working."),
+    ("digest-title", "{{count}} notifications"),
+    ("repeated-once", "Repeated once since the last notification"),
+    (
+        "repeated",
+        "Repeated {{count}} times since the last notification",
+    ),
+    ("unacknowledged", "Unacknowledged: {{title}}"),
+    ("open-task", "Open in Vibe Kanban"),
+    ("field-status", "Status"),
+    ("field-duration", "Duration"),
+    ("field-project", "Project"),
+    ("event-task-started", "Started"),
+    ("event-attempt-completed", "Completed"),
+    ("event-attempt-failed", "Failed"),
+    ("event-approval-needed", "Approval needed"),
+    ("event-pr-opened", "PR opened"),
+    ("event-pr-merged", "PR merged"),
+    ("event-merge-conflict", "Merge conflict"),
+    ("event-summary", "Summary"),
+    ("event-task-unblocked", "Unblocked"),
+    ("event-task-overdue", "Overdue"),
+    ("event-wip-limit-exceeded", "WIP limit"),
+    ("event-epic-completed", "Epic done"),
+    ("event-budget-limit-reached", "Budget limit"),
+    ("event-attempt-stalled", "Stalled"),
+    ("summary-daily-title", "Daily summary"),
+    ("summary-weekly-title", "Weekly summary"),
+    ("summary-completed", "Completed: {{count}}"),
+    ("summary-failed", "Failed: {{count}}"),
+    ("summary-awaiting-review", "Awaiting review: {{count}}"),
+    ("summary-cost", "API spend: ${{cost}}"),
+];
+
+const JA: &[(&str, &str)] = &[
+    ("status-completed", "ステータス: ✅"),
+    ("status-failed", "ステータス: ❌"),
+    ("view-task", "表示: {{url}}"),
+    ("task-started", "{{executor}} で開始しました"),
+    ("approval-needed-title", "承認が必要です"),
+    (
+        "approval-needed",
+        "ツール「{{tool}}」の実行には承認が必要です",
+    ),
+    ("pr-opened-title", "PR #{{number}} を作成しました"),
+    ("pr-merged-title", "PR #{{number}} をマージしました"),
+    ("pr-merged", "「{{task}}」を完了に移動しました"),
+    ("task-unblocked-title", "ブロック解除: {{task}}"),
+    (
+        "task-unblocked",
+        "「{{task}}」をブロックしていたタスクはすべて完了しました",
+    ),
+    (
+        "task-unblocked-started",
+        "「{{task}}」をブロックしていたタスクがすべて完了したため、開始しました",
+    ),
+    (
+        "task-unblocked-queued",
+        "「{{task}}」をブロックしていたタスクがすべて完了したため、開始待ちのキューに入れました",
+    ),
+    ("task-overdue-title", "期限切れ: {{task}}"),
+    (
+        "task-overdue",
+        "「{{task}}」の期限（{{due}}）を過ぎましたが、まだ完了していません",
+    ),
+    ("wip-limit-title", "WIP 上限に到達: {{task}}"),
+    (
+        "wip-limit-held",
+        "「{{task}}」は自動で開始されませんでした: {{limits}}",
+    ),
+    (
+        "wip-limit-overridden",
+        "「{{task}}」が WIP 上限を超えました: {{limits}}",
+    ),
+    ("budget-limit-title", "予算上限に到達: {{scope}}"),
+    (
+        "budget-limit-soft",
+        "{{month}} の支出が ${{spent}} になり、ソフト上限 ${{limit}} を超えました",
+    ),
+    (
+        "budget-limit-hard",
+        "{{month}} の支出が ${{spent}} になり、ハード上限 ${{limit}} に達しました。来月になるか上限を引き上げるまで新しい試行は開始できません",
+    ),
+    ("budget-all-projects", "すべてのプロジェクト"),
+    ("epic-completed-title", "エピック完了: {{epic}}"),
+    (
+        "epic-completed",
+        "「{{epic}}」のすべてのタスクが完了またはキャンセルされました",
+    ),
+    (
+        "epic-totals",
+        "タスク: {{tasks}}、プロジェクト: {{projects}}、試行: {{attempts}}、エージェント時間: {{time}}",
+    ),
+    ("merge-conflict-title", "マージ競合: {{task}}"),
+    (
+        "merge-conflict",
+        "{{branch}} を {{base}} にリベース中、{{repo}} で競合が発生しました",
+    ),
+    ("attempt-stalled-title", "エージェントが停止: {{task}}"),
+    (
+        "attempt-stalled",
+        "エージェントの出力が {{minutes}} 分間ありません",
+    ),
+    (
+        "attempt-stalled-continued",
+        "エージェントの出力が {{minutes}} 分間なかったため、続行を指示しました",
+    ),
+    (
+        "attempt-stalled-killed",
+        "エージェントの出力が {{minutes}} 分間なかったため、停止しました",
+    ),
+    (
+        "attempt-timed-out-title",
+        "エージェントがタイムアウト: {{task}}",
+    ),
+    (
+        "attempt-timed-out",
+        "エージェントが {{minutes}} 分の制限を超えたため、停止しました",
+    ),
+    ("test-title", "テスト通知"),
+    ("test-message", "{{channel}} の通知は正常に動作しています。"),
+    ("digest-title", "{{count}} 件の通知"),
+    ("repeated-once", "前回の通知以降 1 回繰り返されました"),
+    ("repeated", "前回の通知以降 {{count}} 回繰り返されました"),
+    ("unacknowledged", "未確認: {{title}}"),
+    ("open-task", "Vibe Kanban で開く"),
+    ("field-status", "ステータス"),
+    ("field-duration", "所要時間"),
+    ("field-project", "プロジェクト"),
+    ("event-task-started", "開始"),
+    ("event-attempt-completed", "完了"),
+    ("event-attempt-failed", "失敗"),
+    ("event-approval-needed", "承認待ち"),
+    ("event-pr-opened", "PR 作成"),
+    ("event-pr-merged", "PR マージ"),
+    ("event-merge-conflict", "マージ競合"),
+    ("event-summary", "サマリー"),
+    ("event-task-unblocked", "ブロック解除"),
+    ("event-task-overdue", "期限切れ"),
+    ("event-wip-limit-exceeded", "WIP 上限"),
+    ("event-epic-completed", "エピック完了"),
+    ("event-budget-limit-reached", "予算上限"),
+    ("event-attempt-stalled", "停止"),
+    ("summary-daily-title", "デイリーサマリー"),
+    ("summary-weekly-title", "ウィークリーサマリー"),
+    ("summary-completed", "完了: {{count}}"),
+    ("summary-failed", "失敗: {{count}}"),
+    ("summary-awaiting-review", "レビュー待ち: {{count}}"),
+    ("summary-cost", "API 費用: ${{cost}}"),
+];
+
+const ES: &[(&str, &str)] = &[
+    ("status-completed", "Estado: ✅"),
+    ("status-failed", "Estado: ❌"),
+    ("view-task", "Ver: {{url}}"),
+    ("task-started", "Iniciada con {{executor}}"),
+    ("approval-needed-title", "Se requiere aprobación"),
+    (
+        "approval-needed",
+        "La herramienta '{{tool}}' requiere aprobación",
+    ),
+    ("pr-opened-title", "PR #{{number}} abierta"),
+    ("pr-merged-title", "PR #{{number}} fusionada"),
+    ("pr-merged", "'{{task}}' se ha movido a completada"),
+    ("task-unblocked-title", "Desbloqueada: {{task}}"),
+    (
+        "task-unblocked",
+        "Todas las tareas que bloqueaban '{{task}}' están completadas",
+    ),
+    (
+        "task-unblocked-started",
+        "Todas las tareas que bloqueaban '{{task}}' están completadas, así que se ha iniciado",
+    ),
+    (
+        "task-unblocked-queued",
+        "Todas las tareas que bloqueaban '{{task}}' están completadas, así que está en cola para iniciarse",
+    ),
+    ("task-overdue-title", "Vencida: {{task}}"),
+    (
+        "task-overdue",
+        "'{{task}}' vencía el {{due}} y no está completada",
+    ),
+    ("wip-limit-title", "Límite WIP alcanzado: {{task}}"),
+    (
+        "wip-limit-held",
+        "'{{task}}' no se inició automáticamente: {{limits}}",
+    ),
+    (
+        "wip-limit-overridden",
+        "'{{task}}' superó un límite WIP: {{limits}}",
+    ),
+    (
+        "budget-limit-title",
+        "Límite de presupuesto alcanzado: {{scope}}",
+    ),
+    (
+        "budget-limit-soft",
+        "${{spent}} gastados en {{month}}, por encima del límite suave de ${{limit}}",
+    ),
+    (
+        "budget-limit-hard",
+        "${{spent}} gastados en {{month}}, el límite estricto de ${{limit}}. No se iniciarán nuevos intentos hasta el próximo mes o hasta que se aumente el límite",
+    ),
+    ("budget-all-projects", "Todos los proyectos"),
+    ("epic-completed-title", "Épica terminada: {{epic}}"),
+    (
+        "epic-completed",
+        "Todas las tareas de '{{epic}}' están terminadas o canceladas",
+    ),
+    (
+        "epic-totals",
+        "Tareas: {{tasks}}, proyectos: {{projects}}, intentos: {{attempts}}, tiempo del agente: {{time}}",
+    ),
+    ("merge-conflict-title", "Conflicto de fusión: {{task}}"),
+    (
+        "merge-conflict",
+        "Al hacer rebase de {{branch}} sobre {{base}} hubo conflictos en {{repo}}",
+    ),
+    ("attempt-stalled-title", "Agente detenido: {{task}}"),
+    (
+        "attempt-stalled",
+        "El agente no ha producido salida en {{minutes}} minutos",
+    ),
+    (
+        "attempt-stalled-continued",
+        "El agente no produjo salida en {{minutes}} minutos; se le pidió que continuara",
+    ),
+    (
+        "attempt-stalled-killed",
+        "El agente no produjo salida en {{minutes}} minutos; se detuvo",
+    ),
+    (
+        "attempt-timed-out-title",
+        "Tiempo agotado del agente: {{task}}",
+    ),
+    (
+        "attempt-timed-out",
+        "El agente superó el límite de {{minutes}} minutos y se detuvo",
+    ),
+    ("test-title", "Notificación de prueba"),
+    (
+        "test-message",
+        "Las notificaciones de {{channel}} funcionan.",
+    ),
+    ("digest-title", "{{count}} notificaciones"),
+    (
+        "repeated-once",
+        "Repetida una vez desde la última notificación",
+    ),
+    (
+        "repeated",
+        "Repetida {{count}} veces desde la última notificación",
+    ),
+    ("unacknowledged", "Sin confirmar: {{title}}"),
+    ("open-task", "Abrir en Vibe Kanban"),
+    ("field-status", "Estado"),
+    ("field-duration", "Duración"),
+    ("field-project", "Proyecto"),
+    ("event-task-started", "Iniciada"),
+    ("event-attempt-completed", "Completada"),
+    ("event-attempt-failed", "Fallida"),
+    ("event-approval-needed", "Aprobación necesaria"),
+    ("event-pr-opened", "PR abierta"),
+    ("event-pr-merged", "PR fusionada"),
+    ("event-merge-conflict", "Conflicto de fusión"),
+    ("event-summary", "Resumen"),
+    ("event-task-unblocked", "Desbloqueada"),
+    ("event-task-overdue", "Vencida"),
+    ("event-wip-limit-exceeded", "Límite WIP"),
+    ("event-epic-completed", "Épica terminada"),
+    ("event-budget-limit-reached", "Límite de presupuesto"),
+    ("event-attempt-stalled", "Detenido"),
+    ("summary-daily-title", "Resumen diario"),
+    ("summary-weekly-title", "Resumen semanal"),
+    ("summary-completed", "Completadas: {{count}}"),
+    ("summary-failed", "Fallidas: {{count}}"),
+    (
+        "summary-awaiting-review",
+        "Pendientes de revisión: {{count}}",
+    ),
+    ("summary-cost", "Gasto en API: ${{cost}}"),
+];
+
+const KO: &[(&str, &str)] = &[
+    ("status-completed", "상태: ✅"),
+    ("status-failed", "상태: ❌"),
+    ("view-task", "보기: {{url}}"),
+    ("task-started", "{{executor}}(으)로 시작됨"),
+    ("approval-needed-title", "승인 필요"),
+    (
+        "approval-needed",
+        "'{{tool}}' 도구를 실행하려면 승인이 필요합니다",
+    ),
+    ("pr-opened-title", "PR #{{number}} 생성됨"),
+    ("pr-merged-title", "PR #{{number}} 병합됨"),
+    ("pr-merged", "'{{task}}'이(가) 완료로 이동되었습니다"),
+    ("task-unblocked-title", "차단 해제: {{task}}"),
+    (
+        "task-unblocked",
+        "'{{task}}'을(를) 막던 작업이 모두 완료되었습니다",
+    ),
+    (
+        "task-unblocked-started",
+        "'{{task}}'을(를) 막던 작업이 모두 완료되어 시작했습니다",
+    ),
+    (
+        "task-unblocked-queued",
+        "'{{task}}'을(를) 막던 작업이 모두 완료되어 시작 대기열에 넣었습니다",
+    ),
+    ("task-overdue-title", "기한 초과: {{task}}"),
+    (
+        "task-overdue",
+        "'{{task}}'의 기한({{due}})이 지났지만 완료되지 않았습니다",
+    ),
+    ("wip-limit-title", "WIP 한도 도달: {{task}}"),
+    (
+        "wip-limit-held",
+        "'{{task}}'이(가) 자동으로 시작되지 않았습니다: {{limits}}",
+    ),
+    (
+        "wip-limit-overridden",
+        "'{{task}}'이(가) WIP 한도를 초과했습니다: {{limits}}",
+    ),
+    ("budget-limit-title", "예산 한도 도달: {{scope}}"),
+    (
+        "budget-limit-soft",
+        "{{month}}에 ${{spent}}를 사용해 소프트 한도 ${{limit}}를 넘었습니다",
+    ),
+    (
+        "budget-limit-hard",
+        "{{month}}에 ${{spent}}를 사용해 하드 한도 ${{limit}}에 도달했습니다. 다음 달이 되거나 한도를 올릴 때까지 새 시도는 거부됩니다",
+    ),
+    ("budget-all-projects", "모든 프로젝트"),
+    ("epic-completed-title", "에픽 완료: {{epic}}"),
+    (
+        "epic-completed",
+        "'{{epic}}'의 모든 작업이 완료되거나 취소되었습니다",
+    ),
+    (
+        "epic-totals",
+        "작업: {{tasks}}, 프로젝트: {{projects}}, 시도: {{attempts}}, 에이전트 시간: {{time}}",
+    ),
+    ("merge-conflict-title", "병합 충돌: {{task}}"),
+    (
+        "merge-conflict",
+        "{{branch}}을(를) {{base}}에 리베이스하는 중 {{repo}}에서 충돌이 발생했습니다",
+    ),
+    ("attempt-stalled-title", "에이전트 멈춤: {{task}}"),
+    (
+        "attempt-stalled",
+        "에이전트 출력이 {{minutes}}분 동안 없습니다",
+    ),
+    (
+        "attempt-stalled-continued",
+        "에이전트 출력이 {{minutes}}분 동안 없어 계속하도록 요청했습니다",
+    ),
+    (
+        "attempt-stalled-killed",
+        "에이전트 출력이 {{minutes}}분 동안 없어 중지했습니다",
+    ),
+    ("attempt-timed-out-title", "에이전트 시간 초과: {{task}}"),
+    (
+        "attempt-timed-out",
+        "에이전트가 {{minutes}}분 제한을 넘겨 중지했습니다",
+    ),
+    ("test-title", "테스트 알림"),
+    ("test-message", "{{channel}} 알림이 정상적으로 작동합니다."),
+    ("digest-title", "알림 {{count}}개"),
+    ("repeated-once", "마지막 알림 이후 1회 반복됨"),
+    ("repeated", "마지막 알림 이후 {{count}}회 반복됨"),
+    ("unacknowledged", "미확인: {{title}}"),
+    ("open-task", "Vibe Kanban에서 열기"),
+    ("field-status", "상태"),
+    ("field-duration", "소요 시간"),
+    ("field-project", "프로젝트"),
+    ("event-task-started", "시작됨"),
+    ("event-attempt-completed", "완료됨"),
+    ("event-attempt-failed", "실패"),
+    ("event-approval-needed", "승인 필요"),
+    ("event-pr-opened", "PR 생성됨"),
+    ("event-pr-merged", "PR 병합됨"),
+    ("event-merge-conflict", "병합 충돌"),
+    ("event-summary", "요약"),
+    ("event-task-unblocked", "차단 해제"),
+    ("event-task-overdue", "기한 초과"),
+    ("event-wip-limit-exceeded", "WIP 한도"),
+    ("event-epic-completed", "에픽 완료"),
+    ("event-budget-limit-reached", "예산 한도"),
+    ("event-attempt-stalled", "멈춤"),
+    ("summary-daily-title", "일일 요약"),
+    ("summary-weekly-title", "주간 요약"),
+    ("summary-completed", "완료: {{count}}"),
+    ("summary-failed", "실패: {{count}}"),
+    ("summary-awaiting-review", "검토 대기: {{count}}"),
+    ("summary-cost", "API 비용: ${{cost}}"),
+];
+
+const ZH_HANS: &[(&str, &str)] = &[
+    ("status-completed", "状态: ✅"),
+    ("status-failed", "状态: ❌"),
+    ("view-task", "点击查看: {{url}}"),
+    ("task-started", "已使用 {{executor}} 启动"),
+    ("approval-needed-title", "需要审批"),
+    ("approval-needed", "工具“{{tool}}”需要审批"),
+    ("pr-opened-title", "PR #{{number}} 已创建"),
+    ("pr-merged-title", "PR #{{number}} 已合并"),
+    ("pr-merged", "“{{task}}”已移至完成"),
+    ("task-unblocked-title", "已解除阻塞：{{task}}"),
+    ("task-unblocked", "阻塞“{{task}}”的任务已全部完成"),
+    (
+        "task-unblocked-started",
+        "阻塞“{{task}}”的任务已全部完成，已自动开始",
+    ),
+    (
+        "task-unblocked-queued",
+        "阻塞“{{task}}”的任务已全部完成，已加入启动队列",
+    ),
+    ("task-overdue-title", "已逾期：{{task}}"),
+    ("task-overdue", "“{{task}}”已于 {{due}} 到期，但尚未完成"),
+    ("wip-limit-title", "已达 WIP 上限：{{task}}"),
+    ("wip-limit-held", "“{{task}}”未自动开始：{{limits}}"),
+    (
+        "wip-limit-overridden",
+        "“{{task}}”超出了 WIP 上限：{{limits}}",
+    ),
+    ("budget-limit-title", "已达预算上限：{{scope}}"),
+    (
+        "budget-limit-soft",
+        "{{month}} 已花费 ${{spent}}，超过软上限 ${{limit}}",
+    ),
+    (
+        "budget-limit-hard",
+        "{{month}} 已花费 ${{spent}}，达到硬上限 ${{limit}}。在下个月或提高上限之前，将拒绝新的尝试",
+    ),
+    ("budget-all-projects", "所有项目"),
+    ("epic-completed-title", "史诗已完成：{{epic}}"),
+    ("epic-completed", "“{{epic}}”中的所有任务均已完成或取消"),
+    (
+        "epic-totals",
+        "任务：{{tasks}}，项目：{{projects}}，尝试：{{attempts}}，代理时间：{{time}}",
+    ),
+    ("merge-conflict-title", "合并冲突: {{task}}"),
+    (
+        "merge-conflict",
+        "将 {{branch}} 变基到 {{base}} 时在 {{repo}} 中出现冲突",
+    ),
+    ("attempt-stalled-title", "智能体停滞：{{task}}"),
+    ("attempt-stalled", "智能体已 {{minutes}} 分钟没有输出"),
+    (
+        "attempt-stalled-continued",
+        "智能体 {{minutes}} 分钟没有输出，已要求其继续",
+    ),
+    (
+        "attempt-stalled-killed",
+        "智能体 {{minutes}} 分钟没有输出，已停止",
+    ),
+    ("attempt-timed-out-title", "智能体超时：{{task}}"),
+    (
+        "attempt-timed-out",
+        "智能体超过 {{minutes}} 分钟的限制，已停止",
+    ),
+    ("test-title", "测试通知"),
+    ("test-message", "{{channel}} 通知工作正常。"),
+    ("digest-title", "{{count}} 条通知"),
+    ("repeated-once", "自上次通知以来重复了 1 次"),
+    ("repeated", "自上次通知以来重复了 {{count}} 次"),
+    ("unacknowledged", "未确认: {{title}}"),
+    ("open-task", "在 Vibe Kanban 中打开"),
+    ("field-status", "状态"),
+    ("field-duration", "耗时"),
+    ("field-project", "项目"),
+    ("event-task-started", "已开始"),
+    ("event-attempt-completed", "已完成"),
+    ("event-attempt-failed", "失败"),
+    ("event-approval-needed", "需要审批"),
+    ("event-pr-opened", "PR 已创建"),
+    ("event-pr-merged", "PR 已合并"),
+    ("event-merge-conflict", "合并冲突"),
+    ("event-summary", "摘要"),
+    ("event-task-unblocked", "已解除阻塞"),
+    ("event-task-overdue", "已逾期"),
+    ("event-wip-limit-exceeded", "WIP 上限"),
+    ("event-epic-completed", "史诗完成"),
+    ("event-budget-limit-reached", "预算上限"),
+    ("event-attempt-stalled", "停滞"),
+    ("summary-daily-title", "每日摘要"),
+    ("summary-weekly-title", "每周摘要"),
+    ("summary-completed", "已完成: {{count}}"),
+    ("summary-failed", "失败: {{count}}"),
+    ("summary-awaiting-review", "待审核: {{count}}"),
+    ("summary-cost", "API 花费: ${{cost}}"),
+];
+
+/// Render the message `key` in `locale`, filling `{{name}}` placeholders from `args`.
+///
+/// Keys without a translation fall back to English, and unknown keys render as the key itself.
+pub fn t(locale: Locale, key: &str, args: &[(&str, &str)]) -> String {
+    let lookup = |catalog: &[(&'static str, &'static str)]| {
+        catalog
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, pattern)| *pattern)
+    };
+    let pattern = lookup(locale.catalog())
+        .or_else(|| lookup(EN))
+        .unwrap_or(key);
+    let vars: HashMap<&str, String> = args
+        .iter()
+        .map(|(name, value)| (*name, value.to_string()))
+        .collect();
+    template::render(pattern, &vars)
+}
+
+/// Short name of an event, e.g. for status fields
+pub fn event_label(locale: Locale, event: NotificationEvent) -> String {
+    let key = match event {
+        NotificationEvent::TaskStarted => "event-task-started",
+        NotificationEvent::AttemptCompleted => "event-attempt-completed",
+        NotificationEvent::AttemptFailed => "event-attempt-failed",
+        NotificationEvent::ApprovalNeeded => "event-approval-needed",
+        NotificationEvent::PrOpened => "event-pr-opened",
+        NotificationEvent::PrMerged => "event-pr-merged",
+        NotificationEvent::MergeConflict => "event-merge-conflict",
+        NotificationEvent::Summary => "event-summary",
+        NotificationEvent::TaskUnblocked => "event-task-unblocked",
+        NotificationEvent::TaskOverdue => "event-task-overdue",
+        NotificationEvent::WipLimitExceeded => "event-wip-limit-exceeded",
+        NotificationEvent::EpicCompleted => "event-epic-completed",
+        NotificationEvent::BudgetLimitReached => "event-budget-limit-reached",
+        NotificationEvent::AttemptStalled => "event-attempt-stalled",
+    };
+    t(locale, key, &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_locale_translates_every_key() {
+        for locale in [Locale::Ja, Locale::Es, Locale::Ko, Locale::ZhHans] {
+            for (key, _) in EN {
+                assert!(
+                    locale.catalog().iter().any(|(k, _)| k == key),
+                    "{locale:?} is missing {key}"
+                );
+            }
+            assert_eq!(locale.catalog().len(), EN.len());
+        }
+    }
+
+    #[test]
+    fn test_placeholders_and_fallbacks() {
+        assert_eq!(
+            t(Locale::Es, "pr-opened-title", &[("number", "42")]),
+            "PR #42 abierta"
+        );
+        assert_eq!(t(Locale::Ja, "no-such-key", &[]), "no-such-key");
+    }
+
+    #[test]
+    fn test_locale_from_tag() {
+        assert_eq!(Locale::from_tag("ja_JP.UTF-8"), Some(Locale::Ja));
+        assert_eq!(Locale::from_tag("zh-Hans"), Some(Locale::ZhHans));
+        assert_eq!(Locale::from_tag("C.UTF-8"), None);
+    }
+}
@@ -0,0 +1,266 @@
+use std::time::Duration;
+
+use base64::Engine;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader},
+    net::TcpStream,
+};
+use tokio_native_tls::{TlsConnector, native_tls};
+
+use super::NotificationError;
+
+const TLS_PORT: u16 = 6697;
+const PLAINTEXT_PORT: u16 = 6667;
+/// Leaves room for the command, channel and server-added prefix within IRC's 512-byte lines
+const MAX_TEXT_BYTES: usize = 400;
+/// Upper bound on connecting, registering, joining and sending
+const SESSION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Server, identity and channel for IRC notifications
+#[derive(Debug, Clone)]
+pub struct IrcTarget {
+    /// Host, optionally with `:port`
+    pub server: String,
+    pub tls: bool,
+    pub nick: String,
+    pub channel: String,
+    /// SASL PLAIN username and password
+    pub sasl: Option<(String, String)>,
+}
+
+fn host_and_port(server: &str, tls: bool) -> (&str, u16) {
+    let default_port = if tls { TLS_PORT } else { PLAINTEXT_PORT };
+    match server.rsplit_once(':') {
+        Some((host, port)) => match port.parse() {
+            Ok(port) => (host, port),
+            Err(_) => (server, default_port),
+        },
+        None => (server, default_port),
+    }
+}
+
+fn channel_name(channel: &str) -> String {
+    if channel.starts_with(['#', '&', '+', '!']) {
+        channel.to_string()
+    } else {
+        format!("#{channel}")
+    }
+}
+
+/// Whether a message line is just a labelled link, like `View: <url>`
+fn is_link_line(line: &str) -> bool {
+    line.split_once(':').is_some_and(|(_, rest)| {
+        let rest = rest.trim();
+        (rest.starts_with("http://") || rest.starts_with("https://"))
+            && !rest.contains(char::is_whitespace)
+    })
+}
+
+/// Fold the title and message into one line, replacing link lines with `url` at the end
+fn format_line(title: &str, message: &str, url: Option<&str>) -> String {
+    let text = std::iter::once(title)
+        .chain(message.lines())
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !is_link_line(line))
+        .collect::<Vec<_>>()
+        .join(" | ")
+        .replace(|c: char| c.is_control(), " ");
+    let suffix = url.map(|url| format!(" {url}")).unwrap_or_default();
+
+    let budget = MAX_TEXT_BYTES.saturating_sub(suffix.len());
+    if text.len() <= budget {
+        return format!("{text}{suffix}");
+    }
+    let mut end = budget.saturating_sub('…'.len_utf8());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…{suffix}", &text[..end])
+}
+
+/// Split a raw line into its command and parameters, dropping tags and the source prefix
+fn parse_line(line: &str) -> Option<(&str, Vec<&str>)> {
+    let mut rest = line.trim_end();
+    if rest.starts_with('@') {
+        rest = rest.split_once(' ')?.1;
+    }
+    if rest.starts_with(':') {
+        rest = rest.split_once(' ')?.1;
+    }
+    let (middle, trailing) = match rest.split_once(" :") {
+        Some((middle, trailing)) => (middle, Some(trailing)),
+        None => (rest, None),
+    };
+    let mut parts = middle.split(' ').filter(|part| !part.is_empty());
+    let command = parts.next()?;
+    let mut params: Vec<&str> = parts.collect();
+    params.extend(trailing);
+    Some((command, params))
+}
+
+async fn send_line<W: AsyncWrite + Unpin>(writer: &mut W, line: &str) -> std::io::Result<()> {
+    writer.write_all(format!("{line}\r\n").as_bytes()).await
+}
+
+/// Register, join the channel, post `text` and quit
+async fn run_session<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: S,
+    target: &IrcTarget,
+    text: &str,
+) -> Result<(), NotificationError> {
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+    let channel = channel_name(&target.channel);
+    let mut nick = target.nick.clone();
+
+    if target.sasl.is_some() {
+        send_line(&mut writer, "CAP REQ :sasl").await?;
+    }
+    send_line(&mut writer, &format!("NICK {nick}")).await?;
+    send_line(&mut writer, &format!("USER {nick} 0 * :Vibe Kanban")).await?;
+
+    while let Some(line) = lines.next_line().await? {
+        let Some((command, params)) = parse_line(&line) else {
+            continue;
+        };
+        let last = params.last().copied().unwrap_or_default();
+        match command {
+            "PING" => send_line(&mut writer, &format!("PONG :{last}")).await?,
+            "CAP" if params.get(1) == Some(&"ACK") => {
+                send_line(&mut writer, "AUTHENTICATE PLAIN").await?
+            }
+            "CAP" if params.get(1) == Some(&"NAK") => {
+                return Err(NotificationError::Delivery(
+                    "IRC server does not support SASL".to_string(),
+                ));
+            }
+            "AUTHENTICATE" if last == "+" => {
+                if let Some((username, password)) = &target.sasl {
+                    let payload = base64::engine::general_purpose::STANDARD
+                        .encode(format!("{username}\0{username}\0{password}"));
+                    send_line(&mut writer, &format!("AUTHENTICATE {payload}")).await?;
+                }
+            }
+            // SASL succeeded
+            "903" => send_line(&mut writer, "CAP END").await?,
+            "902" | "904" | "905" => {
+                return Err(NotificationError::Delivery(format!(
+                    "IRC SASL authentication failed: {last}"
+                )));
+            }
+            // Nick in use
+            "433" => {
+                nick.push('_');
+                send_line(&mut writer, &format!("NICK {nick}")).await?;
+            }
+            // Registered
+            "001" => send_line(&mut writer, &format!("JOIN {channel}")).await?,
+            // End of the channel's names list, sent once the join went through
+            "366" => {
+                send_line(&mut writer, &format!("PRIVMSG {channel} :{text}")).await?;
+                send_line(&mut writer, "QUIT :Vibe Kanban").await?;
+                writer.flush().await?;
+                return Ok(());
+            }
+            "403" | "405" | "471" | "473" | "474" | "475" | "477" => {
+                return Err(NotificationError::Delivery(format!(
+                    "Could not join {channel}: {last}"
+                )));
+            }
+            "ERROR" => {
+                return Err(NotificationError::Delivery(format!(
+                    "IRC server closed the connection: {last}"
+                )));
+            }
+            _ => {}
+        }
+    }
+    Err(NotificationError::Delivery(
+        "IRC server closed the connection".to_string(),
+    ))
+}
+
+async fn connect_and_send(target: &IrcTarget, text: &str) -> Result<(), NotificationError> {
+    let (host, port) = host_and_port(target.server.trim(), target.tls);
+    let tcp = TcpStream::connect((host, port)).await?;
+    if !target.tls {
+        return run_session(tcp, target, text).await;
+    }
+    let connector = native_tls::TlsConnector::new()
+        .map(TlsConnector::from)
+        .map_err(|e| NotificationError::Delivery(format!("TLS setup failed: {e}")))?;
+    let stream = connector
+        .connect(host, tcp)
+        .await
+        .map_err(|e| NotificationError::Delivery(format!("TLS handshake failed: {e}")))?;
+    run_session(stream, target, text).await
+}
+
+/// Post a one-line notification, connecting for just this message
+pub async fn send_irc(
+    target: &IrcTarget,
+    title: &str,
+    message: &str,
+    task_url: Option<&str>,
+) -> Result<(), NotificationError> {
+    let text = format_line(title, message, task_url);
+    tokio::time::timeout(SESSION_TIMEOUT, connect_and_send(target, &text))
+        .await
+        .map_err(|_| {
+            NotificationError::Delivery("Timed out talking to the IRC server".to_string())
+        })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_and_port() {
+        assert_eq!(
+            host_and_port("irc.libera.chat", true),
+            ("irc.libera.chat", 6697)
+        );
+        assert_eq!(
+            host_and_port("irc.libera.chat", false),
+            ("irc.libera.chat", 6667)
+        );
+        assert_eq!(
+            host_and_port("irc.example.org:7000", true),
+            ("irc.example.org", 7000)
+        );
+    }
+
+    #[test]
+    fn test_format_line_is_single_line_with_link() {
+        assert_eq!(
+            format_line(
+                "Fix login",
+                "Status: ✅\nView: http://localhost:3000/projects/p/tasks/t",
+                Some("http://localhost:3000/api/t/1a2b3c4d")
+            ),
+            "Fix login | Status: ✅ http://localhost:3000/api/t/1a2b3c4d"
+        );
+
+        let long = format_line(&"x".repeat(600), "", Some("http://h/t/1"));
+        assert_eq!(long.len(), MAX_TEXT_BYTES);
+        assert!(long.ends_with("… http://h/t/1"));
+    }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            parse_line(":irc.example.org 001 vibe :Welcome to IRC"),
+            Some(("001", vec!["vibe", "Welcome to IRC"]))
+        );
+        assert_eq!(parse_line("PING :abc"), Some(("PING", vec!["abc"])));
+        assert_eq!(
+            parse_line("@time=x :srv CAP * ACK :sasl"),
+            Some(("CAP", vec!["*", "ACK", "sasl"]))
+        );
+        assert_eq!(
+            parse_line("AUTHENTICATE +"),
+            Some(("AUTHENTICATE", vec!["+"]))
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use serde::Serialize;
+use url::Url;
+use uuid::Uuid;
+
+use super::{
+    NotificationError,
+    i18n::{self, Locale},
+    send_checked,
+};
+use crate::services::config::NotificationRetryPolicy;
+
+/// Account and room used to post Matrix notices
+#[derive(Debug, Clone)]
+pub struct MatrixTarget {
+    pub homeserver_url: String,
+    pub access_token: String,
+    pub room_id: String,
+}
+
+/// `m.room.message` content; notices are meant for bots and never trigger replies from other bots
+#[derive(Debug, Serialize)]
+struct MatrixNotice {
+    msgtype: &'static str,
+    body: String,
+    format: &'static str,
+    formatted_body: String,
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn build_notice(
+    locale: Locale,
+    title: &str,
+    message: &str,
+    task_url: Option<&str>,
+) -> MatrixNotice {
+    let mut body = format!("{title}\n{message}");
+    let mut formatted_body = format!(
+        "<strong>{}</strong><br>{}",
+        escape_html(title),
+        message
+            .lines()
+            .map(escape_html)
+            .collect::<Vec<_>>()
+            .join("<br>")
+    );
+    if let Some(url) = task_url {
+        body.push_str(&format!("\n{url}"));
+        formatted_body.push_str(&format!(
+            "<br><a href=\"{}\">{}</a>",
+            escape_html(url),
+            escape_html(&i18n::t(locale, "open-task", &[]))
+        ));
+    }
+
+    MatrixNotice {
+        msgtype: "m.notice",
+        body,
+        format: "org.matrix.custom.html",
+        formatted_body,
+    }
+}
+
+/// `PUT /_matrix/client/v3/rooms/{roomId}/send/m.room.message/{txnId}`, with each segment escaped
+fn send_url(homeserver_url: &str, room_id: &str, txn_id: &str) -> Option<Url> {
+    let mut url = Url::parse(homeserver_url.trim()).ok()?;
+    url.path_segments_mut()
+        .ok()?
+        .pop_if_empty()
+        .extend(["_matrix", "client", "v3", "rooms", room_id])
+        .extend(["send", "m.room.message", txn_id]);
+    Some(url)
+}
+
+pub async fn send_matrix(
+    target: &MatrixTarget,
+    locale: Locale,
+    title: &str,
+    message: &str,
+    task_url: Option<&str>,
+    retry: &NotificationRetryPolicy,
+) -> Result<(), NotificationError> {
+    // The transaction ID stays the same across retries so the homeserver can de-duplicate
+    let txn_id = Uuid::new_v4().to_string();
+    let url = send_url(&target.homeserver_url, &target.room_id, &txn_id).ok_or_else(|| {
+        NotificationError::Delivery(format!(
+            "Invalid Matrix homeserver URL: {}",
+            target.homeserver_url
+        ))
+    })?;
+
+    let client = reqwest::Client::new();
+    let request = client
+        .put(url)
+        .bearer_auth(&target.access_token)
+        .json(&build_notice(locale, title, message, task_url));
+    send_checked(request, retry).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_send_url_escapes_room_id() {
+        let url = send_url("https://matrix.example.com/", "!abc:example.com", "txn1").unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://matrix.example.com/_matrix/client/v3/rooms/!abc:example.com/send/m.room.message/txn1"
+        );
+        let url = send_url("https://matrix.example.com", "#room/x", "txn1").unwrap();
+        assert!(url.as_str().contains("/rooms/%23room%2Fx/send/"));
+    }
+
+    #[test]
+    fn test_build_notice_html() {
+        let notice = build_notice(
+            Locale::En,
+            "<Fix> login",
+            "Status: done\nAll good",
+            Some("http://localhost:3000/t/1"),
+        );
+        assert_eq!(notice.msgtype, "m.notice");
+        assert_eq!(
+            notice.formatted_body,
+            "<strong>&lt;Fix&gt; login</strong><br>Status: done<br>All good<br><a href=\"http://localhost:3000/t/1\">Open in Vibe Kanban</a>"
+        );
+        assert_eq!(
+            notice.body,
+            "<Fix> login\nStatus: done\nAll good\nhttp://localhost:3000/t/1"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use serde::Serialize;
+use url::Url;
+
+use super::{NotificationError, send_checked};
+use crate::services::config::{NotificationPriority, NotificationRetryPolicy};
+
+/// Settings needed to publish a single ntfy message
+#[derive(Debug, Clone)]
+pub struct NtfyTarget {
+    pub topic_url: String,
+    pub token: Option<String>,
+    pub priority: Option<u8>,
+}
+
+/// JSON publish body, see https://docs.ntfy.sh/publish/#publish-as-json
+///
+/// Publishing as JSON (instead of using `Title`/`Click` headers) keeps non-ASCII
+/// titles intact, since HTTP header values cannot carry arbitrary UTF-8.
+#[derive(Debug, Serialize)]
+struct NtfyMessage<'a> {
+    topic: &'a str,
+    title: &'a str,
+    message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    priority: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    click: Option<&'a str>,
+}
+
+/// ntfy priority (1-5) for a rule's urgency
+pub fn priority_level(priority: NotificationPriority) -> u8 {
+    match priority {
+        NotificationPriority::Low => 2,
+        NotificationPriority::Normal => 3,
+        NotificationPriority::High => 4,
+        NotificationPriority::Urgent => 5,
+    }
+}
+
+/// Split a topic URL such as `https://ntfy.sh/my-topic` into the server root and topic name
+fn split_topic_url(topic_url: &str) -> Option<(String, String)> {
+    let mut url = Url::parse(topic_url.trim()).ok()?;
+    let topic = url
+        .path_segments()?
+        .filter(|segment| !segment.is_empty())
+        .next_back()?
+        .to_string();
+
+    // Keep any path prefix (ntfy can be served under a sub-path) but drop the topic itself
+    let prefix: Vec<String> = url
+        .path_segments()?
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.to_string())
+        .collect();
+    let prefix = &prefix[..prefix.len() - 1];
+    url.set_path(&prefix.join("/"));
+    url.set_query(None);
+    url.set_fragment(None);
+
+    Some((url.as_str().trim_end_matches('/').to_string(), topic))
+}
+
+pub async fn send_ntfy(
+    target: &NtfyTarget,
+    title: &str,
+    message: &str,
+    click: Option<&str>,
+    retry: &NotificationRetryPolicy,
+) -> Result<(), NotificationError> {
+    let (server, topic) = split_topic_url(&target.topic_url).ok_or_else(|| {
+        NotificationError::Delivery(format!("Invalid ntfy topic URL: {}", target.topic_url))
+    })?;
+
+    let body = NtfyMessage {
+        topic: &topic,
+        title,
+        message,
+        priority: target.priority.map(|p| p.clamp(1, 5)),
+        click,
+    };
+
+    let client = reqwest::Client::new();
+    let mut request = client.post(&server).json(&body);
+    if let Some(token) = &target.token {
+        request = request.bearer_auth(token);
+    }
+    send_checked(request, retry).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_topic_url() {
+        assert_eq!(
+            split_topic_url("https://ntfy.sh/vibe-kanban"),
+            Some(("https://ntfy.sh".to_string(), "vibe-kanban".to_string()))
+        );
+        assert_eq!(
+            split_topic_url("https://example.com/ntfy/alerts/"),
+            Some(("https://example.com/ntfy".to_string(), "alerts".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_split_topic_url_requires_topic() {
+        assert_eq!(split_topic_url("https://ntfy.sh/"), None);
+        assert_eq!(split_topic_url("not a url"), None);
+    }
+}
@@ -0,0 +1,71 @@
+use serde::Serialize;
+
+use super::{NotificationError, send_checked};
+use crate::services::config::{NotificationPriority, NotificationRetryPolicy};
+
+const PUSHOVER_API_URL: &str = "https://api.pushover.net/1/messages.json";
+
+/// Credentials and defaults for a Pushover message
+#[derive(Debug, Clone)]
+pub struct PushoverTarget {
+    pub app_token: String,
+    pub user_key: String,
+    pub priority: Option<i8>,
+}
+
+/// Request body, see https://pushover.net/api#messages
+#[derive(Debug, Serialize)]
+struct PushoverMessage<'a> {
+    token: &'a str,
+    user: &'a str,
+    title: &'a str,
+    message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    priority: Option<i8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url_title: Option<&'a str>,
+    /// Emergency priority (2) requires retry/expire parameters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retry: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expire: Option<u32>,
+}
+
+/// Pushover priority for a rule's urgency; urgent notifications repeat until acknowledged
+pub fn priority_level(priority: NotificationPriority) -> i8 {
+    match priority {
+        NotificationPriority::Low => -1,
+        NotificationPriority::Normal => 0,
+        NotificationPriority::High => 1,
+        NotificationPriority::Urgent => 2,
+    }
+}
+
+pub async fn send_pushover(
+    target: &PushoverTarget,
+    title: &str,
+    message: &str,
+    url: Option<&str>,
+    retry: &NotificationRetryPolicy,
+) -> Result<(), NotificationError> {
+    // Pushover accepts -2 (lowest) through 2 (emergency)
+    let priority = target.priority.map(|p| p.clamp(-2, 2));
+    let emergency = priority == Some(2);
+
+    let body = PushoverMessage {
+        token: &target.app_token,
+        user: &target.user_key,
+        title,
+        message,
+        priority,
+        url,
+        url_title: url.map(|_| "Open in Vibe Kanban"),
+        retry: emergency.then_some(60),
+        expire: emergency.then_some(3600),
+    };
+
+    let client = reqwest::Client::new();
+    send_checked(client.post(PUSHOVER_API_URL).json(&body), retry).await
+}
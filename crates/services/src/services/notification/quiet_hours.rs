@@ -0,0 +1,254 @@
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, NaiveDateTime, NaiveTime, TimeZone, Utc,
+};
+use chrono_tz::Tz;
+
+use crate::services::config::{DayOfWeek, NotificationChannel, QuietHours};
+
+/// Parse an offset such as `+02:00`, `-0530` or `UTC`
+pub(crate) fn parse_utc_offset(value: &str) -> Option<FixedOffset> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("utc") || value.eq_ignore_ascii_case("z") {
+        return FixedOffset::east_opt(0);
+    }
+
+    let (sign, rest) = match value.as_bytes().first()? {
+        b'+' => (1, &value[1..]),
+        b'-' => (-1, &value[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = match rest.split_once(':') {
+        Some((hours, minutes)) => (hours, minutes),
+        None if rest.len() == 4 => rest.split_at(2),
+        None => (rest, "0"),
+    };
+    let seconds = hours.parse::<i32>().ok()? * 3600 + minutes.parse::<i32>().ok()? * 60;
+    FixedOffset::east_opt(sign * seconds)
+}
+
+pub(super) fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+/// Timezone a notification schedule is read in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum ScheduleZone {
+    Named(Tz),
+    Fixed(FixedOffset),
+    Local,
+}
+
+impl ScheduleZone {
+    /// Resolve an IANA name such as `Europe/Berlin`, falling back to a fixed offset such as
+    /// `+02:00` and then to the system's timezone
+    pub(super) fn resolve(timezone: Option<&str>) -> Self {
+        let Some(timezone) = timezone.map(str::trim).filter(|s| !s.is_empty()) else {
+            return Self::Local;
+        };
+        if let Ok(tz) = timezone.parse::<Tz>() {
+            return Self::Named(tz);
+        }
+        match parse_utc_offset(timezone) {
+            Some(offset) => Self::Fixed(offset),
+            None => {
+                tracing::warn!(
+                    "Invalid notification schedule timezone '{}', using local time",
+                    timezone
+                );
+                Self::Local
+            }
+        }
+    }
+
+    /// Wall-clock time at `now`
+    pub(super) fn local_time(self, now: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Self::Named(tz) => now.with_timezone(&tz).naive_local(),
+            Self::Fixed(offset) => now.with_timezone(&offset).naive_local(),
+            Self::Local => now.with_timezone(&Local).naive_local(),
+        }
+    }
+
+    /// Instant of a wall-clock time: the earlier one when a DST change repeats it, `None` when
+    /// a DST change skips it
+    pub(super) fn to_utc(self, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Named(tz) => tz
+                .from_local_datetime(&local)
+                .earliest()
+                .map(|at| at.with_timezone(&Utc)),
+            Self::Fixed(offset) => offset
+                .from_local_datetime(&local)
+                .earliest()
+                .map(|at| at.with_timezone(&Utc)),
+            Self::Local => Local
+                .from_local_datetime(&local)
+                .earliest()
+                .map(|at| at.with_timezone(&Utc)),
+        }
+    }
+}
+
+/// Channels that alert someone directly are held back during quiet hours; chat and webhook
+/// deliveries are left for whoever reads them later
+pub(super) fn silences(channel: NotificationChannel) -> bool {
+    matches!(
+        channel,
+        NotificationChannel::Sound
+            | NotificationChannel::Push
+            | NotificationChannel::Ntfy
+            | NotificationChannel::Pushover
+            | NotificationChannel::Gotify
+            | NotificationChannel::WebPush
+    )
+}
+
+/// Whether `now` falls inside the configured quiet hours
+pub fn is_quiet(settings: &QuietHours, now: DateTime<Utc>) -> bool {
+    if !settings.enabled {
+        return false;
+    }
+    let (Some(start), Some(end)) = (parse_time(&settings.start), parse_time(&settings.end)) else {
+        tracing::warn!(
+            "Invalid quiet hours window '{}'-'{}', expected HH:MM",
+            settings.start,
+            settings.end
+        );
+        return false;
+    };
+    if start == end {
+        return false;
+    }
+
+    let local = ScheduleZone::resolve(settings.timezone.as_deref()).local_time(now);
+    let time = local.time();
+    // The day a window belongs to is the day it started on
+    let window_day = if start < end {
+        (start <= time && time < end).then_some(local.date())
+    } else if time >= start {
+        Some(local.date())
+    } else if time < end {
+        Some(local.date() - Duration::days(1))
+    } else {
+        None
+    };
+
+    window_day.is_some_and(|day| {
+        settings.days.is_empty() || settings.days.contains(&DayOfWeek::from(day.weekday()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn settings(start: &str, end: &str, days: Vec<DayOfWeek>) -> QuietHours {
+        QuietHours {
+            enabled: true,
+            start: start.to_string(),
+            end: end.to_string(),
+            timezone: Some("UTC".to_string()),
+            days,
+        }
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        // 2024-01-01 was a Monday
+        Utc.with_ymd_and_hms(2024, 1, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_utc_offset() {
+        assert_eq!(parse_utc_offset("+02:00"), FixedOffset::east_opt(7200));
+        assert_eq!(parse_utc_offset("-0530"), FixedOffset::east_opt(-19800));
+        assert_eq!(parse_utc_offset("UTC"), FixedOffset::east_opt(0));
+        assert_eq!(parse_utc_offset("Europe/Berlin"), None);
+    }
+
+    #[test]
+    fn test_overnight_window() {
+        let quiet = settings("22:00", "07:00", Vec::new());
+        assert!(is_quiet(&quiet, at(1, 23, 0)));
+        assert!(is_quiet(&quiet, at(2, 6, 59)));
+        assert!(!is_quiet(&quiet, at(2, 7, 0)));
+        assert!(!is_quiet(&quiet, at(2, 12, 0)));
+    }
+
+    #[test]
+    fn test_days_match_window_start() {
+        // Friday night into Saturday morning, but not Saturday night
+        let quiet = settings("22:00", "07:00", vec![DayOfWeek::Friday]);
+        assert!(is_quiet(&quiet, at(5, 23, 0)));
+        assert!(is_quiet(&quiet, at(6, 3, 0)));
+        assert!(!is_quiet(&quiet, at(6, 23, 0)));
+    }
+
+    #[test]
+    fn test_offset_applied() {
+        let mut quiet = settings("09:00", "17:00", Vec::new());
+        quiet.timezone = Some("+02:00".to_string());
+        assert!(is_quiet(&quiet, at(1, 7, 30)));
+        assert!(!is_quiet(&quiet, at(1, 15, 30)));
+    }
+
+    #[test]
+    fn test_resolve_timezone() {
+        assert_eq!(
+            ScheduleZone::resolve(Some("Europe/Berlin")),
+            ScheduleZone::Named(Tz::Europe__Berlin)
+        );
+        assert_eq!(
+            ScheduleZone::resolve(Some("-0530")),
+            ScheduleZone::Fixed(FixedOffset::east_opt(-19800).unwrap())
+        );
+        assert_eq!(ScheduleZone::resolve(None), ScheduleZone::Local);
+        assert_eq!(
+            ScheduleZone::resolve(Some("Mars/Olympus")),
+            ScheduleZone::Local
+        );
+    }
+
+    #[test]
+    fn test_named_zone_follows_dst() {
+        // Berlin moved from UTC+1 to UTC+2 overnight into 2024-03-31
+        let mut quiet = settings("22:00", "07:00", Vec::new());
+        quiet.timezone = Some("Europe/Berlin".to_string());
+        let march =
+            |day, hour, minute| Utc.with_ymd_and_hms(2024, 3, day, hour, minute, 0).unwrap();
+        assert!(is_quiet(&quiet, march(30, 5, 30)));
+        assert!(!is_quiet(&quiet, march(30, 6, 30)));
+        assert!(is_quiet(&quiet, march(31, 4, 30)));
+        assert!(!is_quiet(&quiet, march(31, 5, 30)));
+        assert!(is_quiet(&quiet, march(31, 20, 0)));
+    }
+
+    #[test]
+    fn test_disabled() {
+        let mut quiet = settings("00:00", "23:59", Vec::new());
+        quiet.enabled = false;
+        assert!(!is_quiet(&quiet, at(1, 12, 0)));
+    }
+
+    #[test]
+    fn test_silences_push_style_channels() {
+        for channel in [
+            NotificationChannel::Sound,
+            NotificationChannel::Push,
+            NotificationChannel::Ntfy,
+            NotificationChannel::Pushover,
+            NotificationChannel::Gotify,
+            NotificationChannel::WebPush,
+        ] {
+            assert!(silences(channel), "{channel} should be silenced");
+        }
+        for channel in [
+            NotificationChannel::Slack,
+            NotificationChannel::Webhook,
+            NotificationChannel::Matrix,
+        ] {
+            assert!(!silences(channel), "{channel} should still be delivered");
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use super::NotificationContext;
+use crate::services::config::NotificationRule;
+
+fn matches(rule: &NotificationRule, context: &NotificationContext) -> bool {
+    if !rule.events.is_empty() && !rule.events.contains(&context.event) {
+        return false;
+    }
+    if !rule.project_ids.is_empty()
+        && !context
+            .project_id
+            .is_some_and(|id| rule.project_ids.contains(&id))
+    {
+        return false;
+    }
+    if !rule.labels.is_empty()
+        && !rule.labels.iter().any(|wanted| {
+            context
+                .labels
+                .iter()
+                .any(|label| label.eq_ignore_ascii_case(wanted))
+        })
+    {
+        return false;
+    }
+    if !rule.executors.is_empty()
+        && !context.executor.as_deref().is_some_and(|executor| {
+            rule.executors
+                .iter()
+                .any(|wanted| wanted.eq_ignore_ascii_case(executor))
+        })
+    {
+        return false;
+    }
+    if !rule.assignees.is_empty()
+        && !rule.assignees.iter().any(|wanted| {
+            context
+                .assignees
+                .iter()
+                .any(|assignee| assignee.eq_ignore_ascii_case(wanted))
+        })
+    {
+        return false;
+    }
+    match rule.outcome {
+        Some(outcome) => context.event.outcome() == Some(outcome),
+        None => true,
+    }
+}
+
+/// First rule that matches the notification, if any
+pub fn find_rule<'a>(
+    rules: &'a [NotificationRule],
+    context: &NotificationContext,
+) -> Option<&'a NotificationRule> {
+    rules.iter().find(|rule| matches(rule, context))
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::services::config::{NotificationChannel, NotificationEvent, NotificationOutcome};
+
+    fn rule(name: &str) -> NotificationRule {
+        NotificationRule {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_first_matching_rule_wins() {
+        let project_id = Uuid::new_v4();
+        let rules = vec![
+            NotificationRule {
+                project_ids: vec![project_id],
+                outcome: Some(NotificationOutcome::Failure),
+                channels: vec![NotificationChannel::Slack, NotificationChannel::Pushover],
+                ..rule("oncall")
+            },
+            NotificationRule {
+                outcome: Some(NotificationOutcome::Success),
+                channels: vec![NotificationChannel::Sound],
+                ..rule("successes")
+            },
+        ];
+
+        let failed = NotificationContext::new(NotificationEvent::AttemptFailed)
+            .with_task(project_id, Uuid::new_v4());
+        assert_eq!(find_rule(&rules, &failed).unwrap().name, "oncall");
+
+        let other_project = NotificationContext::new(NotificationEvent::AttemptFailed)
+            .with_task(Uuid::new_v4(), Uuid::new_v4());
+        assert!(find_rule(&rules, &other_project).is_none());
+
+        let merged = NotificationContext::new(NotificationEvent::PrMerged);
+        assert_eq!(find_rule(&rules, &merged).unwrap().name, "successes");
+
+        // Events without an outcome never match outcome conditions
+        let started = NotificationContext::new(NotificationEvent::TaskStarted);
+        assert!(find_rule(&rules, &started).is_none());
+    }
+
+    #[test]
+    fn test_label_and_executor_conditions() {
+        let rules = vec![NotificationRule {
+            labels: vec!["Urgent".to_string()],
+            executors: vec!["CLAUDE_CODE".to_string()],
+            ..rule("urgent claude")
+        }];
+
+        let mut context = NotificationContext::new(NotificationEvent::AttemptCompleted)
+            .with_executor("claude_code");
+        assert!(find_rule(&rules, &context).is_none());
+
+        context.labels = vec!["backend".to_string(), "urgent".to_string()];
+        assert!(find_rule(&rules, &context).is_some());
+
+        context.executor = Some("CODEX".to_string());
+        assert!(find_rule(&rules, &context).is_none());
+    }
+
+    #[test]
+    fn test_assignee_condition() {
+        let rules = vec![NotificationRule {
+            assignees: vec!["alice".to_string()],
+            ..rule("alice's tasks")
+        }];
+
+        let mut context = NotificationContext::new(NotificationEvent::AttemptFailed);
+        assert!(find_rule(&rules, &context).is_none());
+
+        context.assignees = vec!["Bob".to_string(), "Alice".to_string()];
+        assert!(find_rule(&rules, &context).is_some());
+    }
+}
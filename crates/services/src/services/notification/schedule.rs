@@ -0,0 +1,80 @@
+use chrono::{DateTime, Datelike, Duration, Utc};
+
+use super::quiet_hours::{ScheduleZone, parse_time};
+use crate::services::config::{DayOfWeek, NotificationSummary, SummaryFrequency};
+
+/// Length of the period a summary covers
+pub fn period(frequency: SummaryFrequency) -> Duration {
+    match frequency {
+        SummaryFrequency::Daily => Duration::days(1),
+        SummaryFrequency::Weekly => Duration::weeks(1),
+    }
+}
+
+/// Most recent time at or before `now` that a summary was due
+pub fn latest_run(settings: &NotificationSummary, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let Some(time) = parse_time(&settings.time) else {
+        tracing::warn!("Invalid summary time '{}', expected HH:MM", settings.time);
+        return None;
+    };
+    let zone = ScheduleZone::resolve(settings.utc_offset.as_deref());
+    let local_now = zone.local_time(now);
+
+    let mut day = local_now.date();
+    if day.and_time(time) > local_now {
+        day -= Duration::days(1);
+    }
+    if settings.frequency == SummaryFrequency::Weekly {
+        while DayOfWeek::from(day.weekday()) != settings.weekday {
+            day -= Duration::days(1);
+        }
+    }
+    zone.to_utc(day.and_time(time))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn settings(frequency: SummaryFrequency) -> NotificationSummary {
+        NotificationSummary {
+            enabled: true,
+            frequency,
+            time: "09:00".to_string(),
+            weekday: DayOfWeek::Wednesday,
+            utc_offset: Some("UTC".to_string()),
+        }
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        // 2024-01-01 was a Monday
+        Utc.with_ymd_and_hms(2024, 1, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_daily_run() {
+        let daily = settings(SummaryFrequency::Daily);
+        assert_eq!(latest_run(&daily, at(2, 9, 0)), Some(at(2, 9, 0)));
+        assert_eq!(latest_run(&daily, at(2, 8, 59)), Some(at(1, 9, 0)));
+        assert_eq!(latest_run(&daily, at(2, 23, 0)), Some(at(2, 9, 0)));
+    }
+
+    #[test]
+    fn test_weekly_run() {
+        let weekly = settings(SummaryFrequency::Weekly);
+        assert_eq!(latest_run(&weekly, at(10, 9, 30)), Some(at(10, 9, 0)));
+        assert_eq!(latest_run(&weekly, at(10, 8, 0)), Some(at(3, 9, 0)));
+        assert_eq!(latest_run(&weekly, at(12, 12, 0)), Some(at(10, 9, 0)));
+    }
+
+    #[test]
+    fn test_offset_applied() {
+        let mut daily = settings(SummaryFrequency::Daily);
+        daily.utc_offset = Some("+02:00".to_string());
+        assert_eq!(latest_run(&daily, at(2, 7, 30)), Some(at(2, 7, 0)));
+        daily.time = "9am".to_string();
+        assert_eq!(latest_run(&daily, at(2, 7, 30)), None);
+    }
+}
@@ -0,0 +1,320 @@
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::{
+    NotificationContext, NotificationError,
+    i18n::{self, Locale},
+    send_checked, send_with_retry,
+    template::format_duration,
+};
+use crate::services::config::{NotificationEvent, NotificationRetryPolicy};
+
+/// Slack rejects header blocks longer than this
+const HEADER_MAX_CHARS: usize = 150;
+
+const POST_MESSAGE_URL: &str = "https://slack.com/api/chat.postMessage";
+
+/// Credentials for posting through the Web API instead of an incoming webhook
+#[derive(Debug, Clone)]
+pub struct SlackBot {
+    pub token: String,
+    pub channel: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PostMessageResponse {
+    ok: bool,
+    ts: Option<String>,
+    error: Option<String>,
+}
+
+fn escape_mrkdwn(s: &str) -> String {
+    s.replace('\\', r"\\")
+        .replace('*', r"\*")
+        .replace('_', r"\_")
+        .replace('~', r"\~")
+        .replace('`', r"\`")
+}
+
+fn extract_url(s: &str) -> Option<String> {
+    let s = s.trim();
+
+    // Slack-style: <url|text> or <url>
+    if let Some(stripped) = s.strip_prefix('<')
+        && let Some(close) = stripped.find('>')
+    {
+        let inner = &stripped[..close];
+        let url = inner.split('|').next().unwrap_or("").trim();
+        if url.starts_with("http://") || url.starts_with("https://") {
+            return Some(url.to_string());
+        }
+    }
+
+    // Markdown-style: [text](url)
+    if let Some(open) = s.find("](")
+        && s.starts_with('[')
+        && s.ends_with(')')
+    {
+        let url = &s[open + 2..s.len() - 1];
+        let url = url.trim();
+        if url.starts_with("http://") || url.starts_with("https://") {
+            return Some(url.to_string());
+        }
+    }
+
+    // Raw URL token
+    for token in s.split_whitespace() {
+        if token.starts_with("http://") || token.starts_with("https://") {
+            return Some(
+                token
+                    .trim_matches(|c: char| c == ')' || c == ']')
+                    .to_string(),
+            );
+        }
+    }
+
+    None
+}
+
+/// Convert `<label>: <url>` lines into Slack links, or drop them when `button_url`
+/// already links to the same place
+fn format_slack_message(message: &str, button_url: Option<&str>) -> String {
+    let mut out = Vec::new();
+    for line in message.lines() {
+        let trimmed = line.trim();
+        if let Some((label, rest)) = trimmed.split_once(':')
+            && !label.trim().is_empty()
+            && !rest.trim().contains(char::is_whitespace)
+            && let Some(url) = extract_url(rest)
+        {
+            if button_url != Some(url.as_str()) {
+                out.push(format!("<{url}|{}>", label.trim()));
+            }
+            continue;
+        }
+        out.push(trimmed.to_string());
+    }
+    out.join("\n")
+}
+
+fn status_label(locale: Locale, event: NotificationEvent) -> String {
+    let emoji = match event {
+        NotificationEvent::TaskStarted => "▶️",
+        NotificationEvent::AttemptCompleted => "✅",
+        NotificationEvent::AttemptFailed => "❌",
+        NotificationEvent::ApprovalNeeded => "✋",
+        NotificationEvent::PrOpened => "🔀",
+        NotificationEvent::PrMerged => "🎉",
+        NotificationEvent::MergeConflict => "⚠️",
+        NotificationEvent::Summary => "📊",
+        NotificationEvent::TaskUnblocked => "🔓",
+        NotificationEvent::TaskOverdue => "⏰",
+        NotificationEvent::WipLimitExceeded => "🚧",
+        NotificationEvent::EpicCompleted => "🏁",
+        NotificationEvent::BudgetLimitReached => "💸",
+        NotificationEvent::AttemptStalled => "💤",
+    };
+    format!("{emoji} {}", i18n::event_label(locale, event))
+}
+
+/// Build a Block Kit payload, with `text` kept as the fallback for notifications.
+///
+/// `mention` is a member ID; it goes in the fallback text too, which is what Slack uses for
+/// the mention's own notification.
+fn build_message(
+    context: &NotificationContext,
+    title: &str,
+    message: &str,
+    mention: Option<&str>,
+) -> Value {
+    let locale = context.locale;
+    let task_url = context.task_url.as_deref();
+    let mut body = format_slack_message(message, task_url);
+    if let Some(member_id) = mention {
+        body = format!("<@{member_id}> {body}");
+    }
+
+    let header: String = context
+        .task_title
+        .as_deref()
+        .unwrap_or(title)
+        .chars()
+        .take(HEADER_MAX_CHARS)
+        .collect();
+    let mut blocks = vec![json!({
+        "type": "header",
+        "text": { "type": "plain_text", "text": header, "emoji": true },
+    })];
+
+    let mut fields = vec![json!({
+        "type": "mrkdwn",
+        "text": format!(
+            "*{}*\n{}",
+            i18n::t(locale, "field-status", &[]),
+            status_label(locale, context.event)
+        ),
+    })];
+    if let Some(duration) = context.attempt_duration {
+        fields.push(json!({
+            "type": "mrkdwn",
+            "text": format!(
+                "*{}*\n{}",
+                i18n::t(locale, "field-duration", &[]),
+                format_duration(duration)
+            ),
+        }));
+    }
+    blocks.push(json!({ "type": "section", "fields": fields }));
+
+    if !body.trim().is_empty() {
+        blocks.push(json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": body },
+        }));
+    }
+
+    if let Some(project_name) = &context.project_name {
+        blocks.push(json!({
+            "type": "context",
+            "elements": [
+                {
+                    "type": "mrkdwn",
+                    "text": format!(
+                        "{}: *{}*",
+                        i18n::t(locale, "field-project", &[]),
+                        escape_mrkdwn(project_name)
+                    ),
+                },
+            ],
+        }));
+    }
+
+    if let Some(url) = task_url {
+        blocks.push(json!({
+            "type": "actions",
+            "elements": [{
+                "type": "button",
+                "text": { "type": "plain_text", "text": i18n::t(locale, "open-task", &[]) },
+                "url": url,
+            }],
+        }));
+    }
+
+    json!({
+        "text": format!("*{}*\n{}", escape_mrkdwn(title), body),
+        "blocks": blocks,
+    })
+}
+
+/// Send a notification through a Slack incoming webhook
+pub async fn send_slack(
+    webhook_url: &str,
+    context: &NotificationContext,
+    title: &str,
+    message: &str,
+    mention: Option<&str>,
+    retry: &NotificationRetryPolicy,
+) -> Result<(), NotificationError> {
+    let payload = build_message(context, title, message, mention);
+    let client = reqwest::Client::new();
+    send_checked(client.post(webhook_url).json(&payload), retry).await
+}
+
+/// Post a notification with a bot token, as a reply when `thread_ts` is given.
+///
+/// Returns the `ts` of the posted message so it can become the parent of later replies.
+pub async fn post_message(
+    bot: &SlackBot,
+    thread_ts: Option<&str>,
+    context: &NotificationContext,
+    title: &str,
+    message: &str,
+    mention: Option<&str>,
+    retry: &NotificationRetryPolicy,
+) -> Result<String, NotificationError> {
+    let mut payload = build_message(context, title, message, mention);
+    payload["channel"] = json!(bot.channel);
+    if let Some(thread_ts) = thread_ts {
+        payload["thread_ts"] = json!(thread_ts);
+    }
+
+    let client = reqwest::Client::new();
+    let request = client
+        .post(POST_MESSAGE_URL)
+        .bearer_auth(&bot.token)
+        .json(&payload);
+    // The Web API reports failures in the body with a 200 status
+    let response: PostMessageResponse = send_with_retry(request, retry).await?.json().await?;
+    match response {
+        PostMessageResponse {
+            ok: true,
+            ts: Some(ts),
+            ..
+        } => Ok(ts),
+        PostMessageResponse { error, .. } => Err(NotificationError::Delivery(format!(
+            "Slack API error: {}",
+            error.as_deref().unwrap_or("unknown")
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_format_slack_message_links() {
+        let message = "状态: ✅\n点击查看: http://localhost:3000/t/1";
+        assert_eq!(
+            format_slack_message(message, None),
+            "状态: ✅\n<http://localhost:3000/t/1|点击查看>"
+        );
+        assert_eq!(
+            format_slack_message(message, Some("http://localhost:3000/t/1")),
+            "状态: ✅"
+        );
+        assert_eq!(
+            format_slack_message("Status: ✅\nView: http://localhost:3000/t/1", None),
+            "Status: ✅\n<http://localhost:3000/t/1|View>"
+        );
+    }
+
+    #[test]
+    fn test_build_message_blocks() {
+        let mut context = NotificationContext::new(NotificationEvent::AttemptFailed)
+            .with_task(Uuid::new_v4(), Uuid::new_v4())
+            .with_names("Kanban", "Fix login")
+            .with_attempt_duration(Duration::seconds(75));
+        context.task_url = Some("http://localhost:3000/t/1".to_string());
+
+        let payload = build_message(&context, "[Kanban]: Fix login", "Tests failed", None);
+        let blocks = payload["blocks"].as_array().unwrap();
+        let types: Vec<&str> = blocks
+            .iter()
+            .map(|block| block["type"].as_str().unwrap())
+            .collect();
+
+        assert_eq!(
+            types,
+            ["header", "section", "section", "context", "actions"]
+        );
+        assert_eq!(blocks[0]["text"]["text"], "Fix login");
+        assert_eq!(blocks[1]["fields"][1]["text"], "*Duration*\n1m 15s");
+        assert_eq!(blocks[4]["elements"][0]["url"], "http://localhost:3000/t/1");
+        assert_eq!(payload["text"], "*[Kanban]: Fix login*\nTests failed");
+    }
+
+    #[test]
+    fn test_build_message_mention() {
+        let context = NotificationContext::new(NotificationEvent::AttemptFailed);
+        let payload = build_message(&context, "Fix login", "Tests failed", Some("U0123ABCD"));
+        assert_eq!(
+            payload["blocks"][2]["text"]["text"],
+            "<@U0123ABCD> Tests failed"
+        );
+        assert_eq!(payload["text"], "*Fix login*\n<@U0123ABCD> Tests failed");
+    }
+}
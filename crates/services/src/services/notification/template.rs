@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use chrono::Duration;
+
+/// Replace `{{name}}` placeholders with values from `vars`.
+///
+/// Whitespace inside the braces is ignored. Unknown variables are left untouched so
+/// typos show up in the delivered notification instead of silently disappearing.
+pub fn render(template: &str, vars: &HashMap<&str, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            // Unterminated placeholder, emit the remainder verbatim
+            out.push_str(&rest[start..]);
+            return out;
+        };
+
+        let name = after_open[..end].trim();
+        match vars.get(name) {
+            Some(value) => out.push_str(value),
+            None => out.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after_open[end + 2..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Human-readable duration such as `1h 2m`, `3m 12s` or `45s`
+pub fn format_duration(duration: Duration) -> String {
+    let total = duration.num_seconds().max(0);
+    let (hours, minutes, seconds) = (total / 3600, (total % 3600) / 60, total % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> HashMap<&'static str, String> {
+        HashMap::from([
+            ("task.title", "Fix login".to_string()),
+            ("url", "http://localhost:3000/t/1".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_render_substitutes_variables() {
+        assert_eq!(
+            render("{{task.title}} -> {{ url }}", &vars()),
+            "Fix login -> http://localhost:3000/t/1"
+        );
+    }
+
+    #[test]
+    fn test_render_keeps_unknown_and_unterminated_placeholders() {
+        assert_eq!(
+            render("{{nope}} {{task.title", &vars()),
+            "{{nope}} {{task.title"
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::seconds(45)), "45s");
+        assert_eq!(format_duration(Duration::seconds(192)), "3m 12s");
+        assert_eq!(format_duration(Duration::seconds(3720)), "1h 2m");
+    }
+}
@@ -0,0 +1,255 @@
+use std::{io::Write, sync::OnceLock};
+
+use aes_gcm::{Aes128Gcm, KeyInit, Nonce, aead::Aead};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use hkdf::Hkdf;
+use p256::{
+    PublicKey, SecretKey,
+    ecdsa::{Signature, SigningKey, signature::Signer},
+    elliptic_curve::sec1::ToEncodedPoint,
+};
+use rand::{RngCore, rngs::OsRng};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::Sha256;
+use thiserror::Error;
+
+/// Record size advertised in the aes128gcm header; payloads always fit in one record
+const RECORD_SIZE: u32 = 4096;
+/// How long VAPID tokens stay valid; push services reject anything over 24h
+const VAPID_TTL_SECS: i64 = 12 * 60 * 60;
+const VAPID_SUBJECT: &str = "https://www.vibekanban.com";
+
+static VAPID_KEYS: OnceLock<VapidKeys> = OnceLock::new();
+
+#[derive(Debug, Error)]
+pub enum WebPushError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Base64(#[from] base64::DecodeError),
+    #[error(transparent)]
+    Url(#[from] url::ParseError),
+    #[error("Invalid {0}")]
+    InvalidKey(&'static str),
+    #[error("Failed to encrypt push payload")]
+    Encryption,
+}
+
+/// Notification shown by the frontend's service worker
+#[derive(Debug, Clone, Serialize)]
+pub struct WebPushPayload {
+    pub title: String,
+    pub body: String,
+    pub url: Option<String>,
+    /// Lets the browser replace an older notification about the same task
+    pub tag: Option<String>,
+}
+
+/// Application server key pair identifying this instance to push services (RFC 8292)
+#[derive(Debug, Clone)]
+pub struct VapidKeys {
+    secret: SecretKey,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredVapidKeys {
+    private_key: String,
+}
+
+impl VapidKeys {
+    /// Keys for this installation, generated and saved on first use
+    pub fn get() -> Result<&'static VapidKeys, WebPushError> {
+        if let Some(keys) = VAPID_KEYS.get() {
+            return Ok(keys);
+        }
+        let keys = Self::load_or_generate()?;
+        Ok(VAPID_KEYS.get_or_init(|| keys))
+    }
+
+    fn load_or_generate() -> Result<Self, WebPushError> {
+        let path = utils::assets::vapid_keys_path();
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => {
+                let stored: StoredVapidKeys = serde_json::from_str(&contents)?;
+                let secret = SecretKey::from_slice(&URL_SAFE_NO_PAD.decode(stored.private_key)?)
+                    .map_err(|_| WebPushError::InvalidKey("VAPID private key"))?;
+                Ok(Self { secret })
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let secret = SecretKey::random(&mut OsRng);
+                let stored = StoredVapidKeys {
+                    private_key: URL_SAFE_NO_PAD.encode(secret.to_bytes()),
+                };
+                let mut options = std::fs::OpenOptions::new();
+                options.write(true).create_new(true);
+                #[cfg(unix)]
+                std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+                options
+                    .open(&path)?
+                    .write_all(serde_json::to_string_pretty(&stored)?.as_bytes())?;
+                tracing::info!("Generated VAPID keys at {}", path.display());
+                Ok(Self { secret })
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Uncompressed public key, base64url encoded, as `PushManager.subscribe` expects it
+    pub fn public_key(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.secret.public_key().to_encoded_point(false).as_bytes())
+    }
+
+    /// `Authorization` header value for a request to `endpoint`
+    pub fn authorization(&self, endpoint: &str) -> Result<String, WebPushError> {
+        let audience = url::Url::parse(endpoint)?.origin().ascii_serialization();
+        let claims = json!({
+            "aud": audience,
+            "exp": chrono::Utc::now().timestamp() + VAPID_TTL_SECS,
+            "sub": VAPID_SUBJECT,
+        });
+        let unsigned = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(br#"{"typ":"JWT","alg":"ES256"}"#),
+            URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims)?)
+        );
+        let signature: Signature = SigningKey::from(&self.secret).sign(unsigned.as_bytes());
+        Ok(format!(
+            "vapid t={}.{}, k={}",
+            unsigned,
+            URL_SAFE_NO_PAD.encode(signature.to_bytes()),
+            self.public_key()
+        ))
+    }
+}
+
+/// Check a browser's subscription keys before storing them
+pub fn validate_subscription_keys(p256dh: &str, auth: &str) -> Result<(), WebPushError> {
+    PublicKey::from_sec1_bytes(&URL_SAFE_NO_PAD.decode(p256dh)?)
+        .map_err(|_| WebPushError::InvalidKey("p256dh key"))?;
+    if URL_SAFE_NO_PAD.decode(auth)?.len() != 16 {
+        return Err(WebPushError::InvalidKey("auth secret"));
+    }
+    Ok(())
+}
+
+/// Content encryption key and nonce shared by sender and browser (RFC 8291, section 3.4)
+fn derive_keys(
+    ecdh_secret: &[u8],
+    auth_secret: &[u8],
+    ua_public: &[u8],
+    as_public: &[u8],
+    salt: &[u8],
+) -> Result<([u8; 16], [u8; 12]), WebPushError> {
+    let mut key_info = b"WebPush: info\0".to_vec();
+    key_info.extend_from_slice(ua_public);
+    key_info.extend_from_slice(as_public);
+    let mut ikm = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(auth_secret), ecdh_secret)
+        .expand(&key_info, &mut ikm)
+        .map_err(|_| WebPushError::Encryption)?;
+
+    let prk = Hkdf::<Sha256>::new(Some(salt), &ikm);
+    let mut cek = [0u8; 16];
+    let mut nonce = [0u8; 12];
+    prk.expand(b"Content-Encoding: aes128gcm\0", &mut cek)
+        .map_err(|_| WebPushError::Encryption)?;
+    prk.expand(b"Content-Encoding: nonce\0", &mut nonce)
+        .map_err(|_| WebPushError::Encryption)?;
+    Ok((cek, nonce))
+}
+
+/// Encrypt a payload for one subscription using the `aes128gcm` content encoding
+pub fn encrypt(p256dh: &str, auth: &str, payload: &[u8]) -> Result<Vec<u8>, WebPushError> {
+    let ua_public_bytes = URL_SAFE_NO_PAD.decode(p256dh)?;
+    let auth_secret = URL_SAFE_NO_PAD.decode(auth)?;
+    let ua_public = PublicKey::from_sec1_bytes(&ua_public_bytes)
+        .map_err(|_| WebPushError::InvalidKey("p256dh key"))?;
+
+    let as_secret = SecretKey::random(&mut OsRng);
+    let as_public = as_secret.public_key().to_encoded_point(false);
+    let shared = p256::ecdh::diffie_hellman(as_secret.to_nonzero_scalar(), ua_public.as_affine());
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+
+    let (cek, nonce) = derive_keys(
+        shared.raw_secret_bytes(),
+        &auth_secret,
+        &ua_public_bytes,
+        as_public.as_bytes(),
+        &salt,
+    )?;
+
+    // A single record ends with the 0x02 delimiter and needs no further padding
+    let mut plaintext = payload.to_vec();
+    plaintext.push(0x02);
+    let ciphertext = Aes128Gcm::new_from_slice(&cek)
+        .map_err(|_| WebPushError::Encryption)?
+        .encrypt(&Nonce::from(nonce), plaintext.as_slice())
+        .map_err(|_| WebPushError::Encryption)?;
+
+    let mut body = Vec::with_capacity(21 + as_public.len() + ciphertext.len());
+    body.extend_from_slice(&salt);
+    body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+    body.push(as_public.len() as u8);
+    body.extend_from_slice(as_public.as_bytes());
+    body.extend_from_slice(&ciphertext);
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decrypt the way a browser would
+    fn decrypt(ua_secret: &SecretKey, auth_secret: &[u8], body: &[u8]) -> Vec<u8> {
+        let (salt, rest) = body.split_at(16);
+        let key_len = rest[4] as usize;
+        let (as_public_bytes, ciphertext) = rest[5..].split_at(key_len);
+        let as_public = PublicKey::from_sec1_bytes(as_public_bytes).unwrap();
+        let shared =
+            p256::ecdh::diffie_hellman(ua_secret.to_nonzero_scalar(), as_public.as_affine());
+        let ua_public = ua_secret.public_key().to_encoded_point(false);
+
+        let (cek, nonce) = derive_keys(
+            shared.raw_secret_bytes(),
+            auth_secret,
+            ua_public.as_bytes(),
+            as_public_bytes,
+            salt,
+        )
+        .unwrap();
+        let mut plaintext = Aes128Gcm::new_from_slice(&cek)
+            .unwrap()
+            .decrypt(Nonce::from_slice(&nonce), ciphertext)
+            .unwrap();
+        assert_eq!(plaintext.pop(), Some(0x02));
+        plaintext
+    }
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let ua_secret = SecretKey::random(&mut OsRng);
+        let p256dh = URL_SAFE_NO_PAD.encode(ua_secret.public_key().to_encoded_point(false));
+        let auth_secret = [7u8; 16];
+        let auth = URL_SAFE_NO_PAD.encode(auth_secret);
+
+        let body = encrypt(&p256dh, &auth, b"{\"title\":\"Done\"}").unwrap();
+        assert_eq!(&body[16..20], &RECORD_SIZE.to_be_bytes());
+        assert_eq!(
+            decrypt(&ua_secret, &auth_secret, &body),
+            b"{\"title\":\"Done\"}"
+        );
+    }
+
+    #[test]
+    fn test_validate_subscription_keys() {
+        let ua_secret = SecretKey::random(&mut OsRng);
+        let p256dh = URL_SAFE_NO_PAD.encode(ua_secret.public_key().to_encoded_point(false));
+        assert!(validate_subscription_keys(&p256dh, &URL_SAFE_NO_PAD.encode([1u8; 16])).is_ok());
+        assert!(validate_subscription_keys(&p256dh, &URL_SAFE_NO_PAD.encode([1u8; 8])).is_err());
+        assert!(validate_subscription_keys("not-a-key", "AAAA").is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use uuid::Uuid;
+
+use super::{NotificationError, send_checked};
+use crate::services::config::{NotificationEvent, NotificationRetryPolicy};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Header carrying the HMAC-SHA256 signature of the request body
+pub const SIGNATURE_HEADER: &str = "X-Signature";
+
+/// JSON body POSTed to every configured webhook URL
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookPayload {
+    pub event: NotificationEvent,
+    pub project_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    /// Label names on the task, for routing on the receiving end
+    pub labels: Vec<String>,
+    pub title: String,
+    pub message: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Sign a webhook body with the shared secret.
+///
+/// Returns the header value in the format `sha256=<hex-signature>`, matching the
+/// scheme GitHub uses for `X-Hub-Signature-256` so existing verifiers can be reused.
+pub fn sign_payload(secret: &[u8], body: &[u8]) -> String {
+    // HMAC accepts keys of any length, so this cannot fail
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// POST the payload to each URL, signing the body when a secret is configured.
+///
+/// Every URL is attempted; failures are reported together once all have been tried.
+pub async fn send_webhooks(
+    urls: &[String],
+    secret: Option<String>,
+    payload: &WebhookPayload,
+    retry: &NotificationRetryPolicy,
+) -> Result<(), NotificationError> {
+    let body = serde_json::to_vec(payload)
+        .map_err(|e| NotificationError::Delivery(format!("Invalid webhook payload: {e}")))?;
+    let signature = secret.map(|secret| sign_payload(secret.as_bytes(), &body));
+
+    let client = reqwest::Client::new();
+    let mut failures = Vec::new();
+    let mut gave_up_after = None;
+    for (index, url) in urls.iter().enumerate() {
+        let mut request = client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.clone());
+        if let Some(signature) = &signature {
+            request = request.header(SIGNATURE_HEADER, signature);
+        }
+
+        if let Err(e) = send_checked(request, retry).await {
+            if let NotificationError::RetriesExhausted { attempts, .. } = &e {
+                gave_up_after = Some(*attempts);
+            }
+            // Refer to URLs by position, they may contain credentials
+            failures.push(format!("webhook #{}: {}", index + 1, e));
+        }
+    }
+
+    match (failures.is_empty(), gave_up_after) {
+        (true, _) => Ok(()),
+        (false, Some(attempts)) => Err(NotificationError::RetriesExhausted {
+            attempts,
+            message: failures.join("; "),
+        }),
+        (false, None) => Err(NotificationError::Delivery(failures.join("; "))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload_matches_hmac() {
+        // RFC 4231, test case 2
+        assert_eq!(
+            sign_payload(b"Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_sign_payload_depends_on_secret() {
+        let body = b"payload";
+        assert_ne!(
+            sign_payload(b"secret-a", body),
+            sign_payload(b"secret-b", body)
+        );
+    }
+
+    #[test]
+    fn test_payload_serialization() {
+        let payload = WebhookPayload {
+            event: NotificationEvent::AttemptFailed,
+            project_id: None,
+            task_id: None,
+            labels: vec!["backend".to_string()],
+            title: "title".to_string(),
+            message: "message".to_string(),
+            timestamp: DateTime::<Utc>::from_timestamp(0, 0).unwrap(),
+        };
+        let value = serde_json::to_value(&payload).unwrap();
+        assert_eq!(value["event"], "ATTEMPT_FAILED");
+        assert_eq!(value["timestamp"], "1970-01-01T00:00:00Z");
+        assert!(value["task_id"].is_null());
+        assert_eq!(value["labels"], serde_json::json!(["backend"]));
+    }
+}
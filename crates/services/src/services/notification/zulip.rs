@@ -0,0 +1,107 @@
+use serde::Serialize;
+
+use super::{
+    NotificationError,
+    i18n::{self, Locale},
+    send_checked,
+};
+use crate::services::config::NotificationRetryPolicy;
+
+/// Zulip truncates topics longer than this
+const TOPIC_MAX_CHARS: usize = 60;
+/// Used when neither a topic nor a project name is available
+const DEFAULT_TOPIC: &str = "Vibe Kanban";
+
+/// Bot credentials and destination for Zulip stream messages
+#[derive(Debug, Clone)]
+pub struct ZulipTarget {
+    pub site_url: String,
+    pub bot_email: String,
+    pub api_key: String,
+    pub stream: String,
+    pub topic: Option<String>,
+}
+
+/// Form body, see https://zulip.com/api/send-message
+#[derive(Debug, Serialize)]
+struct ZulipMessage<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    to: &'a str,
+    topic: String,
+    content: String,
+}
+
+/// The configured topic, falling back to the project so each project gets its own topic
+fn resolve_topic(topic: Option<&str>, project_name: Option<&str>) -> String {
+    topic
+        .or(project_name)
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .unwrap_or(DEFAULT_TOPIC)
+        .chars()
+        .take(TOPIC_MAX_CHARS)
+        .collect()
+}
+
+fn build_content(locale: Locale, title: &str, message: &str, task_url: Option<&str>) -> String {
+    let mut content = format!("**{title}**\n{message}");
+    if let Some(url) = task_url {
+        content.push_str(&format!("\n[{}]({url})", i18n::t(locale, "open-task", &[])));
+    }
+    content
+}
+
+pub async fn send_zulip(
+    target: &ZulipTarget,
+    locale: Locale,
+    project_name: Option<&str>,
+    title: &str,
+    message: &str,
+    task_url: Option<&str>,
+    retry: &NotificationRetryPolicy,
+) -> Result<(), NotificationError> {
+    let body = ZulipMessage {
+        kind: "stream",
+        to: &target.stream,
+        topic: resolve_topic(target.topic.as_deref(), project_name),
+        content: build_content(locale, title, message, task_url),
+    };
+
+    let url = format!("{}/api/v1/messages", target.site_url.trim_end_matches('/'));
+    let client = reqwest::Client::new();
+    let request = client
+        .post(url)
+        .basic_auth(&target.bot_email, Some(&target.api_key))
+        .form(&body);
+    send_checked(request, retry).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topic_defaults_to_project_name() {
+        assert_eq!(resolve_topic(None, Some("Kanban")), "Kanban");
+        assert_eq!(resolve_topic(Some("Deploys"), Some("Kanban")), "Deploys");
+        assert_eq!(resolve_topic(None, None), DEFAULT_TOPIC);
+        assert_eq!(
+            resolve_topic(Some(&"x".repeat(100)), None).len(),
+            TOPIC_MAX_CHARS
+        );
+    }
+
+    #[test]
+    fn test_content_links_task() {
+        assert_eq!(
+            build_content(
+                Locale::En,
+                "Fix login",
+                "Done",
+                Some("http://localhost:3000/t/1")
+            ),
+            "**Fix login**\nDone\n[Open in Vibe Kanban](http://localhost:3000/t/1)"
+        );
+    }
+}
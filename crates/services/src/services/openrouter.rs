@@ -0,0 +1,129 @@
+//! Spend of executions whose model is routed through OpenRouter. Agents don't pass on the cost
+//! OpenRouter reports per request, so it is read from the API key's usage before and after the
+//! run. Runs sharing a key at the same time can't be told apart, so those fall back to the
+//! usage read from the executor's output.
+
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use executors::openrouter::{KEY_URL, parse_key_usage, usage_delta};
+use serde_json::Value;
+use thiserror::Error;
+use uuid::Uuid;
+
+const KEY_USAGE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time OpenRouter gets to settle the cost of a run's last requests before it is read
+pub const SETTLE_DELAY: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Error)]
+pub enum OpenRouterError {
+    #[error("network error: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("OpenRouter returned {0}")]
+    Http(u16),
+    #[error("OpenRouter's key response has no usage")]
+    MissingUsage,
+}
+
+/// Credits in USD spent by the key so far
+pub async fn key_usage(api_key: &str) -> Result<f64, OpenRouterError> {
+    let response = reqwest::Client::new()
+        .get(KEY_URL)
+        .bearer_auth(api_key)
+        .timeout(KEY_USAGE_TIMEOUT)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(OpenRouterError::Http(response.status().as_u16()));
+    }
+    let body = response.json::<Value>().await?;
+    parse_key_usage(&body).ok_or(OpenRouterError::MissingUsage)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackedRun {
+    pub api_key: String,
+    pub model: String,
+    pub usage_before: f64,
+    /// Another run used the same key while this one was running
+    pub overlapped: bool,
+}
+
+impl TrackedRun {
+    /// Cost of the run given the key's usage once it finished; `None` when another run shared
+    /// the key meanwhile
+    pub fn cost(&self, usage_after: f64) -> Option<f64> {
+        (!self.overlapped).then(|| usage_delta(self.usage_before, usage_after))
+    }
+}
+
+/// Executions currently running on an OpenRouter key
+#[derive(Debug, Default)]
+pub struct OpenRouterRuns {
+    runs: Mutex<HashMap<Uuid, TrackedRun>>,
+}
+
+impl OpenRouterRuns {
+    pub fn start(&self, exec_id: Uuid, api_key: String, model: String, usage_before: f64) {
+        let mut runs = self.runs.lock().unwrap();
+        let mut overlapped = false;
+        for run in runs.values_mut().filter(|run| run.api_key == api_key) {
+            run.overlapped = true;
+            overlapped = true;
+        }
+        runs.insert(
+            exec_id,
+            TrackedRun {
+                api_key,
+                model,
+                usage_before,
+                overlapped,
+            },
+        );
+    }
+
+    /// Key a run is tracked on. A run stays tracked until `finish`, including while its cost
+    /// settles after it ended, so runs starting on the key meanwhile count as overlapping.
+    pub fn api_key(&self, exec_id: &Uuid) -> Option<String> {
+        self.runs
+            .lock()
+            .unwrap()
+            .get(exec_id)
+            .map(|run| run.api_key.clone())
+    }
+
+    pub fn finish(&self, exec_id: &Uuid) -> Option<TrackedRun> {
+        self.runs.lock().unwrap().remove(exec_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_run_cost() {
+        let runs = OpenRouterRuns::default();
+        let exec_id = Uuid::new_v4();
+        runs.start(exec_id, "sk-or-1".into(), "qwen/qwen3-coder".into(), 4.0);
+
+        assert_eq!(runs.api_key(&exec_id).as_deref(), Some("sk-or-1"));
+        let run = runs.finish(&exec_id).unwrap();
+        assert_eq!(run.cost(4.5), Some(0.5));
+        assert!(runs.api_key(&exec_id).is_none());
+        assert!(runs.finish(&exec_id).is_none());
+    }
+
+    #[test]
+    fn test_overlapping_runs_on_one_key() {
+        let runs = OpenRouterRuns::default();
+        let (first, second, other_key) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        runs.start(first, "sk-or-1".into(), "a".into(), 1.0);
+        runs.start(other_key, "sk-or-2".into(), "b".into(), 7.0);
+        runs.start(second, "sk-or-1".into(), "c".into(), 1.2);
+
+        assert_eq!(runs.finish(&first).unwrap().cost(2.0), None);
+        assert_eq!(runs.finish(&second).unwrap().cost(2.0), None);
+        assert_eq!(runs.finish(&other_key).unwrap().cost(7.25), Some(0.25));
+    }
+}
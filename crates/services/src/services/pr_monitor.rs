@@ -16,7 +16,9 @@ use tracing::{debug, error, info};
 
 use crate::services::{
     analytics::AnalyticsContext,
+    config::NotificationEvent,
     github::{GitHubRepoInfo, GitHubService, GitHubServiceError},
+    notification::{NotificationContext, NotificationService, i18n},
     share::SharePublisher,
 };
 
@@ -36,6 +38,7 @@ pub struct PrMonitorService {
     poll_interval: Duration,
     analytics: Option<AnalyticsContext>,
     publisher: Option<SharePublisher>,
+    notification_service: NotificationService,
 }
 
 impl PrMonitorService {
@@ -43,12 +46,14 @@ impl PrMonitorService {
         db: DBService,
         analytics: Option<AnalyticsContext>,
         publisher: Option<SharePublisher>,
+        notification_service: NotificationService,
     ) -> tokio::task::JoinHandle<()> {
         let service = Self {
             db,
             poll_interval: Duration::from_secs(60), // Check every minute
             analytics,
             publisher,
+            notification_service,
         };
         tokio::spawn(async move {
             service.start().await;
@@ -130,6 +135,24 @@ impl PrMonitorService {
                 );
                 Task::update_status(&self.db.pool, workspace.task_id, TaskStatus::Done).await?;
 
+                if let Some(task) = Task::find_by_id(&self.db.pool, workspace.task_id).await?
+                    && let Some(project) = task.parent_project(&self.db.pool).await?
+                {
+                    let context = NotificationContext::new(NotificationEvent::PrMerged)
+                        .with_task(project.id, task.id)
+                        .with_workspace(workspace.id)
+                        .with_names(&project.name, &task.title);
+                    let locale = self.notification_service.locale().await;
+                    let number = pr_merge.pr_info.number.to_string();
+                    self.notification_service
+                        .notify(
+                            &context,
+                            &i18n::t(locale, "pr-merged-title", &[("number", number.as_str())]),
+                            &i18n::t(locale, "pr-merged", &[("task", task.title.as_str())]),
+                        )
+                        .await;
+                }
+
                 // Track analytics event
                 if let Some(analytics) = &self.analytics
                     && let Ok(Some(task)) = Task::find_by_id(&self.db.pool, workspace.task_id).await
@@ -0,0 +1,247 @@
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use executors::{
+    env::ExecutionEnv,
+    executors::{AvailabilityInfo, CodingAgent, StandardCodingAgentExecutor},
+    logs::{
+        NormalizedEntry, NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch,
+    },
+    process_limits::{ProcessConfinement, ProcessLimits},
+    profile::{ExecutorConfigs, ExecutorProfileId},
+    profile_test::{CHECK_FILE, ProfileTestResult, check_prompt},
+    sandbox::{Sandbox, SandboxConfig},
+};
+use futures::{FutureExt, StreamExt, TryStreamExt, stream::select};
+use tokio_util::io::ReaderStream;
+use utils::{log_msg::LogMsg, msg_store::MsgStore};
+use uuid::Uuid;
+
+use crate::services::openrouter::{self, OpenRouterError};
+
+const SPAWN_TIMEOUT: Duration = Duration::from_secs(30);
+const RUN_TIMEOUT: Duration = Duration::from_secs(180);
+/// Time left for log normalization to catch up once the agent exits
+const NORMALIZE_GRACE: Duration = Duration::from_secs(1);
+/// First replies slower than this get a warning
+const SLOW_FIRST_RESPONSE: Duration = Duration::from_secs(30);
+/// Tail of stderr kept as the error when the agent never replied
+const STDERR_TAIL_CHARS: usize = 1000;
+
+struct CheckRun {
+    entries: Vec<NormalizedEntry>,
+    stderr: String,
+    first_response: Option<Duration>,
+    duration: Duration,
+    finished: bool,
+}
+
+/// Runs a tiny canned task against the profile in a scratch repository, so a profile with bad
+/// credentials, a missing model or no tool access fails here rather than mid-task. The agent
+/// runs in the sandbox and under the process limits a real attempt would.
+pub async fn test_executor_profile(
+    profile_id: &ExecutorProfileId,
+    sandbox_config: SandboxConfig,
+    process_limits: ProcessLimits,
+) -> ProfileTestResult {
+    let Some(agent) = ExecutorConfigs::get_cached().get_coding_agent(profile_id) else {
+        let mut result = ProfileTestResult::new(AvailabilityInfo::NotFound, None);
+        result.error = Some(format!("Profile `{profile_id}` not found"));
+        return result;
+    };
+    let mut result = ProfileTestResult::new(agent.get_availability_info(), agent.model());
+    if !result.availability.is_available() {
+        result.warnings.push(
+            "No installation or login was detected for this agent; the test run may fail"
+                .to_string(),
+        );
+    }
+
+    if let Some((_, api_key)) = agent.openrouter() {
+        match openrouter::key_usage(&api_key).await {
+            Ok(_) => {}
+            Err(OpenRouterError::Http(401 | 403)) => {
+                result.auth_valid = Some(false);
+                result.error = Some("OpenRouter rejected the API key".to_string());
+                return result;
+            }
+            Err(e) => result
+                .warnings
+                .push(format!("Could not check the OpenRouter key: {e}")),
+        }
+    }
+
+    let dir = match tempfile::Builder::new().prefix("vk-profile-test").tempdir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            result.error = Some(format!("Could not create a scratch directory: {e}"));
+            return result;
+        }
+    };
+    let token = Uuid::new_v4().simple().to_string();
+    if let Err(e) = git2::Repository::init(dir.path()) {
+        result.error = Some(format!("Could not create a scratch repository: {e}"));
+        return result;
+    }
+    if let Err(e) = tokio::fs::write(dir.path().join(CHECK_FILE), &token).await {
+        result.error = Some(format!("Could not write {CHECK_FILE}: {e}"));
+        return result;
+    }
+
+    let mut env = ExecutionEnv::new();
+    if sandbox_config.enabled {
+        let name = format!("vibe-kanban-profile-test-{token}");
+        let mounts = vec![dir.path().to_path_buf()];
+        match Sandbox::new(sandbox_config, name, mounts, dir.path()).await {
+            Ok(sandbox) => env.sandbox = Some(sandbox),
+            Err(e) => {
+                result.error = Some(format!("Could not set up the sandbox: {e}"));
+                return result;
+            }
+        }
+    }
+    if !process_limits.is_empty() {
+        env.confinement = Some(ProcessConfinement::new(process_limits).await);
+    }
+
+    let run = run_check(&agent, dir.path(), &env).await;
+    if let Some(sandbox) = &env.sandbox
+        && let Err(e) = sandbox.remove().await
+    {
+        tracing::debug!("Sandbox container {} already gone: {}", sandbox.name, e);
+    }
+    let run = match run {
+        Ok(run) => run,
+        Err(e) => {
+            result.error = Some(e);
+            return result;
+        }
+    };
+    result.first_response_ms = run.first_response.map(|elapsed| elapsed.as_millis() as u64);
+    result.duration_ms = Some(run.duration.as_millis() as u64);
+    result.record_run(&run.entries, &run.stderr, &token);
+    if run
+        .first_response
+        .is_some_and(|elapsed| elapsed > SLOW_FIRST_RESPONSE)
+    {
+        result.warnings.push(format!(
+            "The first reply took over {} seconds",
+            SLOW_FIRST_RESPONSE.as_secs()
+        ));
+    }
+    if !run.finished && result.error.is_none() {
+        result.error = Some(format!(
+            "The agent did not finish within {} seconds",
+            RUN_TIMEOUT.as_secs()
+        ));
+    }
+    result
+}
+
+async fn run_check(
+    agent: &CodingAgent,
+    dir: &Path,
+    env: &ExecutionEnv,
+) -> Result<CheckRun, String> {
+    let started = Instant::now();
+    let mut spawned = tokio::time::timeout(SPAWN_TIMEOUT, agent.spawn(dir, &check_prompt(), env))
+        .await
+        .map_err(|_| {
+            format!(
+                "The agent took more than {} seconds to start",
+                SPAWN_TIMEOUT.as_secs()
+            )
+        })?
+        .map_err(|e| format!("Could not start the agent: {e}"))?;
+    if let Err(e) = env.confine(&mut spawned.child) {
+        tracing::warn!("Failed to apply process limits to the profile test: {}", e);
+    }
+
+    let store = Arc::new(MsgStore::new());
+    let (Some(out), Some(err)) = (
+        spawned.child.inner().stdout.take(),
+        spawned.child.inner().stderr.take(),
+    ) else {
+        let _ = spawned.child.kill().await;
+        return Err("The agent's output could not be captured".to_string());
+    };
+    let out = ReaderStream::new(out)
+        .map_ok(|chunk| LogMsg::Stdout(String::from_utf8_lossy(&chunk).into_owned()));
+    let err = ReaderStream::new(err)
+        .map_ok(|chunk| LogMsg::Stderr(String::from_utf8_lossy(&chunk).into_owned()));
+    store.clone().spawn_forwarder(select(out, err));
+    agent.normalize_logs(store.clone(), dir);
+
+    let first_response = tokio::spawn({
+        let store = store.clone();
+        async move {
+            let mut stream = store.history_plus_stream();
+            while let Some(Ok(msg)) = stream.next().await {
+                if let LogMsg::JsonPatch(patch) = msg
+                    && let Some((_, entry)) = extract_normalized_entry_from_patch(&patch)
+                    && matches!(entry.entry_type, NormalizedEntryType::AssistantMessage)
+                {
+                    return Some(started.elapsed());
+                }
+            }
+            None
+        }
+    });
+
+    // Some agents stay up after answering and signal completion instead of exiting
+    let exit_signal = spawned
+        .exit_signal
+        .take()
+        .map(|rx| rx.map(|_| ()).boxed())
+        .unwrap_or_else(|| std::future::pending().boxed());
+    let finished = tokio::time::timeout(RUN_TIMEOUT, async {
+        tokio::select! {
+            _ = exit_signal => {}
+            _ = spawned.child.wait() => {}
+        }
+    })
+    .await
+    .is_ok();
+    let duration = started.elapsed();
+    let _ = spawned.child.kill().await;
+
+    store.push_finished();
+    tokio::time::sleep(NORMALIZE_GRACE).await;
+    let first_response = if first_response.is_finished() {
+        first_response.await.ok().flatten()
+    } else {
+        first_response.abort();
+        None
+    };
+
+    let mut entries = BTreeMap::new();
+    let mut stderr = String::new();
+    for msg in store.get_history() {
+        match msg {
+            LogMsg::JsonPatch(patch) => {
+                if let Some((index, entry)) = extract_normalized_entry_from_patch(&patch) {
+                    entries.insert(index, entry);
+                }
+            }
+            LogMsg::Stderr(chunk) => stderr.push_str(&chunk),
+            _ => {}
+        }
+    }
+    let tail_start = stderr
+        .char_indices()
+        .rev()
+        .nth(STDERR_TAIL_CHARS - 1)
+        .map_or(0, |(index, _)| index);
+
+    Ok(CheckRun {
+        entries: entries.into_values().collect(),
+        stderr: stderr[tail_start..].to_string(),
+        first_response,
+        duration,
+        finished,
+    })
+}
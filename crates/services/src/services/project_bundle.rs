@@ -0,0 +1,597 @@
+//! Whole-project export to a single `.tar.gz` bundle and the matching import, for moving a project
+//! between machines or keeping a copy before trying something risky.
+
+use std::{collections::HashMap, io::Read};
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    label::Label,
+    project::{CreateProject, Project, UpdateProject},
+    project_column::{CreateProjectColumn, ProjectColumn, ProjectColumnError},
+    project_repo::{CreateProjectRepo, ProjectRepo, ProjectRepoError, UpdateProjectRepo},
+    session::Session,
+    task::{CreateTask, Task, TaskPriority, TaskStatus},
+    task_checklist_item::TaskChecklistItem,
+    task_comment::TaskComment,
+    task_dependency::{TaskDependency, TaskDependencyError},
+    task_subtask::{TaskSubtask, TaskSubtaskError},
+    workspace::{Workspace, WorkspaceError},
+    workspace_repo::WorkspaceRepo,
+};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{
+    config::ProjectSettings,
+    project::{ProjectService, ProjectServiceError},
+    repo::RepoService,
+};
+
+/// Bumped whenever the bundle layout changes in a way older builds can't read
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Name of the bundle's manifest inside the archive
+const MANIFEST: &str = "project.json";
+
+#[derive(Debug, Error)]
+pub enum ProjectBundleError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("Invalid bundle: {0}")]
+    Archive(String),
+    #[error("Bundle version {0} is not supported; this build reads version {BUNDLE_VERSION}")]
+    UnsupportedVersion(u32),
+    #[error(transparent)]
+    Project(#[from] ProjectServiceError),
+    #[error(transparent)]
+    ProjectRepo(#[from] ProjectRepoError),
+    #[error(transparent)]
+    Column(#[from] ProjectColumnError),
+    #[error(transparent)]
+    Subtask(#[from] TaskSubtaskError),
+    #[error(transparent)]
+    Dependency(#[from] TaskDependencyError),
+    #[error(transparent)]
+    Workspace(#[from] WorkspaceError),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleProject {
+    pub name: String,
+    pub dev_script: Option<String>,
+    pub dev_script_working_dir: Option<String>,
+    pub default_agent_working_dir: Option<String>,
+}
+
+/// Repos are recorded by path; on import only the ones present on the new machine are attached
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleRepo {
+    pub display_name: String,
+    pub path: String,
+    pub setup_script: Option<String>,
+    pub cleanup_script: Option<String>,
+    pub copy_files: Option<String>,
+    pub parallel_setup_script: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleColumn {
+    pub name: String,
+    pub status: TaskStatus,
+    pub wip_limit: Option<i64>,
+    pub counts_as_done: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleComment {
+    pub author: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleChecklistItem {
+    pub text: String,
+    pub done: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleAttemptRepo {
+    pub repo: String,
+    pub target_branch: String,
+}
+
+/// What an attempt ran on. Kept for reference only: worktrees and processes stay behind, so
+/// attempts aren't recreated on import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleAttempt {
+    pub branch: String,
+    pub executor: Option<String>,
+    pub repos: Vec<BundleAttemptRepo>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A task with everything hanging off it. `id`, `parent_id` and `blocker_ids` are the exporting
+/// machine's ids and only link tasks within the bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleTask {
+    pub id: Uuid,
+    pub number: i64,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    pub priority: TaskPriority,
+    pub due_at: Option<DateTime<Utc>>,
+    /// Name of the board column the task sits in
+    pub column: Option<String>,
+    pub archived_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub parent_id: Option<Uuid>,
+    pub blocker_ids: Vec<Uuid>,
+    pub labels: Vec<String>,
+    pub comments: Vec<BundleComment>,
+    pub checklist: Vec<BundleChecklistItem>,
+    pub attempts: Vec<BundleAttempt>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectBundle {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub project: BundleProject,
+    pub repos: Vec<BundleRepo>,
+    /// In board order
+    pub columns: Vec<BundleColumn>,
+    /// In `number` order
+    pub tasks: Vec<BundleTask>,
+    /// The project's config overrides and notification settings
+    pub settings: ProjectSettings,
+}
+
+/// Outcome of importing a bundle
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProjectImportResult {
+    pub project: Project,
+    /// Tasks created
+    pub tasks: usize,
+    /// Paths of bundled repos that don't exist on this machine and were left out
+    pub skipped_repos: Vec<String>,
+    /// Whether the bundled project settings were valid here and applied
+    pub settings_applied: bool,
+}
+
+/// Pack a bundle into a gzipped tarball
+pub fn write_archive(bundle: &ProjectBundle) -> Result<Vec<u8>, ProjectBundleError> {
+    let manifest = serde_json::to_vec_pretty(bundle)
+        .map_err(|e| ProjectBundleError::Archive(e.to_string()))?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(bundle.exported_at.timestamp().max(0) as u64);
+    header.set_cksum();
+
+    let mut archive = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    archive
+        .append_data(&mut header, MANIFEST, manifest.as_slice())
+        .map_err(|e| ProjectBundleError::Archive(e.to_string()))?;
+    archive
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map_err(|e| ProjectBundleError::Archive(e.to_string()))
+}
+
+/// Unpack a bundle written by [`write_archive`]
+pub fn read_archive(bytes: &[u8]) -> Result<ProjectBundle, ProjectBundleError> {
+    let mut archive = tar::Archive::new(GzDecoder::new(bytes));
+    let entries = archive
+        .entries()
+        .map_err(|e| ProjectBundleError::Archive(e.to_string()))?;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| ProjectBundleError::Archive(e.to_string()))?;
+        let is_manifest = entry.path().is_ok_and(|path| path.as_os_str() == MANIFEST);
+        if !is_manifest {
+            continue;
+        }
+        let mut manifest = Vec::new();
+        entry
+            .read_to_end(&mut manifest)
+            .map_err(|e| ProjectBundleError::Archive(e.to_string()))?;
+        let value: serde_json::Value = serde_json::from_slice(&manifest)
+            .map_err(|e| ProjectBundleError::Archive(e.to_string()))?;
+        let version = value
+            .get("version")
+            .and_then(|version| version.as_u64())
+            .unwrap_or_default() as u32;
+        if version != BUNDLE_VERSION {
+            return Err(ProjectBundleError::UnsupportedVersion(version));
+        }
+        return serde_json::from_value(value)
+            .map_err(|e| ProjectBundleError::Archive(e.to_string()));
+    }
+    Err(ProjectBundleError::Archive(format!(
+        "{MANIFEST} is missing"
+    )))
+}
+
+async fn export_attempts(
+    pool: &SqlitePool,
+    task_id: Uuid,
+) -> Result<Vec<BundleAttempt>, ProjectBundleError> {
+    let mut attempts = Vec::new();
+    for workspace in Workspace::fetch_all(pool, Some(task_id))
+        .await?
+        .into_iter()
+        .rev()
+    {
+        let executor = Session::find_latest_by_workspace_id(pool, workspace.id)
+            .await?
+            .and_then(|session| session.executor);
+        let repos = WorkspaceRepo::find_repos_with_target_branch_for_workspace(pool, workspace.id)
+            .await?
+            .into_iter()
+            .map(|repo| BundleAttemptRepo {
+                repo: repo.repo.display_name,
+                target_branch: repo.target_branch,
+            })
+            .collect();
+        attempts.push(BundleAttempt {
+            branch: workspace.branch,
+            executor,
+            repos,
+            created_at: workspace.created_at,
+        });
+    }
+    Ok(attempts)
+}
+
+/// Gather a project, including archived tasks, into a bundle
+pub async fn export(
+    pool: &SqlitePool,
+    project: &Project,
+    settings: ProjectSettings,
+) -> Result<ProjectBundle, ProjectBundleError> {
+    let project_repos = ProjectRepo::find_by_project_id(pool, project.id).await?;
+    let repos = ProjectRepo::find_repos_for_project(pool, project.id)
+        .await?
+        .into_iter()
+        .filter_map(|repo| {
+            let project_repo = project_repos.iter().find(|pr| pr.repo_id == repo.id)?;
+            Some(BundleRepo {
+                display_name: repo.display_name,
+                path: repo.path.to_string_lossy().to_string(),
+                setup_script: project_repo.setup_script.clone(),
+                cleanup_script: project_repo.cleanup_script.clone(),
+                copy_files: project_repo.copy_files.clone(),
+                parallel_setup_script: project_repo.parallel_setup_script,
+            })
+        })
+        .collect();
+
+    let board = ProjectColumn::find_by_project_id(pool, project.id).await?;
+    let column_names: HashMap<Uuid, String> = board
+        .iter()
+        .map(|column| (column.id, column.name.clone()))
+        .collect();
+    let columns = board
+        .into_iter()
+        .map(|column| BundleColumn {
+            name: column.name,
+            status: column.status,
+            wip_limit: column.wip_limit,
+            counts_as_done: column.counts_as_done,
+        })
+        .collect();
+
+    let mut project_tasks: Vec<Task> =
+        Task::find_by_project_id_with_attempt_status(pool, project.id, true)
+            .await?
+            .into_iter()
+            .map(|task| task.task)
+            .collect();
+    project_tasks.sort_by_key(|task| task.number);
+
+    let mut tasks = Vec::with_capacity(project_tasks.len());
+    for task in project_tasks {
+        let comments = TaskComment::find_by_task_id(pool, task.id)
+            .await?
+            .into_iter()
+            .map(|comment| BundleComment {
+                author: comment.author,
+                body: comment.body,
+                created_at: comment.created_at,
+            })
+            .collect();
+        let checklist = TaskChecklistItem::find_by_task_id(pool, task.id)
+            .await?
+            .into_iter()
+            .map(|item| BundleChecklistItem {
+                text: item.text,
+                done: item.done,
+            })
+            .collect();
+        let blocker_ids = TaskDependency::find_for_task(pool, task.id)
+            .await?
+            .blockers
+            .into_iter()
+            .map(|blocker| blocker.id)
+            .collect();
+        tasks.push(BundleTask {
+            id: task.id,
+            number: task.number,
+            column: task.column_id.and_then(|id| column_names.get(&id).cloned()),
+            parent_id: TaskSubtask::find_parent_id(pool, task.id).await?,
+            blocker_ids,
+            labels: Label::names_for_task(pool, task.id).await?,
+            comments,
+            checklist,
+            attempts: export_attempts(pool, task.id).await?,
+            title: task.title,
+            description: task.description,
+            status: task.status,
+            priority: task.priority,
+            due_at: task.due_at,
+            archived_at: task.archived_at,
+            created_at: task.created_at,
+        });
+    }
+
+    Ok(ProjectBundle {
+        version: BUNDLE_VERSION,
+        exported_at: Utc::now(),
+        project: BundleProject {
+            name: project.name.clone(),
+            dev_script: project.dev_script.clone(),
+            dev_script_working_dir: project.dev_script_working_dir.clone(),
+            default_agent_working_dir: project.default_agent_working_dir.clone(),
+        },
+        repos,
+        columns,
+        tasks,
+        settings,
+    })
+}
+
+/// Create a new project from a bundle, named `name` or after the bundled project. Repos missing
+/// on this machine are skipped; the bundle's settings are left for the caller to apply, since
+/// they live in the config rather than the database.
+pub async fn import(
+    pool: &SqlitePool,
+    project_service: &ProjectService,
+    repo_service: &RepoService,
+    bundle: &ProjectBundle,
+    name: Option<String>,
+) -> Result<ProjectImportResult, ProjectBundleError> {
+    let (present, missing): (Vec<_>, Vec<_>) = bundle.repos.iter().partition(|repo| {
+        repo_service
+            .normalize_path(&repo.path)
+            .is_ok_and(|path| repo_service.validate_git_repo_path(&path).is_ok())
+    });
+    let repositories = present
+        .iter()
+        .map(|repo| CreateProjectRepo {
+            display_name: repo.display_name.clone(),
+            git_repo_path: repo.path.clone(),
+        })
+        .collect();
+    let project = project_service
+        .create_project(
+            pool,
+            repo_service,
+            CreateProject {
+                name: name.unwrap_or_else(|| bundle.project.name.clone()),
+                repositories,
+            },
+        )
+        .await?;
+    let project = Project::update(
+        pool,
+        project.id,
+        &UpdateProject {
+            name: None,
+            dev_script: bundle.project.dev_script.clone(),
+            dev_script_working_dir: bundle.project.dev_script_working_dir.clone(),
+            default_agent_working_dir: bundle.project.default_agent_working_dir.clone(),
+        },
+    )
+    .await?;
+
+    for repo in ProjectRepo::find_repos_for_project(pool, project.id).await? {
+        let Some(bundled) = present.iter().find(|bundled| {
+            repo_service
+                .normalize_path(&bundled.path)
+                .is_ok_and(|path| path == repo.path)
+        }) else {
+            continue;
+        };
+        ProjectRepo::update(
+            pool,
+            project.id,
+            repo.id,
+            &UpdateProjectRepo {
+                setup_script: bundled.setup_script.clone(),
+                cleanup_script: bundled.cleanup_script.clone(),
+                copy_files: bundled.copy_files.clone(),
+                parallel_setup_script: Some(bundled.parallel_setup_script),
+            },
+        )
+        .await?;
+    }
+
+    // Swap the default board for the bundled one while it is still empty
+    if !bundle.columns.is_empty() {
+        for column in ProjectColumn::find_by_project_id(pool, project.id).await? {
+            ProjectColumn::delete(pool, &column).await?;
+        }
+        for column in &bundle.columns {
+            ProjectColumn::create(
+                pool,
+                project.id,
+                &CreateProjectColumn {
+                    name: column.name.clone(),
+                    status: Some(column.status.clone()),
+                    wip_limit: column.wip_limit,
+                    counts_as_done: Some(column.counts_as_done),
+                },
+            )
+            .await?;
+        }
+    }
+    let columns = ProjectColumn::find_by_project_id(pool, project.id).await?;
+
+    let mut ids = HashMap::new();
+    for bundled in &bundle.tasks {
+        let task = Task::create(
+            pool,
+            &CreateTask {
+                project_id: project.id,
+                title: bundled.title.clone(),
+                description: bundled.description.clone(),
+                status: Some(bundled.status.clone()),
+                priority: Some(bundled.priority),
+                due_at: bundled.due_at,
+                parent_workspace_id: None,
+                image_ids: None,
+                shared_task_id: None,
+                executor_profile_id: None,
+            },
+            Uuid::new_v4(),
+        )
+        .await?;
+        ids.insert(bundled.id, task.id);
+
+        if let Some(column) = bundled
+            .column
+            .as_ref()
+            .and_then(|name| columns.iter().find(|column| &column.name == name))
+        {
+            column.move_task(pool, &task, true).await?;
+        }
+        for name in &bundled.labels {
+            let label = Label::find_or_create_by_name(pool, name).await?;
+            Label::add_to_task(pool, task.id, label.id).await?;
+        }
+        for comment in &bundled.comments {
+            TaskComment::create(pool, task.id, &comment.author, &comment.body).await?;
+        }
+        for item in &bundled.checklist {
+            TaskChecklistItem::create(pool, task.id, &item.text, item.done).await?;
+        }
+        if let Some(archived_at) = bundled.archived_at {
+            Task::archive(pool, task.id, archived_at).await?;
+        }
+    }
+
+    // Links go in once every task exists; ones pointing outside the bundle are dropped
+    for bundled in &bundle.tasks {
+        let task_id = ids[&bundled.id];
+        if let Some(parent_id) = bundled.parent_id.and_then(|id| ids.get(&id)) {
+            TaskSubtask::set_parent(pool, task_id, Some(*parent_id)).await?;
+        }
+        for blocker_id in bundled.blocker_ids.iter().filter_map(|id| ids.get(id)) {
+            TaskDependency::create(pool, task_id, *blocker_id).await?;
+        }
+    }
+
+    Ok(ProjectImportResult {
+        project,
+        tasks: ids.len(),
+        skipped_repos: missing.into_iter().map(|repo| repo.path.clone()).collect(),
+        settings_applied: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle() -> ProjectBundle {
+        let created_at = "2026-01-02T03:04:05Z".parse().unwrap();
+        ProjectBundle {
+            version: BUNDLE_VERSION,
+            exported_at: created_at,
+            project: BundleProject {
+                name: "Demo".to_string(),
+                dev_script: Some("npm run dev".to_string()),
+                dev_script_working_dir: None,
+                default_agent_working_dir: None,
+            },
+            repos: vec![BundleRepo {
+                display_name: "demo".to_string(),
+                path: "/src/demo".to_string(),
+                setup_script: None,
+                cleanup_script: None,
+                copy_files: Some(".env".to_string()),
+                parallel_setup_script: false,
+            }],
+            columns: vec![BundleColumn {
+                name: "Backlog".to_string(),
+                status: TaskStatus::Todo,
+                wip_limit: Some(3),
+                counts_as_done: false,
+            }],
+            tasks: vec![BundleTask {
+                id: Uuid::new_v4(),
+                number: 1,
+                title: "Write docs".to_string(),
+                description: Some("See #2".to_string()),
+                status: TaskStatus::Todo,
+                priority: TaskPriority::default(),
+                due_at: None,
+                column: Some("Backlog".to_string()),
+                archived_at: None,
+                created_at,
+                parent_id: None,
+                blocker_ids: vec![],
+                labels: vec!["docs".to_string()],
+                comments: vec![BundleComment {
+                    author: "me".to_string(),
+                    body: "Started".to_string(),
+                    created_at,
+                }],
+                checklist: vec![BundleChecklistItem {
+                    text: "Outline".to_string(),
+                    done: true,
+                }],
+                attempts: vec![BundleAttempt {
+                    branch: "vk/1-write-docs".to_string(),
+                    executor: Some("CLAUDE_CODE".to_string()),
+                    repos: vec![BundleAttemptRepo {
+                        repo: "demo".to_string(),
+                        target_branch: "main".to_string(),
+                    }],
+                    created_at,
+                }],
+            }],
+            settings: ProjectSettings::default(),
+        }
+    }
+
+    #[test]
+    fn test_archive_round_trip() {
+        let bundle = bundle();
+        let bytes = write_archive(&bundle).unwrap();
+        assert_eq!(
+            serde_json::to_value(read_archive(&bytes).unwrap()).unwrap(),
+            serde_json::to_value(&bundle).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_read_archive_rejects_other_versions() {
+        let mut bundle = bundle();
+        bundle.version = BUNDLE_VERSION + 1;
+        let bytes = write_archive(&bundle).unwrap();
+        assert!(matches!(
+            read_archive(&bytes),
+            Err(ProjectBundleError::UnsupportedVersion(v)) if v == BUNDLE_VERSION + 1
+        ));
+        assert!(matches!(
+            read_archive(b"not a bundle"),
+            Err(ProjectBundleError::Archive(_))
+        ));
+    }
+}
@@ -0,0 +1,448 @@
+//! Recurrence rules for scheduled tasks: five-field cron expressions and a subset of RFC 5545
+//! RRULEs (`FREQ=DAILY|WEEKLY|MONTHLY` with `INTERVAL`, `BYDAY`, `BYMONTHDAY`, `BYHOUR` and
+//! `BYMINUTE`). Times are wall-clock times in the schedule's UTC offset.
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, Offset, TimeZone,
+    Timelike, Utc,
+};
+use thiserror::Error;
+
+use crate::services::notification::quiet_hours::parse_utc_offset;
+
+/// How far ahead to look for the next run before treating a rule as never firing again,
+/// e.g. `0 0 30 2 *`
+const MAX_DAYS_AHEAD: i64 = 366 * 5;
+
+const WEEKDAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+const RRULE_WEEKDAYS: [&str; 7] = ["SU", "MO", "TU", "WE", "TH", "FR", "SA"];
+const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+#[derive(Debug, Error)]
+pub enum RecurrenceError {
+    #[error("Invalid recurrence rule: {0}")]
+    Invalid(String),
+    #[error("Invalid UTC offset '{0}', expected e.g. +02:00 or UTC")]
+    InvalidOffset(String),
+}
+
+fn invalid(message: impl Into<String>) -> RecurrenceError {
+    RecurrenceError::Invalid(message.into())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+#[derive(Debug, Clone)]
+enum Days {
+    Cron {
+        days_of_month: Vec<u32>,
+        months: Vec<u32>,
+        /// 0 is Sunday
+        weekdays: Vec<u32>,
+        /// Cron matches either day field when both are restricted
+        either: bool,
+    },
+    Rule {
+        frequency: Frequency,
+        interval: u32,
+        start: NaiveDate,
+        weekdays: Vec<u32>,
+        days_of_month: Vec<u32>,
+    },
+}
+
+impl Days {
+    fn matches(&self, date: NaiveDate) -> bool {
+        let weekday = date.weekday().num_days_from_sunday();
+        match self {
+            Days::Cron {
+                days_of_month,
+                months,
+                weekdays,
+                either,
+            } => {
+                let day_of_month = days_of_month.contains(&date.day());
+                let day_of_week = weekdays.contains(&weekday);
+                months.contains(&date.month())
+                    && if *either {
+                        day_of_month || day_of_week
+                    } else {
+                        day_of_month && day_of_week
+                    }
+            }
+            Days::Rule {
+                frequency,
+                interval,
+                start,
+                weekdays,
+                days_of_month,
+            } => {
+                if date < *start {
+                    return false;
+                }
+                let periods = match frequency {
+                    Frequency::Daily => (date - *start).num_days(),
+                    Frequency::Weekly => {
+                        let week_start = |d: NaiveDate| {
+                            d - Duration::days(d.weekday().num_days_from_sunday().into())
+                        };
+                        (week_start(date) - week_start(*start)).num_days() / 7
+                    }
+                    Frequency::Monthly => {
+                        let months = |d: NaiveDate| i64::from(d.year()) * 12 + i64::from(d.month());
+                        months(date) - months(*start)
+                    }
+                };
+                periods % i64::from(*interval) == 0
+                    && (weekdays.is_empty() || weekdays.contains(&weekday))
+                    && (days_of_month.is_empty() || days_of_month.contains(&date.day()))
+            }
+        }
+    }
+}
+
+/// A parsed recurrence rule
+#[derive(Debug, Clone)]
+pub struct Recurrence {
+    minutes: Vec<u32>,
+    hours: Vec<u32>,
+    days: Days,
+}
+
+fn parse_value(value: &str, names: &[&str], offset: u32) -> Option<u32> {
+    value.parse().ok().or_else(|| {
+        names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(value))
+            .map(|index| index as u32 + offset)
+    })
+}
+
+/// Values of one cron field, e.g. `*/15`, `1-5` or `MON,WED,FRI`
+fn parse_cron_field(
+    field: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+    name_offset: u32,
+) -> Result<Vec<u32>, RecurrenceError> {
+    let mut values = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (
+                range,
+                step.parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| invalid(format!("bad step in `{part}`")))?,
+            ),
+            None => (part, 1),
+        };
+        let value = |v: &str| {
+            parse_value(v, names, name_offset)
+                .filter(|v| (min..=max).contains(v))
+                .ok_or_else(|| invalid(format!("`{v}` is out of range in `{field}`")))
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (value(start)?, value(end)?),
+                // `5/10` means every 10 starting at 5
+                None if step > 1 => (value(range)?, max),
+                None => {
+                    let single = value(range)?;
+                    (single, single)
+                }
+            },
+        };
+        if start > end {
+            return Err(invalid(format!("range `{range}` is backwards")));
+        }
+        values.extend((start..=end).step_by(step as usize));
+    }
+    values.sort_unstable();
+    values.dedup();
+    Ok(values)
+}
+
+fn parse_cron(expression: &str) -> Result<Recurrence, RecurrenceError> {
+    let expression = match expression.to_ascii_lowercase().as_str() {
+        "@hourly" => "0 * * * *",
+        "@daily" | "@midnight" => "0 0 * * *",
+        "@weekly" => "0 0 * * 0",
+        "@monthly" => "0 0 1 * *",
+        "@yearly" | "@annually" => "0 0 1 1 *",
+        _ => expression,
+    };
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    let [minute, hour, day_of_month, month, weekday] = fields[..] else {
+        return Err(invalid(
+            "cron expressions need five fields: minute hour day-of-month month day-of-week",
+        ));
+    };
+    let mut weekdays = parse_cron_field(weekday, 0, 7, &WEEKDAY_NAMES, 0)?;
+    // Both 0 and 7 are Sunday
+    if weekdays.last() == Some(&7) {
+        weekdays.pop();
+        if weekdays.first() != Some(&0) {
+            weekdays.insert(0, 0);
+        }
+    }
+    Ok(Recurrence {
+        minutes: parse_cron_field(minute, 0, 59, &[], 0)?,
+        hours: parse_cron_field(hour, 0, 23, &[], 0)?,
+        days: Days::Cron {
+            days_of_month: parse_cron_field(day_of_month, 1, 31, &[], 0)?,
+            months: parse_cron_field(month, 1, 12, &MONTH_NAMES, 1)?,
+            weekdays,
+            either: day_of_month != "*" && weekday != "*",
+        },
+    })
+}
+
+fn parse_list(value: &str, min: u32, max: u32, key: &str) -> Result<Vec<u32>, RecurrenceError> {
+    let mut values = value
+        .split(',')
+        .map(|v| {
+            v.trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|v| (min..=max).contains(v))
+                .ok_or_else(|| invalid(format!("`{v}` is out of range for {key}")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    values.sort_unstable();
+    values.dedup();
+    Ok(values)
+}
+
+/// RRULE such as `FREQ=WEEKLY;BYDAY=MO;BYHOUR=9;BYMINUTE=0`. Intervals count from `start`,
+/// which also supplies the time and day when the rule doesn't give them.
+fn parse_rrule(rule: &str, start: NaiveDateTime) -> Result<Recurrence, RecurrenceError> {
+    let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+    let mut frequency = None;
+    let mut interval = 1;
+    let mut weekdays = Vec::new();
+    let mut days_of_month = Vec::new();
+    let mut hours = vec![start.hour()];
+    let mut minutes = vec![start.minute()];
+
+    for part in rule.split(';').filter(|part| !part.trim().is_empty()) {
+        let (key, value) = part
+            .split_once('=')
+            .ok_or_else(|| invalid(format!("expected KEY=VALUE, got `{part}`")))?;
+        match key.trim().to_ascii_uppercase().as_str() {
+            "FREQ" => {
+                frequency = Some(match value.trim().to_ascii_uppercase().as_str() {
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    "MONTHLY" => Frequency::Monthly,
+                    other => {
+                        return Err(invalid(format!(
+                            "FREQ={other} is not supported, use DAILY, WEEKLY or MONTHLY"
+                        )));
+                    }
+                })
+            }
+            "INTERVAL" => {
+                interval = value
+                    .trim()
+                    .parse()
+                    .ok()
+                    .filter(|interval| *interval > 0)
+                    .ok_or_else(|| invalid(format!("bad INTERVAL `{value}`")))?
+            }
+            "BYDAY" => {
+                weekdays = value
+                    .split(',')
+                    .map(|day| {
+                        parse_value(day.trim(), &RRULE_WEEKDAYS, 0)
+                            .filter(|day| *day < 7)
+                            .ok_or_else(|| invalid(format!("bad BYDAY value `{day}`")))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+            "BYMONTHDAY" => days_of_month = parse_list(value, 1, 31, "BYMONTHDAY")?,
+            "BYHOUR" => hours = parse_list(value, 0, 23, "BYHOUR")?,
+            "BYMINUTE" => minutes = parse_list(value, 0, 59, "BYMINUTE")?,
+            other => return Err(invalid(format!("{other} is not supported"))),
+        }
+    }
+
+    let frequency = frequency.ok_or_else(|| invalid("FREQ is required"))?;
+    // Without BYDAY/BYMONTHDAY, weekly and monthly rules repeat on the start's day
+    match frequency {
+        Frequency::Weekly if weekdays.is_empty() => {
+            weekdays.push(start.weekday().num_days_from_sunday())
+        }
+        Frequency::Monthly if weekdays.is_empty() && days_of_month.is_empty() => {
+            days_of_month.push(start.day())
+        }
+        _ => {}
+    }
+    Ok(Recurrence {
+        minutes,
+        hours,
+        days: Days::Rule {
+            frequency,
+            interval,
+            start: start.date(),
+            weekdays,
+            days_of_month,
+        },
+    })
+}
+
+impl Recurrence {
+    /// Parse a cron expression, or an RRULE when the rule contains `FREQ=`
+    pub fn parse(rule: &str, start: NaiveDateTime) -> Result<Self, RecurrenceError> {
+        let rule = rule.trim();
+        if rule.to_ascii_uppercase().contains("FREQ=") {
+            parse_rrule(rule, start)
+        } else {
+            parse_cron(rule)
+        }
+    }
+
+    /// First wall-clock time strictly after `after` that the rule fires
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut date = after.date();
+        for _ in 0..MAX_DAYS_AHEAD {
+            if self.days.matches(date) {
+                for hour in &self.hours {
+                    for minute in &self.minutes {
+                        let time = date.and_hms_opt(*hour, *minute, 0)?;
+                        if time > after {
+                            return Some(time);
+                        }
+                    }
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+}
+
+fn offset_for(
+    utc_offset: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<FixedOffset, RecurrenceError> {
+    match utc_offset.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(offset) => {
+            parse_utc_offset(offset).ok_or_else(|| RecurrenceError::InvalidOffset(offset.into()))
+        }
+        None => Ok(now.with_timezone(&Local).offset().fix()),
+    }
+}
+
+/// Next time a schedule created at `start` runs after `after`, or `None` when it never runs
+/// again. Rule times are read in `utc_offset`, or the system's timezone when unset.
+pub fn next_run(
+    rule: &str,
+    utc_offset: Option<&str>,
+    start: DateTime<Utc>,
+    after: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>, RecurrenceError> {
+    let offset = offset_for(utc_offset, after)?;
+    let recurrence = Recurrence::parse(rule, start.with_timezone(&offset).naive_local())?;
+    Ok(recurrence
+        .next_after(after.with_timezone(&offset).naive_local())
+        .and_then(|next| offset.from_local_datetime(&next).single())
+        .map(|next| next.with_timezone(&Utc)))
+}
+
+/// Calendar date of `at` in `utc_offset`, or the system's timezone when unset
+pub fn local_date(
+    utc_offset: Option<&str>,
+    at: DateTime<Utc>,
+) -> Result<NaiveDate, RecurrenceError> {
+    Ok(at.with_timezone(&offset_for(utc_offset, at)?).date_naive())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        // 2024-01-01 was a Monday
+        NaiveDate::from_ymd_opt(2024, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn next(rule: &str, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        Recurrence::parse(rule, at(1, 1, 9, 0))
+            .unwrap()
+            .next_after(after)
+    }
+
+    #[test]
+    fn test_cron() {
+        // Every Monday at 09:00
+        assert_eq!(next("0 9 * * MON", at(1, 1, 9, 0)), Some(at(1, 8, 9, 0)));
+        assert_eq!(next("0 9 * * 1", at(1, 1, 8, 59)), Some(at(1, 1, 9, 0)));
+        assert_eq!(next("*/15 * * * *", at(1, 1, 9, 7)), Some(at(1, 1, 9, 15)));
+        assert_eq!(next("0 0 * * 7", at(1, 1, 0, 0)), Some(at(1, 7, 0, 0)));
+        assert_eq!(next("@monthly", at(1, 15, 0, 0)), Some(at(2, 1, 0, 0)));
+        // Day-of-month and day-of-week match either way when both are set
+        assert_eq!(next("0 0 15 * FRI", at(1, 1, 0, 0)), Some(at(1, 5, 0, 0)));
+        assert_eq!(next("0 0 30 2 *", at(1, 1, 0, 0)), None);
+    }
+
+    #[test]
+    fn test_rrule() {
+        assert_eq!(
+            next("FREQ=WEEKLY;BYDAY=MO,TH", at(1, 1, 10, 0)),
+            Some(at(1, 4, 9, 0))
+        );
+        assert_eq!(
+            next(
+                "RRULE:FREQ=WEEKLY;INTERVAL=2;BYHOUR=6;BYMINUTE=30",
+                at(1, 1, 10, 0)
+            ),
+            Some(at(1, 15, 6, 30))
+        );
+        assert_eq!(
+            next("FREQ=DAILY;INTERVAL=3", at(1, 2, 0, 0)),
+            Some(at(1, 4, 9, 0))
+        );
+        assert_eq!(
+            next("FREQ=MONTHLY;BYMONTHDAY=31", at(1, 31, 12, 0)),
+            Some(at(3, 31, 9, 0))
+        );
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        for rule in [
+            "0 9 * *",
+            "61 * * * *",
+            "5-1 * * * *",
+            "FREQ=HOURLY",
+            "BYDAY=MO",
+        ] {
+            assert!(
+                Recurrence::parse(rule, at(1, 1, 9, 0)).is_err(),
+                "{rule} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_next_run_uses_offset() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            next_run("0 9 * * *", Some("+02:00"), start, start).unwrap(),
+            Some(Utc.with_ymd_and_hms(2024, 1, 1, 7, 0, 0).unwrap())
+        );
+    }
+}
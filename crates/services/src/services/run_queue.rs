@@ -0,0 +1,206 @@
+//! Attempts beyond the global or per-project concurrency limit wait in a queue and start as
+//! running ones finish: the most urgent priority band first, in queue order within a band.
+//! Paused attempts give up their slot until they are resumed.
+
+use std::{collections::HashMap, sync::Arc};
+
+use db::models::{execution_process::ExecutionProcess, queued_attempt::QueuedAttempt};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tokio::sync::{Mutex, MutexGuard};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::services::config::Config;
+
+/// What became of a request to start an attempt
+#[derive(Debug)]
+pub enum AttemptStart {
+    Started(ExecutionProcess),
+    /// Waiting for a free slot
+    Queued(QueuedAttempt),
+}
+
+/// Attempts running their setup scripts or coding agent, leaving out paused ones
+#[derive(Debug, Clone, Default)]
+pub struct RunningAttempts {
+    pub total: i64,
+    pub by_project: HashMap<Uuid, i64>,
+}
+
+impl RunningAttempts {
+    pub async fn load(pool: &SqlitePool) -> Result<Self, sqlx::Error> {
+        let by_project: HashMap<Uuid, i64> =
+            ExecutionProcess::count_running_attempts_by_project(pool)
+                .await?
+                .into_iter()
+                .collect();
+        Ok(Self {
+            total: by_project.values().sum(),
+            by_project,
+        })
+    }
+
+    fn in_project(&self, project_id: Uuid) -> i64 {
+        self.by_project.get(&project_id).copied().unwrap_or(0)
+    }
+}
+
+fn below(limit: Option<u32>, running: i64) -> bool {
+    limit.is_none_or(|limit| running < limit as i64)
+}
+
+/// Queued attempts that can start now, in the order they should. `queue` is in queue order.
+/// An attempt held back by its project's limit doesn't hold back those of other projects
+/// behind it.
+pub fn startable(
+    queue: &[QueuedAttempt],
+    mut running: RunningAttempts,
+    global_limit: Option<u32>,
+    project_limit: impl Fn(Uuid) -> Option<u32>,
+) -> Vec<&QueuedAttempt> {
+    let mut starting = Vec::new();
+    for attempt in queue {
+        if !below(global_limit, running.total) {
+            break;
+        }
+        if below(
+            project_limit(attempt.project_id),
+            running.in_project(attempt.project_id),
+        ) {
+            running.total += 1;
+            *running.by_project.entry(attempt.project_id).or_default() += 1;
+            starting.push(attempt);
+        }
+    }
+    starting
+}
+
+/// The queue with the running attempts and the limit they count against
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct RunQueueStatus {
+    /// Attempts running now, in the project when the status is for one
+    pub running: i64,
+    /// Limit `running` counts against; `None` when unlimited
+    pub max_concurrent_attempts: Option<u32>,
+    /// In the order they start
+    pub queued: Vec<QueuedAttempt>,
+}
+
+/// The whole queue against the global limit, or one project's against its own
+pub async fn status(
+    pool: &SqlitePool,
+    config: &Config,
+    project_id: Option<Uuid>,
+) -> Result<RunQueueStatus, sqlx::Error> {
+    let running = RunningAttempts::load(pool).await?;
+    let queued = QueuedAttempt::find_all(pool, project_id).await?;
+    Ok(match project_id {
+        Some(project_id) => RunQueueStatus {
+            running: running.in_project(project_id),
+            max_concurrent_attempts: config
+                .for_project(Some(project_id))
+                .max_concurrent_attempts_per_project,
+            queued,
+        },
+        None => RunQueueStatus {
+            running: running.total,
+            max_concurrent_attempts: config.max_concurrent_attempts,
+            queued,
+        },
+    })
+}
+
+/// Serializes starts, so two requests can't both take the last free slot
+#[derive(Debug, Clone, Default)]
+pub struct RunQueue {
+    starting: Arc<Mutex<()>>,
+}
+
+impl RunQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn lock(&self) -> MutexGuard<'_, ()> {
+        self.starting.lock().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use db::models::task::TaskPriority;
+    use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+    use sqlx::types::Json;
+
+    use super::*;
+
+    fn attempt(title: &str, project_id: Uuid) -> QueuedAttempt {
+        QueuedAttempt {
+            id: Uuid::new_v4(),
+            workspace_id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            project_id,
+            task_title: title.to_string(),
+            priority: TaskPriority::Normal,
+            executor_profile_id: Json(ExecutorProfileId::new(BaseCodingAgent::ClaudeCode)),
+            position: 0,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn titles(attempts: Vec<&QueuedAttempt>) -> Vec<&str> {
+        attempts
+            .into_iter()
+            .map(|attempt| attempt.task_title.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_startable_global_limit() {
+        let project = Uuid::new_v4();
+        let queue = [
+            attempt("a", project),
+            attempt("b", project),
+            attempt("c", project),
+        ];
+        let running = RunningAttempts {
+            total: 1,
+            by_project: HashMap::from([(project, 1)]),
+        };
+
+        assert_eq!(
+            titles(startable(&queue, running.clone(), Some(3), |_| None)),
+            vec!["a", "b"]
+        );
+        assert_eq!(
+            titles(startable(&queue, running.clone(), Some(1), |_| None)),
+            Vec::<&str>::new()
+        );
+        assert_eq!(
+            titles(startable(&queue, running, None, |_| None)),
+            vec!["a", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn test_startable_project_limit() {
+        let (busy, idle) = (Uuid::new_v4(), Uuid::new_v4());
+        let queue = [
+            attempt("busy 1", busy),
+            attempt("idle 1", idle),
+            attempt("busy 2", busy),
+            attempt("idle 2", idle),
+            attempt("idle 3", idle),
+        ];
+        let running = RunningAttempts {
+            total: 1,
+            by_project: HashMap::from([(busy, 1)]),
+        };
+
+        // The busy project has one slot left; its second attempt doesn't block the idle one's
+        let starting = startable(&queue, running, Some(4), |_| Some(2));
+        assert_eq!(titles(starting), vec!["busy 1", "idle 1", "idle 2"]);
+    }
+}
@@ -0,0 +1,245 @@
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+use tokio::fs;
+
+const MAX_SOUND_BYTES: u64 = 5 * 1024 * 1024;
+const MAX_NAME_CHARS: usize = 64;
+
+#[derive(Debug, Error)]
+pub enum SoundError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Unsupported audio format")]
+    InvalidFormat,
+
+    #[error("Sound too large: {0} bytes (max: {1} bytes)")]
+    TooLarge(u64, u64),
+
+    #[error("Invalid sound name: {0}")]
+    InvalidName(String),
+
+    #[error("Sound not found: {0}")]
+    NotFound(String),
+}
+
+/// Audio formats accepted for user-provided sounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundFormat {
+    Wav,
+    Mp3,
+    Ogg,
+    Flac,
+}
+
+impl SoundFormat {
+    /// Sniff the format from the file header rather than trusting the upload's name
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        match data {
+            [
+                b'R',
+                b'I',
+                b'F',
+                b'F',
+                _,
+                _,
+                _,
+                _,
+                b'W',
+                b'A',
+                b'V',
+                b'E',
+                ..,
+            ] => Some(Self::Wav),
+            [b'I', b'D', b'3', ..] => Some(Self::Mp3),
+            // MPEG audio frame sync without an ID3 tag
+            [0xFF, second, ..] if second & 0xE0 == 0xE0 => Some(Self::Mp3),
+            [b'O', b'g', b'g', b'S', ..] => Some(Self::Ogg),
+            [b'f', b'L', b'a', b'C', ..] => Some(Self::Flac),
+            _ => None,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "wav" => Some(Self::Wav),
+            "mp3" => Some(Self::Mp3),
+            "ogg" => Some(Self::Ogg),
+            "flac" => Some(Self::Flac),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Wav => "wav",
+            Self::Mp3 => "mp3",
+            Self::Ogg => "ogg",
+            Self::Flac => "flac",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Wav => "audio/wav",
+            Self::Mp3 => "audio/mpeg",
+            Self::Ogg => "audio/ogg",
+            Self::Flac => "audio/flac",
+        }
+    }
+}
+
+/// Directory holding uploaded sounds, next to the config file
+pub fn sounds_dir() -> PathBuf {
+    utils::assets::asset_dir().join("sounds")
+}
+
+/// Turn an upload's file name into a safe stem, e.g. `My Ding!.wav` -> `my-ding`
+fn sanitize_stem(original_name: &str) -> String {
+    let stem = Path::new(original_name)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+    let mut sanitized = String::new();
+    for c in stem.chars().take(MAX_NAME_CHARS) {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            sanitized.push(c.to_ascii_lowercase());
+        } else if !sanitized.ends_with('-') {
+            sanitized.push('-');
+        }
+    }
+    let sanitized = sanitized.trim_matches('-');
+    if sanitized.is_empty() {
+        "sound".to_string()
+    } else {
+        sanitized.to_string()
+    }
+}
+
+/// Names must refer to a file directly inside the sounds directory
+fn validate_name(name: &str) -> Result<(), SoundError> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && !name.contains(['/', '\\'])
+        && SoundFormat::from_path(Path::new(name)).is_some();
+    if valid {
+        Ok(())
+    } else {
+        Err(SoundError::InvalidName(name.to_string()))
+    }
+}
+
+/// Validate and save an uploaded sound, returning the name to reference it by.
+///
+/// Uploading a file with the same name replaces the previous one.
+pub async fn store_sound(data: &[u8], original_name: &str) -> Result<String, SoundError> {
+    let size = data.len() as u64;
+    if size > MAX_SOUND_BYTES {
+        return Err(SoundError::TooLarge(size, MAX_SOUND_BYTES));
+    }
+    let format = SoundFormat::detect(data).ok_or(SoundError::InvalidFormat)?;
+
+    let name = format!("{}.{}", sanitize_stem(original_name), format.extension());
+    let dir = sounds_dir();
+    fs::create_dir_all(&dir).await?;
+    fs::write(dir.join(&name), data).await?;
+    Ok(name)
+}
+
+/// Names of every uploaded sound, sorted
+pub async fn list_sounds() -> Result<Vec<String>, SoundError> {
+    let mut names = Vec::new();
+    let mut entries = match fs::read_dir(sounds_dir()).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(names),
+        Err(e) => return Err(e.into()),
+    };
+    while let Some(entry) = entries.next_entry().await? {
+        if let Some(name) = entry.file_name().to_str()
+            && validate_name(name).is_ok()
+            && entry.file_type().await?.is_file()
+        {
+            names.push(name.to_string());
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// Path of an uploaded sound; only names inside the sounds directory are accepted
+pub fn uploaded_sound(name: &str) -> Result<PathBuf, SoundError> {
+    validate_name(name)?;
+    let path = sounds_dir().join(name);
+    if path.is_file() {
+        Ok(path)
+    } else {
+        Err(SoundError::NotFound(name.to_string()))
+    }
+}
+
+/// Resolve a configured custom sound, given either as an uploaded sound's name or an absolute path
+pub fn resolve_sound(sound: &str) -> Result<PathBuf, SoundError> {
+    let sound = sound.trim();
+    let path = Path::new(sound);
+    if !path.is_absolute() {
+        return uploaded_sound(sound);
+    }
+    if SoundFormat::from_path(path).is_none() {
+        return Err(SoundError::InvalidFormat);
+    }
+    if path.is_file() {
+        Ok(path.to_path_buf())
+    } else {
+        Err(SoundError::NotFound(sound.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(
+            SoundFormat::detect(b"RIFF\x24\x08\x00\x00WAVEfmt "),
+            Some(SoundFormat::Wav)
+        );
+        assert_eq!(SoundFormat::detect(b"ID3\x04\x00"), Some(SoundFormat::Mp3));
+        assert_eq!(
+            SoundFormat::detect(&[0xFF, 0xFB, 0x90]),
+            Some(SoundFormat::Mp3)
+        );
+        assert_eq!(SoundFormat::detect(b"OggS\x00"), Some(SoundFormat::Ogg));
+        assert_eq!(SoundFormat::detect(b"fLaC\x00"), Some(SoundFormat::Flac));
+        assert_eq!(SoundFormat::detect(b"\x89PNG\r\n"), None);
+    }
+
+    #[test]
+    fn test_sanitize_stem() {
+        assert_eq!(sanitize_stem("My Ding!.wav"), "my-ding");
+        assert_eq!(sanitize_stem("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_stem("???.mp3"), "sound");
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("ding.wav").is_ok());
+        assert!(validate_name("../ding.wav").is_err());
+        assert!(validate_name(".hidden.wav").is_err());
+        assert!(validate_name("notes.txt").is_err());
+    }
+
+    #[test]
+    fn test_uploaded_sound_rejects_paths() {
+        assert!(matches!(
+            uploaded_sound("/home/user/ding.wav"),
+            Err(SoundError::InvalidName(_))
+        ));
+        assert!(matches!(
+            uploaded_sound("../ding.wav"),
+            Err(SoundError::InvalidName(_))
+        ));
+    }
+}
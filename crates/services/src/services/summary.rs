@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use db::{DBService, models::task::Task};
+use tokio::time::interval;
+use tracing::{error, info};
+
+use crate::services::notification::{NotificationService, schedule};
+
+/// Background scheduler for the opt-in daily or weekly activity summary
+pub struct SummaryService {
+    db: DBService,
+    poll_interval: Duration,
+    notification_service: NotificationService,
+}
+
+impl SummaryService {
+    pub async fn spawn(
+        db: DBService,
+        notification_service: NotificationService,
+    ) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            poll_interval: Duration::from_secs(60),
+            notification_service,
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting notification summary service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+        // Runs that were already due before startup or before the summary was enabled are
+        // skipped rather than sent late
+        let mut last_run: Option<DateTime<Utc>> = None;
+
+        loop {
+            interval.tick().await;
+            let settings = self.notification_service.summary_settings().await;
+            if !settings.enabled {
+                last_run = None;
+                continue;
+            }
+            let Some(run) = schedule::latest_run(&settings, Utc::now()) else {
+                continue;
+            };
+            if last_run.replace(run).is_none_or(|last| last >= run) {
+                continue;
+            }
+
+            let since = run - schedule::period(settings.frequency);
+            match Task::activity_summary(&self.db.pool, since).await {
+                Ok(activity) => {
+                    self.notification_service
+                        .send_summary(settings.frequency, &activity)
+                        .await
+                }
+                Err(e) => error!("Error loading activity for the notification summary: {}", e),
+            }
+        }
+    }
+}
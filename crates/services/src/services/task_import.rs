@@ -0,0 +1,478 @@
+//! Bulk task import from CSV or JSON. Rows are mapped onto task fields, validated and checked for
+//! duplicates up front, so the result can be previewed before anything is created.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use db::models::task::{TaskPriority, TaskStatus};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Most rows one import may hold
+pub const MAX_ROWS: usize = 5000;
+
+/// CSV has a header row; JSON is an array of objects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    Csv,
+    Json,
+}
+
+/// Source column (CSV header or JSON key) of each task field. Fields left out are read from a
+/// column named like the field, compared case-insensitively, if there is one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct ImportMapping {
+    #[serde(default)]
+    #[ts(optional)]
+    pub title: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub description: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub status: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub priority: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub due_at: Option<String>,
+    /// Labels are split on commas and semicolons
+    #[serde(default)]
+    #[ts(optional)]
+    pub labels: Option<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum TaskImportError {
+    #[error("Invalid CSV: {0}")]
+    Csv(String),
+    #[error("Invalid JSON: {0}")]
+    Json(String),
+    #[error("Column '{0}' not found")]
+    UnknownColumn(String),
+    #[error("No title column; map one with `title`")]
+    NoTitleColumn,
+    #[error("Too many rows ({0}); import at most {MAX_ROWS} at once")]
+    TooManyRows(usize),
+}
+
+/// A row's task once mapped and validated
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+pub struct ImportedTask {
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    pub priority: TaskPriority,
+    pub due_at: Option<DateTime<Utc>>,
+    pub labels: Vec<String>,
+}
+
+/// One data row of an import, as previewed
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ImportRow {
+    /// 1-based position among the data rows
+    pub row: usize,
+    /// `None` when the row has errors
+    pub task: Option<ImportedTask>,
+    pub errors: Vec<String>,
+    /// Task in the project with the same title
+    pub duplicate_of_task_id: Option<Uuid>,
+    /// Earlier row of the import with the same title
+    pub duplicate_of_row: Option<usize>,
+}
+
+impl ImportRow {
+    pub fn is_duplicate(&self) -> bool {
+        self.duplicate_of_task_id.is_some() || self.duplicate_of_row.is_some()
+    }
+}
+
+/// Records of RFC 4180 CSV: fields may be quoted, with `""` for a quote, and quoted fields may
+/// span lines. Quotes inside unquoted fields are kept as text and blank lines are skipped.
+pub fn parse_csv(content: &str) -> Result<Vec<Vec<String>>, TaskImportError> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut quoted = false;
+    let mut line = 1;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() && !quoted => {
+                in_quotes = true;
+                quoted = true;
+            }
+            ',' => {
+                record.push(std::mem::take(&mut field));
+                quoted = false;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                quoted = false;
+                if record.iter().any(|field| !field.is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                } else {
+                    record.clear();
+                }
+                line += 1;
+            }
+            _ if quoted => {
+                return Err(TaskImportError::Csv(format!(
+                    "text after a closing quote on line {line}"
+                )));
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(TaskImportError::Csv(format!(
+            "unterminated quote on line {line}"
+        )));
+    }
+    record.push(field);
+    if record.iter().any(|field| !field.is_empty()) {
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn json_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(values) => values
+            .iter()
+            .map(json_text)
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
+    }
+}
+
+/// Header and rows of the content, rows padded to the header's width
+fn table(
+    format: ImportFormat,
+    content: &str,
+) -> Result<(Vec<String>, Vec<Vec<String>>), TaskImportError> {
+    let (header, mut rows) = match format {
+        ImportFormat::Csv => {
+            let mut records = parse_csv(content)?.into_iter();
+            let header: Vec<String> = records
+                .next()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|name| name.trim().to_string())
+                .collect();
+            (header, records.collect::<Vec<_>>())
+        }
+        ImportFormat::Json => {
+            let objects: Vec<serde_json::Map<String, Value>> =
+                serde_json::from_str(content).map_err(|e| TaskImportError::Json(e.to_string()))?;
+            let mut header: Vec<String> = Vec::new();
+            for key in objects.iter().flat_map(|object| object.keys()) {
+                if !header.contains(key) {
+                    header.push(key.clone());
+                }
+            }
+            let rows = objects
+                .iter()
+                .map(|object| {
+                    header
+                        .iter()
+                        .map(|key| object.get(key).map(json_text).unwrap_or_default())
+                        .collect()
+                })
+                .collect();
+            (header, rows)
+        }
+    };
+    if rows.len() > MAX_ROWS {
+        return Err(TaskImportError::TooManyRows(rows.len()));
+    }
+    for row in &mut rows {
+        row.resize(header.len().max(row.len()), String::new());
+    }
+    Ok((header, rows))
+}
+
+/// Index of the column a field is read from
+fn column(
+    header: &[String],
+    mapped: Option<&str>,
+    field: &str,
+) -> Result<Option<usize>, TaskImportError> {
+    match mapped {
+        Some(name) => header
+            .iter()
+            .position(|column| column == name)
+            .map(Some)
+            .ok_or_else(|| TaskImportError::UnknownColumn(name.to_string())),
+        None => Ok(header
+            .iter()
+            .position(|column| column.eq_ignore_ascii_case(field))),
+    }
+}
+
+/// Status names as other tools write them, e.g. `To Do`, `in-progress` or `Completed`
+fn parse_status(value: &str) -> Result<TaskStatus, String> {
+    let normalized: String = value
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '_' | '-'))
+        .collect::<String>()
+        .to_lowercase();
+    match normalized.as_str() {
+        "" | "todo" | "open" | "backlog" => Ok(TaskStatus::Todo),
+        "inprogress" | "doing" | "started" => Ok(TaskStatus::InProgress),
+        "inreview" | "review" => Ok(TaskStatus::InReview),
+        "done" | "complete" | "completed" | "closed" => Ok(TaskStatus::Done),
+        "cancelled" | "canceled" => Ok(TaskStatus::Cancelled),
+        _ => Err(format!("unknown status '{value}'")),
+    }
+}
+
+fn parse_priority(value: &str) -> Result<TaskPriority, String> {
+    match value.trim().to_lowercase().as_str() {
+        "" | "normal" | "medium" => Ok(TaskPriority::Normal),
+        "urgent" | "critical" | "highest" => Ok(TaskPriority::Urgent),
+        "high" => Ok(TaskPriority::High),
+        "low" | "lowest" => Ok(TaskPriority::Low),
+        _ => Err(format!("unknown priority '{value}'")),
+    }
+}
+
+/// RFC 3339 timestamps, or dates (`2026-03-01`), which are due at the end of that day in UTC
+fn parse_due_at(value: &str) -> Result<Option<DateTime<Utc>>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    if let Ok(due_at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(Some(due_at.with_timezone(&Utc)));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(23, 59, 59))
+        .map(|due_at| Some(due_at.and_utc()))
+        .ok_or_else(|| format!("invalid due date '{value}', expected e.g. 2026-03-01"))
+}
+
+fn parse_labels(value: &str) -> Vec<String> {
+    let mut labels: Vec<String> = Vec::new();
+    for label in value.split([',', ';']).map(str::trim) {
+        if !label.is_empty() && !labels.iter().any(|l| l.eq_ignore_ascii_case(label)) {
+            labels.push(label.to_string());
+        }
+    }
+    labels
+}
+
+/// Titles compared case- and whitespace-insensitively
+fn normalize_title(title: &str) -> String {
+    title
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Map, validate and check each row for duplicates among `existing` (the project's task ids and
+/// titles) and the rows before it
+pub fn preview(
+    format: ImportFormat,
+    content: &str,
+    mapping: &ImportMapping,
+    existing: &[(Uuid, &str)],
+) -> Result<Vec<ImportRow>, TaskImportError> {
+    let (header, rows) = table(format, content)?;
+    let title_column = column(&header, mapping.title.as_deref(), "title")?
+        .ok_or(TaskImportError::NoTitleColumn)?;
+    let description_column = column(&header, mapping.description.as_deref(), "description")?;
+    let status_column = column(&header, mapping.status.as_deref(), "status")?;
+    let priority_column = column(&header, mapping.priority.as_deref(), "priority")?;
+    let due_at_column = column(&header, mapping.due_at.as_deref(), "due_at")?;
+    let labels_column = column(&header, mapping.labels.as_deref(), "labels")?;
+
+    let existing: HashMap<String, Uuid> = existing
+        .iter()
+        .map(|(id, title)| (normalize_title(title), *id))
+        .collect();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    let mut previews = Vec::with_capacity(rows.len());
+    for (index, row) in rows.iter().enumerate() {
+        let number = index + 1;
+        let cell = |column: Option<usize>| column.map(|i| row[i].as_str()).unwrap_or_default();
+        let mut errors = Vec::new();
+
+        let title = cell(Some(title_column)).trim().to_string();
+        if title.is_empty() {
+            errors.push("title is empty".to_string());
+        }
+        let description = Some(cell(description_column).trim())
+            .filter(|description| !description.is_empty())
+            .map(str::to_string);
+        let status = parse_status(cell(status_column)).map_err(|e| errors.push(e));
+        let priority = parse_priority(cell(priority_column)).map_err(|e| errors.push(e));
+        let due_at = parse_due_at(cell(due_at_column)).map_err(|e| errors.push(e));
+        let labels = parse_labels(cell(labels_column));
+
+        let key = normalize_title(&title);
+        let duplicate_of_task_id = existing.get(&key).copied();
+        let duplicate_of_row = seen.get(&key).copied();
+        if !title.is_empty() {
+            seen.entry(key).or_insert(number);
+        }
+
+        let task = match (errors.is_empty(), status, priority, due_at) {
+            (true, Ok(status), Ok(priority), Ok(due_at)) => Some(ImportedTask {
+                title,
+                description,
+                status,
+                priority,
+                due_at,
+                labels,
+            }),
+            _ => None,
+        };
+        previews.push(ImportRow {
+            row: number,
+            task,
+            errors,
+            duplicate_of_task_id,
+            duplicate_of_row,
+        });
+    }
+    Ok(previews)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let csv = "title,description\r\n\"Fix \"\"login\"\"\",\"Two\nlines\"\n\nPlain,\n";
+        assert_eq!(
+            parse_csv(csv).unwrap(),
+            vec![
+                vec!["title".to_string(), "description".to_string()],
+                vec!["Fix \"login\"".to_string(), "Two\nlines".to_string()],
+                vec!["Plain".to_string(), String::new()],
+            ]
+        );
+        assert_eq!(
+            parse_csv("5\" screen").unwrap(),
+            vec![vec!["5\" screen".to_string()]]
+        );
+        assert!(parse_csv("a,\"b\nc").is_err());
+        assert!(parse_csv("a,\"b\"c").is_err());
+    }
+
+    #[test]
+    fn test_preview_csv_with_mapping() {
+        let csv = "Summary,State,Due,Tags\n\
+                   Add export,In Progress,2026-03-01,\"api, ui\"\n\
+                   ,Todo,,\n\
+                   Fix login,Blocked,soon,\n\
+                   add  EXPORT,Done,,";
+        let mapping = ImportMapping {
+            title: Some("Summary".to_string()),
+            status: Some("State".to_string()),
+            due_at: Some("Due".to_string()),
+            labels: Some("Tags".to_string()),
+            ..Default::default()
+        };
+        let existing_id = Uuid::new_v4();
+        let rows = preview(
+            ImportFormat::Csv,
+            csv,
+            &mapping,
+            &[(existing_id, "Fix login")],
+        )
+        .unwrap();
+
+        let first = rows[0].task.as_ref().unwrap();
+        assert_eq!(first.status, TaskStatus::InProgress);
+        assert_eq!(
+            first.due_at.unwrap().to_rfc3339(),
+            "2026-03-01T23:59:59+00:00"
+        );
+        assert_eq!(first.labels, vec!["api", "ui"]);
+        assert!(!rows[0].is_duplicate());
+
+        assert!(rows[1].task.is_none());
+        assert_eq!(rows[1].errors, vec!["title is empty"]);
+
+        assert!(rows[2].task.is_none());
+        assert_eq!(rows[2].errors.len(), 2);
+        assert_eq!(rows[2].duplicate_of_task_id, Some(existing_id));
+
+        assert_eq!(rows[3].duplicate_of_row, Some(1));
+        assert_eq!(rows[3].task.as_ref().unwrap().status, TaskStatus::Done);
+    }
+
+    #[test]
+    fn test_preview_json() {
+        let json = r#"[{"title": "Ship it", "priority": "High", "labels": ["release", "ops"]},
+                       {"Title": "Later", "priority": "someday", "estimate": 3}]"#;
+        let rows = preview(ImportFormat::Json, json, &ImportMapping::default(), &[]).unwrap();
+        let task = rows[0].task.as_ref().unwrap();
+        assert_eq!(task.priority, TaskPriority::High);
+        assert_eq!(task.labels, vec!["release", "ops"]);
+        assert_eq!(
+            rows[1].errors,
+            vec!["title is empty", "unknown priority 'someday'"]
+        );
+    }
+
+    #[test]
+    fn test_preview_errors() {
+        let mapping = ImportMapping {
+            title: Some("Name".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            preview(ImportFormat::Csv, "title\nA", &mapping, &[]),
+            Err(TaskImportError::UnknownColumn(_))
+        ));
+        assert!(matches!(
+            preview(
+                ImportFormat::Csv,
+                "summary\nA",
+                &ImportMapping::default(),
+                &[]
+            ),
+            Err(TaskImportError::NoTitleColumn)
+        ));
+        assert!(matches!(
+            preview(ImportFormat::Json, "{}", &ImportMapping::default(), &[]),
+            Err(TaskImportError::Json(_))
+        ));
+    }
+}
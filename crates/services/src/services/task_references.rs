@@ -0,0 +1,103 @@
+use db::models::{
+    project_repo::ProjectRepo,
+    repo::Repo,
+    task::Task,
+    task_reference::{NewTaskReference, TaskReference, TaskReferenceKind, TaskReferences},
+};
+use sqlx::SqlitePool;
+use utils::references::{Reference, extract_references};
+
+use super::git::GitService;
+
+/// Link to `path` (e.g. `commit/<sha>`) on GitHub, if the repo is hosted there
+fn github_url(git: &GitService, repo: &Repo, path: &str) -> Option<String> {
+    let info = git.get_github_repo_info(&repo.path).ok()?;
+    Some(format!(
+        "https://github.com/{}/{}/{path}",
+        info.owner, info.repo_name
+    ))
+}
+
+fn resolve_commit(git: &GitService, repos: &[Repo], sha: &str) -> Option<NewTaskReference> {
+    repos.iter().find_map(|repo| {
+        let full_sha = git.resolve_commit_sha(&repo.path, sha).ok().flatten()?;
+        Some(NewTaskReference {
+            kind: TaskReferenceKind::Commit,
+            reference: sha.to_string(),
+            target_task_id: None,
+            repo_id: Some(repo.id),
+            url: github_url(git, repo, &format!("commit/{full_sha}")),
+            resolved: Some(full_sha),
+        })
+    })
+}
+
+fn resolve_file(
+    git: &GitService,
+    repos: &[Repo],
+    reference: &Reference,
+    path: &str,
+    line: Option<u32>,
+) -> Option<NewTaskReference> {
+    let repo = repos.iter().find(|repo| repo.path.join(path).is_file())?;
+    let anchor = line.map(|line| format!("#L{line}")).unwrap_or_default();
+    Some(NewTaskReference {
+        kind: TaskReferenceKind::File,
+        reference: reference.text(),
+        target_task_id: None,
+        repo_id: Some(repo.id),
+        resolved: Some(path.to_string()),
+        url: github_url(git, repo, &format!("blob/HEAD/{path}{anchor}")),
+    })
+}
+
+/// Resolve the references in the task's description against the project's tasks and repos and
+/// store them; ones that point at nothing are dropped
+pub async fn refresh(pool: &SqlitePool, git: &GitService, task: &Task) -> Result<(), sqlx::Error> {
+    let references = extract_references(task.description.as_deref().unwrap_or_default());
+    let repos = if references
+        .iter()
+        .any(|reference| !matches!(reference, Reference::Task(_)))
+    {
+        ProjectRepo::find_repos_for_project(pool, task.project_id).await?
+    } else {
+        Vec::new()
+    };
+
+    let mut resolved = Vec::new();
+    for reference in &references {
+        let new = match reference {
+            Reference::Task(number) => Task::find_by_number(pool, task.project_id, *number)
+                .await?
+                .filter(|target| target.id != task.id)
+                .map(|target| NewTaskReference {
+                    kind: TaskReferenceKind::Task,
+                    reference: reference.text(),
+                    target_task_id: Some(target.id),
+                    repo_id: None,
+                    resolved: None,
+                    url: None,
+                }),
+            Reference::Commit(sha) => resolve_commit(git, &repos, sha),
+            Reference::File { path, line } => resolve_file(git, &repos, reference, path, *line),
+        };
+        resolved.extend(new);
+    }
+    TaskReference::replace_for_task(pool, task.id, &resolved).await
+}
+
+/// The task's references and backlinks, resolving its description first if it changed since the
+/// last scan
+pub async fn find_for_task(
+    pool: &SqlitePool,
+    git: &GitService,
+    task: &Task,
+) -> Result<TaskReferences, sqlx::Error> {
+    if !TaskReference::is_scanned(pool, task.id).await? {
+        refresh(pool, git, task).await?;
+    }
+    Ok(TaskReferences {
+        references: TaskReference::find_by_task_id(pool, task.id).await?,
+        backlinks: TaskReference::find_backlinks(pool, task.id).await?,
+    })
+}
@@ -0,0 +1,374 @@
+//! One-shot import of a Trello board into a project, from the board's JSON export or fetched
+//! through the Trello API. Lists become columns, cards become tasks in them, and card labels and
+//! checklists carry over.
+
+use std::{collections::HashMap, time::Duration};
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    assignment_rule::TaskSource,
+    label::Label,
+    project_column::{CreateProjectColumn, ProjectColumn, ProjectColumnError},
+    task::{CreateTask, Task, TaskStatus},
+    task_checklist_item::TaskChecklistItem,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::auto_assign;
+
+const API_URL: &str = "https://api.trello.com/1";
+
+#[derive(Debug, Error)]
+pub enum TrelloImportError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Column(#[from] ProjectColumnError),
+    #[error("Not a Trello board export: {0}")]
+    InvalidExport(String),
+    #[error("Not a Trello board link or id: {0}")]
+    InvalidBoard(String),
+    #[error("Couldn't reach Trello: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("Trello returned {status}: {body}")]
+    Http { status: u16, body: String },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TrelloList {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub closed: bool,
+    #[serde(default)]
+    pub pos: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TrelloLabel {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+impl TrelloLabel {
+    /// Color-only labels are named after their color
+    fn display_name(&self) -> Option<String> {
+        let name = self.name.trim();
+        if !name.is_empty() {
+            return Some(name.to_string());
+        }
+        self.color.clone().filter(|color| !color.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrelloCard {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub desc: String,
+    #[serde(default)]
+    pub closed: bool,
+    pub id_list: String,
+    #[serde(default)]
+    pub due: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub id_labels: Vec<String>,
+    #[serde(default)]
+    pub pos: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TrelloCheckItem {
+    pub name: String,
+    /// `complete` or `incomplete`
+    pub state: String,
+    #[serde(default)]
+    pub pos: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrelloChecklist {
+    pub id_card: String,
+    pub name: String,
+    #[serde(default)]
+    pub pos: f64,
+    #[serde(default)]
+    pub check_items: Vec<TrelloCheckItem>,
+}
+
+/// The parts of a board export the import reads
+#[derive(Debug, Clone, Deserialize)]
+pub struct TrelloBoard {
+    pub name: String,
+    #[serde(default)]
+    pub lists: Vec<TrelloList>,
+    #[serde(default)]
+    pub cards: Vec<TrelloCard>,
+    #[serde(default)]
+    pub labels: Vec<TrelloLabel>,
+    #[serde(default)]
+    pub checklists: Vec<TrelloChecklist>,
+}
+
+/// What the import created
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct TrelloImportResult {
+    pub board_name: String,
+    pub columns_created: usize,
+    pub tasks_created: usize,
+    pub checklist_items_created: usize,
+    /// Archived lists and cards left out
+    pub archived_skipped: usize,
+}
+
+pub fn parse_export(content: &str) -> Result<TrelloBoard, TrelloImportError> {
+    serde_json::from_str(content).map_err(|e| TrelloImportError::InvalidExport(e.to_string()))
+}
+
+/// The board id or short link in a board URL such as `https://trello.com/b/AbC123/roadmap`, or
+/// the input itself when it already is one
+pub fn board_id(input: &str) -> Result<String, TrelloImportError> {
+    let input = input.trim();
+    let id = match input.split_once("trello.com/b/") {
+        Some((_, rest)) => rest.split(['/', '?', '#']).next().unwrap_or_default(),
+        None => input,
+    };
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(TrelloImportError::InvalidBoard(input.to_string()));
+    }
+    Ok(id.to_string())
+}
+
+/// Task status for cards in a list, guessed from the list's name
+pub fn status_for_list(name: &str) -> TaskStatus {
+    let name = name.to_lowercase();
+    let has = |words: &[&str]| words.iter().any(|word| name.contains(word));
+    if has(&["cancel", "won't", "wont", "rejected", "dropped"]) {
+        TaskStatus::Cancelled
+    } else if has(&[
+        "done", "complete", "finished", "shipped", "released", "closed",
+    ]) {
+        TaskStatus::Done
+    } else if has(&["review", "qa", "testing", "verify"]) {
+        TaskStatus::InReview
+    } else if has(&["doing", "progress", "wip", "working", "started", "active"]) {
+        TaskStatus::InProgress
+    } else {
+        TaskStatus::Todo
+    }
+}
+
+/// Fetch a board with its lists, cards, labels and checklists through the Trello API
+pub async fn fetch_board(
+    board: &str,
+    api_key: &str,
+    token: &str,
+) -> Result<TrelloBoard, TrelloImportError> {
+    let id = board_id(board)?;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(60))
+        .build()?;
+    let response = client
+        .get(format!("{API_URL}/boards/{id}"))
+        .query(&[
+            ("key", api_key.trim()),
+            ("token", token.trim()),
+            ("fields", "name"),
+            ("lists", "all"),
+            ("cards", "all"),
+            ("labels", "all"),
+            ("checklists", "all"),
+        ])
+        .send()
+        .await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(TrelloImportError::Http {
+            status: status.as_u16(),
+            body: response.text().await.unwrap_or_default(),
+        });
+    }
+    Ok(response.json().await?)
+}
+
+/// The project's column for a list, reusing one of the same name
+async fn column_for_list(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    columns: &mut Vec<ProjectColumn>,
+    list: &TrelloList,
+    result: &mut TrelloImportResult,
+) -> Result<ProjectColumn, TrelloImportError> {
+    let name = list.name.trim();
+    if let Some(column) = columns
+        .iter()
+        .find(|column| column.name.eq_ignore_ascii_case(name))
+    {
+        return Ok(column.clone());
+    }
+    let status = status_for_list(name);
+    let counts_as_done = matches!(status, TaskStatus::Done | TaskStatus::Cancelled);
+    let column = ProjectColumn::create(
+        pool,
+        project_id,
+        &CreateProjectColumn {
+            name: name.to_string(),
+            status: Some(status),
+            wip_limit: None,
+            counts_as_done: Some(counts_as_done),
+        },
+    )
+    .await?;
+    columns.push(column.clone());
+    result.columns_created += 1;
+    Ok(column)
+}
+
+/// Create the board's lists and cards in the project. Archived lists and cards are skipped
+/// unless `include_archived`.
+pub async fn import(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    board: &TrelloBoard,
+    include_archived: bool,
+) -> Result<TrelloImportResult, TrelloImportError> {
+    let mut result = TrelloImportResult {
+        board_name: board.name.clone(),
+        ..Default::default()
+    };
+    let labels: HashMap<&str, &TrelloLabel> = board
+        .labels
+        .iter()
+        .map(|label| (label.id.as_str(), label))
+        .collect();
+    let mut columns = ProjectColumn::find_by_project_id(pool, project_id).await?;
+
+    let mut lists: Vec<&TrelloList> = board.lists.iter().collect();
+    lists.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+    for list in lists {
+        let cards: Vec<&TrelloCard> = board
+            .cards
+            .iter()
+            .filter(|card| card.id_list == list.id)
+            .collect();
+        if list.closed && !include_archived {
+            result.archived_skipped += 1 + cards.len();
+            continue;
+        }
+        let column = column_for_list(pool, project_id, &mut columns, list, &mut result).await?;
+
+        let mut cards = cards;
+        cards.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+        for card in cards {
+            if card.closed && !include_archived {
+                result.archived_skipped += 1;
+                continue;
+            }
+            let description = Some(card.desc.trim().to_string()).filter(|desc| !desc.is_empty());
+            let create = CreateTask {
+                status: Some(column.status.clone()),
+                due_at: card.due,
+                ..CreateTask::from_title_description(
+                    project_id,
+                    card.name.trim().to_string(),
+                    description,
+                )
+            };
+            let task = Task::create(pool, &create, Uuid::new_v4()).await?;
+            column.move_task(pool, &task, true).await?;
+
+            for name in card
+                .id_labels
+                .iter()
+                .filter_map(|id| labels.get(id.as_str()))
+                .filter_map(|label| label.display_name())
+            {
+                let label = Label::find_or_create_by_name(pool, &name).await?;
+                Label::add_to_task(pool, task.id, label.id).await?;
+            }
+            auto_assign::apply(pool, &task, TaskSource::Trello).await?;
+
+            let mut checklists: Vec<&TrelloChecklist> = board
+                .checklists
+                .iter()
+                .filter(|checklist| checklist.id_card == card.id)
+                .collect();
+            checklists.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+            let named = checklists.len() > 1;
+            for checklist in checklists {
+                let mut items: Vec<&TrelloCheckItem> = checklist.check_items.iter().collect();
+                items.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+                for item in items {
+                    // Items of several checklists are told apart by their checklist's name
+                    let text = if named {
+                        format!("{}: {}", checklist.name.trim(), item.name.trim())
+                    } else {
+                        item.name.trim().to_string()
+                    };
+                    TaskChecklistItem::create(pool, task.id, &text, item.state == "complete")
+                        .await?;
+                    result.checklist_items_created += 1;
+                }
+            }
+            result.tasks_created += 1;
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_board_id() {
+        assert_eq!(
+            board_id("https://trello.com/b/AbC123/product-roadmap").unwrap(),
+            "AbC123"
+        );
+        assert_eq!(board_id(" AbC123 ").unwrap(), "AbC123");
+        assert!(board_id("https://example.com/board").is_err());
+    }
+
+    #[test]
+    fn test_status_for_list() {
+        assert_eq!(status_for_list("Backlog"), TaskStatus::Todo);
+        assert_eq!(status_for_list("In Progress"), TaskStatus::InProgress);
+        assert_eq!(status_for_list("Code review"), TaskStatus::InReview);
+        assert_eq!(status_for_list("Done ✅"), TaskStatus::Done);
+        assert_eq!(status_for_list("Won't do"), TaskStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_parse_export() {
+        let board = parse_export(
+            r#"{
+                "name": "Roadmap",
+                "lists": [{"id": "l1", "name": "To Do", "closed": false, "pos": 16384}],
+                "cards": [{"id": "c1", "name": "Login page", "desc": "", "closed": false,
+                           "idList": "l1", "due": "2026-02-01T12:00:00.000Z", "idLabels": ["g"],
+                           "pos": 1}],
+                "labels": [{"id": "g", "name": "", "color": "green"}],
+                "checklists": [{"id": "k1", "idCard": "c1", "name": "Checklist", "pos": 1,
+                                "checkItems": [{"name": "Design", "state": "complete", "pos": 1}]}],
+                "actions": []
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(board.cards[0].id_list, "l1");
+        assert!(board.cards[0].due.is_some());
+        assert_eq!(board.labels[0].display_name().as_deref(), Some("green"));
+        assert_eq!(board.checklists[0].check_items[0].state, "complete");
+        assert!(parse_export(r#"{"lists": []}"#).is_err());
+    }
+}
@@ -0,0 +1,374 @@
+//! Token usage and API spend read from an execution's raw output. Claude Code, and executors
+//! speaking its stream-json format, report usage and cost on their final `result` line; Codex
+//! reports running token totals in `token_count` events and no cost, so its cost is estimated
+//! from list prices. Aider prints a `Tokens: ... Cost: ...` line after each model response.
+
+use db::models::execution_usage::RecordUsage;
+use serde_json::{Map, Value};
+
+/// USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ModelPrice {
+    input: f64,
+    output: f64,
+    cache_read: f64,
+    cache_write: f64,
+}
+
+const fn price(input: f64, output: f64, cache_read: f64, cache_write: f64) -> ModelPrice {
+    ModelPrice {
+        input,
+        output,
+        cache_read,
+        cache_write,
+    }
+}
+
+/// List prices by model name prefix, more specific prefixes first. Only used when the executor
+/// doesn't report a cost itself.
+const PRICES: &[(&str, ModelPrice)] = &[
+    ("claude-opus-4-5", price(5.0, 25.0, 0.5, 6.25)),
+    ("claude-opus-4", price(15.0, 75.0, 1.5, 18.75)),
+    ("claude-sonnet-4", price(3.0, 15.0, 0.3, 3.75)),
+    ("claude-3-7-sonnet", price(3.0, 15.0, 0.3, 3.75)),
+    ("claude-haiku-4-5", price(1.0, 5.0, 0.1, 1.25)),
+    ("claude-3-5-haiku", price(0.8, 4.0, 0.08, 1.0)),
+    ("gpt-5-mini", price(0.25, 2.0, 0.025, 0.0)),
+    ("gpt-5-nano", price(0.05, 0.4, 0.005, 0.0)),
+    ("gpt-5", price(1.25, 10.0, 0.125, 0.0)),
+    ("gemini-2.5-pro", price(1.25, 10.0, 0.125, 0.0)),
+    ("gemini-2.5-flash", price(0.3, 2.5, 0.03, 0.0)),
+];
+
+/// Cost of the tokens at the model's list price; `None` for models without a known price
+pub fn estimate_cost(model: &str, usage: &RecordUsage) -> Option<f64> {
+    let model = model.to_ascii_lowercase();
+    let (_, price) = PRICES
+        .iter()
+        .find(|(prefix, _)| model.starts_with(prefix))?;
+    let cost = usage.input_tokens as f64 * price.input
+        + usage.output_tokens as f64 * price.output
+        + usage.cache_read_tokens as f64 * price.cache_read
+        + usage.cache_write_tokens as f64 * price.cache_write;
+    Some(cost / 1_000_000.0)
+}
+
+/// Codex token counts, where input includes cached input
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct CodexTokens {
+    input: i64,
+    cached_input: i64,
+    output: i64,
+}
+
+impl CodexTokens {
+    fn from_json(value: Option<&Value>) -> Option<Self> {
+        let value = value?.as_object()?;
+        let count = |key: &str| value.get(key).and_then(Value::as_i64).unwrap_or(0);
+        Some(Self {
+            input: count("input_tokens"),
+            cached_input: count("cached_input_tokens"),
+            output: count("output_tokens"),
+        })
+    }
+
+    fn minus(self, other: Self) -> Self {
+        Self {
+            input: (self.input - other.input).max(0),
+            cached_input: (self.cached_input - other.cached_input).max(0),
+            output: (self.output - other.output).max(0),
+        }
+    }
+}
+
+/// First object in `value`, itself included, that satisfies `pred`
+fn find_object<'a>(
+    value: &'a Value,
+    pred: &dyn Fn(&Map<String, Value>) -> bool,
+) -> Option<&'a Map<String, Value>> {
+    match value {
+        Value::Object(map) if pred(map) => Some(map),
+        Value::Object(map) => map.values().find_map(|value| find_object(value, pred)),
+        Value::Array(values) => values.iter().find_map(|value| find_object(value, pred)),
+        _ => None,
+    }
+}
+
+/// Reads usage from an execution's stdout, one line at a time
+#[derive(Debug, Default)]
+pub struct UsageReader {
+    model: Option<String>,
+    /// Usage on the last Claude `result` line
+    claude: Option<RecordUsage>,
+    /// Codex session totals from before this execution, for resumed sessions
+    codex_start: Option<CodexTokens>,
+    codex_total: Option<CodexTokens>,
+    /// Sum of Aider's per-response reports
+    aider: Option<RecordUsage>,
+}
+
+impl UsageReader {
+    pub fn read_line(&mut self, line: &str) {
+        let line = line.trim();
+        if let Some(model) = line.strip_prefix("Main model: ") {
+            // `Main model: anthropic/claude-sonnet-4-20250514 with diff edit format, ...`
+            let model = model.split(" with ").next().unwrap_or(model);
+            let model = model.rsplit('/').next().unwrap_or(model);
+            self.model = Some(model.trim().to_string());
+            return;
+        }
+        if let Some(report) = line.strip_prefix("Tokens: ") {
+            self.read_aider_report(report);
+            return;
+        }
+        if !line.starts_with('{') {
+            return;
+        }
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            return;
+        };
+        match value.get("type").and_then(Value::as_str) {
+            Some("result") if value.get("usage").is_some() => self.read_claude_result(&value),
+            Some("system") | Some("assistant") => {
+                let model = value
+                    .get("model")
+                    .or_else(|| value.pointer("/message/model"))
+                    .and_then(Value::as_str);
+                if self.model.is_none()
+                    && let Some(model) = model
+                {
+                    self.model = Some(model.to_string());
+                }
+            }
+            _ => self.read_codex_event(&value),
+        }
+    }
+
+    fn read_claude_result(&mut self, value: &Value) {
+        let count = |key: &str| {
+            value
+                .pointer(&format!("/usage/{key}"))
+                .and_then(Value::as_i64)
+                .unwrap_or(0)
+        };
+        // The model that cost the most, when the result breaks usage down per model
+        let main_model = value
+            .get("modelUsage")
+            .and_then(Value::as_object)
+            .and_then(|models| {
+                models
+                    .iter()
+                    .max_by(|(_, a), (_, b)| {
+                        let cost = |usage: &Value| {
+                            usage.get("costUSD").and_then(Value::as_f64).unwrap_or(0.0)
+                        };
+                        cost(a).total_cmp(&cost(b))
+                    })
+                    .map(|(model, _)| model.clone())
+            });
+        if main_model.is_some() {
+            self.model = main_model;
+        }
+        self.claude = Some(RecordUsage {
+            model: None,
+            input_tokens: count("input_tokens"),
+            output_tokens: count("output_tokens"),
+            cache_read_tokens: count("cache_read_input_tokens"),
+            cache_write_tokens: count("cache_creation_input_tokens"),
+            cost_usd: value.get("total_cost_usd").and_then(Value::as_f64),
+            cost_estimated: false,
+        });
+    }
+
+    fn read_codex_event(&mut self, value: &Value) {
+        if let Some(session) = find_object(value, &|map| {
+            map.get("type").and_then(Value::as_str) == Some("session_configured")
+        }) && let Some(model) = session.get("model").and_then(Value::as_str)
+        {
+            self.model = Some(model.to_string());
+        }
+
+        let Some(info) = find_object(value, &|map| map.contains_key("total_token_usage")) else {
+            return;
+        };
+        let Some(total) = CodexTokens::from_json(info.get("total_token_usage")) else {
+            return;
+        };
+        if self.codex_start.is_none() {
+            let last = CodexTokens::from_json(info.get("last_token_usage")).unwrap_or_default();
+            self.codex_start = Some(total.minus(last));
+        }
+        self.codex_total = Some(total);
+    }
+
+    /// `2.5k sent, 1.0k cache write, 9.0k cache hit, 307 received. Cost: $0.02 message, $0.05
+    /// session.`
+    fn read_aider_report(&mut self, report: &str) {
+        let (tokens, cost) = report.split_once(". Cost: ").unwrap_or((report, ""));
+        let usage = self.aider.get_or_insert_with(RecordUsage::default);
+        for part in tokens.trim_end_matches('.').split(", ") {
+            let Some((count, label)) = part.split_once(' ') else {
+                continue;
+            };
+            let Some(count) = parse_token_count(count) else {
+                continue;
+            };
+            match label {
+                "sent" => usage.input_tokens += count,
+                "received" => usage.output_tokens += count,
+                "cache hit" => usage.cache_read_tokens += count,
+                "cache write" => usage.cache_write_tokens += count,
+                _ => {}
+            }
+        }
+        if let Some(cost) = cost
+            .strip_prefix('$')
+            .and_then(|cost| cost.split_once(" message"))
+            .and_then(|(cost, _)| cost.parse::<f64>().ok())
+        {
+            usage.cost_usd = Some(usage.cost_usd.unwrap_or(0.0) + cost);
+        }
+    }
+
+    /// The execution's usage, with its cost reported or estimated where possible. `None` when
+    /// the output carried no usage.
+    pub fn finish(self) -> Option<RecordUsage> {
+        let mut usage = match (self.claude.or(self.aider), self.codex_total) {
+            (Some(usage), _) => usage,
+            (None, Some(total)) => {
+                let tokens = total.minus(self.codex_start.unwrap_or_default());
+                RecordUsage {
+                    input_tokens: (tokens.input - tokens.cached_input).max(0),
+                    output_tokens: tokens.output,
+                    cache_read_tokens: tokens.cached_input,
+                    ..Default::default()
+                }
+            }
+            (None, None) => return None,
+        };
+        usage.model = self.model;
+        if usage.cost_usd.is_none()
+            && let Some(cost) = usage
+                .model
+                .as_deref()
+                .and_then(|model| estimate_cost(model, &usage))
+        {
+            usage.cost_usd = Some(cost);
+            usage.cost_estimated = true;
+        }
+        Some(usage)
+    }
+}
+
+/// Aider's rounded token counts: `307`, `2.5k`, `12k`, `1.2M`
+fn parse_token_count(count: &str) -> Option<i64> {
+    let (number, scale) = match count.chars().last()? {
+        'k' => (&count[..count.len() - 1], 1_000.0),
+        'M' => (&count[..count.len() - 1], 1_000_000.0),
+        _ => (count, 1.0),
+    };
+    let count = number.parse::<f64>().ok()? * scale;
+    Some(count.round() as i64)
+}
+
+/// Usage from a whole execution's stdout
+pub fn read_usage<'a>(lines: impl IntoIterator<Item = &'a str>) -> Option<RecordUsage> {
+    let mut reader = UsageReader::default();
+    for line in lines {
+        reader.read_line(line);
+    }
+    reader.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claude_result_with_reported_cost() {
+        let output = [
+            r#"{"type":"system","subtype":"init","model":"claude-sonnet-4-5-20250929","session_id":"s"}"#,
+            r#"{"type":"assistant","message":{"model":"claude-sonnet-4-5-20250929","content":[]}}"#,
+            "not json",
+            r#"{"type":"result","subtype":"success","total_cost_usd":0.42,"usage":{"input_tokens":120,"output_tokens":3400,"cache_creation_input_tokens":9000,"cache_read_input_tokens":250000},"modelUsage":{"claude-haiku-4-5":{"costUSD":0.01},"claude-sonnet-4-5-20250929":{"costUSD":0.41}}}"#,
+        ];
+        assert_eq!(
+            read_usage(output),
+            Some(RecordUsage {
+                model: Some("claude-sonnet-4-5-20250929".to_string()),
+                input_tokens: 120,
+                output_tokens: 3_400,
+                cache_read_tokens: 250_000,
+                cache_write_tokens: 9_000,
+                cost_usd: Some(0.42),
+                cost_estimated: false,
+            })
+        );
+        assert_eq!(read_usage(["plain text", r#"{"type":"user"}"#]), None);
+    }
+
+    #[test]
+    fn test_codex_resumed_session_is_estimated() {
+        let output = [
+            r#"{"method":"codex/event/session_configured","params":{"msg":{"type":"session_configured","model":"gpt-5-codex"}}}"#,
+            r#"{"method":"codex/event/token_count","params":{"msg":{"type":"token_count","info":{"total_token_usage":{"input_tokens":11000,"cached_input_tokens":8000,"output_tokens":600},"last_token_usage":{"input_tokens":1000,"cached_input_tokens":0,"output_tokens":100}}}}}"#,
+            r#"{"method":"codex/event/token_count","params":{"msg":{"type":"token_count","info":null}}}"#,
+            r#"{"method":"codex/event/token_count","params":{"msg":{"type":"token_count","info":{"total_token_usage":{"input_tokens":1010000,"cached_input_tokens":8000,"output_tokens":100500},"last_token_usage":{"input_tokens":999000,"cached_input_tokens":0,"output_tokens":99900}}}}}"#,
+        ];
+        let usage = read_usage(output).unwrap();
+        assert_eq!(usage.model.as_deref(), Some("gpt-5-codex"));
+        assert_eq!(
+            (
+                usage.input_tokens,
+                usage.output_tokens,
+                usage.cache_read_tokens
+            ),
+            (1_000_000, 100_000, 0)
+        );
+        assert!(usage.cost_estimated);
+        assert_eq!(usage.cost_usd, Some(2.25));
+    }
+
+    #[test]
+    fn test_aider_reports_are_summed() {
+        let output = [
+            "Aider v0.86.1",
+            "Main model: anthropic/claude-sonnet-4-20250514 with diff edit format, infinite output",
+            "Tokens: 2.5k sent, 1.0k cache write, 307 received. Cost: $0.02 message, $0.02 session.",
+            "Applied edit to src/main.rs",
+            "Tokens: 12k sent, 9.0k cache hit, 1.2k received. Cost: $0.05 message, $0.07 session.",
+        ];
+        let usage = read_usage(output).unwrap();
+        assert_eq!(usage.model.as_deref(), Some("claude-sonnet-4-20250514"));
+        assert_eq!(
+            (
+                usage.input_tokens,
+                usage.output_tokens,
+                usage.cache_read_tokens,
+                usage.cache_write_tokens
+            ),
+            (14_500, 1_507, 9_000, 1_000)
+        );
+        assert!(!usage.cost_estimated);
+        assert!((usage.cost_usd.unwrap() - 0.07).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_estimate_cost_by_model_prefix() {
+        let usage = RecordUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 1_000_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            estimate_cost("claude-opus-4-5-20251101", &usage),
+            Some(30.0)
+        );
+        assert_eq!(
+            estimate_cost("claude-opus-4-1-20250805", &usage),
+            Some(90.0)
+        );
+        assert_eq!(estimate_cost("GPT-5-mini", &usage), Some(2.25));
+        assert_eq!(estimate_cost("some-local-model", &usage), None);
+    }
+}
@@ -0,0 +1,199 @@
+use std::{collections::HashMap, fmt};
+
+use db::models::{
+    execution_process::ExecutionProcess,
+    project_column::ProjectColumn,
+    task::{Task, TaskStatus},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum WipScope {
+    Column,
+    Executor,
+}
+
+/// A WIP limit that is exceeded, or would be by the change being made
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct WipViolation {
+    pub scope: WipScope,
+    /// Column name or executor
+    pub name: String,
+    pub limit: i64,
+    /// Tasks in the column or attempts running, counting the one being added
+    pub count: i64,
+}
+
+impl fmt::Display for WipViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.scope {
+            WipScope::Column => write!(
+                f,
+                "column '{}' would hold {} tasks (WIP limit {})",
+                self.name, self.count, self.limit
+            ),
+            WipScope::Executor => write!(
+                f,
+                "{} would run {} attempts at once (WIP limit {})",
+                self.name, self.count, self.limit
+            ),
+        }
+    }
+}
+
+/// Violations as one sentence, for API errors
+pub fn describe(violations: &[WipViolation]) -> String {
+    let parts: Vec<String> = violations.iter().map(ToString::to_string).collect();
+    format!("WIP limit reached: {}", parts.join("; "))
+}
+
+/// Violations as `name count/limit` pairs, for notifications in any language
+pub fn counts(violations: &[WipViolation]) -> String {
+    violations
+        .iter()
+        .map(|violation| format!("{} {}/{}", violation.name, violation.count, violation.limit))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The violation from adding one more item, if `current` is already at `limit`
+fn check(scope: WipScope, name: &str, limit: Option<i64>, current: i64) -> Option<WipViolation> {
+    let limit = limit?;
+    (current >= limit).then(|| WipViolation {
+        scope,
+        name: name.to_string(),
+        limit,
+        count: current + 1,
+    })
+}
+
+/// The violation from moving one more task into `column`
+pub fn column_violation(column: &ProjectColumn) -> Option<WipViolation> {
+    check(
+        WipScope::Column,
+        &column.name,
+        column.wip_limit,
+        column.task_count,
+    )
+}
+
+/// Limits that starting an attempt with `executor` would exceed: the executor's own limit, and
+/// the limit of the in-progress column the task moves to unless it is in one already. `task` is
+/// `None` for a task that is yet to be created.
+pub async fn start_violations(
+    pool: &SqlitePool,
+    executor_limits: &HashMap<String, u32>,
+    project_id: Uuid,
+    task: Option<&Task>,
+    executor: &str,
+) -> Result<Vec<WipViolation>, sqlx::Error> {
+    let mut violations = Vec::new();
+
+    if task.is_none_or(|task| task.status != TaskStatus::InProgress)
+        && let Some(column) =
+            ProjectColumn::find_first_for_status(pool, project_id, TaskStatus::InProgress).await?
+        && let Some(violation) = column_violation(&column)
+    {
+        violations.push(violation);
+    }
+
+    if let Some(&limit) = executor_limits.get(executor) {
+        let running = ExecutionProcess::count_running_attempts_by_executor(pool)
+            .await?
+            .into_iter()
+            .find(|(name, _)| name == executor)
+            .map(|(_, count)| count)
+            .unwrap_or(0);
+        if let Some(violation) = check(WipScope::Executor, executor, Some(limit as i64), running) {
+            violations.push(violation);
+        }
+    }
+
+    Ok(violations)
+}
+
+/// Limits that are currently exceeded, e.g. after a move or start that overrode them; columns
+/// are limited to the project's
+pub async fn current_violations(
+    pool: &SqlitePool,
+    executor_limits: &HashMap<String, u32>,
+    project_id: Uuid,
+) -> Result<Vec<WipViolation>, sqlx::Error> {
+    let mut violations: Vec<WipViolation> = ProjectColumn::find_by_project_id(pool, project_id)
+        .await?
+        .into_iter()
+        .filter_map(|column| {
+            over_limit(
+                WipScope::Column,
+                column.name,
+                column.wip_limit,
+                column.task_count,
+            )
+        })
+        .collect();
+
+    for (executor, running) in ExecutionProcess::count_running_attempts_by_executor(pool).await? {
+        let limit = executor_limits.get(&executor).map(|&limit| limit as i64);
+        violations.extend(over_limit(WipScope::Executor, executor, limit, running));
+    }
+
+    Ok(violations)
+}
+
+fn over_limit(
+    scope: WipScope,
+    name: String,
+    limit: Option<i64>,
+    count: i64,
+) -> Option<WipViolation> {
+    let limit = limit?;
+    (count > limit).then_some(WipViolation {
+        scope,
+        name,
+        limit,
+        count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(check(WipScope::Column, "Doing", None, 10), None);
+        assert_eq!(check(WipScope::Column, "Doing", Some(3), 2), None);
+        let violation = check(WipScope::Column, "Doing", Some(3), 3).unwrap();
+        assert_eq!(violation.count, 4);
+        assert_eq!(
+            over_limit(WipScope::Executor, "CODEX".into(), Some(2), 2),
+            None
+        );
+        assert_eq!(
+            over_limit(WipScope::Executor, "CODEX".into(), Some(2), 3)
+                .unwrap()
+                .count,
+            3
+        );
+
+        let violations = [
+            violation,
+            WipViolation {
+                scope: WipScope::Executor,
+                name: "CLAUDE_CODE".to_string(),
+                limit: 2,
+                count: 3,
+            },
+        ];
+        assert_eq!(
+            describe(&violations),
+            "WIP limit reached: column 'Doing' would hold 4 tasks (WIP limit 3); \
+             CLAUDE_CODE would run 3 attempts at once (WIP limit 2)"
+        );
+        assert_eq!(counts(&violations), "Doing 4/3, CLAUDE_CODE 3/2");
+    }
+}
@@ -36,6 +36,14 @@ pub fn credentials_path() -> std::path::PathBuf {
     asset_dir().join("credentials.json")
 }
 
+pub fn vapid_keys_path() -> std::path::PathBuf {
+    asset_dir().join("vapid_keys.json")
+}
+
+pub fn secrets_key_path() -> std::path::PathBuf {
+    asset_dir().join("secrets.key")
+}
+
 #[derive(RustEmbed)]
 #[folder = "../../assets/sounds"]
 pub struct SoundAssets;
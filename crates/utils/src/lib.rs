@@ -13,6 +13,7 @@ pub mod log_msg;
 pub mod msg_store;
 pub mod path;
 pub mod port_file;
+pub mod references;
 pub mod response;
 pub mod sentry;
 pub mod shell;
@@ -69,21 +70,17 @@ pub async fn get_powershell_script()
     let cache_dir = cache_dir();
     let script_path = cache_dir.join("toast-notification.ps1");
 
-    // Check if cached file already exists and is valid
-    if script_path.exists() {
-        // Verify file has content (basic validation)
-        if let Ok(metadata) = std::fs::metadata(&script_path)
-            && metadata.len() > 0
-        {
-            return Ok(script_path);
-        }
-    }
-
-    // File doesn't exist or is invalid, create it
     let script_content = assets::ScriptAssets::get("toast-notification.ps1")
         .ok_or("Embedded PowerShell script not found: toast-notification.ps1")?
         .data;
 
+    // Reuse the cached file unless it is missing or from an older release
+    if let Ok(existing) = std::fs::read(&script_path)
+        && existing == script_content.as_ref()
+    {
+        return Ok(script_path);
+    }
+
     // Ensure cache directory exists
     std::fs::create_dir_all(&cache_dir)
         .map_err(|e| format!("Failed to create cache directory: {e}"))?;
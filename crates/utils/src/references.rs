@@ -0,0 +1,170 @@
+//! References to other tasks, files and commits written in markdown, e.g. `#12`,
+//! `src/main.rs:40` or `3f2a9c1`
+
+/// A reference as written; whether it points at anything is decided when it's resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reference {
+    /// `#number` of a task in the same project
+    Task(i64),
+    /// Abbreviated or full commit SHA
+    Commit(String),
+    /// Repo-relative path, optionally with a line
+    File { path: String, line: Option<u32> },
+}
+
+impl Reference {
+    /// The reference in the form it's written in
+    pub fn text(&self) -> String {
+        match self {
+            Reference::Task(number) => format!("#{number}"),
+            Reference::Commit(sha) => sha.clone(),
+            Reference::File {
+                path,
+                line: Some(line),
+            } => format!("{path}:{line}"),
+            Reference::File { path, line: None } => path.clone(),
+        }
+    }
+}
+
+fn task_number(word: &str) -> Option<i64> {
+    let digits = word.strip_prefix('#')?;
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().filter(|number| *number > 0)
+}
+
+/// 7 to 40 lowercase hex digits mixing letters and digits, so plain numbers and words such as
+/// `deadbeef` or `defaced` aren't taken for SHAs
+fn commit_sha(word: &str) -> Option<String> {
+    let hex = (7..=40).contains(&word.len())
+        && word
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+    let mixed =
+        word.bytes().any(|b| b.is_ascii_digit()) && word.bytes().any(|b| b.is_ascii_alphabetic());
+    (hex && mixed).then(|| word.to_string())
+}
+
+/// Relative paths with a directory or an extension; absolute paths, URLs and paths leaving the
+/// repo are skipped. Outside code spans a directory is required, so `e.g.` or `v1.2` don't count.
+fn file_path(word: &str, in_code: bool) -> Option<(String, Option<u32>)> {
+    let (path, line) = match word.rsplit_once(':') {
+        Some((path, line)) if !line.is_empty() && line.bytes().all(|b| b.is_ascii_digit()) => {
+            (path, line.parse().ok())
+        }
+        _ => (word, None),
+    };
+    let path = path.strip_prefix("./").unwrap_or(path);
+    let valid_chars = path
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '.' | '_' | '-'));
+    let file_name = path.rsplit('/').next().unwrap_or_default();
+    if !valid_chars
+        || path.starts_with('/')
+        || path.ends_with('/')
+        || path.split('/').any(|part| part.is_empty() || part == "..")
+        || file_name.trim_matches('.').is_empty()
+        || !path.chars().any(|c| c.is_ascii_alphabetic())
+    {
+        return None;
+    }
+    let has_dir = path.contains('/');
+    let has_extension = file_name
+        .rsplit_once('.')
+        .is_some_and(|(stem, ext)| !stem.is_empty() && !ext.is_empty());
+    (has_dir || (in_code && has_extension)).then(|| (path.to_string(), line))
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace()
+        || matches!(
+            c,
+            '(' | ')' | '[' | ']' | '<' | '>' | '"' | '\'' | ',' | ';'
+        )
+}
+
+/// References in a markdown text in the order they first appear, without duplicates. Fenced code
+/// blocks are skipped; inline code spans are read, since that's how paths are usually written.
+pub fn extract_references(markdown: &str) -> Vec<Reference> {
+    let mut references = Vec::new();
+    let mut in_fence = false;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        for word in line.split(is_delimiter) {
+            if word.contains("://") {
+                continue;
+            }
+            let in_code = word.starts_with('`') || word.ends_with('`');
+            let word = word
+                .trim_matches('`')
+                .trim_end_matches(['.', ':', '!', '?', '*', '_']);
+            let word = word.trim_start_matches(['*', '_']);
+            let reference = if let Some(number) = task_number(word) {
+                Reference::Task(number)
+            } else if let Some(sha) = commit_sha(word) {
+                Reference::Commit(sha)
+            } else if let Some((path, line)) = file_path(word, in_code) {
+                Reference::File { path, line }
+            } else {
+                continue;
+            };
+            if !references.contains(&reference) {
+                references.push(reference);
+            }
+        }
+    }
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, line: Option<u32>) -> Reference {
+        Reference::File {
+            path: path.to_string(),
+            line,
+        }
+    }
+
+    #[test]
+    fn test_extract_references() {
+        let markdown = "Follows #12 and (#7), see `crates/db/src/models/task.rs:40` and \
+                        ./frontend/src/App.tsx.\nBroke in 3f2a9c1, fixed by #12 again.\n\
+                        Config lives in `Cargo.toml`; docs at https://example.com/docs/a.md";
+        assert_eq!(
+            extract_references(markdown),
+            vec![
+                Reference::Task(12),
+                Reference::Task(7),
+                file("crates/db/src/models/task.rs", Some(40)),
+                file("frontend/src/App.tsx", None),
+                Reference::Commit("3f2a9c1".to_string()),
+                file("Cargo.toml", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_references_ignores_lookalikes() {
+        let markdown = "Issue#4, #0, #abc, e.g. v1.2 or README.md, 1234567 and deadbeef, \
+                        /etc/passwd and ../secret.txt\n\
+                        ```\nlet x = \"#5 src/lib.rs\";\n```";
+        assert_eq!(extract_references(markdown), vec![]);
+    }
+
+    #[test]
+    fn test_reference_text() {
+        assert_eq!(Reference::Task(3).text(), "#3");
+        assert_eq!(file("src/a.rs", Some(9)).text(), "src/a.rs:9");
+        assert_eq!(file("src/a.rs", None).text(), "src/a.rs");
+    }
+}
@@ -40,6 +40,40 @@ pub fn truncate_to_char_boundary(content: &str, max_len: usize) -> &str {
     &content[..cutoff]
 }
 
+/// Items of a markdown task list with whether each is ticked, e.g. `- [x] Write tests`
+pub fn checklist_states(markdown: &str) -> Vec<(String, bool)> {
+    markdown
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim_start();
+            let rest = line
+                .strip_prefix("- ")
+                .or_else(|| line.strip_prefix("* "))
+                .or_else(|| line.strip_prefix("+ "))?
+                .trim_start();
+            let (done, item) = if let Some(item) = rest.strip_prefix("[ ]") {
+                (false, item)
+            } else {
+                (
+                    true,
+                    rest.strip_prefix("[x]")
+                        .or_else(|| rest.strip_prefix("[X]"))?,
+                )
+            };
+            let item = item.trim();
+            (!item.is_empty()).then(|| (item.to_string(), done))
+        })
+        .collect()
+}
+
+/// Unchecked items of a markdown task list, such as `- [ ] Write tests`
+pub fn checklist_items(markdown: &str) -> Vec<String> {
+    checklist_states(markdown)
+        .into_iter()
+        .filter_map(|(item, done)| (!done).then_some(item))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -57,4 +91,31 @@ mod tests {
         assert_eq!(truncate_to_char_boundary(input, 5), "🔥");
         assert_eq!(truncate_to_char_boundary(input, 3), "");
     }
+
+    #[test]
+    fn test_checklist_items() {
+        use super::checklist_items;
+
+        let description = "Plan:\n- [ ] Add the table\n  * [x] Sketch the API\n+ [ ]  Wire the routes \n- [ ]\n- not a task";
+        assert_eq!(
+            checklist_items(description),
+            vec!["Add the table".to_string(), "Wire the routes".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_checklist_states() {
+        use super::checklist_states;
+
+        let message =
+            "Done:\n- [x] Add the table\n* [X] Sketch the API\n- [ ] Wire the routes\n- [x]";
+        assert_eq!(
+            checklist_states(message),
+            vec![
+                ("Add the table".to_string(), true),
+                ("Sketch the API".to_string(), true),
+                ("Wire the routes".to_string(), false),
+            ]
+        );
+    }
 }
@@ -9,7 +9,8 @@ import { Card, CardContent } from '@/components/ui/card';
 import { Button } from '@/components/ui/button';
 import { Loader2 } from 'lucide-react';
 import { shadcnTheme } from './rjsf';
-import { BaseCodingAgent } from 'shared/types';
+import { profilesApi } from '@/lib/api';
+import { BaseCodingAgent, LocalLlm, LocalLlmTestResult } from 'shared/types';
 // Using custom shadcn/ui widgets instead of @rjsf/shadcn theme
 
 interface ExecutorConfigFormProps {
@@ -40,10 +41,37 @@ export function ExecutorConfigForm({
     RJSFValidationError[]
   >([]);
 
+  const [localLlmTest, setLocalLlmTest] = useState<LocalLlmTestResult | null>(
+    null
+  );
+  const [localLlmTesting, setLocalLlmTesting] = useState(false);
+  const [localLlmTestError, setLocalLlmTestError] = useState<string | null>(
+    null
+  );
+
   const schema = useMemo(() => {
     return schemas[executor];
   }, [executor]);
 
+  const localLlm = (formData as { local_llm?: LocalLlm | null } | null)
+    ?.local_llm;
+
+  const handleTestLocalLlm = async () => {
+    if (!localLlm?.model) return;
+    setLocalLlmTesting(true);
+    setLocalLlmTest(null);
+    setLocalLlmTestError(null);
+    try {
+      setLocalLlmTest(await profilesApi.testLocalLlm(localLlm));
+    } catch (err) {
+      setLocalLlmTestError(
+        err instanceof Error ? err.message : 'Failed to test the endpoint'
+      );
+    } finally {
+      setLocalLlmTesting(false);
+    }
+  };
+
   // Custom handler for env field updates
   const handleEnvChange = useCallback(
     (envData: Record<string, string> | undefined) => {
@@ -81,6 +109,11 @@ export function ExecutorConfigForm({
     setValidationErrors([]);
   }, [value, executor]);
 
+  useEffect(() => {
+    setLocalLlmTest(null);
+    setLocalLlmTestError(null);
+  }, [executor]);
+
   const handleChange = (event: IChangeEvent<unknown>) => {
     const newFormData = event.formData;
     setFormData(newFormData);
@@ -150,6 +183,59 @@ export function ExecutorConfigForm({
         </CardContent>
       </Card>
 
+      {localLlm && (
+        <div className="space-y-3">
+          <div className="flex items-center justify-between gap-4">
+            <p className="text-sm text-muted-foreground">
+              Check that the local model endpoint answers and how fast the
+              model responds.
+            </p>
+            <Button
+              type="button"
+              variant="outline"
+              onClick={handleTestLocalLlm}
+              disabled={!localLlm.model || localLlmTesting}
+            >
+              {localLlmTesting && (
+                <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+              )}
+              Test Endpoint
+            </Button>
+          </div>
+          {localLlmTestError && (
+            <Alert variant="destructive">
+              <AlertDescription>{localLlmTestError}</AlertDescription>
+            </Alert>
+          )}
+          {localLlmTest && (
+            <Alert variant={localLlmTest.error ? 'destructive' : 'default'}>
+              <AlertDescription>
+                <ul className="list-disc list-inside space-y-1">
+                  {localLlmTest.error ? (
+                    <li>{localLlmTest.error}</li>
+                  ) : (
+                    <li>
+                      Model answered in {localLlmTest.response_latency_ms} ms
+                    </li>
+                  )}
+                  {localLlmTest.endpoint_latency_ms !== null && (
+                    <li>
+                      Endpoint reachable in {localLlmTest.endpoint_latency_ms}{' '}
+                      ms
+                    </li>
+                  )}
+                  {localLlmTest.warnings.map((warning, index) => (
+                    <li key={index} className="text-warning">
+                      {warning}
+                    </li>
+                  ))}
+                </ul>
+              </AlertDescription>
+            </Alert>
+          )}
+        </div>
+      )}
+
       {validationErrors.length > 0 && (
         <Alert variant="destructive">
           <AlertDescription>
@@ -9,6 +9,8 @@ import {
   DialogTitle,
 } from '@/components/ui/dialog';
 import { Button } from '@/components/ui/button';
+import { Input } from '@/components/ui/input';
+import { Label } from '@/components/ui/label';
 import RepoBranchSelector from '@/components/tasks/RepoBranchSelector';
 import { ExecutorProfileSelector } from '@/components/settings';
 import { useAttemptCreation } from '@/hooks/useAttemptCreation';
@@ -50,6 +52,7 @@ const CreateAttemptDialogImpl = NiceModal.create<CreateAttemptDialogProps>(
 
     const [userSelectedProfile, setUserSelectedProfile] =
       useState<ExecutorProfileId | null>(null);
+    const [modelOverride, setModelOverride] = useState('');
 
     const { data: attempts = [], isLoading: isLoadingAttempts } =
       useTaskAttemptsWithSessions(taskId, {
@@ -94,6 +97,7 @@ const CreateAttemptDialogImpl = NiceModal.create<CreateAttemptDialogProps>(
     useEffect(() => {
       if (!modal.visible) {
         setUserSelectedProfile(null);
+        setModelOverride('');
         resetBranchSelection();
       }
     }, [modal.visible, resetBranchSelection]);
@@ -149,8 +153,11 @@ const CreateAttemptDialogImpl = NiceModal.create<CreateAttemptDialogProps>(
       try {
         const repos = getWorkspaceRepoInputs();
 
+        const model = modelOverride.trim();
         await createAttempt({
-          profile: effectiveProfile,
+          profile: model
+            ? { ...effectiveProfile, model_override: model }
+            : effectiveProfile,
           repos,
         });
 
@@ -192,6 +199,19 @@ const CreateAttemptDialogImpl = NiceModal.create<CreateAttemptDialogProps>(
               </div>
             )}
 
+            <div className="space-y-2">
+              <Label htmlFor="model-override">
+                {t('createAttemptDialog.modelOverride')}
+              </Label>
+              <Input
+                id="model-override"
+                value={modelOverride}
+                onChange={(e) => setModelOverride(e.target.value)}
+                placeholder={t('createAttemptDialog.modelOverridePlaceholder')}
+                disabled={isCreating}
+              />
+            </div>
+
             <RepoBranchSelector
               configs={repoBranchConfigs}
               onBranchChange={setRepoBranch}
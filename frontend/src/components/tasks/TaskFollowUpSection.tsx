@@ -2,6 +2,8 @@ import {
   Loader2,
   Send,
   StopCircle,
+  Pause,
+  Play,
   AlertCircle,
   Clock,
   X,
@@ -42,6 +44,7 @@ import { useProject } from '@/contexts/ProjectContext';
 import { VariantSelector } from '@/components/tasks/VariantSelector';
 import { useAttemptBranch } from '@/hooks/useAttemptBranch';
 import { FollowUpConflictSection } from '@/components/tasks/follow-up/FollowUpConflictSection';
+import { CheckpointsMenu } from '@/components/tasks/follow-up/CheckpointsMenu';
 import { ClickedElementsBanner } from '@/components/tasks/ClickedElementsBanner';
 import WYSIWYGEditor from '@/components/ui/wysiwyg';
 import { useRetryUi } from '@/contexts/RetryUiContext';
@@ -80,6 +83,24 @@ export function TaskFollowUpSection({
 
   const { isAttemptRunning, stopExecution, isStopping, processes } =
     useAttemptExecution(workspaceId, task.id);
+  const [isTogglingPause, setIsTogglingPause] = useState(false);
+
+  // The task's paused flag arrives over the task stream once the request lands
+  const togglePause = useCallback(async () => {
+    if (!workspaceId || isTogglingPause) return;
+    try {
+      setIsTogglingPause(true);
+      if (task.has_paused_attempt) {
+        await attemptsApi.resume(workspaceId);
+      } else {
+        await attemptsApi.pause(workspaceId);
+      }
+    } catch (error) {
+      console.error('Failed to pause or resume attempt:', error);
+    } finally {
+      setIsTogglingPause(false);
+    }
+  }, [workspaceId, isTogglingPause, task.has_paused_attempt]);
 
   const { data: branchStatus, refetch: refetchBranchStatus } =
     useBranchStatus(workspaceId);
@@ -792,6 +813,12 @@ export function TaskFollowUpSection({
             <MessageSquare className="h-4 w-4" />
           </Button>
 
+          {/* Checkpoints dropdown - save one or roll back between turns */}
+          <CheckpointsMenu
+            workspaceId={workspaceId}
+            disabled={isAttemptRunning}
+          />
+
           {/* Scripts dropdown - only show if project has any scripts */}
           {hasAnyScript && (
             <DropdownMenu>
@@ -868,6 +895,26 @@ export function TaskFollowUpSection({
                   )}
                 </Button>
               )}
+              <Button
+                onClick={togglePause}
+                disabled={isTogglingPause || isStopping}
+                size="sm"
+                variant="outline"
+              >
+                {isTogglingPause ? (
+                  <Loader2 className="animate-spin h-4 w-4 mr-2" />
+                ) : task.has_paused_attempt ? (
+                  <>
+                    <Play className="h-4 w-4 mr-2" />
+                    {t('followUp.resume')}
+                  </>
+                ) : (
+                  <>
+                    <Pause className="h-4 w-4 mr-2" />
+                    {t('followUp.pause')}
+                  </>
+                )}
+              </Button>
               <Button
                 onClick={stopExecution}
                 disabled={isStopping}
@@ -0,0 +1,112 @@
+import { useCallback } from 'react';
+import { useQuery, useQueryClient } from '@tanstack/react-query';
+import { useTranslation } from 'react-i18next';
+import { History } from 'lucide-react';
+import { Button } from '@/components/ui/button';
+import {
+  DropdownMenu,
+  DropdownMenuContent,
+  DropdownMenuItem,
+  DropdownMenuLabel,
+  DropdownMenuSeparator,
+  DropdownMenuTrigger,
+} from '@/components/ui/dropdown-menu';
+import { ConfirmDialog } from '@/components/dialogs';
+import { attemptsApi } from '@/lib/api';
+import type { AttemptCheckpoint } from 'shared/types';
+
+type Props = {
+  workspaceId?: string;
+  disabled: boolean;
+};
+
+export function CheckpointsMenu({ workspaceId, disabled }: Props) {
+  const { t } = useTranslation('tasks');
+  const queryClient = useQueryClient();
+
+  const { data: checkpoints = [] } = useQuery({
+    queryKey: ['attemptCheckpoints', workspaceId],
+    queryFn: () => attemptsApi.getCheckpoints(workspaceId!),
+    enabled: !!workspaceId && !disabled,
+  });
+
+  const refresh = useCallback(
+    () =>
+      queryClient.invalidateQueries({
+        queryKey: ['attemptCheckpoints', workspaceId],
+      }),
+    [queryClient, workspaceId]
+  );
+
+  const handleSave = useCallback(async () => {
+    if (!workspaceId) return;
+    try {
+      await attemptsApi.createCheckpoint(workspaceId, { label: null });
+      await refresh();
+    } catch (error) {
+      console.error('Failed to save checkpoint:', error);
+    }
+  }, [workspaceId, refresh]);
+
+  const handleRollback = useCallback(
+    async (checkpoint: AttemptCheckpoint) => {
+      if (!workspaceId) return;
+      const result = await ConfirmDialog.show({
+        title: t('followUp.checkpoints.rollbackTitle'),
+        message: t('followUp.checkpoints.rollbackMessage'),
+        confirmText: t('followUp.checkpoints.rollback'),
+        variant: 'destructive',
+      });
+      if (result !== 'confirmed') return;
+      try {
+        await attemptsApi.rollbackToCheckpoint(workspaceId, checkpoint.id, {
+          force_when_dirty: true,
+        });
+        await refresh();
+      } catch (error) {
+        console.error('Failed to roll back to checkpoint:', error);
+      }
+    },
+    [workspaceId, refresh, t]
+  );
+
+  return (
+    <DropdownMenu>
+      <DropdownMenuTrigger asChild>
+        <Button
+          size="sm"
+          variant="outline"
+          disabled={disabled || !workspaceId}
+          aria-label={t('followUp.checkpoints.title')}
+          title={t('followUp.checkpoints.title')}
+        >
+          <History className="h-4 w-4" />
+        </Button>
+      </DropdownMenuTrigger>
+      <DropdownMenuContent align="end">
+        <DropdownMenuItem onClick={handleSave}>
+          {t('followUp.checkpoints.save')}
+        </DropdownMenuItem>
+        <DropdownMenuSeparator />
+        <DropdownMenuLabel>
+          {t('followUp.checkpoints.rollBackTo')}
+        </DropdownMenuLabel>
+        {checkpoints.length === 0 ? (
+          <DropdownMenuItem disabled>
+            {t('followUp.checkpoints.empty')}
+          </DropdownMenuItem>
+        ) : (
+          [...checkpoints].reverse().map((checkpoint) => (
+            <DropdownMenuItem
+              key={checkpoint.id}
+              onClick={() => handleRollback(checkpoint)}
+            >
+              {checkpoint.label ??
+                new Date(checkpoint.created_at).toLocaleString()}
+            </DropdownMenuItem>
+          ))
+        )}
+      </DropdownMenuContent>
+    </DropdownMenu>
+  );
+}
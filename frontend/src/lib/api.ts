@@ -7,6 +7,20 @@ import {
   CreateFollowUpAttempt,
   EditorType,
   CreateGitHubPrRequest,
+  ApprovalDecision,
+  AttemptCheckpoint,
+  AttemptHandoff,
+  AttemptPatches,
+  AttemptComparison,
+  AttemptComparisonDetails,
+  CreateAttemptComparisonBody,
+  AttemptPlan,
+  AttemptReviews,
+  ApprovePlanRequest,
+  RejectPlanRequest,
+  HandOffRequest,
+  CreateCheckpointRequest,
+  RollbackCheckpointRequest,
   CreateTask,
   CreateAndStartTaskRequest,
   CreateTaskAttemptBody,
@@ -48,6 +62,7 @@ import {
   CheckEditorAvailabilityResponse,
   AvailabilityInfo,
   BaseCodingAgent,
+  ExecutorProfileId,
   RunAgentSetupRequest,
   RunAgentSetupResponse,
   GhCliSetupError,
@@ -89,6 +104,9 @@ import {
   AbortConflictsRequest,
   Session,
   Workspace,
+  LocalLlm,
+  LocalLlmTestResult,
+  ProfileTestResult,
 } from 'shared/types';
 import type { WorkspaceWithSession } from '@/types/attempt';
 import { createWorkspaceWithSession } from '@/types/attempt';
@@ -547,6 +565,141 @@ export const attemptsApi = {
     return handleApiResponse<void>(response);
   },
 
+  pause: async (attemptId: string): Promise<void> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}/pause`, {
+      method: 'POST',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  resume: async (attemptId: string): Promise<void> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}/resume`, {
+      method: 'POST',
+    });
+    return handleApiResponse<void>(response);
+  },
+
+  getPlan: async (attemptId: string): Promise<AttemptPlan | null> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}/plan`);
+    return handleApiResponse<AttemptPlan | null>(response);
+  },
+
+  approvePlan: async (
+    attemptId: string,
+    data: ApprovePlanRequest
+  ): Promise<ExecutionProcess> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/plan/approve`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ExecutionProcess>(response);
+  },
+
+  rejectPlan: async (
+    attemptId: string,
+    data: RejectPlanRequest
+  ): Promise<ExecutionProcess | null> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/plan/reject`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ExecutionProcess | null>(response);
+  },
+
+  getApprovals: async (attemptId: string): Promise<ApprovalDecision[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/approvals`
+    );
+    return handleApiResponse<ApprovalDecision[]>(response);
+  },
+
+  getReviews: async (attemptId: string): Promise<AttemptReviews> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/reviews`
+    );
+    return handleApiResponse<AttemptReviews>(response);
+  },
+
+  getHandoffs: async (attemptId: string): Promise<AttemptHandoff[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/handoffs`
+    );
+    return handleApiResponse<AttemptHandoff[]>(response);
+  },
+
+  handOff: async (
+    attemptId: string,
+    data: HandOffRequest
+  ): Promise<AttemptHandoff> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/handoff`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<AttemptHandoff>(response);
+  },
+
+  getPatches: async (attemptId: string): Promise<AttemptPatches> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/patches`
+    );
+    return handleApiResponse<AttemptPatches>(response);
+  },
+
+  promotePatches: async (attemptId: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/patches/promote`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  getCheckpoints: async (attemptId: string): Promise<AttemptCheckpoint[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/checkpoints`
+    );
+    return handleApiResponse<AttemptCheckpoint[]>(response);
+  },
+
+  createCheckpoint: async (
+    attemptId: string,
+    data: CreateCheckpointRequest
+  ): Promise<AttemptCheckpoint> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/checkpoints`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<AttemptCheckpoint>(response);
+  },
+
+  rollbackToCheckpoint: async (
+    attemptId: string,
+    checkpointId: string,
+    data: RollbackCheckpointRequest
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/checkpoints/${checkpointId}/rollback`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
   runAgentSetup: async (
     attemptId: string,
     data: RunAgentSetupRequest
@@ -755,6 +908,47 @@ export const attemptsApi = {
   },
 };
 
+// Attempt Comparison APIs
+export const attemptComparisonsApi = {
+  getAll: async (taskId: string): Promise<AttemptComparison[]> => {
+    const response = await makeRequest(
+      `/api/attempt-comparisons?task_id=${taskId}`
+    );
+    return handleApiResponse<AttemptComparison[]>(response);
+  },
+
+  get: async (comparisonId: string): Promise<AttemptComparisonDetails> => {
+    const response = await makeRequest(
+      `/api/attempt-comparisons/${comparisonId}`
+    );
+    return handleApiResponse<AttemptComparisonDetails>(response);
+  },
+
+  create: async (
+    data: CreateAttemptComparisonBody
+  ): Promise<AttemptComparison> => {
+    const response = await makeRequest(`/api/attempt-comparisons`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<AttemptComparison>(response);
+  },
+
+  choose: async (
+    comparisonId: string,
+    workspaceId: string
+  ): Promise<AttemptComparison> => {
+    const response = await makeRequest(
+      `/api/attempt-comparisons/${comparisonId}/choose`,
+      {
+        method: 'POST',
+        body: JSON.stringify({ workspace_id: workspaceId }),
+      }
+    );
+    return handleApiResponse<AttemptComparison>(response);
+  },
+};
+
 // Execution Process APIs
 export const executionProcessesApi = {
   getDetails: async (processId: string): Promise<ExecutionProcess> => {
@@ -946,6 +1140,22 @@ export const profilesApi = {
     });
     return handleApiResponse<string>(response);
   },
+  testLocalLlm: async (config: LocalLlm): Promise<LocalLlmTestResult> => {
+    const response = await makeRequest('/api/profiles/test-local-llm', {
+      method: 'POST',
+      body: JSON.stringify(config),
+    });
+    return handleApiResponse<LocalLlmTestResult>(response);
+  },
+  testProfile: async (
+    profileId: ExecutorProfileId
+  ): Promise<ProfileTestResult> => {
+    const response = await makeRequest('/api/profiles/test', {
+      method: 'POST',
+      body: JSON.stringify(profileId),
+    });
+    return handleApiResponse<ProfileTestResult>(response);
+  },
 };
 
 // Images API
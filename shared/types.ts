@@ -18,6 +18,36 @@ export type CreateProject = { name: string, repositories: Array<CreateProjectRep
 
 export type UpdateProject = { name: string | null, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, };
 
+/**
+ * A column on a project's kanban board
+ */
+export type ProjectColumn = { id: string, project_id: string, name: string, 
+/**
+ * Columns are shown in ascending order
+ */
+position: bigint, 
+/**
+ * Status a task takes on when moved into the column; status changes made by the agent move
+ * the task to the first column for its new status
+ */
+status: TaskStatus, 
+/**
+ * Most tasks the column may hold; moves into a full column are rejected
+ */
+wip_limit: bigint | null, counts_as_done: boolean, task_count: bigint, created_at: string, updated_at: string, };
+
+export type CreateProjectColumn = { name: string, 
+/**
+ * Defaults to `done` for columns that count as done, otherwise `todo`
+ */
+status?: TaskStatus, wip_limit?: bigint, counts_as_done?: boolean, };
+
+export type UpdateProjectColumn = { name?: string, status?: TaskStatus, wip_limit?: bigint, 
+/**
+ * Remove the WIP limit
+ */
+clear_wip_limit?: boolean, counts_as_done?: boolean, };
+
 export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, };
 
 export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
@@ -42,287 +72,2305 @@ export type CreateTag = { tag_name: string, content: string, };
 
 export type UpdateTag = { tag_name: string | null, content: string | null, };
 
-export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
-
-export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, shared_task_id: string | null, created_at: string, updated_at: string, };
-
-export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, last_attempt_failed: boolean, executor: string, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, shared_task_id: string | null, created_at: string, updated_at: string, };
-
-export type TaskRelationships = { parent_task: Task | null, current_workspace: Workspace, children: Array<Task>, };
-
-export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, shared_task_id: string | null, };
+/**
+ * Label that can be attached to any number of tasks; names are unique ignoring case
+ */
+export type Label = { id: string, name: string, 
+/**
+ * CSS color, e.g. `#ef4444`
+ */
+color: string | null, created_at: string, updated_at: string, };
 
-export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, };
+export type CreateLabel = { name: string, color: string | null, };
 
-export type DraftFollowUpData = { message: string, variant: string | null, };
+export type UpdateLabel = { name: string | null, 
+/**
+ * An empty string clears the color
+ */
+color: string | null, };
 
-export type ScratchPayload = { "type": "DRAFT_TASK", "data": string } | { "type": "DRAFT_FOLLOW_UP", "data": DraftFollowUpData };
+export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
 
-export enum ScratchType { DRAFT_TASK = "DRAFT_TASK", DRAFT_FOLLOW_UP = "DRAFT_FOLLOW_UP" }
+/**
+ * How urgently a task should run; variants are declared most urgent first, so sorting
+ * ascending puts urgent tasks at the front
+ */
+export type TaskPriority = "urgent" | "high" | "normal" | "low";
 
-export type Scratch = { id: string, payload: ScratchPayload, created_at: string, updated_at: string, };
+export type Task = { id: string, project_id: string, number: bigint, title: string, description: string | null, status: TaskStatus, column_id: string | null, priority: TaskPriority, due_at: string | null, parent_workspace_id: string | null, shared_task_id: string | null, archived_at: string | null, created_at: string, updated_at: string, };
 
-export type CreateScratch = { payload: ScratchPayload, };
+export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, 
+/**
+ * An attempt's agent is paused; `has_in_progress_attempt` stays set meanwhile
+ */
+has_paused_attempt: boolean, last_attempt_failed: boolean, executor: string, 
+/**
+ * Checklist items on the task
+ */
+checklist_total: bigint, 
+/**
+ * Of those, items that are ticked
+ */
+checklist_done: bigint, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, column_id: string | null, priority: TaskPriority, due_at: string | null, parent_workspace_id: string | null, shared_task_id: string | null, archived_at: string | null, created_at: string, updated_at: string, };
 
-export type UpdateScratch = { payload: ScratchPayload, };
+/**
+ * `task_id` can't start until `blocker_id` is done or cancelled
+ */
+export type TaskDependency = { task_id: string, blocker_id: string, 
+/**
+ * When the dependent was last released by the blocker completing
+ */
+resolved_at: string | null, created_at: string, };
 
-export type Image = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
+/**
+ * Tasks that must finish before a task can start, and tasks waiting on it
+ */
+export type TaskDependencies = { blockers: Array<Task>, dependents: Array<Task>, };
 
-export type CreateImage = { file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, };
+/**
+ * How one task relates to another. `Duplicates` and `Supersedes` read from the task to the
+ * related task; `RelatesTo` goes both ways.
+ */
+export type TaskRelationKind = "duplicates" | "relates_to" | "supersedes";
 
-export type Workspace = { id: string, task_id: string, container_ref: string | null, branch: string, agent_working_dir: string | null, setup_completed_at: string | null, created_at: string, updated_at: string, };
+export type TaskRelation = { task_id: string, related_id: string, kind: TaskRelationKind, created_at: string, };
 
-export type Session = { id: string, workspace_id: string, executor: string | null, created_at: string, updated_at: string, };
+/**
+ * A task's relations, from its point of view
+ */
+export type TaskRelations = { 
+/**
+ * Tasks this one duplicates
+ */
+duplicates: Array<Task>, 
+/**
+ * Tasks that duplicate this one
+ */
+duplicated_by: Array<Task>, 
+/**
+ * Tasks this one replaces
+ */
+supersedes: Array<Task>, 
+/**
+ * Tasks that replace this one
+ */
+superseded_by: Array<Task>, relates_to: Array<Task>, };
 
-export type ExecutionProcess = { id: string, session_id: string, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, status: ExecutionProcessStatus, exit_code: bigint | null, 
 /**
- * dropped: true if this process is excluded from the current
- * history view (due to restore/trimming). Hidden from logs/timeline;
- * still listed in the Processes tab.
+ * A task's parent, if it is a subtask, and its own subtasks in order
  */
-dropped: boolean, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
+export type TaskSubtasks = { parent: Task | null, subtasks: Array<Task>, };
 
-export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
+/**
+ * Repository and base branch a scheduled attempt runs against
+ */
+export type ScheduleRepo = { repo_id: string, target_branch: string, };
 
-export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver";
+/**
+ * Creates a task from `title` and `description` each time `rule` fires, and starts an
+ * attempt on it when an executor profile and repos are set
+ */
+export type TaskSchedule = { id: string, project_id: string, 
+/**
+ * The title and description may contain `{{date}}`, replaced with the run's date
+ */
+title: string, description: string | null, 
+/**
+ * Cron expression, e.g. `0 9 * * MON`, or RRULE, e.g. `FREQ=WEEKLY;BYDAY=MO;BYHOUR=9`
+ */
+rule: string, 
+/**
+ * Offset the rule's times are in, e.g. `+02:00`; the server's timezone when unset
+ */
+utc_offset: string | null, executor_profile_id: ExecutorProfileId | null, repos: Array<ScheduleRepo>, enabled: boolean, 
+/**
+ * `None` once the rule will never fire again
+ */
+next_run_at: string | null, last_run_at: string | null, last_task_id: string | null, created_at: string, updated_at: string, };
 
-export type ExecutionProcessRepoState = { id: string, execution_process_id: string, repo_id: string, before_head_commit: string | null, after_head_commit: string | null, merge_commit: string | null, created_at: Date, updated_at: Date, };
+export type CreateTaskSchedule = { project_id: string, title: string, description: string | null, rule: string, utc_offset: string | null, executor_profile_id: ExecutorProfileId | null, repos: Array<ScheduleRepo>, enabled: boolean | null, };
 
-export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
+export type UpdateTaskSchedule = { title: string | null, 
+/**
+ * An empty string clears the description
+ */
+description: string | null, rule: string | null, 
+/**
+ * An empty string falls back to the server's timezone
+ */
+utc_offset: string | null, executor_profile_id: ExecutorProfileId | null, 
+/**
+ * Stop starting attempts, only creating the task
+ */
+clear_executor_profile_id: boolean | null, repos: Array<ScheduleRepo> | null, enabled: boolean | null, };
 
-export type DirectMerge = { id: string, workspace_id: string, repo_id: string, merge_commit: string, target_branch_name: string, created_at: string, };
+/**
+ * Reusable starting point for a task, available in one project or, without a project, in all
+ */
+export type TaskTemplate = { id: string, 
+/**
+ * `None` for templates shared by every project
+ */
+project_id: string | null, name: string, 
+/**
+ * Task title; `{{variable}}` placeholders are filled in when the task is created
+ */
+title_pattern: string, 
+/**
+ * Description boilerplate, with the same placeholders as the title
+ */
+description: string | null, labels: Array<string>, executor_profile_id: ExecutorProfileId | null, 
+/**
+ * Branch attempts started from the template target in each project repo
+ */
+base_branch: string | null, created_at: string, updated_at: string, };
 
-export type PrMerge = { id: string, workspace_id: string, repo_id: string, created_at: string, target_branch_name: string, pr_info: PullRequestInfo, };
+export type CreateTaskTemplate = { project_id: string | null, name: string, title_pattern: string, description: string | null, labels: Array<string>, executor_profile_id: ExecutorProfileId | null, base_branch: string | null, };
 
-export type MergeStatus = "open" | "merged" | "closed" | "unknown";
+export type UpdateTaskTemplate = { name: string | null, title_pattern: string | null, 
+/**
+ * An empty string clears the description
+ */
+description: string | null, labels: Array<string> | null, executor_profile_id: ExecutorProfileId | null, clear_executor_profile_id: boolean | null, 
+/**
+ * An empty string clears the base branch
+ */
+base_branch: string | null, };
 
-export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus, merged_at: string | null, merge_commit_sha: string | null, };
+/**
+ * Where a new task came from
+ */
+export type TaskSource = "manual" | "template" | "import" | "trello" | "github" | "jira" | "linear" | "schedule";
 
-export type ApprovalStatus = { "status": "pending" } | { "status": "approved" } | { "status": "denied", reason?: string, } | { "status": "timed_out" };
+/**
+ * Labels, executor profile and base branch for new tasks matching every condition; empty
+ * conditions match anything. Available in one project or, without a project, in all.
+ */
+export type AssignmentRule = { id: string, 
+/**
+ * `None` for rules applied in every project
+ */
+project_id: string | null, name: string, enabled: boolean, 
+/**
+ * Rules are tried in ascending order
+ */
+position: bigint, 
+/**
+ * Matches titles containing any of these words, ignoring case
+ */
+title_keywords: Array<string>, 
+/**
+ * Matches tasks created by any of these
+ */
+sources: Array<TaskSource>, 
+/**
+ * Matches descriptions mentioning a path under any of these, e.g. `docs/`
+ */
+path_prefixes: Array<string>, 
+/**
+ * Added to matching tasks, together with those of other matching rules
+ */
+labels: Array<string>, 
+/**
+ * Executor profile for attempts; the first matching rule that sets one wins
+ */
+executor_profile_id: ExecutorProfileId | null, 
+/**
+ * Branch attempts target in each project repo; the first matching rule that sets one wins
+ */
+base_branch: string | null, created_at: string, updated_at: string, };
 
-export type CreateApprovalRequest = { tool_name: string, tool_input: JsonValue, tool_call_id: string, };
+export type CreateAssignmentRule = { project_id: string | null, name: string, enabled?: boolean, position?: bigint, title_keywords: Array<string>, sources: Array<TaskSource>, path_prefixes: Array<string>, labels: Array<string>, executor_profile_id: ExecutorProfileId | null, base_branch: string | null, };
 
-export type ApprovalResponse = { execution_process_id: string, status: ApprovalStatus, };
+export type UpdateAssignmentRule = { name: string | null, enabled: boolean | null, position: bigint | null, title_keywords: Array<string> | null, sources: Array<TaskSource> | null, path_prefixes: Array<string> | null, labels: Array<string> | null, executor_profile_id: ExecutorProfileId | null, clear_executor_profile_id: boolean | null, 
+/**
+ * An empty string clears the base branch
+ */
+base_branch: string | null, };
 
-export type Diff = { change: DiffChangeKind, oldPath: string | null, newPath: string | null, oldContent: string | null, newContent: string | null, 
 /**
- * True when file contents are intentionally omitted (e.g., too large)
+ * Executor profile and base branch the assignment rules picked for a task
  */
-contentOmitted: boolean, 
+export type TaskRunDefaults = { task_id: string, executor_profile_id: ExecutorProfileId | null, base_branch: string | null, 
 /**
- * Optional precomputed stats for omitted content
+ * `None` once the rule is deleted
  */
-additions: number | null, deletions: number | null, };
+rule_id: string | null, created_at: string, };
 
-export type DiffChangeKind = "added" | "deleted" | "modified" | "renamed" | "copied" | "permissionChange";
+/**
+ * Order of a task list
+ */
+export type TaskSort = "created_at" | "priority";
 
-export type ApiResponse<T, E = T> = { success: boolean, data: T | null, error_data: E | null, message: string | null, };
+/**
+ * Conditions a task must meet to be listed; unset ones match every task
+ */
+export type ViewFilters = { 
+/**
+ * Only tasks carrying all of these labels
+ */
+labels: Array<string>, 
+/**
+ * Only tasks in any of these statuses
+ */
+statuses: Array<TaskStatus>, priority: TaskPriority | null, 
+/**
+ * Only tasks assigned to this person or agent
+ */
+assignee: string | null, 
+/**
+ * Only tasks whose latest attempt ran on this executor, e.g. `CLAUDE_CODE`
+ */
+executor: string | null, 
+/**
+ * Only tasks with this text in the title or description, ignoring case
+ */
+text: string | null, };
 
-export type LoginStatus = { "status": "loggedout" } | { "status": "loggedin", profile: ProfileResponse, };
+/**
+ * A named set of board filters, sort and columns, shared by everyone using the project's board
+ */
+export type SavedView = { id: string, project_id: string, name: string, filters: ViewFilters, sort: TaskSort | null, 
+/**
+ * Board columns shown, in order; empty shows all
+ */
+columns: Array<string>, created_at: string, updated_at: string, };
 
-export type ProfileResponse = { user_id: string, username: string | null, email: string, providers: Array<ProviderProfile>, };
+export type CreateSavedView = { name: string, filters: ViewFilters, sort: TaskSort | null, columns: Array<string>, };
 
-export type ProviderProfile = { provider: string, username: string | null, display_name: string | null, email: string | null, avatar_url: string | null, };
+export type UpdateSavedView = { name: string | null, filters: ViewFilters | null, sort: TaskSort | null, clear_sort: boolean | null, columns: Array<string> | null, };
 
-export type StatusResponse = { logged_in: boolean, profile: ProfileResponse | null, degraded: boolean | null, };
+/**
+ * What a board's swimlanes group tasks by
+ */
+export type SwimlaneKey = "label" | "assignee" | "epic";
 
-export enum MemberRole { ADMIN = "ADMIN", MEMBER = "MEMBER" }
+/**
+ * One row of the board. A task with several labels, assignees or epics shows in each of their
+ * lanes.
+ */
+export type Swimlane = { 
+/**
+ * Label or epic id, or assignee name in lowercase; `None` for the lane of tasks without one
+ */
+key: string | null, name: string, tasks: Array<TaskWithAttemptStatus>, };
 
-export enum InvitationStatus { PENDING = "PENDING", ACCEPTED = "ACCEPTED", DECLINED = "DECLINED", EXPIRED = "EXPIRED" }
+/**
+ * Comment in a task's discussion thread; the body is markdown
+ */
+export type TaskComment = { id: string, task_id: string, author: string, body: string, 
+/**
+ * Number of times the body has been edited; see the comment's history for earlier versions
+ */
+edit_count: bigint, created_at: string, updated_at: string, };
 
-export type Organization = { id: string, name: string, slug: string, is_personal: boolean, created_at: string, updated_at: string, };
+/**
+ * A comment body as it was before an edit
+ */
+export type TaskCommentEdit = { id: string, comment_id: string, body: string, edited_at: string, };
 
-export type OrganizationWithRole = { id: string, name: string, slug: string, is_personal: boolean, created_at: string, updated_at: string, user_role: MemberRole, };
+export type CreateTaskComment = { 
+/**
+ * Defaults to the configured GitHub username
+ */
+author?: string, body: string, };
 
-export type ListOrganizationsResponse = { organizations: Array<OrganizationWithRole>, };
+export type UpdateTaskComment = { body: string, };
 
-export type GetOrganizationResponse = { organization: Organization, user_role: string, };
+export type AttachmentStorage = "local" | "s3";
 
-export type CreateOrganizationRequest = { name: string, slug: string, };
+/**
+ * File attached to a task, e.g. a screenshot, log or design doc
+ */
+export type TaskAttachment = { id: string, task_id: string, original_name: string, mime_type: string | null, size_bytes: bigint, storage: AttachmentStorage, created_at: string, };
 
-export type CreateOrganizationResponse = { organization: OrganizationWithRole, };
+/**
+ * One item of a task's checklist
+ */
+export type TaskChecklistItem = { id: string, task_id: string, text: string, done: boolean, 
+/**
+ * Items are shown in ascending order
+ */
+position: bigint, 
+/**
+ * When the item was last ticked, while it is
+ */
+completed_at: string | null, created_at: string, updated_at: string, };
 
-export type UpdateOrganizationRequest = { name: string, };
+export type CreateTaskChecklistItem = { text: string, done?: boolean, };
 
-export type Invitation = { id: string, organization_id: string, invited_by_user_id: string | null, email: string, role: MemberRole, status: InvitationStatus, token: string, created_at: string, expires_at: string, };
+export type UpdateTaskChecklistItem = { text?: string, done?: boolean, };
 
-export type CreateInvitationRequest = { email: string, role: MemberRole, };
+export type ReorderTaskChecklistRequest = { 
+/**
+ * Item ids in their new order
+ */
+item_ids: Array<string>, };
 
-export type CreateInvitationResponse = { invitation: Invitation, };
+export type ReorderRunQueueRequest = { 
+/**
+ * Queued attempt ids in their new order; the order applies within each priority band
+ */
+queued_attempt_ids: Array<string>, };
 
-export type ListInvitationsResponse = { invitations: Array<Invitation>, };
+export type TaskReferenceKind = "task" | "file" | "commit";
 
-export type GetInvitationResponse = { id: string, organization_slug: string, role: MemberRole, expires_at: string, };
+/**
+ * A resolved reference in a task's description
+ */
+export type TaskReference = { id: string, task_id: string, kind: TaskReferenceKind, 
+/**
+ * As written in the description, e.g. `#12`, `src/main.rs:40` or `3f2a9c1`
+ */
+reference: string, 
+/**
+ * The task a `task` reference points at
+ */
+target_task_id: string | null, 
+/**
+ * The repo a `file` or `commit` reference was found in
+ */
+repo_id: string | null, 
+/**
+ * Full commit SHA or repo-relative file path
+ */
+resolved: string | null, 
+/**
+ * Web link for files and commits in repos hosted on GitHub
+ */
+url: string | null, created_at: string, };
 
-export type AcceptInvitationResponse = { organization_id: string, organization_slug: string, role: MemberRole, };
+/**
+ * A task whose description refers to another task
+ */
+export type TaskBacklink = { task_id: string, project_id: string, number: bigint, title: string, status: TaskStatus, 
+/**
+ * As written in the referring task, e.g. `#12`
+ */
+reference: string, };
 
-export type RevokeInvitationRequest = { invitation_id: string, };
+/**
+ * A task's outgoing references and the tasks referring to it
+ */
+export type TaskReferences = { references: Array<TaskReference>, backlinks: Array<TaskBacklink>, };
 
-export type OrganizationMember = { user_id: string, role: MemberRole, joined_at: string, };
+export type GitHubIssueState = "open" | "closed";
 
-export type OrganizationMemberWithProfile = { user_id: string, role: MemberRole, joined_at: string, first_name: string | null, last_name: string | null, username: string | null, email: string | null, avatar_url: string | null, };
+/**
+ * A project's issue sync with the GitHub repo behind one of its repos
+ */
+export type GitHubIssueSync = { project_id: string, repo_id: string, 
+/**
+ * Only issues carrying this label are imported
+ */
+label: string | null, enabled: boolean, 
+/**
+ * Issues updated since are fetched on the next poll; `None` until the first sync
+ */
+last_synced_at: string | null, 
+/**
+ * Why the last sync failed, cleared once one succeeds
+ */
+last_error: string | null, created_at: string, updated_at: string, };
 
-export type ListMembersResponse = { members: Array<OrganizationMemberWithProfile>, };
+export type UpsertGitHubIssueSync = { repo_id: string, label?: string, enabled: boolean, };
 
-export type UpdateMemberRoleRequest = { role: MemberRole, };
+/**
+ * A task mirroring a GitHub issue, with both sides as of the last sync
+ */
+export type GitHubIssueLink = { task_id: string, project_id: string, owner: string, repo: string, issue_number: bigint, issue_url: string, synced_state: GitHubIssueState, synced_status: TaskStatus, synced_labels: Array<string>, synced_at: string, created_at: string, };
 
-export type UpdateMemberRoleResponse = { user_id: string, role: MemberRole, };
+/**
+ * The category every Jira status belongs to, whatever a workflow names the status itself
+ */
+export type JiraStatusCategory = "new" | "indeterminate" | "done";
 
-export type RemoteProject = { id: string, organization_id: string, name: string, metadata: Record<string, unknown>, created_at: string, };
+/**
+ * A project's import of the Jira issues a JQL filter matches
+ */
+export type JiraSync = { project_id: string, jql: string, 
+/**
+ * Transition that completes an issue; the first one leading to a done status when unset
+ */
+done_transition: string | null, enabled: boolean, 
+/**
+ * Issues updated since are fetched on the next poll; `None` until the first sync
+ */
+last_synced_at: string | null, 
+/**
+ * Why the last sync failed, cleared once one succeeds
+ */
+last_error: string | null, created_at: string, updated_at: string, };
 
-export type ListProjectsResponse = { projects: Array<RemoteProject>, };
+export type UpsertJiraSync = { jql: string, done_transition?: string, enabled: boolean, };
 
-export type RemoteProjectMembersResponse = { organization_id: string, members: Array<OrganizationMemberWithProfile>, };
+/**
+ * A task mirroring a Jira issue, with both sides as of the last sync
+ */
+export type JiraIssueLink = { task_id: string, project_id: string, 
+/**
+ * e.g. `PROJ-123`
+ */
+issue_key: string, issue_url: string, jira_status: string, jira_category: JiraStatusCategory, synced_status: TaskStatus, synced_at: string, created_at: string, };
 
-export type CreateRemoteProjectRequest = { organization_id: string, name: string, };
+/**
+ * A project's sync with the issues of one Linear team
+ */
+export type LinearSync = { project_id: string, team_id: string, 
+/**
+ * e.g. `ENG`, the prefix of the team's issue identifiers
+ */
+team_key: string, enabled: boolean, 
+/**
+ * Issues updated since are fetched on the next import; `None` until the first one
+ */
+last_synced_at: string | null, 
+/**
+ * Why the last sync failed, cleared once one succeeds
+ */
+last_error: string | null, created_at: string, updated_at: string, };
+
+export type UpsertLinearSync = { 
+/**
+ * Key of the team to sync with, e.g. `ENG`
+ */
+team_key: string, enabled: boolean, };
+
+/**
+ * A task mirroring a Linear issue
+ */
+export type LinearIssueLink = { task_id: string, project_id: string, issue_id: string, 
+/**
+ * e.g. `ENG-123`
+ */
+identifier: string, issue_url: string, 
+/**
+ * Workflow state type as of the last sync, e.g. `started`
+ */
+synced_state: string, 
+/**
+ * Task activity up to this point has been posted to the issue
+ */
+posted_through: string, created_at: string, };
+
+/**
+ * A project's read-only iCal feed, served at a URL carrying `token`
+ */
+export type CalendarFeed = { project_id: string, token: string, created_at: string, };
+
+export type TaskActivityKind = "created" | "status_changed" | "field_changed" | "attempt_started" | "attempt_stopped" | "pr_opened" | "pr_status_changed" | "merged";
+
+/**
+ * One entry in a task's audit history. Entries are recorded by database triggers, so they cover
+ * changes made by humans through the API as well as by the agent and background services.
+ */
+export type TaskActivity = { id: string, task_id: string, kind: TaskActivityKind, 
+/**
+ * Changed field for `status_changed`, `field_changed`, `attempt_stopped` and
+ * `pr_status_changed`, e.g. `title`, `due_at` or `executor` when an attempt is handed off
+ */
+field: string | null, old_value: string | null, 
+/**
+ * Title for `created`, executor for `attempt_started`, PR URL for `pr_opened` and merge
+ * commit for `merged`; otherwise the field's new value
+ */
+new_value: string | null, workspace_id: string | null, execution_process_id: string | null, created_at: string, };
+
+/**
+ * A person who owns the task, or the coding agent that runs its attempts
+ */
+export type TaskAssigneeKind = "human" | "agent";
+
+/**
+ * Someone a task is assigned to. A task has any number of human assignees and at most one agent.
+ */
+export type TaskAssignee = { task_id: string, kind: TaskAssigneeKind, 
+/**
+ * A person's name or handle, or an executor such as `CLAUDE_CODE`; matched ignoring case
+ */
+name: string, created_at: string, };
+
+export type TaskRelationships = { parent_task: Task | null, current_workspace: Workspace, children: Array<Task>, };
+
+export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, priority?: TaskPriority, due_at?: string, parent_workspace_id: string | null, image_ids: Array<string> | null, shared_task_id: string | null, 
+/**
+ * Executor profile, and optionally model, for the task's attempts; overrides the one an
+ * assignment rule would pick
+ */
+executor_profile_id?: ExecutorProfileId, };
+
+export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, priority?: TaskPriority, due_at?: string, 
+/**
+ * Remove the due date
+ */
+clear_due_at?: boolean, parent_workspace_id: string | null, image_ids: Array<string> | null, };
+
+/**
+ * Unfinished tasks past their due date, and those due within the requested window
+ */
+export type DueTasks = { 
+/**
+ * Earliest due first
+ */
+overdue: Array<Task>, 
+/**
+ * Earliest due first
+ */
+due_soon: Array<Task>, };
+
+/**
+ * Time spent on one task attempt. Only setup, coding agent and cleanup processes count, so a
+ * dev server left running doesn't inflate it; running processes count up to now.
+ */
+export type AttemptTime = { workspace_id: string, task_id: string, 
+/**
+ * When the attempt's first process started; `None` if nothing has run yet
+ */
+started_at: string | null, 
+/**
+ * From the first process starting to the last one finishing
+ */
+wall_clock_seconds: bigint, 
+/**
+ * Sum of the processes' own run times
+ */
+active_seconds: bigint, running: boolean, };
+
+export type TimeTotals = { attempts: bigint, wall_clock_seconds: bigint, active_seconds: bigint, };
+
+/**
+ * A task's attempts, newest first, with their totals
+ */
+export type TaskTime = { task_id: string, totals: TimeTotals, attempts: Array<AttemptTime>, };
+
+export type TaskTimeTotals = { task_id: string, title: string, totals: TimeTotals, };
+
+/**
+ * Time spent across a project, and per task for tasks with at least one attempt
+ */
+export type ProjectTime = { project_id: string, totals: TimeTotals, 
+/**
+ * Most time spent first
+ */
+tasks: Array<TaskTimeTotals>, };
+
+/**
+ * Tokens and API spend of one execution process, as read from its executor's output
+ */
+export type ExecutionUsage = { execution_process_id: string, workspace_id: string, task_id: string, 
+/**
+ * Executor and variant the run started with; `None` for runs recorded before these were
+ */
+executor: string | null, variant: string | null, 
+/**
+ * The model that did most of the work, when the executor names it, otherwise the model its
+ * profile named
+ */
+model: string | null, 
+/**
+ * Input tokens besides cache reads and writes
+ */
+input_tokens: bigint, output_tokens: bigint, cache_read_tokens: bigint, cache_write_tokens: bigint, 
+/**
+ * `None` when the executor reported no cost and the model's price isn't known
+ */
+cost_usd: number | null, 
+/**
+ * Whether the cost was worked out from token counts rather than reported by the executor
+ */
+cost_estimated: boolean, created_at: string, };
+
+export type UsageTotals = { runs: bigint, input_tokens: bigint, output_tokens: bigint, cache_read_tokens: bigint, cache_write_tokens: bigint, 
+/**
+ * Reported and estimated costs together; runs without a cost add nothing
+ */
+cost_usd: number, 
+/**
+ * Runs whose cost was estimated from token counts
+ */
+estimated_runs: bigint, 
+/**
+ * Runs without a cost
+ */
+unpriced_runs: bigint, };
+
+/**
+ * A task's runs, newest first, with their totals
+ */
+export type TaskUsage = { task_id: string, totals: UsageTotals, runs: Array<ExecutionUsage>, };
+
+export type TaskUsageTotals = { task_id: string, title: string, totals: UsageTotals, };
+
+export type ModelUsageTotals = { 
+/**
+ * `None` for runs whose executor didn't name the model
+ */
+model: string | null, totals: UsageTotals, };
+
+export type ProfileUsageTotals = { 
+/**
+ * `None` for runs recorded before profiles were
+ */
+executor: string | null, variant: string | null, totals: UsageTotals, };
+
+export type DailyUsageTotals = { 
+/**
+ * UTC day, `YYYY-MM-DD`
+ */
+date: string, totals: UsageTotals, };
+
+/**
+ * Usage across the runs matching a stats query
+ */
+export type UsageStats = { totals: UsageTotals, 
+/**
+ * Most expensive first
+ */
+tasks: Array<TaskUsageTotals>, 
+/**
+ * Most expensive first
+ */
+models: Array<ModelUsageTotals>, 
+/**
+ * Most expensive first
+ */
+profiles: Array<ProfileUsageTotals>, 
+/**
+ * Oldest first, days without runs left out
+ */
+days: Array<DailyUsageTotals>, };
+
+/**
+ * An attempt waiting for a free slot under the concurrency limits. Its workspace exists, but
+ * nothing has run in it yet.
+ */
+export type QueuedAttempt = { id: string, workspace_id: string, task_id: string, project_id: string, task_title: string, 
+/**
+ * The task's current priority, which is the band the attempt waits in
+ */
+priority: TaskPriority, executor_profile_id: ExecutorProfileId, 
+/**
+ * Order within the priority band, ascending
+ */
+position: bigint, created_at: string, };
+
+/**
+ * A repo's HEAD when the checkpoint was taken
+ */
+export type CheckpointCommit = { repo_id: string, commit: string, };
+
+/**
+ * A point in an attempt the worktrees and agent session can be rolled back to
+ */
+export type AttemptCheckpoint = { id: string, workspace_id: string, session_id: string, 
+/**
+ * The coding agent process the checkpoint follows
+ */
+execution_process_id: string, 
+/**
+ * Agent session a follow-up resumes from after rolling back
+ */
+agent_session_id: string | null, 
+/**
+ * Set for checkpoints taken on request
+ */
+label: string | null, repo_commits: Array<CheckpointCommit>, created_at: string, };
+
+export enum AttemptPlanStatus { drafting = "drafting", awaiting_approval = "awaiting_approval", approved = "approved", rejected = "rejected" }
+
+/**
+ * The plan of an attempt started in plan-then-execute mode
+ */
+export type AttemptPlan = { workspace_id: string, status: AttemptPlanStatus, 
+/**
+ * The agent's plan, as edited when it was approved; absent until the first draft is done
+ */
+plan: string | null, 
+/**
+ * The planning run the current draft came from
+ */
+execution_process_id: string | null, created_at: string, updated_at: string, };
+
+/**
+ * The second executor profile reviewing an attempt's work
+ */
+export type AttemptReviewer = { workspace_id: string, executor_profile_id: ExecutorProfileId, 
+/**
+ * The reviewer's own session, once it has reviewed
+ */
+reviewer_session_id: string | null, created_at: string, };
+
+export enum ReviewVerdict { pending = "pending", approved = "approved", changes_requested = "changes_requested", failed = "failed" }
+
+/**
+ * One review of the agent's work and what came of it
+ */
+export type AttemptReviewRound = { id: string, workspace_id: string, 
+/**
+ * The agent run whose work was reviewed
+ */
+reviewed_process_id: string, reviewer_process_id: string, verdict: ReviewVerdict, 
+/**
+ * The reviewer's comments, without the verdict line
+ */
+comments: string | null, 
+/**
+ * The agent run that addressed the comments
+ */
+follow_up_process_id: string | null, created_at: string, completed_at: string | null, };
+
+/**
+ * An attempt handed from one executor profile to another
+ */
+export type AttemptHandoff = { id: string, workspace_id: string, from_session_id: string, to_session_id: string, from_executor_profile_id: ExecutorProfileId, to_executor_profile_id: ExecutorProfileId, reason: string | null, 
+/**
+ * The new executor's first run
+ */
+execution_process_id: string, created_at: string, };
+
+/**
+ * An attempt started in dry-run mode, whose changes are kept as patches rather than commits
+ */
+export type AttemptDryRun = { workspace_id: string, 
+/**
+ * When the patches were committed to the attempt's branch; from then on it works as usual
+ */
+promoted_at: string | null, created_at: string, };
+
+/**
+ * A repo's uncommitted changes in a dry-run attempt, as of its latest run
+ */
+export type AttemptPatch = { id: string, workspace_id: string, repo_id: string, 
+/**
+ * The run the patch was captured after
+ */
+execution_process_id: string, 
+/**
+ * `git diff --binary` output against the branch's head
+ */
+patch: string, files_changed: bigint, additions: bigint, deletions: bigint, created_at: string, };
+
+/**
+ * The same task run against several executor profiles side by side
+ */
+export type AttemptComparison = { id: string, task_id: string, 
+/**
+ * The variant picked over the others, if any yet
+ */
+chosen_workspace_id: string | null, chosen_at: string | null, created_at: string, };
+
+/**
+ * One of a comparison's attempts
+ */
+export type ComparisonVariant = { workspace_id: string, comparison_id: string, executor_profile_id: ExecutorProfileId, 
+/**
+ * Order the variants were requested in
+ */
+position: bigint, };
+
+export enum ApprovalDecisionStatus { pending = "pending", approved = "approved", denied = "denied", timed_out = "timed_out" }
+
+/**
+ * A tool call an agent asked the user to approve, and what was decided
+ */
+export type ApprovalDecision = { id: string, 
+/**
+ * Id the approval was requested and answered under
+ */
+approval_id: string, execution_process_id: string, tool_name: string, tool_input: JsonValue, 
+/**
+ * The command gate pattern that asked for approval, absent when the agent asked itself
+ */
+gate_pattern: string | null, status: ApprovalDecisionStatus, 
+/**
+ * Why the call was denied
+ */
+reason: string | null, requested_at: string, decided_at: string | null, };
+
+/**
+ * A monthly API spend limit for one project, or across every project
+ */
+export type CostBudget = { id: string, 
+/**
+ * `None` for the budget across every project
+ */
+project_id: string | null, 
+/**
+ * Spend in a UTC calendar month that sends a notification
+ */
+soft_limit_usd: number | null, 
+/**
+ * Spend in a UTC calendar month after which new attempts are refused
+ */
+hard_limit_usd: number | null, 
+/**
+ * Month (`YYYY-MM`) the soft limit was last reported for
+ */
+soft_notified_month: string | null, 
+/**
+ * Month (`YYYY-MM`) the hard limit was last reported for
+ */
+hard_notified_month: string | null, created_at: string, updated_at: string, };
+
+export type SetCostBudget = { 
+/**
+ * `None` for the budget across every project
+ */
+project_id: string | null, soft_limit_usd: number | null, hard_limit_usd: number | null, };
+
+/**
+ * How a month's spend compares to a budget
+ */
+export type BudgetLevel = "under" | "soft" | "hard";
+
+/**
+ * A budget with this month's spend against it
+ */
+export type BudgetStatus = { budget: CostBudget, 
+/**
+ * `None` for the budget across every project
+ */
+project_name: string | null, 
+/**
+ * `YYYY-MM`
+ */
+month: string, spent_usd: number, level: BudgetLevel, 
+/**
+ * Left before the hard limit; `None` without one
+ */
+remaining_usd: number | null, };
+
+export type SearchResultKind = "task" | "log" | "comment";
+
+export type SearchResult = { kind: SearchResultKind, task_id: string, project_id: string, task_title: string, 
+/**
+ * Archived tasks are off the board but still turn up in search
+ */
+task_archived: boolean, 
+/**
+ * Attempt and process the matching log line came from; only set for `log` results
+ */
+workspace_id: string | null, execution_process_id: string | null, 
+/**
+ * Only set for `comment` results
+ */
+comment_id: string | null, 
+/**
+ * Matching text with the matched terms wrapped in `**`
+ */
+snippet: string, 
+/**
+ * bm25 score; lower is a better match
+ */
+rank: number, };
+
+/**
+ * Delivery outcome; `suppressed` marks notifications skipped on purpose (e.g. quiet hours)
+ * and `deadletter` ones that still failed after every retry
+ */
+export type NotificationStatus = "sent" | "failed" | "suppressed" | "deadletter";
+
+/**
+ * A notification delivered (or attempted) through a single channel
+ */
+export type NotificationRecord = { id: string, channel: string, event: string, project_id: string | null, task_id: string | null, title: string, message: string, task_url: string | null, status: NotificationStatus, error: string | null, created_at: string, };
+
+/**
+ * A change to one config key
+ */
+export type ConfigAuditEntry = { id: string, 
+/**
+ * What made the change, e.g. `PUT /api/config` or `config file`
+ */
+source: string, 
+/**
+ * Dotted path to the key, e.g. `notifications.webhook_urls`
+ */
+path: string, 
+/**
+ * JSON value before the change, absent when the key was added
+ */
+old_value: string | null, 
+/**
+ * JSON value after the change, absent when the key was removed
+ */
+new_value: string | null, created_at: string, };
+
+/**
+ * A failure notification that is re-sent on other channels unless acknowledged by `escalate_at`
+ */
+export type NotificationEscalation = { id: string, event: string, project_id: string | null, task_id: string | null, workspace_id: string | null, title: string, message: string, task_url: string | null, escalate_at: string, acknowledged_at: string | null, escalated_at: string | null, created_at: string, };
+
+export type DraftFollowUpData = { message: string, variant: string | null, };
+
+export type ScratchPayload = { "type": "DRAFT_TASK", "data": string } | { "type": "DRAFT_FOLLOW_UP", "data": DraftFollowUpData };
+
+export enum ScratchType { DRAFT_TASK = "DRAFT_TASK", DRAFT_FOLLOW_UP = "DRAFT_FOLLOW_UP" }
+
+export type Scratch = { id: string, payload: ScratchPayload, created_at: string, updated_at: string, };
+
+export type CreateScratch = { payload: ScratchPayload, };
+
+export type UpdateScratch = { payload: ScratchPayload, };
+
+export type Image = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
+
+export type CreateImage = { file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, };
+
+export type Workspace = { id: string, task_id: string, container_ref: string | null, branch: string, agent_working_dir: string | null, setup_completed_at: string | null, created_at: string, updated_at: string, };
+
+export type Session = { id: string, workspace_id: string, executor: string | null, created_at: string, updated_at: string, };
+
+export type ExecutionProcess = { id: string, session_id: string, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, status: ExecutionProcessStatus, exit_code: bigint | null, 
+/**
+ * dropped: true if this process is excluded from the current
+ * history view (due to restore/trimming). Hidden from logs/timeline;
+ * still listed in the Processes tab.
+ */
+dropped: boolean, started_at: string, completed_at: string | null, created_at: string, updated_at: string, };
+
+export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
+
+export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver";
+
+export type ExecutionProcessRepoState = { id: string, execution_process_id: string, repo_id: string, before_head_commit: string | null, after_head_commit: string | null, merge_commit: string | null, created_at: Date, updated_at: Date, };
+
+export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
+
+export type DirectMerge = { id: string, workspace_id: string, repo_id: string, merge_commit: string, target_branch_name: string, created_at: string, };
+
+export type PrMerge = { id: string, workspace_id: string, repo_id: string, created_at: string, target_branch_name: string, pr_info: PullRequestInfo, };
+
+export type MergeStatus = "open" | "merged" | "closed" | "unknown";
+
+export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus, merged_at: string | null, merge_commit_sha: string | null, };
+
+export type ApprovalStatus = { "status": "pending" } | { "status": "approved" } | { "status": "denied", reason?: string, } | { "status": "timed_out" };
+
+export type CreateApprovalRequest = { tool_name: string, tool_input: JsonValue, tool_call_id: string, };
+
+export type ApprovalResponse = { execution_process_id: string, status: ApprovalStatus, };
+
+export type Diff = { change: DiffChangeKind, oldPath: string | null, newPath: string | null, oldContent: string | null, newContent: string | null, 
+/**
+ * True when file contents are intentionally omitted (e.g., too large)
+ */
+contentOmitted: boolean, 
+/**
+ * Optional precomputed stats for omitted content
+ */
+additions: number | null, deletions: number | null, };
+
+export type DiffChangeKind = "added" | "deleted" | "modified" | "renamed" | "copied" | "permissionChange";
+
+export type ApiResponse<T, E = T> = { success: boolean, data: T | null, error_data: E | null, message: string | null, };
+
+export type LoginStatus = { "status": "loggedout" } | { "status": "loggedin", profile: ProfileResponse, };
+
+export type ProfileResponse = { user_id: string, username: string | null, email: string, providers: Array<ProviderProfile>, };
+
+export type ProviderProfile = { provider: string, username: string | null, display_name: string | null, email: string | null, avatar_url: string | null, };
+
+export type StatusResponse = { logged_in: boolean, profile: ProfileResponse | null, degraded: boolean | null, };
+
+export enum MemberRole { ADMIN = "ADMIN", MEMBER = "MEMBER" }
+
+export enum InvitationStatus { PENDING = "PENDING", ACCEPTED = "ACCEPTED", DECLINED = "DECLINED", EXPIRED = "EXPIRED" }
+
+export type Organization = { id: string, name: string, slug: string, is_personal: boolean, created_at: string, updated_at: string, };
+
+export type OrganizationWithRole = { id: string, name: string, slug: string, is_personal: boolean, created_at: string, updated_at: string, user_role: MemberRole, };
+
+export type ListOrganizationsResponse = { organizations: Array<OrganizationWithRole>, };
+
+export type GetOrganizationResponse = { organization: Organization, user_role: string, };
+
+export type CreateOrganizationRequest = { name: string, slug: string, };
+
+export type CreateOrganizationResponse = { organization: OrganizationWithRole, };
+
+export type UpdateOrganizationRequest = { name: string, };
+
+export type Invitation = { id: string, organization_id: string, invited_by_user_id: string | null, email: string, role: MemberRole, status: InvitationStatus, token: string, created_at: string, expires_at: string, };
+
+export type CreateInvitationRequest = { email: string, role: MemberRole, };
+
+export type CreateInvitationResponse = { invitation: Invitation, };
+
+export type ListInvitationsResponse = { invitations: Array<Invitation>, };
+
+export type GetInvitationResponse = { id: string, organization_slug: string, role: MemberRole, expires_at: string, };
+
+export type AcceptInvitationResponse = { organization_id: string, organization_slug: string, role: MemberRole, };
+
+export type RevokeInvitationRequest = { invitation_id: string, };
+
+export type OrganizationMember = { user_id: string, role: MemberRole, joined_at: string, };
+
+export type OrganizationMemberWithProfile = { user_id: string, role: MemberRole, joined_at: string, first_name: string | null, last_name: string | null, username: string | null, email: string | null, avatar_url: string | null, };
+
+export type ListMembersResponse = { members: Array<OrganizationMemberWithProfile>, };
+
+export type UpdateMemberRoleRequest = { role: MemberRole, };
+
+export type UpdateMemberRoleResponse = { user_id: string, role: MemberRole, };
+
+export type RemoteProject = { id: string, organization_id: string, name: string, metadata: Record<string, unknown>, created_at: string, };
+
+export type ListProjectsResponse = { projects: Array<RemoteProject>, };
+
+export type RemoteProjectMembersResponse = { organization_id: string, members: Array<OrganizationMemberWithProfile>, };
+
+export type CreateRemoteProjectRequest = { organization_id: string, name: string, };
+
+export type LinkToExistingRequest = { remote_project_id: string, };
+
+export type ProjectConfigResponse = { settings: ProjectSettings, 
+/**
+ * Global config with the project's overrides applied
+ */
+effective: Config, };
+
+export type RegisterRepoRequest = { path: string, display_name: string | null, };
+
+export type InitRepoRequest = { parent_path: string, folder_name: string, };
+
+export type TagSearchParams = { search: string | null, };
+
+export type NotificationHistoryQuery = { limit: bigint | null, offset: bigint | null, };
+
+export type NotificationHistoryPage = { notifications: Array<NotificationRecord>, total: bigint, };
+
+/**
+ * Delivery health of one channel, from its notification history
+ */
+export type NotificationChannelHealth = { channel: NotificationChannel, enabled: boolean, 
+/**
+ * False when the channel's most recent delivery failed
+ */
+healthy: boolean, 
+/**
+ * Status of the most recent delivery that wasn't suppressed
+ */
+last_status: NotificationStatus | null, last_attempt_at: string | null, last_success_at: string | null, last_error: string | null, last_error_at: string | null, };
+
+/**
+ * Outcome of sending a test notification through one channel
+ */
+export type ChannelTestResult = { channel: NotificationChannel, success: boolean, latency_ms: bigint, error: string | null, };
+
+/**
+ * Keys from the browser's `PushSubscription.toJSON()`
+ */
+export type PushSubscriptionKeys = { p256dh: string, auth: string, };
+
+export type PushSubscribeRequest = { endpoint: string, keys: PushSubscriptionKeys, };
+
+export type PushUnsubscribeRequest = { endpoint: string, };
+
+export type TokenResponse = { access_token: string, expires_at: string | null, };
+
+export type UserSystemInfo = { config: Config, analytics_user_id: string, login_status: LoginStatus, environment: Environment, 
+/**
+ * Capabilities supported per executor (e.g., { "CLAUDE_CODE": ["SESSION_FORK"] })
+ */
+capabilities: { [key in string]?: Array<BaseAgentCapability> }, executors: { [key in BaseCodingAgent]?: ExecutorConfig }, };
+
+export type Environment = { os_type: string, os_version: string, os_architecture: string, bitness: string, };
+
+export type McpServerQuery = { executor: BaseCodingAgent, };
+
+export type UpdateMcpServersBody = { servers: { [key in string]?: JsonValue }, };
+
+export type GetMcpServerResponse = { mcp_config: McpConfig, config_path: string, };
+
+export type CheckEditorAvailabilityQuery = { editor_type: EditorType, };
+
+export type CheckEditorAvailabilityResponse = { available: boolean, };
+
+export type CheckAgentAvailabilityQuery = { executor: BaseCodingAgent, };
+
+/**
+ * Sound to play on the server; omitted fields use the configured sound
+ */
+export type PreviewSoundRequest = { sound_file: SoundFile | null, custom_sound: string | null, 
+/**
+ * Theme to preview instead of the configured one
+ */
+sound_theme: SoundTheme | null, 
+/**
+ * Preview the sound this event plays, when no sound is given explicitly
+ */
+event: NotificationEvent | null, };
+
+export type ExportConfigQuery = { 
+/**
+ * Include credentials instead of redacting them
+ */
+include_secrets: boolean, };
+
+export type ImportConfigRequest = { 
+/**
+ * A config from `GET /config/export`; redacted credentials keep their current values
+ */
+config: JsonValue, 
+/**
+ * Report what would change without applying it
+ */
+dry_run: boolean, };
+
+export type ImportConfigResponse = { changes: Array<ConfigChange>, applied: boolean, };
+
+export type ConfigAuditQuery = { 
+/**
+ * Only changes to this key or keys nested under it, e.g. `notifications`
+ */
+path: string | null, limit: bigint | null, offset: bigint | null, };
+
+export type SearchQuery = { q: string, project_id: string | null, limit: number | null, };
+
+export type CurrentUserResponse = { user_id: string, };
+
+export type CreateFollowUpAttempt = { prompt: string, variant: string | null, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, };
+
+export type ChangeTargetBranchRequest = { repo_id: string, new_target_branch: string, };
+
+export type ChangeTargetBranchResponse = { repo_id: string, new_target_branch: string, status: [number, number], };
+
+export type MergeTaskAttemptRequest = { repo_id: string, };
+
+export type PushTaskAttemptRequest = { repo_id: string, };
+
+export type RenameBranchRequest = { new_branch_name: string, };
+
+export type RenameBranchResponse = { branch: string, };
+
+export type OpenEditorRequest = { editor_type: string | null, file_path: string | null, };
+
+export type OpenEditorResponse = { url: string | null, };
+
+export type AssignSharedTaskRequest = { new_assignee_user_id: string | null, };
+
+export type ShareTaskResponse = { shared_task_id: string, };
+
+export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, 
+/**
+ * Start even though a column or executor is at its WIP limit
+ */
+ignore_wip_limits?: boolean, };
+
+export type AddTaskDependencyRequest = { 
+/**
+ * Task that must be done before this one can start
+ */
+blocker_id: string, };
+
+export type AddTaskRelationRequest = { related_id: string, 
+/**
+ * How this task relates to the other, e.g. `duplicates` when this task is the duplicate
+ */
+kind: TaskRelationKind, 
+/**
+ * Cancel the duplicate, or the task being superseded, once linked
+ */
+close?: boolean, };
+
+export type CreateSubtaskRequest = { title: string, description: string | null, };
+
+export type ReorderSubtasksRequest = { 
+/**
+ * Subtask ids in their new order
+ */
+task_ids: Array<string>, };
+
+export type MoveTaskToColumnRequest = { column_id: string, 
+/**
+ * Move even though the column is at its WIP limit
+ */
+ignore_wip_limit?: boolean, };
+
+export type ReorderProjectColumnsRequest = { 
+/**
+ * Column ids in their new board order
+ */
+column_ids: Array<string>, };
+
+export type WipScope = "column" | "executor";
+
+/**
+ * A WIP limit that is exceeded, or would be by the change being made
+ */
+export type WipViolation = { scope: WipScope, 
+/**
+ * Column name or executor
+ */
+name: string, limit: bigint, 
+/**
+ * Tasks in the column or attempts running, counting the one being added
+ */
+count: bigint, };
+
+/**
+ * The queue with the running attempts and the limit they count against
+ */
+export type RunQueueStatus = { 
+/**
+ * Attempts running now, in the project when the status is for one
+ */
+running: bigint, 
+/**
+ * Limit `running` counts against; `None` when unlimited
+ */
+max_concurrent_attempts: number | null, 
+/**
+ * In the order they start
+ */
+queued: Array<QueuedAttempt>, };
+
+/**
+ * A sprint or release: a date range and the project tasks planned for it. A task belongs to at
+ * most one milestone.
+ */
+export type Milestone = { id: string, project_id: string, name: string, description: string | null, start_date: string, 
+/**
+ * Last day of the milestone, inclusive
+ */
+end_date: string, task_count: bigint, 
+/**
+ * Tasks that are done or cancelled
+ */
+completed_count: bigint, created_at: string, updated_at: string, };
+
+export type CreateMilestone = { name: string, description: string | null, start_date: string, end_date: string, };
+
+export type UpdateMilestone = { name?: string, 
+/**
+ * An empty string clears the description
+ */
+description?: string, start_date?: string, end_date?: string, };
+
+/**
+ * One day of a burndown chart, counted at the end of the day (UTC)
+ */
+export type BurndownPoint = { date: string, 
+/**
+ * Tasks in the milestone by the end of the day
+ */
+scope: bigint, 
+/**
+ * Of those, tasks not yet done or cancelled
+ */
+remaining: bigint, 
+/**
+ * Where `remaining` would be if the current scope burned down evenly to zero by the end date
+ */
+ideal: number, };
+
+export type MilestoneStats = { milestone_id: string, total: bigint, todo: bigint, in_progress: bigint, in_review: bigint, done: bigint, cancelled: bigint, 
+/**
+ * Share of tasks done or cancelled, from 0 to 100
+ */
+percent_complete: number, 
+/**
+ * From the start date through today or the end date, whichever comes first
+ */
+burndown: Array<BurndownPoint>, };
+
+export type AddMilestoneTasksRequest = { 
+/**
+ * Tasks in other milestones are moved to this one
+ */
+task_ids: Array<string>, };
+
+/**
+ * A group of related tasks, which may come from different projects
+ */
+export type Epic = { id: string, name: string, description: string | null, task_count: bigint, 
+/**
+ * Tasks that are done or cancelled
+ */
+completed_count: bigint, 
+/**
+ * Projects the epic's tasks belong to
+ */
+project_count: bigint, 
+/**
+ * When every task in the epic was last found done or cancelled; cleared when one reopens
+ */
+completed_at: string | null, created_at: string, updated_at: string, };
+
+export type CreateEpic = { name: string, description: string | null, 
+/**
+ * Tasks to start the epic with
+ */
+task_ids?: Array<string>, };
+
+export type UpdateEpic = { name?: string, 
+/**
+ * An empty string clears the description
+ */
+description?: string, };
+
+/**
+ * A task's part in an epic, with the time its attempts took
+ */
+export type EpicTask = { task_id: string, project_id: string, project_name: string, title: string, status: TaskStatus, time: TimeTotals, };
+
+/**
+ * Aggregate status and cost of an epic's tasks
+ */
+export type EpicSummary = { epic: Epic, 
+/**
+ * Status of the tasks taken together, see `aggregate_status`
+ */
+status: TaskStatus, todo: bigint, in_progress: bigint, in_review: bigint, done: bigint, cancelled: bigint, 
+/**
+ * Share of tasks done or cancelled, from 0 to 100
+ */
+percent_complete: number, 
+/**
+ * Attempts and agent time across every task
+ */
+time: TimeTotals, 
+/**
+ * Grouped by project name, oldest task first within each project
+ */
+tasks: Array<EpicTask>, };
+
+export type AddEpicTasksRequest = { 
+/**
+ * Tasks from any project; ones already in the epic are skipped
+ */
+task_ids: Array<string>, };
+
+export type SetParentTaskRequest = { 
+/**
+ * Task to nest this one under, or `null` to make it a top-level task again
+ */
+parent_task_id: string | null, };
+
+export type CreateChecklistSubtasksRequest = { 
+/**
+ * Start an attempt on each new subtask with this profile; requires `repos`
+ */
+executor_profile_id: ExecutorProfileId | null, repos: Array<WorkspaceRepoInput> | null, };
+
+export type CreateTaskFromTemplateRequest = { template_id: string, project_id: string, 
+/**
+ * Values for the template's `{{variable}}` placeholders
+ */
+variables: { [key in string]?: string }, 
+/**
+ * Start an attempt once the task is created
+ */
+start: boolean, 
+/**
+ * Overrides the template's executor profile
+ */
+executor_profile_id: ExecutorProfileId | null, 
+/**
+ * Overrides the template's base branch with explicit repos
+ */
+repos: Array<WorkspaceRepoInput> | null, 
+/**
+ * Start even though a column or executor is at its WIP limit
+ */
+ignore_wip_limits?: boolean, };
+
+export type CloneTaskRequest = { 
+/**
+ * Defaults to the original's title
+ */
+title: string | null, 
+/**
+ * Start an attempt on the clone once it's created
+ */
+start: boolean, 
+/**
+ * Overrides the executor profile of the original's latest attempt
+ */
+executor_profile_id: ExecutorProfileId | null, 
+/**
+ * Base the attempt on the branch of the original's latest attempt instead of that attempt's
+ * base branch, to build on its work
+ */
+from_branch: boolean, 
+/**
+ * Overrides the repos and branches of the original's latest attempt
+ */
+repos: Array<WorkspaceRepoInput> | null, 
+/**
+ * Start even though a column or executor is at its WIP limit
+ */
+ignore_wip_limits?: boolean, };
+
+/**
+ * CSV has a header row; JSON is an array of objects
+ */
+export type ImportFormat = "csv" | "json";
+
+/**
+ * Source column (CSV header or JSON key) of each task field. Fields left out are read from a
+ * column named like the field, compared case-insensitively, if there is one.
+ */
+export type ImportMapping = { title?: string, description?: string, status?: string, priority?: string, due_at?: string, 
+/**
+ * Labels are split on commas and semicolons
+ */
+labels?: string, };
 
-export type LinkToExistingRequest = { remote_project_id: string, };
+/**
+ * A row's task once mapped and validated
+ */
+export type ImportedTask = { title: string, description: string | null, status: TaskStatus, priority: TaskPriority, due_at: string | null, labels: Array<string>, };
 
-export type RegisterRepoRequest = { path: string, display_name: string | null, };
+/**
+ * One data row of an import, as previewed
+ */
+export type ImportRow = { 
+/**
+ * 1-based position among the data rows
+ */
+row: number, 
+/**
+ * `None` when the row has errors
+ */
+task: ImportedTask | null, errors: Array<string>, 
+/**
+ * Task in the project with the same title
+ */
+duplicate_of_task_id: string | null, 
+/**
+ * Earlier row of the import with the same title
+ */
+duplicate_of_row: number | null, };
 
-export type InitRepoRequest = { parent_path: string, folder_name: string, };
+export type ImportTasksRequest = { format: ImportFormat, 
+/**
+ * The CSV or JSON text
+ */
+content: string, mapping?: ImportMapping, 
+/**
+ * Only validate and return the preview; nothing is created
+ */
+dry_run: boolean, 
+/**
+ * Also create rows whose title matches an existing task or an earlier row
+ */
+include_duplicates: boolean, };
 
-export type TagSearchParams = { search: string | null, };
+export type ImportTasksResponse = { rows: Array<ImportRow>, 
+/**
+ * Tasks created, in row order; empty for a dry run
+ */
+created: Array<Task>, };
 
-export type TokenResponse = { access_token: string, expires_at: string | null, };
+export type TrelloImportSource = { "type": "export", content: string, } | { "type": "api", 
+/**
+ * Board URL or id
+ */
+board: string, api_key: string, token: string, };
 
-export type UserSystemInfo = { config: Config, analytics_user_id: string, login_status: LoginStatus, environment: Environment, 
+export type TrelloImportRequest = { source: TrelloImportSource, 
 /**
- * Capabilities supported per executor (e.g., { "CLAUDE_CODE": ["SESSION_FORK"] })
+ * Also import archived lists and cards
  */
-capabilities: { [key in string]?: Array<BaseAgentCapability> }, executors: { [key in BaseCodingAgent]?: ExecutorConfig }, };
+include_archived: boolean, };
 
-export type Environment = { os_type: string, os_version: string, os_architecture: string, bitness: string, };
+/**
+ * What the import created
+ */
+export type TrelloImportResult = { board_name: string, columns_created: number, tasks_created: number, checklist_items_created: number, 
+/**
+ * Archived lists and cards left out
+ */
+archived_skipped: number, };
 
-export type McpServerQuery = { executor: BaseCodingAgent, };
+/**
+ * Outcome of importing a bundle
+ */
+export type ProjectImportResult = { project: Project, 
+/**
+ * Tasks created
+ */
+tasks: number, 
+/**
+ * Paths of bundled repos that don't exist on this machine and were left out
+ */
+skipped_repos: Array<string>, 
+/**
+ * Whether the bundled project settings were valid here and applied
+ */
+settings_applied: boolean, };
 
-export type UpdateMcpServersBody = { servers: { [key in string]?: JsonValue }, };
+/**
+ * What one sync changed
+ */
+export type GitHubIssueSyncResult = { tasks_created: number, tasks_updated: number, issues_updated: number, comments_imported: number, comments_posted: number, };
 
-export type GetMcpServerResponse = { mcp_config: McpConfig, config_path: string, };
+/**
+ * What one sync changed
+ */
+export type JiraSyncResult = { tasks_created: number, tasks_updated: number, issues_transitioned: number, };
+
+export type LinearTeam = { id: string, key: string, name: string, };
+
+/**
+ * What one sync changed
+ */
+export type LinearSyncResult = { tasks_created: number, tasks_updated: number, comments_posted: number, };
+
+export type CalendarFeedResponse = { feed: CalendarFeed, 
+/**
+ * Path of the `.ics` feed on this server
+ */
+path: string, 
+/**
+ * Full feed URL, when the server's public base URL is configured
+ */
+url: string | null, };
+
+export type SetTaskLabelsRequest = { 
+/**
+ * Replaces the task's labels
+ */
+label_ids: Array<string>, };
+
+export type SetTaskAssigneesRequest = { 
+/**
+ * Names or handles of the people who own the task
+ */
+humans: Array<string>, 
+/**
+ * Executor that runs the task's attempts, e.g. `CLAUDE_CODE`
+ */
+agent: string | null, };
+
+export type TaskActivityQuery = { limit: bigint | null, offset: bigint | null, };
+
+export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
+
+export type CreateCheckpointRequest = { label: string | null, };
+
+export type RollbackCheckpointRequest = { 
+/**
+ * Discard uncommitted changes in the worktrees
+ */
+force_when_dirty: boolean | null, };
+
+export type ApprovePlanRequest = { 
+/**
+ * The plan as edited by the reviewer; the agent's draft is used when absent
+ */
+plan: string | null, };
+
+export type RejectPlanRequest = { 
+/**
+ * What to change; with feedback the agent revises the plan, without it the plan is dropped
+ */
+feedback: string | null, };
+
+export type HandOffRequest = { 
+/**
+ * The executor profile that continues the attempt
+ */
+executor_profile_id: ExecutorProfileId, 
+/**
+ * Why the attempt is handed off, passed on to the new executor
+ */
+reason: string | null, };
+
+export type AttemptPatches = { 
+/**
+ * Absent unless the attempt was started as a dry run
+ */
+dry_run: AttemptDryRun | null, patches: Array<AttemptPatch>, };
+
+export type AttemptComparisonQuery = { task_id: string, };
+
+export type CreateAttemptComparisonBody = { task_id: string, 
+/**
+ * Two or more different profiles, each run in an attempt and branch of its own
+ */
+executor_profile_ids: Array<ExecutorProfileId>, repos: Array<WorkspaceRepoInput>, 
+/**
+ * Start even though tasks blocking this one aren't done yet
+ */
+ignore_blockers?: boolean, 
+/**
+ * Start even though a column or executor is at its WIP limit
+ */
+ignore_wip_limits?: boolean, };
+
+export type ChooseComparisonVariantRequest = { workspace_id: string, };
+
+export type ComparisonRepoDiff = { repo_name: string, diffs: Array<Diff>, };
+
+/**
+ * One variant's changes, time and cost, for showing side by side with the others
+ */
+export type ComparisonVariantDetails = { workspace: Workspace, executor_profile_id: ExecutorProfileId, 
+/**
+ * `None` before anything has run
+ */
+time: AttemptTime | null, usage: UsageTotals, files_changed: bigint, additions: bigint, deletions: bigint, 
+/**
+ * Changes against the target branch, uncommitted ones included
+ */
+repos: Array<ComparisonRepoDiff>, 
+/**
+ * Took the least time among the finished variants
+ */
+fastest: boolean, 
+/**
+ * Cost the least among the finished variants whose runs all have a cost
+ */
+cheapest: boolean, chosen: boolean, };
+
+export type AttemptComparisonDetails = { comparison: AttemptComparison, 
+/**
+ * In the order they were requested
+ */
+variants: Array<ComparisonVariantDetails>, };
+
+export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
+
+export type ImageMetadata = { exists: boolean, file_name: string | null, path: string | null, size_bytes: bigint | null, format: string | null, proxy_url: string | null, };
+
+export type CreateTaskAttemptBody = { task_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, 
+/**
+ * Start even though tasks blocking this one aren't done yet
+ */
+ignore_blockers?: boolean, 
+/**
+ * Start even though a column or executor is at its WIP limit
+ */
+ignore_wip_limits?: boolean, 
+/**
+ * Have the agent write a plan first, and wait for it to be approved before carrying it out
+ */
+plan_first?: boolean, 
+/**
+ * Have this executor profile review the agent's work once it finishes, feeding requested
+ * changes back to the agent
+ */
+reviewer_profile_id?: ExecutorProfileId, 
+/**
+ * Keep the agent's changes as reviewable patches instead of committing or pushing them
+ */
+dry_run?: boolean, };
+
+export type WorkspaceRepoInput = { repo_id: string, target_branch: string, };
+
+export type RunAgentSetupRequest = { executor_profile_id: ExecutorProfileId, };
+
+export type RunAgentSetupResponse = Record<string, never>;
+
+export type GhCliSetupError = "BREW_MISSING" | "SETUP_HELPER_NOT_SUPPORTED" | { "OTHER": { message: string, } };
+
+export type RebaseTaskAttemptRequest = { repo_id: string, old_base_branch: string | null, new_base_branch: string | null, };
+
+export type AbortConflictsRequest = { repo_id: string, };
+
+export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, } | { "type": "rebase_in_progress" };
+
+export type PushError = { "type": "force_push_required" };
+
+export type CreatePrError = { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, };
+
+export type BranchStatus = { commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
+/**
+ * True if a `git rebase` is currently in progress in this worktree
+ */
+is_rebase_in_progress: boolean, 
+/**
+ * Current conflict operation if any
+ */
+conflict_op: ConflictOp | null, 
+/**
+ * List of files currently in conflicted (unmerged) state
+ */
+conflicted_files: Array<string>, };
+
+export type RunScriptError = { "type": "no_script_configured" } | { "type": "process_already_running" };
+
+export type AttachPrResponse = { pr_attached: boolean, pr_url: string | null, pr_number: bigint | null, pr_status: MergeStatus | null, };
+
+export type AttachExistingPrRequest = { repo_id: string, };
+
+export type PrCommentsResponse = { comments: Array<UnifiedPrComment>, };
+
+export type GetPrCommentsError = { "type": "no_pr_attached" } | { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" };
+
+export type GetPrCommentsQuery = { repo_id: string, };
+
+export type UnifiedPrComment = { "comment_type": "general", id: string, author: string, author_association: string, body: string, created_at: string, url: string, } | { "comment_type": "review", id: bigint, author: string, author_association: string, body: string, created_at: string, url: string, path: string, line: bigint | null, diff_hunk: string, };
+
+export type RepoBranchStatus = { repo_id: string, repo_name: string, commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
+/**
+ * True if a `git rebase` is currently in progress in this worktree
+ */
+is_rebase_in_progress: boolean, 
+/**
+ * Current conflict operation if any
+ */
+conflict_op: ConflictOp | null, 
+/**
+ * List of files currently in conflicted (unmerged) state
+ */
+conflicted_files: Array<string>, };
+
+export type AttemptReviews = { 
+/**
+ * Absent when the attempt has no reviewer stage
+ */
+reviewer: AttemptReviewer | null, rounds: Array<AttemptReviewRound>, };
+
+export type DirectoryEntry = { name: string, path: string, is_directory: boolean, is_git_repo: boolean, last_modified: bigint | null, };
+
+export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
+
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, server: ServerConfig, config_profiles: { [key in string]?: ConfigProfile }, active_config_profile: string | null, project_overrides: { [key in string]?: ProjectConfigOverrides }, on_task_unblocked: TaskUnblockedAction, attachments: AttachmentConfig, 
+/**
+ * Most coding agents of each executor, e.g. `CLAUDE_CODE`, that may run at once; executors
+ * not listed are unlimited
+ */
+executor_wip_limits: { [key in string]?: number }, 
+/**
+ * Most attempts that may run at once across all projects; further attempts wait in the run
+ * queue. Unlimited when unset
+ */
+max_concurrent_attempts: number | null, 
+/**
+ * Most attempts that may run at once in each project, unless the project sets its own
+ */
+max_concurrent_attempts_per_project: number | null, 
+/**
+ * Archive tasks that have been done or cancelled, untouched, for this many days; off when
+ * unset
+ */
+auto_archive_after_days: number | null, 
+/**
+ * Credentials for projects that sync with Jira; the JQL filters are set per project
+ */
+jira: JiraConfig | null, 
+/**
+ * Credentials for projects that sync with a Linear team
+ */
+linear: LinearConfig | null, attempt_retry: AttemptRetryPolicy, 
+/**
+ * Run limits per executor profile, keyed like `CLAUDE_CODE` or `CLAUDE_CODE:PLAN`; a
+ * variant's entry takes precedence over its executor's
+ */
+execution_limits: { [key in string]?: ExecutionLimits }, sandbox: SandboxConfig, process_limits: ProcessLimits, approval_gates: ApprovalGates, 
+/**
+ * Most times an attempt's reviewer reviews the agent's work before the task goes to review
+ * anyway
+ */
+max_review_rounds: number, };
+
+export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, 
+/**
+ * User-provided sound played instead of `sound_file`: an uploaded sound's name or an
+ * absolute path
+ */
+custom_sound: string | null, sound_playback: SoundPlayback, 
+/**
+ * Pack that picks different sounds for successes, failures and events needing attention
+ */
+sound_theme: SoundTheme, 
+/**
+ * Per-event sounds that take precedence over the theme
+ */
+event_sounds: { [key in NotificationEvent]?: EventSound }, slack_enabled: boolean, slack_webhook_url: string | null, 
+/**
+ * Bot token for `chat.postMessage`; when set with `slack_channel` it replaces the webhook
+ * and follow-ups for the same task attempt are posted as thread replies
+ */
+slack_bot_token: string | null, 
+/**
+ * Channel ID the bot posts to
+ */
+slack_channel: string | null, 
+/**
+ * Member ID, or a handle from `slack_member_ids`, mentioned on failure notifications
+ */
+slack_on_call: string | null, 
+/**
+ * Handles mapped to Slack member IDs such as `U0123ABCD`
+ */
+slack_member_ids: { [key in string]?: string }, webhook_enabled: boolean, webhook_urls: Array<string>, webhook_secret: string | null, ntfy_enabled: boolean, ntfy_topic_url: string | null, ntfy_token: string | null, ntfy_priority: number | null, pushover_enabled: boolean, pushover_app_token: string | null, pushover_user_key: string | null, pushover_priority: number | null, 
+/**
+ * Deliver to browsers subscribed through the frontend, even when the tab is closed
+ */
+web_push_enabled: boolean, gotify_enabled: boolean, gotify_server_url: string | null, gotify_app_token: string | null, matrix_enabled: boolean, matrix_homeserver_url: string | null, matrix_access_token: string | null, matrix_room_id: string | null, mattermost_enabled: boolean, mattermost_webhook_url: string | null, 
+/**
+ * Channel name to post in instead of the webhook's default; needs channel overrides
+ * enabled on the webhook
+ */
+mattermost_channel: string | null, mattermost_icon_url: string | null, rocketchat_enabled: boolean, rocketchat_webhook_url: string | null, 
+/**
+ * `#channel` or `@user` to post to instead of the integration's default
+ */
+rocketchat_channel: string | null, rocketchat_avatar_url: string | null, zulip_enabled: boolean, zulip_site_url: string | null, zulip_bot_email: string | null, zulip_api_key: string | null, zulip_stream: string | null, 
+/**
+ * Topic within `zulip_stream`; defaults to the project name
+ */
+zulip_topic: string | null, irc_enabled: boolean, 
+/**
+ * Host, optionally with `:port`; the port defaults to 6697 with TLS and 6667 without
+ */
+irc_server: string | null, 
+/**
+ * Connect over TLS; on unless set to false
+ */
+irc_tls: boolean | null, irc_nick: string | null, irc_channel: string | null, 
+/**
+ * SASL PLAIN credentials, for networks that only accept registered nicks
+ */
+irc_sasl_username: string | null, irc_sasl_password: string | null, 
+/**
+ * Per-channel event toggles; events missing from a channel's map use their default
+ */
+event_toggles: { [key in NotificationChannel]?: { [key in NotificationEvent]?: boolean } }, 
+/**
+ * Per-channel minimum severity, applied on top of the event toggles; channels missing
+ * from the map get every severity
+ */
+min_severity: { [key in NotificationChannel]?: NotificationSeverity }, 
+/**
+ * Project-scoped settings layered over the global ones when notifying about that project
+ */
+project_overrides: { [key in string]?: NotificationOverrides }, 
+/**
+ * Custom title/message per event, rendered with `{{variable}}` substitution
+ */
+templates: { [key in NotificationEvent]?: NotificationTemplate }, quiet_hours: QuietHours, retry: NotificationRetryPolicy, digest: NotificationDigest, dedup: NotificationDedup, 
+/**
+ * Checked in order before the per-channel event toggles; the first matching rule decides
+ * which channels fire
+ */
+rules: Array<NotificationRule>, escalation: EscalationPolicy, summary: NotificationSummary, };
+
+/**
+ * Delivery channels that can be toggled per event
+ */
+export enum NotificationChannel { SOUND = "SOUND", PUSH = "PUSH", SLACK = "SLACK", WEBHOOK = "WEBHOOK", NTFY = "NTFY", PUSHOVER = "PUSHOVER", WEB_PUSH = "WEB_PUSH", GOTIFY = "GOTIFY", MATRIX = "MATRIX", MATTERMOST = "MATTERMOST", ROCKET_CHAT = "ROCKET_CHAT", ZULIP = "ZULIP", IRC = "IRC" }
 
-export type CheckEditorAvailabilityQuery = { editor_type: EditorType, };
+/**
+ * Kinds of events that can trigger a notification
+ */
+export enum NotificationEvent { TASK_STARTED = "TASK_STARTED", ATTEMPT_COMPLETED = "ATTEMPT_COMPLETED", ATTEMPT_FAILED = "ATTEMPT_FAILED", APPROVAL_NEEDED = "APPROVAL_NEEDED", PR_OPENED = "PR_OPENED", PR_MERGED = "PR_MERGED", MERGE_CONFLICT = "MERGE_CONFLICT", SUMMARY = "SUMMARY", TASK_UNBLOCKED = "TASK_UNBLOCKED", TASK_OVERDUE = "TASK_OVERDUE", WIP_LIMIT_EXCEEDED = "WIP_LIMIT_EXCEEDED", EPIC_COMPLETED = "EPIC_COMPLETED", BUDGET_LIMIT_REACHED = "BUDGET_LIMIT_REACHED", ATTEMPT_STALLED = "ATTEMPT_STALLED" }
 
-export type CheckEditorAvailabilityResponse = { available: boolean, };
+/**
+ * Per-project notification settings; unset fields inherit the global value
+ */
+export type NotificationOverrides = { sound_enabled: boolean | null, push_enabled: boolean | null, sound_file: SoundFile | null, custom_sound: string | null, sound_playback: SoundPlayback | null, sound_theme: SoundTheme | null, slack_enabled: boolean | null, slack_webhook_url: string | null, slack_bot_token: string | null, slack_channel: string | null, slack_on_call: string | null, webhook_enabled: boolean | null, webhook_urls: Array<string> | null, webhook_secret: string | null, ntfy_enabled: boolean | null, ntfy_topic_url: string | null, ntfy_token: string | null, ntfy_priority: number | null, pushover_enabled: boolean | null, pushover_app_token: string | null, pushover_user_key: string | null, pushover_priority: number | null, web_push_enabled: boolean | null, gotify_enabled: boolean | null, gotify_server_url: string | null, gotify_app_token: string | null, matrix_enabled: boolean | null, matrix_homeserver_url: string | null, matrix_access_token: string | null, matrix_room_id: string | null, mattermost_enabled: boolean | null, mattermost_webhook_url: string | null, mattermost_channel: string | null, mattermost_icon_url: string | null, rocketchat_enabled: boolean | null, rocketchat_webhook_url: string | null, rocketchat_channel: string | null, rocketchat_avatar_url: string | null, zulip_enabled: boolean | null, zulip_site_url: string | null, zulip_bot_email: string | null, zulip_api_key: string | null, zulip_stream: string | null, zulip_topic: string | null, irc_enabled: boolean | null, irc_server: string | null, irc_tls: boolean | null, irc_nick: string | null, irc_channel: string | null, irc_sasl_username: string | null, irc_sasl_password: string | null, 
+/**
+ * Merged into the global toggles event by event
+ */
+event_toggles: { [key in NotificationChannel]?: { [key in NotificationEvent]?: boolean } }, 
+/**
+ * Merged into the global minimum severities channel by channel
+ */
+min_severity: { [key in NotificationChannel]?: NotificationSeverity }, };
 
-export type CheckAgentAvailabilityQuery = { executor: BaseCodingAgent, };
+/**
+ * Title and message templates for one event; unset parts keep the built-in text.
+ *
+ * Available variables: `event`, `title`, `message`, `url`, `project.id`,
+ * `project.name`, `task.id`, `task.title`, `task.labels`, `task.assignees` and
+ * `attempt.duration`.
+ */
+export type NotificationTemplate = { title: string | null, message: string | null, };
 
-export type CurrentUserResponse = { user_id: string, };
+/**
+ * Coalesces bursts of notifications into one summary per channel
+ */
+export type NotificationDigest = { 
+/**
+ * How long to collect notifications after the first one arrives
+ */
+window_secs: number, 
+/**
+ * Channels that batch their notifications; all others deliver immediately
+ */
+channels: Array<NotificationChannel>, };
 
-export type CreateFollowUpAttempt = { prompt: string, variant: string | null, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, };
+/**
+ * Suppresses repeats of the same event for the same task on a channel, e.g. when an executor
+ * keeps failing
+ */
+export type NotificationDedup = { 
+/**
+ * How long repeats are suppressed after a notification goes out; 0 disables deduplication
+ */
+window_secs: number, 
+/**
+ * `Drop` discards repeats; `Count` sends one follow-up with the number of repeats once
+ * the window closes
+ */
+mode: NotificationDedupMode, };
 
-export type ChangeTargetBranchRequest = { repo_id: string, new_target_branch: string, };
+export enum NotificationDedupMode { DROP = "DROP", COUNT = "COUNT" }
 
-export type ChangeTargetBranchResponse = { repo_id: string, new_target_branch: string, status: [number, number], };
+/**
+ * Routes notifications matching every condition to a fixed set of channels; empty conditions
+ * match anything
+ */
+export type NotificationRule = { name: string, events: Array<NotificationEvent>, project_ids: Array<string>, 
+/**
+ * Matches tasks carrying any of these labels
+ */
+labels: Array<string>, 
+/**
+ * Executor names such as `CLAUDE_CODE`
+ */
+executors: Array<string>, 
+/**
+ * Matches tasks assigned to any of these people or agents
+ */
+assignees: Array<string>, outcome: NotificationOutcome | null, 
+/**
+ * Channels to deliver through, regardless of their event toggles; each must still be
+ * enabled and configured
+ */
+channels: Array<NotificationChannel>, 
+/**
+ * Urgency for channels that support it (ntfy, Pushover and Gotify)
+ */
+priority: NotificationPriority | null, 
+/**
+ * Slack channel ID to post to instead of `slack_channel`, when posting with a bot token
+ */
+slack_channel: string | null, };
 
-export type MergeTaskAttemptRequest = { repo_id: string, };
+/**
+ * Result of the work an event reports on
+ */
+export enum NotificationOutcome { SUCCESS = "SUCCESS", FAILURE = "FAILURE" }
 
-export type PushTaskAttemptRequest = { repo_id: string, };
+/**
+ * How serious an event is: `Info` for progress and successes, `Warning` for events waiting on
+ * someone, `Failure` for errors and merge conflicts
+ */
+export enum NotificationSeverity { INFO = "INFO", WARNING = "WARNING", FAILURE = "FAILURE" }
 
-export type RenameBranchRequest = { new_branch_name: string, };
+export enum NotificationPriority { LOW = "LOW", NORMAL = "NORMAL", HIGH = "HIGH", URGENT = "URGENT" }
 
-export type RenameBranchResponse = { branch: string, };
+/**
+ * Re-sends failures that nobody acknowledged in time through other channels
+ */
+export type EscalationPolicy = { enabled: boolean, 
+/**
+ * How long to wait for an acknowledgment before escalating
+ */
+after_minutes: number, 
+/**
+ * Channels for the escalated notification; each must be enabled and configured
+ */
+channels: Array<NotificationChannel>, };
 
-export type OpenEditorRequest = { editor_type: string | null, file_path: string | null, };
+/**
+ * Scheduled roll-up of task activity, sent through every enabled channel
+ */
+export type NotificationSummary = { enabled: boolean, frequency: SummaryFrequency, 
+/**
+ * Send time as `HH:MM`
+ */
+time: string, 
+/**
+ * Day the weekly summary goes out; ignored for daily summaries
+ */
+weekday: DayOfWeek, 
+/**
+ * Fixed UTC offset such as `+02:00`; the system's local time is used when unset
+ */
+utc_offset: string | null, };
 
-export type OpenEditorResponse = { url: string | null, };
+export enum SummaryFrequency { DAILY = "DAILY", WEEKLY = "WEEKLY" }
 
-export type AssignSharedTaskRequest = { new_assignee_user_id: string | null, };
+/**
+ * How loud and how long notification sounds play
+ */
+export type SoundPlayback = { 
+/**
+ * Percentage of full volume, from 0 to 100
+ */
+volume: number, 
+/**
+ * Cut sounds off after this long; unset plays them to the end
+ */
+max_duration_ms: number | null, };
 
-export type ShareTaskResponse = { shared_task_id: string, };
+/**
+ * Bundled sound packs; `Classic` plays `custom_sound` or `sound_file` for every event
+ */
+export enum SoundTheme { CLASSIC = "CLASSIC", ABSTRACT = "ABSTRACT", FARMYARD = "FARMYARD" }
 
-export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
+/**
+ * Sound for a single event; `custom_sound` wins over `sound_file` like the global settings
+ */
+export type EventSound = { sound_file: SoundFile | null, 
+/**
+ * An uploaded sound's name or an absolute path
+ */
+custom_sound: string | null, };
 
-export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
+/**
+ * Retry policy for channels delivered over HTTP
+ */
+export type NotificationRetryPolicy = { 
+/**
+ * Total attempts, including the first one
+ */
+max_attempts: number, initial_delay_ms: number, max_delay_ms: number, 
+/**
+ * Randomize delays so many failing sends don't retry in lockstep
+ */
+jitter: boolean, };
 
-export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
+/**
+ * Do-not-disturb window during which sound and push-style notifications (desktop, ntfy,
+ * Pushover, Gotify and web push) are suppressed
+ */
+export type QuietHours = { enabled: boolean, 
+/**
+ * Start of the window as `HH:MM`
+ */
+start: string, 
+/**
+ * End of the window as `HH:MM`; earlier than `start` means the window spans midnight
+ */
+end: string, 
+/**
+ * IANA timezone such as `Europe/Berlin`; the system's local time is used when unset
+ */
+timezone: string | null, 
+/**
+ * Days on which the window starts; every day when empty
+ */
+days: Array<DayOfWeek>, };
 
-export type ImageMetadata = { exists: boolean, file_name: string | null, path: string | null, size_bytes: bigint | null, format: string | null, proxy_url: string | null, };
+export enum DayOfWeek { MONDAY = "MONDAY", TUESDAY = "TUESDAY", WEDNESDAY = "WEDNESDAY", THURSDAY = "THURSDAY", FRIDAY = "FRIDAY", SATURDAY = "SATURDAY", SUNDAY = "SUNDAY" }
 
-export type CreateTaskAttemptBody = { task_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
+export enum ThemeMode { LIGHT = "LIGHT", DARK = "DARK", SYSTEM = "SYSTEM" }
 
-export type WorkspaceRepoInput = { repo_id: string, target_branch: string, };
+export type EditorConfig = { editor_type: EditorType, custom_command: string | null, remote_ssh_host: string | null, remote_ssh_user: string | null, };
 
-export type RunAgentSetupRequest = { executor_profile_id: ExecutorProfileId, };
+export enum EditorType { VS_CODE = "VS_CODE", CURSOR = "CURSOR", WINDSURF = "WINDSURF", INTELLI_J = "INTELLI_J", ZED = "ZED", XCODE = "XCODE", CUSTOM = "CUSTOM" }
 
-export type RunAgentSetupResponse = Record<string, never>;
+export type EditorOpenError = { "type": "executable_not_found", executable: string, editor_type: EditorType, } | { "type": "invalid_command", details: string, editor_type: EditorType, } | { "type": "launch_failed", executable: string, details: string, editor_type: EditorType, };
 
-export type GhCliSetupError = "BREW_MISSING" | "SETUP_HELPER_NOT_SUPPORTED" | { "OTHER": { message: string, } };
+export type GitHubConfig = { pat: string | null, oauth_token: string | null, username: string | null, primary_email: string | null, default_pr_base: string | null, };
 
-export type RebaseTaskAttemptRequest = { repo_id: string, old_base_branch: string | null, new_base_branch: string | null, };
+export enum SoundFile { ABSTRACT_SOUND1 = "ABSTRACT_SOUND1", ABSTRACT_SOUND2 = "ABSTRACT_SOUND2", ABSTRACT_SOUND3 = "ABSTRACT_SOUND3", ABSTRACT_SOUND4 = "ABSTRACT_SOUND4", COW_MOOING = "COW_MOOING", PHONE_VIBRATION = "PHONE_VIBRATION", ROOSTER = "ROOSTER" }
 
-export type AbortConflictsRequest = { repo_id: string, };
+export type UiLanguage = "BROWSER" | "EN" | "JA" | "ES" | "KO" | "ZH_HANS";
 
-export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, } | { "type": "rebase_in_progress" };
+export type ShowcaseState = { seen_features: Array<string>, };
 
-export type PushError = { "type": "force_push_required" };
+export type ServerConfig = { 
+/**
+ * URL the app is reached at from outside, e.g. behind a reverse proxy or tunnel.
+ * Used for links in notifications ahead of `SERVER_PUBLIC_BASE_URL` and port discovery.
+ */
+public_base_url: string | null, };
 
-export type CreatePrError = { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, };
+/**
+ * Named set of executor, notification and editor settings that can be switched to at once
+ */
+export type ConfigProfile = { executor_profile: ExecutorProfileId, notifications: NotificationConfig, editor: EditorConfig, };
 
-export type BranchStatus = { commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
 /**
- * True if a `git rebase` is currently in progress in this worktree
+ * Settings a project uses in place of the global ones; unset fields inherit the global value.
+ * Notification overrides live in `notifications.project_overrides`.
  */
-is_rebase_in_progress: boolean, 
+export type ProjectConfigOverrides = { executor_profile: ExecutorProfileId | null, git_branch_prefix: string | null, 
 /**
- * Current conflict operation if any
+ * 0 turns auto-archiving off for the project
  */
-conflict_op: ConflictOp | null, 
+auto_archive_after_days: number | null, 
 /**
- * List of files currently in conflicted (unmerged) state
+ * Most attempts running at once in the project; 0 lifts the per-project limit
  */
-conflicted_files: Array<string>, };
-
-export type RunScriptError = { "type": "no_script_configured" } | { "type": "process_already_running" };
-
-export type AttachPrResponse = { pr_attached: boolean, pr_url: string | null, pr_number: bigint | null, pr_status: MergeStatus | null, };
-
-export type AttachExistingPrRequest = { repo_id: string, };
-
-export type PrCommentsResponse = { comments: Array<UnifiedPrComment>, };
+max_concurrent_attempts: number | null, };
 
-export type GetPrCommentsError = { "type": "no_pr_attached" } | { "type": "github_cli_not_installed" } | { "type": "github_cli_not_logged_in" };
+/**
+ * Everything a project overrides, edited together from the project's settings
+ */
+export type ProjectSettings = { overrides: ProjectConfigOverrides, notifications: NotificationOverrides, };
 
-export type GetPrCommentsQuery = { repo_id: string, };
+/**
+ * What happens to a to-do task once every task blocking it is done. `Start` also notifies, and
+ * starts an attempt on the branches the last blocker's attempt targeted.
+ */
+export enum TaskUnblockedAction { NOTHING = "NOTHING", NOTIFY = "NOTIFY", START = "START" }
 
-export type UnifiedPrComment = { "comment_type": "general", id: string, author: string, author_association: string, body: string, created_at: string, url: string, } | { "comment_type": "review", id: bigint, author: string, author_association: string, body: string, created_at: string, url: string, path: string, line: bigint | null, diff_hunk: string, };
+/**
+ * Where task attachments are kept and how large they may be
+ */
+export type AttachmentConfig = { max_size_mb: bigint, 
+/**
+ * Store new attachments in an S3-compatible bucket instead of the app data directory.
+ * Attachments already stored keep their original location.
+ */
+s3: S3StorageConfig | null, };
 
-export type RepoBranchStatus = { repo_id: string, repo_name: string, commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
+export type S3StorageConfig = { 
 /**
- * True if a `git rebase` is currently in progress in this worktree
+ * e.g. `https://s3.us-east-1.amazonaws.com`, or a MinIO/R2 endpoint
  */
-is_rebase_in_progress: boolean, 
+endpoint: string, 
 /**
- * Current conflict operation if any
+ * Defaults to `auto`
  */
-conflict_op: ConflictOp | null, 
+region: string | null, bucket: string, 
 /**
- * List of files currently in conflicted (unmerged) state
+ * Key prefix objects are stored under, e.g. `vibe-kanban/`
  */
-conflicted_files: Array<string>, };
+prefix: string | null, access_key_id: string, secret_access_key: string, };
 
-export type DirectoryEntry = { name: string, path: string, is_directory: boolean, is_git_repo: boolean, last_modified: bigint | null, };
+/**
+ * Jira Cloud site and the account tasks are imported and transitioned as
+ */
+export type JiraConfig = { 
+/**
+ * e.g. `https://your-team.atlassian.net`
+ */
+base_url: string, email: string, 
+/**
+ * API token created at id.atlassian.com for `email`
+ */
+api_token: string, };
 
-export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
+/**
+ * Linear workspace access; the team each project syncs with is set per project
+ */
+export type LinearConfig = { 
+/**
+ * Personal API key from Linear's security settings
+ */
+api_key: string, 
+/**
+ * Signing secret of the webhook pointed at `/api/linear/webhook`; webhooks are rejected
+ * while unset
+ */
+webhook_secret: string | null, };
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, };
+/**
+ * Retries of coding agent runs that fail with a transient error: a rate limit, a network
+ * error or a timed-out tool
+ */
+export type AttemptRetryPolicy = { 
+/**
+ * Retries after the first failure; 0 turns automatic retries off
+ */
+max_retries: number, 
+/**
+ * Wait before the first retry, doubling with each retry after it
+ */
+initial_delay_secs: number, max_delay_secs: number, };
 
-export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, slack_enabled: boolean, slack_webhook_url: string | null, };
+/**
+ * What happens once a coding agent has gone quiet. `Warn` logs it and notifies; `Continue`
+ * stops the run and sends the agent a follow-up asking it to carry on.
+ */
+export enum StallAction { WARN = "WARN", CONTINUE = "CONTINUE", KILL = "KILL" }
 
-export enum ThemeMode { LIGHT = "LIGHT", DARK = "DARK", SYSTEM = "SYSTEM" }
+/**
+ * Limits on how long one executor profile's coding agent may run
+ */
+export type ExecutionLimits = { 
+/**
+ * The run is killed once it has gone on this long, not counting time paused
+ */
+max_duration_minutes: number | null, 
+/**
+ * Minutes without new output before the run counts as stalled
+ */
+stall_after_minutes: number | null, on_stall: StallAction, };
 
-export type EditorConfig = { editor_type: EditorType, custom_command: string | null, remote_ssh_host: string | null, remote_ssh_user: string | null, };
+export enum SandboxRuntime { DOCKER = "DOCKER", PODMAN = "PODMAN" }
 
-export enum EditorType { VS_CODE = "VS_CODE", CURSOR = "CURSOR", WINDSURF = "WINDSURF", INTELLI_J = "INTELLI_J", ZED = "ZED", XCODE = "XCODE", CUSTOM = "CUSTOM" }
+/**
+ * `Full` leaves the container on the runtime's default network. `None` cuts it off entirely,
+ * the agent's own API included, for agents whose model runs in the image.
+ */
+export enum SandboxNetwork { FULL = "FULL", NONE = "NONE" }
 
-export type EditorOpenError = { "type": "executable_not_found", executable: string, editor_type: EditorType, } | { "type": "invalid_command", details: string, editor_type: EditorType, } | { "type": "launch_failed", executable: string, details: string, editor_type: EditorType, };
+/**
+ * Running coding agents in a container; setup scripts and dev servers stay on the host
+ */
+export type SandboxConfig = { enabled: boolean, runtime: SandboxRuntime, 
+/**
+ * Image with the coding agent CLIs installed
+ */
+image: string, 
+/**
+ * Host environment variables passed into the container, e.g. `ANTHROPIC_API_KEY`
+ */
+env_passthrough: Array<string>, 
+/**
+ * CPUs the container may use, e.g. 1.5; unlimited when unset
+ */
+cpus: number | null, 
+/**
+ * Unlimited when unset
+ */
+memory_mb: number | null, network: SandboxNetwork, };
 
-export type GitHubConfig = { pat: string | null, oauth_token: string | null, username: string | null, primary_email: string | null, default_pr_base: string | null, };
+/**
+ * Limits for each execution process on the host; sandboxed agents get the sandbox's instead
+ */
+export type ProcessLimits = { 
+/**
+ * CPU time the process and its children may use, in percent of one core; 150 is one and a
+ * half cores
+ */
+cpu_percent: number | null, memory_mb: number | null, 
+/**
+ * Most processes running at once; on Linux threads count too
+ */
+max_processes: number | null, 
+/**
+ * Niceness from 0 to 19, higher running at lower priority. On Windows, up to 14 runs below
+ * normal priority and 15 or more at idle priority.
+ */
+nice: number | null, };
 
-export enum SoundFile { ABSTRACT_SOUND1 = "ABSTRACT_SOUND1", ABSTRACT_SOUND2 = "ABSTRACT_SOUND2", ABSTRACT_SOUND3 = "ABSTRACT_SOUND3", ABSTRACT_SOUND4 = "ABSTRACT_SOUND4", COW_MOOING = "COW_MOOING", PHONE_VIBRATION = "PHONE_VIBRATION", ROOSTER = "ROOSTER" }
+/**
+ * Shell commands an agent must get approved before running them, even when it otherwise runs
+ * without asking. Only Claude Code can be gated this way; agents in their own approvals mode
+ * already ask about every command.
+ */
+export type ApprovalGates = { 
+/**
+ * Regexes searched for in the command line, e.g. `rm\s+-rf` or `git\s+push`
+ */
+command_patterns: Array<string>, };
 
-export type UiLanguage = "BROWSER" | "EN" | "JA" | "ES" | "KO" | "ZH_HANS";
+/**
+ * A problem with one config key, with enough detail to fix it
+ */
+export type ConfigIssue = { 
+/**
+ * Dotted path to the key, e.g. `notifications.sound_file`
+ */
+path: string, message: string, 
+/**
+ * What the key accepts, when known
+ */
+expected: string | null, suggestion: string | null, };
 
-export type ShowcaseState = { seen_features: Array<string>, };
+/**
+ * A key whose value differs between two configs; credentials are shown redacted
+ */
+export type ConfigChange = { 
+/**
+ * Dotted path to the key, e.g. `notifications.sound_enabled`
+ */
+path: string, old: JsonValue | null, new: JsonValue | null, };
 
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
@@ -363,9 +2411,9 @@ working_dir: string | null, };
 
 export type ScriptRequestLanguage = "Bash";
 
-export enum BaseCodingAgent { CLAUDE_CODE = "CLAUDE_CODE", AMP = "AMP", GEMINI = "GEMINI", CODEX = "CODEX", OPENCODE = "OPENCODE", CURSOR_AGENT = "CURSOR_AGENT", QWEN_CODE = "QWEN_CODE", COPILOT = "COPILOT", DROID = "DROID" }
+export enum BaseCodingAgent { CLAUDE_CODE = "CLAUDE_CODE", AMP = "AMP", GEMINI = "GEMINI", CODEX = "CODEX", OPENCODE = "OPENCODE", CURSOR_AGENT = "CURSOR_AGENT", QWEN_CODE = "QWEN_CODE", COPILOT = "COPILOT", DROID = "DROID", AIDER = "AIDER", OPENHANDS = "OPENHANDS", GOOSE = "GOOSE", CUSTOM_COMMAND = "CUSTOM_COMMAND" }
 
-export type CodingAgent = { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid };
+export type CodingAgent = { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid } | { "AIDER": Aider } | { "OPENHANDS": Openhands } | { "GOOSE": Goose } | { "CUSTOM_COMMAND": CustomCommand };
 
 export type AvailabilityInfo = { "type": "LOGIN_DETECTED", last_auth_timestamp: bigint, } | { "type": "INSTALLATION_FOUND" } | { "type": "NOT_FOUND" };
 
@@ -387,9 +2435,13 @@ executor: BaseCodingAgent,
 /**
  * Optional variant name (e.g., "PLAN", "ROUTER")
  */
-variant: string | null, };
+variant: string | null, 
+/**
+ * Model to run instead of the variant's, for one task attempt
+ */
+model_override?: string, };
 
-export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid } };
+export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "DROID": Droid } | { "AIDER": Aider } | { "OPENHANDS": Openhands } | { "GOOSE": Goose } | { "CUSTOM_COMMAND": CustomCommand } };
 
 export type ExecutorConfigs = { executors: { [key in BaseCodingAgent]?: ExecutorConfig }, };
 
@@ -431,6 +2483,64 @@ export type Autonomy = "normal" | "low" | "medium" | "high" | "skip-permissions-
 
 export type DroidReasoningEffort = "none" | "dynamic" | "off" | "low" | "medium" | "high";
 
+export type Aider = { append_prompt: AppendPrompt, model?: string | null, edit_format?: AiderEditFormat | null, auto_commits?: boolean | null, local_llm?: LocalLlm | null, openrouter?: OpenRouter | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+
+export type AiderEditFormat = "diff" | "diff-fenced" | "udiff" | "whole";
+
+export type Openhands = { append_prompt: AppendPrompt, model?: string | null, base_url?: string | null, confirmation_mode: OpenhandsConfirmationMode, local_llm?: LocalLlm | null, openrouter?: OpenRouter | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+
+export type OpenhandsConfirmationMode = "always-approve" | "llm-approve";
+
+export type Goose = { append_prompt: AppendPrompt, provider?: string | null, model?: string | null, mode: GooseMode, builtins?: Array<string> | null, extensions?: Array<string> | null, http_extensions?: Array<string> | null, max_turns?: number | null, local_llm?: LocalLlm | null, openrouter?: OpenRouter | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+
+export type GooseMode = "auto" | "smart_approve" | "approve" | "chat";
+
+export type CustomCommand = { append_prompt: AppendPrompt, command: string, follow_up_command?: string | null, model?: string | null, prompt_on_stdin: boolean, output_format: CustomCommandOutputFormat, output_rules: Array<CustomCommandOutputRule>, success_pattern?: string | null, failure_pattern?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+
+export type CustomCommandOutputFormat = "text" | "jsonl";
+
+export type CustomCommandOutputKind = "assistant" | "thinking" | "system" | "error" | "command" | "file_read" | "file_edit" | "session_id" | "ignore";
+
+export type CustomCommandOutputRule = { pattern: string, field?: string | null, kind: CustomCommandOutputKind, content?: string | null, };
+
+export type LocalLlmProvider = "ollama" | "openai_compatible";
+
+export type LocalLlm = { provider: LocalLlmProvider, base_url?: string | null, model: string, context_window?: number | null, };
+
+export type LocalLlmTestResult = { reachable: boolean, model_available: boolean, 
+/**
+ * Time taken to list the server's models
+ */
+endpoint_latency_ms: number | null, 
+/**
+ * Time taken to answer a one-token completion, including loading the model
+ */
+response_latency_ms: number | null, warnings: Array<string>, error: string | null, };
+
+export type ProfileTestResult = { availability: AvailabilityInfo, 
+/**
+ * The model the profile names, if any
+ */
+model: string | null, 
+/**
+ * `None` when the run did not get far enough to tell
+ */
+auth_valid: boolean | null, model_available: boolean | null, 
+/**
+ * Whether the agent could read a file with its tools
+ */
+tool_access: boolean | null, 
+/**
+ * Time from starting the agent to its first reply
+ */
+first_response_ms: number | null, 
+/**
+ * Time the whole task took
+ */
+duration_ms: number | null, warnings: Array<string>, error: string | null, };
+
+export type OpenRouter = { model: string, };
+
 export type AppendPrompt = string | null;
 
 export type CodingAgentInitialRequest = { prompt: string, 